@@ -1,8 +1,29 @@
 use crate::util::{
+    constant::TAB_SIZE,
+    extension::Extension,
     line_ending::LineEnding,
     mdx::{EsmParse as MdxEsmParse, ExpressionParse as MdxExpressionParse},
 };
-use alloc::{boxed::Box, fmt, string::String};
+use alloc::{boxed::Box, fmt, string::String, vec, vec::Vec};
+
+/// Signature of a function that turns the target and anchor of a wiki link
+/// into a URL.
+///
+/// Used in [`wiki_link_url`][CompileOptions::wiki_link_url].
+pub type WikiLinkUrl = dyn Fn(&str, Option<&str>) -> String + Send + Sync;
+
+/// Signature of a function that turns the shortcode of an emoji (such as
+/// `tada`) into an emoji (such as `🎉`), or `None` if it is not known.
+///
+/// Used in [`emoji_lookup`][ParseOptions::emoji_lookup].
+pub type EmojiLookup = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Signature of a function that decides whether a GFM autolink literal
+/// (such as `www.example.com`, `https://example.com`, or `a@example.com`) is
+/// a link, given its source.
+///
+/// Used in [`gfm_autolink_literal_filter`][ParseOptions::gfm_autolink_literal_filter].
+pub type GfmAutolinkLiteralFilter = dyn Fn(&str) -> bool + Send + Sync;
 
 /// Control which constructs are enabled.
 ///
@@ -27,11 +48,26 @@ use alloc::{boxed::Box, fmt, string::String};
 ///   math_text: true,
 ///   ..Constructs::gfm()
 /// };
+///
+/// // Which can also be done with `with_*` and `without_*` methods:
+/// let custom = Constructs::gfm().without_autolink().with_math(true);
 /// # }
 /// ```
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Constructs {
+    /// Abbreviation definition.
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hypertext Markup Language
+    ///     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// Occurrences of the label in text turn into abbreviations.
+    ///
+    /// This is not part of `CommonMark` or GFM, but PHP Markdown Extra and
+    /// several other parsers support it.
+    pub abbreviation: bool,
     /// Attention.
     ///
     /// ```markdown
@@ -39,6 +75,18 @@ pub struct Constructs {
     ///       ^^^   ^^^^^
     /// ```
     pub attention: bool,
+    /// Attributes.
+    ///
+    /// ```markdown
+    /// > | # a {#b .c}
+    ///         ^^^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but Pandoc and PHP Markdown
+    /// Extra support it.
+    /// Attributes can end headings, follow the info of fenced code, and
+    /// directly follow code (text).
+    pub attributes: bool,
     /// Autolink.
     ///
     /// ```markdown
@@ -67,6 +115,13 @@ pub struct Constructs {
     ///       ^^^^^
     /// ```
     pub character_reference: bool,
+    /// Cite.
+    ///
+    /// ```markdown
+    /// > | a [@b] c
+    ///       ^^^^
+    /// ```
+    pub cite: bool,
     /// Code (indented).
     ///
     /// ```markdown
@@ -92,6 +147,16 @@ pub struct Constructs {
     ///       ^^^
     /// ```
     pub code_text: bool,
+    /// `CriticMarkup`.
+    ///
+    /// ```markdown
+    /// > | a {++b++} {--c--} {~~d~>e~~} {==f==} {>>g<<}
+    ///       ^^^^^^^ ^^^^^^^ ^^^^^^^^^^ ^^^^^^^ ^^^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but it is used to track
+    /// changes and comments when reviewing documents.
+    pub critic_markup: bool,
     /// Definition.
     ///
     /// ```markdown
@@ -99,6 +164,28 @@ pub struct Constructs {
     ///     ^^^^^^^^^^
     /// ```
     pub definition: bool,
+    /// Definition list.
+    ///
+    /// ```markdown
+    /// > | a
+    ///     ^
+    /// > | : b
+    ///     ^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but PHP Markdown Extra and
+    /// Pandoc support it.
+    pub definition_list: bool,
+    /// Emoji.
+    ///
+    /// ```markdown
+    /// > | a :tada: b
+    ///       ^^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but GitHub and many chat
+    /// and forum platforms support it.
+    pub emoji: bool,
     /// Frontmatter.
     ///
     /// ````markdown
@@ -110,6 +197,16 @@ pub struct Constructs {
     ///     ^^^
     /// ````
     pub frontmatter: bool,
+    /// GFM: alert.
+    ///
+    /// ```markdown
+    /// > | > [!NOTE]
+    ///       ^^^^^^^
+    ///   | > a
+    /// ```
+    ///
+    /// This is not part of the GFM spec, but GitHub supports it.
+    pub gfm_alert: bool,
     /// GFM: autolink literal.
     ///
     /// ```markdown
@@ -148,6 +245,17 @@ pub struct Constructs {
     ///     ^^^^^
     /// ```
     pub gfm_table: bool,
+    /// GFM: table caption (not part of GFM).
+    ///
+    /// Only used if `gfm_table` is on too.
+    ///
+    /// ```markdown
+    ///   | | a |
+    ///   | | - |
+    /// > | Table: b
+    ///     ^^^^^^^^
+    /// ```
+    pub gfm_table_caption: bool,
     /// GFM: task list item.
     ///
     /// ```markdown
@@ -155,6 +263,23 @@ pub struct Constructs {
     ///       ^^^
     /// ```
     pub gfm_task_list_item: bool,
+    /// Grid table.
+    ///
+    /// ```markdown
+    /// > | +---+
+    ///     ^^^^^
+    /// > | | a |
+    ///     ^^^^^
+    /// > | +===+
+    ///     ^^^^^
+    /// > | | b |
+    ///     ^^^^^
+    /// > | +---+
+    ///     ^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but Pandoc supports it.
+    pub grid_table: bool,
     /// Hard break (escape).
     ///
     /// ```markdown
@@ -187,6 +312,16 @@ pub struct Constructs {
     ///     ^^
     /// ```
     pub heading_setext: bool,
+    /// Highlight.
+    ///
+    /// ```markdown
+    /// > | a ==b== c.
+    ///       ^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but Obsidian and several
+    /// wikis support it.
+    pub highlight: bool,
     /// HTML (flow).
     ///
     /// ```markdown
@@ -201,6 +336,16 @@ pub struct Constructs {
     ///       ^^^
     /// ```
     pub html_text: bool,
+    /// Insert.
+    ///
+    /// ```markdown
+    /// > | a ++b++ c.
+    ///       ^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but several markdown parsers
+    /// support it, as a complement to GFM strikethrough.
+    pub insert: bool,
     /// Label start (image).
     ///
     /// ```markdown
@@ -208,6 +353,16 @@ pub struct Constructs {
     ///       ^^
     /// ```
     pub label_start_image: bool,
+    /// Label start (inline footnote).
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but Pandoc and several other
+    /// markdown parsers support it.
+    pub label_start_inline_footnote: bool,
     /// Label start (link).
     ///
     /// ```markdown
@@ -320,6 +475,23 @@ pub struct Constructs {
     /// > Otherwise, expressions are parsed with a basic algorithm that only
     /// > cares about braces.
     pub mdx_jsx_text: bool,
+    /// Obsidian: block identifier.
+    ///
+    /// ```markdown
+    /// > | a ^b
+    ///       ^^
+    /// ```
+    pub obsidian_block_id: bool,
+    /// Smart punctuation.
+    ///
+    /// ```markdown
+    /// > | "a" -- b...
+    ///     ^ ^ ^^  ^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but many markdown parsers
+    /// (such as `pulldown-cmark` and Pandoc) support it.
+    pub smart_punctuation: bool,
     /// Thematic break.
     ///
     /// ```markdown
@@ -327,6 +499,16 @@ pub struct Constructs {
     ///     ^^^
     /// ```
     pub thematic_break: bool,
+    /// Wiki link.
+    ///
+    /// ```markdown
+    /// > | a [[b|c]] d
+    ///       ^^^^^^^
+    /// ```
+    ///
+    /// This is not part of `CommonMark` or GFM, but note-taking apps, such as
+    /// Obsidian, support it.
+    pub wiki_link: bool,
 }
 
 impl Default for Constructs {
@@ -340,29 +522,41 @@ impl Default for Constructs {
     /// <https://spec.commonmark.org>.
     fn default() -> Self {
         Self {
+            abbreviation: false,
             attention: true,
+            attributes: false,
             autolink: true,
             block_quote: true,
             character_escape: true,
             character_reference: true,
+            cite: false,
             code_indented: true,
             code_fenced: true,
             code_text: true,
+            critic_markup: false,
             definition: true,
+            definition_list: false,
+            emoji: false,
             frontmatter: false,
+            gfm_alert: false,
             gfm_autolink_literal: false,
             gfm_label_start_footnote: false,
             gfm_footnote_definition: false,
             gfm_strikethrough: false,
             gfm_table: false,
+            gfm_table_caption: false,
             gfm_task_list_item: false,
+            grid_table: false,
             hard_break_escape: true,
             hard_break_trailing: true,
             heading_atx: true,
             heading_setext: true,
+            highlight: false,
             html_flow: true,
             html_text: true,
+            insert: false,
             label_start_image: true,
+            label_start_inline_footnote: false,
             label_start_link: true,
             label_end: true,
             list_item: true,
@@ -373,12 +567,25 @@ impl Default for Constructs {
             mdx_expression_text: false,
             mdx_jsx_flow: false,
             mdx_jsx_text: false,
+            obsidian_block_id: false,
+            smart_punctuation: false,
             thematic_break: true,
+            wiki_link: false,
         }
     }
 }
 
 impl Constructs {
+    /// `CommonMark`.
+    ///
+    /// This is the same as [`Constructs::default()`][Constructs::default].
+    ///
+    /// For more information, see the `CommonMark` specification:
+    /// <https://spec.commonmark.org>.
+    pub fn commonmark() -> Self {
+        Self::default()
+    }
+
     /// GFM.
     ///
     /// GFM stands for **GitHub flavored markdown**.
@@ -432,6 +639,206 @@ impl Constructs {
             ..Self::default()
         }
     }
+
+    /// Pedantic.
+    ///
+    /// This turns on `CommonMark`, and turns off constructs that were not
+    /// in the original `Markdown.pl` (code (fenced) and hard break
+    /// (escape)).
+    ///
+    /// For more information, see the original markdown syntax:
+    /// <https://daringfireball.net/projects/markdown/syntax>.
+    pub fn pedantic() -> Self {
+        Self {
+            code_fenced: false,
+            hard_break_escape: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Defines a `with_*` and `without_*` method on [`Constructs`][] for each
+/// construct, or group of related constructs.
+macro_rules! constructs_builders {
+    ($($with:ident, $without:ident => $($field:ident),+;)*) => {
+        impl Constructs {
+            $(
+                /// Turn on (`true`) or off (`false`):
+                ///
+                $(#[doc = concat!("*   [`", stringify!($field), "`][Constructs::", stringify!($field), "]")])+
+                #[must_use]
+                pub fn $with(mut self, on: bool) -> Self {
+                    $(self.$field = on;)+
+                    self
+                }
+
+                /// Turn off:
+                ///
+                $(#[doc = concat!("*   [`", stringify!($field), "`][Constructs::", stringify!($field), "]")])+
+                #[must_use]
+                pub fn $without(self) -> Self {
+                    self.$with(false)
+                }
+            )*
+        }
+    };
+}
+
+constructs_builders! {
+    with_abbreviation, without_abbreviation => abbreviation;
+    with_attention, without_attention => attention;
+    with_attributes, without_attributes => attributes;
+    with_autolink, without_autolink => autolink;
+    with_block_quote, without_block_quote => block_quote;
+    with_character_escape, without_character_escape => character_escape;
+    with_character_reference, without_character_reference => character_reference;
+    with_cite, without_cite => cite;
+    with_code_fenced, without_code_fenced => code_fenced;
+    with_code_indented, without_code_indented => code_indented;
+    with_code_text, without_code_text => code_text;
+    with_critic_markup, without_critic_markup => critic_markup;
+    with_definition, without_definition => definition;
+    with_definition_list, without_definition_list => definition_list;
+    with_emoji, without_emoji => emoji;
+    with_frontmatter, without_frontmatter => frontmatter;
+    with_gfm_alert, without_gfm_alert => gfm_alert;
+    with_gfm_autolink_literal, without_gfm_autolink_literal => gfm_autolink_literal;
+    with_gfm_footnote, without_gfm_footnote => gfm_footnote_definition, gfm_label_start_footnote;
+    with_gfm_footnote_definition, without_gfm_footnote_definition => gfm_footnote_definition;
+    with_gfm_label_start_footnote, without_gfm_label_start_footnote => gfm_label_start_footnote;
+    with_gfm_strikethrough, without_gfm_strikethrough => gfm_strikethrough;
+    with_gfm_table, without_gfm_table => gfm_table;
+    with_gfm_table_caption, without_gfm_table_caption => gfm_table_caption;
+    with_gfm_task_list_item, without_gfm_task_list_item => gfm_task_list_item;
+    with_grid_table, without_grid_table => grid_table;
+    with_hard_break_escape, without_hard_break_escape => hard_break_escape;
+    with_hard_break_trailing, without_hard_break_trailing => hard_break_trailing;
+    with_heading_atx, without_heading_atx => heading_atx;
+    with_heading_setext, without_heading_setext => heading_setext;
+    with_highlight, without_highlight => highlight;
+    with_html, without_html => html_flow, html_text;
+    with_html_flow, without_html_flow => html_flow;
+    with_html_text, without_html_text => html_text;
+    with_insert, without_insert => insert;
+    with_label_end, without_label_end => label_end;
+    with_label_start_image, without_label_start_image => label_start_image;
+    with_label_start_inline_footnote, without_label_start_inline_footnote => label_start_inline_footnote;
+    with_label_start_link, without_label_start_link => label_start_link;
+    with_list_item, without_list_item => list_item;
+    with_math, without_math => math_flow, math_text;
+    with_math_flow, without_math_flow => math_flow;
+    with_math_text, without_math_text => math_text;
+    with_mdx_esm, without_mdx_esm => mdx_esm;
+    with_mdx_expression, without_mdx_expression => mdx_expression_flow, mdx_expression_text;
+    with_mdx_expression_flow, without_mdx_expression_flow => mdx_expression_flow;
+    with_mdx_expression_text, without_mdx_expression_text => mdx_expression_text;
+    with_mdx_jsx, without_mdx_jsx => mdx_jsx_flow, mdx_jsx_text;
+    with_mdx_jsx_flow, without_mdx_jsx_flow => mdx_jsx_flow;
+    with_mdx_jsx_text, without_mdx_jsx_text => mdx_jsx_text;
+    with_obsidian_block_id, without_obsidian_block_id => obsidian_block_id;
+    with_smart_punctuation, without_smart_punctuation => smart_punctuation;
+    with_thematic_break, without_thematic_break => thematic_break;
+    with_wiki_link, without_wiki_link => wiki_link;
+}
+
+/// How to handle definitions (and GFM footnote definitions) that have the
+/// same identifier as an earlier one.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::DuplicateDefinitionKind;
+/// # fn main() {
+///
+/// // Use the default trait to follow `CommonMark`, where the first wins:
+/// let first = DuplicateDefinitionKind::default();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DuplicateDefinitionKind {
+    /// The first definition wins (default).
+    ///
+    /// This is what `CommonMark` prescribes.
+    First,
+    /// The last definition wins.
+    Last,
+    /// Duplicate definitions are an error.
+    Error,
+}
+
+impl Default for DuplicateDefinitionKind {
+    /// `CommonMark` default.
+    fn default() -> Self {
+        Self::First
+    }
+}
+
+/// Kind of character references to use when encoding characters in HTML.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::CharacterReferenceKind;
+/// # fn main() {
+///
+/// // Use the default trait to get named references, such as `&amp;`:
+/// let named = CharacterReferenceKind::default();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CharacterReferenceKind {
+    /// Named character references, such as `&amp;` (default).
+    ///
+    /// Only names from HTML 4 are used, as they are widely supported.
+    /// Characters without such a name use a numeric reference instead.
+    Named,
+    /// Numeric (hexadecimal) character references, such as `&#x26;`.
+    ///
+    /// These work everywhere, including in XML.
+    Numeric,
+}
+
+impl Default for CharacterReferenceKind {
+    /// Named references.
+    fn default() -> Self {
+        Self::Named
+    }
+}
+
+/// Where to place GFM footnote definitions in HTML.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::GfmFootnotePlacementKind;
+/// # fn main() {
+///
+/// // Use the default trait to get one section at the end, like GitHub:
+/// let document = GfmFootnotePlacementKind::default();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GfmFootnotePlacementKind {
+    /// One footnote section at the end of the document (default).
+    ///
+    /// This is what GitHub does.
+    Document,
+    /// A footnote section at the end of each top-level section.
+    ///
+    /// Top-level sections start at each heading of rank 1 that is not in a
+    /// container (such as a block quote or list).
+    /// Each footnote section contains the footnotes that were first called
+    /// in that section.
+    /// Footnotes are still numbered across the whole document, so the lists
+    /// in later sections continue that numbering (`<ol start="3">`).
+    Section,
+}
+
+impl Default for GfmFootnotePlacementKind {
+    /// GitHub default.
+    fn default() -> Self {
+        Self::Document
+    }
 }
 
 /// Configuration that describes how to compile to HTML.
@@ -466,7 +873,7 @@ impl Constructs {
 /// # }
 /// ```
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Clone, Debug, Default)]
+#[derive(Default)]
 pub struct CompileOptions {
     /// Whether to allow (dangerous) HTML.
     ///
@@ -553,6 +960,82 @@ pub struct CompileOptions {
     /// ```
     pub allow_dangerous_protocol: bool,
 
+    /// Kind of character references to use when encoding characters.
+    ///
+    /// The default is [`CharacterReferenceKind::Named`][], which encodes
+    /// `&` as `&amp;`.
+    /// Pass [`CharacterReferenceKind::Numeric`][] to encode it as `&#x26;`
+    /// instead, which is useful when embedding the output in XML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CharacterReferenceKind, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses named character references by default:
+    /// assert_eq!(
+    ///     to_html("a & <b>"),
+    ///     "<p>a &amp; &lt;b&gt;</p>"
+    /// );
+    ///
+    /// // Pass `character_reference_kind: CharacterReferenceKind::Numeric` to use numeric ones:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a & <b>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               character_reference_kind: CharacterReferenceKind::Numeric,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a &#x26; &#x3C;b&#x3E;</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub character_reference_kind: CharacterReferenceKind,
+
+    /// Whether to highlight lines in code (fenced).
+    ///
+    /// The default is `false`, which ignores the meta of code.
+    /// Pass `true` to wrap the lines that are listed in braces in the meta
+    /// (such as `{1,3-4}`) in `<span class="highlighted">`.
+    /// See [`CodeMeta`][crate::CodeMeta] for how the meta is parsed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` ignores the meta by default:
+    /// assert_eq!(
+    ///     to_html("```js {2}\na\nb\n```"),
+    ///     "<pre><code class=\"language-js\">a\nb\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `code_highlight_lines: true` to highlight lines:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "```js {2}\na\nb\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               code_highlight_lines: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code class=\"language-js\">a\n<span class=\"highlighted\">b</span>\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_highlight_lines: bool,
+
     /// Default line ending to use when compiling to HTML, for line endings not
     /// in `value`.
     ///
@@ -597,106 +1080,226 @@ pub struct CompileOptions {
     /// ```
     pub default_line_ending: LineEnding,
 
-    /// Textual label to use for the footnotes section.
-    ///
-    /// The default value is `"Footnotes"`.
-    /// Change it when the markdown is not in English.
+    /// Whether to drop line endings between East Asian wide characters.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// The default is `false`, which outputs line endings in paragraphs as
+    /// they are, which browsers show as a space.
+    /// Pass `true` to drop a line ending when the characters on both sides
+    /// of it are East Asian wide characters (such as CJK ideographs, kana,
+    /// and fullwidth punctuation), so that Chinese and Japanese prose can be
+    /// wrapped in the source without gaining spaces in the output.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"Footnotes"` is used by default:
+    /// // `markdown-rs` keeps line endings between East Asian characters by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("日本語の\n文章"),
+    ///     "<p>日本語の\n文章</p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label` to use something else:
+    /// // Pass `east_asian_line_breaks: true` to drop them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "日本語の\n文章",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               east_asian_line_breaks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>日本語の文章</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label: Option<String>,
+    pub east_asian_line_breaks: bool,
 
-    /// HTML tag name to use for the footnote label element.
-    ///
-    /// The default value is `"h2"`.
-    /// Change it to match your document structure.
+    /// Whether to encode apostrophes (`'`).
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// The default is `false`, which leaves apostrophes as they are.
+    /// Pass `true` to encode them as `&#x27;`, which is useful when the
+    /// output is placed in single-quoted attributes.
+    /// A numeric reference is used, as `&apos;` is not supported in HTML 4.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"h2"` is used by default:
+    /// // `markdown-rs` does not encode apostrophes by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("Mercury's"),
+    ///     "<p>Mercury's</p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// // Pass `encode_apostrophe: true` to encode them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "Mercury's",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label_tag_name: Some("h1".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               encode_apostrophe: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>Mercury&#x27;s</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_tag_name: Option<String>,
+    pub encode_apostrophe: bool,
 
-    /// Attributes to use on the footnote label.
+    /// Whether to encode non-ASCII characters.
     ///
-    /// The default value is `"class=\"sr-only\""`.
-    /// Change it to show the label and add other attributes.
+    /// The default is `false`, which leaves characters such as `é` as they
+    /// are.
+    /// Pass `true` to encode them with character references (following
+    /// `character_reference_kind`), so that the output is pure ASCII, which
+    /// is useful for legacy systems that do not support UTF-8.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass an empty string.
-    /// You can also add different attributes.
+    /// This does not affect raw HTML, which is passed through as it is when
+    /// `allow_dangerous_html` is on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not encode non-ASCII characters by default:
+    /// assert_eq!(
+    ///     to_html("café ☕"),
+    ///     "<p>café ☕</p>"
+    /// );
+    ///
+    /// // Pass `encode_non_ascii: true` to encode them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "café ☕",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               encode_non_ascii: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>caf&eacute; &#x2615;</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub encode_non_ascii: bool,
+
+    /// Textual label to use for the footnotes section.
+    ///
+    /// The default value is `"Footnotes"`.
+    /// Change it when the markdown is not in English.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"Footnotes"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label: Option<String>,
+
+    /// HTML tag name to use for the footnote label element.
+    ///
+    /// The default value is `"h2"`.
+    /// Change it to match your document structure.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"h2"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label_tag_name: Some("h1".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label_tag_name: Option<String>,
+
+    /// Attributes to use on the footnote label.
+    ///
+    /// The default value is `"class=\"sr-only\""`.
+    /// Change it to show the label and add other attributes.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass an empty string.
+    /// You can also add different attributes.
     ///
     /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
     /// > calls use it with `aria-describedby` to provide an accessible label.
@@ -834,6 +1437,41 @@ pub struct CompileOptions {
     /// ```
     pub gfm_footnote_clobber_prefix: Option<String>,
 
+    /// Where to place footnote definitions.
+    ///
+    /// The default is [`GfmFootnotePlacementKind::Document`][], which places
+    /// them in one section at the end, like GitHub.
+    /// Pass [`GfmFootnotePlacementKind::Section`][] to place them at the
+    /// end of each top-level section instead (before the next heading of
+    /// rank 1), which is closer to printed layouts.
+    /// Footnote sections after the first get a label with an `id` of
+    /// `footnote-label-2`, `footnote-label-3`, and so on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, GfmFootnotePlacementKind, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Pass `gfm_footnote_placement: GfmFootnotePlacementKind::Section` to place footnotes in sections:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a[^x]\n\n# b\n\n[^x]: c",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_placement: GfmFootnotePlacementKind::Section,
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p>a<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-x\">\n<p>c <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n<h1>b</h1>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_placement: GfmFootnotePlacementKind,
+
     /// Whether or not GFM task list html `<input>` items are enabled.
     ///
     /// This determines whether or not the user of the browser is able
@@ -921,6 +1559,110 @@ pub struct CompileOptions {
     /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
     /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
     pub gfm_tagfilter: bool,
+
+    /// Function to turn the target and anchor of a wiki link into a URL.
+    ///
+    /// This option does nothing if `wiki_link` is not turned on in
+    /// [`Constructs`][].
+    /// The default is `None`, which uses the target, followed by `#` and the
+    /// anchor if there is one.
+    /// Pass a function to link to where notes are actually published.
+    /// The URL it returns is sanitized, like the URLs of other links.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // By default, the target is used as the URL:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[[Saturn#Rings]]",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///                 constructs: Constructs {
+    ///                     wiki_link: true,
+    ///                     ..Constructs::default()
+    ///                 },
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"Saturn#Rings\">Saturn#Rings</a></p>"
+    /// );
+    ///
+    /// // Pass `wiki_link_url` to resolve targets:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[[Saturn#Rings|rings]]",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///                 constructs: Constructs {
+    ///                     wiki_link: true,
+    ///                     ..Constructs::default()
+    ///                 },
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             compile: CompileOptions {
+    ///               wiki_link_url: Some(Box::new(|target, anchor| {
+    ///                   let mut url = format!("/notes/{}/", target.to_lowercase());
+    ///                   if let Some(anchor) = anchor {
+    ///                       url.push('#');
+    ///                       url.push_str(&anchor.to_lowercase());
+    ///                   }
+    ///                   url
+    ///               })),
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"/notes/saturn/#rings\">rings</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub wiki_link_url: Option<Box<WikiLinkUrl>>,
+}
+
+impl fmt::Debug for CompileOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompileOptions")
+            .field("allow_dangerous_html", &self.allow_dangerous_html)
+            .field("allow_dangerous_protocol", &self.allow_dangerous_protocol)
+            .field("character_reference_kind", &self.character_reference_kind)
+            .field("code_highlight_lines", &self.code_highlight_lines)
+            .field("default_line_ending", &self.default_line_ending)
+            .field("east_asian_line_breaks", &self.east_asian_line_breaks)
+            .field("encode_apostrophe", &self.encode_apostrophe)
+            .field("encode_non_ascii", &self.encode_non_ascii)
+            .field("gfm_footnote_label", &self.gfm_footnote_label)
+            .field(
+                "gfm_footnote_label_tag_name",
+                &self.gfm_footnote_label_tag_name,
+            )
+            .field(
+                "gfm_footnote_label_attributes",
+                &self.gfm_footnote_label_attributes,
+            )
+            .field("gfm_footnote_back_label", &self.gfm_footnote_back_label)
+            .field(
+                "gfm_footnote_clobber_prefix",
+                &self.gfm_footnote_clobber_prefix,
+            )
+            .field("gfm_footnote_placement", &self.gfm_footnote_placement)
+            .field(
+                "gfm_task_list_item_checkable",
+                &self.gfm_task_list_item_checkable,
+            )
+            .field("gfm_tagfilter", &self.gfm_tagfilter)
+            .field(
+                "wiki_link_url",
+                &self.wiki_link_url.as_ref().map(|_d| "[Function]"),
+            )
+            .finish()
+    }
 }
 
 impl CompileOptions {
@@ -1005,27 +1747,33 @@ pub struct ParseOptions {
     /// ```
     pub constructs: Constructs,
 
-    /// Whether to support GFM strikethrough with a single tilde
+    /// Protocols to support in GFM autolink literals.
     ///
-    /// This option does nothing if `gfm_strikethrough` is not turned on in
+    /// This option does nothing if `gfm_autolink_literal` is not turned on in
     /// `constructs`.
-    /// This option does not affect strikethrough with double tildes.
-    ///
-    /// The default is `true`, which follows how markdown on `github.com`
-    /// works, as strikethrough with single tildes is supported.
-    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
-    /// strikethrough with single tildes.
+    /// Each protocol (such as `ftp`) is matched case-insensitively, and must
+    /// be followed by `://` and a domain.
+    /// This option does not affect email autolink literals (including
+    /// `mailto:` and `xmpp:`).
+    ///
+    /// The default is `["http", "https"]`, which follows GFM.
+    /// Pass other protocols to link them too, or fewer to link less.
+    /// Note that URLs with protocols other than `http`, `https`, `irc`,
+    /// `ircs`, `mailto`, and `xmpp` are still sanitized when compiling to
+    /// HTML, unless
+    /// [`allow_dangerous_protocol`][CompileOptions::allow_dangerous_protocol]
+    /// is on.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single tildes by default:
+    /// // `markdown-rs` links `http` and `https` by default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "ftp://example.com",
     ///         &Options {
     ///             parse: ParseOptions {
     ///               constructs: Constructs::gfm(),
@@ -1034,42 +1782,39 @@ pub struct ParseOptions {
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><del>a</del></p>"
+    ///     "<p>ftp://example.com</p>"
     /// );
     ///
-    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// // Pass `gfm_autolink_literal_protocols` to link other protocols:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "ftp://example.com",
     ///         &Options {
     ///             parse: ParseOptions {
     ///               constructs: Constructs::gfm(),
-    ///               gfm_strikethrough_single_tilde: false,
+    ///               gfm_autolink_literal_protocols: vec!["ftp".into(), "https".into()],
     ///               ..ParseOptions::default()
     ///             },
-    ///             ..Options::default()
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_protocol: true,
+    ///               ..CompileOptions::default()
+    ///             }
     ///         }
     ///     )?,
-    ///     "<p>~a~</p>"
+    ///     "<p><a href=\"ftp://example.com\">ftp://example.com</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_strikethrough_single_tilde: bool,
+    pub gfm_autolink_literal_protocols: Vec<String>,
 
-    /// Whether to support math (text) with a single dollar
+    /// Whether to support GFM autolink literals that start with `www.`.
     ///
-    /// This option does nothing if `math_text` is not turned on in
+    /// This option does nothing if `gfm_autolink_literal` is not turned on in
     /// `constructs`.
-    /// This option does not affect math (text) with two or more dollars.
     ///
-    /// The default is `true`, which is more close to how code (text) and
-    /// Pandoc work, as it allows math with a single dollar to form.
-    /// However, single dollars can interfere with “normal” dollars in text.
-    /// Pass `false`, to only allow math (text) to form when two or more
-    /// dollars are used.
-    /// If you pass `false`, you can still use two or more dollars for text
-    /// math.
+    /// The default is `true`, which follows GFM.
+    /// Pass `false` to only link URLs with a protocol and email addresses.
     ///
     /// ## Examples
     ///
@@ -1077,56 +1822,602 @@ pub struct ParseOptions {
     /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single dollars by default:
+    /// // `markdown-rs` links `www.` by default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "www.example.com",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
+    ///               constructs: Constructs::gfm(),
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    ///     "<p><a href=\"http://www.example.com\">www.example.com</a></p>"
     /// );
     ///
-    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// // Pass `gfm_autolink_literal_www: false` to turn that off:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "www.example.com",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               math_text_single_dollar: false,
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_autolink_literal_www: false,
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>$a$</p>"
+    ///     "<p>www.example.com</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub math_text_single_dollar: bool,
+    pub gfm_autolink_literal_www: bool,
 
-    /// Function to parse expressions with.
+    /// Whether to support GFM strikethrough with a single tilde
     ///
-    /// This function can be used to add support for arbitrary programming
-    /// languages within expressions.
+    /// This option does nothing if `gfm_strikethrough` is not turned on in
+    /// `constructs`.
+    /// This option does not affect strikethrough with double tildes.
     ///
-    /// It only makes sense to pass this when compiling to a syntax tree
-    /// with [`to_mdast()`][crate::to_mdast()].
+    /// The default is `true`, which follows how markdown on `github.com`
+    /// works, as strikethrough with single tildes is supported.
+    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
+    /// strikethrough with single tildes.
     ///
-    /// For an example that adds support for JavaScript with SWC, see
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single tildes by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><del>a</del></p>"
+    /// );
+    ///
+    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_strikethrough_single_tilde: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>~a~</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_strikethrough_single_tilde: bool,
+
+    /// Whether to support math (text) with a single dollar
+    ///
+    /// This option does nothing if `math_text` is not turned on in
+    /// `constructs`.
+    /// This option does not affect math (text) with two or more dollars.
+    ///
+    /// The default is `true`, which is more close to how code (text) and
+    /// Pandoc work, as it allows math with a single dollar to form.
+    /// However, single dollars can interfere with “normal” dollars in text.
+    /// Pass `false`, to only allow math (text) to form when two or more
+    /// dollars are used.
+    /// If you pass `false`, you can still use two or more dollars for text
+    /// math.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single dollars by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    /// );
+    ///
+    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               math_text_single_dollar: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>$a$</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_text_single_dollar: bool,
+
+    /// Whether to support LaTeX delimiters for math.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to also support `\(` and `\)` around math (text), when
+    /// [`constructs.math_text`][Constructs::math_text] is on, and `\[` and
+    /// `\]` around math (flow), when
+    /// [`constructs.math_flow`][Constructs::math_flow] is on.
+    ///
+    /// The delimiters of math (flow) must each be on their own line.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let constructs = Constructs {
+    ///     math_flow: true,
+    ///     math_text: true,
+    ///     ..Constructs::default()
+    /// };
+    ///
+    /// // `markdown-rs` does not support LaTeX delimiters by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "\\(a\\)",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: constructs.clone(),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>(a)</p>"
+    /// );
+    ///
+    /// // Pass `math_latex_delimiters: true` to turn them on:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "\\(a\\)\n\\[\nb\n\\]",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs,
+    ///               math_latex_delimiters: true,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>\n<pre><code class=\"language-math math-display\">b\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_latex_delimiters: bool,
+
+    /// Whether to turn line endings in text into hard breaks.
+    ///
+    /// The default is `false`, which follows `CommonMark`: line endings in
+    /// paragraphs (and other text) are soft breaks, which show up as
+    /// whitespace, and hard breaks need a backslash or trailing spaces.
+    /// Pass `true` to instead turn every line ending in text into a hard
+    /// break, which is how GitHub renders comments (and what
+    /// [`remark-breaks`](https://github.com/remarkjs/remark-breaks) does).
+    /// Such line endings compile to `<br />` and a line ending in HTML, and
+    /// to [`Break`][crate::mdast::Break]s in mdast.
+    ///
+    /// Line endings in code (text), HTML (text), and other raw text are not
+    /// affected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` turns line endings into soft breaks by default:
+    /// assert_eq!(
+    ///     to_html("a\nb"),
+    ///     "<p>a\nb</p>"
+    /// );
+    ///
+    /// // Pass `breaks: true` to turn them into hard breaks:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\nb",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               breaks: true,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a<br />\nb</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub breaks: bool,
+
+    /// How to handle duplicate definitions.
+    ///
+    /// The default is [`DuplicateDefinitionKind::First`][], which follows
+    /// `CommonMark`: the first definition with an identifier is used.
+    /// Pass [`DuplicateDefinitionKind::Last`][] to use the last definition
+    /// instead, or [`DuplicateDefinitionKind::Error`][] to fail when there
+    /// are duplicates.
+    ///
+    /// Duplicates are always reported as messages, which you can get with
+    /// [`to_html_with_messages()`][crate::to_html_with_messages()] and
+    /// [`to_mdast_with_messages()`][crate::to_mdast_with_messages()].
+    ///
+    /// This affects definitions and GFM footnote definitions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, DuplicateDefinitionKind, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses the first definition by default:
+    /// assert_eq!(
+    ///     to_html("[a]\n\n[a]: b\n[a]: c"),
+    ///     "<p><a href=\"b\">a</a></p>\n"
+    /// );
+    ///
+    /// // Pass `duplicate_definitions: DuplicateDefinitionKind::Last` to use the last one:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a]\n\n[a]: b\n[a]: c",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               duplicate_definitions: DuplicateDefinitionKind::Last,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"c\">a</a></p>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub duplicate_definitions: DuplicateDefinitionKind,
+
+    /// Whether to keep character references as nodes in mdast.
+    ///
+    /// The default is `false`, which decodes character references (such as
+    /// `&amp;`) into the surrounding [`Text`][crate::mdast::Text].
+    /// Pass `true` to instead turn them into
+    /// [`CharacterReference`][crate::mdast::CharacterReference]s, which have
+    /// both the decoded value and how the reference was written, for tools
+    /// that must reproduce the original references.
+    ///
+    /// This option does nothing when compiling to HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` decodes character references by default:
+    /// assert_eq!(
+    ///     to_mdast("a &amp; b", &ParseOptions::default())?.children().unwrap()[0]
+    ///         .children()
+    ///         .unwrap()
+    ///         .len(),
+    ///     1
+    /// );
+    ///
+    /// // Pass `preserve_character_references: true` to keep them:
+    /// let tree = to_mdast(
+    ///     "a &amp; b",
+    ///     &ParseOptions {
+    ///         preserve_character_references: true,
+    ///         ..ParseOptions::default()
+    ///     },
+    /// )?;
+    ///
+    /// if let Node::CharacterReference(node) = &tree.children().unwrap()[0].children().unwrap()[1] {
+    ///     assert_eq!(node.value, "&");
+    ///     assert_eq!(node.raw, "&amp;");
+    /// } else {
+    ///     unreachable!("expected character reference");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub preserve_character_references: bool,
+
+    /// Whether to turn HTML comments into comment nodes in mdast.
+    ///
+    /// The default is `false`, which turns HTML comments (such as
+    /// `<!-- toc -->`) into [`Html`][crate::mdast::Html] nodes, like other
+    /// HTML.
+    /// Pass `true` to instead turn HTML that is exactly one comment into
+    /// [`Comment`][crate::mdast::Comment]s, whose value is what is between
+    /// `<!--` and `-->`, for tools that use comments as markers.
+    /// HTML with other things around the comment (such as
+    /// `<!-- a --> <b>`) stays `Html`.
+    ///
+    /// This option does nothing when compiling to HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` turns comments into `Html` by default:
+    /// assert!(matches!(
+    ///     to_mdast("<!-- toc -->", &ParseOptions::default())?.children().unwrap()[0],
+    ///     Node::Html(_)
+    /// ));
+    ///
+    /// // Pass `html_comment_nodes: true` to turn them into `Comment`s:
+    /// let tree = to_mdast(
+    ///     "<!-- toc -->",
+    ///     &ParseOptions {
+    ///         html_comment_nodes: true,
+    ///         ..ParseOptions::default()
+    ///     },
+    /// )?;
+    ///
+    /// if let Node::Comment(node) = &tree.children().unwrap()[0] {
+    ///     assert_eq!(node.value, " toc ");
+    /// } else {
+    ///     unreachable!("expected comment");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub html_comment_nodes: bool,
+
+    /// Number of columns between tab stops.
+    ///
+    /// The default is `4`, which is what `CommonMark` uses.
+    /// Tabs in markdown are expanded to the next tab stop, which affects
+    /// whether lines are indented enough to form code (indented), how far
+    /// the content of list items is indented, and the columns in positional
+    /// info.
+    /// Pass another number (such as `2` or `8`) to match how tabs are
+    /// displayed in the environment that the markdown is written in.
+    ///
+    /// The number of columns needed for code (indented) stays `4`.
+    /// Parsing fails when this is `0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses tab stops of 4 columns by default:
+    /// assert_eq!(
+    ///     to_html("\ta"),
+    ///     "<pre><code>a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `tab_size: 2` for tab stops of 2 columns:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "\ta",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               tab_size: 2,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub tab_size: usize,
+
+    /// Maximum depth to which constructs can be nested.
+    ///
+    /// The default is `None`, which means there is no limit.
+    /// Pass a number when parsing markdown that cannot be trusted: deeply
+    /// nested constructs take a lot of work to parse, and the resulting
+    /// syntax trees take a lot of stack space to walk.
+    ///
+    /// Each block quote, list item, footnote definition, definition
+    /// description, emphasis, strong, strikethrough, highlight, insert,
+    /// link, image, and MDX JSX element counts as one level.
+    /// When constructs are nested deeper than this, an error is returned.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports deeply nested constructs by default:
+    /// assert_eq!(
+    ///     to_html("> > a"),
+    ///     "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `max_nesting` to limit how deep they can go:
+    /// let result = to_html_with_options(
+    ///     "> > a",
+    ///     &Options {
+    ///         parse: ParseOptions {
+    ///           max_nesting: Some(1),
+    ///           ..ParseOptions::default()
+    ///         },
+    ///         ..Options::default()
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(
+    ///     result.unwrap_err().to_string(),
+    ///     "1:3: Unexpected construct nested deeper than `max_nesting` (1), expected fewer nested constructs (markdown-rs:max-nesting)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub max_nesting: Option<usize>,
+
+    /// Function to turn the shortcodes of emoji into emoji.
+    ///
+    /// This option does nothing if `emoji` is not turned on in
+    /// [`Constructs`][].
+    /// The default is `None`, which makes every shortcode an emoji, which is
+    /// kept as it is.
+    /// Pass a function to turn shortcodes into emoji: shortcodes it returns
+    /// `None` for are not emoji.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // By default, shortcodes are kept:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         ":tada: :a:",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///                 constructs: Constructs {
+    ///                     emoji: true,
+    ///                     ..Constructs::default()
+    ///                 },
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>:tada: :a:</p>"
+    /// );
+    ///
+    /// // Pass `emoji_lookup` to turn them into emoji:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         ":tada: :a:",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///                 constructs: Constructs {
+    ///                     emoji: true,
+    ///                     ..Constructs::default()
+    ///                 },
+    ///                 emoji_lookup: Some(Box::new(|shortcode| match shortcode {
+    ///                     "tada" => Some("🎉".into()),
+    ///                     _ => None,
+    ///                 })),
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>🎉 :a:</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub emoji_lookup: Option<Box<EmojiLookup>>,
+
+    /// Function to decide whether GFM autolink literals are links.
+    ///
+    /// This option does nothing if `gfm_autolink_literal` is not turned on in
+    /// `constructs`.
+    ///
+    /// The default is `None`, which links everything that GFM links.
+    /// Pass a function to veto some of them: it gets the source of each
+    /// autolink literal (such as `www.example.com`), and when it returns
+    /// `false`, that source is treated as text.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Pass `gfm_autolink_literal_filter` to only link some domains:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "https://example.com and https://example.org",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_autolink_literal_filter: Some(Box::new(|value| value.ends_with(".com"))),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://example.com\">https://example.com</a> and https://example.org</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_autolink_literal_filter: Option<Box<GfmAutolinkLiteralFilter>>,
+
+    /// Constructs in text, defined outside of `markdown-rs`.
+    ///
+    /// The default is no extensions.
+    /// Pass [`Extension`][]s to add support for syntax that `markdown-rs`
+    /// does not support, such as mentions (`@alice`) or issue references
+    /// (`#123`), without forking the crate.
+    ///
+    /// Extensions are tried in order, before the constructs of
+    /// `markdown-rs`.
+    /// They are turned into HTML and syntax tree nodes by the functions
+    /// they define.
+    /// Only constructs in text, on a single line, are supported: there are
+    /// no extensions of flow (blocks), and the source of extensions is not
+    /// parsed as markdown.
+    ///
+    /// See [`Extension`][] for an example.
+    pub extensions: Vec<Extension>,
+
+    /// Function to parse expressions with.
+    ///
+    /// This function can be used to add support for arbitrary programming
+    /// languages within expressions.
+    ///
+    /// It only makes sense to pass this when compiling to a syntax tree
+    /// with [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// For an example that adds support for JavaScript with SWC, see
     /// `tests/test_utils/mod.rs`.
     pub mdx_expression_parse: Option<Box<MdxExpressionParse>>,
 
@@ -1152,11 +2443,38 @@ impl fmt::Debug for ParseOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ParseOptions")
             .field("constructs", &self.constructs)
+            .field(
+                "gfm_autolink_literal_protocols",
+                &self.gfm_autolink_literal_protocols,
+            )
+            .field("gfm_autolink_literal_www", &self.gfm_autolink_literal_www)
             .field(
                 "gfm_strikethrough_single_tilde",
                 &self.gfm_strikethrough_single_tilde,
             )
             .field("math_text_single_dollar", &self.math_text_single_dollar)
+            .field("math_latex_delimiters", &self.math_latex_delimiters)
+            .field("breaks", &self.breaks)
+            .field("duplicate_definitions", &self.duplicate_definitions)
+            .field(
+                "preserve_character_references",
+                &self.preserve_character_references,
+            )
+            .field("html_comment_nodes", &self.html_comment_nodes)
+            .field("tab_size", &self.tab_size)
+            .field("max_nesting", &self.max_nesting)
+            .field(
+                "emoji_lookup",
+                &self.emoji_lookup.as_ref().map(|_d| "[Function]"),
+            )
+            .field(
+                "gfm_autolink_literal_filter",
+                &self
+                    .gfm_autolink_literal_filter
+                    .as_ref()
+                    .map(|_d| "[Function]"),
+            )
+            .field("extensions", &self.extensions)
             .field(
                 "mdx_expression_parse",
                 &self.mdx_expression_parse.as_ref().map(|_d| "[Function]"),
@@ -1174,8 +2492,20 @@ impl Default for ParseOptions {
     fn default() -> Self {
         Self {
             constructs: Constructs::default(),
+            gfm_autolink_literal_protocols: vec!["http".into(), "https".into()],
+            gfm_autolink_literal_www: true,
             gfm_strikethrough_single_tilde: true,
             math_text_single_dollar: true,
+            math_latex_delimiters: false,
+            breaks: false,
+            duplicate_definitions: DuplicateDefinitionKind::First,
+            preserve_character_references: false,
+            html_comment_nodes: false,
+            tab_size: TAB_SIZE,
+            max_nesting: None,
+            emoji_lookup: None,
+            gfm_autolink_literal_filter: None,
+            extensions: vec![],
             mdx_expression_parse: None,
             mdx_esm_parse: None,
         }
@@ -1225,6 +2555,329 @@ impl ParseOptions {
     }
 }
 
+/// Style of hard breaks when serializing to markdown.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::BreakKind;
+/// # fn main() {
+///
+/// // Use the default trait to get backslash breaks:
+/// let backslash = BreakKind::default();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreakKind {
+    /// A backslash before the line ending, such as `a\` (default).
+    ///
+    /// This survives tools that strip trailing whitespace.
+    Backslash,
+    /// Two spaces before the line ending, such as `a  `.
+    ///
+    /// This is invisible, but works in markdown parsers that do not support
+    /// backslash breaks.
+    Spaces,
+}
+
+impl Default for BreakKind {
+    /// Backslash breaks.
+    fn default() -> Self {
+        Self::Backslash
+    }
+}
+
+/// Configuration that describes how to serialize syntax trees to markdown.
+///
+/// Options that are `None` keep the style that nodes record of the original
+/// markdown (such as the marker of emphasis), and otherwise use the canonical
+/// style.
+/// Options that are set override how every node is serialized, which is
+/// useful to format documents in a certain house style.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("* _a_\n* __b__", &ParseOptions::default())?;
+///
+/// // Keep the style of the document:
+/// assert_eq!(
+///     to_markdown_with_options(&tree, &SerializeOptions::default()),
+///     "* _a_\n* __b__\n\n"
+/// );
+///
+/// // Use a house style:
+/// assert_eq!(
+///     to_markdown_with_options(
+///         &tree,
+///         &SerializeOptions {
+///             bullet: Some('-'),
+///             emphasis: Some('*'),
+///             strong: Some('*'),
+///             ..SerializeOptions::default()
+///         }
+///     ),
+///     "- *a*\n- **b**\n\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerializeOptions {
+    /// Marker to use for items of unordered lists: `*`, `+`, or `-`.
+    ///
+    /// The default is `None`, which keeps the marker that lists record, or
+    /// uses `*`.
+    pub bullet: Option<char>,
+
+    /// Marker to use after the numbers of items of ordered lists: `.` or
+    /// `)`.
+    ///
+    /// The default is `None`, which keeps the marker that lists record, or
+    /// uses `.`.
+    pub bullet_ordered: Option<char>,
+
+    /// Whether to increment the numbers of items of ordered lists.
+    ///
+    /// The default is `true`, which numbers items `1.`, `2.`, `3.`.
+    /// Pass `false` to use the number of the first item for all items (`1.`,
+    /// `1.`, `1.`), which keeps diffs small when items are added.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("1. a\n2. b", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             increment_list_marker: false,
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     ),
+    ///     "1. a\n1. b\n\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub increment_list_marker: bool,
+
+    /// Marker to use for emphasis: `*` or `_`.
+    ///
+    /// The default is `None`, which keeps the marker that emphasis records,
+    /// or uses `*`.
+    pub emphasis: Option<char>,
+
+    /// Marker to use for strong: `*` or `_`.
+    ///
+    /// The default is `None`, which keeps the marker that strong records, or
+    /// uses `*`.
+    pub strong: Option<char>,
+
+    /// Marker to use for fenced code: `` ` `` or `~`.
+    ///
+    /// The default is `None`, which keeps the marker that code records, or
+    /// uses `` ` ``.
+    /// Tildes are used anyway when the info string of code contains a
+    /// backtick.
+    pub fence: Option<char>,
+
+    /// Whether to use setext headings (underlined with `=` or `-`).
+    ///
+    /// The default is `None`, which keeps the style that headings record, or
+    /// uses ATX headings (`#`).
+    /// Pass `Some(true)` to use setext headings where possible (for headings
+    /// of rank 1 and 2 that have content), or `Some(false)` to always use
+    /// ATX headings.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("# a\n### b", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             setext: Some(true),
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     ),
+    ///     "a\n=\n\n### b\n\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub setext: Option<bool>,
+
+    /// Marker to use for thematic breaks: `*`, `-`, or `_`.
+    ///
+    /// The default is `*`.
+    pub rule: char,
+
+    /// Maximum number of characters in lines of paragraphs.
+    ///
+    /// The default is `None`, which does not wrap.
+    /// Pass `Some(80)` to reflow the text of paragraphs: line endings in text
+    /// are turned into spaces, and lines are broken at spaces so that they
+    /// have at most 80 characters, where possible.
+    /// Other markdown, such as inline code and link destinations, is never
+    /// broken, so lines are longer when it does not fit.
+    /// Lines are also not broken where the next line would start with
+    /// something that could start other markdown, such as `#` or `1.`.
+    /// Other blocks, such as headings and tables, are not wrapped.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a b\nc `d e` f", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             line_width: Some(5),
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     ),
+    ///     "a b c\n`d e`\nf\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub line_width: Option<usize>,
+
+    /// Whether to pad the cells of tables so that their pipes line up.
+    ///
+    /// The default is `false`, which puts one space around the content of
+    /// cells.
+    /// Pass `true` to pad cells to the width of the widest cell in their
+    /// column, and to fill in missing cells.
+    /// Widths are counted in columns, where East Asian wide characters (such
+    /// as `中`) take up two.
+    /// Cells in columns that are aligned right or center are padded on the
+    /// left or on both sides.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("| a | b |\n| - | -: |\n| 中文 | c |", &ParseOptions::gfm())?;
+    ///
+    /// assert_eq!(
+    ///     to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             pad_tables: true,
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     ),
+    ///     "| a    |   b |\n| ---- | --: |\n| 中文 |   c |\n\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub pad_tables: bool,
+
+    /// Style of hard breaks.
+    ///
+    /// The default is [`BreakKind::Backslash`][], which puts a backslash
+    /// before the line ending, as trailing whitespace is often stripped by
+    /// editors and other tools.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown_with_options, to_mdast, BreakKind, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a\\\nb", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             break_style: BreakKind::Spaces,
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     ),
+    ///     "a  \nb\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub break_style: BreakKind,
+
+    /// Line ending to use.
+    ///
+    /// The default is `None`, which uses `\n` for the line endings that are
+    /// generated, and keeps the line endings in values (such as the value of
+    /// code) as they are.
+    /// Pass a line ending to use it for all line endings, which is useful to
+    /// keep the line endings of documents that use `\r\n`.
+    /// [`LineEnding::detect`][] finds the line ending that a document uses
+    /// most.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown_with_options, to_mdast, LineEnding, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let value = "# a\r\n\r\nb\r\nc";
+    /// let tree = to_mdast(value, &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             line_ending: LineEnding::detect(value),
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     ),
+    ///     "# a\r\n\r\nb\r\nc\r\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub line_ending: Option<LineEnding>,
+}
+
+impl Default for SerializeOptions {
+    /// Keep the style of documents.
+    fn default() -> Self {
+        Self {
+            bullet: None,
+            bullet_ordered: None,
+            increment_list_marker: true,
+            emphasis: None,
+            strong: None,
+            fence: None,
+            setext: None,
+            rule: '*',
+            line_width: None,
+            pad_tables: false,
+            break_style: BreakKind::Backslash,
+            line_ending: None,
+        }
+    }
+}
+
 /// Configuration that describes how to parse from markdown and compile to
 /// HTML.
 ///
@@ -1318,6 +2971,56 @@ mod tests {
         );
         assert!(constructs.mdx_jsx_flow, "should support `mdx` shortcut (3)");
         assert!(!constructs.frontmatter, "should support `mdx` shortcut (4)");
+
+        assert_eq!(
+            Constructs::commonmark(),
+            Constructs::default(),
+            "should support `commonmark` shortcut"
+        );
+
+        let constructs = Constructs::pedantic();
+        assert!(
+            constructs.attention,
+            "should support `pedantic` shortcut (1)"
+        );
+        assert!(
+            !constructs.code_fenced,
+            "should support `pedantic` shortcut (2)"
+        );
+        assert!(
+            !constructs.hard_break_escape,
+            "should support `pedantic` shortcut (3)"
+        );
+
+        assert_eq!(
+            Constructs::gfm().without_autolink().with_frontmatter(true),
+            Constructs {
+                autolink: false,
+                frontmatter: true,
+                ..Constructs::gfm()
+            },
+            "should support `with_*` and `without_*` methods"
+        );
+
+        assert_eq!(
+            Constructs::default().with_math(true).without_html(),
+            Constructs {
+                html_flow: false,
+                html_text: false,
+                math_flow: true,
+                math_text: true,
+                ..Constructs::default()
+            },
+            "should support `with_*` and `without_*` methods for groups"
+        );
+
+        assert_eq!(
+            Constructs::mdx().with_mdx_jsx(false),
+            Constructs::mdx()
+                .without_mdx_jsx_flow()
+                .without_mdx_jsx_text(),
+            "should support `with_*` w/ `false` as `without_*`"
+        );
     }
 
     #[test]
@@ -1370,7 +3073,7 @@ mod tests {
 
         assert_eq!(
             format!("{:?}", ParseOptions::default()),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: None, mdx_esm_parse: None }",
+            "ParseOptions { constructs: Constructs { abbreviation: false, attention: true, attributes: false, autolink: true, block_quote: true, character_escape: true, character_reference: true, cite: false, code_indented: true, code_fenced: true, code_text: true, critic_markup: false, definition: true, definition_list: false, emoji: false, frontmatter: false, gfm_alert: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_table_caption: false, gfm_task_list_item: false, grid_table: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, highlight: false, html_flow: true, html_text: true, insert: false, label_start_image: true, label_start_inline_footnote: false, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, obsidian_block_id: false, smart_punctuation: false, thematic_break: true, wiki_link: false }, gfm_autolink_literal_protocols: [\"http\", \"https\"], gfm_autolink_literal_www: true, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, math_latex_delimiters: false, breaks: false, duplicate_definitions: First, preserve_character_references: false, html_comment_nodes: false, tab_size: 4, max_nesting: None, emoji_lookup: None, gfm_autolink_literal_filter: None, extensions: [], mdx_expression_parse: None, mdx_esm_parse: None }",
             "should support `Debug` trait"
         );
         assert_eq!(
@@ -1383,7 +3086,7 @@ mod tests {
                 })),
                 ..Default::default()
             }),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
+            "ParseOptions { constructs: Constructs { abbreviation: false, attention: true, attributes: false, autolink: true, block_quote: true, character_escape: true, character_reference: true, cite: false, code_indented: true, code_fenced: true, code_text: true, critic_markup: false, definition: true, definition_list: false, emoji: false, frontmatter: false, gfm_alert: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_table_caption: false, gfm_task_list_item: false, grid_table: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, highlight: false, html_flow: true, html_text: true, insert: false, label_start_image: true, label_start_inline_footnote: false, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, obsidian_block_id: false, smart_punctuation: false, thematic_break: true, wiki_link: false }, gfm_autolink_literal_protocols: [\"http\", \"https\"], gfm_autolink_literal_www: true, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, math_latex_delimiters: false, breaks: false, duplicate_definitions: First, preserve_character_references: false, html_comment_nodes: false, tab_size: 4, max_nesting: None, emoji_lookup: None, gfm_autolink_literal_filter: None, extensions: [], mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
             "should support `Debug` trait on mdx functions"
         );
     }
@@ -0,0 +1,459 @@
+//! Abbreviation definition occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Abbreviation definitions form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the label must contain at least one character that is not
+//! ; whitespace.
+//! abbreviation_definition ::= '*' '[' 1*label_char ']' ':' *space_or_tab [title]
+//! label_char ::= code - '[' - ']' - eol
+//! title ::= 1*(code - eol)
+//! ```
+//!
+//! As this construct occurs in flow, like all flow constructs, it must be
+//! followed by an eol (line ending) or eof (end of file).
+//! Like definitions, abbreviation definitions cannot interrupt paragraphs.
+//!
+//! Abbreviation definitions do not turn into anything on their own.
+//! Instead, occurrences of their label in the [text][] content type, anywhere
+//! in the document (also before the definition), turn into abbreviations.
+//! Occurrences must match the label exactly (it is case-sensitive, and
+//! whitespace at the start and end of the label is ignored), and must not be
+//! preceded or followed by a letter or digit, so that `HTML` does not match
+//! in `XHTML`.
+//! When several labels match at the same place, the longest wins.
+//! When several definitions have the same label, the first one is used.
+//!
+//! The label and title are not parsed: character escapes and character
+//! references are not supported in them, and the title is used as it is
+//! (without whitespace at its start and end).
+//!
+//! Occurrences are only found in plain text, so not in code (text), math
+//! (text), autolinks, HTML, or other things.
+//!
+//! ## HTML
+//!
+//! Abbreviation definitions in markdown do not, on their own, relate to
+//! anything in HTML.
+//! Occurrences of them relate to the `<abbr>` element in HTML, with the
+//! title of the definition as its `title` attribute.
+//! See [*§ 4.5.9 The `abbr` element*][html_abbr] in the HTML spec for more
+//! info.
+//!
+//! ## Recommendation
+//!
+//! Abbreviations are not supported in most places that render markdown.
+//! When the markdown is also read elsewhere, the definitions show up as
+//! paragraphs.
+//!
+//! ## Tokens
+//!
+//! *   [`Abbreviation`][Name::Abbreviation]
+//! *   [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+//! *   [`AbbreviationDefinitionLabelMarker`][Name::AbbreviationDefinitionLabelMarker]
+//! *   [`AbbreviationDefinitionLabelString`][Name::AbbreviationDefinitionLabelString]
+//! *   [`AbbreviationDefinitionMarker`][Name::AbbreviationDefinitionMarker]
+//! *   [`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//!
+//! ## References
+//!
+//! *   [*Abbreviations* in PHP Markdown Extra](https://michelf.ca/projects/php-markdown/extra/#abbr)
+//! *   [*Abbreviations* in `markdown-it-abbr`](https://github.com/markdown-it/markdown-it-abbr)
+//!
+//! [flow]: crate::construct::flow
+//! [text]: crate::construct::text
+//! [html_abbr]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-abbr-element
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::{Event, Kind, Name};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{
+    char::{after_index as char_after_index, before_index as char_before_index},
+    constant::TAB_SIZE,
+    slice::{Position, Slice},
+};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// At start of abbreviation definition.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    // Do not interrupt paragraphs.
+    if tokenizer.parse_state.options.constructs.abbreviation && !tokenizer.interrupt {
+        tokenizer.enter(Name::AbbreviationDefinition);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::AbbreviationDefinitionBefore),
+                State::Nok,
+            );
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    TAB_SIZE - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::AbbreviationDefinitionBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at `*`.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'*') {
+        tokenizer.enter(Name::AbbreviationDefinitionMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::AbbreviationDefinitionMarker);
+        State::Next(StateName::AbbreviationDefinitionLabelOpen)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `*`, at `[`.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///      ^
+/// ```
+pub fn label_open(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.enter(Name::AbbreviationDefinitionLabelMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::AbbreviationDefinitionLabelMarker);
+        State::Next(StateName::AbbreviationDefinitionLabelBefore)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `[`, at label.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///       ^
+/// ```
+pub fn label_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[' | b']') => State::Nok,
+        Some(_) => {
+            tokenizer.enter(Name::AbbreviationDefinitionLabelString);
+            State::Retry(StateName::AbbreviationDefinitionLabelInside)
+        }
+    }
+}
+
+/// In label.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///       ^^^^
+/// ```
+pub fn label_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[') => {
+            tokenizer.tokenize_state.seen = false;
+            State::Nok
+        }
+        Some(b']') => {
+            if tokenizer.tokenize_state.seen {
+                tokenizer.tokenize_state.seen = false;
+                tokenizer.exit(Name::AbbreviationDefinitionLabelString);
+                tokenizer.enter(Name::AbbreviationDefinitionLabelMarker);
+                tokenizer.consume();
+                tokenizer.exit(Name::AbbreviationDefinitionLabelMarker);
+                State::Next(StateName::AbbreviationDefinitionLabelAfter)
+            } else {
+                State::Nok
+            }
+        }
+        Some(byte) => {
+            if !matches!(byte, b'\t' | b' ') {
+                tokenizer.tokenize_state.seen = true;
+            }
+
+            tokenizer.consume();
+            State::Next(StateName::AbbreviationDefinitionLabelInside)
+        }
+    }
+}
+
+/// After `]`, at `:`.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///            ^
+/// ```
+pub fn label_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::AbbreviationDefinitionMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::AbbreviationDefinitionMarker);
+        State::Next(StateName::AbbreviationDefinitionMarkerAfter)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `:`, at optional whitespace.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///             ^
+/// ```
+pub fn marker_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::AbbreviationDefinitionTitleBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::AbbreviationDefinitionTitleBefore)
+    }
+}
+
+/// After `:` and optional whitespace, at title.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///              ^
+/// ```
+pub fn title_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Retry(StateName::AbbreviationDefinitionAfter),
+        Some(_) => {
+            tokenizer.enter(Name::AbbreviationDefinitionTitle);
+            State::Retry(StateName::AbbreviationDefinitionTitle)
+        }
+    }
+}
+
+/// In title.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///              ^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+pub fn title(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::AbbreviationDefinitionTitle);
+            State::Retry(StateName::AbbreviationDefinitionAfter)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::AbbreviationDefinitionTitle)
+        }
+    }
+}
+
+/// At eol or eof.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///                                       ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::AbbreviationDefinition);
+
+    let label_index = tokenizer
+        .events
+        .iter()
+        .rposition(|event| {
+            event.kind == Kind::Exit && event.name == Name::AbbreviationDefinitionLabelString
+        })
+        .expect("expected label");
+
+    let label = value(
+        &tokenizer.events,
+        tokenizer.parse_state.bytes,
+        label_index,
+        tokenizer.parse_state.options.tab_size,
+    );
+    tokenizer.tokenize_state.abbreviations.push(label);
+
+    // Feel free to interrupt.
+    tokenizer.interrupt = false;
+    State::Ok
+}
+
+/// Turn occurrences of the labels of abbreviation definitions in data into
+/// abbreviations.
+pub fn resolve(tokenizer: &mut Tokenizer) {
+    tokenizer.map.consume(&mut tokenizer.events);
+
+    let bytes = tokenizer.parse_state.bytes;
+    let tab_size = tokenizer.parse_state.options.tab_size;
+    let mut index = 0;
+
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        // Linked data is another content type (such as string), parsed
+        // later.
+        if event.kind == Kind::Exit
+            && event.name == Name::Data
+            && tokenizer.events[index - 1].link.is_none()
+        {
+            let enter = &tokenizer.events[index - 1];
+            let start_index = enter.point.index;
+            let slice = Slice::from_position(
+                bytes,
+                &Position::from_exit_event(&tokenizer.events, index),
+                tab_size,
+            );
+            let value = slice.as_str();
+            let mut replace: Vec<Event> = Vec::new();
+            let mut point = enter.point.clone();
+            let mut min = 0;
+
+            for (char_index, _) in value.char_indices() {
+                if char_index < min
+                    || !is_boundary(char_before_index(bytes, start_index + char_index))
+                {
+                    continue;
+                }
+
+                let mut size = 0;
+
+                for label in &tokenizer.parse_state.abbreviations {
+                    if label.len() > size
+                        && value[char_index..].starts_with(label.as_str())
+                        && is_boundary(char_after_index(
+                            bytes,
+                            start_index + char_index + label.len(),
+                        ))
+                    {
+                        size = label.len();
+                    }
+                }
+
+                if size == 0 {
+                    continue;
+                }
+
+                // Data before this abbreviation.
+                if min != char_index {
+                    replace.push(Event {
+                        kind: Kind::Enter,
+                        name: Name::Data,
+                        point: point.clone(),
+                        link: None,
+                    });
+                    point = point.shift_to(bytes, start_index + char_index, tab_size);
+                    replace.push(Event {
+                        kind: Kind::Exit,
+                        name: Name::Data,
+                        point: point.clone(),
+                        link: None,
+                    });
+                }
+
+                replace.push(Event {
+                    kind: Kind::Enter,
+                    name: Name::Abbreviation,
+                    point: point.clone(),
+                    link: None,
+                });
+                point = point.shift_to(bytes, start_index + char_index + size, tab_size);
+                replace.push(Event {
+                    kind: Kind::Exit,
+                    name: Name::Abbreviation,
+                    point: point.clone(),
+                    link: None,
+                });
+                min = char_index + size;
+            }
+
+            // Data after the last abbreviation.
+            if !replace.is_empty() && min < value.len() {
+                replace.push(Event {
+                    kind: Kind::Enter,
+                    name: Name::Data,
+                    point,
+                    link: None,
+                });
+                replace.push(Event {
+                    kind: Kind::Exit,
+                    name: Name::Data,
+                    point: event.point.clone(),
+                    link: None,
+                });
+            }
+
+            if !replace.is_empty() {
+                tokenizer.map.add(index - 1, 2, replace);
+            }
+        }
+
+        index += 1;
+    }
+}
+
+/// Get the titles of abbreviation definitions in `events`, by label.
+///
+/// When several definitions have the same label, the first one is used.
+pub fn titles(events: &[Event], bytes: &[u8], tab_size: usize) -> BTreeMap<String, Option<String>> {
+    let mut titles = BTreeMap::new();
+    let mut label = None;
+    let mut title = None;
+
+    for (index, event) in events.iter().enumerate() {
+        if event.kind == Kind::Exit {
+            match event.name {
+                Name::AbbreviationDefinitionLabelString => {
+                    label = Some(value(events, bytes, index, tab_size));
+                }
+                Name::AbbreviationDefinitionTitle => {
+                    title = Some(value(events, bytes, index, tab_size));
+                }
+                Name::AbbreviationDefinition => {
+                    titles
+                        .entry(label.take().expect("expected label"))
+                        .or_insert_with(|| title.take());
+                    title = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    titles
+}
+
+/// Get the trimmed value of the exit event at `index`.
+pub fn value(events: &[Event], bytes: &[u8], index: usize, tab_size: usize) -> String {
+    Slice::from_position(bytes, &Position::from_exit_event(events, index), tab_size)
+        .as_str()
+        .trim()
+        .to_string()
+}
+
+/// Check whether `char` can be next to an abbreviation.
+fn is_boundary(char: Option<char>) -> bool {
+    char.map_or(true, |char| !char.is_alphanumeric())
+}
@@ -1,5 +1,5 @@
-//! Attention (emphasis, strong, optionally GFM strikethrough) occurs in the
-//! [text][] content type.
+//! Attention (emphasis, strong, optionally GFM strikethrough, highlight, and
+//! insert) occurs in the [text][] content type.
 //!
 //! ## Grammar
 //!
@@ -9,6 +9,8 @@
 //! ```bnf
 //! attention_sequence ::= 1*'*' | 1*'_'
 //! gfm_attention_sequence ::= 1*'~'
+//! highlight_attention_sequence ::= 1*'='
+//! insert_attention_sequence ::= 1*'+'
 //! ```
 //!
 //! Sequences are matched together to form attention based on which character
@@ -29,6 +31,15 @@
 //! HTML.
 //! See [*§ 4.7.2 The `del` element*][html-del] in the HTML spec for more info.
 //!
+//! When equals sign sequences of two markers match, they together relate to
+//! the `<mark>` element in HTML.
+//! See [*§ 4.5.23 The `mark` element*][html-mark] in the HTML spec for more
+//! info.
+//!
+//! When plus sign sequences of two markers match, they together relate to the
+//! `<ins>` element in HTML.
+//! See [*§ 4.7.1 The `ins` element*][html-ins] in the HTML spec for more info.
+//!
 //! ## Recommendation
 //!
 //! It is recommended to use asterisks for emphasis/strong attention when
@@ -49,6 +60,11 @@
 //! While `github.com` allows single tildes too, it technically prohibits it in
 //! their spec.
 //!
+//! Highlight and insert are not supported in most places that render
+//! markdown.
+//! When the markdown is also read elsewhere, the equals and plus signs show up
+//! as text.
+//!
 //! ## Tokens
 //!
 //! *   [`Emphasis`][Name::Emphasis]
@@ -57,6 +73,12 @@
 //! *   [`GfmStrikethrough`][Name::GfmStrikethrough]
 //! *   [`GfmStrikethroughSequence`][Name::GfmStrikethroughSequence]
 //! *   [`GfmStrikethroughText`][Name::GfmStrikethroughText]
+//! *   [`Highlight`][Name::Highlight]
+//! *   [`HighlightSequence`][Name::HighlightSequence]
+//! *   [`HighlightText`][Name::HighlightText]
+//! *   [`Insert`][Name::Insert]
+//! *   [`InsertSequence`][Name::InsertSequence]
+//! *   [`InsertText`][Name::InsertText]
 //! *   [`Strong`][Name::Strong]
 //! *   [`StrongSequence`][Name::StrongSequence]
 //! *   [`StrongText`][Name::StrongText]
@@ -70,11 +92,14 @@
 //! *   [`micromark-extension-gfm-strikethrough`](https://github.com/micromark/micromark-extension-gfm-strikethrough)
 //! *   [*§ 6.2 Emphasis and strong emphasis* in `CommonMark`](https://spec.commonmark.org/0.31/#emphasis-and-strong-emphasis)
 //! *   [*§ 6.5 Strikethrough (extension)* in `GFM`](https://github.github.com/gfm/#strikethrough-extension-)
+//! *   [*Basic formatting syntax* in Obsidian](https://help.obsidian.md/Editing+and+formatting/Basic+formatting+syntax)
 //!
 //! [text]: crate::construct::text
 //! [html-em]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-em-element
 //! [html-strong]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-strong-element
 //! [html-del]: https://html.spec.whatwg.org/multipage/edits.html#the-del-element
+//! [html-mark]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-mark-element
+//! [html-ins]: https://html.spec.whatwg.org/multipage/edits.html#the-ins-element
 
 use crate::event::{Event, Kind, Name, Point};
 use crate::resolve::Name as ResolveName;
@@ -122,6 +147,10 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         && matches!(tokenizer.current, Some(b'*' | b'_')))
         // GFM strikethrough:
         || (tokenizer.parse_state.options.constructs.gfm_strikethrough && tokenizer.current == Some(b'~'))
+        // Highlight:
+        || (tokenizer.parse_state.options.constructs.highlight && tokenizer.current == Some(b'='))
+        // Insert:
+        || (tokenizer.parse_state.options.constructs.insert && tokenizer.current == Some(b'+'))
     {
         tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
         tokenizer.enter(Name::AttentionSequence);
@@ -200,6 +229,14 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                         continue;
                     }
 
+                    // For highlight and insert, both sequences must have two
+                    // markers.
+                    if matches!(sequence_close.marker, b'+' | b'=')
+                        && (sequence_close.size != 2 || sequence_open.size != 2)
+                    {
+                        continue;
+                    }
+
                     // We found a match!
                     next_index = match_sequences(tokenizer, &mut sequences, open, close);
 
@@ -243,18 +280,28 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
                 let before = classify_opt(before_char);
                 let after_char = char_after_index(tokenizer.parse_state.bytes, exit.point.index);
                 let after = classify_opt(after_char);
+                let regular = matches!(marker, b'*' | b'_');
                 let open = after == CharacterKind::Other
                     || (after == CharacterKind::Punctuation && before != CharacterKind::Other)
-                    // For regular attention markers (not strikethrough), the
-                    // other attention markers can be used around them
-                    || (marker != b'~' && matches!(after_char, Some('*' | '_')))
-                    || (marker != b'~' && tokenizer.parse_state.options.constructs.gfm_strikethrough && matches!(after_char, Some('~')));
+                    // For regular attention markers (not strikethrough,
+                    // highlight, or insert), the other attention markers can
+                    // be used around them
+                    || (regular && matches!(after_char, Some('*' | '_')))
+                    || (regular && tokenizer.parse_state.options.constructs.gfm_strikethrough && matches!(after_char, Some('~')))
+                    || (regular && tokenizer.parse_state.options.constructs.highlight && matches!(after_char, Some('=')))
+                    || (regular && tokenizer.parse_state.options.constructs.insert && matches!(after_char, Some('+')));
                 let close = before == CharacterKind::Other
                     || (before == CharacterKind::Punctuation && after != CharacterKind::Other)
-                    || (marker != b'~' && matches!(before_char, Some('*' | '_')))
-                    || (marker != b'~'
+                    || (regular && matches!(before_char, Some('*' | '_')))
+                    || (regular
                         && tokenizer.parse_state.options.constructs.gfm_strikethrough
-                        && matches!(before_char, Some('~')));
+                        && matches!(before_char, Some('~')))
+                    || (regular
+                        && tokenizer.parse_state.options.constructs.highlight
+                        && matches!(before_char, Some('=')))
+                    || (regular
+                        && tokenizer.parse_state.options.constructs.insert
+                        && matches!(before_char, Some('+')));
 
                 sequences.push(Sequence {
                     index,
@@ -333,6 +380,14 @@ fn match_sequences(
             Name::GfmStrikethroughSequence,
             Name::GfmStrikethroughText,
         )
+    } else if sequences[open].marker == b'=' {
+        (
+            Name::Highlight,
+            Name::HighlightSequence,
+            Name::HighlightText,
+        )
+    } else if sequences[open].marker == b'+' {
+        (Name::Insert, Name::InsertSequence, Name::InsertText)
     } else if take == 1 {
         (Name::Emphasis, Name::EmphasisSequence, Name::EmphasisText)
     } else {
@@ -0,0 +1,228 @@
+//! Attributes occur at the end of headings in the [text][] content type, in
+//! the opening fence of [code (fenced)][raw_flow], and after
+//! [code (text)][raw_text].
+//!
+//! ## Grammar
+//!
+//! Attributes form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: in headings, followed by optional whitespace and the end of
+//! ; text.
+//! ; Restriction: in code (fenced), after the sequence or the info, and
+//! ; followed by optional whitespace and the end of the line.
+//! ; Restriction: in code (text), directly after the closing sequence.
+//! attributes ::= '{' *space_or_tab attribute *( 1*space_or_tab attribute ) *space_or_tab '}'
+//!
+//! attribute ::= '#' 1*name | '.' 1*name | key '=' value
+//! key ::= ( ascii_alpha | '_' | ':' ) *( ascii_alphanumeric | '-' | '.' | '_' | ':' )
+//! value ::= 1*name | '"' *( line - '"' ) '"' | "'" *( line - "'" ) "'"
+//! name ::= line - ( space_or_tab | '"' | "'" | '<' | '=' | '>' | '`' | '{' | '}' )
+//! ```
+//!
+//! Attributes give an identifier (`#id`), classes (`.class`), and other
+//! attributes (`key=value`) to the heading or code they are on.
+//! The keys `id` and `class` can also be used to set the identifier and
+//! classes.
+//! Braces that do not contain valid attributes, such as `{1,3}` in the meta
+//! of code, are not attributes.
+//!
+//! In code (fenced), attributes either replace the info
+//! (`` ```{.js} ``), or follow the info (`` ```js {.a} ``) or the meta
+//! (`` ```js b {.a} ``).
+//!
+//! ## HTML
+//!
+//! Attributes do not relate to anything in HTML themselves.
+//! They are output as attributes on the heading (`<h1>` and such) or
+//! `<code>` element they are on.
+//! Classes on code (fenced) are added after the class for the language.
+//!
+//! Attributes that start with `on` (event handlers, such as `onclick`) are
+//! dangerous and are not output, unless
+//! [`allow_dangerous_html`][crate::CompileOptions::allow_dangerous_html] is
+//! on.
+//!
+//! ## Recommendation
+//!
+//! Attributes are not supported in most places that render markdown.
+//! When the markdown is also read elsewhere, they show up as text.
+//!
+//! ## Tokens
+//!
+//! *   [`Attributes`][Name::Attributes]
+//! *   [`AttributesMarker`][Name::AttributesMarker]
+//! *   [`AttributesValue`][Name::AttributesValue]
+//!
+//! ## References
+//!
+//! *   [*Extension: `header_attributes`* in Pandoc](https://pandoc.org/MANUAL.html#extension-header_attributes)
+//! *   [*Special Attributes* in PHP Markdown Extra](https://michelf.ca/projects/php-markdown/extra/#spe-attr)
+//!
+//! [text]: crate::construct::text
+//! [raw_flow]: crate::construct::raw_flow
+//! [raw_text]: crate::construct::raw_text
+
+use crate::construct::partial_space_or_tab::space_or_tab;
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{
+    attributes::parse,
+    slice::{Position, Slice},
+};
+
+/// At start of attributes in text.
+///
+/// Attributes are only allowed in text in headings, and only at the end.
+///
+/// ```markdown
+/// > | # a {#b}
+///         ^
+/// ```
+pub fn text_start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.tokenize_state.text_in_heading {
+        tokenizer.attempt(State::Next(StateName::AttributesTextAfter), State::Nok);
+        State::Retry(StateName::AttributesStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After attributes in text.
+///
+/// ```markdown
+/// > | # a {#b}
+///             ^
+/// ```
+pub fn text_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None => State::Ok,
+        // Space or tab?
+        // Check what comes after.
+        Some(b'\t' | b' ') => {
+            tokenizer.check(State::Ok, State::Nok);
+            tokenizer.attempt(
+                State::Next(StateName::AttributesTextAfterSpaceOrTab),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        }
+        _ => State::Nok,
+    }
+}
+
+/// After whitespace, after attributes in text.
+///
+/// ```markdown
+/// > | # a {#b}␠
+///              ^
+/// ```
+pub fn text_after_space_or_tab(tokenizer: &mut Tokenizer) -> State {
+    // Only the end of text is okay.
+    if tokenizer.current.is_none() {
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
+
+/// At start of attributes.
+///
+/// ```markdown
+/// > | # a {#b}
+///         ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.attributes && tokenizer.current == Some(b'{') {
+        tokenizer.enter(Name::Attributes);
+        tokenizer.enter(Name::AttributesMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::AttributesMarker);
+        State::Next(StateName::AttributesValueBefore)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `{`, at value.
+///
+/// ```markdown
+/// > | # a {#b}
+///          ^
+/// ```
+pub fn value_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'}') => State::Nok,
+        _ => {
+            tokenizer.enter(Name::AttributesValue);
+            State::Retry(StateName::AttributesValue)
+        }
+    }
+}
+
+/// In value.
+///
+/// ```markdown
+/// > | # a {#b}
+///          ^
+/// ```
+pub fn value(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'{') => State::Nok,
+        Some(b'"' | b'\'') => {
+            tokenizer.tokenize_state.marker_b = tokenizer.current.unwrap();
+            tokenizer.consume();
+            State::Next(StateName::AttributesValueQuoted)
+        }
+        Some(b'}') => {
+            tokenizer.exit(Name::AttributesValue);
+
+            let slice = Slice::from_position(
+                tokenizer.parse_state.bytes,
+                &Position::from_exit_event(&tokenizer.events, tokenizer.events.len() - 1),
+                tokenizer.parse_state.options.tab_size,
+            );
+
+            if parse(slice.as_str()).is_some() {
+                tokenizer.enter(Name::AttributesMarker);
+                tokenizer.consume();
+                tokenizer.exit(Name::AttributesMarker);
+                tokenizer.exit(Name::Attributes);
+                State::Ok
+            } else {
+                State::Nok
+            }
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::AttributesValue)
+        }
+    }
+}
+
+/// In quoted value.
+///
+/// ```markdown
+/// > | # a {b="c"}
+///             ^
+/// ```
+pub fn value_quoted(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.marker_b = 0;
+            State::Nok
+        }
+        Some(byte) => {
+            if byte == tokenizer.tokenize_state.marker_b {
+                tokenizer.tokenize_state.marker_b = 0;
+                tokenizer.consume();
+                State::Next(StateName::AttributesValue)
+            } else {
+                tokenizer.consume();
+                State::Next(StateName::AttributesValueQuoted)
+            }
+        }
+    }
+}
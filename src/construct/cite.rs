@@ -0,0 +1,116 @@
+//! Citations occur in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Citations form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: not followed by `(` or `[`.
+//! cite ::= '[' citation *( ';' citation ) ']' | in_text
+//!
+//! ; Restriction: `in_text` is not preceded by an alphanumeric or `_`.
+//! ; Restriction: `affix` in `in_text` cannot contain `@`.
+//! in_text ::= '@' key [ ' ' '[' affix ']' ]
+//!
+//! ; Restriction: `-` or `@` is at the start or after whitespace.
+//! citation ::= affix [ '-' ] '@' key affix
+//!
+//! ; Restriction: punctuation must be followed by an alphanumeric or `_`.
+//! key ::= ( alphanumeric | '_' ) *( alphanumeric | '_' | ':' | '.' | '#' | '$' | '%' | '&' | '-' | '+' | '?' | '<' | '>' | '~' | '/' ) | '{' 1*( line - '}' ) '}'
+//! affix ::= *( line - ( '[' | ']' | ';' ) )
+//! ```
+//!
+//! Citations refer to works in a bibliography by their key, as in Pandoc.
+//! They are either in brackets (`[see @doe99, p. 33; @smith04]`), where the
+//! author can be suppressed (`[-@doe99]`), or in text (`@doe99 says`),
+//! optionally followed by a locator or suffix in brackets
+//! (`@doe99 [p. 33] says`).
+//! Text before a key is the prefix.
+//! Text after a key is the suffix, which starts with a locator if it starts
+//! with a term such as `p.`, `chap.`, or `sec.` followed by a page or other
+//! number.
+//!
+//! Nothing is interpreted in citations: character escapes, character
+//! references, and other constructs are not supported.
+//! Citations cannot span multiple lines.
+//!
+//! ## HTML
+//!
+//! Citations do not relate to anything in HTML: they are meant to be resolved
+//! against a bibliography by the caller, using the syntax tree.
+//! When turned into HTML directly, they are turned into a
+//! `<span class="citation">` element, with the keys in a `data-cites`
+//! attribute, around their source.
+//!
+//! ## Recommendation
+//!
+//! Citations are not supported in most places that render markdown.
+//! When the markdown is also read elsewhere, the source of the citations
+//! shows up.
+//!
+//! ## Tokens
+//!
+//! *   [`Cite`][Name::Cite]
+//!
+//! ## References
+//!
+//! *   [*Citation syntax* in Pandoc](https://pandoc.org/MANUAL.html#citation-syntax)
+//!
+//! [text]: crate::construct::text
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{char::before_index as char_before_index, cite::parse, slice::rest_of_line};
+
+/// Start of citations.
+///
+/// ```markdown
+/// > | a [@b] c
+///       ^
+/// > | a @b c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    let bytes = tokenizer.parse_state.bytes;
+    let index = tokenizer.point.index;
+
+    if tokenizer.parse_state.options.constructs.cite
+        && (tokenizer.current == Some(b'[')
+            || (tokenizer.current == Some(b'@')
+                && !char_before_index(bytes, index)
+                    .map_or(false, |char| char.is_alphanumeric() || char == '_')))
+    {
+        if let Some((size, _)) = parse(rest_of_line(bytes, index)) {
+            tokenizer.tokenize_state.end = index + size;
+            tokenizer.enter(Name::Cite);
+            return State::Retry(StateName::CiteInside);
+        }
+    }
+
+    State::Nok
+}
+
+/// In citations.
+///
+/// ```markdown
+/// > | a [@b] c
+///       ^^^^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        // The text can end before the line does, such as in table cells.
+        if tokenizer.current.is_none() {
+            tokenizer.tokenize_state.end = 0;
+            return State::Nok;
+        }
+
+        tokenizer.consume();
+        State::Next(StateName::CiteInside)
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        tokenizer.exit(Name::Cite);
+        State::Ok
+    }
+}
@@ -0,0 +1,272 @@
+//! `CriticMarkup` occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! `CriticMarkup` forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! critic_markup ::= addition | deletion | substitution | highlight | comment
+//!
+//! addition ::= '{++' content '++}'
+//! deletion ::= '{--' content '--}'
+//! substitution ::= '{~~' content '~>' content '~~}'
+//! highlight ::= '{==' content '==}'
+//! comment ::= '{>>' content '<<}'
+//!
+//! ; Restriction: the content cannot contain the closing marker of the
+//! ; construct (or `~>` in the first part of a substitution).
+//! content ::= 1*byte
+//! ```
+//!
+//! `CriticMarkup` is used to track changes and comments when reviewing
+//! documents: text can be added (`{++a++}`), deleted (`{--a--}`), replaced
+//! (`{~~a~>b~~}`), highlighted (`{==a==}`), and commented on (`{>>a<<}`).
+//!
+//! The content is parsed as [text][], so it can include emphasis, links, and
+//! other constructs, and can span multiple lines.
+//! The content ends at the first closing marker: the same kind of
+//! `CriticMarkup` cannot be nested.
+//! Each part must contain at least one byte.
+//!
+//! ## HTML
+//!
+//! Additions relate to the `<ins>` element, deletions to the `<del>` element,
+//! and highlights to the `<mark>` element in HTML.
+//! See [*§ 4.7.1 The `ins` element*][html_ins],
+//! [*§ 4.7.2 The `del` element*][html_del], and
+//! [*§ 4.5.23 The `mark` element*][html_mark] in the HTML spec for more
+//! info.
+//! Substitutions are turned into a `<del>` element followed by an `<ins>`
+//! element.
+//! Comments are turned into a `<span class="critic comment">` element.
+//!
+//! ## Recommendation
+//!
+//! `CriticMarkup` is not supported in most places that render markdown.
+//! It is meant for documents in review: when the markdown is also read
+//! elsewhere, accept or reject the changes first.
+//!
+//! ## Tokens
+//!
+//! *   [`CriticAddition`][Name::CriticAddition]
+//! *   [`CriticChunk`][Name::CriticChunk]
+//! *   [`CriticComment`][Name::CriticComment]
+//! *   [`CriticDeletion`][Name::CriticDeletion]
+//! *   [`CriticHighlight`][Name::CriticHighlight]
+//! *   [`CriticMarker`][Name::CriticMarker]
+//! *   [`CriticSubstitution`][Name::CriticSubstitution]
+//!
+//! ## References
+//!
+//! *   [`CriticMarkup-toolkit`](https://github.com/CriticMarkup/CriticMarkup-toolkit)
+//!
+//! [text]: crate::construct::text
+//! [html_ins]: https://html.spec.whatwg.org/multipage/edits.html#the-ins-element
+//! [html_del]: https://html.spec.whatwg.org/multipage/edits.html#the-del-element
+//! [html_mark]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-mark-element
+
+use crate::event::{Content, Link, Name};
+use crate::state::{Name as StateName, State};
+use crate::subtokenize::link;
+use crate::tokenizer::Tokenizer;
+
+/// At start of `CriticMarkup`.
+///
+/// ```markdown
+/// > | a {++b++} c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    let bytes = tokenizer.parse_state.bytes;
+    let index = tokenizer.point.index;
+
+    if tokenizer.parse_state.options.constructs.critic_markup
+        && tokenizer.current == Some(b'{')
+        && index + 2 < bytes.len()
+        && matches!(bytes[index + 1], b'+' | b'-' | b'~' | b'=' | b'>')
+        && bytes[index + 1] == bytes[index + 2]
+    {
+        tokenizer.tokenize_state.marker = bytes[index + 1];
+        tokenizer.enter(group(tokenizer.tokenize_state.marker));
+        tokenizer.enter(Name::CriticMarker);
+        tokenizer.consume();
+        State::Next(StateName::CriticMarkupOpen)
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening marker, after `{`.
+///
+/// ```markdown
+/// > | a {++b++} c
+///        ^^
+/// ```
+pub fn open(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.consume();
+    tokenizer.tokenize_state.size += 1;
+
+    if tokenizer.tokenize_state.size < 2 {
+        State::Next(StateName::CriticMarkupOpen)
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::CriticMarker);
+
+        if tokenizer.tokenize_state.marker == b'~' {
+            tokenizer.enter(Name::CriticDeletion);
+        }
+
+        State::Next(StateName::CriticMarkupBefore)
+    }
+}
+
+/// Before a chunk of content, or at a closing marker.
+///
+/// ```markdown
+/// > | a {++b++} c
+///          ^  ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current.is_none() {
+        nok(tokenizer)
+    } else if at_close(tokenizer) {
+        if tokenizer.tokenize_state.seen {
+            State::Retry(StateName::CriticMarkupClose)
+        } else {
+            nok(tokenizer)
+        }
+    } else {
+        tokenizer.enter_link(
+            Name::CriticChunk,
+            Link {
+                previous: None,
+                next: None,
+                content: Content::Text,
+            },
+        );
+
+        if tokenizer.tokenize_state.connect {
+            let index = tokenizer.events.len() - 1;
+            link(&mut tokenizer.events, index);
+        } else {
+            tokenizer.tokenize_state.connect = true;
+        }
+
+        tokenizer.tokenize_state.seen = true;
+        State::Retry(StateName::CriticMarkupInside)
+    }
+}
+
+/// In a chunk of content.
+///
+/// ```markdown
+/// > | a {++b++} c
+///          ^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None => nok(tokenizer),
+        Some(b'\n') => {
+            tokenizer.consume();
+            tokenizer.exit(Name::CriticChunk);
+            State::Next(StateName::CriticMarkupBefore)
+        }
+        _ if at_close(tokenizer) => {
+            tokenizer.exit(Name::CriticChunk);
+            State::Retry(StateName::CriticMarkupClose)
+        }
+        _ => {
+            tokenizer.consume();
+            State::Next(StateName::CriticMarkupInside)
+        }
+    }
+}
+
+/// At closing marker, or at `~>` in a substitution.
+///
+/// ```markdown
+/// > | a {++b++} c
+///           ^
+/// ```
+pub fn close(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.connect = false;
+    tokenizer.tokenize_state.seen = false;
+
+    if tokenizer.tokenize_state.marker == b'~' {
+        tokenizer.exit(if tokenizer.tokenize_state.size_b == 0 {
+            Name::CriticDeletion
+        } else {
+            Name::CriticAddition
+        });
+    }
+
+    tokenizer.tokenize_state.size = closing(tokenizer).len();
+    tokenizer.enter(Name::CriticMarker);
+    State::Retry(StateName::CriticMarkupCloseInside)
+}
+
+/// In closing marker, or in `~>` in a substitution.
+///
+/// ```markdown
+/// > | a {++b++} c
+///           ^^^
+/// ```
+pub fn close_inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.tokenize_state.size > 0 {
+        tokenizer.consume();
+        tokenizer.tokenize_state.size -= 1;
+        State::Next(StateName::CriticMarkupCloseInside)
+    } else {
+        tokenizer.exit(Name::CriticMarker);
+
+        if tokenizer.tokenize_state.marker == b'~' && tokenizer.tokenize_state.size_b == 0 {
+            tokenizer.tokenize_state.size_b = 1;
+            tokenizer.enter(Name::CriticAddition);
+            State::Retry(StateName::CriticMarkupBefore)
+        } else {
+            tokenizer.exit(group(tokenizer.tokenize_state.marker));
+            tokenizer.tokenize_state.marker = 0;
+            tokenizer.tokenize_state.size_b = 0;
+            State::Ok
+        }
+    }
+}
+
+/// Reset state and fail.
+fn nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.marker = 0;
+    tokenizer.tokenize_state.size = 0;
+    tokenizer.tokenize_state.size_b = 0;
+    tokenizer.tokenize_state.connect = false;
+    tokenizer.tokenize_state.seen = false;
+    State::Nok
+}
+
+/// Get the name of the group for `marker`.
+fn group(marker: u8) -> Name {
+    match marker {
+        b'+' => Name::CriticAddition,
+        b'-' => Name::CriticDeletion,
+        b'~' => Name::CriticSubstitution,
+        b'=' => Name::CriticHighlight,
+        _ => Name::CriticComment,
+    }
+}
+
+/// Get the marker that closes the current part.
+fn closing(tokenizer: &Tokenizer) -> &'static [u8] {
+    match tokenizer.tokenize_state.marker {
+        b'+' => b"++}",
+        b'-' => b"--}",
+        b'~' if tokenizer.tokenize_state.size_b == 0 => b"~>",
+        b'~' => b"~~}",
+        b'=' => b"==}",
+        _ => b"<<}",
+    }
+}
+
+/// Whether the current part closes here.
+fn at_close(tokenizer: &Tokenizer) -> bool {
+    tokenizer.parse_state.bytes[tokenizer.point.index..].starts_with(closing(tokenizer))
+}
@@ -0,0 +1,342 @@
+//! Definition list occurs in the [document][] content type.
+//!
+//! ## Grammar
+//!
+//! Definition lists form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: must follow a paragraph (the term), optionally with blank
+//! ; lines between them, or another description.
+//! ; Restriction: the first line must not be blank.
+//! definition_description_start ::= ':' 1*space_or_tab
+//!
+//! ; Restriction: blank line allowed.
+//! definition_description_cont ::= 4(space_or_tab)
+//! ```
+//!
+//! Further lines that are not prefixed with `definition_description_cont`
+//! cause the description to be exited, except when those lines are lazy
+//! continuation or blank.
+//!
+//! Descriptions are containers (like block quotes and list items), so they
+//! can contain other flow, such as more paragraphs, code, or lists, when it
+//! is indented.
+//!
+//! The paragraph before the first description is the term.
+//! Each line of that paragraph is a separate term, unless something (such
+//! as emphasis) spans several lines.
+//! A term can have several descriptions, and several terms with their
+//! descriptions next to each other form one list:
+//!
+//! ```markdown
+//! Mercury
+//! : The smallest planet.
+//! : The closest to the Sun.
+//!
+//! Venus
+//! Earth's twin
+//! : Second from the Sun.
+//! ```
+//!
+//! Terms are not parsed by a state machine: the paragraph is parsed as
+//! normal text first, and turned into terms afterwards, when all events are
+//! known.
+//!
+//! ## HTML
+//!
+//! Definition lists relate to the `<dl>`, `<dt>`, and `<dd>` elements in
+//! HTML.
+//! See [*§ 4.4.9 The `dl` element*][html_dl],
+//! [*§ 4.4.10 The `dt` element*][html_dt], and
+//! [*§ 4.4.11 The `dd` element*][html_dd] in the HTML spec for more info.
+//!
+//! Like lists, definition lists are tight or loose: when there are blank
+//! lines between their terms and descriptions, or between the things in a
+//! description, paragraphs in descriptions are wrapped in `<p>` elements.
+//!
+//! ## Recommendation
+//!
+//! Use a single space after a marker.
+//! Never use lazy continuation.
+//!
+//! Definition lists are not supported in most places that render markdown.
+//! When the markdown is also read elsewhere, the terms and descriptions
+//! merge into one paragraph.
+//!
+//! ## Tokens
+//!
+//! *   [`DefinitionDescription`][Name::DefinitionDescription]
+//! *   [`DefinitionDescriptionMarker`][Name::DefinitionDescriptionMarker]
+//! *   [`DefinitionDescriptionPrefix`][Name::DefinitionDescriptionPrefix]
+//! *   [`DefinitionList`][Name::DefinitionList]
+//! *   [`DefinitionTerm`][Name::DefinitionTerm]
+//!
+//! ## References
+//!
+//! *   [*Definition Lists* in PHP Markdown Extra](https://michelf.ca/projects/php-markdown/extra/#def-list)
+//! *   [*Extension: `definition_lists`* in Pandoc](https://pandoc.org/MANUAL.html#extension-definition_lists)
+//!
+//! [document]: crate::construct::document
+//! [html_dl]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-dl-element
+//! [html_dt]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-dt-element
+//! [html_dd]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-dd-element
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::{Event, Kind, Name};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{Container, Tokenizer};
+use crate::util::{constant::TAB_SIZE, edit_map::EditMap, skip};
+use alloc::{vec, vec::Vec};
+
+/// Start of definition description.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.definition_list
+        && after_term_or_description(tokenizer)
+    {
+        tokenizer.enter(Name::DefinitionDescription);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DefinitionListBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                1,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    TAB_SIZE - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DefinitionListBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// At marker, after optional whitespace.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DefinitionDescriptionPrefix);
+        tokenizer.enter(Name::DefinitionDescriptionMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DefinitionDescriptionMarker);
+        State::Next(StateName::DefinitionListMarkerAfter)
+    } else {
+        State::Nok
+    }
+}
+
+/// After marker, at whitespace.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///      ^
+/// ```
+pub fn marker_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::DefinitionListWhitespaceAfter),
+            State::Nok,
+        );
+        // Any whitespace after the marker is eaten, forming indented code
+        // is not possible.
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Nok
+    }
+}
+
+/// After whitespace, at the start of the description.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///       ^
+/// ```
+pub fn whitespace_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, None | Some(b'\n')) {
+        State::Nok
+    } else {
+        tokenizer.exit(Name::DefinitionDescriptionPrefix);
+        State::Ok
+    }
+}
+
+/// Start of definition description continuation.
+///
+/// ```markdown
+///   | a
+///   | : b
+/// > |     c
+///     ^
+/// ```
+pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.check(
+        State::Next(StateName::DefinitionListContBlank),
+        State::Next(StateName::DefinitionListContFilled),
+    );
+    State::Retry(StateName::BlankLineStart)
+}
+
+/// Start of definition description continuation, at a blank line.
+///
+/// ```markdown
+///   | a
+///   | : b
+/// > | ␠␠␊
+///     ^
+/// ```
+pub fn cont_blank(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        State::Retry(space_or_tab_min_max(tokenizer, 0, TAB_SIZE))
+    } else {
+        State::Ok
+    }
+}
+
+/// Start of definition description continuation, at a filled line.
+///
+/// ```markdown
+///   | a
+///   | : b
+/// > |     c
+///     ^
+/// ```
+pub fn cont_filled(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        // Consume exactly `TAB_SIZE`.
+        State::Retry(space_or_tab_min_max(tokenizer, TAB_SIZE, TAB_SIZE))
+    } else {
+        State::Nok
+    }
+}
+
+/// Check whether a description can start here: after a paragraph in the same
+/// containers, or after another description.
+///
+/// Expects the new container to be at `document_continued` in the stack, and
+/// the existing container there, if any, to be at the end of the stack.
+fn after_term_or_description(tokenizer: &Tokenizer) -> bool {
+    let stack = &tokenizer.tokenize_state.document_container_stack;
+    let continued = tokenizer.tokenize_state.document_continued;
+
+    tokenizer.tokenize_state.document_definition_term == Some(continued)
+        || (continued + 1 < stack.len()
+            && stack[stack.len() - 1].kind == Container::DefinitionDescription)
+}
+
+/// Turn paragraphs before descriptions into terms, and wrap adjacent terms
+/// and descriptions in lists.
+pub fn resolve(events: &mut Vec<Event>) {
+    let mut map = EditMap::new();
+    // Start and end (exit) indices of lists.
+    let mut lists: Vec<(usize, usize)> = vec![];
+    let mut index = 0;
+
+    while index < events.len() {
+        if events[index].kind == Kind::Enter && events[index].name == Name::DefinitionDescription {
+            let end = skip::opt(events, index, &[Name::DefinitionDescription]) - 1;
+            let mut start = index;
+            let mut before = previous(events, index);
+
+            // A paragraph before the description: the terms.
+            if events[before].kind == Kind::Exit && events[before].name == Name::Paragraph {
+                let enter = skip::to_back(events, before - 1, &[Name::Paragraph]);
+                terms(events, &mut map, enter, before);
+                start = enter;
+                before = previous(events, enter);
+            }
+
+            if let Some(list) = lists.iter_mut().find(|list| list.1 == before) {
+                list.1 = end;
+            } else {
+                lists.push((start, end));
+            }
+        }
+
+        index += 1;
+    }
+
+    for (start, end) in lists {
+        let mut enter = events[start].clone();
+        enter.name = Name::DefinitionList;
+        enter.link = None;
+        let mut exit = events[end].clone();
+        exit.name = Name::DefinitionList;
+        exit.link = None;
+        map.add(start, 0, vec![enter]);
+        map.add(end + 1, 0, vec![exit]);
+    }
+
+    map.consume(events);
+}
+
+/// Find the index of the thing before `index`, ignoring whitespace and
+/// block quote prefixes.
+fn previous(events: &[Event], index: usize) -> usize {
+    if index == 0 {
+        index
+    } else {
+        skip::opt_back(
+            events,
+            index - 1,
+            &[
+                Name::BlankLineEnding,
+                Name::BlockQuotePrefix,
+                Name::LineEnding,
+                Name::SpaceOrTab,
+            ],
+        )
+    }
+}
+
+/// Turn the paragraph from `enter` to `exit` into terms, one per line,
+/// except when something (such as emphasis) spans several lines.
+fn terms(events: &mut [Event], map: &mut EditMap, enter: usize, exit: usize) {
+    events[enter].name = Name::DefinitionTerm;
+    events[exit].name = Name::DefinitionTerm;
+
+    let mut balance = 0;
+    let mut index = enter + 1;
+
+    while index < exit {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            if balance == 0 && event.name == Name::LineEnding {
+                let after = skip::opt(
+                    events,
+                    index + 2,
+                    &[Name::BlockQuotePrefix, Name::SpaceOrTab],
+                );
+                let mut term_exit = events[exit].clone();
+                term_exit.point = event.point.clone();
+                let mut term_enter = events[enter].clone();
+                term_enter.point = events[after].point.clone();
+                map.add(index, 0, vec![term_exit]);
+                map.add(after, 0, vec![term_enter]);
+            }
+
+            balance += 1;
+        } else {
+            balance -= 1;
+        }
+
+        index += 1;
+    }
+}
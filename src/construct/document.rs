@@ -8,9 +8,11 @@
 //! *   [Block quote][crate::construct::block_quote]
 //! *   [List item][crate::construct::list_item]
 //! *   [GFM: Footnote definition][crate::construct::gfm_footnote_definition]
+//! *   [Definition list][crate::construct::definition_list] (description)
 
 use crate::event::{Content, Event, Kind, Link, Name};
 use crate::message;
+use crate::parser::too_deep;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::divide_events;
 use crate::tokenizer::{Container, ContainerState, Tokenizer};
@@ -103,6 +105,7 @@ pub fn container_existing_before(tokenizer: &mut Tokenizer) -> State {
             Container::BlockQuote => StateName::BlockQuoteContStart,
             Container::GfmFootnoteDefinition => StateName::GfmFootnoteDefinitionContStart,
             Container::ListItem => StateName::ListItemContStart,
+            Container::DefinitionDescription => StateName::DefinitionListContStart,
         };
 
         tokenizer.attempt(
@@ -230,11 +233,38 @@ pub fn container_new_before_not_list(tokenizer: &mut Tokenizer) -> State {
 /// At new container, but not a block quote, list item, or footnote definition.
 //
 /// ```markdown
-/// > | a
+///   | a
+/// > | : b
 ///     ^
 /// ```
 pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -> State {
-    // It wasn’t a new block quote, list item, or footnote definition.
+    // Definition description?
+    // We replace the empty footnote definition container for this new
+    // description one.
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued] = ContainerState {
+        kind: Container::DefinitionDescription,
+        blank_initial: false,
+        size: 0,
+    };
+
+    tokenizer.attempt(
+        State::Next(StateName::DocumentContainerNewAfter),
+        State::Next(StateName::DocumentContainerNewBeforeNotDefinitionDescription),
+    );
+    State::Retry(StateName::DefinitionListStart)
+}
+
+/// At new container, but not a block quote, list item, footnote definition,
+/// or definition description.
+//
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+pub fn container_new_before_not_definition_description(tokenizer: &mut Tokenizer) -> State {
+    // It wasn’t a new block quote, list item, footnote definition, or
+    // definition description.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Drop what was in the middle.
     tokenizer
@@ -254,7 +284,8 @@ pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -
 ///       ^
 /// ```
 pub fn container_new_after(tokenizer: &mut Tokenizer) -> State {
-    // It was a new block quote, list item, or footnote definition.
+    // It was a new block quote, list item, footnote definition, or definition
+    // description.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Take the new container.
     let container = tokenizer
@@ -284,6 +315,30 @@ pub fn container_new_after(tokenizer: &mut Tokenizer) -> State {
         .tokenize_state
         .document_container_stack
         .push(container);
+
+    // Stop early when containers are nested too deep, instead of continuing
+    // all of them on every following line.
+    if let Some(max) = tokenizer.parse_state.options.max_nesting {
+        if tokenizer.tokenize_state.document_container_stack.len() > max {
+            let enter = tokenizer
+                .events
+                .iter()
+                .rev()
+                .find(|event| {
+                    event.kind == Kind::Enter
+                        && matches!(
+                            event.name,
+                            Name::BlockQuote
+                                | Name::DefinitionDescription
+                                | Name::GfmFootnoteDefinition
+                                | Name::ListItem
+                        )
+                })
+                .expect("expected container enter");
+            return State::Error(too_deep(max, enter.point.to_unist()));
+        }
+    }
+
     tokenizer.tokenize_state.document_continued += 1;
     tokenizer.interrupt = false;
     State::Retry(StateName::DocumentContainerNewBefore)
@@ -429,6 +484,20 @@ pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
         }
     }
 
+    // Whether this line is part of a paragraph, which can be a definition
+    // term, or blank.
+    let mut definition_term_current = false;
+    let mut blank = false;
+
+    if tokenizer.parse_state.options.constructs.definition_list && !child.events.is_empty() {
+        let before = skip::opt_back(&child.events, child.events.len() - 1, &[Name::LineEnding]);
+        // A GFM table head row is still a paragraph until its delimiter row.
+        definition_term_current = child.stack.contains(&Name::Content)
+            || child.stack.contains(&Name::GfmTableHead)
+            || child.events[before].name == Name::Content;
+        blank = child.events[child.events.len() - 1].name == Name::BlankLineEnding;
+    }
+
     // Reset “piercing”.
     child.pierce = false;
 
@@ -450,6 +519,19 @@ pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
         debug_assert!(result.is_ok(), "did not expect error when exiting");
     }
 
+    // Blank lines between a term and its description are fine, anything else
+    // is not.
+    if tokenizer.parse_state.options.constructs.definition_list {
+        let depth = tokenizer.tokenize_state.document_container_stack.len();
+        let term = &mut tokenizer.tokenize_state.document_definition_term;
+
+        if definition_term_current {
+            *term = Some(depth);
+        } else if !blank || *term != Some(depth) {
+            *term = None;
+        }
+    }
+
     match tokenizer.current {
         None => {
             tokenizer.tokenize_state.document_continued = 0;
@@ -500,6 +582,7 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
                 Container::BlockQuote => Name::BlockQuote,
                 Container::GfmFootnoteDefinition => Name::GfmFootnoteDefinition,
                 Container::ListItem => Name::ListItem,
+                Container::DefinitionDescription => Name::DefinitionDescription,
             };
 
             exits.push(Event {
@@ -628,4 +711,9 @@ fn resolve(tokenizer: &mut Tokenizer) {
         .tokenize_state
         .definitions
         .append(&mut child.tokenize_state.definitions.split_off(0));
+
+    tokenizer
+        .tokenize_state
+        .abbreviations
+        .append(&mut child.tokenize_state.abbreviations.split_off(0));
 }
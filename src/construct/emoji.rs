@@ -0,0 +1,137 @@
+//! Emoji occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Emoji form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: not preceded by an ASCII alphanumeric.
+//! ; Restriction: the shortcode must be known to `emoji_lookup`, if given.
+//! emoji ::= ':' 1*(ascii_alphanumeric | '+' | '-' | '_') ':'
+//! ```
+//!
+//! Emoji are shortcodes, such as `:tada:`, that stand for an emoji.
+//! Which shortcodes exist differs between platforms, so this crate does not
+//! know any.
+//! Pass [`emoji_lookup`][crate::ParseOptions::emoji_lookup] to turn
+//! shortcodes into emoji, in which case shortcodes that it does not know are
+//! not emoji.
+//! Without it, every shortcode is an emoji, which is kept as it is.
+//!
+//! ## HTML
+//!
+//! Emoji do not relate to anything in HTML.
+//! When [`emoji_lookup`][crate::ParseOptions::emoji_lookup] is passed, the
+//! value it gives is output, otherwise the shortcode (with colons) is.
+//!
+//! ## Recommendation
+//!
+//! Emoji are not supported in most places that render markdown.
+//! When the markdown is also read elsewhere, they show up as text.
+//! It is recommended to use actual emoji instead, if possible.
+//!
+//! ## Tokens
+//!
+//! *   [`Emoji`][Name::Emoji]
+//! *   [`EmojiMarker`][Name::EmojiMarker]
+//! *   [`EmojiShortcode`][Name::EmojiShortcode]
+//!
+//! ## References
+//!
+//! *   [*Using emoji* on `docs.github.com`](https://docs.github.com/en/get-started/writing-on-github/getting-started-with-writing-and-formatting-on-github/basic-writing-and-formatting-syntax#using-emoji)
+//! *   [*Extension: `emoji`* in Pandoc](https://pandoc.org/MANUAL.html#extension-emoji)
+//!
+//! [text]: crate::construct::text
+
+use crate::configuration::EmojiLookup;
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::slice::{Position, Slice};
+use alloc::{format, string::String};
+
+/// At start of emoji.
+///
+/// ```markdown
+/// > | a :b: c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.emoji
+        && tokenizer.current == Some(b':')
+        && !matches!(
+            tokenizer.previous,
+            Some(b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z')
+        )
+    {
+        tokenizer.enter(Name::Emoji);
+        tokenizer.enter(Name::EmojiMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::EmojiMarker);
+        State::Next(StateName::EmojiShortcodeBefore)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `:`, at shortcode.
+///
+/// ```markdown
+/// > | a :b: c
+///        ^
+/// ```
+pub fn shortcode_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'+' | b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.enter(Name::EmojiShortcode);
+            State::Retry(StateName::EmojiShortcode)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// In shortcode.
+///
+/// ```markdown
+/// > | a :b: c
+///        ^
+/// ```
+pub fn shortcode(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'+' | b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.consume();
+            State::Next(StateName::EmojiShortcode)
+        }
+        Some(b':') => {
+            tokenizer.exit(Name::EmojiShortcode);
+
+            if let Some(emoji_lookup) = &tokenizer.parse_state.options.emoji_lookup {
+                let slice = Slice::from_position(
+                    tokenizer.parse_state.bytes,
+                    &Position::from_exit_event(&tokenizer.events, tokenizer.events.len() - 1),
+                    tokenizer.parse_state.options.tab_size,
+                );
+
+                if emoji_lookup(slice.as_str()).is_none() {
+                    return State::Nok;
+                }
+            }
+
+            tokenizer.enter(Name::EmojiMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::EmojiMarker);
+            tokenizer.exit(Name::Emoji);
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
+
+/// Get the value of the emoji with `shortcode`: what `emoji_lookup` gives, or
+/// the shortcode with colons.
+pub fn value(shortcode: &str, emoji_lookup: Option<&EmojiLookup>) -> String {
+    emoji_lookup
+        .and_then(|emoji_lookup| emoji_lookup(shortcode))
+        .unwrap_or_else(|| format!(":{}:", shortcode))
+}
@@ -0,0 +1,91 @@
+//! Extensions occur in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Extensions form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: `marker` is one of the markers of an extension.
+//! ; Restriction: the whole is matched by the `tokenize` function of that
+//! ; extension.
+//! extension ::= marker *line
+//! ```
+//!
+//! Extensions are defined outside of `markdown-rs`, with an
+//! [`Extension`][crate::Extension] in
+//! [`extensions`][crate::ParseOptions::extensions].
+//! At each of their markers, extensions are tried in order, before the
+//! constructs of `markdown-rs`.
+//! The first extension whose `tokenize` function matches the rest of the
+//! line is used.
+//!
+//! Extensions cannot contain line endings, and their source is not parsed as
+//! markdown.
+//!
+//! ## HTML
+//!
+//! Extensions do not relate to anything in HTML on their own.
+//! They are turned into HTML with the `to_html` function of the extension.
+//!
+//! ## Recommendation
+//!
+//! Extensions are not supported elsewhere.
+//! When the markdown is also read elsewhere, the source of the extensions
+//! shows up.
+//!
+//! ## Tokens
+//!
+//! *   [`Extension`][Name::Extension]
+//!
+//! ## References
+//!
+//! *   [*Creating a micromark extension* in `micromark`](https://github.com/micromark/micromark#creating-a-micromark-extension)
+//!
+//! [text]: crate::construct::text
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{extension::find, slice::rest_of_line};
+
+/// Start of extension.
+///
+/// ```markdown
+/// > | a @b c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    let index = tokenizer.point.index;
+
+    if let Some((extension, size)) = find(
+        &tokenizer.parse_state.options.extensions,
+        rest_of_line(tokenizer.parse_state.bytes, index),
+    ) {
+        tokenizer.tokenize_state.size = extension;
+        tokenizer.tokenize_state.end = index + size;
+        tokenizer.enter(Name::Extension(extension));
+        State::Retry(StateName::ExtensionInside)
+    } else {
+        State::Nok
+    }
+}
+
+/// In extension.
+///
+/// ```markdown
+/// > | a @b c
+///       ^^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        tokenizer.consume();
+        State::Next(StateName::ExtensionInside)
+    } else {
+        let extension = tokenizer.tokenize_state.size;
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.tokenize_state.end = 0;
+        tokenizer.exit(Name::Extension(extension));
+        State::Ok
+    }
+}
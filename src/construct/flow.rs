@@ -10,8 +10,10 @@
 //!
 //! The constructs found in flow are:
 //!
+//! *   [Abbreviation definition][crate::construct::abbreviation_definition]
 //! *   [Blank line][crate::construct::blank_line]
 //! *   [Code (indented)][crate::construct::code_indented]
+//! *   [Grid table][crate::construct::grid_table]
 //! *   [Heading (atx)][crate::construct::heading_atx]
 //! *   [Heading (setext)][crate::construct::heading_setext]
 //! *   [HTML (flow)][crate::construct::html_flow]
@@ -51,12 +53,20 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::RawFlowStart)
         }
+        // Note: a GFM table can start with an escape, so it’s tried next.
+        Some(b'\\') => {
+            tokenizer.attempt(
+                State::Next(StateName::FlowAfter),
+                State::Next(StateName::FlowBeforeGfmTable),
+            );
+            State::Retry(StateName::RawFlowStart)
+        }
         // Note: `-` is also used in setext heading underline so it’s not
         // included here.
         Some(b'*' | b'_') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
-                State::Next(StateName::FlowBeforeContent),
+                State::Next(StateName::FlowBeforeAbbreviationDefinition),
             );
             State::Retry(StateName::ThematicBreakStart)
         }
@@ -196,11 +206,25 @@ pub fn before_heading_setext(tokenizer: &mut Tokenizer) -> State {
 pub fn before_thematic_break(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(
         State::Next(StateName::FlowAfter),
-        State::Next(StateName::FlowBeforeMdxExpression),
+        State::Next(StateName::FlowBeforeAbbreviationDefinition),
     );
     State::Retry(StateName::ThematicBreakStart)
 }
 
+/// At abbreviation definition.
+///
+/// ```markdown
+/// > | *[HTML]: Hypertext Markup Language
+///     ^
+/// ```
+pub fn before_abbreviation_definition(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::FlowAfter),
+        State::Next(StateName::FlowBeforeMdxExpression),
+    );
+    State::Retry(StateName::AbbreviationDefinitionStart)
+}
+
 /// At MDX expression (flow).
 ///
 /// ```markdown
@@ -210,11 +234,25 @@ pub fn before_thematic_break(tokenizer: &mut Tokenizer) -> State {
 pub fn before_mdx_expression(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(
         State::Next(StateName::FlowAfter),
-        State::Next(StateName::FlowBeforeGfmTable),
+        State::Next(StateName::FlowBeforeGridTable),
     );
     State::Retry(StateName::MdxExpressionFlowStart)
 }
 
+/// At grid table.
+///
+/// ```markdown
+/// > | +---+
+///     ^
+/// ```
+pub fn before_grid_table(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::FlowAfter),
+        State::Next(StateName::FlowBeforeGfmTable),
+    );
+    State::Retry(StateName::GridTableStart)
+}
+
 /// At GFM table.
 ///
 /// ```markdown
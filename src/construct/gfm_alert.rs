@@ -0,0 +1,191 @@
+//! GFM: alert occurs at the start of [block quotes][block_quote].
+//!
+//! ## Grammar
+//!
+//! Alerts form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! gfm_alert ::= '[!' alert_kind ']' *space_or_tab ( eol | eof )
+//!
+//! ; Restriction: case-insensitive.
+//! alert_kind ::= 'NOTE' | 'TIP' | 'IMPORTANT' | 'WARNING' | 'CAUTION'
+//! ```
+//!
+//! The alert is only allowed on the first line of the first paragraph in a
+//! block quote, and must be the only thing on that line.
+//! The rest of the paragraph, if any, stays a paragraph.
+//!
+//! Alerts are not parsed by a state machine: the first line of a paragraph
+//! is parsed as normal text first, and turned into an alert afterwards, when
+//! all events are known.
+//!
+//! ## HTML
+//!
+//! GitHub turns block quotes with alerts into `<div>` elements with a title,
+//! instead of `<blockquote>` elements, such as:
+//!
+//! ```html
+//! <div class="markdown-alert markdown-alert-note">
+//! <p class="markdown-alert-title">Note</p>
+//! <p>a</p>
+//! </div>
+//! ```
+//!
+//! ## Recommendation
+//!
+//! Use uppercase kinds (`[!NOTE]` instead of `[!note]`), as that is what
+//! GitHub documents.
+//! Do not use alerts to emphasize every block quote: they lose their effect
+//! when used too often.
+//!
+//! ## Tokens
+//!
+//! *   [`GfmAlert`][Name::GfmAlert]
+//!
+//! ## References
+//!
+//! *   [*Alerts* in GitHub Docs](https://docs.github.com/en/get-started/writing-on-github/getting-started-with-writing-and-formatting-on-github/basic-writing-and-formatting-syntax#alerts)
+//!
+//! [block_quote]: crate::construct::block_quote
+
+use crate::event::{Event, Kind, Name};
+use crate::mdast::AlertKind;
+use crate::util::{edit_map::EditMap, skip};
+use alloc::{vec, vec::Vec};
+
+/// Turn the first lines of block quotes that are alert markers into alerts.
+pub fn resolve(events: &mut Vec<Event>, bytes: &[u8]) {
+    let mut map = EditMap::new();
+    let mut index = 0;
+
+    while index < events.len() {
+        if events[index].kind == Kind::Enter && events[index].name == Name::BlockQuote {
+            let paragraph = skip::opt(
+                events,
+                index + 1,
+                &[Name::BlockQuotePrefix, Name::SpaceOrTab],
+            );
+
+            if paragraph < events.len()
+                && events[paragraph].kind == Kind::Enter
+                && events[paragraph].name == Name::Paragraph
+            {
+                resolve_paragraph(&mut map, events, bytes, paragraph);
+            }
+        }
+
+        index += 1;
+    }
+
+    map.consume(events);
+}
+
+/// Turn the first line of the paragraph entered at `paragraph` into an
+/// alert, if it is an alert marker.
+fn resolve_paragraph(map: &mut EditMap, events: &[Event], bytes: &[u8], paragraph: usize) {
+    let start = &events[paragraph].point;
+    let size = match marker_size(&bytes[start.index..]) {
+        Some(size) => size,
+        None => return,
+    };
+    let exit = skip::to(events, paragraph + 1, &[Name::Paragraph]);
+    let mut eol = start.index + size;
+
+    while eol < bytes.len() && matches!(bytes[eol], b'\t' | b' ') {
+        eol += 1;
+    }
+
+    let mut end = start.clone();
+    end.index += size;
+    end.column += size;
+    let mut add = vec![
+        Event {
+            kind: Kind::Enter,
+            name: Name::GfmAlert,
+            point: start.clone(),
+            link: None,
+        },
+        Event {
+            kind: Kind::Exit,
+            name: Name::GfmAlert,
+            point: end,
+            link: None,
+        },
+    ];
+
+    // Only the marker.
+    if eol >= events[exit].point.index {
+        map.add(paragraph, exit - paragraph + 1, add);
+        return;
+    }
+
+    let mut line_ending = paragraph + 1;
+
+    while line_ending < exit
+        && !(events[line_ending].kind == Kind::Enter
+            && matches!(
+                events[line_ending].name,
+                Name::LineEnding | Name::HardBreakLineEnding
+            )
+            && events[line_ending].point.index == eol)
+    {
+        line_ending += 1;
+    }
+
+    // Something else on the first line, such as an escape.
+    if line_ending == exit {
+        return;
+    }
+
+    // The rest of the paragraph starts after the prefixes of containers.
+    let rest = skip::opt(
+        events,
+        line_ending + 2,
+        &[Name::BlockQuotePrefix, Name::SpaceOrTab],
+    );
+    let mut enter = events[paragraph].clone();
+    enter.point = events[rest].point.clone();
+
+    // The line ending is not a hard break (from `breaks`) after the marker.
+    let mut line_ending_enter = events[line_ending].clone();
+    let mut line_ending_exit = events[line_ending + 1].clone();
+    line_ending_enter.name = Name::LineEnding;
+    line_ending_exit.name = Name::LineEnding;
+    add.push(line_ending_enter);
+    add.push(line_ending_exit);
+    map.add(paragraph, line_ending + 2 - paragraph, add);
+    map.add(rest, 0, vec![enter]);
+}
+
+/// Get the size of the alert marker at the start of `bytes`, if there is one
+/// followed by whitespace and a line ending (or the end).
+fn marker_size(bytes: &[u8]) -> Option<usize> {
+    if !bytes.starts_with(b"[!") {
+        return None;
+    }
+
+    let close = bytes.iter().position(|byte| *byte == b']')?;
+    let name = core::str::from_utf8(&bytes[2..close]).ok()?;
+    AlertKind::from_name(name)?;
+    let mut index = close + 1;
+
+    while index < bytes.len() && matches!(bytes[index], b'\t' | b' ') {
+        index += 1;
+    }
+
+    if index == bytes.len() || matches!(bytes[index], b'\n' | b'\r') {
+        Some(close + 1)
+    } else {
+        None
+    }
+}
+
+/// Get the kind of the alert whose marker is `value` (such as `[!NOTE]`).
+pub fn kind(value: &str) -> AlertKind {
+    value
+        .strip_prefix("[!")
+        .and_then(|value| value.strip_suffix(']'))
+        .and_then(AlertKind::from_name)
+        .expect("expected alert marker")
+}
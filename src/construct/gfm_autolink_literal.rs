@@ -151,7 +151,7 @@ use crate::util::{
     char::{kind_after_index, Kind as CharacterKind},
     slice::{Position, Slice},
 };
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 /// Start of protocol autolink literal.
 ///
@@ -160,12 +160,17 @@ use alloc::vec::Vec;
 ///     ^
 /// ```
 pub fn protocol_start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer
-        .parse_state
-        .options
-        .constructs
-        .gfm_autolink_literal &&
-        matches!(tokenizer.current, Some(b'H' | b'h'))
+    let options = &tokenizer.parse_state.options;
+
+    if options.constructs.gfm_autolink_literal
+        && tokenizer.current.map_or(false, |byte| {
+            options.gfm_autolink_literal_protocols.iter().any(|protocol| {
+                protocol
+                    .as_bytes()
+                    .first()
+                    .map_or(false, |first| first.eq_ignore_ascii_case(&byte))
+            })
+        })
             // Source: <https://github.com/github/cmark-gfm/blob/ef1cfcb/extensions/autolink.c#L214>.
             && !matches!(tokenizer.previous, Some(b'A'..=b'Z' | b'a'..=b'z'))
     {
@@ -203,10 +208,13 @@ pub fn protocol_after(tokenizer: &mut Tokenizer) -> State {
 ///     ^^^^^
 /// ```
 pub fn protocol_prefix_inside(tokenizer: &mut Tokenizer) -> State {
+    let protocols = &tokenizer.parse_state.options.gfm_autolink_literal_protocols;
+
     match tokenizer.current {
-        Some(b'A'..=b'Z' | b'a'..=b'z')
-            // `5` is size of `https`
-            if tokenizer.point.index - tokenizer.tokenize_state.start < 5 =>
+        Some(b'+' | b'-' | b'.' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z')
+            // Longest protocol (such as `5` for `https`).
+            if tokenizer.point.index - tokenizer.tokenize_state.start
+                < protocols.iter().map(String::len).max().unwrap_or(0) =>
         {
             tokenizer.consume();
             State::Next(StateName::GfmAutolinkLiteralProtocolPrefixInside)
@@ -217,11 +225,14 @@ pub fn protocol_prefix_inside(tokenizer: &mut Tokenizer) -> State {
                 tokenizer.tokenize_state.start,
                 tokenizer.point.index,
             );
-            let name = slice.as_str().to_ascii_lowercase();
+            let name = slice.as_str();
 
             tokenizer.tokenize_state.start = 0;
 
-            if name == "http" || name == "https" {
+            if protocols
+                .iter()
+                .any(|protocol| protocol.eq_ignore_ascii_case(name))
+            {
                 tokenizer.consume();
                 State::Next(StateName::GfmAutolinkLiteralProtocolSlashesInside)
             } else {
@@ -264,12 +275,11 @@ pub fn protocol_slashes_inside(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn www_start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer
-        .parse_state
-        .options
-        .constructs
-        .gfm_autolink_literal &&
-        matches!(tokenizer.current, Some(b'W' | b'w'))
+    let options = &tokenizer.parse_state.options;
+
+    if options.constructs.gfm_autolink_literal
+        && options.gfm_autolink_literal_www
+        && matches!(tokenizer.current, Some(b'W' | b'w'))
             // Source: <https://github.com/github/cmark-gfm/blob/ef1cfcb/extensions/autolink.c#L156>.
             && matches!(tokenizer.previous, None | Some(b'\t' | b'\n' | b' ' | b'(' | b'*' | b'_' | b'[' | b']' | b'~'))
     {
@@ -612,14 +622,43 @@ pub fn trail_char_ref_inside(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
-/// Resolve: postprocess text to find email autolink literals.
+/// Resolve: postprocess text to find email autolink literals, and to turn
+/// vetoed autolink literals into data.
 pub fn resolve(tokenizer: &mut Tokenizer) {
     tokenizer.map.consume(&mut tokenizer.events);
 
+    let filter = tokenizer
+        .parse_state
+        .options
+        .gfm_autolink_literal_filter
+        .as_deref();
     let mut index = 0;
     let mut links = 0;
 
     while index < tokenizer.events.len() {
+        // Turn vetoed protocol and www autolink literals into data.
+        if let Some(filter) = filter {
+            let event = &tokenizer.events[index];
+
+            if event.kind == Kind::Exit
+                && matches!(
+                    event.name,
+                    Name::GfmAutolinkLiteralProtocol | Name::GfmAutolinkLiteralWww
+                )
+            {
+                let slice = Slice::from_position(
+                    tokenizer.parse_state.bytes,
+                    &Position::from_exit_event(&tokenizer.events, index),
+                    tokenizer.parse_state.options.tab_size,
+                );
+
+                if !filter(slice.as_str()) {
+                    tokenizer.events[index - 1].name = Name::Data;
+                    tokenizer.events[index].name = Name::Data;
+                }
+            }
+        }
+
         let event = &tokenizer.events[index];
 
         if event.kind == Kind::Enter {
@@ -631,6 +670,7 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                 let slice = Slice::from_position(
                     tokenizer.parse_state.bytes,
                     &Position::from_exit_event(&tokenizer.events, index),
+                    tokenizer.parse_state.options.tab_size,
                 );
                 let bytes = slice.bytes;
                 let mut byte_index = 0;
@@ -659,7 +699,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                 // trailing.
                                 // So we can ignore truncating while
                                 // postprocessing!
-                                range = (start, end, kind);
+                                if filter.map_or(true, |filter| {
+                                    filter(Slice::from_indices(bytes, start, end).as_str())
+                                }) {
+                                    range = (start, end, kind);
+                                }
                             }
                         }
 
@@ -675,8 +719,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                     point: point.clone(),
                                     link: None,
                                 });
-                                point = point
-                                    .shift_to(tokenizer.parse_state.bytes, start_index + range.0);
+                                point = point.shift_to(
+                                    tokenizer.parse_state.bytes,
+                                    start_index + range.0,
+                                    tokenizer.parse_state.options.tab_size,
+                                );
                                 replace.push(Event {
                                     kind: Kind::Exit,
                                     name: Name::Data,
@@ -692,8 +739,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                 point: point.clone(),
                                 link: None,
                             });
-                            point =
-                                point.shift_to(tokenizer.parse_state.bytes, start_index + range.1);
+                            point = point.shift_to(
+                                tokenizer.parse_state.bytes,
+                                start_index + range.1,
+                                tokenizer.parse_state.options.tab_size,
+                            );
                             replace.push(Event {
                                 kind: Kind::Exit,
                                 name: range.2.clone(),
@@ -340,6 +340,7 @@ pub fn label_after(tokenizer: &mut Tokenizer) -> State {
                 Slice::from_position(
                     tokenizer.parse_state.bytes,
                     &Position::from_exit_event(&tokenizer.events, end),
+                    tokenizer.parse_state.options.tab_size,
                 )
                 .as_str(),
             );
@@ -18,6 +18,11 @@
 //! gfm_table_delimiter_row ::= ['|'] gfm_table_delimiter_cell 0*('|' gfm_table_delimiter_cell) ['|'] *space_or_tab
 //! gfm_table_delimiter_cell ::= *space_or_tab gfm_table_delimiter_value *space_or_tab
 //! gfm_table_delimiter_value ::= [':'] 1*'-' [':']
+//!
+//! ; Restriction: only directly after a table head or row.
+//! ; Restriction: `gfm_table_caption_text` cannot contain `]` in brackets.
+//! gfm_table_caption ::= *space_or_tab ( ( 'Table:' | 'Caption:' ) *space_or_tab gfm_table_caption_text | '[' *space_or_tab gfm_table_caption_text *space_or_tab ']' ) *space_or_tab
+//! gfm_table_caption_text ::= 1*line
 //! ```
 //!
 //! As this construct occurs in flow, like all flow constructs, it must be
@@ -80,7 +85,21 @@
 //! </table>
 //! ```
 //!
-//! Each cell’s text is interpreted as the [text][] content type.
+//! When table captions are turned on (which is not GFM), a table can be
+//! followed by a caption: a line starting with `Table:` or `Caption:`, or a
+//! line in brackets (`[Caption]`), directly after the last row.
+//! The caption ends the table.
+//! To illustrate:
+//!
+//! ```markdown
+//! | a |
+//! | - |
+//! | b |
+//! Table: c
+//! ```
+//!
+//! Each cell’s text, and the text of the caption, is interpreted as the
+//! [text][] content type.
 //! That means that it can include constructs such as [attention][attention].
 //!
 //! The grammar for cells prohibits the use of `|` in them.
@@ -145,6 +164,11 @@
 //! [*§ 4.9.8 The `tr` element*][html_tr]
 //! in the HTML spec for more info.
 //!
+//! Captions relate to the `<caption>` element, which is the first child of
+//! the `<table>` element.
+//! See [*§ 4.9.2 The `caption` element*][html_caption] in the HTML spec for
+//! more info.
+//!
 //! If the alignment of a column is left, right, or center, a deprecated
 //! `align` attribute is added to each `<th>` and `<td>` element belonging to
 //! that column.
@@ -197,6 +221,9 @@
 //!
 //! *   [`GfmTable`][Name::GfmTable]
 //! *   [`GfmTableBody`][Name::GfmTableBody]
+//! *   [`GfmTableCaption`][Name::GfmTableCaption]
+//! *   [`GfmTableCaptionMarker`][Name::GfmTableCaptionMarker]
+//! *   [`GfmTableCaptionText`][Name::GfmTableCaptionText]
 //! *   [`GfmTableCell`][Name::GfmTableCell]
 //! *   [`GfmTableCellDivider`][Name::GfmTableCellDivider]
 //! *   [`GfmTableCellText`][Name::GfmTableCellText]
@@ -213,12 +240,14 @@
 //!
 //! *   [`micromark-extension-gfm-table`](https://github.com/micromark/micromark-extension-gfm-table)
 //! *   [*§ 4.10 Tables (extension)* in `GFM`](https://github.github.com/gfm/#tables-extension-)
+//! *   [*Table captions* in Pandoc](https://pandoc.org/MANUAL.html#extension-table_captions)
 //!
 //! [flow]: crate::construct::flow
 //! [text]: crate::construct::text
 //! [attention]: crate::construct::attention
 //! [raw_text]: crate::construct::raw_text
 //! [html_table]: https://html.spec.whatwg.org/multipage/tables.html#the-table-element
+//! [html_caption]: https://html.spec.whatwg.org/multipage/tables.html#the-caption-element
 //! [html_tbody]: https://html.spec.whatwg.org/multipage/tables.html#the-tbody-element
 //! [html_td]: https://html.spec.whatwg.org/multipage/tables.html#the-td-element
 //! [html_th]: https://html.spec.whatwg.org/multipage/tables.html#the-th-element
@@ -231,13 +260,13 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, skip::opt_back as skip_opt_back};
+use crate::util::{constant::TAB_SIZE, skip::opt_back as skip_opt_back, slice::rest_of_line};
 use alloc::vec;
 
 /// Start of a GFM table.
 ///
 /// If there is a valid table row or table head before, then we try to parse
-/// another row.
+/// a caption (if turned on) or another row.
 /// Otherwise, we try to parse a head.
 ///
 /// ```markdown
@@ -246,6 +275,8 @@ use alloc::vec;
 ///   | | - |
 /// > | | b |
 ///     ^
+/// > | Table: c
+///     ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
     if tokenizer.parse_state.options.constructs.gfm_table {
@@ -261,7 +292,12 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 Name::GfmTableHead | Name::GfmTableRow
             )
         {
-            State::Retry(StateName::GfmTableBodyRowStart)
+            if tokenizer.parse_state.options.constructs.gfm_table_caption {
+                tokenizer.attempt(State::Ok, State::Next(StateName::GfmTableBodyRowStart));
+                State::Retry(StateName::GfmTableCaptionStart)
+            } else {
+                State::Retry(StateName::GfmTableBodyRowStart)
+            }
         } else {
             State::Retry(StateName::GfmTableHeadRowBefore)
         }
@@ -767,6 +803,170 @@ pub fn body_row_escape(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// Before table caption.
+///
+/// ```markdown
+///   | | a |
+///   | | - |
+/// > | Table: b
+///     ^
+/// ```
+pub fn caption_start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.lazy {
+        State::Nok
+    } else {
+        tokenizer.enter(Name::GfmTableCaption);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::GfmTableCaptionOpen), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    TAB_SIZE - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::GfmTableCaptionOpen)
+        }
+    }
+}
+
+/// Before table caption, after whitespace.
+///
+/// ```markdown
+///   | | a |
+///   | | - |
+/// > | Table: b
+///     ^
+/// ```
+pub fn caption_open(tokenizer: &mut Tokenizer) -> State {
+    let index = tokenizer.point.index;
+    let line = rest_of_line(tokenizer.parse_state.bytes, index).trim_end_matches(is_space_or_tab);
+    let (open, close, text) = if let Some(rest) = line
+        .strip_prefix("Table:")
+        .or_else(|| line.strip_prefix("Caption:"))
+    {
+        (line.len() - rest.len(), 0, rest)
+    } else if line.len() > 1 && line.starts_with('[') && line.ends_with(']') {
+        (1, b']', &line[1..line.len() - 1])
+    } else {
+        return State::Nok;
+    };
+
+    if text.trim_start_matches(is_space_or_tab).is_empty() || (close != 0 && text.contains(']')) {
+        State::Nok
+    } else {
+        tokenizer.tokenize_state.size = open;
+        tokenizer.tokenize_state.end = index + open + text.trim_end_matches(is_space_or_tab).len();
+        tokenizer.tokenize_state.marker = close;
+        tokenizer.enter(Name::GfmTableCaptionMarker);
+        State::Retry(StateName::GfmTableCaptionMarker)
+    }
+}
+
+/// In opening table caption marker.
+///
+/// ```markdown
+///   | | a |
+///   | | - |
+/// > | Table: b
+///     ^^^^^^
+/// ```
+pub fn caption_marker(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.tokenize_state.size > 0 {
+        tokenizer.tokenize_state.size -= 1;
+        tokenizer.consume();
+        State::Next(StateName::GfmTableCaptionMarker)
+    } else {
+        tokenizer.exit(Name::GfmTableCaptionMarker);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::GfmTableCaptionTextBefore),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::GfmTableCaptionTextBefore)
+        }
+    }
+}
+
+/// Before table caption text.
+///
+/// ```markdown
+///   | | a |
+///   | | - |
+/// > | Table: b
+///            ^
+/// ```
+pub fn caption_text_before(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::GfmTableCaptionText);
+    tokenizer.enter_link(
+        Name::Data,
+        Link {
+            previous: None,
+            next: None,
+            content: Content::Text,
+        },
+    );
+    State::Retry(StateName::GfmTableCaptionText)
+}
+
+/// In table caption text.
+///
+/// ```markdown
+///   | | a |
+///   | | - |
+/// > | Table: b
+///            ^
+/// ```
+pub fn caption_text(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        tokenizer.consume();
+        State::Next(StateName::GfmTableCaptionText)
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        tokenizer.exit(Name::Data);
+        tokenizer.exit(Name::GfmTableCaptionText);
+        State::Retry(StateName::GfmTableCaptionAfter)
+    }
+}
+
+/// After table caption text.
+///
+/// ```markdown
+///   | | a |
+///   | | - |
+/// > | Table: b
+///             ^
+/// > | [c]
+///       ^
+/// ```
+pub fn caption_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::GfmTableCaption);
+            State::Ok
+        }
+        Some(b'\t' | b' ') => {
+            tokenizer.attempt(State::Next(StateName::GfmTableCaptionAfter), State::Nok);
+            State::Retry(space_or_tab(tokenizer))
+        }
+        _ => {
+            debug_assert_eq!(tokenizer.tokenize_state.marker, b']', "expected `]`");
+            tokenizer.tokenize_state.marker = 0;
+            tokenizer.enter(Name::GfmTableCaptionMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::GfmTableCaptionMarker);
+            State::Next(StateName::GfmTableCaptionAfter)
+        }
+    }
+}
+
 /// Resolve GFM table.
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     let mut index = 0;
@@ -802,6 +1002,20 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                     link: None,
                 };
                 tokenizer.map.add(index, 0, vec![enter]);
+            } else if event.name == Name::GfmTableCaption {
+                after_head_awaiting_first_body_row = false;
+
+                // Inject table body end, as the caption is after the body.
+                if last_table_has_body {
+                    last_table_has_body = false;
+                    let exit = Event {
+                        kind: Kind::Exit,
+                        name: Name::GfmTableBody,
+                        point: tokenizer.events[last_table_end].point.clone(),
+                        link: None,
+                    };
+                    tokenizer.map.add(last_table_end + 1, 0, vec![exit]);
+                }
             } else if matches!(event.name, Name::GfmTableRow | Name::GfmTableDelimiterRow) {
                 in_delimiter_row = event.name == Name::GfmTableDelimiterRow;
                 in_row = true;
@@ -855,6 +1069,8 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                 }
             }
         // Exit events.
+        } else if event.name == Name::GfmTableCaption {
+            last_table_end = index;
         } else if event.name == Name::GfmTableHead {
             after_head_awaiting_first_body_row = true;
             last_table_end = index;
@@ -887,6 +1103,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     None
 }
 
+/// Check whether `char` is a space or tab.
+fn is_space_or_tab(char: char) -> bool {
+    matches!(char, '\t' | ' ')
+}
+
 /// Generate a cell.
 fn flush_cell(
     tokenizer: &mut Tokenizer,
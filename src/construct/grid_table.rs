@@ -0,0 +1,705 @@
+//! Grid table occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Grid tables form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! grid_table ::= grid_table_border eol grid_table_row eol grid_table_head_border 0*(eol grid_table_row eol grid_table_border)
+//!
+//! ; Restriction: all borders must have their `+` at the same columns as the
+//! ; first border.
+//! grid_table_border ::= '+' 1*(grid_table_border_cell '+') *space_or_tab
+//! grid_table_border_cell ::= [':'] 1*'-' [':']
+//! grid_table_head_border ::= '+' 1*(grid_table_head_border_cell '+') *space_or_tab
+//! grid_table_head_border_cell ::= [':'] 1*'=' [':']
+//!
+//! ; Restriction: each line must have a `|` at the columns where the borders
+//! ; have a `+`.
+//! grid_table_row ::= grid_table_row_line 0*(eol grid_table_row_line)
+//! grid_table_row_line ::= '|' 1*(1*line '|') *space_or_tab
+//! ```
+//!
+//! As this construct occurs in flow, like all flow constructs, it must be
+//! followed by an eol (line ending) or eof (end of file).
+//!
+//! Grid tables, unlike [GFM tables][gfm_table], are drawn with borders
+//! around each row, so that rows can span multiple lines.
+//! Each cell contains [flow][], such as paragraphs, lists, and code, which is
+//! parsed as a separate document: the parts of the lines between the
+//! dividers (`|`) are joined with line endings.
+//! When all lines of a cell start with a space, that space is removed.
+//! Whitespace at the end of each line of a cell is removed.
+//! Definitions in the document can be referenced in cells, but definitions
+//! in a cell can only be referenced in that cell.
+//!
+//! The first row is the head, which is followed by a border made with equals
+//! signs (`=`) instead of dashes (`-`).
+//! The head is required.
+//! Cells cannot span multiple columns or rows.
+//! Grid tables cannot be indented and cannot interrupt paragraphs.
+//!
+//! The columns of the table are found by the position of `+` in the first
+//! border.
+//! Characters are measured by how many columns they take up in a monospace
+//! font: CJK characters and emoji take up two columns.
+//! Tabs take up one column.
+//!
+//! Each column can be marked with an alignment.
+//! The alignment marker is a colon (`:`) used before and/or after the filler
+//! in the head border.
+//! To illustrate:
+//!
+//! ```markdown
+//! +------+------+-------+--------+
+//! | none | left | right | center |
+//! +======+:=====+======:+:======:+
+//! | a    | b    | c     | d      |
+//! +------+------+-------+--------+
+//! ```
+//!
+//! ## HTML
+//!
+//! Grid tables relate to the `<table>` element in HTML, in the same way as
+//! [GFM tables][gfm_table].
+//! When a cell contains just one paragraph, the paragraph is not wrapped in
+//! a `<p>` element.
+//!
+//! ## Recommendation
+//!
+//! Grid tables are not supported in most places that render markdown.
+//! When block content in cells is not needed, use [GFM tables][gfm_table]
+//! instead.
+//!
+//! ## Tokens
+//!
+//! *   [`GridTable`][Name::GridTable]
+//! *   [`GridTableBody`][Name::GridTableBody]
+//! *   [`GridTableBorder`][Name::GridTableBorder]
+//! *   [`GridTableCell`][Name::GridTableCell]
+//! *   [`GridTableHead`][Name::GridTableHead]
+//! *   [`GridTableRow`][Name::GridTableRow]
+//!
+//! ## References
+//!
+//! *   [*Grid tables* in Pandoc](https://pandoc.org/MANUAL.html#extension-grid_tables)
+//!
+//! [flow]: crate::construct::flow
+//! [gfm_table]: crate::construct::gfm_table
+
+use crate::event::{Event, Kind, Name, Point};
+use crate::mdast::AlignKind;
+use crate::message;
+use crate::parser::{parse_in, ParseState};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::unist;
+use crate::util::{
+    char::width,
+    edit_map::EditMap,
+    skip,
+    slice::{rest_of_line, Slice},
+};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+/// A line of a cell.
+#[derive(Debug)]
+struct CellLine {
+    /// Place in the document where the line starts.
+    start: Point,
+    /// Index in the cell where the line starts.
+    offset: usize,
+    /// Places in the document where the line ending after the line starts
+    /// and ends.
+    line_ending: (Point, Point),
+}
+
+/// Start of grid table.
+///
+/// ```markdown
+/// > | +---+
+///     ^
+///   | | a |
+///   | +===+
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.grid_table
+        && !tokenizer.interrupt
+        && tokenizer.current == Some(b'+')
+    {
+        let index = tokenizer.point.index;
+        let line = rest_of_line(tokenizer.parse_state.bytes, index);
+
+        if matches!(border(line), Some((b'-', _))) {
+            tokenizer.tokenize_state.start = index;
+            tokenizer.tokenize_state.size_b = line.len();
+            tokenizer.tokenize_state.marker = b'-';
+            tokenizer.enter(Name::GridTable);
+            return State::Retry(StateName::GridTableLineStart);
+        }
+    }
+
+    State::Nok
+}
+
+/// At start of a border or a line of a row.
+///
+/// ```markdown
+/// > | +---+
+///     ^
+/// > | | a |
+///     ^
+/// ```
+pub fn line_start(tokenizer: &mut Tokenizer) -> State {
+    let index = tokenizer.point.index;
+    tokenizer.tokenize_state.end = index + rest_of_line(tokenizer.parse_state.bytes, index).len();
+
+    if tokenizer.tokenize_state.marker == b'|' {
+        tokenizer.enter(Name::GridTableRow);
+    } else {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.enter(Name::GridTableBorder);
+    }
+
+    State::Retry(StateName::GridTableLineInside)
+}
+
+/// In a border or a line of a row.
+///
+/// ```markdown
+/// > | +---+
+///     ^^^^^
+/// > | | a |
+///     ^^^^^
+/// ```
+pub fn line_inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        tokenizer.consume();
+        State::Next(StateName::GridTableLineInside)
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        tokenizer.exit(if tokenizer.tokenize_state.marker == b'|' {
+            Name::GridTableRow
+        } else {
+            Name::GridTableBorder
+        });
+        tokenizer.attempt(
+            State::Next(StateName::GridTableLineStart),
+            State::Next(StateName::GridTableAfter),
+        );
+        State::Retry(StateName::GridTableContinuationStart)
+    }
+}
+
+/// At eol, before a border or a line of a row.
+///
+/// ```markdown
+/// > | +---+
+///          ^
+///   | | a |
+/// ```
+pub fn continuation_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'\n') => {
+            tokenizer.enter(Name::LineEnding);
+            tokenizer.consume();
+            tokenizer.exit(Name::LineEnding);
+            State::Next(StateName::GridTableContinuationAfter)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// After eol, at a border or a line of a row.
+///
+/// ```markdown
+///   | +---+
+/// > | | a |
+///     ^
+/// ```
+pub fn continuation_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.lazy {
+        return State::Nok;
+    }
+
+    let bytes = tokenizer.parse_state.bytes;
+    let start = tokenizer.tokenize_state.start;
+    let first = Slice::from_indices(bytes, start, start + tokenizer.tokenize_state.size_b);
+    let marker = tokenizer.tokenize_state.marker;
+    let size = tokenizer.tokenize_state.size;
+
+    match kind(first.as_str(), rest_of_line(bytes, tokenizer.point.index)) {
+        // Head border, after the first row, or body border, after another
+        // row.
+        Some(kind)
+            if kind == b'|'
+                || (marker == b'|'
+                    && ((kind == b'=' && size == 1) || (kind == b'-' && size > 1))) =>
+        {
+            tokenizer.tokenize_state.marker = kind;
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
+
+/// After grid table.
+///
+/// ```markdown
+///   | | a |
+/// > | +---+
+///          ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    // There must be a head, and the last line must be a border.
+    let done = tokenizer.tokenize_state.marker != b'|' && tokenizer.tokenize_state.size > 1;
+    tokenizer.tokenize_state.start = 0;
+    tokenizer.tokenize_state.size_b = 0;
+    tokenizer.tokenize_state.marker = 0;
+    tokenizer.tokenize_state.size = 0;
+
+    if done {
+        tokenizer.exit(Name::GridTable);
+        // Feel free to interrupt.
+        tokenizer.interrupt = false;
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
+
+/// Turn the lines of grid tables into rows and cells.
+///
+/// The cells are parsed as separate documents, whose events are placed in
+/// the cells.
+///
+/// Returns the number of definitions and GFM footnote definitions found in
+/// cells.
+pub fn resolve(
+    events: &mut Vec<Event>,
+    parse_state: &ParseState,
+) -> Result<usize, message::Message> {
+    let mut map = EditMap::new();
+    let mut found = 0;
+    let mut index = 0;
+
+    while index < events.len() {
+        if events[index].kind == Kind::Enter && events[index].name == Name::GridTable {
+            let exit = skip::to(events, index + 1, &[Name::GridTable]);
+            let (add, count) = table(events, index, exit, parse_state)?;
+            map.add(index, exit - index + 1, add);
+            found += count;
+            index = exit;
+        }
+
+        index += 1;
+    }
+
+    map.consume(events);
+    Ok(found)
+}
+
+/// Figure out the alignment of a grid table from its head border.
+pub fn align(value: &str) -> Vec<AlignKind> {
+    let value = value.trim_end_matches(|char| matches!(char, '\t' | ' '));
+    let inner = &value[1..value.len() - 1];
+
+    inner
+        .split('+')
+        .map(|cell| match (cell.starts_with(':'), cell.ends_with(':')) {
+            (true, true) => AlignKind::Center,
+            (true, false) => AlignKind::Left,
+            (false, true) => AlignKind::Right,
+            (false, false) => AlignKind::None,
+        })
+        .collect()
+}
+
+/// Create the events of the grid table between `enter` and `exit`.
+fn table(
+    events: &[Event],
+    enter: usize,
+    exit: usize,
+    parse_state: &ParseState,
+) -> Result<(Vec<Event>, usize), message::Message> {
+    let bytes = parse_state.bytes;
+    let top = enter + 1;
+    // Lines of each row, with the border after it.
+    let mut groups: Vec<(Vec<usize>, usize)> = vec![];
+    let mut lines = vec![];
+    let mut index = top + 2;
+
+    while index < exit {
+        if events[index].kind == Kind::Enter {
+            if events[index].name == Name::GridTableBorder {
+                groups.push((core::mem::take(&mut lines), index));
+            } else if events[index].name == Name::GridTableRow {
+                lines.push(index);
+            }
+        }
+
+        index += 1;
+    }
+
+    let first = Slice::from_indices(bytes, events[top].point.index, events[top + 1].point.index);
+    let (_, places) = border(first.as_str()).unwrap();
+    let mut result = vec![
+        events[enter].clone(),
+        events[top].clone(),
+        events[top + 1].clone(),
+    ];
+    let mut found = 0;
+
+    for (group_index, (lines, border)) in groups.iter().enumerate() {
+        if group_index < 2 {
+            result.push(Event {
+                kind: Kind::Enter,
+                name: if group_index == 0 {
+                    Name::GridTableHead
+                } else {
+                    Name::GridTableBody
+                },
+                point: events[lines[0]].point.clone(),
+                link: None,
+            });
+        }
+
+        found += row(&mut result, events, lines, &places, parse_state)?;
+        result.push(events[*border].clone());
+        result.push(events[border + 1].clone());
+
+        if group_index == 0 {
+            result.push(Event {
+                kind: Kind::Exit,
+                name: Name::GridTableHead,
+                point: events[border + 1].point.clone(),
+                link: None,
+            });
+        }
+    }
+
+    if groups.len() > 1 {
+        result.push(Event {
+            kind: Kind::Exit,
+            name: Name::GridTableBody,
+            point: events[groups[groups.len() - 1].1 + 1].point.clone(),
+            link: None,
+        });
+    }
+
+    result.push(events[exit].clone());
+    Ok((result, found))
+}
+
+/// Add the events of the row made from `lines`.
+///
+/// Returns the number of definitions and GFM footnote definitions found in
+/// its cells.
+fn row(
+    result: &mut Vec<Event>,
+    events: &[Event],
+    lines: &[usize],
+    places: &[usize],
+    parse_state: &ParseState,
+) -> Result<usize, message::Message> {
+    let bytes = parse_state.bytes;
+    let mut starts = vec![];
+    let mut found = 0;
+
+    for enter in lines {
+        let start = &events[*enter].point;
+        let value = Slice::from_indices(bytes, start.index, events[enter + 1].point.index);
+        let dividers = dividers(value.as_str(), places).unwrap();
+        starts.push(
+            dividers
+                .iter()
+                .map(|divider| start.index + divider)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    result.push(Event {
+        kind: Kind::Enter,
+        name: Name::GridTableRow,
+        point: events[lines[0]].point.clone(),
+        link: None,
+    });
+
+    let mut column = 0;
+
+    while column < places.len() - 1 {
+        // Bytes of each line in this cell, between the dividers.
+        let segments = starts
+            .iter()
+            .map(|dividers| (dividers[column] + 1, dividers[column + 1]))
+            .collect::<Vec<_>>();
+        found += cell(result, events, lines, &segments, parse_state)?;
+        column += 1;
+    }
+
+    result.push(Event {
+        kind: Kind::Exit,
+        name: Name::GridTableRow,
+        point: events[lines[lines.len() - 1] + 1].point.clone(),
+        link: None,
+    });
+
+    Ok(found)
+}
+
+/// Add the events of the cell made from `segments`, which are the bytes in
+/// each of `lines`.
+///
+/// Returns the number of definitions and GFM footnote definitions found in
+/// it.
+fn cell(
+    result: &mut Vec<Event>,
+    events: &[Event],
+    lines: &[usize],
+    segments: &[(usize, usize)],
+    parse_state: &ParseState,
+) -> Result<usize, message::Message> {
+    let bytes = parse_state.bytes;
+    let tab_size = parse_state.options.tab_size;
+    let mut texts = segments
+        .iter()
+        .map(|&(start, mut end)| {
+            while end > start && matches!(bytes[end - 1], b'\t' | b' ') {
+                end -= 1;
+            }
+            (start, end)
+        })
+        .collect::<Vec<_>>();
+
+    if texts
+        .iter()
+        .all(|&(start, end)| start == end || bytes[start] == b' ')
+    {
+        for text in &mut texts {
+            if text.0 < text.1 {
+                text.0 += 1;
+            }
+        }
+    }
+
+    let mut value = String::new();
+    let mut cell_lines = vec![];
+    let mut index = 0;
+
+    while index < texts.len() {
+        let (start, end) = texts[index];
+        let enter = lines[index];
+
+        if index > 0 {
+            value.push('\n');
+        }
+
+        cell_lines.push(CellLine {
+            start: shift(&events[enter].point, bytes, start, tab_size),
+            offset: value.len(),
+            line_ending: (
+                events[enter + 2].point.clone(),
+                events[enter + 3].point.clone(),
+            ),
+        });
+        value.push_str(Slice::from_indices(bytes, start, end).as_str());
+        index += 1;
+    }
+
+    let (cell_events, _) =
+        parse_in(&value, parse_state.options, Some(parse_state)).map_err(|mut message| {
+            message.place = message.place.map(|place| {
+                Box::new(match *place {
+                    message::Place::Point(point) => {
+                        message::Place::Point(unist_point(&cell_lines, bytes, tab_size, &point))
+                    }
+                    message::Place::Position(position) => {
+                        message::Place::Position(unist::Position {
+                            start: unist_point(&cell_lines, bytes, tab_size, &position.start),
+                            end: unist_point(&cell_lines, bytes, tab_size, &position.end),
+                        })
+                    }
+                })
+            });
+            message
+        })?;
+
+    let first = lines[0];
+    let last = lines[lines.len() - 1];
+    result.push(Event {
+        kind: Kind::Enter,
+        name: Name::GridTableCell,
+        point: shift(&events[first].point, bytes, segments[0].0, tab_size),
+        link: None,
+    });
+
+    let mut found = 0;
+
+    for event in cell_events {
+        if event.kind == Kind::Enter
+            && matches!(event.name, Name::Definition | Name::GfmFootnoteDefinition)
+        {
+            found += 1;
+        }
+
+        let point = match event.name {
+            Name::BlankLineEnding | Name::HardBreakLineEnding | Name::LineEnding => {
+                if event.kind == Kind::Enter {
+                    cell_lines[event.point.line - 1].line_ending.0.clone()
+                } else {
+                    cell_lines[event.point.line - 2].line_ending.1.clone()
+                }
+            }
+            _ => point(&cell_lines, bytes, tab_size, &event.point),
+        };
+
+        result.push(Event {
+            kind: event.kind,
+            name: event.name,
+            point,
+            link: None,
+        });
+    }
+
+    result.push(Event {
+        kind: Kind::Exit,
+        name: Name::GridTableCell,
+        point: shift(
+            &events[last].point,
+            bytes,
+            segments[segments.len() - 1].1,
+            tab_size,
+        ),
+        link: None,
+    });
+
+    Ok(found)
+}
+
+/// Move `point` in a cell to the document.
+fn point(lines: &[CellLine], bytes: &[u8], tab_size: usize, point: &Point) -> Point {
+    let line = &lines[point.line - 1];
+    let mut result = shift(
+        &line.start,
+        bytes,
+        line.start.index + point.index - line.offset,
+        tab_size,
+    );
+    result.column += point.vs;
+    result.vs = point.vs;
+    result
+}
+
+/// Move a unist `point` in a cell to the document.
+fn unist_point(
+    lines: &[CellLine],
+    bytes: &[u8],
+    tab_size: usize,
+    point: &unist::Point,
+) -> unist::Point {
+    self::point(
+        lines,
+        bytes,
+        tab_size,
+        &Point {
+            line: point.line,
+            column: point.column,
+            index: point.offset,
+            vs: 0,
+        },
+    )
+    .to_unist()
+}
+
+/// Shift `point` forward, to `index` on the same line.
+fn shift(point: &Point, bytes: &[u8], index: usize, tab_size: usize) -> Point {
+    if index > point.index {
+        point.shift_to(bytes, index, tab_size)
+    } else {
+        point.clone()
+    }
+}
+
+/// Get the kind of `line`, if it fits in the table that starts with the
+/// border `first`.
+///
+/// Returns `-` or `=` for borders, and `|` for lines of rows.
+fn kind(first: &str, line: &str) -> Option<u8> {
+    let (_, places) = border(first)?;
+
+    if line.starts_with('+') {
+        let (fill, other) = border(line)?;
+        if other == places {
+            Some(fill)
+        } else {
+            None
+        }
+    } else if line.starts_with('|') && dividers(line, &places).is_some() {
+        Some(b'|')
+    } else {
+        None
+    }
+}
+
+/// Parse a border.
+///
+/// Returns the fill (`-` or `=`) and the columns of each `+`.
+fn border(value: &str) -> Option<(u8, Vec<usize>)> {
+    let value = value.trim_end_matches(|char| matches!(char, '\t' | ' '));
+    let inner = value.strip_prefix('+')?.strip_suffix('+')?;
+    let mut fill = 0;
+    let mut places = vec![0];
+
+    for cell in inner.split('+') {
+        let core = cell.strip_prefix(':').unwrap_or(cell);
+        let core = core.strip_suffix(':').unwrap_or(core);
+        let byte = *core.as_bytes().first()?;
+
+        if !matches!(byte, b'-' | b'=')
+            || (fill != 0 && byte != fill)
+            || !core.bytes().all(|other| other == byte)
+        {
+            return None;
+        }
+
+        fill = byte;
+        places.push(places[places.len() - 1] + cell.len() + 1);
+    }
+
+    Some((fill, places))
+}
+
+/// Find the dividers in a line of a row, which must be at the columns in
+/// `places`.
+///
+/// Returns the byte index of each divider.
+fn dividers(value: &str, places: &[usize]) -> Option<Vec<usize>> {
+    let value = value.trim_end_matches(|char| matches!(char, '\t' | ' '));
+    let mut dividers = vec![];
+    let mut column = 0;
+
+    for (index, char) in value.char_indices() {
+        let size = width(char);
+
+        if size == 0 {
+            continue;
+        }
+
+        if dividers.len() < places.len() && column + size > places[dividers.len()] {
+            if column != places[dividers.len()] || char != '|' {
+                return None;
+            }
+
+            dividers.push(index);
+        }
+
+        column += size;
+    }
+
+    if dividers.len() == places.len() && column == places[places.len() - 1] + 1 {
+        Some(dividers)
+    } else {
+        None
+    }
+}
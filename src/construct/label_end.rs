@@ -46,8 +46,9 @@
 //! attribute in case of a [label start (link)][label_start_link], and an
 //! `src` attribute in case of a [label start (image)][label_start_image].
 //! The title is formed, optionally, on either `<a>` or `<img>`.
-//! When matched with a [gfm label start (footnote)][gfm_label_start_footnote],
-//! no reference or resource can follow the label end.
+//! When matched with a [gfm label start (footnote)][gfm_label_start_footnote]
+//! or a [label start (inline footnote)][label_start_inline_footnote], no
+//! reference or resource can follow the label end.
 //!
 //! For info on how to encode characters in URLs, see
 //! [`destination`][destination].
@@ -55,8 +56,9 @@
 //! `<img>` when compiling, see
 //! [`sanitize_uri`][sanitize_uri].
 //!
-//! In case of a matched [gfm label start (footnote)][gfm_label_start_footnote],
-//! a counter is injected.
+//! In case of a matched [gfm label start (footnote)][gfm_label_start_footnote]
+//! or [label start (inline footnote)][label_start_inline_footnote], a counter
+//! is injected.
 //! In case of a matched [label start (link)][label_start_link], the interpreted
 //! content between it and the label end, is placed between the opening and
 //! closing tags.
@@ -81,6 +83,7 @@
 //! the HTML, see above).
 //! But it’s not possible to use links (or footnotes, which result in links)
 //! in links.
+//! Links (and footnotes) can be used in inline footnotes though.
 //! The “deepest” link (or footnote) wins.
 //! To illustrate:
 //!
@@ -130,6 +133,7 @@
 //! *   [`Data`][Name::Data]
 //! *   [`GfmFootnoteCall`][Name::GfmFootnoteCall]
 //! *   [`Image`][Name::Image]
+//! *   [`InlineFootnote`][Name::InlineFootnote]
 //! *   [`Label`][Name::Label]
 //! *   [`LabelEnd`][Name::LabelEnd]
 //! *   [`LabelMarker`][Name::LabelMarker]
@@ -171,6 +175,7 @@
 //! [label_start_image]: crate::construct::label_start_image
 //! [label_start_link]: crate::construct::label_start_link
 //! [gfm_label_start_footnote]: crate::construct::gfm_label_start_footnote
+//! [label_start_inline_footnote]: crate::construct::label_start_inline_footnote
 //! [definition]: crate::construct::definition
 //! [autolink]: crate::construct::autolink
 //! [sanitize_uri]: crate::util::sanitize_uri::sanitize
@@ -258,6 +263,16 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
         tokenizer.events[tokenizer.tokenize_state.end].point.index,
     );
 
+    // Inline footnotes are never followed by anything, but must not be
+    // empty.
+    if start.kind == LabelKind::InlineFootnote {
+        return State::Retry(if indices.0 == indices.1 {
+            StateName::LabelEndNok
+        } else {
+            StateName::LabelEndOk
+        });
+    }
+
     // We don’t care about virtual spaces, so `indices` and `as_str` are fine.
     let mut id = normalize_identifier(
         Slice::from_indices(tokenizer.parse_state.bytes, indices.0, indices.1).as_str(),
@@ -351,11 +366,13 @@ pub fn ok(tokenizer: &mut Tokenizer) -> State {
     // longer viable for use (as they would otherwise contain a link).
     // These link starts are still looking for balanced closing brackets, so
     // we can’t remove them, but we can mark them.
+    // Inline footnotes can contain links, so they are not marked.
     if label_start.kind != LabelKind::Image {
         let mut index = 0;
         while index < tokenizer.tokenize_state.label_starts.len() {
             let label_start = &mut tokenizer.tokenize_state.label_starts[index];
-            if label_start.kind != LabelKind::Image {
+            if label_start.kind != LabelKind::Image && label_start.kind != LabelKind::InlineFootnote
+            {
                 label_start.inactive = true;
             }
             index += 1;
@@ -605,6 +622,7 @@ pub fn reference_full_after(tokenizer: &mut Tokenizer) -> State {
                         &[Name::ReferenceString],
                     ),
                 ),
+                tokenizer.parse_state.options.tab_size,
             )
             .as_str(),
         ))
@@ -693,6 +711,8 @@ fn inject_labels(tokenizer: &mut Tokenizer, labels: &[Label]) {
         let label = &labels[index];
         let group_name = if label.kind == LabelKind::GfmFootnote {
             Name::GfmFootnoteCall
+        } else if label.kind == LabelKind::InlineFootnote {
+            Name::InlineFootnote
         } else if label.kind == LabelKind::Image {
             Name::Image
         } else {
@@ -0,0 +1,165 @@
+//! Label start (inline footnote) occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Label start (inline footnote) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! label_start_inline_footnote ::= '^' '['
+//! ```
+//!
+//! When matched with a [label end][label_end], they together form an inline
+//! footnote.
+//! No reference or resource can follow the label end, and the label cannot
+//! be empty.
+//! The text between the brackets is the content of the footnote, which can
+//! contain links.
+//!
+//! Inline footnotes are numbered automatically, in the order they occur, and
+//! are turned into a footnote call with a footnote definition, as if they
+//! were written as [GFM footnotes][gfm_label_start_footnote].
+//! Numbers that are used as the identifier of a GFM footnote definition
+//! (such as `[^1]: a`) are skipped.
+//!
+//! ## HTML
+//!
+//! Label start (inline footnote) does not, on its own, relate to anything in
+//! HTML.
+//! When matched with a [label end][label_end], they together relate to the
+//! `<sup>` and `<a>` elements in HTML, with the content placed in the
+//! footnote section, exactly like GFM footnotes.
+//! Without an end, the characters (`^[`) are output.
+//!
+//! ## Recommendation
+//!
+//! Inline footnotes are not supported in GFM.
+//! When the markdown is also read elsewhere, it is recommended to use GFM
+//! footnotes instead.
+//!
+//! ## Tokens
+//!
+//! *   [`LabelInlineFootnote`][Name::LabelInlineFootnote]
+//! *   [`LabelInlineFootnoteMarker`][Name::LabelInlineFootnoteMarker]
+//! *   [`LabelMarker`][Name::LabelMarker]
+//!
+//! ## References
+//!
+//! *   [*Extension: `inline_notes`* in Pandoc](https://pandoc.org/MANUAL.html#extension-inline_notes)
+//! *   [`remark-footnotes`](https://github.com/remarkjs/remark-footnotes)
+//!
+//! [text]: crate::construct::text
+//! [label_end]: crate::construct::label_end
+//! [gfm_label_start_footnote]: crate::construct::gfm_label_start_footnote
+
+use crate::event::{Event, Kind, Name};
+use crate::resolve::Name as ResolveName;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{LabelKind, LabelStart, Tokenizer};
+use crate::util::{
+    normalize_identifier::normalize_identifier,
+    slice::{Position, Slice},
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+};
+
+/// Start of label (inline footnote) start.
+///
+/// ```markdown
+/// > | a ^[b] c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer
+        .parse_state
+        .options
+        .constructs
+        .label_start_inline_footnote
+        && tokenizer.current == Some(b'^')
+    {
+        tokenizer.enter(Name::LabelInlineFootnote);
+        tokenizer.enter(Name::LabelInlineFootnoteMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::LabelInlineFootnoteMarker);
+        State::Next(StateName::LabelStartInlineFootnoteOpen)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `^`, at `[`.
+///
+/// ```markdown
+/// > | a ^[b] c
+///        ^
+/// ```
+pub fn open(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[') => {
+            tokenizer.enter(Name::LabelMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::LabelMarker);
+            tokenizer.exit(Name::LabelInlineFootnote);
+            tokenizer.tokenize_state.label_starts.push(LabelStart {
+                kind: LabelKind::InlineFootnote,
+                start: (tokenizer.events.len() - 6, tokenizer.events.len() - 1),
+                inactive: false,
+            });
+            tokenizer.register_resolver_before(ResolveName::Label);
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
+
+/// Get the identifiers of inline footnotes in `events`, by the index of
+/// their start in `bytes`.
+///
+/// Inline footnotes are numbered from `1`, skipping numbers used by GFM
+/// footnote definitions.
+pub fn identifiers(events: &[Event], bytes: &[u8], tab_size: usize) -> BTreeMap<usize, String> {
+    let mut taken = BTreeSet::new();
+    let mut identifiers = BTreeMap::new();
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Exit && event.name == Name::GfmFootnoteDefinitionLabelString {
+            taken.insert(normalize_identifier(
+                Slice::from_position(bytes, &Position::from_exit_event(events, index), tab_size)
+                    .as_str(),
+            ));
+        }
+
+        index += 1;
+    }
+
+    let mut number = 0;
+    index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter && event.name == Name::InlineFootnote {
+            let mut identifier;
+
+            loop {
+                number += 1;
+                identifier = number.to_string();
+
+                if !taken.contains(&identifier) {
+                    break;
+                }
+            }
+
+            identifiers.insert(event.point.index, identifier);
+        }
+
+        index += 1;
+    }
+
+    identifiers
+}
@@ -291,6 +291,7 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
                 start: &tokenizer.events[start].point,
                 end: &tokenizer.point,
             },
+            tokenizer.parse_state.options.tab_size,
         )
         .len();
 
@@ -198,6 +198,7 @@ fn parse_esm(tokenizer: &mut Tokenizer) -> State {
     let result = collect(
         &tokenizer.events,
         tokenizer.parse_state.bytes,
+        tokenizer.parse_state.options.tab_size,
         tokenizer.tokenize_state.start,
         &[Name::MdxEsmData, Name::LineEnding],
         &[],
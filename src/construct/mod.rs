@@ -34,7 +34,8 @@
 //!
 //! The following constructs are found in markdown (`CommonMark`):
 //!
-//! *   [attention][] (strong, emphasis, extension: GFM strikethrough)
+//! *   [attention][] (strong, emphasis, extensions: GFM strikethrough, highlight,
+//!     insert)
 //! *   [autolink][]
 //! *   [blank line][blank_line]
 //! *   [block quote][block_quote]
@@ -62,17 +63,30 @@
 //!
 //! The following constructs are extensions found in markdown:
 //!
+//! *   [abbreviation definition][abbreviation_definition]
+//! *   [attributes][]
+//! *   [cite][]
+//! *   [critic markup][critic_markup]
+//! *   [definition list][definition_list]
+//! *   [emoji][]
+//! *   [extension][]
 //! *   [frontmatter][]
+//! *   [gfm alert][gfm_alert]
 //! *   [gfm autolink literal][gfm_autolink_literal]
 //! *   [gfm footnote definition][gfm_footnote_definition]
 //! *   [gfm label start footnote][gfm_label_start_footnote]
 //! *   [gfm table][gfm_table]
 //! *   [gfm task list item check][gfm_task_list_item_check]
+//! *   [grid table][grid_table]
+//! *   [label start (inline footnote)][label_start_inline_footnote]
 //! *   [mdx esm][mdx_esm]
 //! *   [mdx expression (flow)][mdx_expression_flow]
 //! *   [mdx expression (text)][mdx_expression_text]
 //! *   [mdx jsx (flow)][mdx_jsx_flow]
 //! *   [mdx jsx (text)][mdx_jsx_text]
+//! *   [obsidian block id][obsidian_block_id]
+//! *   [smart punctuation][smart_punctuation]
+//! *   [wiki link][wiki_link]
 //!
 //! There are also several small subroutines typically used in different places:
 //!
@@ -148,23 +162,32 @@
 //!
 //! [bnf]: http://trevorjim.com/a-specification-for-markdown/
 
+pub mod abbreviation_definition;
 pub mod attention;
+pub mod attributes;
 pub mod autolink;
 pub mod blank_line;
 pub mod block_quote;
 pub mod character_escape;
 pub mod character_reference;
+pub mod cite;
 pub mod code_indented;
 pub mod content;
+pub mod critic_markup;
 pub mod definition;
+pub mod definition_list;
 pub mod document;
+pub mod emoji;
+pub mod extension;
 pub mod flow;
 pub mod frontmatter;
+pub mod gfm_alert;
 pub mod gfm_autolink_literal;
 pub mod gfm_footnote_definition;
 pub mod gfm_label_start_footnote;
 pub mod gfm_table;
 pub mod gfm_task_list_item_check;
+pub mod grid_table;
 pub mod hard_break_escape;
 pub mod heading_atx;
 pub mod heading_setext;
@@ -172,6 +195,7 @@ pub mod html_flow;
 pub mod html_text;
 pub mod label_end;
 pub mod label_start_image;
+pub mod label_start_inline_footnote;
 pub mod label_start_link;
 pub mod list_item;
 pub mod mdx_esm;
@@ -179,6 +203,7 @@ pub mod mdx_expression_flow;
 pub mod mdx_expression_text;
 pub mod mdx_jsx_flow;
 pub mod mdx_jsx_text;
+pub mod obsidian_block_id;
 pub mod paragraph;
 pub mod partial_bom;
 pub mod partial_data;
@@ -193,6 +218,8 @@ pub mod partial_title;
 pub mod partial_whitespace;
 pub mod raw_flow;
 pub mod raw_text;
+pub mod smart_punctuation;
 pub mod string;
 pub mod text;
 pub mod thematic_break;
+pub mod wiki_link;
@@ -0,0 +1,144 @@
+//! Obsidian: block identifier occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Block identifiers form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: preceded by whitespace or a line ending, or at the start of
+//! ; text.
+//! ; Restriction: followed by optional whitespace and the end of text.
+//! obsidian_block_id ::= '^' 1*(ascii_alphanumeric | '-')
+//! ```
+//!
+//! A block identifier gives an identifier to the paragraph or heading it
+//! ends, so that other documents can link to that block (as in
+//! `[[page#^id]]`).
+//! In Obsidian, an identifier on a line of its own after a list, block quote,
+//! or table, identifies that block.
+//! Such an identifier forms a paragraph of its own, which in HTML is not
+//! output, as the identifier is placed on that block instead.
+//!
+//! ## HTML
+//!
+//! Block identifiers do not relate to anything in HTML themselves.
+//! They are output as an `id` attribute (with a `^` prefix, such as
+//! `id="^id"`, so that the links from Obsidian resolve) on the paragraph or
+//! heading they end.
+//! In tight list items, where paragraphs are not output, the identifier is
+//! placed on the `<li>`.
+//! An identifier on a line of its own after a list, block quote, or table is
+//! placed on the `<ol>`, `<ul>`, `<blockquote>`, or `<table>`.
+//!
+//! ## Tokens
+//!
+//! *   [`ObsidianBlockId`][Name::ObsidianBlockId]
+//! *   [`ObsidianBlockIdMarker`][Name::ObsidianBlockIdMarker]
+//! *   [`ObsidianBlockIdValue`][Name::ObsidianBlockIdValue]
+//!
+//! ## References
+//!
+//! *   [*Link to blocks* in Obsidian](https://help.obsidian.md/Linking+notes+and+files/Internal+links#Link+to+a+block+in+a+note)
+//!
+//! [text]: crate::construct::text
+
+use crate::construct::partial_space_or_tab::space_or_tab;
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// At start of block identifier.
+///
+/// ```markdown
+/// > | a ^b
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.obsidian_block_id
+        && tokenizer.current == Some(b'^')
+        && matches!(tokenizer.previous, None | Some(b'\t' | b'\n' | b' '))
+    {
+        tokenizer.enter(Name::ObsidianBlockId);
+        tokenizer.enter(Name::ObsidianBlockIdMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::ObsidianBlockIdMarker);
+        State::Next(StateName::ObsidianBlockIdValueBefore)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `^`, at value.
+///
+/// ```markdown
+/// > | a ^b
+///        ^
+/// ```
+pub fn value_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z') => {
+            tokenizer.enter(Name::ObsidianBlockIdValue);
+            State::Retry(StateName::ObsidianBlockIdValue)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// In value.
+///
+/// ```markdown
+/// > | a ^b
+///        ^
+/// ```
+pub fn value(tokenizer: &mut Tokenizer) -> State {
+    if matches!(
+        tokenizer.current,
+        Some(b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z')
+    ) {
+        tokenizer.consume();
+        State::Next(StateName::ObsidianBlockIdValue)
+    } else {
+        tokenizer.exit(Name::ObsidianBlockIdValue);
+        tokenizer.exit(Name::ObsidianBlockId);
+        State::Retry(StateName::ObsidianBlockIdAfter)
+    }
+}
+
+/// After block identifier.
+///
+/// ```markdown
+/// > | a ^b
+///         ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None => State::Ok,
+        // Space or tab?
+        // Check what comes after.
+        Some(b'\t' | b' ') => {
+            tokenizer.check(State::Ok, State::Nok);
+            tokenizer.attempt(
+                State::Next(StateName::ObsidianBlockIdAfterSpaceOrTab),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        }
+        _ => State::Nok,
+    }
+}
+
+/// After whitespace, after block identifier.
+///
+/// ```markdown
+/// > | a ^b␠
+///          ^
+/// ```
+pub fn after_space_or_tab(tokenizer: &mut Tokenizer) -> State {
+    // Only the end of text is okay.
+    if tokenizer.current.is_none() {
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
@@ -75,11 +75,25 @@ pub fn resolve_whitespace(tokenizer: &mut Tokenizer, hard_break: bool, trim_whol
         if event.kind == Kind::Exit && event.name == Name::Data {
             let trim_start = (trim_whole && index == 1)
                 || (index > 1 && tokenizer.events[index - 2].name == Name::LineEnding);
+            // Whitespace before a block identifier (Obsidian) or attributes,
+            // which are always at the end, is trimmed, but never a hard break.
+            let block_id = index + 1 < tokenizer.events.len()
+                && matches!(
+                    tokenizer.events[index + 1].name,
+                    Name::Attributes | Name::ObsidianBlockId
+                );
             let trim_end = (trim_whole && index == tokenizer.events.len() - 1)
+                || block_id
                 || (index + 1 < tokenizer.events.len()
                     && tokenizer.events[index + 1].name == Name::LineEnding);
 
-            trim_data(tokenizer, index, trim_start, trim_end, hard_break);
+            trim_data(
+                tokenizer,
+                index,
+                trim_start,
+                trim_end,
+                hard_break && !block_id,
+            );
         }
 
         index += 1;
@@ -99,6 +113,7 @@ fn trim_data(
     let mut slice = Slice::from_position(
         tokenizer.parse_state.bytes,
         &Position::from_exit_event(&tokenizer.events, exit_index),
+        tokenizer.parse_state.options.tab_size,
     );
 
     if trim_end {
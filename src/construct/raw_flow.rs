@@ -18,6 +18,10 @@
 //! ; marker in the opening fence sequence
 //! fence_close ::= sequence *space_or_tab
 //! sequence ::= 3*'`' | 3*'~' | 2*'$'
+//! ; Restriction: only with `math_latex_delimiters`, and without `info` or
+//! ; `meta`.
+//! ; Restriction: the opening fence uses `[`, the closing fence `]`.
+//! sequence_latex ::= '\\' ( '[' | ']' )
 //! ; Restriction: the marker cannot occur in `info` if it is the `$` or `` ` `` character.
 //! info ::= 1*text
 //! ; Restriction: the marker cannot occur in `meta` if it is the `$` or `` ` `` character.
@@ -38,6 +42,14 @@
 //! It can be indented up to a separate 3 spaces or tabs.
 //! A bigger indent makes it part of the content instead of a fence.
 //!
+//! When [`attributes`][crate::Constructs::attributes] is on, code (fenced)
+//! can end its opening fence with [attributes][crate::construct::attributes]
+//! (`` ```js {#a .b} ``).
+//!
+//! When [`math_latex_delimiters`][parse_options] is on, math (flow) can also
+//! be formed with `\[` and `\]` fences, as in LaTeX, which must each be on
+//! their own line.
+//!
 //! The `info` and `meta` parts are interpreted as the [string][] content type.
 //! That means that [character escapes][character_escape] and
 //! [character references][character_reference] are allowed.
@@ -137,6 +149,7 @@
 //! [raw_text]: crate::construct::raw_text
 //! [html_code]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-code-element
 //! [html_pre]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-pre-element
+//! [parse_options]: crate::ParseOptions
 
 use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
 use crate::event::{Content, Link, Name};
@@ -175,7 +188,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
             ));
         }
 
-        if matches!(tokenizer.current, Some(b'$' | b'`' | b'~')) {
+        if matches!(tokenizer.current, Some(b'$' | b'\\' | b'`' | b'~')) {
             return State::Retry(StateName::RawFlowBeforeSequenceOpen);
         }
     }
@@ -200,6 +213,7 @@ pub fn before_sequence_open(tokenizer: &mut Tokenizer) -> State {
             prefix = Slice::from_position(
                 tokenizer.parse_state.bytes,
                 &Position::from_exit_event(&tokenizer.events, tokenizer.events.len() - 1),
+                tokenizer.parse_state.options.tab_size,
             )
             .len();
         }
@@ -210,10 +224,14 @@ pub fn before_sequence_open(tokenizer: &mut Tokenizer) -> State {
         && matches!(tokenizer.current, Some(b'`' | b'~')))
         // Math (flow).
         || (tokenizer.parse_state.options.constructs.math_flow && tokenizer.current == Some(b'$'))
+        // Math (flow) with LaTeX delimiters.
+        || (tokenizer.parse_state.options.constructs.math_flow
+            && tokenizer.parse_state.options.math_latex_delimiters
+            && tokenizer.current == Some(b'\\'))
     {
         tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
         tokenizer.tokenize_state.size_c = prefix;
-        if tokenizer.tokenize_state.marker == b'$' {
+        if matches!(tokenizer.tokenize_state.marker, b'$' | b'\\') {
             tokenizer.tokenize_state.token_1 = Name::MathFlow;
             tokenizer.tokenize_state.token_2 = Name::MathFlowFence;
             tokenizer.tokenize_state.token_3 = Name::MathFlowFenceSequence;
@@ -248,15 +266,18 @@ pub fn before_sequence_open(tokenizer: &mut Tokenizer) -> State {
 ///   | ~~~
 /// ```
 pub fn sequence_open(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.current == Some(tokenizer.tokenize_state.marker) {
+    let expected = expected(tokenizer, tokenizer.tokenize_state.size, b'[');
+
+    if expected.is_some() && tokenizer.current == expected {
         tokenizer.tokenize_state.size += 1;
         tokenizer.consume();
         State::Next(StateName::RawFlowSequenceOpen)
     } else if tokenizer.tokenize_state.size
-        < (if tokenizer.tokenize_state.marker == b'$' {
-            MATH_FLOW_SEQUENCE_SIZE_MIN
-        } else {
-            CODE_FENCED_SEQUENCE_SIZE_MIN
+        < (match tokenizer.tokenize_state.marker {
+            b'$' => MATH_FLOW_SEQUENCE_SIZE_MIN,
+            // `\[`.
+            b'\\' => 2,
+            _ => CODE_FENCED_SEQUENCE_SIZE_MIN,
         })
     {
         tokenizer.tokenize_state.marker = 0;
@@ -272,10 +293,11 @@ pub fn sequence_open(tokenizer: &mut Tokenizer) -> State {
     } else {
         // Math (flow) does not support an `info` part: everything after the
         // opening sequence is the `meta` part.
-        let next = if tokenizer.tokenize_state.marker == b'$' {
-            StateName::RawFlowMetaBefore
-        } else {
-            StateName::RawFlowInfoBefore
+        // With LaTeX delimiters, it does not support `meta` either.
+        let next = match tokenizer.tokenize_state.marker {
+            b'$' => StateName::RawFlowMetaBefore,
+            b'\\' => StateName::RawFlowLatexAfterSequenceOpen,
+            _ => StateName::RawFlowInfoBefore,
         };
 
         if matches!(tokenizer.current, Some(b'\t' | b' ')) {
@@ -289,6 +311,32 @@ pub fn sequence_open(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// In opening fence, after the `\[` sequence (and optional whitespace).
+///
+/// ```markdown
+/// > | \[
+///       ^
+///   | a
+///   | \]
+/// ```
+pub fn latex_after_sequence_open(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Retry(StateName::RawFlowInfoBefore),
+        _ => {
+            tokenizer.tokenize_state.marker = 0;
+            tokenizer.tokenize_state.size_c = 0;
+            tokenizer.tokenize_state.size = 0;
+            tokenizer.tokenize_state.token_1 = Name::Data;
+            tokenizer.tokenize_state.token_2 = Name::Data;
+            tokenizer.tokenize_state.token_3 = Name::Data;
+            tokenizer.tokenize_state.token_4 = Name::Data;
+            tokenizer.tokenize_state.token_5 = Name::Data;
+            tokenizer.tokenize_state.token_6 = Name::Data;
+            State::Nok
+        }
+    }
+}
+
 /// In opening fence, after the sequence (and optional whitespace), before info.
 ///
 /// ```markdown
@@ -309,21 +357,38 @@ pub fn info_before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::NonLazyContinuationStart)
         }
-        _ => {
-            tokenizer.enter(tokenizer.tokenize_state.token_4.clone());
-            tokenizer.enter_link(
-                Name::Data,
-                Link {
-                    previous: None,
-                    next: None,
-                    content: Content::String,
-                },
+        Some(b'{') if tokenizer.tokenize_state.token_1 == Name::CodeFenced => {
+            tokenizer.attempt(
+                State::Next(StateName::RawFlowInfoBefore),
+                State::Next(StateName::RawFlowInfoOpen),
             );
-            State::Retry(StateName::RawFlowInfo)
+            State::Retry(StateName::RawFlowAttributes)
         }
+        _ => State::Retry(StateName::RawFlowInfoOpen),
     }
 }
 
+/// In opening fence, at info.
+///
+/// ```markdown
+/// > | ~~~js
+///        ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn info_open(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(tokenizer.tokenize_state.token_4.clone());
+    tokenizer.enter_link(
+        Name::Data,
+        Link {
+            previous: None,
+            next: None,
+            content: Content::String,
+        },
+    );
+    State::Retry(StateName::RawFlowInfo)
+}
+
 /// In info.
 ///
 /// ```markdown
@@ -380,21 +445,38 @@ pub fn info(tokenizer: &mut Tokenizer) -> State {
 pub fn meta_before(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         None | Some(b'\n') => State::Retry(StateName::RawFlowInfoBefore),
-        _ => {
-            tokenizer.enter(tokenizer.tokenize_state.token_5.clone());
-            tokenizer.enter_link(
-                Name::Data,
-                Link {
-                    previous: None,
-                    next: None,
-                    content: Content::String,
-                },
+        Some(b'{') if tokenizer.tokenize_state.token_1 == Name::CodeFenced => {
+            tokenizer.attempt(
+                State::Next(StateName::RawFlowInfoBefore),
+                State::Next(StateName::RawFlowMetaOpen),
             );
-            State::Retry(StateName::RawFlowMeta)
+            State::Retry(StateName::RawFlowAttributes)
         }
+        _ => State::Retry(StateName::RawFlowMetaOpen),
     }
 }
 
+/// In opening fence, at meta.
+///
+/// ```markdown
+/// > | ~~~js eval
+///           ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn meta_open(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(tokenizer.tokenize_state.token_5.clone());
+    tokenizer.enter_link(
+        Name::Data,
+        Link {
+            previous: None,
+            next: None,
+            content: Content::String,
+        },
+    );
+    State::Retry(StateName::RawFlowMeta)
+}
+
 /// In meta.
 ///
 /// ```markdown
@@ -410,6 +492,17 @@ pub fn meta(tokenizer: &mut Tokenizer) -> State {
             tokenizer.exit(tokenizer.tokenize_state.token_5.clone());
             State::Retry(StateName::RawFlowInfoBefore)
         }
+        // Whitespace, maybe before attributes.
+        Some(b'\t' | b' ')
+            if tokenizer.tokenize_state.token_1 == Name::CodeFenced
+                && tokenizer.parse_state.options.constructs.attributes =>
+        {
+            tokenizer.check(
+                State::Next(StateName::RawFlowMetaAttributes),
+                State::Next(StateName::RawFlowMetaSpaceOrTab),
+            );
+            State::Retry(StateName::RawFlowAttributesBefore)
+        }
         Some(byte) => {
             // This looks like code (text) / math (text).
             // Note: no reason to check for `~`, because 3 of them can‘t be
@@ -434,6 +527,83 @@ pub fn meta(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// In meta, at whitespace that is not before attributes.
+///
+/// ```markdown
+/// > | ~~~js a b
+///            ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn meta_space_or_tab(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.consume();
+    State::Next(StateName::RawFlowMeta)
+}
+
+/// In meta, at whitespace before attributes.
+///
+/// ```markdown
+/// > | ~~~js a {#b}
+///            ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn meta_attributes(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::Data);
+    tokenizer.exit(tokenizer.tokenize_state.token_5.clone());
+    tokenizer.attempt(State::Next(StateName::RawFlowInfoBefore), State::Nok);
+    State::Retry(StateName::RawFlowAttributesBefore)
+}
+
+/// In opening fence, at optional whitespace before attributes.
+///
+/// ```markdown
+/// > | ~~~js a {#b}
+///            ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn attributes_before(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::RawFlowAttributes), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::RawFlowAttributes)
+    }
+}
+
+/// In opening fence, at attributes.
+///
+/// ```markdown
+/// > | ~~~js {#a}
+///           ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn attributes(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(State::Next(StateName::RawFlowAttributesAfter), State::Nok);
+    State::Retry(StateName::AttributesStart)
+}
+
+/// In opening fence, after attributes.
+///
+/// ```markdown
+/// > | ~~~js {#a}
+///               ^
+///   | console.log(1)
+///   | ~~~
+/// ```
+pub fn attributes_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Ok,
+        Some(b'\t' | b' ') => {
+            tokenizer.attempt(State::Next(StateName::RawFlowAttributesAfter), State::Nok);
+            State::Retry(space_or_tab(tokenizer))
+        }
+        _ => State::Nok,
+    }
+}
+
 /// At eol/eof in raw, before a non-lazy closing fence or content.
 ///
 /// ```markdown
@@ -511,7 +681,9 @@ pub fn before_sequence_close(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn sequence_close(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.current == Some(tokenizer.tokenize_state.marker) {
+    let expected = expected(tokenizer, tokenizer.tokenize_state.size_b, b']');
+
+    if expected.is_some() && tokenizer.current == expected {
         tokenizer.tokenize_state.size_b += 1;
         tokenizer.consume();
         State::Next(StateName::RawFlowSequenceClose)
@@ -661,3 +833,14 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
     tokenizer.concrete = false;
     State::Ok
 }
+
+/// Get the byte expected at `size` in a sequence.
+///
+/// That is the marker, or, for LaTeX delimiters, `\` and then `bracket`.
+fn expected(tokenizer: &Tokenizer, size: usize, bracket: u8) -> Option<u8> {
+    if tokenizer.tokenize_state.marker == b'\\' {
+        [b'\\', bracket].get(size).copied()
+    } else {
+        Some(tokenizer.tokenize_state.marker)
+    }
+}
@@ -13,6 +13,9 @@
 //!
 //! ; Restriction: not preceded or followed by the same marker.
 //! sequence ::= 1*'`' | 1*'$'
+//!
+//! ; Restriction: only with `math_latex_delimiters`.
+//! raw_text_latex ::= '\\' '(' *byte '\\' ')'
 //! ```
 //!
 //! The above grammar shows that it is not possible to create empty raw (text).
@@ -57,6 +60,14 @@
 //! if both exist and there is also a non-space in the code, are removed.
 //! Line endings, at that stage, are considered as spaces.
 //!
+//! When [`math_latex_delimiters`][parse_options] is on, math (text) can also
+//! be wrapped in `\(` and `\)`, as in LaTeX.
+//! The first `\)` closes it.
+//!
+//! When [`attributes`][crate::Constructs::attributes] is on, code (text) can
+//! be directly followed by [attributes][crate::construct::attributes]
+//! (`` `a`{.b} ``).
+//!
 //! In markdown, it is possible to create code or math with the
 //! [raw (flow)][raw_flow] (or [code (indented)][code_indented]) constructs
 //! in the [flow][] content type.
@@ -129,13 +140,17 @@ use crate::tokenizer::Tokenizer;
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
     // Code (text):
-    if ((tokenizer.parse_state.options.constructs.code_text && tokenizer.current == Some(b'`'))
+    if (((tokenizer.parse_state.options.constructs.code_text && tokenizer.current == Some(b'`'))
         // Math (text):
         || (tokenizer.parse_state.options.constructs.math_text && tokenizer.current == Some(b'$')))
         // Not the same marker (except when escaped).
         && (tokenizer.previous != tokenizer.current
             || (!tokenizer.events.is_empty()
-                && tokenizer.events[tokenizer.events.len() - 1].name == Name::CharacterEscape))
+                && tokenizer.events[tokenizer.events.len() - 1].name == Name::CharacterEscape)))
+        // Math (text) with LaTeX delimiters:
+        || (tokenizer.parse_state.options.constructs.math_text
+            && tokenizer.parse_state.options.math_latex_delimiters
+            && tokenizer.current == Some(b'\\'))
     {
         let marker = tokenizer.current.unwrap();
         if marker == b'`' {
@@ -163,15 +178,19 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn sequence_open(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.current == Some(tokenizer.tokenize_state.marker) {
+    let expected = expected(tokenizer, tokenizer.tokenize_state.size, b'(');
+
+    if expected.is_some() && tokenizer.current == expected {
         tokenizer.tokenize_state.size += 1;
         tokenizer.consume();
         State::Next(StateName::RawTextSequenceOpen)
     }
     // Not enough markers in the sequence.
-    else if tokenizer.tokenize_state.marker == b'$'
+    else if (tokenizer.tokenize_state.marker == b'$'
         && tokenizer.tokenize_state.size == 1
-        && !tokenizer.parse_state.options.math_text_single_dollar
+        && !tokenizer.parse_state.options.math_text_single_dollar)
+        // Not `\(`.
+        || (tokenizer.tokenize_state.marker == b'\\' && tokenizer.tokenize_state.size < 2)
     {
         tokenizer.tokenize_state.marker = 0;
         tokenizer.tokenize_state.size = 0;
@@ -244,21 +263,26 @@ pub fn data(tokenizer: &mut Tokenizer) -> State {
 ///       ^
 /// ```
 pub fn sequence_close(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.current == Some(tokenizer.tokenize_state.marker) {
+    let expected = expected(tokenizer, tokenizer.tokenize_state.size_b, b')');
+
+    if expected.is_some() && tokenizer.current == expected {
         tokenizer.tokenize_state.size_b += 1;
         tokenizer.consume();
         State::Next(StateName::RawTextSequenceClose)
     } else {
         tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
         if tokenizer.tokenize_state.size == tokenizer.tokenize_state.size_b {
-            tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
-            tokenizer.tokenize_state.marker = 0;
-            tokenizer.tokenize_state.size = 0;
-            tokenizer.tokenize_state.size_b = 0;
-            tokenizer.tokenize_state.token_1 = Name::Data;
-            tokenizer.tokenize_state.token_2 = Name::Data;
-            tokenizer.tokenize_state.token_3 = Name::Data;
-            State::Ok
+            // Code (text) can be followed by attributes.
+            if tokenizer.tokenize_state.token_1 == Name::CodeText && tokenizer.current == Some(b'{')
+            {
+                tokenizer.attempt(
+                    State::Next(StateName::RawTextAfter),
+                    State::Next(StateName::RawTextAfter),
+                );
+                State::Retry(StateName::AttributesStart)
+            } else {
+                State::Retry(StateName::RawTextAfter)
+            }
         } else {
             // More or less accents: mark as data.
             let len = tokenizer.events.len();
@@ -269,3 +293,31 @@ pub fn sequence_close(tokenizer: &mut Tokenizer) -> State {
         }
     }
 }
+
+/// After closing sequence, and optional attributes.
+///
+/// ```markdown
+/// > | `a`
+///        ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
+    tokenizer.tokenize_state.marker = 0;
+    tokenizer.tokenize_state.size = 0;
+    tokenizer.tokenize_state.size_b = 0;
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    tokenizer.tokenize_state.token_2 = Name::Data;
+    tokenizer.tokenize_state.token_3 = Name::Data;
+    State::Ok
+}
+
+/// Get the byte expected at `size` in a sequence.
+///
+/// That is the marker, or, for LaTeX delimiters, `\` and then `bracket`.
+fn expected(tokenizer: &Tokenizer, size: usize, bracket: u8) -> Option<u8> {
+    if tokenizer.tokenize_state.marker == b'\\' {
+        [b'\\', bracket].get(size).copied()
+    } else {
+        Some(tokenizer.tokenize_state.marker)
+    }
+}
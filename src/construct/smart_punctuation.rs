@@ -0,0 +1,176 @@
+//! Smart punctuation occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Smart punctuation forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! smart_punctuation ::= '"' | '\'' | 2*'-' | '...'
+//! ```
+//!
+//! Straight quotes are turned into curly quotes, `--` into an en dash (`–`),
+//! `---` into an em dash (`—`), and `...` into an ellipsis (`…`).
+//!
+//! Whether a quote opens or closes is decided by the characters around it,
+//! similar to how [attention][crate::construct::attention] decides whether
+//! a sequence can close:
+//! a quote closes (`”`, `’`) if it is preceded by something other than
+//! whitespace and punctuation, or preceded by punctuation and followed by
+//! whitespace or punctuation.
+//! Otherwise, it opens (`“`, `‘`).
+//! That means that apostrophes, such as in `it's`, are closing single quotes
+//! (`’`), which is the typographically correct character for them.
+//!
+//! Sequences of more than three dashes are turned into em dashes if
+//! possible, and en dashes otherwise, preferring em dashes, so that `----`
+//! is two en dashes and `-----` is an em dash and an en dash.
+//!
+//! Smart punctuation is not found in code (text), math (text), or other
+//! places where markdown is not parsed, such as in the destination or title
+//! of links.
+//! Escaped characters (such as `\"` or `\--`) are also not turned into smart
+//! punctuation.
+//!
+//! ## HTML
+//!
+//! Smart punctuation does not relate to anything in HTML.
+//! The typographic character is output instead of the straight character.
+//!
+//! ## Recommendation
+//!
+//! Smart punctuation is not supported in most places that render markdown.
+//! When the markdown is also read elsewhere, the straight characters show up.
+//! It is recommended to use typographic characters instead, if possible.
+//!
+//! ## Tokens
+//!
+//! *   [`SmartPunctuation`][Name::SmartPunctuation]
+//!
+//! ## References
+//!
+//! *   [*Smart punctuation* in `pulldown-cmark`](https://docs.rs/pulldown-cmark/latest/pulldown_cmark/struct.Options.html#associatedconstant.ENABLE_SMART_PUNCTUATION)
+//! *   [*Extension: `smart`* in Pandoc](https://pandoc.org/MANUAL.html#extension-smart)
+//!
+//! [text]: crate::construct::text
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::char::{
+    after_index as char_after_index, before_index as char_before_index, classify_opt,
+    Kind as CharacterKind,
+};
+use alloc::string::String;
+
+/// At start of smart punctuation.
+///
+/// ```markdown
+/// > | "a" -- b...
+///     ^   ^   ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.smart_punctuation {
+        match tokenizer.current {
+            Some(b'"' | b'\'') => {
+                tokenizer.enter(Name::SmartPunctuation);
+                tokenizer.consume();
+                tokenizer.exit(Name::SmartPunctuation);
+                State::Ok
+            }
+            Some(b'-') => {
+                tokenizer.enter(Name::SmartPunctuation);
+                State::Retry(StateName::SmartPunctuationDashes)
+            }
+            Some(b'.') => {
+                tokenizer.enter(Name::SmartPunctuation);
+                State::Retry(StateName::SmartPunctuationEllipsis)
+            }
+            _ => State::Nok,
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// In dashes.
+///
+/// ```markdown
+/// > | a -- b
+///       ^^
+/// ```
+pub fn dashes(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'-') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        State::Next(StateName::SmartPunctuationDashes)
+    } else if tokenizer.tokenize_state.size > 1 {
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::SmartPunctuation);
+        State::Ok
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// In ellipsis.
+///
+/// ```markdown
+/// > | a...
+///      ^^^
+/// ```
+pub fn ellipsis(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'.') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+
+        if tokenizer.tokenize_state.size == 3 {
+            tokenizer.tokenize_state.size = 0;
+            tokenizer.exit(Name::SmartPunctuation);
+            State::Ok
+        } else {
+            State::Next(StateName::SmartPunctuationEllipsis)
+        }
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// Get the typographic value of the smart punctuation from `start` to `end`
+/// in `bytes`.
+pub fn value(bytes: &[u8], start: usize, end: usize) -> String {
+    match bytes[start] {
+        b'"' | b'\'' => {
+            let before = classify_opt(char_before_index(bytes, start));
+            let after = classify_opt(char_after_index(bytes, end));
+            let close = before == CharacterKind::Other
+                || (before == CharacterKind::Punctuation && after != CharacterKind::Other);
+
+            String::from(match (bytes[start], close) {
+                (b'"', false) => "“",
+                (b'"', true) => "”",
+                (_, false) => "‘",
+                (_, true) => "’",
+            })
+        }
+        b'-' => {
+            let size = end - start;
+            let (em, en) = if size % 3 == 0 {
+                (size / 3, 0)
+            } else if size % 2 == 0 {
+                (0, size / 2)
+            } else if size % 3 == 2 {
+                ((size - 2) / 3, 1)
+            } else {
+                ((size - 4) / 3, 2)
+            };
+
+            let mut value = "—".repeat(em);
+            value.push_str(&"–".repeat(en));
+            value
+        }
+        _ => String::from("…"),
+    }
+}
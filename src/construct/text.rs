@@ -6,51 +6,100 @@
 //!
 //! The constructs found in text are:
 //!
-//! *   [Attention][crate::construct::attention] (emphasis, gfm strikethrough, strong)
+//! *   [Attention][crate::construct::attention] (emphasis, gfm strikethrough, highlight, insert, strong)
+//! *   [Attributes][crate::construct::attributes]
 //! *   [Autolink][crate::construct::autolink]
 //! *   [Character escape][crate::construct::character_escape]
 //! *   [Character reference][crate::construct::character_reference]
+//! *   [Cite][crate::construct::cite]
+//! *   [Critic markup][crate::construct::critic_markup]
+//! *   [Emoji][crate::construct::emoji]
+//! *   [Extension][crate::construct::extension]
 //! *   [Raw (text)][crate::construct::raw_text] (code (text), math (text))
 //! *   [GFM: Label start (footnote)][crate::construct::gfm_label_start_footnote]
 //! *   [GFM: Task list item check][crate::construct::gfm_task_list_item_check]
 //! *   [Hard break (escape)][crate::construct::hard_break_escape]
 //! *   [HTML (text)][crate::construct::html_text]
 //! *   [Label start (image)][crate::construct::label_start_image]
+//! *   [Label start (inline footnote)][crate::construct::label_start_inline_footnote]
 //! *   [Label start (link)][crate::construct::label_start_link]
 //! *   [Label end][crate::construct::label_end]
 //! *   [MDX: expression (text)][crate::construct::mdx_expression_text]
 //! *   [MDX: JSX (text)][crate::construct::mdx_jsx_text]
+//! *   [Obsidian: block id][crate::construct::obsidian_block_id]
+//! *   [Smart punctuation][crate::construct::smart_punctuation]
+//! *   [Wiki link][crate::construct::wiki_link]
 //!
 //! > 👉 **Note**: for performance reasons, hard break (trailing) is formed by
 //! > [whitespace][crate::construct::partial_whitespace].
 
+use crate::construct::abbreviation_definition::resolve as resolve_abbreviation;
 use crate::construct::gfm_autolink_literal::resolve as resolve_gfm_autolink_literal;
 use crate::construct::partial_whitespace::resolve_whitespace;
+use crate::event::{Kind, Name};
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
+use crate::ParseOptions;
+use alloc::{vec, vec::Vec};
 
 /// Characters that can start something in text.
-const MARKERS: [u8; 16] = [
+const MARKERS: [u8; 25] = [
     b'!',  // `label_start_image`
+    b'"',  // `smart_punctuation`
     b'$',  // `raw_text` (math (text))
     b'&',  // `character_reference`
+    b'\'', // `smart_punctuation`
+    b'+',  // `attention` (insert)
     b'*',  // `attention` (emphasis, strong)
+    b'-',  // `smart_punctuation`
+    b'.',  // `smart_punctuation`
+    b':',  // `emoji`
     b'<',  // `autolink`, `html_text`, `mdx_jsx_text`
+    b'=',  // `attention` (highlight)
+    b'@',  // `cite`
     b'H',  // `gfm_autolink_literal` (`protocol` kind)
     b'W',  // `gfm_autolink_literal` (`www.` kind)
-    b'[',  // `label_start_link`
-    b'\\', // `character_escape`, `hard_break_escape`
+    b'[',  // `label_start_link`, `wiki_link`, `cite`
+    b'\\', // `raw_text` (math (text)), `character_escape`, `hard_break_escape`
     b']',  // `label_end`, `gfm_label_start_footnote`
+    b'^',  // `label_start_inline_footnote`, `obsidian_block_id`
     b'_',  // `attention` (emphasis, strong)
     b'`',  // `raw_text` (code (text))
     b'h',  // `gfm_autolink_literal` (`protocol` kind)
     b'w',  // `gfm_autolink_literal` (`www.` kind)
-    b'{',  // `mdx_expression_text`
+    b'{',  // `attributes`, `critic_markup`, `mdx_expression_text`
     b'~',  // `attention` (gfm strikethrough)
 ];
 
+/// Get the characters that can start something in text, including the
+/// markers of extensions and the first letters of GFM autolink literal
+/// protocols.
+pub fn markers(options: &ParseOptions) -> Vec<u8> {
+    let mut markers = MARKERS.to_vec();
+    let mut extra = vec![];
+
+    for extension in &options.extensions {
+        extra.extend_from_slice(&extension.markers);
+    }
+
+    for protocol in &options.gfm_autolink_literal_protocols {
+        if let Some(first) = protocol.as_bytes().first() {
+            extra.push(first.to_ascii_lowercase());
+            extra.push(first.to_ascii_uppercase());
+        }
+    }
+
+    for marker in extra {
+        if !markers.contains(&marker) {
+            markers.push(marker);
+        }
+    }
+
+    markers
+}
+
 /// Start of text.
 ///
 /// There is a slightly weird case where task list items have their check at
@@ -62,7 +111,8 @@ const MARKERS: [u8; 16] = [
 ///     ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
-    tokenizer.tokenize_state.markers = &MARKERS;
+    let parse_state = tokenizer.parse_state;
+    tokenizer.tokenize_state.markers = &parse_state.text_markers;
     tokenizer.attempt(
         State::Next(StateName::TextBefore),
         State::Next(StateName::TextBefore),
@@ -77,6 +127,34 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(byte)
+            if tokenizer
+                .parse_state
+                .options
+                .extensions
+                .iter()
+                .any(|extension| extension.markers.contains(&byte)) =>
+        {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeConstruct),
+            );
+            State::Retry(StateName::ExtensionStart)
+        }
+        _ => State::Retry(StateName::TextBeforeConstruct),
+    }
+}
+
+/// Before a construct of `markdown-rs`.
+///
+/// At something that wasn’t an extension.
+///
+/// ```markdown
+/// > | abc
+///     ^
+/// ```
+pub fn before_construct(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         None => {
             tokenizer.register_resolver(ResolveName::Data);
@@ -105,14 +183,28 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::CharacterReferenceStart)
         }
-        // attention (emphasis, gfm strikethrough, strong)
-        Some(b'*' | b'_' | b'~') => {
+        // attention (emphasis, gfm strikethrough, highlight, insert, strong)
+        Some(b'*' | b'+' | b'=' | b'_' | b'~') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
                 State::Next(StateName::TextBeforeData),
             );
             State::Retry(StateName::AttentionStart)
         }
+        Some(b'"' | b'\'' | b'-' | b'.') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::SmartPunctuationStart)
+        }
+        Some(b':') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::EmojiStart)
+        }
         // `autolink`, `html_text` (order does not matter), `mdx_jsx_text` (order matters).
         Some(b'<') => {
             tokenizer.attempt(
@@ -121,33 +213,37 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::AutolinkStart)
         }
-        Some(b'H' | b'h') => {
+        Some(b'W' | b'w') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
-                State::Next(StateName::TextBeforeData),
+                State::Next(StateName::TextBeforeGfmAutolinkLiteralProtocol),
             );
-            State::Retry(StateName::GfmAutolinkLiteralProtocolStart)
+            State::Retry(StateName::GfmAutolinkLiteralWwwStart)
         }
-        Some(b'W' | b'w') => {
+        // `gfm_autolink_literal` (`protocol` kind).
+        Some(b'A'..=b'Z' | b'a'..=b'z') => {
+            State::Retry(StateName::TextBeforeGfmAutolinkLiteralProtocol)
+        }
+        Some(b'@') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
                 State::Next(StateName::TextBeforeData),
             );
-            State::Retry(StateName::GfmAutolinkLiteralWwwStart)
+            State::Retry(StateName::CiteStart)
         }
         Some(b'[') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
-                State::Next(StateName::TextBeforeLabelStartLink),
+                State::Next(StateName::TextBeforeCite),
             );
-            State::Retry(StateName::GfmLabelStartFootnoteStart)
+            State::Retry(StateName::WikiLinkStart)
         }
         Some(b'\\') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
-                State::Next(StateName::TextBeforeHardBreakEscape),
+                State::Next(StateName::TextBeforeCharacterEscape),
             );
-            State::Retry(StateName::CharacterEscapeStart)
+            State::Retry(StateName::RawTextStart)
         }
         Some(b']') => {
             tokenizer.attempt(
@@ -156,12 +252,19 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::LabelEndStart)
         }
+        Some(b'^') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeLabelStartInlineFootnote),
+            );
+            State::Retry(StateName::ObsidianBlockIdStart)
+        }
         Some(b'{') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
-                State::Next(StateName::TextBeforeData),
+                State::Next(StateName::TextBeforeAttributes),
             );
-            State::Retry(StateName::MdxExpressionTextStart)
+            State::Retry(StateName::CriticMarkupStart)
         }
         _ => State::Retry(StateName::TextBeforeData),
     }
@@ -199,6 +302,38 @@ pub fn before_mdx_jsx(tokenizer: &mut Tokenizer) -> State {
     State::Retry(StateName::MdxJsxTextStart)
 }
 
+/// Before gfm autolink literal (protocol).
+///
+/// At a letter, which wasn’t a gfm autolink literal (www).
+///
+/// ```markdown
+/// > | a https://b
+///       ^
+/// ```
+pub fn before_gfm_autolink_literal_protocol(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeData),
+    );
+    State::Retry(StateName::GfmAutolinkLiteralProtocolStart)
+}
+
+/// Before character escape.
+///
+/// At `\`, which wasn’t math (text).
+///
+/// ```markdown
+/// > | a \*
+///       ^
+/// ```
+pub fn before_character_escape(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeHardBreakEscape),
+    );
+    State::Retry(StateName::CharacterEscapeStart)
+}
+
 /// Before hard break escape.
 ///
 /// At `\`, which wasn’t a character escape.
@@ -215,9 +350,42 @@ pub fn before_hard_break_escape(tokenizer: &mut Tokenizer) -> State {
     State::Retry(StateName::HardBreakEscapeStart)
 }
 
+/// Before cite.
+///
+/// At `[`, which wasn’t a wiki link.
+///
+/// ```markdown
+/// > | a [@b] c
+///       ^
+/// ```
+pub fn before_cite(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeGfmLabelStartFootnote),
+    );
+    State::Retry(StateName::CiteStart)
+}
+
+/// Before GFM label start (footnote).
+///
+/// At `[`, which wasn’t a wiki link or citations.
+///
+/// ```markdown
+/// > | [^a]
+///     ^
+/// ```
+pub fn before_gfm_label_start_footnote(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeLabelStartLink),
+    );
+    State::Retry(StateName::GfmLabelStartFootnoteStart)
+}
+
 /// Before label start (link).
 ///
-/// At `[`, which wasn’t a GFM label start (footnote).
+/// At `[`, which wasn’t a wiki link, citations, or GFM label start
+/// (footnote).
 ///
 /// ```markdown
 /// > | [a](b)
@@ -231,6 +399,54 @@ pub fn before_label_start_link(tokenizer: &mut Tokenizer) -> State {
     State::Retry(StateName::LabelStartLinkStart)
 }
 
+/// Before label start (inline footnote).
+///
+/// At `^`, which wasn’t an Obsidian block id.
+///
+/// ```markdown
+/// > | a ^[b]
+///       ^
+/// ```
+pub fn before_label_start_inline_footnote(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeData),
+    );
+    State::Retry(StateName::LabelStartInlineFootnoteStart)
+}
+
+/// Before attributes.
+///
+/// At `{`, which wasn’t `CriticMarkup`.
+///
+/// ```markdown
+/// > | a {#b}
+///       ^
+/// ```
+pub fn before_attributes(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeMdxExpression),
+    );
+    State::Retry(StateName::AttributesTextStart)
+}
+
+/// Before mdx expression (text).
+///
+/// At `{`, which wasn’t `CriticMarkup` or attributes.
+///
+/// ```markdown
+/// > | a {b}
+///       ^
+/// ```
+pub fn before_mdx_expression(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::TextBefore),
+        State::Next(StateName::TextBeforeData),
+    );
+    State::Retry(StateName::MdxExpressionTextStart)
+}
+
 /// Before data.
 ///
 /// ```markdown
@@ -259,6 +475,58 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
         resolve_gfm_autolink_literal(tokenizer);
     }
 
+    if !tokenizer.parse_state.abbreviations.is_empty() {
+        resolve_abbreviation(tokenizer);
+    }
+
     tokenizer.map.consume(&mut tokenizer.events);
+
+    if tokenizer.parse_state.options.breaks {
+        resolve_breaks(tokenizer);
+    }
+
     None
 }
+
+/// Turn line endings in text into hard breaks.
+///
+/// Line endings after hard breaks, and in raw text (such as code (text) and
+/// html (text)) or in the resources of links, are left alone.
+fn resolve_breaks(tokenizer: &mut Tokenizer) {
+    let mut stack = vec![];
+    let mut index = 0;
+
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if event.kind == Kind::Enter {
+            if event.name == Name::LineEnding
+                && !(index > 0
+                    && matches!(
+                        tokenizer.events[index - 1].name,
+                        Name::HardBreakEscape | Name::HardBreakTrailing
+                    ))
+                && stack.last().map_or(true, |name| {
+                    matches!(
+                        name,
+                        Name::EmphasisText
+                            | Name::GfmStrikethroughText
+                            | Name::HighlightText
+                            | Name::InsertText
+                            | Name::LabelText
+                            | Name::StrongText
+                    )
+                })
+            {
+                tokenizer.events[index].name = Name::HardBreakLineEnding;
+                tokenizer.events[index + 1].name = Name::HardBreakLineEnding;
+            }
+
+            stack.push(tokenizer.events[index].name.clone());
+        } else {
+            stack.pop();
+        }
+
+        index += 1;
+    }
+}
@@ -0,0 +1,286 @@
+//! Wiki link occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Wiki links form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: `target` and `anchor` cannot both be missing.
+//! wiki_link ::= '[[' [ target ] [ '#' anchor ] [ '|' label ] ']]'
+//!
+//! target ::= 1*( line - ( '[' | ']' | '#' | '|' ) )
+//! anchor ::= 1*( line - ( '[' | ']' | '|' ) )
+//! label ::= 1*( line - ( '[' | ']' ) )
+//! ```
+//!
+//! Wiki links are used by note-taking apps, such as Obsidian, to link to
+//! other notes by their name (`[[target]]`), optionally to a heading or
+//! block in them (`[[target#anchor]]`), optionally with different text
+//! (`[[target|label]]`).
+//! The target can be missing when there is an anchor, to link to something
+//! in the current note (`[[#anchor]]`).
+//!
+//! Nothing is interpreted in wiki links: character escapes, character
+//! references, and other constructs are not supported.
+//! Wiki links cannot span multiple lines.
+//! Like links, wiki links cannot be in links: in `[a [[b]]](c)`, the outer
+//! brackets are not a link.
+//!
+//! ## HTML
+//!
+//! Wiki links relate to the `<a>` element in HTML.
+//! See [*§ 4.5.1 The `a` element*][html_a] in the HTML spec for more info.
+//! The URL is the target, followed by `#` and the anchor if there is one,
+//! unless [`wiki_link_url`][crate::CompileOptions::wiki_link_url] is used to
+//! resolve it.
+//! The text is the label if there is one, and the target and anchor
+//! otherwise.
+//!
+//! ## Recommendation
+//!
+//! Wiki links only work in tools that know how to resolve their targets.
+//! Use regular links when the markdown is also read elsewhere.
+//!
+//! ## Tokens
+//!
+//! *   [`WikiLink`][Name::WikiLink]
+//! *   [`WikiLinkAnchor`][Name::WikiLinkAnchor]
+//! *   [`WikiLinkAnchorMarker`][Name::WikiLinkAnchorMarker]
+//! *   [`WikiLinkLabel`][Name::WikiLinkLabel]
+//! *   [`WikiLinkLabelMarker`][Name::WikiLinkLabelMarker]
+//! *   [`WikiLinkMarker`][Name::WikiLinkMarker]
+//! *   [`WikiLinkTarget`][Name::WikiLinkTarget]
+//!
+//! ## References
+//!
+//! *   [*Internal links* in Obsidian](https://help.obsidian.md/Linking+notes+and+files/Internal+links)
+//!
+//! [text]: crate::construct::text
+//! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{LabelKind, Tokenizer};
+
+/// At start of wiki link.
+///
+/// ```markdown
+/// > | a [[b]] c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.wiki_link && tokenizer.current == Some(b'[') {
+        tokenizer.enter(Name::WikiLink);
+        tokenizer.enter(Name::WikiLinkMarker);
+        tokenizer.consume();
+        State::Next(StateName::WikiLinkOpen)
+    } else {
+        State::Nok
+    }
+}
+
+/// After first `[`, at second.
+///
+/// ```markdown
+/// > | a [[b]] c
+///        ^
+/// ```
+pub fn open(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.consume();
+        tokenizer.exit(Name::WikiLinkMarker);
+        State::Next(StateName::WikiLinkTargetBefore)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `[[`, at target or anchor marker.
+///
+/// ```markdown
+/// > | a [[b]] c
+///         ^
+/// ```
+pub fn target_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'#') => State::Retry(StateName::WikiLinkTargetAfter),
+        None | Some(b'\n' | b'[' | b']' | b'|') => State::Nok,
+        Some(_) => {
+            tokenizer.enter(Name::WikiLinkTarget);
+            State::Retry(StateName::WikiLinkTarget)
+        }
+    }
+}
+
+/// In target.
+///
+/// ```markdown
+/// > | a [[b]] c
+///         ^
+/// ```
+pub fn target(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[') => State::Nok,
+        Some(b'#' | b'|' | b']') => {
+            tokenizer.exit(Name::WikiLinkTarget);
+            State::Retry(StateName::WikiLinkTargetAfter)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::WikiLinkTarget)
+        }
+    }
+}
+
+/// After target, at anchor marker, label marker, or end.
+///
+/// ```markdown
+/// > | a [[b#c]] d
+///          ^
+/// ```
+pub fn target_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'#') {
+        tokenizer.enter(Name::WikiLinkAnchorMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::WikiLinkAnchorMarker);
+        State::Next(StateName::WikiLinkAnchorBefore)
+    } else {
+        State::Retry(StateName::WikiLinkAnchorAfter)
+    }
+}
+
+/// After `#`, at anchor.
+///
+/// ```markdown
+/// > | a [[b#c]] d
+///           ^
+/// ```
+pub fn anchor_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[' | b']' | b'|') => State::Nok,
+        Some(_) => {
+            tokenizer.enter(Name::WikiLinkAnchor);
+            State::Retry(StateName::WikiLinkAnchor)
+        }
+    }
+}
+
+/// In anchor.
+///
+/// ```markdown
+/// > | a [[b#c]] d
+///           ^
+/// ```
+pub fn anchor(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[') => State::Nok,
+        Some(b'|' | b']') => {
+            tokenizer.exit(Name::WikiLinkAnchor);
+            State::Retry(StateName::WikiLinkAnchorAfter)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::WikiLinkAnchor)
+        }
+    }
+}
+
+/// After target or anchor, at label marker or end.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///          ^
+/// ```
+pub fn anchor_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'|') {
+        tokenizer.enter(Name::WikiLinkLabelMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::WikiLinkLabelMarker);
+        State::Next(StateName::WikiLinkLabelBefore)
+    } else {
+        State::Retry(StateName::WikiLinkEnd)
+    }
+}
+
+/// After `|`, at label.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///           ^
+/// ```
+pub fn label_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[' | b']') => State::Nok,
+        Some(_) => {
+            tokenizer.enter(Name::WikiLinkLabel);
+            State::Retry(StateName::WikiLinkLabel)
+        }
+    }
+}
+
+/// In label.
+///
+/// ```markdown
+/// > | a [[b|c]] d
+///           ^
+/// ```
+pub fn label(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b'[') => State::Nok,
+        Some(b']') => {
+            tokenizer.exit(Name::WikiLinkLabel);
+            State::Retry(StateName::WikiLinkEnd)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::WikiLinkLabel)
+        }
+    }
+}
+
+/// At first `]`.
+///
+/// ```markdown
+/// > | a [[b]] c
+///          ^
+/// ```
+pub fn end(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b']') {
+        tokenizer.enter(Name::WikiLinkMarker);
+        tokenizer.consume();
+        State::Next(StateName::WikiLinkEndClose)
+    } else {
+        State::Nok
+    }
+}
+
+/// At second `]`.
+///
+/// ```markdown
+/// > | a [[b]] c
+///           ^
+/// ```
+pub fn end_close(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b']') {
+        tokenizer.consume();
+        tokenizer.exit(Name::WikiLinkMarker);
+        tokenizer.exit(Name::WikiLink);
+
+        // Mark earlier link starts as no longer viable, as they would
+        // otherwise contain a link (see `label_end`).
+        let mut index = 0;
+        while index < tokenizer.tokenize_state.label_starts.len() {
+            let label_start = &mut tokenizer.tokenize_state.label_starts[index];
+            if label_start.kind != LabelKind::Image && label_start.kind != LabelKind::InlineFootnote
+            {
+                label_start.inactive = true;
+            }
+            index += 1;
+        }
+
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
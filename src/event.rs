@@ -1,15 +1,183 @@
 //! Semantic labels of things happening.
 
 use crate::unist;
-use crate::util::constant::TAB_SIZE;
 
 /// Semantic label of a span.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Name {
+    /// Abbreviation.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a HTML b
+    ///       ^^^^
+    /// ```
+    Abbreviation,
+    /// Whole abbreviation definition.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [flow content][crate::construct::flow]
+    /// *   **Content model**:
+    ///     [`AbbreviationDefinitionLabelMarker`][Name::AbbreviationDefinitionLabelMarker],
+    ///     [`AbbreviationDefinitionLabelString`][Name::AbbreviationDefinitionLabelString],
+    ///     [`AbbreviationDefinitionMarker`][Name::AbbreviationDefinitionMarker],
+    ///     [`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hypertext Markup Language
+    ///     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    AbbreviationDefinition,
+    /// Abbreviation definition label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hypertext Markup Language
+    ///      ^    ^
+    /// ```
+    AbbreviationDefinitionLabelMarker,
+    /// Abbreviation definition label string.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hypertext Markup Language
+    ///       ^^^^
+    /// ```
+    AbbreviationDefinitionLabelString,
+    /// Abbreviation definition marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hypertext Markup Language
+    ///     ^      ^
+    /// ```
+    AbbreviationDefinitionMarker,
+    /// Abbreviation definition title.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hypertext Markup Language
+    ///              ^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    AbbreviationDefinitionTitle,
     /// Attention sequence.
     ///
     /// > 👉 **Note**: this is used while parsing but compiled away.
     AttentionSequence,
+    /// Whole attributes.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text] (in headings),
+    ///     [`CodeFencedFence`][Name::CodeFencedFence],
+    ///     [`CodeText`][Name::CodeText]
+    /// *   **Content model**:
+    ///     [`AttributesMarker`][Name::AttributesMarker],
+    ///     [`AttributesValue`][Name::AttributesValue]
+    /// *   **Construct**:
+    ///     [`attributes`][crate::construct::attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | # a {#b .c}
+    ///         ^^^^^^^
+    /// ```
+    Attributes,
+    /// Attributes marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attributes`][Name::Attributes]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attributes`][crate::construct::attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | # a {#b .c}
+    ///         ^     ^
+    /// ```
+    AttributesMarker,
+    /// Attributes value.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attributes`][Name::Attributes]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attributes`][crate::construct::attributes]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | # a {#b .c}
+    ///          ^^^^^
+    /// ```
+    AttributesValue,
     /// Whole autolink.
     ///
     /// ## Info
@@ -342,6 +510,24 @@ pub enum Name {
     ///        ^^^      ^^^^       ^^^^^
     /// ```
     CharacterReferenceValue,
+    /// Citations.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`cite`][crate::construct::cite]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [@b, p. 1] c @d e
+    ///       ^^^^^^^^^^   ^^
+    /// ```
+    Cite,
     /// Whole code (fenced).
     ///
     /// ## Info
@@ -374,6 +560,7 @@ pub enum Name {
     /// *   **Context**:
     ///     [`CodeFenced`][Name::CodeFenced]
     /// *   **Content model**:
+    ///     [`Attributes`][Name::Attributes],
     ///     [`CodeFencedFenceInfo`][Name::CodeFencedFenceInfo],
     ///     [`CodeFencedFenceMeta`][Name::CodeFencedFenceMeta],
     ///     [`CodeFencedFenceSequence`][Name::CodeFencedFenceSequence],
@@ -506,6 +693,7 @@ pub enum Name {
     /// *   **Context**:
     ///     [text content][crate::construct::text]
     /// *   **Content model**:
+    ///     [`Attributes`][Name::Attributes],
     ///     [`CodeTextData`][Name::CodeTextData],
     ///     [`CodeTextSequence`][Name::CodeTextSequence],
     ///     [`LineEnding`][Name::LineEnding]
@@ -575,6 +763,154 @@ pub enum Name {
     ///     ^^
     /// ```
     Content,
+    /// `CriticMarkup`: addition.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text],
+    ///     [`CriticSubstitution`][Name::CriticSubstitution]
+    /// *   **Content model**:
+    ///     [`CriticChunk`][Name::CriticChunk],
+    ///     [`CriticMarker`][Name::CriticMarker],
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {++b++} c
+    ///       ^^^^^^^
+    /// ```
+    CriticAddition,
+    /// `CriticMarkup`: chunk of content.
+    ///
+    /// Chunks are linked together and replaced by
+    /// [text content][crate::construct::text].
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`CriticAddition`][Name::CriticAddition],
+    ///     [`CriticComment`][Name::CriticComment],
+    ///     [`CriticDeletion`][Name::CriticDeletion],
+    ///     [`CriticHighlight`][Name::CriticHighlight]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {++b++} c
+    ///          ^
+    /// ```
+    CriticChunk,
+    /// `CriticMarkup`: comment.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`CriticChunk`][Name::CriticChunk],
+    ///     [`CriticMarker`][Name::CriticMarker],
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {>>b<<} c
+    ///       ^^^^^^^
+    /// ```
+    CriticComment,
+    /// `CriticMarkup`: deletion.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text],
+    ///     [`CriticSubstitution`][Name::CriticSubstitution]
+    /// *   **Content model**:
+    ///     [`CriticChunk`][Name::CriticChunk],
+    ///     [`CriticMarker`][Name::CriticMarker],
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {--b--} c
+    ///       ^^^^^^^
+    /// ```
+    CriticDeletion,
+    /// `CriticMarkup`: highlight.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`CriticChunk`][Name::CriticChunk],
+    ///     [`CriticMarker`][Name::CriticMarker],
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {==b==} c
+    ///       ^^^^^^^
+    /// ```
+    CriticHighlight,
+    /// `CriticMarkup`: marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`CriticAddition`][Name::CriticAddition],
+    ///     [`CriticComment`][Name::CriticComment],
+    ///     [`CriticDeletion`][Name::CriticDeletion],
+    ///     [`CriticHighlight`][Name::CriticHighlight],
+    ///     [`CriticSubstitution`][Name::CriticSubstitution]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {~~b~>c~~} d
+    ///       ^^^ ^^ ^^^
+    /// ```
+    CriticMarker,
+    /// `CriticMarkup`: substitution.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`CriticAddition`][Name::CriticAddition],
+    ///     [`CriticDeletion`][Name::CriticDeletion],
+    ///     [`CriticMarker`][Name::CriticMarker]
+    /// *   **Construct**:
+    ///     [`critic_markup`][crate::construct::critic_markup]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a {~~b~>c~~} d
+    ///       ^^^^^^^^^^
+    /// ```
+    CriticSubstitution,
     /// Data.
     ///
     /// ## Info
@@ -617,6 +953,65 @@ pub enum Name {
     ///     ^^^^^^^^^^
     /// ```
     Definition,
+    /// Whole definition description.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionList`][Name::DefinitionList]
+    /// *   **Content model**:
+    ///     [`DefinitionDescriptionPrefix`][Name::DefinitionDescriptionPrefix],
+    ///     [flow content][crate::construct::flow]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | a
+    /// > | : b
+    ///     ^^^
+    /// ```
+    DefinitionDescription,
+    /// Definition description marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionDescriptionPrefix`][Name::DefinitionDescriptionPrefix]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | a
+    /// > | : b
+    ///     ^
+    /// ```
+    DefinitionDescriptionMarker,
+    /// Definition description prefix.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionDescription`][Name::DefinitionDescription]
+    /// *   **Content model**:
+    ///     [`DefinitionDescriptionMarker`][Name::DefinitionDescriptionMarker],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | a
+    /// > | : b
+    ///     ^^
+    /// ```
+    DefinitionDescriptionPrefix,
     /// Whole definition destination.
     ///
     /// ## Info
@@ -771,6 +1166,30 @@ pub enum Name {
     ///      ^
     /// ```
     DefinitionLabelString,
+    /// Whole definition list.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [document content][crate::construct::document]
+    /// *   **Content model**:
+    ///     [`BlankLineEnding`][Name::BlankLineEnding],
+    ///     [`DefinitionDescription`][Name::DefinitionDescription],
+    ///     [`DefinitionTerm`][Name::DefinitionTerm],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a
+    ///     ^
+    /// > | : b
+    ///     ^^^
+    /// ```
+    DefinitionList,
     /// Definition marker.
     ///
     /// ## Info
@@ -790,6 +1209,25 @@ pub enum Name {
     ///        ^
     /// ```
     DefinitionMarker,
+    /// Definition term.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionList`][Name::DefinitionList]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a
+    ///     ^
+    ///   | : b
+    /// ```
+    DefinitionTerm,
     /// Whole definition title.
     ///
     /// ## Info
@@ -828,25 +1266,80 @@ pub enum Name {
     /// > | [a]: b "c"
     ///            ^ ^
     /// ```
-    DefinitionTitleMarker,
-    /// Definition title data.
+    DefinitionTitleMarker,
+    /// Definition title data.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionTitle`][Name::DefinitionTitle]
+    /// *   **Content model**:
+    ///     [string content][crate::construct::string]
+    /// *   **Construct**:
+    ///     [`title`][crate::construct::partial_title]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///             ^
+    /// ```
+    DefinitionTitleString,
+    /// Emoji.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`EmojiMarker`][Name::EmojiMarker],
+    ///     [`EmojiShortcode`][Name::EmojiShortcode]
+    /// *   **Construct**:
+    ///     [`emoji`][crate::construct::emoji]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b: c
+    ///       ^^^
+    /// ```
+    Emoji,
+    /// Emoji marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Emoji`][Name::Emoji]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`emoji`][crate::construct::emoji]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b: c
+    ///       ^ ^
+    /// ```
+    EmojiMarker,
+    /// Emoji shortcode.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`DefinitionTitle`][Name::DefinitionTitle]
+    ///     [`Emoji`][Name::Emoji]
     /// *   **Content model**:
-    ///     [string content][crate::construct::string]
+    ///     void
     /// *   **Construct**:
-    ///     [`title`][crate::construct::partial_title]
+    ///     [`emoji`][crate::construct::emoji]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///             ^
+    /// > | a :b: c
+    ///        ^
     /// ```
-    DefinitionTitleString,
+    EmojiShortcode,
     /// Emphasis.
     ///
     /// ## Info
@@ -902,6 +1395,27 @@ pub enum Name {
     ///      ^
     /// ```
     EmphasisText,
+    /// Extension.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`extension`][crate::construct::extension]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a @b c
+    ///       ^^
+    /// ```
+    ///
+    /// The value is the index of the extension that matched in
+    /// [`extensions`][crate::ParseOptions::extensions].
+    Extension(usize),
     /// Whole frontmatter.
     ///
     /// ## Info
@@ -989,6 +1503,25 @@ pub enum Name {
     ///     ^^^
     /// ```
     FrontmatterSequence,
+    /// GFM extension: alert marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`BlockQuote`][Name::BlockQuote]
+    /// *   **Content model**:
+    ///     void.
+    /// *   **Construct**:
+    ///     [`gfm_alert`][crate::construct::gfm_alert]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | > [!NOTE]
+    ///       ^^^^^^^
+    ///   | > a
+    /// ```
+    GfmAlert,
     /// GFM extension: email autolink.
     ///
     /// ## Info
@@ -1307,6 +1840,7 @@ pub enum Name {
     ///     [flow content][crate::construct::flow]
     /// *   **Content model**:
     ///     [`GfmTableBody`][Name::GfmTableBody],
+    ///     [`GfmTableCaption`][Name::GfmTableCaption],
     ///     [`GfmTableHead`][Name::GfmTableHead],
     ///     [`LineEnding`][Name::LineEnding]
     /// *   **Construct**:
@@ -1344,6 +1878,70 @@ pub enum Name {
     ///     ^^^^^
     /// ```
     GfmTableBody,
+    /// GFM extension: Table caption.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GfmTable`][Name::GfmTable]
+    /// *   **Content model**:
+    ///     [`GfmTableCaptionMarker`][Name::GfmTableCaptionMarker],
+    ///     [`GfmTableCaptionText`][Name::GfmTableCaptionText],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`gfm_table`][crate::construct::gfm_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | | a |
+    ///   | | - |
+    /// > | Table: b
+    ///     ^^^^^^^^
+    /// ```
+    GfmTableCaption,
+    /// GFM extension: Table caption marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GfmTableCaption`][Name::GfmTableCaption]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`gfm_table`][crate::construct::gfm_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | | a |
+    ///   | | - |
+    /// > | Table: b
+    ///     ^^^^^^
+    /// > | [c]
+    ///     ^ ^
+    /// ```
+    GfmTableCaptionMarker,
+    /// GFM extension: Table caption text.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GfmTableCaption`][Name::GfmTableCaption]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`gfm_table`][crate::construct::gfm_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | | a |
+    ///   | | - |
+    /// > | Table: b
+    ///            ^
+    /// ```
+    GfmTableCaptionText,
     /// GFM extension: Table cell.
     ///
     /// ## Info
@@ -1632,6 +2230,159 @@ pub enum Name {
     ///        ^
     /// ```
     GfmTaskListItemValueUnchecked,
+    /// Grid table.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [flow content][crate::construct::flow]
+    /// *   **Content model**:
+    ///     [`GridTableBody`][Name::GridTableBody],
+    ///     [`GridTableBorder`][Name::GridTableBorder],
+    ///     [`GridTableHead`][Name::GridTableHead]
+    /// *   **Construct**:
+    ///     [`grid_table`][crate::construct::grid_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | +---+
+    ///     ^^^^^
+    /// > | | a |
+    ///     ^^^^^
+    /// > | +===+
+    ///     ^^^^^
+    /// > | | b |
+    ///     ^^^^^
+    /// > | +---+
+    ///     ^^^^^
+    /// ```
+    GridTable,
+    /// Grid table body.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GridTable`][Name::GridTable]
+    /// *   **Content model**:
+    ///     [`GridTableBorder`][Name::GridTableBorder],
+    ///     [`GridTableRow`][Name::GridTableRow]
+    /// *   **Construct**:
+    ///     [`grid_table`][crate::construct::grid_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | +---+
+    ///   | | a |
+    ///   | +===+
+    /// > | | b |
+    ///     ^^^^^
+    /// > | +---+
+    ///     ^^^^^
+    /// ```
+    GridTableBody,
+    /// Grid table border.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GridTable`][Name::GridTable],
+    ///     [`GridTableBody`][Name::GridTableBody],
+    ///     [`GridTableHead`][Name::GridTableHead]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`grid_table`][crate::construct::grid_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | +---+
+    ///     ^^^^^
+    ///   | | a |
+    /// > | +===+
+    ///     ^^^^^
+    ///   | | b |
+    /// > | +---+
+    ///     ^^^^^
+    /// ```
+    GridTableBorder,
+    /// Grid table cell.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GridTableRow`][Name::GridTableRow]
+    /// *   **Content model**:
+    ///     [document content][crate::construct::document]
+    /// *   **Construct**:
+    ///     [`grid_table`][crate::construct::grid_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | +-----+-----+
+    /// > | | a   | b   |
+    ///      ^^^^^ ^^^^^
+    ///   | +=====+=====+
+    /// > | | - c | d   |
+    ///      ^^^^^ ^^^^^
+    /// > | | - e |     |
+    ///      ^^^^^ ^^^^^
+    ///   | +-----+-----+
+    /// ```
+    GridTableCell,
+    /// Grid table head.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GridTable`][Name::GridTable]
+    /// *   **Content model**:
+    ///     [`GridTableBorder`][Name::GridTableBorder],
+    ///     [`GridTableRow`][Name::GridTableRow]
+    /// *   **Construct**:
+    ///     [`grid_table`][crate::construct::grid_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | +---+
+    /// > | | a |
+    ///     ^^^^^
+    /// > | +===+
+    ///     ^^^^^
+    ///   | | b |
+    ///   | +---+
+    /// ```
+    GridTableHead,
+    /// Grid table row.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`GridTableBody`][Name::GridTableBody],
+    ///     [`GridTableHead`][Name::GridTableHead]
+    /// *   **Content model**:
+    ///     [`GridTableCell`][Name::GridTableCell]
+    /// *   **Construct**:
+    ///     [`grid_table`][crate::construct::grid_table]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | +---+
+    /// > | | a |
+    ///     ^^^^^
+    ///   | +===+
+    /// > | | b |
+    ///     ^^^^^
+    /// > | | c |
+    ///     ^^^^^
+    ///   | +---+
+    /// ```
+    GridTableRow,
     /// Whole hard break (escape).
     ///
     /// ## Info
@@ -1670,6 +2421,28 @@ pub enum Name {
     /// > | b
     /// ```
     HardBreakTrailing,
+    /// Line ending that is a hard break.
+    ///
+    /// Only made when [`breaks`][crate::ParseOptions::breaks] is on, for
+    /// line endings in text that are not already after a hard break.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`text`][crate::construct::text]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a␊
+    ///      ^
+    /// > | b
+    /// ```
+    HardBreakLineEnding,
     /// Whole heading (atx).
     ///
     /// ## Info
@@ -1758,55 +2531,110 @@ pub enum Name {
     /// *   **Content model**:
     ///     [text content][crate::construct::text]
     /// *   **Construct**:
-    ///     [`heading_setext`][crate::construct::heading_setext]
+    ///     [`heading_setext`][crate::construct::heading_setext]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | alpha
+    ///     ^^^^^
+    ///   | =====
+    /// ```
+    HeadingSetextText,
+    /// Heading (setext) underline.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`HeadingSetext`][Name::HeadingSetext]
+    /// *   **Content model**:
+    ///     [`HeadingSetextUnderlineSequence`][Name::HeadingSetextUnderlineSequence],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`heading_setext`][crate::construct::heading_setext]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | alpha
+    /// > | =====
+    ///     ^^^^^
+    /// ```
+    HeadingSetextUnderline,
+    /// Heading (setext) underline sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`HeadingSetext`][Name::HeadingSetext]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`heading_setext`][crate::construct::heading_setext]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | alpha
+    /// > | =====
+    ///     ^^^^^
+    /// ```
+    HeadingSetextUnderlineSequence,
+    /// Highlight.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`HighlightSequence`][Name::HighlightSequence],
+    ///     [`HighlightText`][Name::HighlightText]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | alpha
+    /// > | ==a==
     ///     ^^^^^
-    ///   | =====
     /// ```
-    HeadingSetextText,
-    /// Heading (setext) underline.
+    Highlight,
+    /// Highlight sequence.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`HeadingSetext`][Name::HeadingSetext]
+    ///     [`Highlight`][Name::Highlight]
     /// *   **Content model**:
-    ///     [`HeadingSetextUnderlineSequence`][Name::HeadingSetextUnderlineSequence],
-    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    ///     void
     /// *   **Construct**:
-    ///     [`heading_setext`][crate::construct::heading_setext]
+    ///     [`attention`][crate::construct::attention]
     ///
     /// ## Example
     ///
     /// ```markdown
-    ///   | alpha
-    /// > | =====
-    ///     ^^^^^
+    /// > | ==a==
+    ///     ^^ ^^
     /// ```
-    HeadingSetextUnderline,
-    /// Heading (setext) underline sequence.
+    HighlightSequence,
+    /// Highlight text.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`HeadingSetext`][Name::HeadingSetext]
+    ///     [`Highlight`][Name::Highlight]
     /// *   **Content model**:
-    ///     void
+    ///     [text content][crate::construct::text]
     /// *   **Construct**:
-    ///     [`heading_setext`][crate::construct::heading_setext]
+    ///     [`attention`][crate::construct::attention]
     ///
     /// ## Example
     ///
     /// ```markdown
-    ///   | alpha
-    /// > | =====
-    ///     ^^^^^
+    /// > | ==a==
+    ///       ^
     /// ```
-    HeadingSetextUnderlineSequence,
+    HighlightText,
     /// Whole html (flow).
     ///
     /// ## Info
@@ -1883,6 +2711,61 @@ pub enum Name {
     ///       ^^^
     /// ```
     HtmlTextData,
+    /// Insert.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`InsertSequence`][Name::InsertSequence],
+    ///     [`InsertText`][Name::InsertText]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ++a++
+    ///     ^^^^^
+    /// ```
+    Insert,
+    /// Insert sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Insert`][Name::Insert]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ++a++
+    ///     ^^ ^^
+    /// ```
+    InsertSequence,
+    /// Insert text.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Insert`][Name::Insert]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ++a++
+    ///       ^
+    /// ```
+    InsertText,
     /// Image.
     ///
     /// ## Info
@@ -1907,15 +2790,35 @@ pub enum Name {
     ///       ^^^^^^^
     /// ```
     Image,
+    /// Whole inline footnote.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`Label`][Name::Label]
+    /// *   **Construct**:
+    ///     [`label_end`][crate::construct::label_end]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^^^
+    /// ```
+    InlineFootnote,
     /// Label.
     ///
     /// ## Info
     ///
     /// *   **Context**:
     ///     [`Image`][Name::Image],
+    ///     [`InlineFootnote`][Name::InlineFootnote],
     ///     [`Link`][Name::Link]
     /// *   **Content model**:
     ///     [`LabelImage`][Name::LabelImage],
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote],
     ///     [`LabelLink`][Name::LabelLink],
     ///     [`LabelEnd`][Name::LabelEnd],
     ///     [`LabelText`][Name::LabelText]
@@ -1990,6 +2893,43 @@ pub enum Name {
     ///       ^
     /// ```
     LabelImageMarker,
+    /// Label start (inline footnote).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Label`][Name::Label]
+    /// *   **Content model**:
+    ///     [`LabelInlineFootnoteMarker`][Name::LabelInlineFootnoteMarker],
+    ///     [`LabelMarker`][Name::LabelMarker]
+    /// *   **Construct**:
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    LabelInlineFootnote,
+    /// Label start (inline footnote) marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^
+    /// ```
+    LabelInlineFootnoteMarker,
     /// Label start (link).
     ///
     /// ## Info
@@ -2014,12 +2954,14 @@ pub enum Name {
     ///
     /// *   **Context**:
     ///     [`LabelImage`][Name::LabelImage],
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote],
     ///     [`LabelLink`][Name::LabelLink],
     ///     [`LabelEnd`][Name::LabelEnd]
     /// *   **Content model**:
     ///     void
     /// *   **Construct**:
     ///     [`label_start_image`][crate::construct::label_start_image],
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote],
     ///     [`label_start_link`][crate::construct::label_start_link],
     ///     [`label_end`][crate::construct::label_end]
     ///
@@ -2997,6 +3939,61 @@ pub enum Name {
     ///          ^
     /// ```
     MdxJsxTagSelfClosingMarker,
+    /// Obsidian extension: whole block identifier.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`ObsidianBlockIdMarker`][Name::ObsidianBlockIdMarker],
+    ///     [`ObsidianBlockIdValue`][Name::ObsidianBlockIdValue]
+    /// *   **Construct**:
+    ///     [`obsidian_block_id`][crate::construct::obsidian_block_id]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^b
+    ///       ^^
+    /// ```
+    ObsidianBlockId,
+    /// Obsidian extension: block identifier marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`ObsidianBlockId`][Name::ObsidianBlockId]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`obsidian_block_id`][crate::construct::obsidian_block_id]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^b
+    ///       ^
+    /// ```
+    ObsidianBlockIdMarker,
+    /// Obsidian extension: block identifier value.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`ObsidianBlockId`][Name::ObsidianBlockId]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`obsidian_block_id`][crate::construct::obsidian_block_id]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^b
+    ///        ^
+    /// ```
+    ObsidianBlockIdValue,
     /// Paragraph.
     ///
     /// ## Info
@@ -3266,6 +4263,24 @@ pub enum Name {
     ///                 ^
     /// ```
     ResourceTitleString,
+    /// Smart punctuation.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`smart_punctuation`][crate::construct::smart_punctuation]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | "a" -- b...
+    ///     ^ ^ ^^  ^^^
+    /// ```
+    SmartPunctuation,
     /// SpaceOrTab.
     ///
     /// ## Info
@@ -3376,11 +4391,152 @@ pub enum Name {
     ///     ^ ^ ^
     /// ```
     ThematicBreakSequence,
+    /// Wiki link extension: whole wiki link.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`WikiLinkAnchor`][Name::WikiLinkAnchor],
+    ///     [`WikiLinkAnchorMarker`][Name::WikiLinkAnchorMarker],
+    ///     [`WikiLinkLabel`][Name::WikiLinkLabel],
+    ///     [`WikiLinkLabelMarker`][Name::WikiLinkLabelMarker],
+    ///     [`WikiLinkMarker`][Name::WikiLinkMarker],
+    ///     [`WikiLinkTarget`][Name::WikiLinkTarget]
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///       ^^^^^^^^^
+    /// ```
+    WikiLink,
+    /// Wiki link extension: anchor.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///           ^
+    /// ```
+    WikiLinkAnchor,
+    /// Wiki link extension: anchor marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///          ^
+    /// ```
+    WikiLinkAnchorMarker,
+    /// Wiki link extension: label.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///             ^
+    /// ```
+    WikiLinkLabel,
+    /// Wiki link extension: label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///            ^
+    /// ```
+    WikiLinkLabelMarker,
+    /// Wiki link extension: marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///       ^^     ^^
+    /// ```
+    WikiLinkMarker,
+    /// Wiki link extension: target.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`WikiLink`][Name::WikiLink]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`wiki_link`][crate::construct::wiki_link]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a [[b#c|d]] e
+    ///         ^
+    /// ```
+    WikiLinkTarget,
 }
 
 /// List of void events, used to make sure everything is working well.
-pub const VOID_EVENTS: [Name; 76] = [
+///
+/// [`Extension`][Name::Extension] is void too, but not listed, as it has a
+/// value.
+pub const VOID_EVENTS: [Name; 105] = [
+    Name::Abbreviation,
+    Name::AbbreviationDefinitionLabelMarker,
+    Name::AbbreviationDefinitionLabelString,
+    Name::AbbreviationDefinitionMarker,
+    Name::AbbreviationDefinitionTitle,
     Name::AttentionSequence,
+    Name::AttributesMarker,
+    Name::AttributesValue,
     Name::AutolinkEmail,
     Name::AutolinkMarker,
     Name::AutolinkProtocol,
@@ -3394,17 +4550,24 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::CharacterReferenceMarkerNumeric,
     Name::CharacterReferenceMarkerSemi,
     Name::CharacterReferenceValue,
+    Name::Cite,
     Name::CodeFencedFenceSequence,
     Name::CodeFlowChunk,
     Name::CodeTextData,
     Name::CodeTextSequence,
+    Name::CriticChunk,
+    Name::CriticMarker,
     Name::Data,
+    Name::DefinitionDescriptionMarker,
     Name::DefinitionDestinationLiteralMarker,
     Name::DefinitionLabelMarker,
     Name::DefinitionMarker,
     Name::DefinitionTitleMarker,
+    Name::EmojiMarker,
+    Name::EmojiShortcode,
     Name::EmphasisSequence,
     Name::FrontmatterChunk,
+    Name::GfmAlert,
     Name::GfmAutolinkLiteralEmail,
     Name::GfmAutolinkLiteralProtocol,
     Name::GfmAutolinkLiteralWww,
@@ -3412,20 +4575,26 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::GfmFootnoteDefinitionLabelMarker,
     Name::GfmFootnoteDefinitionMarker,
     Name::GfmStrikethroughSequence,
+    Name::GfmTableCaptionMarker,
     Name::GfmTableCellDivider,
     Name::GfmTableDelimiterMarker,
     Name::GfmTableDelimiterFiller,
     Name::GfmTaskListItemMarker,
     Name::GfmTaskListItemValueChecked,
     Name::GfmTaskListItemValueUnchecked,
+    Name::GridTableBorder,
     Name::FrontmatterSequence,
     Name::HardBreakEscape,
     Name::HardBreakTrailing,
+    Name::HardBreakLineEnding,
     Name::HeadingAtxSequence,
     Name::HeadingSetextUnderlineSequence,
+    Name::HighlightSequence,
     Name::HtmlFlowData,
     Name::HtmlTextData,
+    Name::InsertSequence,
     Name::LabelImageMarker,
+    Name::LabelInlineFootnoteMarker,
     Name::LabelMarker,
     Name::LineEnding,
     Name::ListItemMarker,
@@ -3450,12 +4619,21 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::MdxJsxTagAttributeNameLocal,
     Name::MdxJsxTagAttributeValueLiteralMarker,
     Name::MdxJsxEsWhitespace,
+    Name::ObsidianBlockIdMarker,
+    Name::ObsidianBlockIdValue,
     Name::ReferenceMarker,
     Name::ResourceMarker,
     Name::ResourceTitleMarker,
+    Name::SmartPunctuation,
     Name::SpaceOrTab,
     Name::StrongSequence,
     Name::ThematicBreakSequence,
+    Name::WikiLinkAnchor,
+    Name::WikiLinkAnchorMarker,
+    Name::WikiLinkLabel,
+    Name::WikiLinkLabelMarker,
+    Name::WikiLinkMarker,
+    Name::WikiLinkTarget,
 ];
 
 /// Embedded content type.
@@ -3487,6 +4665,12 @@ pub struct Link {
 ///
 /// The interface for the location in the document comes from unist
 /// [`Point`](https://github.com/syntax-tree/unist#point).
+///
+/// `line` and `column` are tracked while tokenizing, instead of computed
+/// from `index` when positions are needed, because tokenizing depends on
+/// them: `column` decides how many virtual spaces a tab is worth (up to the
+/// next tab stop), and `line` finds where containers (such as block quotes)
+/// let each line start.
 #[derive(Clone, Debug)]
 pub struct Point {
     /// 1-indexed line number.
@@ -3516,8 +4700,8 @@ impl Point {
     }
 
     /// Create a new point, that is shifted from the close earlier current
-    /// point, to `index`.
-    pub fn shift_to(&self, bytes: &[u8], index: usize) -> Point {
+    /// point, to `index`, with tabs stopping at every `tab_size` columns.
+    pub fn shift_to(&self, bytes: &[u8], index: usize, tab_size: usize) -> Point {
         let mut next = self.clone();
         debug_assert!(index > next.index, "expected to shift forward");
 
@@ -3525,11 +4709,11 @@ impl Point {
             match bytes[next.index] {
                 b'\n' | b'\r' => unreachable!("cannot move past line endings"),
                 b'\t' => {
-                    let remainder = next.column % TAB_SIZE;
+                    let remainder = next.column % tab_size;
                     let vs = if remainder == 0 {
                         0
                     } else {
-                        TAB_SIZE - remainder
+                        tab_size - remainder
                     };
                     next.index += 1;
                     next.column += 1 + vs;
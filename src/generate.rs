@@ -1,141 +1,1723 @@
-
-use alloc::string::String;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write as _;
 
 use crate::mdast;
 use crate::mdast::Node;
+use crate::message;
+use crate::util::{char, cite::key_needs_braces};
+use crate::{BreakKind, LineEnding, SerializeOptions};
 
 /// Converts an mdast node into a markdown string.
-/// 
-/// This will convert to a canonical representation, 
-/// and will not take into account how the original 
-/// element was formatted. For example, underlined 
-/// headers will be converted to their '#`-prefixed 
-/// equivalents and so on.
+///
+/// This will convert to a canonical representation,
+/// except where nodes record how the original element
+/// was formatted. For example, headers are `#`-prefixed,
+/// unless they record that they were underlined, and
+/// emphasis uses `*`, unless it records `_`.
 pub fn to_markdown(node: &mdast::Node) -> String {
+    serialize(node, &SerializeOptions::default())
+}
+
+/// Writes an mdast node as markdown into `out`, like [`to_markdown()`][]
+/// does.
+pub fn to_markdown_to(node: &mdast::Node, out: &mut dyn fmt::Write) -> fmt::Result {
+    write(node, &SerializeOptions::default(), out)
+}
+
+/// Converts an mdast node into a markdown string, in the style of `options`.
+///
+/// Options that are not set fall back to how nodes record that the original
+/// element was formatted, and then to the canonical representation.
+pub fn serialize(node: &mdast::Node, options: &SerializeOptions) -> String {
+    let mut result = String::new();
+    // Writing to strings does not fail.
+    let _ = write(node, options, &mut result);
+    result
+}
+
+/// Writes an mdast node as markdown into `out`, in the style of `options`.
+///
+/// Markdown is written as it is generated: only nodes that need to know the
+/// size of their content (such as setext headings, wrapped paragraphs, and
+/// padded tables) are first serialized into strings.
+///
+/// The tree is walked with a stack of steps instead of by recursion, so that
+/// deeply nested trees (such as thousands of block quotes) do not overflow
+/// the call stack.
+pub fn write(
+    node: &mdast::Node,
+    options: &SerializeOptions,
+    out: &mut dyn fmt::Write,
+) -> fmt::Result {
+    let mut out = Printer::new(out, options.line_ending.clone());
+    // Block quotes change the options of their descendants.
+    let mut scopes = vec![options.clone()];
+    let mut steps = vec![Step::Node(node)];
+    // Markers of open lists, and of the list just before the next block.
+    let mut bullets = Vec::new();
+    let mut bullet_last = None;
+
+    while let Some(step) = steps.pop() {
+        let options = &scopes[scopes.len() - 1];
+        let node = match step {
+            Step::Node(node) => node,
+            Step::Flow(node) => {
+                if !matches!(node, Node::List(_)) {
+                    bullet_last = None;
+                }
+                // HTML (flow), unlike HTML (text), needs a blank line after
+                // it, as it would otherwise continue.
+                if let Node::Html(_) = node {
+                    steps.push(Step::Write("\n\n".into()));
+                } else if let Node::Comment(_) = node {
+                    steps.push(Step::Write("\n".into()));
+                }
+                steps.push(Step::Node(node));
+                continue;
+            }
+            Step::Write(value) => {
+                out.write_str(&value)?;
+                continue;
+            }
+            Step::Separate => {
+                out.separate();
+                continue;
+            }
+            Step::Item(node, marker) => {
+                // Every line but the first is indented by the size of the
+                // marker.
+                let inner = SerializeOptions {
+                    line_width: options.line_width.map(|d| d.saturating_sub(marker.len())),
+                    ..options.clone()
+                };
+                scopes.push(inner);
+                out.write_str(&marker)?;
+                out.push(Layer::Prefix {
+                    marker: " ".repeat(marker.len()),
+                    blank: "",
+                    pending: false,
+                });
+                out.push(Layer::Blocks {
+                    since: out.texts,
+                    held: 0,
+                });
+                steps.push(Step::Exit(node));
+                if let Node::ListItem(n) = node {
+                    for (index, child) in n.children.iter().enumerate().rev() {
+                        steps.push(Step::Flow(child));
+                        // Tight items only need blank lines where the next
+                        // block would otherwise join a paragraph.
+                        if index > 0
+                            && (n.spread
+                                || (matches!(n.children[index - 1], Node::Paragraph(_))
+                                    && matches!(child, Node::Paragraph(_) | Node::Definition(_))))
+                        {
+                            steps.push(Step::Separate);
+                        }
+                    }
+                }
+                continue;
+            }
+            Step::Exit(node) => {
+                match node {
+                    Node::Root(_) => out.finish(true)?,
+                    Node::BlockQuote(_) | Node::DefinitionDescription(_) | Node::ListItem(_) => {
+                        scopes.pop();
+                        out.finish(false)?;
+                        out.pop_prefix();
+                        out.write_char('\n')?;
+                    }
+                    Node::MdxJsxFlowElement(n) => {
+                        out.finish(false)?;
+                        out.write_char('\n')?;
+                        mdx_jsx_closing(&mut out, n.name.as_deref())?;
+                        out.write_str("\n\n")?;
+                    }
+                    Node::MdxJsxTextElement(n) => mdx_jsx_closing(&mut out, n.name.as_deref())?,
+                    Node::List(_) => bullet_last = bullets.pop(),
+                    Node::TableCell(_) => out.cells -= 1,
+                    _ => {}
+                }
+                continue;
+            }
+        };
+
+        match node {
+            Node::Root(n) => {
+                out.push(Layer::Blocks {
+                    since: out.texts,
+                    held: 0,
+                });
+                steps.push(Step::Exit(node));
+                for child in n.children.iter().rev() {
+                    steps.push(Step::Flow(child));
+                    // Blocks, such as definitions after paragraphs, need a
+                    // blank line between them.
+                    steps.push(Step::Separate);
+                }
+            }
+            Node::BlockQuote(n) => {
+                // Every line is prefixed with `> `.
+                let inner = SerializeOptions {
+                    line_width: options.line_width.map(|d| d.saturating_sub(2)),
+                    ..options.clone()
+                };
+                scopes.push(inner);
+                out.write_str("> ")?;
+                out.push(Layer::Prefix {
+                    marker: "> ".into(),
+                    blank: ">",
+                    pending: false,
+                });
+                if let Some(alert) = n.alert {
+                    out.write_str("[!")?;
+                    out.write_str(alert.name())?;
+                    out.write_str("]\n")?;
+                }
+                out.push(Layer::Blocks {
+                    since: out.texts,
+                    held: 0,
+                });
+                steps.push(Step::Exit(node));
+                for child in n.children.iter().rev() {
+                    steps.push(Step::Flow(child));
+                    steps.push(Step::Separate);
+                }
+            }
+            Node::FootnoteDefinition(_) => todo!(),
+            Node::MdxJsxFlowElement(n) => {
+                mdx_jsx_opening(
+                    &mut out,
+                    n.name.as_deref(),
+                    &n.attributes,
+                    n.children.is_empty(),
+                )?;
+                if n.children.is_empty() {
+                    out.write_str("\n\n")?;
+                } else {
+                    out.write_char('\n')?;
+                    out.push(Layer::Blocks {
+                        since: out.texts,
+                        held: 0,
+                    });
+                    steps.push(Step::Exit(node));
+                    for child in n.children.iter().rev() {
+                        steps.push(Step::Flow(child));
+                        steps.push(Step::Separate);
+                    }
+                }
+            }
+            Node::List(n) => {
+                let mut bullet = if n.ordered {
+                    options.bullet_ordered.or(n.marker).unwrap_or('.')
+                } else {
+                    options.bullet.or(n.marker).unwrap_or('*')
+                };
+                // A list right after a list with the same marker would join
+                // it, and items starting with a thematic break made with the
+                // marker would turn into one.
+                if bullet_last == Some(bullet)
+                    || (!n.ordered
+                        && bullet == options.rule
+                        && n.children.iter().any(|item| {
+                            matches!(
+                                item.children().and_then(|d| d.first()),
+                                Some(Node::ThematicBreak(_))
+                            )
+                        }))
+                {
+                    bullet = match bullet {
+                        '.' => ')',
+                        ')' => '.',
+                        '*' => '-',
+                        _ => '*',
+                    };
+                }
+                bullets.push(bullet);
+                let mut list = Vec::new();
+                let mut number = n.start.unwrap_or(1);
+                for child in &n.children {
+                    if let Node::ListItem(_) = child {
+                        let mut marker = String::new();
+                        if n.ordered {
+                            marker.push_str(&number.to_string());
+                            if options.increment_list_marker {
+                                number += 1;
+                            }
+                        }
+                        marker.push(bullet);
+                        marker.push(' ');
+                        list.push(Step::Item(child, marker));
+                    } else {
+                        list.push(Step::Node(child));
+                    }
+                }
+                list.push(Step::Write("\n".into()));
+                list.push(Step::Exit(node));
+                steps.extend(list.into_iter().rev());
+            }
+            Node::DefinitionList(n) => {
+                let mut list = Vec::new();
+                for (index, child) in n.children.iter().enumerate() {
+                    // Spread lists have blank lines between terms and
+                    // descriptions, but terms of one description cannot be
+                    // apart.
+                    if n.spread
+                        && index > 0
+                        && !(matches!(child, Node::DefinitionTerm(_))
+                            && matches!(n.children[index - 1], Node::DefinitionTerm(_)))
+                    {
+                        list.push(Step::Write("\n".into()));
+                    }
+                    list.push(Step::Node(child));
+                }
+                list.push(Step::Write("\n".into()));
+                steps.extend(list.into_iter().rev());
+            }
+            Node::MdxjsEsm(_) => todo!(),
+            Node::Toml(n) => frontmatter(&mut out, "+++", &n.value)?,
+            Node::Yaml(n) => frontmatter(&mut out, "---", &n.value)?,
+            Node::Break(_) => match options.break_style {
+                BreakKind::Backslash => out.write_str("\\\n")?,
+                BreakKind::Spaces => out.write_str("  \n")?,
+            },
+            Node::CharacterReference(n) => out.write_str(&n.raw)?,
+            Node::Emoji(n) => {
+                out.write_char(':')?;
+                out.write_str(&n.shortcode)?;
+                out.write_char(':')?;
+            }
+            Node::Abbreviation(n) => out.write_str(&n.value)?,
+            Node::BlockId(n) => {
+                out.write_char('^')?;
+                out.write_str(&n.value)?;
+            }
+            Node::InlineCode(n) => {
+                raw_text(&mut out, &n.value, '`')?;
+                if let Some(attributes) = &n.attributes {
+                    write_attributes(&mut out, attributes)?;
+                }
+            }
+            Node::InlineMath(n) => raw_text(&mut out, &n.value, '$')?,
+            Node::CriticAddition(_)
+            | Node::CriticComment(_)
+            | Node::CriticDeletion(_)
+            | Node::CriticHighlight(_)
+            | Node::Delete(_)
+            | Node::Emphasis(_)
+            | Node::Highlight(_)
+            | Node::Insert(_)
+            | Node::LinkReference(_)
+            | Node::Strong(_) => {
+                let intraword =
+                    out.last.map_or(false, word) || first_char(steps.last()).map_or(false, word);
+                let (open, close) = delimiters(node, options, intraword).unwrap_or_default();
+                out.write_str(&open)?;
+                steps.push(Step::Write(close.into()));
+                steps.extend(node.children().into_iter().flatten().rev().map(Step::Node));
+            }
+            Node::CriticSubstitution(n) => {
+                // The children are the old and the new content, which share
+                // the braces.
+                out.write_str("{~~")?;
+                steps.push(Step::Write("~~}".into()));
+                for (index, child) in n.children.iter().enumerate().rev() {
+                    steps.extend(child.children().into_iter().flatten().rev().map(Step::Node));
+                    if index > 0 {
+                        steps.push(Step::Write("~>".into()));
+                    }
+                }
+            }
+            Node::MdxTextExpression(_) => todo!(),
+            Node::FootnoteReference(_) => todo!(),
+            Node::Html(n) => out.write_str(&n.value)?,
+            Node::Comment(n) => {
+                out.write_str("<!--")?;
+                out.write_str(&n.value)?;
+                out.write_str("-->")?;
+            }
+            Node::Image(_) => todo!(),
+            Node::ImageReference(n) => {
+                out.write_str("![")?;
+                out.write_str(&n.alt)?;
+                out.write_char(']')?;
+                reference(
+                    &mut out,
+                    n.reference_kind,
+                    n.label.as_deref(),
+                    &n.identifier,
+                )?;
+            }
+            Node::MdxJsxTextElement(n) => {
+                mdx_jsx_opening(
+                    &mut out,
+                    n.name.as_deref(),
+                    &n.attributes,
+                    n.children.is_empty(),
+                )?;
+                if !n.children.is_empty() {
+                    steps.push(Step::Exit(node));
+                    steps.extend(n.children.iter().rev().map(Step::Node));
+                }
+            }
+            Node::Link(n) => {
+                let (open, close) = match n.link_kind {
+                    Some(mdast::LinkKind::Autolink) => ("<".into(), ">".into()),
+                    Some(mdast::LinkKind::GfmAutolinkLiteral) => (String::new(), String::new()),
+                    _ => delimiters(node, options, false).unwrap_or_default(),
+                };
+                out.write_str(&open)?;
+                steps.push(Step::Write(close.into()));
+                steps.extend(n.children.iter().rev().map(Step::Node));
+            }
+            Node::WikiLink(n) => {
+                out.write_str("[[")?;
+                out.write_str(&n.target)?;
+                if let Some(anchor) = &n.anchor {
+                    out.write_char('#')?;
+                    out.write_str(anchor)?;
+                }
+                if let Some(label) = &n.label {
+                    out.write_char('|')?;
+                    out.write_str(label)?;
+                }
+                out.write_str("]]")?;
+            }
+            Node::Cite(n) => cite(&mut out, n)?,
+            Node::Text(n) => out.write_str(&n.value)?,
+            Node::Code(n) => {
+                let mut attributes = String::new();
+                if let Some(value) = &n.attributes {
+                    write_attributes(&mut attributes, value)?;
+                }
+                let info = n.lang.iter().chain(n.meta.iter()).chain(Some(&attributes));
+                // Backticks cannot occur in the info string of backtick
+                // fences.
+                let marker = match options.fence.or(n.fence_marker) {
+                    Some('~') => '~',
+                    _ if info.clone().any(|d| d.contains('`')) => '~',
+                    _ => '`',
+                };
+                // The fence must be longer than any run of the marker in the
+                // value, which would otherwise close it.
+                let size = n
+                    .fence_size
+                    .unwrap_or(3)
+                    .max(longest_run(&n.value, marker) + 1);
+                repeat(&mut out, marker, size)?;
+                if let Some(lang) = &n.lang {
+                    out.write_str(lang)?;
+                    if let Some(meta) = &n.meta {
+                        out.write_char(' ')?;
+                        out.write_str(meta)?;
+                    }
+                    if !attributes.is_empty() {
+                        out.write_char(' ')?;
+                    }
+                }
+                out.write_str(&attributes)?;
+                out.write_char('\n')?;
+                if !n.value.is_empty() {
+                    out.write_str(&n.value)?;
+                    out.write_char('\n')?;
+                }
+                repeat(&mut out, marker, size)?;
+                out.write_str("\n\n")?;
+            }
+            Node::Math(n) => {
+                let size = 2.max(longest_run(&n.value, '$') + 1);
+                repeat(&mut out, '$', size)?;
+                if let Some(meta) = &n.meta {
+                    out.write_str(meta)?;
+                }
+                out.write_char('\n')?;
+                if !n.value.is_empty() {
+                    out.write_str(&n.value)?;
+                    out.write_char('\n')?;
+                }
+                repeat(&mut out, '$', size)?;
+                out.write_str("\n\n")?;
+            }
+            Node::MdxFlowExpression(_) => todo!(),
+            Node::Heading(n) => {
+                let setext = options
+                    .setext
+                    .unwrap_or(n.heading_kind == Some(mdast::HeadingKind::Setext));
+                // Underlines are as long as the content, so it is serialized
+                // first.
+                let mut content = if setext && n.depth <= 2 {
+                    n.children
+                        .iter()
+                        .map(|child| serialize(child, options))
+                        .collect::<String>()
+                } else {
+                    String::new()
+                };
+                if content.is_empty() {
+                    repeat(&mut out, '#', n.depth.into())?;
+                    out.write_char(' ')?;
+                    let mut end = String::new();
+                    if let Some(attributes) = &n.attributes {
+                        if !n.children.is_empty() {
+                            end.push(' ');
+                        }
+                        write_attributes(&mut end, attributes)?;
+                    }
+                    end.push_str("\n\n");
+                    steps.push(Step::Write(end.into()));
+                    steps.extend(n.children.iter().rev().map(Step::Node));
+                } else {
+                    if let Some(attributes) = &n.attributes {
+                        content.push(' ');
+                        write_attributes(&mut content, attributes)?;
+                    }
+                    let size = content
+                        .rsplit('\n')
+                        .next()
+                        .unwrap_or("")
+                        .chars()
+                        .count()
+                        .max(1);
+                    out.write_str(&content)?;
+                    out.write_char('\n')?;
+                    repeat(&mut out, if n.depth == 1 { '=' } else { '-' }, size)?;
+                    out.write_str("\n\n")?;
+                }
+            }
+            // Cells with flow content (such as lists) need grid tables.
+            Node::Table(n) if grid(n) => grid_table(n, options, &mut out)?,
+            Node::Table(n) if options.pad_tables => {
+                let rows = n
+                    .children
+                    .iter()
+                    .map(|row| {
+                        row.children()
+                            .into_iter()
+                            .flatten()
+                            .map(|cell| serialize(cell, options))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                let size = rows
+                    .iter()
+                    .map(Vec::len)
+                    .max()
+                    .unwrap_or(0)
+                    .max(n.align.len());
+                // Delimiters need at least three characters.
+                let mut widths = vec![3; size];
+                for row in &rows {
+                    for (column, cell) in row.iter().enumerate() {
+                        widths[column] = widths[column].max(width(cell));
+                    }
+                }
+                for (index, row) in rows.iter().enumerate() {
+                    out.write_char('|')?;
+                    for (column, size) in widths.iter().enumerate() {
+                        let cell = row.get(column).map_or("", String::as_str);
+                        let pad = size - width(cell);
+                        let before = match n.align.get(column) {
+                            Some(mdast::AlignKind::Right) => pad,
+                            Some(mdast::AlignKind::Center) => pad / 2,
+                            _ => 0,
+                        };
+                        out.write_char(' ')?;
+                        repeat(&mut out, ' ', before)?;
+                        out.write_str(cell)?;
+                        repeat(&mut out, ' ', pad - before)?;
+                        out.write_str(" |")?;
+                    }
+                    out.write_char('\n')?;
+                    if index == 0 {
+                        out.write_char('|')?;
+                        for (column, size) in widths.iter().enumerate() {
+                            let (start, end) = match n.align.get(column) {
+                                Some(mdast::AlignKind::Left) => (":", ""),
+                                Some(mdast::AlignKind::Right) => ("", ":"),
+                                Some(mdast::AlignKind::Center) => (":", ":"),
+                                _ => ("", ""),
+                            };
+                            out.write_char(' ')?;
+                            out.write_str(start)?;
+                            repeat(&mut out, '-', size - start.len() - end.len())?;
+                            out.write_str(end)?;
+                            out.write_str(" |")?;
+                        }
+                        out.write_char('\n')?;
+                    }
+                }
+                if let Some(caption) = &n.caption {
+                    out.write_str("Table: ")?;
+                    for child in caption {
+                        out.write_str(&serialize(child, options))?;
+                    }
+                    out.write_char('\n')?;
+                }
+                out.write_char('\n')?;
+            }
+            Node::Table(n) => {
+                let mut table = Vec::new();
+                for (index, row) in n.children.iter().enumerate() {
+                    table.push(Step::Node(row));
+                    if index == 0 {
+                        let size = row.children().map_or(0, Vec::len).max(n.align.len());
+                        let mut delimiter = String::from("|");
+                        for column in 0..size {
+                            delimiter.push_str(match n.align.get(column) {
+                                Some(mdast::AlignKind::Left) => " :-- |",
+                                Some(mdast::AlignKind::Right) => " --: |",
+                                Some(mdast::AlignKind::Center) => " :-: |",
+                                _ => " --- |",
+                            });
+                        }
+                        delimiter.push('\n');
+                        table.push(Step::Write(delimiter.into()));
+                    }
+                }
+                if let Some(caption) = &n.caption {
+                    table.push(Step::Write("Table: ".into()));
+                    table.extend(caption.iter().map(Step::Node));
+                    table.push(Step::Write("\n".into()));
+                }
+                table.push(Step::Write("\n".into()));
+                steps.extend(table.into_iter().rev());
+            }
+            // Blank lines around the rule keep a paragraph before it from
+            // turning into a heading (setext); the root adds the one before.
+            Node::ThematicBreak(_) => {
+                repeat(&mut out, options.rule, 3)?;
+                out.write_str("\n\n")?;
+            }
+            Node::TableRow(n) => {
+                out.write_char('|')?;
+                steps.push(Step::Write("\n".into()));
+                for child in n.children.iter().rev() {
+                    steps.push(Step::Write(" |".into()));
+                    steps.push(Step::Node(child));
+                    steps.push(Step::Write(" ".into()));
+                }
+            }
+            Node::TableCell(n) => {
+                // Pipes in cells would start a new cell.
+                out.cells += 1;
+                steps.push(Step::Exit(node));
+                steps.extend(n.children.iter().rev().map(Step::Node));
+            }
+            Node::ListItem(_) => {
+                let mut marker = String::new();
+                marker.push(options.bullet.unwrap_or('*'));
+                marker.push(' ');
+                steps.push(Step::Item(node, marker));
+            }
+            Node::DefinitionTerm(n) => {
+                steps.push(Step::Write("\n".into()));
+                steps.extend(n.children.iter().rev().map(Step::Node));
+            }
+            Node::DefinitionDescription(n) => {
+                // Every line but the first is indented by four spaces.
+                let inner = SerializeOptions {
+                    line_width: options.line_width.map(|d| d.saturating_sub(4)),
+                    ..options.clone()
+                };
+                scopes.push(inner);
+                out.write_str(": ")?;
+                out.push(Layer::Prefix {
+                    marker: "    ".into(),
+                    blank: "",
+                    pending: false,
+                });
+                out.push(Layer::Blocks {
+                    since: out.texts,
+                    held: 0,
+                });
+                steps.push(Step::Exit(node));
+                for child in n.children.iter().rev() {
+                    steps.push(Step::Flow(child));
+                    steps.push(Step::Separate);
+                }
+            }
+            Node::Definition(n) => {
+                out.write_char('[')?;
+                out.write_str(n.label.as_ref().unwrap_or(&n.identifier))?;
+                out.write_str("]: ")?;
+                // Empty URLs, and URLs with whitespace or parens, need
+                // brackets.
+                if n.url.is_empty()
+                    || n.url
+                        .contains(|char: char| char.is_whitespace() || char == '(' || char == ')')
+                {
+                    out.write_char('<')?;
+                    out.write_str(&n.url.replace('<', "\\<").replace('>', "\\>"))?;
+                    out.write_char('>')?;
+                } else {
+                    out.write_str(&n.url)?;
+                }
+                if let Some(title) = &n.title {
+                    out.write_str(" \"")?;
+                    out.write_str(&title.replace('"', "\\\""))?;
+                    out.write_char('"')?;
+                }
+                out.write_char('\n')?;
+            }
+            Node::AbbreviationDefinition(n) => {
+                out.write_str("*[")?;
+                out.write_str(&n.label)?;
+                out.write_str("]:")?;
+                if let Some(title) = &n.title {
+                    out.write_char(' ')?;
+                    out.write_str(title)?;
+                }
+                out.write_char('\n')?;
+            }
+            Node::Paragraph(n) => {
+                if let Some(width) = options.line_width {
+                    let mut segments = Vec::new();
+                    for child in &n.children {
+                        phrasing_segments(child, options, &mut segments);
+                    }
+                    out.write_str(&wrap(&segments, width))?;
+                    out.write_char('\n')?;
+                } else {
+                    steps.push(Step::Write("\n".into()));
+                    steps.extend(n.children.iter().rev().map(Step::Node));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts an mdast node into a markdown string, copying the children of
+/// the root that are the same as in `original` from `source`, which
+/// `original` was parsed from.
+///
+/// Whitespace between copied children is copied too.
+/// Other children are converted like [`to_markdown()`][] does.
+pub fn preserve(node: &mdast::Node, original: &mdast::Node, source: &str) -> String {
+    let mut nodes = BTreeMap::new();
+    collect(original, &mut Vec::new(), &mut nodes);
+    copy(node, &nodes, source, false)
+}
+
+/// Converts an mdast node into a markdown string, copying the nodes that are
+/// the same as in `original` from `source`, which `original` was parsed from,
+/// and regenerating only the nodes that changed.
+///
+/// Nodes that are the same as in `original` except for their children keep
+/// their markup from `source` (such as the `# ` of a heading), with their
+/// children spliced in, if they have as many children as before.
+/// Changed children that span several lines can only be spliced into the
+/// root: other parents of them are regenerated.
+pub fn splice(node: &mdast::Node, original: &mdast::Node, source: &str) -> String {
+    let mut nodes = BTreeMap::new();
+    collect(original, &mut Vec::new(), &mut nodes);
+    copy(node, &nodes, source, true)
+}
+
+/// Nodes by where they start and end, with where their parents (other than
+/// the root) start and end, as the markup of those parents (such as the `> `
+/// of block quotes) is part of the source of their children.
+type Nodes<'a> = BTreeMap<(usize, usize), Vec<(&'a Node, Vec<(usize, usize)>)>>;
+
+/// Converts `node` into a markdown string, copying its children that are in
+/// `nodes` from `source`, and splicing changed grandchildren into otherwise
+/// unchanged children when `deep`.
+fn copy(node: &Node, nodes: &Nodes, source: &str, deep: bool) -> String {
+    if let Some(value) = verbatim(node, &[], nodes, source) {
+        return value.into();
+    }
+
+    let children = if let Node::Root(n) = node {
+        &n.children
+    } else {
+        return reuse(node, &[], nodes, source, deep).unwrap_or_else(|| to_markdown(node));
+    };
+    let mut result = String::new();
+    // Where `source` continues after the last copied child.
+    let mut end = Some(0);
+    for child in children {
+        let value = reuse(child, &[], nodes, source, deep);
+        let gap = match (&value, end, child.position()) {
+            (Some(_), Some(end), Some(position)) => source
+                .get(end..position.start.offset)
+                .filter(|d| d.trim().is_empty()),
+            _ => None,
+        };
+        if let Some(gap) = gap {
+            result.push_str(gap);
+        } else if !result.is_empty() {
+            if !result.ends_with('\n') {
+                result.push('\n');
+            }
+            if !result.ends_with("\n\n") {
+                result.push('\n');
+            }
+        }
+        if let Some(value) = value {
+            result.push_str(&value);
+            end = child.position().map(|d| d.end.offset);
+        } else {
+            // Writing to strings does not fail.
+            let _ = flow(child, &SerializeOptions::default(), &mut result);
+            end = None;
+        }
+    }
+    match end
+        .and_then(|end| source.get(end..))
+        .filter(|d| d.trim().is_empty())
+    {
+        Some(rest) => result.push_str(rest),
+        None => {
+            if !result.is_empty() && !result.ends_with('\n') {
+                result.push('\n');
+            }
+        }
+    }
+    result
+}
+
+/// Collects the nodes in `node`, in the parents in `chain`, by where they
+/// start and end.
+fn collect<'a>(node: &'a Node, chain: &mut Vec<(usize, usize)>, nodes: &mut Nodes<'a>) {
+    let key = node.position().map(|d| (d.start.offset, d.end.offset));
+    if let Some(key) = key {
+        nodes.entry(key).or_default().push((node, chain.clone()));
+    }
+    let parent = match (node, key) {
+        (Node::Root(_), _) | (_, None) => false,
+        (_, Some(key)) => {
+            chain.push(key);
+            true
+        }
+    };
+    for child in node.children().into_iter().flatten() {
+        collect(child, chain, nodes);
+    }
+    if parent {
+        chain.pop();
+    }
+}
+
+/// Gets the markdown of `node`, in the parents in `chain`, from `source`, if
+/// it is one of `nodes`, in the same parents.
+fn verbatim<'a>(
+    node: &Node,
+    chain: &[(usize, usize)],
+    nodes: &Nodes,
+    source: &'a str,
+) -> Option<&'a str> {
+    let position = node.position()?;
+    let key = (position.start.offset, position.end.offset);
+    if nodes
+        .get(&key)?
+        .iter()
+        .any(|(original, parents)| *original == node && parents == chain)
+    {
+        source.get(key.0..key.1)
+    } else {
+        None
+    }
+}
+
+/// Gets the markdown of `node` from `source`, if it is one of `nodes`, or, when
+/// `deep`, if its changed children can be spliced into its markup there.
+fn reuse(
+    node: &Node,
+    chain: &[(usize, usize)],
+    nodes: &Nodes,
+    source: &str,
+    deep: bool,
+) -> Option<String> {
+    match verbatim(node, chain, nodes, source) {
+        Some(value) => Some(value.into()),
+        None if deep => spliced(node, chain, nodes, source),
+        None => None,
+    }
+}
+
+/// Splices the children of `node`, in the parents in `chain`, into the markup
+/// of the node in `nodes` at the same place and in the same parents that is
+/// the same except for its children, if there is one, and it has as many
+/// children.
+///
+/// Children that are not in `nodes` are regenerated, which fails if they
+/// span several lines, as the markup of containers (such as the `> ` of block
+/// quotes) would be missing.
+fn spliced(node: &Node, chain: &[(usize, usize)], nodes: &Nodes, source: &str) -> Option<String> {
+    let position = node.position()?;
+    let key = (position.start.offset, position.end.offset);
+    let children = node.children()?;
+    let (original, _) = nodes
+        .get(&key)?
+        .iter()
+        .find(|(original, parents)| parents == chain && same_except_children(node, original))?;
+    let originals = original.children()?;
+    let mut parents = chain.to_vec();
+    parents.push(key);
+    if children.len() != originals.len() {
+        return None;
+    }
+    let mut result = String::new();
+    let mut end = position.start.offset;
+    for (child, original) in children.iter().zip(originals) {
+        let place = original.position()?;
+        result.push_str(source.get(end..place.start.offset)?);
+        if let Some(value) = reuse(child, &parents, nodes, source, true) {
+            result.push_str(&value);
+        } else {
+            let value = serialize(child, &SerializeOptions::default());
+            if value.contains('\n') {
+                return None;
+            }
+            // Pipes in cells would start a new cell.
+            if matches!(node, Node::TableCell(_)) {
+                result.push_str(&value.replace('|', "\\|"));
+            } else {
+                result.push_str(&value);
+            }
+        }
+        end = place.end.offset;
+    }
+    result.push_str(source.get(end..position.end.offset)?);
+    Some(result)
+}
+
+/// Checks whether `a` and `b` are the same, except for their children.
+fn same_except_children(a: &Node, b: &Node) -> bool {
+    if a.kind() != b.kind() {
+        return false;
+    }
+    let mut a = a.clone();
+    let mut b = b.clone();
+    if let (Some(a), Some(b)) = (a.children_mut(), b.children_mut()) {
+        a.clear();
+        b.clear();
+    }
+    a == b
+}
+
+/// Whether `node` itself can be converted into markdown (not its children).
+pub fn supported(node: &mdast::Node) -> bool {
+    !matches!(
+        node,
+        Node::FootnoteDefinition(_)
+            | Node::FootnoteReference(_)
+            | Node::Image(_)
+            | Node::MdxFlowExpression(_)
+            | Node::MdxTextExpression(_)
+            | Node::MdxjsEsm(_)
+    )
+}
+
+/// Checks that a tree can be converted into markdown.
+///
+/// Frontmatter (`Yaml` and `Toml`) can only be the first child of the root,
+/// and some nodes (see [`supported()`][]) cannot be converted yet.
+pub fn validate(node: &mdast::Node) -> Result<(), message::Message> {
+    let mut result = Ok(());
+    node.each_with_path(&mut |child, path| {
+        if result.is_ok() && !supported(child) {
+            result = Err(message::Message {
+                place: child
+                    .position()
+                    .map(|d| Box::new(message::Place::Position(d.clone()))),
+                reason:
+                    "Unexpected unsupported node, expected a node that can be turned into markdown"
+                        .into(),
+                rule_id: Box::new("unexpected-unsupported-node".into()),
+                source: Box::new("markdown-rs".into()),
+            });
+        }
+
+        if result.is_ok()
+            && matches!(child, Node::Toml(_) | Node::Yaml(_))
+            && !(path.is_empty() || (path.indices() == [0] && matches!(node, Node::Root(_))))
+        {
+            result = Err(message::Message {
+                place: child
+                    .position()
+                    .map(|d| Box::new(message::Place::Position(d.clone()))),
+                reason: "Unexpected frontmatter, expected it only as the first child of the root"
+                    .into(),
+                rule_id: Box::new("unexpected-frontmatter".into()),
+                source: Box::new("markdown-rs".into()),
+            });
+        }
+    });
+    result
+}
+
+/// Writes citations, in text (`@doe99 [p. 33]`) if there is one citation in
+/// that mode, and in brackets (`[see @doe99, p. 33; -@smith04]`) otherwise.
+fn cite(out: &mut dyn fmt::Write, node: &mdast::Cite) -> fmt::Result {
+    let in_text =
+        node.citations.len() == 1 && node.citations[0].mode == mdast::CitationMode::AuthorInText;
+
+    if !in_text {
+        out.write_char('[')?;
+    }
+
+    for (index, citation) in node.citations.iter().enumerate() {
+        if index > 0 {
+            out.write_str("; ")?;
+        }
+        if let (false, Some(prefix)) = (in_text, &citation.prefix) {
+            out.write_str(prefix)?;
+            out.write_char(' ')?;
+        }
+        if citation.mode == mdast::CitationMode::SuppressAuthor {
+            out.write_char('-')?;
+        }
+        out.write_char('@')?;
+        if key_needs_braces(&citation.key) {
+            out.write_char('{')?;
+            out.write_str(&citation.key)?;
+            out.write_char('}')?;
+        } else {
+            out.write_str(&citation.key)?;
+        }
+
+        let affix = citation
+            .locator
+            .iter()
+            .chain(citation.suffix.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if !affix.is_empty() {
+            if in_text {
+                out.write_str(" [")?;
+                out.write_str(&affix)?;
+                out.write_char(']')?;
+            } else {
+                out.write_str(", ")?;
+                out.write_str(&affix)?;
+            }
+        }
+    }
+
+    if !in_text {
+        out.write_char(']')?;
+    }
+
+    Ok(())
+}
+
+/// Writes frontmatter between `fence`s.
+fn frontmatter(out: &mut dyn fmt::Write, fence: &str, value: &str) -> fmt::Result {
+    out.write_str(fence)?;
+    out.write_char('\n')?;
+    if !value.is_empty() {
+        out.write_str(value)?;
+        out.write_char('\n')?;
+    }
+    out.write_str(fence)?;
+    out.write_str("\n\n")
+}
+
+/// Writes a child of a flow parent (such as the root, a block quote, or a
+/// list item) as markdown into `out`.
+///
+/// HTML (flow), unlike HTML (text), needs a blank line after it, as it would
+/// otherwise continue.
+fn flow(node: &Node, options: &SerializeOptions, out: &mut dyn fmt::Write) -> fmt::Result {
+    write(node, options, out)?;
+    if let Node::Html(_) = node {
+        out.write_str("\n\n")?;
+    } else if let Node::Comment(_) = node {
+        out.write_str("\n")?;
+    }
+    Ok(())
+}
+
+/// Checks whether a table has cells with flow content (such as paragraphs
+/// or lists) or line endings, which can only be written as a grid table.
+fn grid(table: &mdast::Table) -> bool {
+    let mut stack = table.children.iter().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        match node {
+            Node::Break(_) => return true,
+            Node::Text(n) if n.value.contains('\n') => return true,
+            Node::TableCell(n) if n.children.iter().any(flow_content) => return true,
+            _ => stack.extend(node.children().into_iter().flatten()),
+        }
+    }
+    false
+}
+
+/// Checks whether `node` is flow content, such as a paragraph or a list.
+fn flow_content(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::BlockQuote(_)
+            | Node::Code(_)
+            | Node::Definition(_)
+            | Node::DefinitionList(_)
+            | Node::FootnoteDefinition(_)
+            | Node::Heading(_)
+            | Node::List(_)
+            | Node::Math(_)
+            | Node::Paragraph(_)
+            | Node::Table(_)
+            | Node::ThematicBreak(_)
+    )
+}
+
+/// Writes a table as a grid table (such as `+---+`, `| a |`, `+===+`) into
+/// `out`.
+fn grid_table(
+    table: &mdast::Table,
+    options: &SerializeOptions,
+    out: &mut dyn fmt::Write,
+) -> fmt::Result {
+    let rows = table
+        .children
+        .iter()
+        .map(|row| {
+            row.children()
+                .into_iter()
+                .flatten()
+                .map(|cell| {
+                    let children = cell.children().map_or(&[][..], Vec::as_slice);
+                    let value = if children.iter().any(flow_content) {
+                        serialize(
+                            &Node::Root(mdast::Root {
+                                children: children.to_vec(),
+                                position: None,
+                            }),
+                            options,
+                        )
+                    } else {
+                        children
+                            .iter()
+                            .map(|child| serialize(child, options))
+                            .collect()
+                    };
+                    value.trim_end_matches('\n').to_string()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let size = rows
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+        .max(table.align.len());
+    let mut widths = vec![3; size];
+    for row in &rows {
+        for (column, cell) in row.iter().enumerate() {
+            for line in cell.split('\n') {
+                widths[column] = widths[column].max(width(line));
+            }
+        }
+    }
+    grid_table_border(out, &widths, '-', None)?;
+    for (index, row) in rows.iter().enumerate() {
+        let lines = row
+            .iter()
+            .map(|cell| cell.split('\n').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let height = lines.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        for line in 0..height {
+            out.write_char('|')?;
+            for (column, size) in widths.iter().enumerate() {
+                let value = lines
+                    .get(column)
+                    .and_then(|cell| cell.get(line))
+                    .copied()
+                    .unwrap_or("");
+                out.write_char(' ')?;
+                out.write_str(value)?;
+                repeat(out, ' ', size - width(value))?;
+                out.write_str(" |")?;
+            }
+            out.write_char('\n')?;
+        }
+        if index == 0 {
+            grid_table_border(out, &widths, '=', Some(&table.align))?;
+        } else {
+            grid_table_border(out, &widths, '-', None)?;
+        }
+    }
+    if let Some(caption) = &table.caption {
+        out.write_str("Table: ")?;
+        for child in caption {
+            out.write_str(&serialize(child, options))?;
+        }
+        out.write_char('\n')?;
+    }
+    out.write_char('\n')
+}
+
+/// Writes a border of a grid table, made with `fill`, and with the alignment
+/// of each column in `align`, into `out`.
+fn grid_table_border(
+    out: &mut dyn fmt::Write,
+    widths: &[usize],
+    fill: char,
+    align: Option<&[mdast::AlignKind]>,
+) -> fmt::Result {
+    out.write_char('+')?;
+    for (column, size) in widths.iter().enumerate() {
+        let (start, end) = match align.and_then(|align| align.get(column)) {
+            Some(mdast::AlignKind::Left) => (":", ""),
+            Some(mdast::AlignKind::Right) => ("", ":"),
+            Some(mdast::AlignKind::Center) => (":", ":"),
+            _ => ("", ""),
+        };
+        out.write_str(start)?;
+        repeat(out, fill, size + 2 - start.len() - end.len())?;
+        out.write_str(end)?;
+        out.write_char('+')?;
+    }
+    out.write_char('\n')
+}
+
+/// Gets the markup around the children of phrasing nodes whose children are
+/// markdown, such as `*` and `*` for emphasis.
+///
+/// `intraword` is whether the node is between letters or digits.
+fn delimiters(
+    node: &Node,
+    options: &SerializeOptions,
+    intraword: bool,
+) -> Option<(String, String)> {
     match node {
-        Node::Root(n) => {
-            let mut result = String::new();
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result
-        }
-        Node::BlockQuote(n) => {
-            let mut result = String::new();
-            result.push_str("> ");
-
-            let mut kids = String::new();
-            for child in &n.children {
-                kids.push_str(&to_markdown(child));
-            }
-            if let Some((pre,post)) = kids.rsplit_once("\n"){
-                result.push_str(&pre.replace("\n", "\n> "));
-                result.push_str(post);
-            }
-            result.push('\n');
-            return result;
-        },
-        Node::FootnoteDefinition(_) => todo!(),
-        Node::MdxJsxFlowElement(_) => todo!(),
-        Node::List(n) => {
-            let mut result = String::new();
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push('\n');
-            result
-        },
-        Node::MdxjsEsm(_) => todo!(),
-        Node::Toml(_) => todo!(),
-        Node::Yaml(_) => todo!(),
-        Node::Break(_) => todo!(),
-        Node::InlineCode(_) => todo!(),
-        Node::InlineMath(_) => todo!(),
-        Node::Delete(n) => {
-            let mut result = String::new();
-            result.push_str("~~");
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push_str("~~");
-            result
-        },
+        Node::Delete(_) => Some(("~~".into(), "~~".into())),
+        Node::Highlight(_) => Some(("==".into(), "==".into())),
+        Node::Insert(_) => Some(("++".into(), "++".into())),
+        Node::CriticAddition(_) => Some(("{++".into(), "++}".into())),
+        Node::CriticDeletion(_) => Some(("{--".into(), "--}".into())),
+        Node::CriticHighlight(_) => Some(("{==".into(), "==}".into())),
+        Node::CriticComment(_) => Some(("{>>".into(), "<<}".into())),
         Node::Emphasis(n) => {
-            let mut result = String::new();
-            result.push('*');
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push('*');
-            result
-        },
-        Node::MdxTextExpression(_) => todo!(),
-        Node::FootnoteReference(_) => todo!(),
-        Node::Html(_) => todo!(),
-        Node::Image(_) => todo!(),
-        Node::ImageReference(_) => todo!(),
-        Node::MdxJsxTextElement(_) => todo!(),
-        Node::Link(n) => {
-            let mut result = String::new();
-            result.push('[');
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push_str("](");
-            result.push_str(&n.url);
-            result.push(')');
-            result
-        },
-        Node::LinkReference(_) => todo!(),
+            let marker = attention_marker(options.emphasis.or(n.marker), intraword).to_string();
+            Some((marker.clone(), marker))
+        }
+        Node::Link(n)
+            if !matches!(
+                n.link_kind,
+                Some(mdast::LinkKind::Autolink | mdast::LinkKind::GfmAutolinkLiteral)
+            ) =>
+        {
+            let mut close = String::from("](");
+            close.push_str(&n.url);
+            close.push(')');
+            Some(("[".into(), close))
+        }
+        Node::LinkReference(n) => {
+            let mut close = String::from("]");
+            // Writing to strings does not fail.
+            let _ = reference(
+                &mut close,
+                n.reference_kind,
+                n.label.as_deref(),
+                &n.identifier,
+            );
+            Some(("[".into(), close))
+        }
         Node::Strong(n) => {
-            let mut result = String::new();
-            result.push_str("**");
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push_str("**");
-            result
-        },
-        Node::Text(n) => n.value.clone(),
-        Node::Code(_) => todo!(),
-        Node::Math(_) => todo!(),
-        Node::MdxFlowExpression(_) => todo!(),
-        Node::Heading(n) => {
-            let mut result = String::new();
-            for _ in 0..n.depth {
-                result.push('#');
-            }
-            result.push(' ');
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push_str("\n\n");
-            result
-        },
-        Node::Table(_) => todo!(),
-        Node::ThematicBreak(_) => todo!(),
-        Node::TableRow(_) => todo!(),
-        Node::TableCell(_) => todo!(),
-        Node::ListItem(n) => {
-            let mut result = String::new();
-            result.push_str("* ");
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result
-        },
-        Node::Definition(_) => todo!(),
-        Node::Paragraph(n) => {
-            let mut result = String::new();
-            for child in &n.children {
-                result.push_str(&to_markdown(&child));
-            }
-            result.push_str("\n");
-            result
-        },
+            let marker = attention_marker(options.strong.or(n.marker), intraword)
+                .to_string()
+                .repeat(2);
+            Some((marker.clone(), marker))
+        }
+        _ => None,
     }
 }
 
+/// Gets the marker to use for emphasis or strong, which is `*` when there is
+/// none, or when the attention is between letters or digits (`intraword`),
+/// where `_` cannot open or close it.
+fn attention_marker(marker: Option<char>, intraword: bool) -> char {
+    match marker {
+        Some('_') if !intraword => '_',
+        _ => '*',
+    }
+}
+
+/// Whether `value` is a letter or digit.
+fn word(value: char) -> bool {
+    value.is_alphanumeric()
+}
+
+/// Gets the first character that `step` writes, if it is known.
+fn first_char(step: Option<&Step>) -> Option<char> {
+    match step? {
+        Step::Node(Node::Text(n)) => n.value.chars().next(),
+        Step::Write(value) => value.chars().next(),
+        _ => None,
+    }
+}
+
+/// Serializes phrasing `node` into `segments`, which pair markdown with
+/// whether it can be broken at its spaces.
+///
+/// Only text can be broken: line endings in it are turned into spaces.
+fn phrasing_segments(node: &Node, options: &SerializeOptions, segments: &mut Vec<(String, bool)>) {
+    let mut steps = vec![Step::Node(node)];
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Node(Node::Text(n)) => segments.push((n.value.replace('\n', " "), true)),
+            Step::Node(node) => {
+                let intraword = segments
+                    .last()
+                    .and_then(|(value, _)| value.chars().next_back())
+                    .map_or(false, word)
+                    || first_char(steps.last()).map_or(false, word);
+                if let Some((open, close)) = delimiters(node, options, intraword) {
+                    segments.push((open, false));
+                    steps.push(Step::Write(close.into()));
+                    steps.extend(node.children().into_iter().flatten().rev().map(Step::Node));
+                } else {
+                    segments.push((serialize(node, options), false));
+                }
+            }
+            Step::Write(value) => segments.push((value.into_owned(), false)),
+            Step::Flow(_) | Step::Item(..) | Step::Separate | Step::Exit(_) => {}
+        }
+    }
+}
+
+/// Joins `segments` into lines of at most `width` characters, where possible.
+fn wrap(segments: &[(String, bool)], width: usize) -> String {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (value, breakable) in segments {
+        if *breakable {
+            let mut parts = value.split(' ');
+            word.push_str(parts.next().unwrap_or(""));
+            for part in parts {
+                words.push(word);
+                word = part.into();
+            }
+        } else {
+            word.push_str(value);
+        }
+    }
+    words.push(word);
+
+    let mut result = String::new();
+    let mut column = 0;
+    for (index, word) in words.iter().enumerate() {
+        let size = word.split('\n').next().unwrap_or("").chars().count();
+        if index > 0 {
+            // Do not break after several spaces, which would be a hard break,
+            // or before something that could start other markdown.
+            if column + 1 + size > width
+                && !word.is_empty()
+                && !words[index - 1].is_empty()
+                && !starts_markdown(word)
+            {
+                result.push('\n');
+                column = 0;
+            } else {
+                result.push(' ');
+                column += 1;
+            }
+        }
+        result.push_str(word);
+        column = match word.rsplit_once('\n') {
+            Some((_, rest)) => rest.chars().count(),
+            None => column + size,
+        };
+    }
+    result
+}
+
+/// Gets the number of columns that `value` takes up in a monospace font.
+fn width(value: &str) -> usize {
+    value.chars().map(char::width).sum()
+}
+
+/// Checks whether a line starting with `word` could start other markdown than
+/// a paragraph, such as a heading (`#`), a list item (`*`, `1.`), or a table
+/// (`|-|`).
+fn starts_markdown(word: &str) -> bool {
+    let rest = word.trim_start_matches(|char: char| char.is_ascii_digit());
+    word.starts_with(&['<', '>'][..])
+        || word.starts_with("```")
+        || word.starts_with("~~~")
+        || word
+            .chars()
+            .all(|char| matches!(char, '#' | '*' | '+' | '-' | ':' | '=' | '_' | '|'))
+        || (rest.len() < word.len() && matches!(rest, "." | ")"))
+}
+
+/// Writes the opening tag of an MDX JSX element, which closes itself when
+/// `void`.
+fn mdx_jsx_opening(
+    out: &mut dyn fmt::Write,
+    name: Option<&str>,
+    attributes: &[mdast::AttributeContent],
+    void: bool,
+) -> fmt::Result {
+    out.write_char('<')?;
+    out.write_str(name.unwrap_or(""))?;
+    for attribute in attributes {
+        out.write_char(' ')?;
+        match attribute {
+            mdast::AttributeContent::Expression { value, .. } => {
+                out.write_char('{')?;
+                out.write_str(value)?;
+                out.write_char('}')?;
+            }
+            mdast::AttributeContent::Property(property) => {
+                out.write_str(&property.name)?;
+                match &property.value {
+                    Some(mdast::AttributeValue::Literal(value)) => {
+                        // There are no escapes in JSX strings, but there are
+                        // character references.
+                        let quote = if value.contains('"') && !value.contains('\'') {
+                            '\''
+                        } else {
+                            '"'
+                        };
+                        out.write_char('=')?;
+                        out.write_char(quote)?;
+                        if quote == '"' {
+                            out.write_str(&value.replace('&', "&amp;").replace('"', "&quot;"))?;
+                        } else {
+                            out.write_str(&value.replace('&', "&amp;"))?;
+                        }
+                        out.write_char(quote)?;
+                    }
+                    Some(mdast::AttributeValue::Expression(value)) => {
+                        out.write_str("={")?;
+                        out.write_str(&value.value)?;
+                        out.write_char('}')?;
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+    if void {
+        // Fragments cannot close themselves.
+        if name.is_some() {
+            out.write_str(" />")
+        } else {
+            out.write_str("></>")
+        }
+    } else {
+        out.write_char('>')
+    }
+}
+
+/// Writes the closing tag of an MDX JSX element.
+fn mdx_jsx_closing(out: &mut dyn fmt::Write, name: Option<&str>) -> fmt::Result {
+    out.write_str("</")?;
+    out.write_str(name.unwrap_or(""))?;
+    out.write_char('>')
+}
+
+/// Writes `value` as code (text) or math (text), fenced with `marker`, into
+/// `out`.
+///
+/// The fence must be longer than any run of the marker in the value, which
+/// would otherwise close it.
+/// A marker at the start or end would merge with the fence, and a space at
+/// both the start and end would be stripped, so pad with a space, which is
+/// stripped again when parsing.
+fn raw_text(out: &mut dyn fmt::Write, value: &str, marker: char) -> fmt::Result {
+    let size = longest_run(value, marker) + 1;
+    let pad = value.starts_with(marker)
+        || value.ends_with(marker)
+        || (value.starts_with(' ') && value.ends_with(' ') && !value.trim_matches(' ').is_empty());
+    repeat(out, marker, size)?;
+    if pad {
+        out.write_char(' ')?;
+    }
+    out.write_str(value)?;
+    if pad {
+        out.write_char(' ')?;
+    }
+    repeat(out, marker, size)
+}
+
+/// Writes `marker` `size` times.
+fn repeat(out: &mut dyn fmt::Write, marker: char, size: usize) -> fmt::Result {
+    for _ in 0..size {
+        out.write_char(marker)?;
+    }
+    Ok(())
+}
+
+/// Counts the longest run of `marker` in `value`.
+fn longest_run(value: &str, marker: char) -> usize {
+    let mut size = 0;
+    let mut longest = 0;
+    for char in value.chars() {
+        size = if char == marker { size + 1 } else { 0 };
+        longest = longest.max(size);
+    }
+    longest
+}
+
+/// Writes attributes, such as `{#a .b c="d"}`.
+///
+/// Values are quoted with `'` if they contain `"`.
+fn write_attributes(out: &mut dyn fmt::Write, attributes: &mdast::Attributes) -> fmt::Result {
+    out.write_char('{')?;
+    let mut first = true;
+    let ids = attributes.id.iter().map(|d| ('#', d.as_str()));
+    let classes = attributes.classes.iter().map(|d| ('.', d.as_str()));
+    for (marker, name) in ids.chain(classes) {
+        if !first {
+            out.write_char(' ')?;
+        }
+        first = false;
+        out.write_char(marker)?;
+        out.write_str(name)?;
+    }
+    for (key, value) in &attributes.pairs {
+        if !first {
+            out.write_char(' ')?;
+        }
+        first = false;
+        let quote = if value.contains('"') { '\'' } else { '"' };
+        out.write_str(key)?;
+        out.write_char('=')?;
+        out.write_char(quote)?;
+        out.write_str(value)?;
+        out.write_char(quote)?;
+    }
+    out.write_char('}')
+}
+
+/// Writes the part of a reference after its text: `[label]`, `[]`, or
+/// nothing.
+fn reference(
+    out: &mut dyn fmt::Write,
+    kind: mdast::ReferenceKind,
+    label: Option<&str>,
+    identifier: &str,
+) -> fmt::Result {
+    match kind {
+        mdast::ReferenceKind::Full => {
+            out.write_char('[')?;
+            out.write_str(label.unwrap_or(identifier))?;
+            out.write_char(']')
+        }
+        mdast::ReferenceKind::Collapsed => out.write_str("[]"),
+        mdast::ReferenceKind::Shortcut => Ok(()),
+    }
+}
+
+/// Work left to do when writing a tree.
+enum Step<'a> {
+    /// Write a node.
+    Node(&'a Node),
+    /// Write a child of a flow parent (such as the root, a block quote, or a
+    /// list item).
+    Flow(&'a Node),
+    /// Write markdown.
+    Write(Cow<'a, str>),
+    /// Write a list item, with its marker (such as `1. `).
+    Item(&'a Node, String),
+    /// Put a blank line before the next block, if there was one before.
+    Separate,
+    /// Finish a node, after its children.
+    Exit(&'a Node),
+}
+
+/// Markup that containers add to what is written in them.
+enum Layer {
+    /// Puts `marker` before every line but the first, such as `> ` for block
+    /// quotes, or `blank` before blank lines, such as `>`.
+    ///
+    /// The marker is written when something is written after a line ending,
+    /// so that there is none after the last line ending.
+    Prefix {
+        marker: String,
+        blank: &'static str,
+        pending: bool,
+    },
+    /// Holds back line endings at the end, to separate blocks by blank lines.
+    ///
+    /// The container started when more was written than when `since`.
+    Blocks { since: usize, held: usize },
+}
+
+/// Sink that markdown is written into while walking a tree.
+///
+/// Containers push layers instead of wrapping the sink, so that writing into
+/// deeply nested containers does not lead to deeply nested calls.
+/// Layers that must write something before what comes next are kept in
+/// `dirty`, so that other layers can be skipped.
+struct Printer<'a> {
+    out: &'a mut dyn fmt::Write,
+    layers: Vec<Layer>,
+    dirty: BTreeSet<usize>,
+    /// Number of writes of something other than line endings.
+    texts: usize,
+    /// Number of open table cells, in which pipes are escaped.
+    cells: usize,
+    /// Last character written, if any.
+    last: Option<char>,
+    /// Line ending to use for all line endings, if any.
+    line_ending: Option<LineEnding>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(out: &'a mut dyn fmt::Write, line_ending: Option<LineEnding>) -> Self {
+        Self {
+            out,
+            layers: Vec::new(),
+            dirty: BTreeSet::new(),
+            texts: 0,
+            cells: 0,
+            last: None,
+            line_ending,
+        }
+    }
+
+    fn push(&mut self, layer: Layer) {
+        self.layers.push(layer);
+    }
+
+    /// Removes the last layer, which is a prefix.
+    fn pop_prefix(&mut self) {
+        self.dirty.remove(&self.layers.len().saturating_sub(1));
+        self.layers.pop();
+    }
+
+    /// Makes sure that what comes next in the last layer, which holds blocks,
+    /// is on a new line after a blank line, if something was written.
+    fn separate(&mut self) {
+        let index = self.layers.len().saturating_sub(1);
+        if let Some(Layer::Blocks { since, held }) = self.layers.last_mut() {
+            if self.texts > *since && *held < 2 {
+                *held += 1;
+                self.dirty.insert(index);
+            }
+        }
+    }
+
+    /// Removes the last layer, which holds blocks, and writes the line
+    /// endings that it held back, if `keep`.
+    fn finish(&mut self, keep: bool) -> fmt::Result {
+        let index = self.layers.len().saturating_sub(1);
+        self.dirty.remove(&index);
+        if let Some(Layer::Blocks { held, .. }) = self.layers.pop() {
+            if keep {
+                for _ in 0..held {
+                    self.line_ending(index)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a line ending into the layers before `level`.
+    fn line_ending(&mut self, level: usize) -> fmt::Result {
+        for index in (0..level).rev() {
+            match &mut self.layers[index] {
+                Layer::Prefix { blank, pending, .. } => {
+                    // Blank lines are prefixed too, but without trailing
+                    // whitespace.
+                    if *pending {
+                        let blank = *blank;
+                        if !blank.is_empty() {
+                            self.flush(index)?;
+                            self.out.write_str(blank)?;
+                            self.texts += 1;
+                        }
+                    } else {
+                        *pending = true;
+                        self.dirty.insert(index);
+                    }
+                }
+                Layer::Blocks { held, .. } => {
+                    *held += 1;
+                    self.dirty.insert(index);
+                    return Ok(());
+                }
+            }
+        }
+        match &self.line_ending {
+            Some(line_ending) => self.out.write_str(line_ending.as_str()),
+            None => self.out.write_char('\n'),
+        }
+    }
+
+    /// Writes what the layers before `level` hold back, from the first layer.
+    fn flush(&mut self, level: usize) -> fmt::Result {
+        while let Some(&index) = self.dirty.range(..level).next() {
+            match &mut self.layers[index] {
+                Layer::Prefix {
+                    marker, pending, ..
+                } => {
+                    *pending = false;
+                    self.dirty.remove(&index);
+                    self.out.write_str(marker)?;
+                    self.texts += 1;
+                }
+                Layer::Blocks { held, .. } => {
+                    // All at once, so that prefixes see the blank lines.
+                    let held = core::mem::take(held);
+                    self.dirty.remove(&index);
+                    for _ in 0..held {
+                        self.line_ending(index)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes something other than line endings.
+    fn text(&mut self, value: &str) -> fmt::Result {
+        self.flush(self.layers.len())?;
+        self.out.write_str(value)?;
+        self.texts += 1;
+        Ok(())
+    }
+}
+
+impl fmt::Write for Printer<'_> {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        // Other line endings in values are turned into the one to use too.
+        let value = if self.line_ending.is_some() && value.contains('\r') {
+            Cow::Owned(value.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            Cow::Borrowed(value)
+        };
+
+        if let Some(char) = value.chars().next_back() {
+            self.last = Some(char);
+        }
+
+        for (index, line) in value.split('\n').enumerate() {
+            if index > 0 {
+                self.line_ending(self.layers.len())?;
+            }
+            if self.cells > 0 {
+                // Pipes in cells would start a new cell.
+                for (index, part) in line.split('|').enumerate() {
+                    if index > 0 {
+                        self.text("\\|")?;
+                    }
+                    if !part.is_empty() {
+                        self.text(part)?;
+                    }
+                }
+            } else if !line.is_empty() {
+                self.text(line)?;
+            }
+        }
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -144,7 +1726,7 @@ mod tests {
 
     use super::*;
     // use crate::unist::Position;
-    use alloc::{format, vec};
+    use alloc::{boxed::Box, format, vec};
 
     macro_rules! cycle_tests {
         ($($name:ident: $value:expr,)*) => {
@@ -161,12 +1743,649 @@ mod tests {
     #[test]
     fn test_plain_text_node() {
         let node = Node::Text(mdast::Text {
-            value: String::from("Hello, world!"),
+            value: "Hello, world!".into(),
             position: None,
         });
         assert_eq!(to_markdown(&node), "Hello, world!");
     }
 
+    #[test]
+    fn test_character_reference_node() {
+        let node = Node::CharacterReference(Box::new(mdast::CharacterReference {
+            value: "&".into(),
+            position: None,
+            raw: "&amp;".into(),
+        }));
+        assert_eq!(to_markdown(&node), "&amp;");
+    }
+
+    #[test]
+    fn test_gfm_tables() {
+        let cycle = |input: &str| to_markdown(&to_mdast(input, &ParseOptions::gfm()).unwrap());
+        assert_eq!(
+            cycle("| a | b | c | d |\n| :- | -: | :-: | - |\n| e | f | g | h |"),
+            "| a | b | c | d |\n| :-- | --: | :-: | --- |\n| e | f | g | h |\n\n"
+        );
+        assert_eq!(
+            cycle("| a |\n| - |\n| b \\| c |"),
+            "| a |\n| --- |\n| b \\| c |\n\n"
+        );
+        assert_eq!(
+            cycle("| a | b |\n| - | - |"),
+            "| a | b |\n| --- | --- |\n\n"
+        );
+        assert_eq!(
+            cycle("| a | b |\n| - | - |\n| c |"),
+            "| a | b |\n| --- | --- |\n| c |\n\n"
+        );
+    }
+
+    #[test]
+    fn test_code_fences() {
+        let code = |value: &str, lang: Option<&str>, meta: Option<&str>| {
+            to_markdown(&Node::Code(Box::new(mdast::Code {
+                value: value.into(),
+                position: None,
+                lang: lang.map(Into::into),
+                meta: meta.map(Into::into),
+                code_kind: None,
+                fence_marker: None,
+                fence_size: None,
+                fence_indent: None,
+                attributes: None,
+            })))
+        };
+        assert_eq!(code("a", Some("js"), Some("b=c")), "```js b=c\na\n```\n\n");
+        assert_eq!(code("a\n```\nb", None, None), "````\na\n```\nb\n````\n\n");
+        assert_eq!(code("````` a", None, None), "``````\n````` a\n``````\n\n");
+        assert_eq!(code("a", Some("b`c"), None), "~~~b`c\na\n~~~\n\n");
+        assert_eq!(code("", None, None), "```\n```\n\n");
+    }
+
+    #[test]
+    fn test_definition_node() {
+        let definition = |url: &str, title: Option<&str>| {
+            to_markdown(&Node::Definition(Box::new(mdast::Definition {
+                position: None,
+                url: url.into(),
+                title: title.map(Into::into),
+                identifier: "a".into(),
+                label: Some("A".into()),
+            })))
+        };
+        assert_eq!(definition("b", None), "[A]: b\n");
+        assert_eq!(definition("b", Some("c \"d\"")), "[A]: b \"c \\\"d\\\"\"\n");
+        assert_eq!(definition("", None), "[A]: <>\n");
+        assert_eq!(definition("b c<d>", None), "[A]: <b c\\<d\\>>\n");
+    }
+
+    #[test]
+    fn test_inline_code_node() {
+        let code = |value: &str| {
+            to_markdown(&Node::InlineCode(mdast::InlineCode {
+                value: value.into(),
+                position: None,
+                attributes: None,
+            }))
+        };
+        assert_eq!(code("a"), "`a`");
+        assert_eq!(code("a``b"), "```a``b```");
+        assert_eq!(code("`a"), "`` `a ``");
+        assert_eq!(code("a`"), "`` a` ``");
+        assert_eq!(code(" a "), "`  a  `");
+        assert_eq!(code(" a"), "` a`");
+        assert_eq!(code("  "), "`  `");
+    }
+
+    #[test]
+    fn test_math() {
+        let cycle = |input: &str| {
+            to_markdown(
+                &to_mdast(
+                    input,
+                    &ParseOptions {
+                        constructs: crate::Constructs {
+                            math_flow: true,
+                            math_text: true,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            )
+        };
+        assert_eq!(cycle("a $b$ c"), "a $b$ c\n");
+        assert_eq!(cycle("a $$b$c$$ d"), "a $$b$c$$ d\n");
+        assert_eq!(cycle("$$x y\na\n$$"), "$$x y\na\n$$\n\n");
+        assert_eq!(cycle("$$$\na\n$$\nb\n$$$"), "$$$\na\n$$\nb\n$$$\n\n");
+        assert_eq!(
+            to_markdown(&Node::InlineMath(mdast::InlineMath {
+                value: "$a".into(),
+                position: None
+            })),
+            "$$ $a $$"
+        );
+    }
+
+    #[test]
+    fn test_mdx_jsx() {
+        let cycle = |input: &str| to_markdown(&to_mdast(input, &ParseOptions::mdx()).unwrap());
+        assert_eq!(cycle("<A />"), "<A />\n\n");
+        assert_eq!(
+            cycle("<A b c=\"d\" e='f\"' g={h} {...i}>\nj\n\nk\n</A>"),
+            "<A b c=\"d\" e='f\"' g={h} {...i}>\nj\n\nk\n</A>\n\n"
+        );
+        assert_eq!(cycle("<>\n<B.c />\n</>"), "<>\n<B.c />\n</>\n\n");
+        assert_eq!(
+            cycle("a <b-c d=\"&amp;\">*e*</b-c> <f:g />."),
+            "a <b-c d=\"&amp;\">*e*</b-c> <f:g />.\n"
+        );
+        assert_eq!(cycle("a <></> b"), "a <></> b\n");
+    }
+
+    #[test]
+    fn test_frontmatter() {
+        let cycle = |input: &str| {
+            to_markdown(
+                &to_mdast(
+                    input,
+                    &ParseOptions {
+                        constructs: crate::Constructs {
+                            frontmatter: true,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            )
+        };
+        assert_eq!(cycle("---\na: b\n---\n# c"), "---\na: b\n---\n\n# c\n\n");
+        assert_eq!(cycle("+++\na = \"b\"\n+++"), "+++\na = \"b\"\n+++\n\n");
+        assert_eq!(cycle("---\n---"), "---\n---\n\n");
+
+        let yaml = || {
+            Node::Yaml(mdast::Yaml {
+                value: "a".into(),
+                position: None,
+            })
+        };
+        let paragraph = || {
+            Node::Paragraph(mdast::Paragraph {
+                children: vec![],
+                position: None,
+            })
+        };
+        assert_eq!(
+            validate(&Node::Root(mdast::Root {
+                children: vec![yaml(), paragraph()],
+                position: None
+            })),
+            Ok(())
+        );
+        assert_eq!(validate(&yaml()), Ok(()));
+        assert_eq!(
+            validate(&Node::Root(mdast::Root {
+                children: vec![paragraph(), yaml()],
+                position: None
+            }))
+            .map_err(|d| d.rule_id),
+            Err(Box::new("unexpected-frontmatter".into()))
+        );
+        assert_eq!(
+            validate(&Node::BlockQuote(mdast::BlockQuote {
+                children: vec![yaml()],
+                position: None,
+                alert: None
+            }))
+            .map_err(|d| d.rule_id),
+            Err(Box::new("unexpected-frontmatter".into()))
+        );
+        assert_eq!(
+            validate(&Node::Paragraph(mdast::Paragraph {
+                children: vec![Node::Image(Box::new(mdast::Image {
+                    alt: "a".into(),
+                    url: "b".into(),
+                    title: None,
+                    position: None
+                }))],
+                position: None
+            }))
+            .map_err(|d| d.rule_id),
+            Err(Box::new("unexpected-unsupported-node".into()))
+        );
+    }
+
+    #[test]
+    fn test_serialize_options() {
+        let cycle = |input: &str, options: &SerializeOptions| {
+            serialize(&to_mdast(input, &ParseOptions::default()).unwrap(), options)
+        };
+        assert_eq!(
+            cycle("+ a\n+ b", &SerializeOptions::default()),
+            "+ a\n+ b\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "+ a\n+ b",
+                &SerializeOptions {
+                    bullet: Some('-'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "- a\n- b\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "3. a\n4. b",
+                &SerializeOptions {
+                    bullet_ordered: Some(')'),
+                    increment_list_marker: false,
+                    ..SerializeOptions::default()
+                }
+            ),
+            "3) a\n3) b\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "* a\n\n- b",
+                &SerializeOptions {
+                    bullet: Some('*'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "* a\n\n- b\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "1. a\n\n1) b",
+                &SerializeOptions {
+                    bullet_ordered: Some('.'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "1. a\n\n1) b\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "- ***",
+                &SerializeOptions {
+                    bullet: Some('*'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "- ***\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "*a* __b__",
+                &SerializeOptions {
+                    emphasis: Some('_'),
+                    strong: Some('*'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "_a_ **b**\n"
+        );
+        assert_eq!(
+            cycle(
+                "a*b*c a**b**c",
+                &SerializeOptions {
+                    emphasis: Some('_'),
+                    strong: Some('_'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "a*b*c a**b**c\n"
+        );
+        assert_eq!(
+            cycle(
+                "a*b*c",
+                &SerializeOptions {
+                    emphasis: Some('_'),
+                    line_width: Some(80),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "a*b*c\n"
+        );
+        assert_eq!(
+            cycle(
+                "~~~\na\n~~~",
+                &SerializeOptions {
+                    fence: Some('`'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "```\na\n```\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "~~~a`b\nc\n~~~",
+                &SerializeOptions {
+                    fence: Some('`'),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "~~~a`b\nc\n~~~\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "a\n===",
+                &SerializeOptions {
+                    setext: Some(false),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "# a\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "## a",
+                &SerializeOptions {
+                    setext: Some(true),
+                    ..SerializeOptions::default()
+                }
+            ),
+            "a\n-\n\n"
+        );
+        assert_eq!(
+            cycle(
+                "---",
+                &SerializeOptions {
+                    rule: '_',
+                    ..SerializeOptions::default()
+                }
+            ),
+            "___\n\n"
+        );
+    }
+
+    #[test]
+    fn test_deeply_nested() {
+        /// Wraps `node` in `depth` parents made by `wrap`.
+        fn nest(mut node: Node, depth: usize, wrap: fn(Node) -> Node) -> Node {
+            for _ in 0..depth {
+                node = wrap(node);
+            }
+            node
+        }
+        /// Drops `node` without recursion, which would overflow the stack too.
+        fn unnest(node: Node) {
+            let mut stack = vec![node];
+            while let Some(mut node) = stack.pop() {
+                if let Some(children) = node.children_mut() {
+                    stack.append(children);
+                }
+            }
+        }
+        let text = || {
+            Node::Text(mdast::Text {
+                value: "a".into(),
+                position: None,
+            })
+        };
+        let paragraph = |child| {
+            Node::Paragraph(mdast::Paragraph {
+                children: vec![child],
+                position: None,
+            })
+        };
+        let depth = 100_000;
+
+        let tree = nest(paragraph(text()), depth, |child| {
+            Node::BlockQuote(mdast::BlockQuote {
+                children: vec![child],
+                position: None,
+                alert: None,
+            })
+        });
+        let tree = Node::Root(mdast::Root {
+            children: vec![tree],
+            position: None,
+        });
+        let mut expected = "> ".repeat(depth);
+        expected.push_str("a\n");
+        assert_eq!(
+            to_markdown(&tree),
+            expected,
+            "should support deeply nested block quotes"
+        );
+        assert_eq!(
+            tree.to_string(),
+            "a",
+            "should get the text of deeply nested block quotes"
+        );
+        unnest(tree);
+
+        let tree = nest(paragraph(text()), depth, |child| {
+            Node::List(mdast::List {
+                children: vec![Node::ListItem(mdast::ListItem {
+                    children: vec![child],
+                    position: None,
+                    spread: false,
+                    checked: None,
+                })],
+                position: None,
+                ordered: false,
+                start: None,
+                spread: false,
+                marker: None,
+            })
+        });
+        assert!(
+            to_markdown(&tree).starts_with("* * * "),
+            "should support deeply nested lists"
+        );
+        unnest(tree);
+
+        let tree = paragraph(nest(text(), depth, |child| {
+            Node::Emphasis(mdast::Emphasis {
+                children: vec![child],
+                position: None,
+                marker: None,
+            })
+        }));
+        let mut expected = "*".repeat(depth);
+        expected.push('a');
+        expected.push_str(&"*".repeat(depth));
+        expected.push('\n');
+        assert_eq!(
+            to_markdown(&tree),
+            expected,
+            "should support deeply nested emphasis"
+        );
+        assert_eq!(
+            serialize(
+                &tree,
+                &SerializeOptions {
+                    line_width: Some(80),
+                    ..SerializeOptions::default()
+                }
+            ),
+            expected,
+            "should support deeply nested emphasis when wrapping"
+        );
+        unnest(tree);
+    }
+
+    #[test]
+    fn test_to_markdown_to() {
+        let tree = to_mdast(
+            "> a\n>\n> > | b |\n> > | - |\n> > | c\\|d |\n\n<e>\n\n* f\n\n# g",
+            &ParseOptions::gfm(),
+        )
+        .unwrap();
+        let mut result = String::new();
+        assert_eq!(to_markdown_to(&tree, &mut result), Ok(()));
+        assert_eq!(
+            result,
+            to_markdown(&tree),
+            "should write the same as `to_markdown`"
+        );
+
+        /// Sink that fails after `0` characters.
+        struct Full(usize);
+        impl fmt::Write for Full {
+            fn write_str(&mut self, value: &str) -> fmt::Result {
+                self.0 = self.0.checked_sub(value.len()).ok_or(fmt::Error)?;
+                Ok(())
+            }
+        }
+        assert_eq!(
+            to_markdown_to(&tree, &mut Full(10)),
+            Err(fmt::Error),
+            "should pass errors through"
+        );
+    }
+
+    #[test]
+    fn test_break_style() {
+        let cycle = |input: &str, break_style: BreakKind| {
+            serialize(
+                &to_mdast(input, &ParseOptions::default()).unwrap(),
+                &SerializeOptions {
+                    break_style,
+                    ..SerializeOptions::default()
+                },
+            )
+        };
+        assert_eq!(
+            cycle("a  \nb", BreakKind::Backslash),
+            "a\\\nb\n",
+            "should use backslashes by default"
+        );
+        assert_eq!(
+            cycle("a\\\nb", BreakKind::Spaces),
+            "a  \nb\n",
+            "should use spaces"
+        );
+        assert_eq!(
+            cycle("*a\\\nb*", BreakKind::Spaces),
+            "*a  \nb*\n",
+            "should use spaces in emphasis"
+        );
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let cycle = |input: &str, line_ending: Option<LineEnding>| {
+            serialize(
+                &to_mdast(input, &ParseOptions::default()).unwrap(),
+                &SerializeOptions {
+                    line_ending,
+                    ..SerializeOptions::default()
+                },
+            )
+        };
+        assert_eq!(
+            cycle("# a\r\nb\r\nc", None),
+            "# a\n\nb\r\nc\n",
+            "should use `\\n` for generated line endings by default"
+        );
+        assert_eq!(
+            cycle(
+                "# a\r\n> b\r\n> c\r\n\r\n```\r\nd\r\n```",
+                Some(LineEnding::CarriageReturnLineFeed)
+            ),
+            "# a\r\n\r\n> b\r\n> c\r\n\r\n```\r\nd\r\n```\r\n\r\n",
+            "should use `\\r\\n`"
+        );
+        assert_eq!(
+            cycle("a\r\nb\nc\rd", Some(LineEnding::LineFeed)),
+            "a\nb\nc\nd\n",
+            "should turn other line endings in values into the line ending"
+        );
+    }
+
+    #[test]
+    fn test_pad_tables() {
+        let cycle = |input: &str| {
+            serialize(
+                &to_mdast(input, &ParseOptions::gfm()).unwrap(),
+                &SerializeOptions {
+                    pad_tables: true,
+                    ..SerializeOptions::default()
+                },
+            )
+        };
+        assert_eq!(
+            cycle("| a | bbbbb |\n| - | - |\n| cc |"),
+            "| a   | bbbbb |\n| --- | ----- |\n| cc  |       |\n\n",
+            "should pad cells, and fill in missing cells"
+        );
+        assert_eq!(
+            cycle("| a | b | c |\n| :- | :-: | -: |\n| dddd | eeeee | ffff |"),
+            "| a    |   b   |    c |\n| :--- | :---: | ---: |\n| dddd | eeeee | ffff |\n\n",
+            "should align cells"
+        );
+        assert_eq!(
+            cycle("| é | 中 |\n| - | - |\n| éé | 中文 |"),
+            "| é   | 中   |\n| --- | ---- |\n| éé  | 中文 |\n\n",
+            "should count columns, not bytes"
+        );
+        assert_eq!(
+            cycle("| a\\|b |\n| - |"),
+            "| a\\|b |\n| ---- |\n\n",
+            "should count escapes"
+        );
+    }
+
+    #[test]
+    fn test_line_width() {
+        let cycle = |input: &str, width: usize| {
+            serialize(
+                &to_mdast(input, &ParseOptions::default()).unwrap(),
+                &SerializeOptions {
+                    line_width: Some(width),
+                    ..SerializeOptions::default()
+                },
+            )
+        };
+        assert_eq!(
+            cycle("aa bb cc\ndd ee", 5),
+            "aa bb\ncc dd\nee\n",
+            "should reflow text"
+        );
+        assert_eq!(
+            cycle("aaaaaa b", 3),
+            "aaaaaa\nb\n",
+            "should not break long words"
+        );
+        assert_eq!(
+            cycle("a `b c d` *e f*", 3),
+            "a\n`b c d`\n*e\nf*\n",
+            "should break in emphasis, not in code"
+        );
+        assert_eq!(
+            cycle("a [b c](d) e", 3),
+            "a\n[b\nc](d)\ne\n",
+            "should break in link text, not after it"
+        );
+        assert_eq!(
+            cycle("a # b 1. c > d ``` e", 1),
+            "a #\nb 1.\nc >\nd ```\ne\n",
+            "should not start lines with other markdown"
+        );
+        assert_eq!(
+            cycle("a   b", 1),
+            "a   b\n",
+            "should not break after several spaces"
+        );
+        assert_eq!(
+            cycle("> a b", 3),
+            "> a\n> b\n",
+            "should count the prefix of block quotes"
+        );
+        assert_eq!(
+            cycle("- aaaa bbbb cccc dddd", 10),
+            "- aaaa\n  bbbb\n  cccc\n  dddd\n\n",
+            "should count the marker of list items"
+        );
+        assert_eq!(cycle("# a b", 1), "# a b\n\n", "should not wrap headings");
+    }
+
     #[test]
     fn test_empty_root_node() {
         let node = Node::Root(mdast::Root {
@@ -177,14 +2396,12 @@ mod tests {
     }
 
     #[test]
-    fn test_simplest_document(){
+    fn test_simplest_document() {
         let node = Node::Root(mdast::Root {
-            children: vec![
-                Node::Text(mdast::Text {
-                    value: String::from("Hello, world!"),
-                    position: None,
-                }),
-            ],
+            children: vec![Node::Text(mdast::Text {
+                value: "Hello, world!".into(),
+                position: None,
+            })],
             position: None,
         });
         assert_eq!(to_markdown(&node), "Hello, world!");
@@ -207,6 +2424,33 @@ mod tests {
         multiline_blockquotes_will_presevre_trailing_newline: ("> Hello\n> world\n", "> Hello\n> world\n"),
         can_parse_list_items: ("* Hello, world!", "* Hello, world!\n\n"),
         can_parse_simple_list: ("* Hello\n* world", "* Hello\n* world\n\n"),
-        can_parse_nested_list: (format!("{:?}", to_mdast("* Hello\n  * world", &ParseOptions::default()).unwrap()), "* Hello\n  * world\n\n"),
+        will_preserve_emphasis_markers: ("_Hello_, __world__!", "_Hello_, __world__!\n"),
+        will_preserve_list_markers: ("- Hello\n- world", "- Hello\n- world\n\n"),
+        will_preserve_ordered_list_delimiters: ("3) Hello\n4) world", "3) Hello\n4) world\n\n"),
+        will_preserve_setext_headers: ("Hello\n---", "Hello\n-----\n\n"),
+        will_preserve_code_fences: ("~~~~js a\nb\n~~~~", "~~~~js a\nb\n~~~~\n\n"),
+        will_render_autolinks: ("<http://example.com>", "<http://example.com>\n"),
+        will_render_email_autolinks: ("<a@example.com>", "<a@example.com>\n"),
+        will_preserve_definitions: ("[a]: b\n[A]: c", "[a]: b\n\n[A]: c\n"),
+        will_preserve_definition_titles: ("[a]: <b c> 'd'", "[a]: <b c> \"d\"\n"),
+        will_preserve_full_references: ("[*b*][A] ![c][A]\n\n[a]: d", "[*b*][A] ![c][A]\n\n[a]: d\n"),
+        will_preserve_collapsed_references: ("[a][] ![a][]\n\n[a]: d", "[a][] ![a][]\n\n[a]: d\n"),
+        will_preserve_shortcut_references: ("[a] ![a]\n\n[a]: d", "[a] ![a]\n\n[a]: d\n"),
+        will_render_inline_code: ("a `b` c", "a `b` c\n"),
+        will_fence_inline_code_with_backticks: ("a ``b`c`` d", "a ``b`c`` d\n"),
+        will_preserve_inline_code_padding: ("a `` `b` `` c", "a ``  `b`  `` c\n"),
+        will_preserve_inline_code_with_only_spaces: ("a `  ` c", "a `  ` c\n"),
+        will_render_thematic_breaks: ("- - -", "***\n\n"),
+        will_separate_thematic_breaks_from_paragraphs: ("a\n\n___\nb", "a\n\n***\n\nb\n"),
+        will_preserve_flow_html: ("<div>\n*a*\n</div>", "<div>\n*a*\n</div>\n\n"),
+        will_preserve_text_html: ("a <b>c</b> <!-- d -->", "a <b>c</b> <!-- d -->\n"),
+        will_separate_flow_html_from_paragraphs: ("a\n\n<!-- b -->\n\nc", "a\n\n<!-- b -->\n\nc\n"),
+        can_parse_nested_list: ("* Hello\n  * world", "* Hello\n  * world\n\n"),
+        will_indent_deeply_nested_lists: ("- a\n  - b\n    - c", "- a\n  - b\n    - c\n\n"),
+        will_indent_list_item_paragraphs: ("- a\n\n  b", "- a\n\n  b\n\n"),
+        will_indent_code_in_list_items: ("- ```\n  x\n  ```", "- ```\n  x\n  ```\n\n"),
+        will_indent_block_quotes_in_list_items: ("- > a\n  > b", "- > a\n  > b\n\n"),
+        will_indent_by_the_size_of_ordered_markers: ("10. a\n\n    b", "10. a\n\n    b\n\n"),
+        will_separate_paragraphs_in_blockquotes: ("> a\n>\n> b", "> a\n>\n> b\n"),
     }
-}
\ No newline at end of file
+}
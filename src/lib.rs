@@ -11,16 +11,36 @@
 //!     constructs (GFM, MDX, and the like)
 //! *   [`to_mdast()`][]
 //!     — turn markdown into a syntax tree
+//! *   [`transform`][]
+//!     — change syntax trees, such as removing unused definitions
+//! *   [`shared`][]
+//!     — syntax trees that are cheap to clone and share across threads
+//! *   [`inspect()`][]
+//!     — show syntax trees in a readable way, for debugging
 //!
 //! ## Features
 //!
 //! *   **`default`**
 //!     — nothing is enabled by default
 //! *   **`serde`**
-//!     — enable serde to serialize the AST (includes `dep:serde`)
+//!     — enable serde to serialize and deserialize syntax trees, with the
+//!     same fields as in unist and mdast (includes `dep:serde`)
+//! *   **`json`**
+//!     — parse syntax trees from JSON, such as from remark, with
+//!     `mdast::Node::from_json()` (includes `serde`, `dep:serde_json`)
 //! *   **`log`**
 //!     — enable logging (includes `dep:log`);
 //!     you can show logs with `RUST_LOG=debug`
+//! *   **`compact_str`**
+//!     — store the values of text and inline code in a small string type
+//!     (includes `dep:compact_str`), see [`mdast::TextValue`][]
+//! *   **`rayon`**
+//!     — compile large documents to HTML on several threads
+//!     (includes `dep:rayon`)
+//! *   **`tracing`**
+//!     — enable [tracing](https://docs.rs/tracing) spans around parsing
+//!     (flow, subtokenization, resolvers) and compiling (includes
+//!     `dep:tracing`)
 
 #![no_std]
 #![deny(clippy::pedantic)]
@@ -29,6 +49,8 @@
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::too_many_lines)]
 #![allow(clippy::result_large_err)]
+// Conversions into `mdast::TextValue` are no-ops without `compact_str`.
+#![cfg_attr(not(feature = "compact_str"), allow(clippy::useless_conversion))]
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/wooorm/markdown-rs/8924580/media/logo-monochromatic.svg?sanitize=true"
 )]
@@ -49,6 +71,8 @@ mod generate;
 
 pub mod mdast; // To do: externalize?
 pub mod message; // To do: externalize.
+pub mod shared;
+pub mod transform;
 pub mod unist; // To do: externalize.
 
 #[doc(hidden)]
@@ -60,16 +84,33 @@ pub use util::sanitize_uri::sanitize;
 #[doc(hidden)]
 pub use util::location::Location;
 
+pub use util::extension::{Extension, ExtensionToHtml, ExtensionToMdast, ExtensionTokenize};
+
 pub use util::line_ending::LineEnding;
 
+pub use util::normalize_identifier::normalize_identifier;
+
+pub use util::line_index::LineIndex;
+
+pub use util::code_meta::CodeMeta;
+
+pub use util::inspect::inspect;
+
+pub use util::roundtrip::{verify_roundtrip, verify_roundtrip_tree, RoundtripDivergence};
+
 pub use util::mdx::{
     EsmParse as MdxEsmParse, ExpressionKind as MdxExpressionKind,
     ExpressionParse as MdxExpressionParse, Signal as MdxSignal,
 };
 
-pub use configuration::{CompileOptions, Constructs, Options, ParseOptions};
+pub use configuration::{
+    BreakKind, CharacterReferenceKind, CompileOptions, Constructs, DuplicateDefinitionKind,
+    EmojiLookup, GfmAutolinkLiteralFilter, GfmFootnotePlacementKind, Options, ParseOptions,
+    SerializeOptions, WikiLinkUrl,
+};
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
 
 /// Turn markdown into HTML.
 ///
@@ -124,12 +165,43 @@ pub fn to_html(value: &str) -> String {
 /// # }
 /// ```
 pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, message::Message> {
+    let (html, _) = to_html_with_messages(value, options)?;
+    Ok(html)
+}
+
+/// Turn markdown into HTML, with configuration, and get messages about the
+/// document.
+///
+/// Messages are not errors: they warn about things that are probably
+/// mistakes, such as duplicate definitions.
+///
+/// ## Errors
+///
+/// See [`to_html_with_options()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_with_messages, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (html, messages) = to_html_with_messages("[a]\n\n[a]: b\n[a]: c", &Options::default())?;
+///
+/// assert_eq!(html, "<p><a href=\"b\">a</a></p>\n");
+/// assert_eq!(
+///     messages[0].to_string(),
+///     "4:1-4:7: Unexpected duplicate definition `a`, expected unique identifiers (first defined at 3:1), the first definition is used (markdown-rs:duplicate-definition)"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_with_messages(
+    value: &str,
+    options: &Options,
+) -> Result<(String, Vec<message::Message>), message::Message> {
     let (events, parse_state) = parser::parse(value, &options.parse)?;
-    Ok(to_html::compile(
-        &events,
-        parse_state.bytes,
-        &options.compile,
-    ))
+    let html = to_html::compile(&events, parse_state.bytes, &options.compile, &options.parse);
+    Ok((html, parse_state.messages))
 }
 
 /// Turn markdown into a syntax tree.
@@ -151,16 +223,45 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, me
 /// let tree = to_mdast("# Hey, *you*!", &ParseOptions::default())?;
 ///
 /// println!("{:?}", tree);
-/// // => Root { children: [Heading { children: [Text { value: "Hey, ", position: Some(1:3-1:8 (2-7)) }, Emphasis { children: [Text { value: "you", position: Some(1:9-1:12 (8-11)) }], position: Some(1:8-1:13 (7-12)) }, Text { value: "!", position: Some(1:13-1:14 (12-13)) }], position: Some(1:1-1:14 (0-13)), depth: 1 }], position: Some(1:1-1:14 (0-13)) }
+/// // => Root { children: [Heading { children: [Text { value: "Hey, ", position: Some(1:3-1:8 (2-7)) }, Emphasis { children: [Text { value: "you", position: Some(1:9-1:12 (8-11)) }], position: Some(1:8-1:13 (7-12)), marker: Some('*') }, Text { value: "!", position: Some(1:13-1:14 (12-13)) }], position: Some(1:1-1:14 (0-13)), depth: 1, heading_kind: Some(Atx), attributes: None }], position: Some(1:1-1:14 (0-13)) }
 /// # Ok(())
 /// # }
 /// ```
 pub fn to_mdast(value: &str, options: &ParseOptions) -> Result<mdast::Node, message::Message> {
-    let (events, parse_state) = parser::parse(value, options)?;
-    let node = to_mdast::compile(&events, parse_state.bytes)?;
+    let (node, _) = to_mdast_with_messages(value, options)?;
     Ok(node)
 }
 
+/// Turn markdown into a syntax tree, and get messages about the document.
+///
+/// Messages are not errors: they warn about things that are probably
+/// mistakes, such as duplicate definitions.
+///
+/// ## Errors
+///
+/// See [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast_with_messages, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (_, messages) = to_mdast_with_messages("[a]: b\n[a]: c", &ParseOptions::default())?;
+///
+/// assert_eq!(messages.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_with_messages(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<(mdast::Node, Vec<message::Message>), message::Message> {
+    let (events, parse_state) = parser::parse(value, options)?;
+    let node = to_mdast::compile(&events, parse_state.bytes, options)?;
+    Ok((node, parse_state.messages))
+}
+
 
 /// Turn a syntax tree into markdown
 /// 
@@ -180,4 +281,183 @@ pub fn to_mdast(value: &str, options: &ParseOptions) -> Result<mdast::Node, mess
 /// ```
 pub fn to_markdown(node: mdast::Node) -> String {
     generate::to_markdown(&node)
-}
\ No newline at end of file
+}
+
+/// Turn a syntax tree into markdown, written into `out`.
+///
+/// This is like [`to_markdown()`][], but markdown is written as it is
+/// generated, instead of building a string for every node, which is useful
+/// for large trees.
+///
+/// ## Errors
+///
+/// There are errors when `out` fails.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_markdown_to, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("> # Hey, *you*!", &ParseOptions::default())?;
+///
+/// let mut markdown = String::new();
+/// to_markdown_to(&tree, &mut markdown).unwrap();
+/// assert_eq!(markdown, "> # Hey, *you*!\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_markdown_to(node: &mdast::Node, out: &mut impl fmt::Write) -> fmt::Result {
+    generate::to_markdown_to(node, out)
+}
+
+/// Turn a syntax tree into markdown, with options.
+///
+/// See [`SerializeOptions`][] for the styles that can be configured.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("***", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     to_markdown_with_options(
+///         &tree,
+///         &SerializeOptions {
+///             rule: '-',
+///             ..SerializeOptions::default()
+///         }
+///     ),
+///     "---\n\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn to_markdown_with_options(node: &mdast::Node, options: &SerializeOptions) -> String {
+    generate::serialize(node, options)
+}
+
+/// Turn a syntax tree into markdown, keeping the formatting of `source` where
+/// the tree did not change.
+///
+/// `tree` is expected to be parsed from `source` with `options`, and then
+/// changed.
+/// `source` is parsed again: children of the root of `tree` that are the same
+/// as there, positions included, are copied from `source`, along with the
+/// whitespace between them.
+/// Other children (such as changed or new ones) are turned into markdown
+/// like [`to_markdown()`][] does.
+///
+/// ## Errors
+///
+/// There are errors when `source` cannot be parsed, which happens with MDX.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_markdown_preserving, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let source = "Some *emphasis*\nover two lines.\n\n# Old  title\n\n+ a\n";
+/// let mut tree = to_mdast(source, &ParseOptions::default())?;
+///
+/// if let Node::Heading(heading) = &mut tree.children_mut().unwrap()[1] {
+///     if let Node::Text(text) = &mut heading.children[0] {
+///         text.value = "New title".into();
+///     }
+/// }
+///
+/// assert_eq!(
+///     to_markdown_preserving(&tree, source, &ParseOptions::default())?,
+///     "Some *emphasis*\nover two lines.\n\n# New title\n\n+ a\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_markdown_preserving(
+    tree: &mdast::Node,
+    source: &str,
+    options: &ParseOptions,
+) -> Result<String, message::Message> {
+    let original = to_mdast(source, options)?;
+    Ok(generate::preserve(tree, &original, source))
+}
+
+/// Turn a syntax tree into markdown, regenerating only the parts that changed
+/// from `original`.
+///
+/// `original` is expected to be parsed from `source`, and `tree` to be a
+/// changed copy of it.
+/// Nodes of `tree` that are the same as in `original`, positions included,
+/// are copied from `source`.
+/// Nodes that only differ in their children keep their markup from `source`,
+/// with their children spliced in, so that diffs stay as small as possible.
+/// Other nodes (such as changed or new ones) are turned into markdown like
+/// [`to_markdown()`][] does.
+///
+/// Unlike [`to_markdown_preserving()`][], this does not parse `source` again,
+/// and it looks into the children of the root.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_markdown_spliced, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let source = "> Some *emphasis*  \n> and a [link][].\n\n[link]: https://example.com\n";
+/// let original = to_mdast(source, &ParseOptions::default())?;
+/// let mut tree = original.clone();
+///
+/// if let Node::BlockQuote(quote) = &mut tree.children_mut().unwrap()[0] {
+///     if let Node::Paragraph(paragraph) = &mut quote.children[0] {
+///         if let Node::Text(text) = &mut paragraph.children[3] {
+///             text.value = "and no ".into();
+///         }
+///     }
+/// }
+///
+/// assert_eq!(
+///     to_markdown_spliced(&tree, &original, source),
+///     "> Some *emphasis*  \n> and no [link][].\n\n[link]: https://example.com\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn to_markdown_spliced(tree: &mdast::Node, original: &mdast::Node, source: &str) -> String {
+    generate::splice(tree, original, source)
+}
+
+/// Turn a syntax tree into markdown, checking that it can be represented.
+///
+/// Frontmatter (`Yaml` and `Toml` nodes) can only be the first child of the
+/// root.
+/// Images, footnotes, and MDX expressions and ESM cannot be turned into
+/// markdown yet.
+///
+/// ## Errors
+///
+/// There are errors when the tree cannot be represented in markdown, or has
+/// nodes that cannot be turned into markdown yet.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::{Node, Root, Yaml}, try_to_markdown};
+///
+/// let yaml = Node::Yaml(Yaml { value: "a: b".into(), position: None });
+///
+/// assert_eq!(
+///     try_to_markdown(&Node::Root(Root { children: vec![yaml.clone()], position: None })).unwrap(),
+///     "---\na: b\n---\n\n"
+/// );
+/// assert!(try_to_markdown(&Node::Root(Root { children: vec![yaml.clone(), yaml], position: None })).is_err());
+/// ```
+pub fn try_to_markdown(node: &mdast::Node) -> Result<String, message::Message> {
+    generate::validate(node)?;
+    Ok(generate::to_markdown(node))
+}
@@ -2,19 +2,38 @@
 //!
 //! [mdast]: https://github.com/syntax-tree/mdast
 
+pub mod build;
+pub mod visit;
+
+#[cfg(feature = "json")]
+use crate::unist::Point;
 use crate::unist::Position;
+use crate::util::code_meta::CodeMeta;
+#[cfg(feature = "json")]
+use alloc::format;
 use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
     fmt,
     string::{String, ToString},
+    vec,
     vec::Vec,
 };
+use core::hash::{Hash, Hasher};
 
 /// MDX: relative byte index into a string, to an absolute byte index into the
 /// whole document.
 pub type Stop = (usize, usize);
 
+/// Value of [`Text`][] and [`InlineCode`][], a `CompactString`.
+#[cfg(feature = "compact_str")]
+pub type TextValue = compact_str::CompactString;
+/// Value of [`Text`][] and [`InlineCode`][], a [`String`][].
+#[cfg(not(feature = "compact_str"))]
+pub type TextValue = String;
+
 /// Explicitness of a reference.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -29,15 +48,163 @@ pub enum ReferenceKind {
     Full,
 }
 
-/// GFM: alignment of phrasing content.
+/// Syntax of a heading.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum HeadingKind {
+    /// The heading starts with `#`s.
+    Atx,
+    /// The heading is underlined with `=`s or `-`s.
+    Setext,
+}
+
+/// Syntax of code (flow).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum CodeKind {
+    /// The code is between fences of `` ` ``s or `~`s.
+    Fenced,
+    /// The code is indented with four or more spaces.
+    Indented,
+}
+
+/// Attributes on a heading or code, such as `{#id .class key="value"}`.
 ///
-/// Used to align the contents of table cells within a table.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Used when [`attributes`][crate::Constructs::attributes] is on.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attributes {
+    /// Identifier (`#id` or `id=id`).
+    ///
+    /// When given several times, the last one is used.
+    pub id: Option<String>,
+    /// Classes (`.class` or `class="class other"`), in order.
+    pub classes: Vec<String>,
+    /// Other attributes (`key=value`), in order.
+    pub pairs: Vec<(String, String)>,
+}
+
+/// One citation in a [`Cite`][].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Citation {
+    /// Key of the cited work, without `@` (such as `doe99` in `@doe99`).
+    pub key: String,
+    /// How the citation is written.
+    pub mode: CitationMode,
+    /// Text before the key (such as `see` in `[see @doe99]`).
+    pub prefix: Option<String>,
+    /// Part of the work that is cited (such as `p. 33` in
+    /// `[@doe99, p. 33]`).
+    pub locator: Option<String>,
+    /// Text after the key and locator (such as `emphasis added` in
+    /// `[@doe99, p. 33, emphasis added]`).
+    pub suffix: Option<String>,
+}
+
+/// How a citation is written.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum CitationMode {
+    /// The citation is in brackets (as in `[@doe99]`).
+    Normal,
+    /// The citation is in brackets and the author is suppressed (as in
+    /// `[-@doe99]`).
+    SuppressAuthor,
+    /// The citation is part of the text, so the author is shown there (as in
+    /// `@doe99 says`).
+    AuthorInText,
+}
+
+/// GFM: kind of an alert.
+///
+/// Used on block quotes that start with a marker such as `[!NOTE]`, which
+/// GitHub renders as callouts.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
     serde(rename_all = "lowercase")
 )]
+pub enum AlertKind {
+    /// `[!NOTE]`: info users should notice, even when skimming.
+    Note,
+    /// `[!TIP]`: optional info to help users be more successful.
+    Tip,
+    /// `[!IMPORTANT]`: crucial info users need to succeed.
+    Important,
+    /// `[!WARNING]`: critical info that needs immediate attention.
+    Warning,
+    /// `[!CAUTION]`: negative consequences of an action.
+    Caution,
+}
+
+impl AlertKind {
+    /// Get the kind of alert from the value in a marker (such as `note` in
+    /// `[!note]`), case-insensitively.
+    ///
+    /// Returns `None` for unknown kinds.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<AlertKind> {
+        [
+            AlertKind::Note,
+            AlertKind::Tip,
+            AlertKind::Important,
+            AlertKind::Warning,
+            AlertKind::Caution,
+        ]
+        .iter()
+        .copied()
+        .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Get the value used in markers for this kind, such as `NOTE`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            AlertKind::Note => "NOTE",
+            AlertKind::Tip => "TIP",
+            AlertKind::Important => "IMPORTANT",
+            AlertKind::Warning => "WARNING",
+            AlertKind::Caution => "CAUTION",
+        }
+    }
+}
+
+/// Syntax of a link.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum LinkKind {
+    /// The link has a destination (`[a](b)`).
+    Resource,
+    /// The link is an autolink (`<https://a>`).
+    Autolink,
+    /// GFM: the link is an autolink literal (`https://a`).
+    GfmAutolinkLiteral,
+}
+
+/// GFM: alignment of phrasing content.
+///
+/// Used to align the contents of table cells within a table.
+///
+/// With the `serde` feature, no alignment is `null`, like in mdast.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum AlignKind {
     /// Left alignment.
     ///
@@ -81,12 +248,62 @@ pub enum AlignKind {
     None,
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlignKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            AlignKind::Left => serializer.serialize_str("left"),
+            AlignKind::Right => serializer.serialize_str("right"),
+            AlignKind::Center => serializer.serialize_str("center"),
+            AlignKind::None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlignKind {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some("left") => Ok(AlignKind::Left),
+            Some("right") => Ok(AlignKind::Right),
+            Some("center") => Ok(AlignKind::Center),
+            Some("none") | None => Ok(AlignKind::None),
+            Some(value) => Err(serde::de::Error::unknown_variant(
+                value,
+                &["left", "right", "center"],
+            )),
+        }
+    }
+}
+
+/// Deserialize `null` as the default value, as some fields that are required
+/// here can be `null` in mdast (such as `alt` on images).
+#[cfg(feature = "serde")]
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + serde::Deserialize<'de>,
+{
+    use serde::Deserialize;
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Nodes.
+///
+/// Nodes with many fields (such as links, definitions, and code) are boxed,
+/// so that they do not make every node, and every list of children, as large
+/// as they are.
+///
+/// Children are stored in a `Vec`.
+/// A small vector that keeps a few nodes inline (such as `SmallVec<[Node; 2]>`)
+/// cannot be used: a node would then contain nodes, which gives the type an
+/// infinite size, and boxing the inline nodes brings back the allocation it is
+/// meant to avoid.
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "type")
+    serde(tag = "type", rename_all = "camelCase")
 )]
 pub enum Node {
     // Document:
@@ -95,17 +312,20 @@ pub enum Node {
 
     // Container:
     /// Block quote.
+    #[cfg_attr(feature = "serde", serde(rename = "blockquote"))]
     BlockQuote(BlockQuote),
     /// Footnote definition.
-    FootnoteDefinition(FootnoteDefinition),
+    FootnoteDefinition(Box<FootnoteDefinition>),
     /// MDX: JSX element (container).
-    MdxJsxFlowElement(MdxJsxFlowElement),
+    MdxJsxFlowElement(Box<MdxJsxFlowElement>),
     /// List.
     List(List),
+    /// Definition list.
+    DefinitionList(DefinitionList),
 
     // Frontmatter:
     /// MDX.js ESM.
-    MdxjsEsm(MdxjsEsm),
+    MdxjsEsm(Box<MdxjsEsm>),
     /// Toml.
     Toml(Toml),
     /// Yaml.
@@ -114,30 +334,58 @@ pub enum Node {
     // Phrasing:
     /// Break.
     Break(Break),
+    /// Character reference.
+    CharacterReference(Box<CharacterReference>),
+    /// Emoji.
+    Emoji(Box<Emoji>),
+    /// Abbreviation.
+    Abbreviation(Box<Abbreviation>),
+    /// Block identifier.
+    BlockId(BlockId),
     /// Code (phrasing).
     InlineCode(InlineCode),
     /// Math (phrasing).
     InlineMath(InlineMath),
     /// Delete.
     Delete(Delete),
+    /// Highlight.
+    Highlight(Highlight),
+    /// Insert.
+    Insert(Insert),
+    /// `CriticMarkup`: addition.
+    CriticAddition(CriticAddition),
+    /// `CriticMarkup`: deletion.
+    CriticDeletion(CriticDeletion),
+    /// `CriticMarkup`: substitution.
+    CriticSubstitution(CriticSubstitution),
+    /// `CriticMarkup`: highlight.
+    CriticHighlight(CriticHighlight),
+    /// `CriticMarkup`: comment.
+    CriticComment(CriticComment),
     /// Emphasis.
     Emphasis(Emphasis),
     // MDX: expression (text).
-    MdxTextExpression(MdxTextExpression),
+    MdxTextExpression(Box<MdxTextExpression>),
     /// Footnote reference.
-    FootnoteReference(FootnoteReference),
+    FootnoteReference(Box<FootnoteReference>),
     /// Html (phrasing).
     Html(Html),
+    /// Comment.
+    Comment(Comment),
     /// Image.
-    Image(Image),
+    Image(Box<Image>),
     /// Image reference.
-    ImageReference(ImageReference),
+    ImageReference(Box<ImageReference>),
     // MDX: JSX element (text).
-    MdxJsxTextElement(MdxJsxTextElement),
+    MdxJsxTextElement(Box<MdxJsxTextElement>),
     /// Link.
-    Link(Link),
+    Link(Box<Link>),
     /// Link reference.
-    LinkReference(LinkReference),
+    LinkReference(Box<LinkReference>),
+    /// Wiki link.
+    WikiLink(Box<WikiLink>),
+    /// Citation.
+    Cite(Cite),
     /// Strong
     Strong(Strong),
     /// Text.
@@ -145,17 +393,17 @@ pub enum Node {
 
     // Flow:
     /// Code (flow).
-    Code(Code),
+    Code(Box<Code>),
     /// Math (flow).
-    Math(Math),
+    Math(Box<Math>),
     // MDX: expression (flow).
-    MdxFlowExpression(MdxFlowExpression),
+    MdxFlowExpression(Box<MdxFlowExpression>),
     /// Heading.
-    Heading(Heading),
+    Heading(Box<Heading>),
     /// Html (flow).
     // Html(Html),
     /// Table.
-    Table(Table),
+    Table(Box<Table>),
     /// Thematic break.
     ThematicBreak(ThematicBreak),
 
@@ -171,13 +419,154 @@ pub enum Node {
     /// List item.
     ListItem(ListItem),
 
+    // Definition list content.
+    /// Definition term.
+    DefinitionTerm(DefinitionTerm),
+    /// Definition description.
+    DefinitionDescription(DefinitionDescription),
+
     // Content.
     /// Definition.
-    Definition(Definition),
+    Definition(Box<Definition>),
+    /// Abbreviation definition.
+    AbbreviationDefinition(Box<AbbreviationDefinition>),
     /// Paragraph.
     Paragraph(Paragraph),
 }
 
+/// Kind of a node.
+///
+/// Each variant corresponds to a variant of [`Node`][], without its fields.
+/// Get it with [`Node::kind()`][].
+/// Kinds can be used as keys in maps and sets, such as tables of hooks for
+/// each kind of node.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum NodeKind {
+    // Document:
+    /// Root.
+    Root,
+
+    // Container:
+    /// Block quote.
+    BlockQuote,
+    /// Footnote definition.
+    FootnoteDefinition,
+    /// MDX: JSX element (container).
+    MdxJsxFlowElement,
+    /// List.
+    List,
+    /// Definition list.
+    DefinitionList,
+
+    // Frontmatter:
+    /// MDX.js ESM.
+    MdxjsEsm,
+    /// Toml.
+    Toml,
+    /// Yaml.
+    Yaml,
+
+    // Phrasing:
+    /// Break.
+    Break,
+    /// Character reference.
+    CharacterReference,
+    /// Emoji.
+    Emoji,
+    /// Abbreviation.
+    Abbreviation,
+    /// Block identifier.
+    BlockId,
+    /// Code (phrasing).
+    InlineCode,
+    /// Math (phrasing).
+    InlineMath,
+    /// Delete.
+    Delete,
+    /// Highlight.
+    Highlight,
+    /// Insert.
+    Insert,
+    /// `CriticMarkup`: addition.
+    CriticAddition,
+    /// `CriticMarkup`: deletion.
+    CriticDeletion,
+    /// `CriticMarkup`: substitution.
+    CriticSubstitution,
+    /// `CriticMarkup`: highlight.
+    CriticHighlight,
+    /// `CriticMarkup`: comment.
+    CriticComment,
+    /// Emphasis.
+    Emphasis,
+    // MDX: expression (text).
+    MdxTextExpression,
+    /// Footnote reference.
+    FootnoteReference,
+    /// Html (phrasing).
+    Html,
+    /// Comment.
+    Comment,
+    /// Image.
+    Image,
+    /// Image reference.
+    ImageReference,
+    // MDX: JSX element (text).
+    MdxJsxTextElement,
+    /// Link.
+    Link,
+    /// Link reference.
+    LinkReference,
+    /// Wiki link.
+    WikiLink,
+    /// Citation.
+    Cite,
+    /// Strong
+    Strong,
+    /// Text.
+    Text,
+
+    // Flow:
+    /// Code (flow).
+    Code,
+    /// Math (flow).
+    Math,
+    // MDX: expression (flow).
+    MdxFlowExpression,
+    /// Heading.
+    Heading,
+    /// Table.
+    Table,
+    /// Thematic break.
+    ThematicBreak,
+
+    // Table content.
+    /// Table row.
+    TableRow,
+
+    // Row content.
+    /// Table cell.
+    TableCell,
+
+    // List content.
+    /// List item.
+    ListItem,
+
+    // Definition list content.
+    /// Definition term.
+    DefinitionTerm,
+    /// Definition description.
+    DefinitionDescription,
+
+    // Content.
+    /// Definition.
+    Definition,
+    /// Abbreviation definition.
+    AbbreviationDefinition,
+    /// Paragraph.
+    Paragraph,
+}
+
 impl fmt::Debug for Node {
     // Debug the wrapped struct.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -187,22 +576,37 @@ impl fmt::Debug for Node {
             Node::FootnoteDefinition(x) => x.fmt(f),
             Node::MdxJsxFlowElement(x) => x.fmt(f),
             Node::List(x) => x.fmt(f),
+            Node::DefinitionList(x) => x.fmt(f),
             Node::MdxjsEsm(x) => x.fmt(f),
             Node::Toml(x) => x.fmt(f),
             Node::Yaml(x) => x.fmt(f),
             Node::Break(x) => x.fmt(f),
+            Node::CharacterReference(x) => x.fmt(f),
+            Node::Emoji(x) => x.fmt(f),
+            Node::Abbreviation(x) => x.fmt(f),
+            Node::BlockId(x) => x.fmt(f),
             Node::InlineCode(x) => x.fmt(f),
             Node::InlineMath(x) => x.fmt(f),
             Node::Delete(x) => x.fmt(f),
+            Node::Highlight(x) => x.fmt(f),
+            Node::Insert(x) => x.fmt(f),
+            Node::CriticAddition(x) => x.fmt(f),
+            Node::CriticDeletion(x) => x.fmt(f),
+            Node::CriticSubstitution(x) => x.fmt(f),
+            Node::CriticHighlight(x) => x.fmt(f),
+            Node::CriticComment(x) => x.fmt(f),
             Node::Emphasis(x) => x.fmt(f),
             Node::MdxTextExpression(x) => x.fmt(f),
             Node::FootnoteReference(x) => x.fmt(f),
             Node::Html(x) => x.fmt(f),
+            Node::Comment(x) => x.fmt(f),
             Node::Image(x) => x.fmt(f),
             Node::ImageReference(x) => x.fmt(f),
             Node::MdxJsxTextElement(x) => x.fmt(f),
             Node::Link(x) => x.fmt(f),
             Node::LinkReference(x) => x.fmt(f),
+            Node::WikiLink(x) => x.fmt(f),
+            Node::Cite(x) => x.fmt(f),
             Node::Strong(x) => x.fmt(f),
             Node::Text(x) => x.fmt(f),
             Node::Code(x) => x.fmt(f),
@@ -214,63 +618,178 @@ impl fmt::Debug for Node {
             Node::TableRow(x) => x.fmt(f),
             Node::TableCell(x) => x.fmt(f),
             Node::ListItem(x) => x.fmt(f),
+            Node::DefinitionTerm(x) => x.fmt(f),
+            Node::DefinitionDescription(x) => x.fmt(f),
             Node::Definition(x) => x.fmt(f),
+            Node::AbbreviationDefinition(x) => x.fmt(f),
             Node::Paragraph(x) => x.fmt(f),
         }
     }
 }
 
-fn children_to_string(children: &[Node]) -> String {
-    children.iter().map(ToString::to_string).collect()
-}
-
 impl ToString for Node {
     fn to_string(&self) -> String {
-        match self {
-            // Parents.
-            Node::Root(x) => children_to_string(&x.children),
-            Node::BlockQuote(x) => children_to_string(&x.children),
-            Node::FootnoteDefinition(x) => children_to_string(&x.children),
-            Node::MdxJsxFlowElement(x) => children_to_string(&x.children),
-            Node::List(x) => children_to_string(&x.children),
-            Node::Delete(x) => children_to_string(&x.children),
-            Node::Emphasis(x) => children_to_string(&x.children),
-            Node::MdxJsxTextElement(x) => children_to_string(&x.children),
-            Node::Link(x) => children_to_string(&x.children),
-            Node::LinkReference(x) => children_to_string(&x.children),
-            Node::Strong(x) => children_to_string(&x.children),
-            Node::Heading(x) => children_to_string(&x.children),
-            Node::Table(x) => children_to_string(&x.children),
-            Node::TableRow(x) => children_to_string(&x.children),
-            Node::TableCell(x) => children_to_string(&x.children),
-            Node::ListItem(x) => children_to_string(&x.children),
-            Node::Paragraph(x) => children_to_string(&x.children),
-
-            // Literals.
-            Node::MdxjsEsm(x) => x.value.clone(),
-            Node::Toml(x) => x.value.clone(),
-            Node::Yaml(x) => x.value.clone(),
-            Node::InlineCode(x) => x.value.clone(),
-            Node::InlineMath(x) => x.value.clone(),
-            Node::MdxTextExpression(x) => x.value.clone(),
-            Node::Html(x) => x.value.clone(),
-            Node::Text(x) => x.value.clone(),
-            Node::Code(x) => x.value.clone(),
-            Node::Math(x) => x.value.clone(),
-            Node::MdxFlowExpression(x) => x.value.clone(),
-
-            // Voids.
-            Node::Break(_)
-            | Node::FootnoteReference(_)
-            | Node::Image(_)
-            | Node::ImageReference(_)
-            | Node::ThematicBreak(_)
-            | Node::Definition(_) => String::new(),
+        // Nodes are walked with a stack instead of by recursion, so that
+        // deeply nested trees do not overflow the call stack.
+        let mut result = String::new();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            match node {
+                // Parents.
+                Node::Root(_)
+                | Node::BlockQuote(_)
+                | Node::FootnoteDefinition(_)
+                | Node::MdxJsxFlowElement(_)
+                | Node::List(_)
+                | Node::DefinitionList(_)
+                | Node::Delete(_)
+                | Node::Highlight(_)
+                | Node::Insert(_)
+                | Node::CriticAddition(_)
+                | Node::CriticDeletion(_)
+                | Node::CriticSubstitution(_)
+                | Node::CriticHighlight(_)
+                | Node::CriticComment(_)
+                | Node::Emphasis(_)
+                | Node::MdxJsxTextElement(_)
+                | Node::Link(_)
+                | Node::LinkReference(_)
+                | Node::Strong(_)
+                | Node::Heading(_)
+                | Node::Table(_)
+                | Node::TableRow(_)
+                | Node::TableCell(_)
+                | Node::ListItem(_)
+                | Node::DefinitionTerm(_)
+                | Node::DefinitionDescription(_)
+                | Node::Paragraph(_) => stack.extend(node.children().into_iter().flatten().rev()),
+
+                // Literals.
+                Node::MdxjsEsm(x) => result.push_str(&x.value),
+                Node::Toml(x) => result.push_str(&x.value),
+                Node::Yaml(x) => result.push_str(&x.value),
+                Node::InlineCode(x) => result.push_str(&x.value),
+                Node::InlineMath(x) => result.push_str(&x.value),
+                Node::MdxTextExpression(x) => result.push_str(&x.value),
+                Node::Html(x) => result.push_str(&x.value),
+                Node::Text(x) => result.push_str(&x.value),
+                Node::CharacterReference(x) => result.push_str(&x.value),
+                Node::Emoji(x) => result.push_str(&x.value),
+                Node::Abbreviation(x) => result.push_str(&x.value),
+                Node::Code(x) => result.push_str(&x.value),
+                Node::Math(x) => result.push_str(&x.value),
+                Node::MdxFlowExpression(x) => result.push_str(&x.value),
+
+                // Wiki links, which show their label, or their target.
+                Node::WikiLink(x) => {
+                    if let Some(label) = &x.label {
+                        result.push_str(label);
+                    } else {
+                        result.push_str(&x.target);
+                        if let Some(anchor) = &x.anchor {
+                            result.push('#');
+                            result.push_str(anchor);
+                        }
+                    }
+                }
+
+                // Voids.
+                Node::Break(_)
+                | Node::BlockId(_)
+                | Node::Cite(_)
+                | Node::Comment(_)
+                | Node::FootnoteReference(_)
+                | Node::Image(_)
+                | Node::ImageReference(_)
+                | Node::ThematicBreak(_)
+                | Node::Definition(_)
+                | Node::AbbreviationDefinition(_) => {}
+            }
         }
+        result
     }
 }
 
 impl Node {
+    /// Get the kind of this node.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodeKind, to_mdast, ParseOptions};
+    /// use std::collections::HashMap;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a *b* *c*", &ParseOptions::default())?;
+    /// let mut counts = HashMap::new();
+    ///
+    /// for node in tree.descendants() {
+    ///     *counts.entry(node.kind()).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(counts[&NodeKind::Emphasis], 2);
+    /// assert_eq!(counts[&NodeKind::Text], 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> NodeKind {
+        match self {
+            Node::Root(_) => NodeKind::Root,
+            Node::BlockQuote(_) => NodeKind::BlockQuote,
+            Node::FootnoteDefinition(_) => NodeKind::FootnoteDefinition,
+            Node::MdxJsxFlowElement(_) => NodeKind::MdxJsxFlowElement,
+            Node::List(_) => NodeKind::List,
+            Node::DefinitionList(_) => NodeKind::DefinitionList,
+            Node::MdxjsEsm(_) => NodeKind::MdxjsEsm,
+            Node::Toml(_) => NodeKind::Toml,
+            Node::Yaml(_) => NodeKind::Yaml,
+            Node::Break(_) => NodeKind::Break,
+            Node::CharacterReference(_) => NodeKind::CharacterReference,
+            Node::Emoji(_) => NodeKind::Emoji,
+            Node::Abbreviation(_) => NodeKind::Abbreviation,
+            Node::BlockId(_) => NodeKind::BlockId,
+            Node::InlineCode(_) => NodeKind::InlineCode,
+            Node::InlineMath(_) => NodeKind::InlineMath,
+            Node::Delete(_) => NodeKind::Delete,
+            Node::Highlight(_) => NodeKind::Highlight,
+            Node::Insert(_) => NodeKind::Insert,
+            Node::CriticAddition(_) => NodeKind::CriticAddition,
+            Node::CriticDeletion(_) => NodeKind::CriticDeletion,
+            Node::CriticSubstitution(_) => NodeKind::CriticSubstitution,
+            Node::CriticHighlight(_) => NodeKind::CriticHighlight,
+            Node::CriticComment(_) => NodeKind::CriticComment,
+            Node::Emphasis(_) => NodeKind::Emphasis,
+            Node::MdxTextExpression(_) => NodeKind::MdxTextExpression,
+            Node::FootnoteReference(_) => NodeKind::FootnoteReference,
+            Node::Html(_) => NodeKind::Html,
+            Node::Comment(_) => NodeKind::Comment,
+            Node::Image(_) => NodeKind::Image,
+            Node::ImageReference(_) => NodeKind::ImageReference,
+            Node::MdxJsxTextElement(_) => NodeKind::MdxJsxTextElement,
+            Node::Link(_) => NodeKind::Link,
+            Node::LinkReference(_) => NodeKind::LinkReference,
+            Node::WikiLink(_) => NodeKind::WikiLink,
+            Node::Cite(_) => NodeKind::Cite,
+            Node::Strong(_) => NodeKind::Strong,
+            Node::Text(_) => NodeKind::Text,
+            Node::Code(_) => NodeKind::Code,
+            Node::Math(_) => NodeKind::Math,
+            Node::MdxFlowExpression(_) => NodeKind::MdxFlowExpression,
+            Node::Heading(_) => NodeKind::Heading,
+            Node::Table(_) => NodeKind::Table,
+            Node::ThematicBreak(_) => NodeKind::ThematicBreak,
+            Node::TableRow(_) => NodeKind::TableRow,
+            Node::TableCell(_) => NodeKind::TableCell,
+            Node::ListItem(_) => NodeKind::ListItem,
+            Node::DefinitionTerm(_) => NodeKind::DefinitionTerm,
+            Node::DefinitionDescription(_) => NodeKind::DefinitionDescription,
+            Node::Definition(_) => NodeKind::Definition,
+            Node::AbbreviationDefinition(_) => NodeKind::AbbreviationDefinition,
+            Node::Paragraph(_) => NodeKind::Paragraph,
+        }
+    }
+
     #[must_use]
     pub fn children(&self) -> Option<&Vec<Node>> {
         match self {
@@ -280,7 +799,10 @@ impl Node {
             Node::Heading(x) => Some(&x.children),
             Node::BlockQuote(x) => Some(&x.children),
             Node::List(x) => Some(&x.children),
+            Node::DefinitionList(x) => Some(&x.children),
             Node::ListItem(x) => Some(&x.children),
+            Node::DefinitionTerm(x) => Some(&x.children),
+            Node::DefinitionDescription(x) => Some(&x.children),
             Node::Emphasis(x) => Some(&x.children),
             Node::Strong(x) => Some(&x.children),
             Node::Link(x) => Some(&x.children),
@@ -290,6 +812,13 @@ impl Node {
             Node::TableRow(x) => Some(&x.children),
             Node::TableCell(x) => Some(&x.children),
             Node::Delete(x) => Some(&x.children),
+            Node::Highlight(x) => Some(&x.children),
+            Node::Insert(x) => Some(&x.children),
+            Node::CriticAddition(x) => Some(&x.children),
+            Node::CriticDeletion(x) => Some(&x.children),
+            Node::CriticSubstitution(x) => Some(&x.children),
+            Node::CriticHighlight(x) => Some(&x.children),
+            Node::CriticComment(x) => Some(&x.children),
             Node::MdxJsxFlowElement(x) => Some(&x.children),
             Node::MdxJsxTextElement(x) => Some(&x.children),
             // Non-parent.
@@ -305,7 +834,10 @@ impl Node {
             Node::Heading(x) => Some(&mut x.children),
             Node::BlockQuote(x) => Some(&mut x.children),
             Node::List(x) => Some(&mut x.children),
+            Node::DefinitionList(x) => Some(&mut x.children),
             Node::ListItem(x) => Some(&mut x.children),
+            Node::DefinitionTerm(x) => Some(&mut x.children),
+            Node::DefinitionDescription(x) => Some(&mut x.children),
             Node::Emphasis(x) => Some(&mut x.children),
             Node::Strong(x) => Some(&mut x.children),
             Node::Link(x) => Some(&mut x.children),
@@ -315,6 +847,13 @@ impl Node {
             Node::TableRow(x) => Some(&mut x.children),
             Node::TableCell(x) => Some(&mut x.children),
             Node::Delete(x) => Some(&mut x.children),
+            Node::Highlight(x) => Some(&mut x.children),
+            Node::Insert(x) => Some(&mut x.children),
+            Node::CriticAddition(x) => Some(&mut x.children),
+            Node::CriticDeletion(x) => Some(&mut x.children),
+            Node::CriticSubstitution(x) => Some(&mut x.children),
+            Node::CriticHighlight(x) => Some(&mut x.children),
+            Node::CriticComment(x) => Some(&mut x.children),
             Node::MdxJsxFlowElement(x) => Some(&mut x.children),
             Node::MdxJsxTextElement(x) => Some(&mut x.children),
             // Non-parent.
@@ -330,22 +869,37 @@ impl Node {
             Node::FootnoteDefinition(x) => x.position.as_ref(),
             Node::MdxJsxFlowElement(x) => x.position.as_ref(),
             Node::List(x) => x.position.as_ref(),
+            Node::DefinitionList(x) => x.position.as_ref(),
             Node::MdxjsEsm(x) => x.position.as_ref(),
             Node::Toml(x) => x.position.as_ref(),
             Node::Yaml(x) => x.position.as_ref(),
             Node::Break(x) => x.position.as_ref(),
+            Node::CharacterReference(x) => x.position.as_ref(),
+            Node::Emoji(x) => x.position.as_ref(),
+            Node::Abbreviation(x) => x.position.as_ref(),
+            Node::BlockId(x) => x.position.as_ref(),
             Node::InlineCode(x) => x.position.as_ref(),
             Node::InlineMath(x) => x.position.as_ref(),
             Node::Delete(x) => x.position.as_ref(),
+            Node::Highlight(x) => x.position.as_ref(),
+            Node::Insert(x) => x.position.as_ref(),
+            Node::CriticAddition(x) => x.position.as_ref(),
+            Node::CriticDeletion(x) => x.position.as_ref(),
+            Node::CriticSubstitution(x) => x.position.as_ref(),
+            Node::CriticHighlight(x) => x.position.as_ref(),
+            Node::CriticComment(x) => x.position.as_ref(),
             Node::Emphasis(x) => x.position.as_ref(),
             Node::MdxTextExpression(x) => x.position.as_ref(),
             Node::FootnoteReference(x) => x.position.as_ref(),
             Node::Html(x) => x.position.as_ref(),
+            Node::Comment(x) => x.position.as_ref(),
             Node::Image(x) => x.position.as_ref(),
             Node::ImageReference(x) => x.position.as_ref(),
             Node::MdxJsxTextElement(x) => x.position.as_ref(),
             Node::Link(x) => x.position.as_ref(),
             Node::LinkReference(x) => x.position.as_ref(),
+            Node::WikiLink(x) => x.position.as_ref(),
+            Node::Cite(x) => x.position.as_ref(),
             Node::Strong(x) => x.position.as_ref(),
             Node::Text(x) => x.position.as_ref(),
             Node::Code(x) => x.position.as_ref(),
@@ -357,7 +911,10 @@ impl Node {
             Node::TableRow(x) => x.position.as_ref(),
             Node::TableCell(x) => x.position.as_ref(),
             Node::ListItem(x) => x.position.as_ref(),
+            Node::DefinitionTerm(x) => x.position.as_ref(),
+            Node::DefinitionDescription(x) => x.position.as_ref(),
             Node::Definition(x) => x.position.as_ref(),
+            Node::AbbreviationDefinition(x) => x.position.as_ref(),
             Node::Paragraph(x) => x.position.as_ref(),
         }
     }
@@ -369,22 +926,37 @@ impl Node {
             Node::FootnoteDefinition(x) => x.position.as_mut(),
             Node::MdxJsxFlowElement(x) => x.position.as_mut(),
             Node::List(x) => x.position.as_mut(),
+            Node::DefinitionList(x) => x.position.as_mut(),
             Node::MdxjsEsm(x) => x.position.as_mut(),
             Node::Toml(x) => x.position.as_mut(),
             Node::Yaml(x) => x.position.as_mut(),
             Node::Break(x) => x.position.as_mut(),
+            Node::CharacterReference(x) => x.position.as_mut(),
+            Node::Emoji(x) => x.position.as_mut(),
+            Node::Abbreviation(x) => x.position.as_mut(),
+            Node::BlockId(x) => x.position.as_mut(),
             Node::InlineCode(x) => x.position.as_mut(),
             Node::InlineMath(x) => x.position.as_mut(),
             Node::Delete(x) => x.position.as_mut(),
+            Node::Highlight(x) => x.position.as_mut(),
+            Node::Insert(x) => x.position.as_mut(),
+            Node::CriticAddition(x) => x.position.as_mut(),
+            Node::CriticDeletion(x) => x.position.as_mut(),
+            Node::CriticSubstitution(x) => x.position.as_mut(),
+            Node::CriticHighlight(x) => x.position.as_mut(),
+            Node::CriticComment(x) => x.position.as_mut(),
             Node::Emphasis(x) => x.position.as_mut(),
             Node::MdxTextExpression(x) => x.position.as_mut(),
             Node::FootnoteReference(x) => x.position.as_mut(),
             Node::Html(x) => x.position.as_mut(),
+            Node::Comment(x) => x.position.as_mut(),
             Node::Image(x) => x.position.as_mut(),
             Node::ImageReference(x) => x.position.as_mut(),
             Node::MdxJsxTextElement(x) => x.position.as_mut(),
             Node::Link(x) => x.position.as_mut(),
             Node::LinkReference(x) => x.position.as_mut(),
+            Node::WikiLink(x) => x.position.as_mut(),
+            Node::Cite(x) => x.position.as_mut(),
             Node::Strong(x) => x.position.as_mut(),
             Node::Text(x) => x.position.as_mut(),
             Node::Code(x) => x.position.as_mut(),
@@ -396,7 +968,10 @@ impl Node {
             Node::TableRow(x) => x.position.as_mut(),
             Node::TableCell(x) => x.position.as_mut(),
             Node::ListItem(x) => x.position.as_mut(),
+            Node::DefinitionTerm(x) => x.position.as_mut(),
+            Node::DefinitionDescription(x) => x.position.as_mut(),
             Node::Definition(x) => x.position.as_mut(),
+            Node::AbbreviationDefinition(x) => x.position.as_mut(),
             Node::Paragraph(x) => x.position.as_mut(),
         }
     }
@@ -408,22 +983,37 @@ impl Node {
             Node::FootnoteDefinition(x) => x.position = position,
             Node::MdxJsxFlowElement(x) => x.position = position,
             Node::List(x) => x.position = position,
+            Node::DefinitionList(x) => x.position = position,
             Node::MdxjsEsm(x) => x.position = position,
             Node::Toml(x) => x.position = position,
             Node::Yaml(x) => x.position = position,
             Node::Break(x) => x.position = position,
+            Node::CharacterReference(x) => x.position = position,
+            Node::Emoji(x) => x.position = position,
+            Node::Abbreviation(x) => x.position = position,
+            Node::BlockId(x) => x.position = position,
             Node::InlineCode(x) => x.position = position,
             Node::InlineMath(x) => x.position = position,
             Node::Delete(x) => x.position = position,
+            Node::Highlight(x) => x.position = position,
+            Node::Insert(x) => x.position = position,
+            Node::CriticAddition(x) => x.position = position,
+            Node::CriticDeletion(x) => x.position = position,
+            Node::CriticSubstitution(x) => x.position = position,
+            Node::CriticHighlight(x) => x.position = position,
+            Node::CriticComment(x) => x.position = position,
             Node::Emphasis(x) => x.position = position,
             Node::MdxTextExpression(x) => x.position = position,
             Node::FootnoteReference(x) => x.position = position,
             Node::Html(x) => x.position = position,
+            Node::Comment(x) => x.position = position,
             Node::Image(x) => x.position = position,
             Node::ImageReference(x) => x.position = position,
             Node::MdxJsxTextElement(x) => x.position = position,
             Node::Link(x) => x.position = position,
             Node::LinkReference(x) => x.position = position,
+            Node::WikiLink(x) => x.position = position,
+            Node::Cite(x) => x.position = position,
             Node::Strong(x) => x.position = position,
             Node::Text(x) => x.position = position,
             Node::Code(x) => x.position = position,
@@ -435,951 +1025,3374 @@ impl Node {
             Node::TableRow(x) => x.position = position,
             Node::TableCell(x) => x.position = position,
             Node::ListItem(x) => x.position = position,
+            Node::DefinitionTerm(x) => x.position = position,
+            Node::DefinitionDescription(x) => x.position = position,
             Node::Definition(x) => x.position = position,
+            Node::AbbreviationDefinition(x) => x.position = position,
             Node::Paragraph(x) => x.position = position,
         }
     }
-}
 
-/// MDX: attribute content.
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxJsxExpressionAttribute")
-)]
-pub enum AttributeContent {
-    /// JSX expression.
+    /// Remove the positional info of this node and all its descendants.
+    ///
+    /// This is useful to compare trees that were made from different
+    /// documents or built by hand, such as in tests.
+    ///
+    /// ## Examples
     ///
-    /// ```markdown
-    /// > | <a {...b} />
-    ///        ^^^^^^
     /// ```
-    Expression { value: String, stops: Vec<Stop> },
-    /// JSX property.
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// ```markdown
-    /// > | <a b />
-    ///        ^
+    /// let mut tree = to_mdast("a", &ParseOptions::default())?;
+    /// tree.remove_positions();
+    ///
+    /// assert_eq!(tree, Node::root(vec![Node::paragraph(vec![Node::text("a")])]));
+    /// # Ok(())
+    /// # }
     /// ```
-    Property(MdxJsxAttribute),
-}
-//
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxJsxAttributeValueExpression")
-)]
-pub struct AttributeValueExpression {
-    pub value: String,
-    pub stops: Vec<Stop>,
-}
+    pub fn remove_positions(&mut self) {
+        let mut stack = vec![self];
 
-/// MDX: attribute value.
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "type")
-)]
-pub enum AttributeValue {
-    /// Expression value.
+        while let Some(node) = stack.pop() {
+            node.position_set(None);
+
+            if let Some(children) = node.children_mut() {
+                stack.extend(children.iter_mut());
+            }
+        }
+    }
+
+    /// Check whether this node and `other` are equal, ignoring the positional
+    /// info of them and their descendants.
+    ///
+    /// This is like comparing them after
+    /// [`remove_positions()`][Node::remove_positions], without cloning.
+    ///
+    /// ## Examples
     ///
-    /// ```markdown
-    /// > | <a b={c} />
-    ///          ^^^
     /// ```
-    Expression(AttributeValueExpression),
-    /// Static value.
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// ```markdown
-    /// > | <a b="c" />
-    ///          ^^^
+    /// let a = to_mdast("a", &ParseOptions::default())?;
+    /// let b = to_mdast("\n\na", &ParseOptions::default())?;
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_ignoring_positions(&b));
+    /// assert!(a.eq_ignoring_positions(&Node::root(vec![Node::paragraph(vec![Node::text("a")])])));
+    /// # Ok(())
+    /// # }
     /// ```
-    #[cfg_attr(feature = "serde", serde(rename = "literal"))]
-    Literal(String),
-}
+    #[must_use]
+    pub fn eq_ignoring_positions(&self, other: &Node) -> bool {
+        let mut stack = vec![(self, other)];
+
+        while let Some((left, right)) = stack.pop() {
+            if !shallow_eq(left, right) {
+                return false;
+            }
+
+            match (left.children(), right.children()) {
+                (Some(left), Some(right)) => {
+                    if left.len() != right.len() {
+                        return false;
+                    }
+
+                    stack.extend(left.iter().zip(right.iter()));
+                }
+                (None, None) => {}
+                _ => return false,
+            }
+        }
 
-/// Document.
-///
-/// ```markdown
-/// > | a
-///     ^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "root")
-)]
-pub struct Root {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
-}
+        true
+    }
 
-/// Paragraph.
-///
-/// ```markdown
-/// > | a
-///     ^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "paragraph")
-)]
-pub struct Paragraph {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
-}
+    /// Get the node at `path`, relative to this node.
+    ///
+    /// Returns `None` if there is no node at `path`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodePath, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a *b*", &ParseOptions::default())?;
+    /// let node = tree.get(&NodePath::from(vec![0, 1, 0])).unwrap();
+    ///
+    /// assert_eq!(node.to_string(), "b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get(&self, path: &NodePath) -> Option<&Node> {
+        let mut node = self;
 
-/// Heading.
-///
-/// ```markdown
-/// > | # a
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "heading")
-)]
-pub struct Heading {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
-    // Extra.
-    /// Rank (between `1` and `6`, both including).
-    pub depth: u8,
+        for &index in path.indices() {
+            node = node.children()?.get(index)?;
+        }
+
+        Some(node)
+    }
+
+    /// Get the node at `path`, relative to this node, mutably.
+    ///
+    /// Returns `None` if there is no node at `path`.
+    pub fn get_mut(&mut self, path: &NodePath) -> Option<&mut Node> {
+        let mut node = self;
+
+        for &index in path.indices() {
+            node = node.children_mut()?.get_mut(index)?;
+        }
+
+        Some(node)
+    }
+
+    /// Call `each` with this node and all its descendants, in document
+    /// order, along with their paths relative to this node.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodeKind, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a *b*", &ParseOptions::default())?;
+    /// let mut paths = vec![];
+    ///
+    /// tree.each_with_path(&mut |node, path| {
+    ///     if node.kind() == NodeKind::Text {
+    ///         paths.push(path.indices().to_vec());
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(paths, vec![vec![0, 0], vec![0, 1, 0]]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn each_with_path(&self, each: &mut dyn FnMut(&Node, &NodePath)) {
+        fn visit(node: &Node, path: &mut NodePath, each: &mut dyn FnMut(&Node, &NodePath)) {
+            each(node, path);
+
+            if let Some(children) = node.children() {
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    visit(child, path, each);
+                    path.pop();
+                }
+            }
+        }
+
+        visit(self, &mut NodePath::new(), each);
+    }
+
+    /// Get stable identifiers for this node and all its descendants, by
+    /// their paths relative to this node.
+    ///
+    /// Identifiers derive from the content of nodes (ignoring positions) and
+    /// from how many nodes with the same content come before them, in
+    /// document order.
+    /// When a document is parsed again, nodes whose content did not change
+    /// get the same identifiers, so that renderers that diff trees can patch
+    /// only the nodes that did change (and their ancestors).
+    ///
+    /// Identifiers are stable for a given version of this crate, not across
+    /// versions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodePath, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let before = to_mdast("a\n\nb", &ParseOptions::default())?.stable_ids();
+    /// let after = to_mdast("c\n\na\n\nb!", &ParseOptions::default())?.stable_ids();
+    ///
+    /// // `a` moved but did not change.
+    /// assert_eq!(before[&NodePath::from(vec![0])], after[&NodePath::from(vec![1])]);
+    /// // `b` changed.
+    /// assert_ne!(before[&NodePath::from(vec![1])], after[&NodePath::from(vec![2])]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stable_ids(&self) -> BTreeMap<NodePath, u64> {
+        fn visit(node: &Node, path: &mut NodePath, hashes: &mut Vec<(NodePath, u64)>) -> u64 {
+            let index = hashes.len();
+            hashes.push((path.clone(), 0));
+
+            // Hash the node itself, without position and children.
+            let mut state = Fnv(FNV_OFFSET);
+            shallow_hash(node, &mut state);
+            let mut hash = state.finish();
+
+            if let Some(children) = node.children() {
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    hash = fnv(hash, &visit(child, path, hashes).to_le_bytes());
+                    path.pop();
+                }
+            }
+
+            hashes[index].1 = hash;
+            hash
+        }
+
+        let mut hashes = vec![];
+        visit(self, &mut NodePath::new(), &mut hashes);
+
+        let mut seen = BTreeMap::new();
+        hashes
+            .into_iter()
+            .map(|(path, hash)| {
+                let count = seen.entry(hash).or_insert(0_u64);
+                let id = fnv(hash, &count.to_le_bytes());
+                *count += 1;
+                (path, id)
+            })
+            .collect()
+    }
+
+    /// Get an iterator over this node and all its descendants, in document
+    /// order (depth-first, parents before their children).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("[a](b) *[c](d)*", &ParseOptions::default())?;
+    ///
+    /// let urls = tree
+    ///     .descendants()
+    ///     .filter_map(|node| match node {
+    ///         Node::Link(link) => Some(link.url.as_str()),
+    ///         _ => None,
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(urls, vec!["b", "d"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants { stack: vec![self] }
+    }
+
+    /// Get an iterator over this node and all its descendants that cannot
+    /// have children (literals, such as text, and voids, such as breaks), in
+    /// document order, which can change them.
+    ///
+    /// Parents are not included, as they cannot be changed while references
+    /// to their children exist.
+    /// Use [`walk_mut()`][visit::walk_mut] to change parents.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let mut tree = to_mdast("a *b* `c`", &ParseOptions::default())?;
+    ///
+    /// for node in tree.descendants_mut() {
+    ///     if let Node::Text(text) = node {
+    ///         text.value = text.value.to_uppercase().into();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(tree.to_string(), "A B c");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn descendants_mut(&mut self) -> DescendantsMut<'_> {
+        DescendantsMut { stack: vec![self] }
+    }
+
+    /// Get the first node that passes `test`, of this node and its
+    /// descendants, in document order.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("# a\n\n## b", &ParseOptions::default())?;
+    /// let heading = tree.find(|node| matches!(node, Node::Heading(_))).unwrap();
+    ///
+    /// assert_eq!(heading.to_string(), "a");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find(&self, mut test: impl FnMut(&Node) -> bool) -> Option<&Node> {
+        self.descendants().find(|node| test(node))
+    }
+
+    /// Get all nodes that pass `test`, of this node and its descendants, in
+    /// document order.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("[a](b) <https://c>", &ParseOptions::default())?;
+    /// let urls = tree
+    ///     .find_all(|node| matches!(node, Node::Link(_)))
+    ///     .into_iter()
+    ///     .filter_map(|node| match node {
+    ///         Node::Link(link) => Some((link.url.as_str(), node.position().unwrap().start.column)),
+    ///         _ => None,
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(urls, vec![("b", 1), ("https://c", 8)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_all(&self, mut test: impl FnMut(&Node) -> bool) -> Vec<&Node> {
+        self.descendants().filter(|node| test(node)).collect()
+    }
+
+    /// Get all nodes that pass `test`, of this node and its descendants, in
+    /// document order, along with their paths relative to this node.
+    pub fn find_all_with_paths(
+        &self,
+        mut test: impl FnMut(&Node) -> bool,
+    ) -> Vec<(NodePath, &Node)> {
+        let mut cursor = TreeCursor::new(self);
+        let mut found = vec![];
+
+        loop {
+            if test(cursor.node()) {
+                found.push((cursor.path(), cursor.node()));
+            }
+
+            if !cursor.goto_next() {
+                break;
+            }
+        }
+
+        found
+    }
+
+    /// Get the deepest node that contains the byte `offset`, of this node and
+    /// its descendants, along with its ancestors.
+    ///
+    /// Returns the nodes from this node down to the deepest node, or an empty
+    /// list when this node does not contain `offset`.
+    /// A node contains the offsets from its start up to (not including) its
+    /// end.
+    /// Nodes without positional info are skipped.
+    ///
+    /// This is useful for editor integrations, such as to show info about the
+    /// node under the cursor.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodeKind, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("> a *b*", &ParseOptions::default())?;
+    /// let kinds = tree
+    ///     .node_at_offset(5)
+    ///     .into_iter()
+    ///     .map(|node| node.kind())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     kinds,
+    ///     vec![
+    ///         NodeKind::Root,
+    ///         NodeKind::BlockQuote,
+    ///         NodeKind::Paragraph,
+    ///         NodeKind::Emphasis,
+    ///         NodeKind::Text
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn node_at_offset(&self, offset: usize) -> Vec<&Node> {
+        let contains = |node: &Node| {
+            node.position().map_or(false, |position| {
+                position.start.offset <= offset && offset < position.end.offset
+            })
+        };
+        let mut nodes = vec![];
+
+        if contains(self) {
+            let mut node = self;
+            nodes.push(node);
+
+            while let Some(child) = node
+                .children()
+                .and_then(|children| children.iter().find(|child| contains(child)))
+            {
+                node = child;
+                nodes.push(node);
+            }
+        }
+
+        nodes
+    }
+
+    /// Get the source of this node in `document`: the exact text from its
+    /// start offset up to its end offset.
+    ///
+    /// `document` must be the value that the tree was made from (such as
+    /// with [`to_mdast`][crate::to_mdast]), as offsets are byte indices into
+    /// it (including a byte order mark, if there was one).
+    /// Returns `None` when this node has no positional info, or when its
+    /// offsets are out of bounds or not on char boundaries in `document`.
+    ///
+    /// The source is everything between the offsets, so it includes markers
+    /// (such as `*` around emphasis), escapes and character references as
+    /// written, and, for nodes spanning several lines in a container, the
+    /// prefixes of those lines (such as `> ` of block quotes).
+    /// The source of a node is always a substring of the source of its
+    /// parent.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let document = "> a *b &amp; c*";
+    /// let tree = to_mdast(document, &ParseOptions::default())?;
+    /// let emphasis = tree.node_at_offset(4).pop().unwrap();
+    ///
+    /// assert_eq!(emphasis.source(document), Some("*b &amp; c*"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn source<'a>(&self, document: &'a str) -> Option<&'a str> {
+        self.position()
+            .and_then(|position| document.get(position.start.offset..position.end.offset))
+    }
+
+    /// Parse a tree from JSON, such as trees made by
+    /// [`remark`](https://github.com/remarkjs/remark) and other tools in
+    /// JavaScript.
+    ///
+    /// Fields that are not used here (such as `data`) are ignored, and
+    /// optional fields can be missing or `null`.
+    /// Note that positions made in JavaScript count columns and offsets in
+    /// UTF-16 code units.
+    ///
+    /// ## Errors
+    ///
+    /// Errors when `value` is not JSON, or when it is not a tree of known
+    /// nodes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::Node;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = Node::from_json(r#"{
+    ///   "type": "root",
+    ///   "children": [{"type": "text", "value": "a", "data": {"b": "c"}}]
+    /// }"#)?;
+    ///
+    /// assert_eq!(tree, Node::root(vec![Node::text("a")]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_json(value: &str) -> Result<Node, crate::message::Message> {
+        serde_json::from_str(value).map_err(|error| {
+            let (line, column) = (error.line(), error.column());
+            let mut reason = error.to_string();
+            let mut place = None;
+
+            // Move the place from the reason to the message.
+            if line > 0 {
+                let suffix = format!(" at line {} column {}", line, column);
+                if reason.ends_with(&suffix) {
+                    reason.truncate(reason.len() - suffix.len());
+                }
+                let start: usize = value
+                    .split_inclusive('\n')
+                    .take(line - 1)
+                    .map(str::len)
+                    .sum();
+                let offset = (start + column.saturating_sub(1)).min(value.len());
+                place = Some(Box::new(crate::message::Place::Point(Point::new(
+                    line, column, offset,
+                ))));
+            }
+
+            crate::message::Message {
+                place,
+                reason: format!("Cannot parse tree from JSON: {}", reason),
+                rule_id: Box::new("invalid-json".into()),
+                source: Box::new("markdown-rs".into()),
+            }
+        })
+    }
 }
 
-/// Thematic break.
-///
-/// ```markdown
-/// > | ***
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "thematicBreak")
-)]
-pub struct ThematicBreak {
-    // Void.
-    /// Positional info.
-    pub position: Option<Position>,
+/// Check whether the fields of two nodes, other than their positions and
+/// children, are equal.
+fn shallow_eq(left: &Node, right: &Node) -> bool {
+    /// Compare the given fields of `$a` and `$b`.
+    macro_rules! fields {
+        ($a:expr, $b:expr $(, $field:ident)*) => {
+            true $(&& $a.$field == $b.$field)*
+        };
+    }
+
+    match (left, right) {
+        (Node::Root(_), Node::Root(_))
+        | (Node::Break(_), Node::Break(_))
+        | (Node::Delete(_), Node::Delete(_))
+        | (Node::Highlight(_), Node::Highlight(_))
+        | (Node::Insert(_), Node::Insert(_))
+        | (Node::CriticAddition(_), Node::CriticAddition(_))
+        | (Node::CriticDeletion(_), Node::CriticDeletion(_))
+        | (Node::CriticSubstitution(_), Node::CriticSubstitution(_))
+        | (Node::CriticHighlight(_), Node::CriticHighlight(_))
+        | (Node::CriticComment(_), Node::CriticComment(_))
+        | (Node::Paragraph(_), Node::Paragraph(_))
+        | (Node::TableRow(_), Node::TableRow(_))
+        | (Node::TableCell(_), Node::TableCell(_))
+        | (Node::DefinitionTerm(_), Node::DefinitionTerm(_))
+        | (Node::DefinitionDescription(_), Node::DefinitionDescription(_))
+        | (Node::ThematicBreak(_), Node::ThematicBreak(_)) => true,
+        (Node::BlockQuote(a), Node::BlockQuote(b)) => fields!(a, b, alert),
+        (Node::FootnoteDefinition(a), Node::FootnoteDefinition(b)) => {
+            fields!(a, b, identifier, label)
+        }
+        (Node::MdxJsxFlowElement(a), Node::MdxJsxFlowElement(b)) => {
+            fields!(a, b, name, attributes)
+        }
+        (Node::List(a), Node::List(b)) => fields!(a, b, ordered, start, spread, marker),
+        (Node::MdxjsEsm(a), Node::MdxjsEsm(b)) => fields!(a, b, value, stops),
+        (Node::Toml(a), Node::Toml(b)) => fields!(a, b, value),
+        (Node::Yaml(a), Node::Yaml(b)) => fields!(a, b, value),
+        (Node::CharacterReference(a), Node::CharacterReference(b)) => fields!(a, b, value, raw),
+        (Node::Emoji(a), Node::Emoji(b)) => fields!(a, b, value, shortcode),
+        (Node::Abbreviation(a), Node::Abbreviation(b)) => fields!(a, b, value, title),
+        (Node::BlockId(a), Node::BlockId(b)) => fields!(a, b, value),
+        (Node::InlineCode(a), Node::InlineCode(b)) => fields!(a, b, value, attributes),
+        (Node::InlineMath(a), Node::InlineMath(b)) => fields!(a, b, value),
+        (Node::Emphasis(a), Node::Emphasis(b)) => fields!(a, b, marker),
+        (Node::MdxTextExpression(a), Node::MdxTextExpression(b)) => fields!(a, b, value, stops),
+        (Node::FootnoteReference(a), Node::FootnoteReference(b)) => {
+            fields!(a, b, identifier, label)
+        }
+        (Node::Html(a), Node::Html(b)) => fields!(a, b, value),
+        (Node::Comment(a), Node::Comment(b)) => fields!(a, b, value),
+        (Node::Image(a), Node::Image(b)) => fields!(a, b, alt, url, title),
+        (Node::ImageReference(a), Node::ImageReference(b)) => {
+            fields!(a, b, alt, reference_kind, identifier, label)
+        }
+        (Node::MdxJsxTextElement(a), Node::MdxJsxTextElement(b)) => {
+            fields!(a, b, name, attributes)
+        }
+        (Node::Link(a), Node::Link(b)) => fields!(a, b, url, title, link_kind),
+        (Node::LinkReference(a), Node::LinkReference(b)) => {
+            fields!(a, b, reference_kind, identifier, label)
+        }
+        (Node::WikiLink(a), Node::WikiLink(b)) => fields!(a, b, target, anchor, label),
+        (Node::Cite(a), Node::Cite(b)) => fields!(a, b, citations),
+        (Node::Strong(a), Node::Strong(b)) => fields!(a, b, marker),
+        (Node::Text(a), Node::Text(b)) => fields!(a, b, value),
+        (Node::Code(a), Node::Code(b)) => {
+            fields!(
+                a,
+                b,
+                value,
+                lang,
+                meta,
+                code_kind,
+                fence_marker,
+                fence_size,
+                fence_indent,
+                attributes
+            )
+        }
+        (Node::Math(a), Node::Math(b)) => fields!(a, b, value, meta),
+        (Node::MdxFlowExpression(a), Node::MdxFlowExpression(b)) => fields!(a, b, value, stops),
+        (Node::Heading(a), Node::Heading(b)) => {
+            fields!(a, b, depth, heading_kind, attributes)
+        }
+        (Node::Table(a), Node::Table(b)) => fields!(a, b, align, caption),
+        (Node::ListItem(a), Node::ListItem(b)) => fields!(a, b, spread, checked),
+        (Node::DefinitionList(a), Node::DefinitionList(b)) => fields!(a, b, spread),
+        (Node::Definition(a), Node::Definition(b)) => {
+            fields!(a, b, url, title, identifier, label)
+        }
+        (Node::AbbreviationDefinition(a), Node::AbbreviationDefinition(b)) => {
+            fields!(a, b, label, title)
+        }
+        _ => false,
+    }
 }
 
-/// Block quote.
-///
-/// ```markdown
-/// > | > a
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "blockquote")
-)]
-pub struct BlockQuote {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
+/// Hash the fields of a node, other than its position and children, into
+/// `state`.
+fn shallow_hash(node: &Node, state: &mut Fnv) {
+    /// Hash the given fields of `$a`.
+    macro_rules! fields {
+        ($a:expr $(, $field:ident)*) => {{
+            $($a.$field.hash(state);)*
+        }};
+    }
+
+    core::mem::discriminant(node).hash(state);
+
+    match node {
+        Node::Root(_)
+        | Node::Break(_)
+        | Node::Delete(_)
+        | Node::Highlight(_)
+        | Node::Insert(_)
+        | Node::CriticAddition(_)
+        | Node::CriticDeletion(_)
+        | Node::CriticSubstitution(_)
+        | Node::CriticHighlight(_)
+        | Node::CriticComment(_)
+        | Node::Paragraph(_)
+        | Node::TableRow(_)
+        | Node::TableCell(_)
+        | Node::DefinitionTerm(_)
+        | Node::DefinitionDescription(_)
+        | Node::ThematicBreak(_) => {}
+        Node::BlockQuote(a) => fields!(a, alert),
+        Node::FootnoteDefinition(a) => {
+            fields!(a, identifier, label);
+        }
+        Node::MdxJsxFlowElement(a) => {
+            fields!(a, name, attributes);
+        }
+        Node::List(a) => fields!(a, ordered, start, spread, marker),
+        Node::MdxjsEsm(a) => fields!(a, value, stops),
+        Node::Toml(a) => fields!(a, value),
+        Node::Yaml(a) => fields!(a, value),
+        Node::CharacterReference(a) => fields!(a, value, raw),
+        Node::Emoji(a) => fields!(a, value, shortcode),
+        Node::Abbreviation(a) => fields!(a, value, title),
+        Node::BlockId(a) => fields!(a, value),
+        Node::InlineCode(a) => fields!(a, value, attributes),
+        Node::InlineMath(a) => fields!(a, value),
+        Node::Emphasis(a) => fields!(a, marker),
+        Node::MdxTextExpression(a) => fields!(a, value, stops),
+        Node::FootnoteReference(a) => {
+            fields!(a, identifier, label);
+        }
+        Node::Html(a) => fields!(a, value),
+        Node::Comment(a) => fields!(a, value),
+        Node::Image(a) => fields!(a, alt, url, title),
+        Node::ImageReference(a) => {
+            fields!(a, alt, reference_kind, identifier, label);
+        }
+        Node::MdxJsxTextElement(a) => {
+            fields!(a, name, attributes);
+        }
+        Node::Link(a) => fields!(a, url, title, link_kind),
+        Node::LinkReference(a) => {
+            fields!(a, reference_kind, identifier, label);
+        }
+        Node::WikiLink(a) => fields!(a, target, anchor, label),
+        Node::Cite(a) => fields!(a, citations),
+        Node::Strong(a) => fields!(a, marker),
+        Node::Text(a) => fields!(a, value),
+        Node::Code(a) => {
+            fields!(
+                a,
+                value,
+                lang,
+                meta,
+                code_kind,
+                fence_marker,
+                fence_size,
+                fence_indent,
+                attributes
+            );
+        }
+        Node::Math(a) => fields!(a, value, meta),
+        Node::MdxFlowExpression(a) => fields!(a, value, stops),
+        Node::Heading(a) => {
+            fields!(a, depth, heading_kind, attributes);
+        }
+        Node::Table(a) => {
+            fields!(a, align);
+            // The caption is not in `children`, so it is part of the table.
+            a.caption.as_ref().map(Vec::len).hash(state);
+            for node in a.caption.iter().flatten().flat_map(Node::descendants) {
+                node.children().map(Vec::len).hash(state);
+                shallow_hash(node, state);
+            }
+        }
+        Node::ListItem(a) => fields!(a, spread, checked),
+        Node::DefinitionList(a) => fields!(a, spread),
+        Node::Definition(a) => {
+            fields!(a, url, title, identifier, label);
+        }
+        Node::AbbreviationDefinition(a) => {
+            fields!(a, label, title);
+        }
+    }
 }
 
-/// List.
+/// Get all [`Definition`][]s in `tree`, by normalized identifier.
+///
+/// Identifiers are normalized the same way as when references are matched
+/// with definitions, so references can be looked up by their (normalized)
+/// `identifier` field.
+/// When several definitions have the same identifier, the first one wins, as
+/// in markdown.
+///
+/// ## Examples
 ///
-/// ```markdown
-/// > | * a
-///     ^^^
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "list")
-)]
-pub struct List {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
-    // Extra.
-    /// Ordered (`true`) or unordered (`false`).
-    pub ordered: bool,
-    /// Starting number of the list.
-    /// `None` when unordered.
-    pub start: Option<u32>,
-    /// One or more of its children are separated with a blank line from its
-    /// siblings (when `true`), or not (when `false`).
-    pub spread: bool,
+/// use markdown::{mdast::index_definitions, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("[A]: b\n[a]: c\n\n> [d]: e", &ParseOptions::default())?;
+/// let definitions = index_definitions(&tree);
+///
+/// assert_eq!(definitions.len(), 2);
+/// assert_eq!(definitions["a"].url, "b");
+/// assert_eq!(definitions["d"].url, "e");
+/// # Ok(())
+/// # }
+/// ```
+pub fn index_definitions(tree: &Node) -> BTreeMap<String, &Definition> {
+    let mut index = BTreeMap::new();
+
+    for node in tree.descendants() {
+        if let Node::Definition(definition) = node {
+            index
+                .entry(identifier_key(&definition.identifier))
+                .or_insert(&**definition);
+        }
+    }
+
+    index
 }
 
-/// List item.
+/// Get all [`FootnoteDefinition`][]s in `tree`, by normalized identifier.
+///
+/// Like [`index_definitions()`][], but for footnote definitions.
+///
+/// ## Examples
 ///
-/// ```markdown
-/// > | * a
-///     ^^^
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "listItem")
-)]
-pub struct ListItem {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
-    // Extra.
-    /// The item contains two or more children separated by a blank line
-    /// (when `true`), or not (when `false`).
-    pub spread: bool,
-    /// GFM: whether the item is done (when `true`), not done (when `false`),
-    /// or indeterminate or not applicable (`None`).
-    pub checked: Option<bool>,
+/// use markdown::{mdast::index_footnote_definitions, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("[^a]: b\n\n[^A]: c", &ParseOptions::gfm())?;
+/// let definitions = index_footnote_definitions(&tree);
+///
+/// assert_eq!(definitions.len(), 1);
+/// assert_eq!(definitions["a"].children[0].to_string(), "b");
+/// # Ok(())
+/// # }
+/// ```
+pub fn index_footnote_definitions(tree: &Node) -> BTreeMap<String, &FootnoteDefinition> {
+    let mut index = BTreeMap::new();
+
+    for node in tree.descendants() {
+        if let Node::FootnoteDefinition(definition) = node {
+            index
+                .entry(identifier_key(&definition.identifier))
+                .or_insert(&**definition);
+        }
+    }
+
+    index
 }
 
-/// Html (flow or phrasing).
+/// Get all [`AbbreviationDefinition`][]s in `tree`, by label.
+///
+/// Like [`index_definitions()`][], but for abbreviation definitions.
+/// Labels are not normalized, as abbreviations match case-sensitively.
+///
+/// ## Examples
 ///
-/// ```markdown
-/// > | <a>
-///     ^^^
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "html")
-)]
-pub struct Html {
-    // Text.
-    /// Content model.
-    pub value: String,
-    /// Positional info.
-    pub position: Option<Position>,
+/// use markdown::{mdast::index_abbreviation_definitions, to_mdast, Constructs, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast(
+///     "*[HTML]: Hypertext Markup Language\n*[HTML]: b",
+///     &ParseOptions {
+///         constructs: Constructs {
+///             abbreviation: true,
+///             ..Constructs::default()
+///         },
+///         ..ParseOptions::default()
+///     },
+/// )?;
+/// let definitions = index_abbreviation_definitions(&tree);
+///
+/// assert_eq!(definitions.len(), 1);
+/// assert_eq!(
+///     definitions["HTML"].title.as_deref(),
+///     Some("Hypertext Markup Language")
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn index_abbreviation_definitions(tree: &Node) -> BTreeMap<String, &AbbreviationDefinition> {
+    let mut index = BTreeMap::new();
+
+    for node in tree.descendants() {
+        if let Node::AbbreviationDefinition(definition) = node {
+            index
+                .entry(definition.label.clone())
+                .or_insert(&**definition);
+        }
+    }
+
+    index
 }
 
-/// Code (flow).
-///
-/// ```markdown
-/// > | ~~~
-///     ^^^
-/// > | a
-///     ^
-/// > | ~~~
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "code")
-)]
-pub struct Code {
-    // Text.
-    /// Content model.
-    pub value: String,
-    /// Positional info.
-    pub position: Option<Position>,
-    // Extra.
-    /// The language of computer code being marked up.
-    pub lang: Option<String>,
-    /// Custom info relating to the node.
-    pub meta: Option<String>,
+/// Turn an identifier into the form used to match references and
+/// definitions.
+fn identifier_key(identifier: &str) -> String {
+    crate::util::normalize_identifier::normalize_identifier(identifier).to_lowercase()
 }
 
-/// Math (flow).
-///
-/// ```markdown
-/// > | $$
-///     ^^
-/// > | a
-///     ^
-/// > | $$
-///     ^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "math")
-)]
-pub struct Math {
-    // Text.
-    /// Content model.
-    pub value: String,
-    /// Positional info.
-    pub position: Option<Position>,
-    // Extra.
-    /// Custom info relating to the node.
-    pub meta: Option<String>,
-}
+/// Offset basis of 64-bit FNV-1a.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
 
-/// Definition.
-///
-/// ```markdown
-/// > | [a]: b
-///     ^^^^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "definition")
-)]
-pub struct Definition {
-    // Void.
-    /// Positional info.
-    pub position: Option<Position>,
-    // Resource.
-    /// URL to the referenced resource.
-    pub url: String,
-    /// Advisory info for the resource, such as something that would be
-    /// appropriate for a tooltip.
-    pub title: Option<String>,
-    // Association.
-    /// Value that can match another node.
-    /// `identifier` is a source value: character escapes and character references
-    /// are *not* parsed.
-    /// Its value must be normalized.
-    pub identifier: String,
-    /// `label` is a string value: it works just like `title` on a link or a
-    /// `lang` on code: character escapes and character references are parsed.
-    ///
-    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
-    /// space, trim the optional initial and/or final space, and perform
-    /// case-folding.
-    pub label: Option<String>,
+/// Hash `bytes` with 64-bit FNV-1a, continuing from `hash`.
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
 }
 
-/// Text.
-///
-/// ```markdown
-/// > | a
-///     ^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "text")
-)]
-pub struct Text {
-    // Text.
-    /// Content model.
-    pub value: String,
-    /// Positional info.
-    pub position: Option<Position>,
-}
+/// 64-bit FNV-1a as a [`Hasher`][], to hash fields that implement
+/// [`Hash`][].
+struct Fnv(u64);
 
-/// Emphasis.
-///
-/// ```markdown
-/// > | *a*
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "emphasis")
-)]
-pub struct Emphasis {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = fnv(self.0, bytes);
+    }
 }
 
-/// Strong.
+/// Place of a node in a tree, as the indices of the children to go through,
+/// starting at the root.
 ///
-/// ```markdown
-/// > | **a**
-///     ^^^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "strong")
-)]
-pub struct Strong {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
+/// The empty path is the root itself.
+/// Paths stay valid across clones of a tree, and while siblings before and
+/// ancestors of the node do not change.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodePath {
+    /// Indices of children.
+    indices: Vec<usize>,
 }
 
-/// Code (phrasing).
-///
-/// ```markdown
-/// > | `a`
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "inlineCode")
-)]
-pub struct InlineCode {
-    // Text.
-    /// Content model.
-    pub value: String,
-    /// Positional info.
-    pub position: Option<Position>,
+impl NodePath {
+    /// Create the path to the root.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the indices of children.
+    #[must_use]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Get the number of steps from the root.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Check if this is the path to the root.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Go to the child at `index`.
+    pub fn push(&mut self, index: usize) {
+        self.indices.push(index);
+    }
+
+    /// Go to the parent, returning the index of the child that was left.
+    ///
+    /// Returns `None` at the root.
+    pub fn pop(&mut self) -> Option<usize> {
+        self.indices.pop()
+    }
+
+    /// Get the path to the parent.
+    ///
+    /// Returns `None` at the root.
+    #[must_use]
+    pub fn parent(&self) -> Option<NodePath> {
+        let mut parent = self.clone();
+        parent.pop()?;
+        Some(parent)
+    }
+
+    /// Get the path to the child at `index`.
+    #[must_use]
+    pub fn child(&self, index: usize) -> NodePath {
+        let mut child = self.clone();
+        child.push(index);
+        child
+    }
+
+    /// Get the node at this path in `tree`.
+    ///
+    /// Returns `None` if there is no node at this path.
+    /// See [`Node::get()`][].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodePath, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a *b*", &ParseOptions::default())?;
+    /// let path = NodePath::from(vec![0, 1]);
+    /// let copy = tree.clone();
+    ///
+    /// assert_eq!(path.resolve(&tree), path.resolve(&copy));
+    /// assert_eq!(path.resolve(&tree).unwrap().to_string(), "b");
+    /// assert_eq!(path.child(1).resolve(&tree), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn resolve<'a>(&self, tree: &'a Node) -> Option<&'a Node> {
+        tree.get(self)
+    }
+
+    /// Get the node at this path in `tree`, mutably.
+    ///
+    /// Returns `None` if there is no node at this path.
+    /// See [`Node::get_mut()`][].
+    pub fn resolve_mut<'a>(&self, tree: &'a mut Node) -> Option<&'a mut Node> {
+        tree.get_mut(self)
+    }
 }
 
-/// Math (phrasing).
-///
-/// ```markdown
-/// > | $a$
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "inlineMath")
-)]
-pub struct InlineMath {
-    // Text.
-    /// Content model.
-    pub value: String,
-    /// Positional info.
-    pub position: Option<Position>,
+impl From<Vec<usize>> for NodePath {
+    fn from(indices: Vec<usize>) -> Self {
+        NodePath { indices }
+    }
 }
 
-/// Break.
+/// Cursor that walks a tree, tracking the ancestors of the current node and
+/// its index in its parent.
+///
+/// ## Examples
 ///
-/// ```markdown
-/// > | a\
-///      ^
-///   | b
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "break")
-)]
-pub struct Break {
-    // Void.
-    /// Positional info.
-    pub position: Option<Position>,
-}
-
-/// Link.
+/// use markdown::{mdast::{NodeKind, TreeCursor}, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
 ///
-/// ```markdown
-/// > | [a](b)
-///     ^^^^^^
+/// let tree = to_mdast("# a *b*\n\nc", &ParseOptions::default())?;
+/// let mut cursor = TreeCursor::new(&tree);
+/// let mut texts = vec![];
+///
+/// while cursor.goto_next() {
+///     if cursor.node().kind() == NodeKind::Text {
+///         let in_heading = cursor
+///             .ancestors()
+///             .any(|node| node.kind() == NodeKind::Heading);
+///         texts.push((cursor.node().to_string(), in_heading));
+///     }
+/// }
+///
+/// assert_eq!(
+///     texts,
+///     vec![
+///         ("a ".into(), true),
+///         ("b".into(), true),
+///         ("c".into(), false)
+///     ]
+/// );
+/// # Ok(())
+/// # }
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "link")
-)]
-pub struct Link {
-    // Parent.
-    /// Content model.
-    pub children: Vec<Node>,
-    /// Positional info.
-    pub position: Option<Position>,
-    // Resource.
-    /// URL to the referenced resource.
-    pub url: String,
-    /// Advisory info for the resource, such as something that would be
-    /// appropriate for a tooltip.
-    pub title: Option<String>,
+#[derive(Clone, Debug)]
+pub struct TreeCursor<'a> {
+    /// Current node.
+    node: &'a Node,
+    /// Ancestors of the current node, the parent last, each with the index
+    /// of the child in it that was entered.
+    ancestors: Vec<(&'a Node, usize)>,
 }
 
-/// Image.
-///
-/// ```markdown
-/// > | ![a](b)
-///     ^^^^^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
+impl<'a> TreeCursor<'a> {
+    /// Create a cursor at `node`, the root of the tree to walk.
+    #[must_use]
+    pub fn new(node: &'a Node) -> Self {
+        TreeCursor {
+            node,
+            ancestors: vec![],
+        }
+    }
+
+    /// Get the current node.
+    #[must_use]
+    pub fn node(&self) -> &'a Node {
+        self.node
+    }
+
+    /// Get the index of the current node in its parent.
+    ///
+    /// Returns `None` at the root.
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        self.ancestors.last().map(|&(_, index)| index)
+    }
+
+    /// Get the number of ancestors of the current node.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.ancestors.len()
+    }
+
+    /// Get the path to the current node, from the root.
+    #[must_use]
+    pub fn path(&self) -> NodePath {
+        NodePath::from(
+            self.ancestors
+                .iter()
+                .map(|&(_, index)| index)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Get an iterator over the ancestors of the current node, the parent
+    /// first and the root last.
+    pub fn ancestors(&self) -> impl Iterator<Item = &'a Node> + '_ {
+        self.ancestors.iter().rev().map(|&(node, _)| node)
+    }
+
+    /// Get the parent of the current node.
+    ///
+    /// Returns `None` at the root.
+    #[must_use]
+    pub fn parent(&self) -> Option<&'a Node> {
+        self.ancestors.last().map(|&(node, _)| node)
+    }
+
+    /// Get the sibling before the current node.
+    ///
+    /// Returns `None` at the root and at first children.
+    #[must_use]
+    pub fn previous_sibling(&self) -> Option<&'a Node> {
+        let &(parent, index) = self.ancestors.last()?;
+        parent.children()?.get(index.checked_sub(1)?)
+    }
+
+    /// Get the sibling after the current node.
+    ///
+    /// Returns `None` at the root and at last children.
+    #[must_use]
+    pub fn next_sibling(&self) -> Option<&'a Node> {
+        let &(parent, index) = self.ancestors.last()?;
+        parent.children()?.get(index + 1)
+    }
+
+    /// Move to the parent of the current node.
+    ///
+    /// Returns whether the cursor moved, which it does not at the root.
+    pub fn goto_parent(&mut self) -> bool {
+        if let Some((parent, _)) = self.ancestors.pop() {
+            self.node = parent;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move to the first child of the current node.
+    ///
+    /// Returns whether the cursor moved, which it does not when there are no
+    /// children.
+    pub fn goto_first_child(&mut self) -> bool {
+        if let Some(child) = self.node.children().and_then(|children| children.first()) {
+            self.ancestors.push((self.node, 0));
+            self.node = child;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move to the sibling before the current node.
+    ///
+    /// Returns whether the cursor moved, which it does not when there is no
+    /// sibling before.
+    pub fn goto_previous_sibling(&mut self) -> bool {
+        self.goto_sibling(self.index().and_then(|index| index.checked_sub(1)))
+    }
+
+    /// Move to the sibling after the current node.
+    ///
+    /// Returns whether the cursor moved, which it does not when there is no
+    /// sibling after.
+    pub fn goto_next_sibling(&mut self) -> bool {
+        self.goto_sibling(self.index().map(|index| index + 1))
+    }
+
+    /// Move to the next node in document order: the first child, or else the
+    /// next sibling of the current node or of its nearest ancestor that has
+    /// one.
+    ///
+    /// Returns whether the cursor moved, which it does not when there is no
+    /// next node (in which case the cursor stays where it was).
+    pub fn goto_next(&mut self) -> bool {
+        if self.goto_first_child() {
+            return true;
+        }
+
+        let mut depth = self.ancestors.len();
+        while depth > 0 {
+            let (parent, index) = self.ancestors[depth - 1];
+            if let Some(sibling) = parent.children().and_then(|x| x.get(index + 1)) {
+                self.ancestors.truncate(depth);
+                self.ancestors[depth - 1].1 = index + 1;
+                self.node = sibling;
+                return true;
+            }
+            depth -= 1;
+        }
+
+        false
+    }
+
+    /// Move to the sibling at `index`, if it exists.
+    fn goto_sibling(&mut self, index: Option<usize>) -> bool {
+        if let (Some(index), Some((parent, current))) = (index, self.ancestors.last_mut()) {
+            if let Some(sibling) = parent.children().and_then(|x| x.get(index)) {
+                *current = index;
+                self.node = sibling;
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Zipper that edits a tree it owns, at a current node.
+///
+/// Unlike [`TreeCursor`][], which borrows a tree, this takes the tree apart
+/// while walking it: the siblings of the current node are kept next to it,
+/// so that moving to a sibling and inserting or removing nodes around the
+/// current node take constant time.
+/// Get the tree back with [`into_node()`][TreeEdit::into_node].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::{Node, TreeEdit}, to_markdown, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# a\n\nb\n\n***\n\nc", &ParseOptions::default())?;
+/// let mut edit = TreeEdit::new(tree);
+///
+/// edit.goto_first_child();
+/// edit.insert_after(Node::paragraph(vec![Node::text("d")]));
+///
+/// while edit.goto_next_sibling() {
+///     if let Node::ThematicBreak(_) = edit.node() {
+///         edit.delete();
+///     }
+/// }
+///
+/// assert_eq!(to_markdown(edit.into_node()), "# a\n\nd\n\nb\n\nc\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TreeEdit {
+    /// Current node.
+    node: Node,
+    /// Ancestors of the current node, the parent last.
+    frames: Vec<TreeEditFrame>,
+}
+
+/// Parent of the current node in a [`TreeEdit`][], with its children taken
+/// apart around the current node.
+#[derive(Clone, Debug)]
+struct TreeEditFrame {
+    /// Parent, without children.
+    parent: Node,
+    /// Siblings before the current node, in order.
+    before: Vec<Node>,
+    /// Siblings after the current node, in reverse order (the next sibling
+    /// last).
+    after: Vec<Node>,
+}
+
+impl TreeEdit {
+    /// Create a zipper at `node`, the root of the tree to edit.
+    #[must_use]
+    pub fn new(node: Node) -> Self {
+        TreeEdit {
+            node,
+            frames: vec![],
+        }
+    }
+
+    /// Get the current node.
+    #[must_use]
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Get the current node, mutably.
+    pub fn node_mut(&mut self) -> &mut Node {
+        &mut self.node
+    }
+
+    /// Get the index of the current node in its parent.
+    ///
+    /// Returns `None` at the root.
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        self.frames.last().map(|frame| frame.before.len())
+    }
+
+    /// Get the number of ancestors of the current node.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Get the path to the current node, from the root.
+    #[must_use]
+    pub fn path(&self) -> NodePath {
+        NodePath::from(
+            self.frames
+                .iter()
+                .map(|frame| frame.before.len())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Get the sibling before the current node.
+    ///
+    /// Returns `None` at the root and at first children.
+    #[must_use]
+    pub fn previous_sibling(&self) -> Option<&Node> {
+        self.frames.last()?.before.last()
+    }
+
+    /// Get the sibling after the current node.
+    ///
+    /// Returns `None` at the root and at last children.
+    #[must_use]
+    pub fn next_sibling(&self) -> Option<&Node> {
+        self.frames.last()?.after.last()
+    }
+
+    /// Move to the parent of the current node, putting its children back
+    /// together.
+    ///
+    /// Returns whether the zipper moved, which it does not at the root.
+    pub fn goto_parent(&mut self) -> bool {
+        if let Some(frame) = self.frames.pop() {
+            let TreeEditFrame {
+                mut parent,
+                mut before,
+                after,
+            } = frame;
+            let node = core::mem::replace(&mut self.node, Node::root(vec![]));
+            before.push(node);
+            before.extend(after.into_iter().rev());
+            if let Some(children) = parent.children_mut() {
+                *children = before;
+            }
+            self.node = parent;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move to the first child of the current node.
+    ///
+    /// Returns whether the zipper moved, which it does not when there are no
+    /// children.
+    pub fn goto_first_child(&mut self) -> bool {
+        let mut after = match self.node.children_mut() {
+            Some(children) if !children.is_empty() => core::mem::take(children),
+            _ => return false,
+        };
+        after.reverse();
+        let child = after.pop().unwrap();
+        let parent = core::mem::replace(&mut self.node, child);
+        self.frames.push(TreeEditFrame {
+            parent,
+            before: vec![],
+            after,
+        });
+        true
+    }
+
+    /// Move to the sibling before the current node.
+    ///
+    /// Returns whether the zipper moved, which it does not when there is no
+    /// sibling before.
+    pub fn goto_previous_sibling(&mut self) -> bool {
+        if let Some(frame) = self.frames.last_mut() {
+            if let Some(sibling) = frame.before.pop() {
+                frame
+                    .after
+                    .push(core::mem::replace(&mut self.node, sibling));
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Move to the sibling after the current node.
+    ///
+    /// Returns whether the zipper moved, which it does not when there is no
+    /// sibling after.
+    pub fn goto_next_sibling(&mut self) -> bool {
+        if let Some(frame) = self.frames.last_mut() {
+            if let Some(sibling) = frame.after.pop() {
+                frame
+                    .before
+                    .push(core::mem::replace(&mut self.node, sibling));
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Insert `node` as the sibling before the current node.
+    ///
+    /// Returns whether it was inserted, which it is not at the root.
+    pub fn insert_before(&mut self, node: Node) -> bool {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.before.push(node);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Insert `node` as the sibling after the current node.
+    ///
+    /// Returns whether it was inserted, which it is not at the root.
+    pub fn insert_after(&mut self, node: Node) -> bool {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.after.push(node);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replace the current node with `node`, returning the current node.
+    pub fn replace(&mut self, node: Node) -> Node {
+        core::mem::replace(&mut self.node, node)
+    }
+
+    /// Remove the current node, returning it.
+    ///
+    /// The zipper moves to the next sibling, or else the previous sibling, or
+    /// else the parent.
+    /// Returns `None` at the root, which cannot be removed.
+    pub fn delete(&mut self) -> Option<Node> {
+        let frame = self.frames.last_mut()?;
+        let replacement = if let Some(sibling) = frame.after.pop() {
+            sibling
+        } else if let Some(sibling) = frame.before.pop() {
+            sibling
+        } else {
+            let mut frame = self.frames.pop().unwrap();
+            if let Some(children) = frame.parent.children_mut() {
+                children.clear();
+            }
+            return Some(core::mem::replace(&mut self.node, frame.parent));
+        };
+
+        Some(core::mem::replace(&mut self.node, replacement))
+    }
+
+    /// Get the edited tree back, moving to the root.
+    #[must_use]
+    pub fn into_node(mut self) -> Node {
+        while self.goto_parent() {}
+        self.node
+    }
+}
+
+/// Iterator over a node and its descendants, in document order.
+///
+/// Made by [`Node::descendants()`][].
+#[derive(Clone, Debug)]
+pub struct Descendants<'a> {
+    /// Nodes left to yield, the next one last.
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if let Some(children) = node.children() {
+            self.stack.extend(children.iter().rev());
+        }
+        Some(node)
+    }
+}
+
+/// Iterator over a node and its descendants that cannot have children, in
+/// document order, which can change them.
+///
+/// Made by [`Node::descendants_mut()`][].
+#[derive(Debug)]
+pub struct DescendantsMut<'a> {
+    /// Nodes left to go through, the next one last.
+    stack: Vec<&'a mut Node>,
+}
+
+impl<'a> Iterator for DescendantsMut<'a> {
+    type Item = &'a mut Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+            if node.children().is_none() {
+                return Some(node);
+            }
+            if let Some(children) = node.children_mut() {
+                self.stack.extend(children.iter_mut().rev());
+            }
+        }
+    }
+}
+
+/// MDX: attribute content.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "image")
+    serde(tag = "type")
 )]
-pub struct Image {
-    // Void.
-    /// Positional info.
-    pub position: Option<Position>,
-    // Alternative.
-    /// Equivalent content for environments that cannot represent the node as
-    /// intended.
-    pub alt: String,
-    // Resource.
-    /// URL to the referenced resource.
-    pub url: String,
-    /// Advisory info for the resource, such as something that would be
-    /// appropriate for a tooltip.
-    pub title: Option<String>,
+pub enum AttributeContent {
+    /// JSX expression.
+    ///
+    /// ```markdown
+    /// > | <a {...b} />
+    ///        ^^^^^^
+    /// ```
+    #[cfg_attr(feature = "serde", serde(rename = "mdxJsxExpressionAttribute"))]
+    Expression {
+        value: String,
+        #[cfg_attr(feature = "serde", serde(default))]
+        stops: Vec<Stop>,
+    },
+    /// JSX property.
+    ///
+    /// ```markdown
+    /// > | <a b />
+    ///        ^
+    /// ```
+    #[cfg_attr(feature = "serde", serde(rename = "mdxJsxAttribute"))]
+    Property(MdxJsxAttribute),
+}
+//
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxJsxAttributeValueExpression")
+)]
+pub struct AttributeValueExpression {
+    pub value: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stops: Vec<Stop>,
 }
 
-/// Link reference.
-///
-/// ```markdown
-/// > | [a]
-///     ^^^
-/// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// MDX: attribute value.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "linkReference")
+    serde(untagged)
 )]
-pub struct LinkReference {
+pub enum AttributeValue {
+    /// Expression value.
+    ///
+    /// ```markdown
+    /// > | <a b={c} />
+    ///          ^^^
+    /// ```
+    Expression(AttributeValueExpression),
+    /// Static value.
+    ///
+    /// ```markdown
+    /// > | <a b="c" />
+    ///          ^^^
+    /// ```
+    Literal(String),
+}
+
+/// Document.
+///
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Root {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
-    // Reference.
-    /// Explicitness of a reference.
-    #[cfg_attr(feature = "serde", serde(rename = "referenceType"))]
-    pub reference_kind: ReferenceKind,
-    // Association.
-    /// Value that can match another node.
-    /// `identifier` is a source value: character escapes and character references
-    /// are *not* parsed.
-    /// Its value must be normalized.
-    pub identifier: String,
-    /// `label` is a string value: it works just like `title` on a link or a
-    /// `lang` on code: character escapes and character references are parsed.
-    ///
-    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
-    /// space, trim the optional initial and/or final space, and perform
-    /// case-folding.
-    pub label: Option<String>,
 }
 
-/// Image reference.
+/// Paragraph.
 ///
 /// ```markdown
-/// > | ![a]
-///     ^^^^
+/// > | a
+///     ^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "imageReference")
-)]
-pub struct ImageReference {
-    // Void.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paragraph {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
-    // Alternative.
-    /// Equivalent content for environments that cannot represent the node as
-    /// intended.
-    pub alt: String,
-    // Reference.
-    /// Explicitness of a reference.
-    #[cfg_attr(feature = "serde", serde(rename = "referenceType"))]
-    pub reference_kind: ReferenceKind,
-    // Association.
-    /// Value that can match another node.
-    /// `identifier` is a source value: character escapes and character references
-    /// are *not* parsed.
-    /// Its value must be normalized.
-    pub identifier: String,
-    /// `label` is a string value: it works just like `title` on a link or a
-    /// `lang` on code: character escapes and character references are parsed.
-    ///
-    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
-    /// space, trim the optional initial and/or final space, and perform
-    /// case-folding.
-    pub label: Option<String>,
 }
 
-/// GFM: footnote definition.
+/// Heading.
 ///
 /// ```markdown
-/// > | [^a]: b
-///     ^^^^^^^
+/// > | # a
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "footnoteDefinition")
-)]
-pub struct FootnoteDefinition {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heading {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
-    // Association.
-    /// Value that can match another node.
-    /// `identifier` is a source value: character escapes and character references
-    /// are *not* parsed.
-    /// Its value must be normalized.
-    pub identifier: String,
-    /// `label` is a string value: it works just like `title` on a link or a
-    /// `lang` on code: character escapes and character references are parsed.
-    ///
-    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
-    /// space, trim the optional initial and/or final space, and perform
-    /// case-folding.
-    pub label: Option<String>,
+    // Extra.
+    /// Rank (between `1` and `6`, both including).
+    pub depth: u8,
+    /// Whether `#`s (ATX) or an underline (setext) was used, if known.
+    #[cfg_attr(feature = "serde", serde(rename = "headingKind"))]
+    pub heading_kind: Option<HeadingKind>,
+    /// Attributes (`{#id .class}`), if any.
+    pub attributes: Option<Box<Attributes>>,
 }
 
-/// GFM: footnote reference.
+/// Thematic break.
 ///
 /// ```markdown
-/// > | [^a]
-///     ^^^^
+/// > | ***
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "footnoteReference")
-)]
-pub struct FootnoteReference {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThematicBreak {
     // Void.
     /// Positional info.
     pub position: Option<Position>,
-    // Association.
-    /// Value that can match another node.
-    /// `identifier` is a source value: character escapes and character references
-    /// are *not* parsed.
-    /// Its value must be normalized.
-    pub identifier: String,
-    /// `label` is a string value: it works just like `title` on a link or a
-    /// `lang` on code: character escapes and character references are parsed.
-    ///
-    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
-    /// space, trim the optional initial and/or final space, and perform
-    /// case-folding.
-    pub label: Option<String>,
 }
 
-/// GFM: table.
+/// Block quote.
 ///
 /// ```markdown
-/// > | | a |
-///     ^^^^^
-/// > | | - |
-///     ^^^^^
+/// > | > a
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "table")
-)]
-pub struct Table {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockQuote {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
     // Extra.
-    /// Represents how cells in columns are aligned.
-    pub align: Vec<AlignKind>,
+    /// GFM: kind of alert, if the block quote starts with a marker such as
+    /// `[!NOTE]`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub alert: Option<AlertKind>,
 }
 
-/// GFM: table row.
+/// List.
 ///
 /// ```markdown
-/// > | | a |
-///     ^^^^^
+/// > | * a
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "tableRow")
-)]
-pub struct TableRow {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct List {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
+    // Extra.
+    /// Ordered (`true`) or unordered (`false`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ordered: bool,
+    /// Starting number of the list.
+    /// `None` when unordered.
+    pub start: Option<u32>,
+    /// One or more of its children are separated with a blank line from its
+    /// siblings (when `true`), or not (when `false`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spread: bool,
+    /// Marker of the items, if known: `*`, `+`, or `-` when unordered, `.` or
+    /// `)` when ordered.
+    pub marker: Option<char>,
 }
 
-/// GFM: table cell.
+/// List item.
 ///
 /// ```markdown
-/// > | | a |
-///     ^^^^^
+/// > | * a
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "tableCell")
-)]
-pub struct TableCell {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListItem {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
+    // Extra.
+    /// The item contains two or more children separated by a blank line
+    /// (when `true`), or not (when `false`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spread: bool,
+    /// GFM: whether the item is done (when `true`), not done (when `false`),
+    /// or indeterminate or not applicable (`None`).
+    pub checked: Option<bool>,
 }
 
-/// GFM: delete.
+/// Definition list.
+///
+/// Only used when [`definition_list`][crate::Constructs::definition_list] is
+/// on.
 ///
 /// ```markdown
-/// > | ~~a~~
-///     ^^^^^
+/// > | a
+///     ^
+/// > | : b
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "delete")
-)]
-pub struct Delete {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefinitionList {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
+    // Extra.
+    /// One or more of its children, or the children of its descriptions, are
+    /// separated with a blank line from their siblings (when `true`), or not
+    /// (when `false`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spread: bool,
 }
 
-/// Frontmatter: yaml.
+/// Definition term.
 ///
 /// ```markdown
-/// > | ---
-///     ^^^
-/// > | a: b
-///     ^^^^
-/// > | ---
-///     ^^^
+/// > | a
+///     ^
+///   | : b
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "yaml")
-)]
-pub struct Yaml {
-    // Void.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefinitionTerm {
+    // Parent.
     /// Content model.
-    pub value: String,
+    pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
 }
 
-/// Frontmatter: toml.
+/// Definition description.
 ///
 /// ```markdown
-/// > | +++
-///     ^^^
-/// > | a: b
-///     ^^^^
-/// > | +++
+///   | a
+/// > | : b
 ///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "toml")
-)]
-pub struct Toml {
-    // Void.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefinitionDescription {
+    // Parent.
     /// Content model.
-    pub value: String,
+    pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
 }
 
-/// MDX: ESM.
+/// Html (flow or phrasing).
 ///
 /// ```markdown
-/// > | import a from 'b'
-///     ^^^^^^^^^^^^^^^^^
+/// > | <a>
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxjsEsm")
-)]
-pub struct MdxjsEsm {
-    // Literal.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Html {
+    // Text.
     /// Content model.
     pub value: String,
     /// Positional info.
     pub position: Option<Position>,
-
-    // Custom data on where each slice of `value` came from.
-    pub stops: Vec<Stop>,
 }
 
-/// MDX: expression (flow).
+/// Comment (flow or phrasing).
+///
+/// Only made for HTML comments when
+/// [`html_comment_nodes`][crate::ParseOptions::html_comment_nodes] is on.
 ///
 /// ```markdown
-/// > | {a}
-///     ^^^
+/// > | <!-- a -->
+///     ^^^^^^^^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxFlowExpression")
-)]
-pub struct MdxFlowExpression {
-    // Literal.
-    /// Content model.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comment {
+    // Text.
+    /// Content model: the value between `<!--` and `-->`.
     pub value: String,
     /// Positional info.
     pub position: Option<Position>,
-
-    // Custom data on where each slice of `value` came from.
-    pub stops: Vec<Stop>,
 }
 
-/// MDX: expression (text).
+/// Code (flow).
 ///
 /// ```markdown
-/// > | a {b}
-///       ^^^
+/// > | ~~~
+///     ^^^
+/// > | a
+///     ^
+/// > | ~~~
+///     ^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxTextExpression")
-)]
-pub struct MdxTextExpression {
-    // Literal.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Code {
+    // Text.
     /// Content model.
     pub value: String,
     /// Positional info.
     pub position: Option<Position>,
+    // Extra.
+    /// The language of computer code being marked up.
+    pub lang: Option<String>,
+    /// Custom info relating to the node.
+    pub meta: Option<String>,
+    /// Whether fences or indent were used, if known.
+    #[cfg_attr(feature = "serde", serde(rename = "codeKind"))]
+    pub code_kind: Option<CodeKind>,
+    /// Marker of the fences, if known and fenced: `` ` `` or `~`.
+    #[cfg_attr(feature = "serde", serde(rename = "fenceMarker"))]
+    pub fence_marker: Option<char>,
+    /// Size of the opening fence, if known and fenced.
+    #[cfg_attr(feature = "serde", serde(rename = "fenceSize"))]
+    pub fence_size: Option<usize>,
+    /// Size of the indent before the opening fence, if known and fenced.
+    ///
+    /// The same indent is removed from the lines in the code.
+    #[cfg_attr(feature = "serde", serde(rename = "fenceIndent"))]
+    pub fence_indent: Option<usize>,
+    /// Attributes (`{#id .class}`), if any.
+    pub attributes: Option<Box<Attributes>>,
+}
 
-    // Custom data on where each slice of `value` came from.
-    pub stops: Vec<Stop>,
+impl Code {
+    /// Get structured info from the info string: the words after commas in
+    /// the language (such as `no_run` in `rust,no_run`), and the attributes
+    /// and highlighted lines in the meta (such as `title="x" {3-5}`).
+    ///
+    /// See [`CodeMeta::from_info`][crate::CodeMeta::from_info] for more info.
+    #[must_use]
+    pub fn info(&self) -> CodeMeta {
+        CodeMeta::from_info(self.lang.as_deref(), self.meta.as_deref())
+    }
 }
 
-/// MDX: JSX element (container).
+/// Math (flow).
 ///
 /// ```markdown
-/// > | <a />
-///     ^^^^^
+/// > | $$
+///     ^^
+/// > | a
+///     ^
+/// > | $$
+///     ^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxJsxFlowElement")
-)]
-pub struct MdxJsxFlowElement {
-    // Parent.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Math {
+    // Text.
     /// Content model.
-    pub children: Vec<Node>,
+    pub value: String,
     /// Positional info.
     pub position: Option<Position>,
-    // JSX element.
-    /// Name.
+    // Extra.
+    /// Custom info relating to the node.
+    pub meta: Option<String>,
+}
+
+/// Definition.
+///
+/// ```markdown
+/// > | [a]: b
+///     ^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Definition {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<String>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
     ///
-    /// Fragments have no name.
-    pub name: Option<String>,
-    /// Attributes.
-    pub attributes: Vec<AttributeContent>,
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
 }
 
-/// MDX: JSX element (text).
+/// Abbreviation definition.
+///
+/// Only used when [`abbreviation`][crate::Constructs::abbreviation] is on.
 ///
 /// ```markdown
-/// > | <a />.
+/// > | *[HTML]: Hypertext Markup Language
+///     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbbreviationDefinition {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Text that is abbreviated, such as `HTML`, without whitespace at its
+    /// start and end.
+    pub label: String,
+    /// What the abbreviation stands for, such as `Hypertext Markup Language`.
+    pub title: Option<String>,
+}
+
+/// Text.
+///
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Text {
+    // Text.
+    /// Content model.
+    pub value: TextValue,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Character reference.
+///
+/// Only used when
+/// [`preserve_character_references`][crate::ParseOptions::preserve_character_references]
+/// is on, otherwise character references are decoded into [`Text`][].
+///
+/// ```markdown
+/// > | &amp;
 ///     ^^^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxJsxTextElement")
-)]
-pub struct MdxJsxTextElement {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterReference {
+    // Text.
+    /// Content model: the decoded value.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// How the reference was written, such as `&amp;` or `&#x26;`.
+    pub raw: String,
+}
+
+/// Emoji.
+///
+/// Only used when [`emoji`][crate::Constructs::emoji] is on.
+///
+/// ```markdown
+/// > | :tada:
+///     ^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Emoji {
+    // Text.
+    /// Content model: the emoji, if
+    /// [`emoji_lookup`][crate::ParseOptions::emoji_lookup] is passed,
+    /// otherwise the shortcode with colons.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Shortcode, without colons, such as `tada`.
+    pub shortcode: String,
+}
+
+/// Abbreviation.
+///
+/// Only used when [`abbreviation`][crate::Constructs::abbreviation] is on.
+///
+/// ```markdown
+/// > | a HTML b
+///       ^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Abbreviation {
+    // Text.
+    /// Content model: the text that is abbreviated, such as `HTML`.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Title of the matching [`AbbreviationDefinition`][], such as
+    /// `Hypertext Markup Language`.
+    pub title: Option<String>,
+}
+
+/// Block identifier.
+///
+/// Only used when
+/// [`obsidian_block_id`][crate::Constructs::obsidian_block_id] is on.
+/// It is the last child of the paragraph or heading it identifies.
+/// On a line of its own after a list, block quote, or table, it is the only
+/// child of a paragraph, and identifies that block.
+///
+/// ```markdown
+/// > | a ^b
+///       ^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockId {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Identifier, without `^`.
+    pub value: String,
+}
+
+/// Emphasis.
+///
+/// ```markdown
+/// > | *a*
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Emphasis {
     // Parent.
     /// Content model.
     pub children: Vec<Node>,
     /// Positional info.
     pub position: Option<Position>,
-    // JSX element.
-    /// Name.
-    ///
-    /// Fragments have no name.
-    pub name: Option<String>,
-    /// Attributes.
-    pub attributes: Vec<AttributeContent>,
+    // Extra.
+    /// Marker, if known: `*` or `_`.
+    pub marker: Option<char>,
 }
 
-/// MDX: JSX attribute.
+/// Strong.
 ///
 /// ```markdown
-/// > | <a b />
-///        ^
+/// > | **a**
+///     ^^^^^
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(tag = "type", rename = "mdxJsxAttribute")
-)]
-pub struct MdxJsxAttribute {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Strong {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Marker, if known: `*` or `_`.
+    pub marker: Option<char>,
+}
+
+/// Code (phrasing).
+///
+/// ```markdown
+/// > | `a`
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlineCode {
+    // Text.
+    /// Content model.
+    pub value: TextValue,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Attributes (`{#id .class}`), if any.
+    pub attributes: Option<Box<Attributes>>,
+}
+
+/// Math (phrasing).
+///
+/// ```markdown
+/// > | $a$
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlineMath {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Break.
+///
+/// ```markdown
+/// > | a\
+///      ^
+///   | b
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Break {
     // Void.
     /// Positional info.
-    // pub position: Option<Position>,
-    /// Key.
-    pub name: String,
-    /// Value.
-    pub value: Option<AttributeValue>,
+    pub position: Option<Position>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::unist::Position;
-    use alloc::{format, string::ToString, vec};
+/// Link.
+///
+/// ```markdown
+/// > | [a](b)
+///     ^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Link {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<String>,
+    // Extra.
+    /// Whether a resource, an autolink, or a GFM autolink literal was used,
+    /// if known.
+    #[cfg_attr(feature = "serde", serde(rename = "linkKind"))]
+    pub link_kind: Option<LinkKind>,
+}
 
-    // Literals.
+/// Image.
+///
+/// ```markdown
+/// > | ![a](b)
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Image {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Alternative.
+    /// Equivalent content for environments that cannot represent the node as
+    /// intended.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "null_as_default")
+    )]
+    pub alt: String,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<String>,
+}
+
+/// Link reference.
+///
+/// ```markdown
+/// > | [a]
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkReference {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Reference.
+    /// Explicitness of a reference.
+    #[cfg_attr(feature = "serde", serde(rename = "referenceType"))]
+    pub reference_kind: ReferenceKind,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// Wiki link.
+///
+/// Only used when [`wiki_link`][crate::Constructs::wiki_link] is on.
+/// Character escapes and character references are *not* parsed in any of
+/// its fields.
+///
+/// ```markdown
+/// > | [[a#b|c]]
+///     ^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WikiLink {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Name of the linked note, which is empty when linking to something in
+    /// the current note (as in `[[#a]]`).
+    pub target: String,
+    /// Heading or block in the linked note, without `#`.
+    pub anchor: Option<String>,
+    /// Text to show instead of the target, without `|`.
+    pub label: Option<String>,
+}
+
+/// Citation.
+///
+/// Only used when [`cite`][crate::Constructs::cite] is on.
+/// Character escapes and character references are *not* parsed in any of
+/// its fields.
+///
+/// ```markdown
+/// > | [see @a, p. 1; @b]
+///     ^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cite {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Citations, in order (such as `see @a, p. 1` and `@b`).
+    pub citations: Vec<Citation>,
+}
+
+/// Image reference.
+///
+/// ```markdown
+/// > | ![a]
+///     ^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageReference {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Alternative.
+    /// Equivalent content for environments that cannot represent the node as
+    /// intended.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "null_as_default")
+    )]
+    pub alt: String,
+    // Reference.
+    /// Explicitness of a reference.
+    #[cfg_attr(feature = "serde", serde(rename = "referenceType"))]
+    pub reference_kind: ReferenceKind,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// GFM: footnote definition.
+///
+/// ```markdown
+/// > | [^a]: b
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteDefinition {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// GFM: footnote reference.
+///
+/// ```markdown
+/// > | [^a]
+///     ^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteReference {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// GFM: table.
+///
+/// ```markdown
+/// > | | a |
+///     ^^^^^
+/// > | | - |
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Represents how cells in columns are aligned.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "null_as_default")
+    )]
+    pub align: Vec<AlignKind>,
+    /// Caption (`Table: a`), if any (not part of GFM).
+    ///
+    /// The caption is phrasing content.
+    pub caption: Option<Vec<Node>>,
+}
+
+/// GFM: table row.
+///
+/// ```markdown
+/// > | | a |
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableRow {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// GFM: table cell.
+///
+/// ```markdown
+/// > | | a |
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCell {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// GFM: delete.
+///
+/// ```markdown
+/// > | ~~a~~
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delete {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Highlight.
+///
+/// ```markdown
+/// > | ==a==
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Highlight {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Insert.
+///
+/// ```markdown
+/// > | ++a++
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Insert {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// `CriticMarkup`: addition.
+///
+/// ```markdown
+/// > | {++a++}
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticAddition {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// `CriticMarkup`: deletion.
+///
+/// ```markdown
+/// > | {--a--}
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticDeletion {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// `CriticMarkup`: substitution.
+///
+/// ```markdown
+/// > | {~~a~>b~~}
+///     ^^^^^^^^^^
+/// ```
+///
+/// Its children are a [`CriticDeletion`][] with the old content, followed by
+/// a [`CriticAddition`][] with the new content.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticSubstitution {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// `CriticMarkup`: highlight.
+///
+/// ```markdown
+/// > | {==a==}
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticHighlight {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// `CriticMarkup`: comment.
+///
+/// ```markdown
+/// > | {>>a<<}
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticComment {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Frontmatter: yaml.
+///
+/// ```markdown
+/// > | ---
+///     ^^^
+/// > | a: b
+///     ^^^^
+/// > | ---
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Yaml {
+    // Void.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Frontmatter: toml.
+///
+/// ```markdown
+/// > | +++
+///     ^^^
+/// > | a: b
+///     ^^^^
+/// > | +++
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Toml {
+    // Void.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// MDX: ESM.
+///
+/// ```markdown
+/// > | import a from 'b'
+///     ^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MdxjsEsm {
+    // Literal.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+
+    // Custom data on where each slice of `value` came from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: expression (flow).
+///
+/// ```markdown
+/// > | {a}
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MdxFlowExpression {
+    // Literal.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+
+    // Custom data on where each slice of `value` came from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: expression (text).
+///
+/// ```markdown
+/// > | a {b}
+///       ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MdxTextExpression {
+    // Literal.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+
+    // Custom data on where each slice of `value` came from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: JSX element (container).
+///
+/// ```markdown
+/// > | <a />
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MdxJsxFlowElement {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // JSX element.
+    /// Name.
+    ///
+    /// Fragments have no name.
+    pub name: Option<String>,
+    /// Attributes.
+    pub attributes: Vec<AttributeContent>,
+}
+
+/// MDX: JSX element (text).
+///
+/// ```markdown
+/// > | <a />.
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MdxJsxTextElement {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // JSX element.
+    /// Name.
+    ///
+    /// Fragments have no name.
+    pub name: Option<String>,
+    /// Attributes.
+    pub attributes: Vec<AttributeContent>,
+}
+
+/// MDX: JSX attribute.
+///
+/// ```markdown
+/// > | <a b />
+///        ^
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MdxJsxAttribute {
+    // Void.
+    /// Positional info.
+    // pub position: Option<Position>,
+    /// Key.
+    pub name: String,
+    /// Value.
+    pub value: Option<AttributeValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unist::Position;
+    use alloc::{format, string::ToString, vec};
+
+    #[test]
+    fn remove_positions() {
+        let position = Some(Position::new(1, 1, 0, 1, 2, 1));
+        let text = |position: Option<Position>| {
+            Node::Text(Text {
+                value: "a".into(),
+                position,
+            })
+        };
+        let mut node = Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![text(position.clone()), text(position.clone())],
+                    position: position.clone(),
+                })],
+                position: position.clone(),
+                alert: None,
+            })],
+            position,
+        });
+
+        node.remove_positions();
+
+        assert_eq!(
+            node,
+            Node::Root(Root {
+                children: vec![Node::BlockQuote(BlockQuote {
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![text(None), text(None)],
+                        position: None,
+                    })],
+                    position: None,
+                    alert: None,
+                })],
+                position: None,
+            }),
+            "should remove positions of all nodes"
+        );
+    }
+
+    #[test]
+    fn eq_ignoring_positions() {
+        let text = |value: &str, position: Option<Position>| {
+            Node::Text(Text {
+                value: value.into(),
+                position,
+            })
+        };
+        let paragraph = |children: Vec<Node>, position: Option<Position>| {
+            Node::Paragraph(Paragraph { children, position })
+        };
+        let a = paragraph(
+            vec![text("a", Some(Position::new(1, 1, 0, 1, 2, 1)))],
+            Some(Position::new(1, 1, 0, 1, 2, 1)),
+        );
+        let b = paragraph(vec![text("a", Some(Position::new(3, 1, 2, 3, 2, 3)))], None);
+
+        assert!(
+            a.eq_ignoring_positions(&b),
+            "should ignore positions of all nodes"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&paragraph(vec![text("b", None)], None)),
+            "should compare fields of descendants"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&paragraph(vec![text("a", None), text("a", None)], None)),
+            "should compare the number of children"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&text("a", None)),
+            "should compare kinds"
+        );
+
+        let definition_list = |position: Option<Position>| {
+            Node::DefinitionList(DefinitionList {
+                children: vec![
+                    Node::DefinitionTerm(DefinitionTerm {
+                        children: vec![text("a", position.clone())],
+                        position: position.clone(),
+                    }),
+                    Node::DefinitionDescription(DefinitionDescription {
+                        children: vec![],
+                        position: position.clone(),
+                    }),
+                ],
+                position,
+                spread: false,
+            })
+        };
+        assert!(
+            definition_list(Some(Position::new(1, 1, 0, 1, 2, 1)))
+                .eq_ignoring_positions(&definition_list(None)),
+            "should support definition lists"
+        );
+    }
+
+    #[test]
+    fn find() {
+        let text = |value: &str| {
+            Node::Text(Text {
+                value: value.into(),
+                position: None,
+            })
+        };
+        let node = Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![text("a")],
+                    position: None,
+                }),
+                Node::BlockQuote(BlockQuote {
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![text("b"), text("c")],
+                        position: None,
+                    })],
+                    position: None,
+                    alert: None,
+                }),
+            ],
+            position: None,
+        });
+        let is_text = |node: &Node| matches!(node, Node::Text(_));
+
+        assert_eq!(
+            node.find(is_text),
+            Some(&text("a")),
+            "should find the first node"
+        );
+        assert_eq!(
+            node.find(|node| matches!(node, Node::Heading(_))),
+            None,
+            "should not find missing nodes"
+        );
+        assert_eq!(
+            node.find(|node| matches!(node, Node::Root(_))),
+            Some(&node),
+            "should include the node itself"
+        );
+        assert_eq!(
+            node.find_all(is_text),
+            vec![&text("a"), &text("b"), &text("c")],
+            "should find all nodes"
+        );
+        assert_eq!(
+            node.find_all_with_paths(is_text)
+                .into_iter()
+                .map(|(path, _)| path.indices().to_vec())
+                .collect::<Vec<_>>(),
+            vec![vec![0, 0], vec![1, 0, 0], vec![1, 0, 1]],
+            "should find all nodes with paths"
+        );
+    }
+
+    #[test]
+    fn tree_cursor() {
+        let text = |value: &str| {
+            Node::Text(Text {
+                value: value.into(),
+                position: None,
+            })
+        };
+        let node = Node::Root(Root {
+            children: vec![
+                Node::Heading(Box::new(Heading {
+                    depth: 1,
+                    children: vec![text("a")],
+                    position: None,
+                    heading_kind: None,
+                    attributes: None,
+                })),
+                Node::Paragraph(Paragraph {
+                    children: vec![
+                        text("b"),
+                        Node::Emphasis(Emphasis {
+                            children: vec![text("c")],
+                            position: None,
+                            marker: None,
+                        }),
+                        text("d"),
+                    ],
+                    position: None,
+                }),
+            ],
+            position: None,
+        });
+
+        let mut cursor = TreeCursor::new(&node);
+        assert_eq!(cursor.node(), &node, "should start at the root");
+        assert_eq!(
+            cursor.parent(),
+            None,
+            "should not have a parent at the root"
+        );
+        assert_eq!(cursor.index(), None, "should not have an index at the root");
+        assert_eq!(
+            cursor.next_sibling(),
+            None,
+            "should not have siblings at the root"
+        );
+        assert!(
+            !cursor.goto_parent(),
+            "should not move to the root’s parent"
+        );
+        assert!(
+            !cursor.goto_next_sibling(),
+            "should not move to the root’s sibling"
+        );
+
+        assert!(cursor.goto_first_child(), "should move to a first child");
+        assert_eq!(cursor.node().kind(), NodeKind::Heading);
+        assert_eq!(
+            cursor.previous_sibling(),
+            None,
+            "should not have a previous sibling at a first child"
+        );
+        assert_eq!(
+            cursor.next_sibling().map(Node::kind),
+            Some(NodeKind::Paragraph),
+            "should get the next sibling"
+        );
+
+        assert!(cursor.goto_next_sibling(), "should move to a next sibling");
+        assert!(cursor.goto_first_child());
+        assert!(cursor.goto_next_sibling());
+        assert_eq!(cursor.node().kind(), NodeKind::Emphasis);
+        assert_eq!(cursor.index(), Some(1), "should track the index");
+        assert_eq!(
+            cursor.path(),
+            NodePath::from(vec![1, 1]),
+            "should get the path"
+        );
+        assert_eq!(
+            cursor.previous_sibling(),
+            Some(&text("b")),
+            "should get the previous sibling"
+        );
+        assert_eq!(
+            cursor.next_sibling(),
+            Some(&text("d")),
+            "should get the next sibling"
+        );
+        assert_eq!(
+            cursor.parent().map(Node::kind),
+            Some(NodeKind::Paragraph),
+            "should get the parent"
+        );
+
+        assert!(cursor.goto_first_child());
+        assert_eq!(cursor.depth(), 3, "should track the depth");
+        assert_eq!(
+            cursor.ancestors().map(Node::kind).collect::<Vec<_>>(),
+            vec![NodeKind::Emphasis, NodeKind::Paragraph, NodeKind::Root],
+            "should get the ancestors, nearest first"
+        );
+        assert!(!cursor.goto_first_child(), "should not move into a literal");
+        assert!(cursor.goto_parent(), "should move to the parent");
+        assert!(
+            cursor.goto_previous_sibling(),
+            "should move to a previous sibling"
+        );
+        assert_eq!(cursor.node(), &text("b"));
+
+        let mut cursor = TreeCursor::new(&node);
+        let mut texts = vec![];
+        while cursor.goto_next() {
+            if let Node::Text(x) = cursor.node() {
+                texts.push((x.value.to_string(), cursor.depth()));
+            }
+        }
+        assert_eq!(
+            texts,
+            vec![
+                ("a".into(), 2),
+                ("b".into(), 2),
+                ("c".into(), 3),
+                ("d".into(), 2)
+            ],
+            "should walk in document order with `goto_next`"
+        );
+        assert_eq!(
+            cursor.node(),
+            &text("d"),
+            "should stay at the last node after `goto_next` is done"
+        );
+    }
+
+    #[test]
+    fn tree_edit() {
+        let text = |value: &str| {
+            Node::Text(Text {
+                value: value.into(),
+                position: None,
+            })
+        };
+        let paragraph = |children: Vec<Node>| {
+            Node::Paragraph(Paragraph {
+                children,
+                position: None,
+            })
+        };
+        let root = |children: Vec<Node>| {
+            Node::Root(Root {
+                children,
+                position: None,
+            })
+        };
+
+        let mut edit = TreeEdit::new(root(vec![paragraph(vec![text("a"), text("b")])]));
+        assert_eq!(edit.index(), None, "should start at the root");
+        assert!(!edit.goto_parent(), "should not go past the root");
+        assert!(
+            !edit.insert_before(text("x")),
+            "should not insert at the root"
+        );
+        assert_eq!(edit.delete(), None, "should not delete the root");
+
+        assert!(edit.goto_first_child(), "should go to first children");
+        assert!(
+            edit.goto_first_child(),
+            "should go to deeper first children"
+        );
+        assert_eq!(edit.node(), &text("a"), "should get the current node");
+        assert!(!edit.goto_first_child(), "should not enter literals");
+        assert!(edit.goto_next_sibling(), "should go to next siblings");
+        assert_eq!(edit.path(), NodePath::from(vec![0, 1]), "should get paths");
+        assert_eq!(
+            edit.previous_sibling(),
+            Some(&text("a")),
+            "should get previous siblings"
+        );
+        assert_eq!(edit.next_sibling(), None, "should not get missing siblings");
+        assert!(
+            !edit.goto_next_sibling(),
+            "should not go past last children"
+        );
+
+        edit.insert_before(text("c"));
+        edit.insert_after(text("d"));
+        assert_eq!(
+            edit.index(),
+            Some(2),
+            "should insert before the current node"
+        );
+        assert_eq!(
+            edit.next_sibling(),
+            Some(&text("d")),
+            "should insert after the current node"
+        );
+        assert_eq!(edit.replace(text("e")), text("b"), "should replace nodes");
+        assert_eq!(edit.delete(), Some(text("e")), "should delete nodes");
+        assert_eq!(
+            edit.node(),
+            &text("d"),
+            "should move to the next sibling after deleting"
+        );
+        assert_eq!(
+            edit.delete(),
+            Some(text("d")),
+            "should delete last children"
+        );
+        assert_eq!(
+            edit.node(),
+            &text("c"),
+            "should move to the previous sibling after deleting"
+        );
+        assert!(
+            edit.goto_previous_sibling(),
+            "should go to previous siblings"
+        );
+
+        if let Node::Text(x) = edit.node_mut() {
+            x.value = "f".into();
+        }
+        assert!(edit.goto_parent(), "should go to parents");
+        assert_eq!(
+            edit.node(),
+            &paragraph(vec![text("f"), text("c")]),
+            "should put children back together"
+        );
+
+        edit.goto_first_child();
+        edit.delete();
+        edit.delete();
+        assert_eq!(
+            edit.node(),
+            &paragraph(vec![]),
+            "should move to the parent after deleting only children"
+        );
+        assert_eq!(
+            edit.into_node(),
+            root(vec![paragraph(vec![])]),
+            "should get the tree back"
+        );
+    }
+
+    #[test]
+    fn descendants() {
+        let text = |value: &str| {
+            Node::Text(Text {
+                value: value.into(),
+                position: None,
+            })
+        };
+        let mut node = Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![
+                        text("a"),
+                        Node::Emphasis(Emphasis {
+                            children: vec![text("b")],
+                            position: None,
+                            marker: None,
+                        }),
+                    ],
+                    position: None,
+                }),
+                Node::ThematicBreak(ThematicBreak { position: None }),
+                Node::Paragraph(Paragraph {
+                    children: vec![],
+                    position: None,
+                }),
+            ],
+            position: None,
+        });
+
+        assert_eq!(
+            node.descendants().map(Node::kind).collect::<Vec<_>>(),
+            vec![
+                NodeKind::Root,
+                NodeKind::Paragraph,
+                NodeKind::Text,
+                NodeKind::Emphasis,
+                NodeKind::Text,
+                NodeKind::ThematicBreak,
+                NodeKind::Paragraph
+            ],
+            "should support `descendants`"
+        );
+        assert_eq!(
+            text("c").descendants().count(),
+            1,
+            "should include the node itself in `descendants`"
+        );
+        assert_eq!(
+            node.descendants_mut()
+                .map(|node| node.kind())
+                .collect::<Vec<_>>(),
+            vec![NodeKind::Text, NodeKind::Text, NodeKind::ThematicBreak],
+            "should support `descendants_mut`, without parents"
+        );
+
+        for node in node.descendants_mut() {
+            node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        }
+        assert_eq!(
+            node.descendants()
+                .filter(|node| node.position().is_some())
+                .count(),
+            3,
+            "should support changing nodes with `descendants_mut`"
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn size() {
+        // Unboxed variants, such as `List`, set the size.
+        assert_eq!(core::mem::size_of::<Node>(), 96, "should be small");
+    }
+
+    // Literals.
+
+    #[test]
+    fn text() {
+        let mut node = Node::Text(Text {
+            value: "a".into(),
+            position: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Text { value: \"a\", position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Text { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn character_reference() {
+        let mut node = Node::CharacterReference(Box::new(CharacterReference {
+            value: "&".into(),
+            position: None,
+            raw: "&amp;".into(),
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "CharacterReference { value: \"&\", position: None, raw: \"&amp;\" }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "&", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 6, 5)));
+        assert_eq!(
+            format!("{:?}", node),
+            "CharacterReference { value: \"&\", position: Some(1:1-1:6 (0-5)), raw: \"&amp;\" }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn emoji() {
+        let mut node = Node::Emoji(Box::new(Emoji {
+            value: "🎉".into(),
+            position: None,
+            shortcode: "tada".into(),
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Emoji { value: \"🎉\", position: None, shortcode: \"tada\" }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "🎉", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 7, 6)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Emoji { value: \"🎉\", position: Some(1:1-1:7 (0-6)), shortcode: \"tada\" }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn abbreviation() {
+        let mut node = Node::Abbreviation(Box::new(Abbreviation {
+            value: "HTML".into(),
+            position: None,
+            title: Some("a".into()),
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Abbreviation { value: \"HTML\", position: None, title: Some(\"a\") }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "HTML", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 5, 4)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Abbreviation { value: \"HTML\", position: Some(1:1-1:5 (0-4)), title: Some(\"a\") }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn inline_code() {
+        let mut node = Node::InlineCode(InlineCode {
+            value: "a".into(),
+            position: None,
+            attributes: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineCode { value: \"a\", position: None, attributes: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineCode { value: \"a\", position: Some(1:1-1:2 (0-1)), attributes: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn code() {
+        let mut node = Node::Code(Box::new(Code {
+            value: "a".into(),
+            position: None,
+            lang: None,
+            meta: None,
+            code_kind: None,
+            fence_marker: None,
+            fence_size: None,
+            fence_indent: None,
+            attributes: None,
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Code { value: \"a\", position: None, lang: None, meta: None, code_kind: None, fence_marker: None, fence_size: None, fence_indent: None, attributes: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Code { value: \"a\", position: Some(1:1-1:2 (0-1)), lang: None, meta: None, code_kind: None, fence_marker: None, fence_size: None, fence_indent: None, attributes: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn inline_math() {
+        let mut node = Node::InlineMath(InlineMath {
+            value: "a".into(),
+            position: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineMath { value: \"a\", position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineMath { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
 
     #[test]
-    fn text() {
-        let mut node = Node::Text(Text {
+    fn math() {
+        let mut node = Node::Math(Box::new(Math {
             value: "a".into(),
             position: None,
-        });
+            meta: None,
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "Text { value: \"a\", position: None }",
+            "Math { value: \"a\", position: None, meta: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1390,21 +4403,21 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Text { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "Math { value: \"a\", position: Some(1:1-1:2 (0-1)), meta: None }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn inline_code() {
-        let mut node = Node::InlineCode(InlineCode {
+    fn html() {
+        let mut node = Node::Html(Html {
             value: "a".into(),
             position: None,
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "InlineCode { value: \"a\", position: None }",
+            "Html { value: \"a\", position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1415,48 +4428,47 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "InlineCode { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "Html { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn code() {
-        let mut node = Node::Code(Code {
-            value: "a".into(),
+    fn comment() {
+        let mut node = Node::Comment(Comment {
+            value: " a ".into(),
             position: None,
-            lang: None,
-            meta: None,
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Code { value: \"a\", position: None, lang: None, meta: None }",
+            "Comment { value: \" a \", position: None }",
             "should support `Debug`"
         );
-        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.to_string(), "", "should support `ToString`");
         assert_eq!(node.children_mut(), None, "should support `children_mut`");
         assert_eq!(node.children(), None, "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
-        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        node.position_set(Some(Position::new(1, 1, 0, 1, 11, 10)));
         assert_eq!(
             format!("{:?}", node),
-            "Code { value: \"a\", position: Some(1:1-1:2 (0-1)), lang: None, meta: None }",
+            "Comment { value: \" a \", position: Some(1:1-1:11 (0-10)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn inline_math() {
-        let mut node = Node::InlineMath(InlineMath {
+    fn mdx_text_expression() {
+        let mut node = Node::MdxTextExpression(Box::new(MdxTextExpression {
             value: "a".into(),
+            stops: vec![],
             position: None,
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "InlineMath { value: \"a\", position: None }",
+            "MdxTextExpression { value: \"a\", position: None, stops: [] }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1467,22 +4479,22 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "InlineMath { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "MdxTextExpression { value: \"a\", position: Some(1:1-1:2 (0-1)), stops: [] }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn math() {
-        let mut node = Node::Math(Math {
+    fn mdx_flow_expression() {
+        let mut node = Node::MdxFlowExpression(Box::new(MdxFlowExpression {
             value: "a".into(),
+            stops: vec![],
             position: None,
-            meta: None,
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "Math { value: \"a\", position: None, meta: None }",
+            "MdxFlowExpression { value: \"a\", position: None, stops: [] }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1493,21 +4505,22 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Math { value: \"a\", position: Some(1:1-1:2 (0-1)), meta: None }",
+            "MdxFlowExpression { value: \"a\", position: Some(1:1-1:2 (0-1)), stops: [] }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn html() {
-        let mut node = Node::Html(Html {
+    fn mdxjs_esm() {
+        let mut node = Node::MdxjsEsm(Box::new(MdxjsEsm {
             value: "a".into(),
+            stops: vec![],
             position: None,
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "Html { value: \"a\", position: None }",
+            "MdxjsEsm { value: \"a\", position: None, stops: [] }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1518,22 +4531,21 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Html { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "MdxjsEsm { value: \"a\", position: Some(1:1-1:2 (0-1)), stops: [] }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn mdx_text_expression() {
-        let mut node = Node::MdxTextExpression(MdxTextExpression {
+    fn toml() {
+        let mut node = Node::Toml(Toml {
             value: "a".into(),
-            stops: vec![],
             position: None,
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "MdxTextExpression { value: \"a\", position: None, stops: [] }",
+            "Toml { value: \"a\", position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1544,22 +4556,21 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "MdxTextExpression { value: \"a\", position: Some(1:1-1:2 (0-1)), stops: [] }",
+            "Toml { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn mdx_flow_expression() {
-        let mut node = Node::MdxFlowExpression(MdxFlowExpression {
+    fn yaml() {
+        let mut node = Node::Yaml(Yaml {
             value: "a".into(),
-            stops: vec![],
             position: None,
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "MdxFlowExpression { value: \"a\", position: None, stops: [] }",
+            "Yaml { value: \"a\", position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "a", "should support `ToString`");
@@ -1570,254 +4581,491 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "MdxFlowExpression { value: \"a\", position: Some(1:1-1:2 (0-1)), stops: [] }",
+            "Yaml { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
+    // Voids.
+
     #[test]
-    fn mdxjs_esm() {
-        let mut node = Node::MdxjsEsm(MdxjsEsm {
+    fn block_id() {
+        let mut node = Node::BlockId(BlockId {
+            position: None,
             value: "a".into(),
-            stops: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "BlockId { position: None, value: \"a\" }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 3, 2)));
+        assert_eq!(
+            format!("{:?}", node),
+            "BlockId { position: Some(1:1-1:3 (0-2)), value: \"a\" }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn break_node() {
+        let mut node = Node::Break(Break { position: None });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Break { position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Break { position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn thematic_break() {
+        let mut node = Node::ThematicBreak(ThematicBreak { position: None });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ThematicBreak { position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ThematicBreak { position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn footnote_reference() {
+        let mut node = Node::FootnoteReference(Box::new(FootnoteReference {
+            position: None,
+            identifier: "a".into(),
+            label: Some("b".into()),
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "FootnoteReference { position: None, identifier: \"a\", label: Some(\"b\") }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "FootnoteReference { position: Some(1:1-1:2 (0-1)), identifier: \"a\", label: Some(\"b\") }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn image_reference() {
+        let mut node = Node::ImageReference(Box::new(ImageReference {
+            position: None,
+            alt: "a".into(),
+            identifier: "b".into(),
+            label: Some("c".into()),
+            reference_kind: ReferenceKind::Full,
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ImageReference { position: None, alt: \"a\", reference_kind: Full, identifier: \"b\", label: Some(\"c\") }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ImageReference { position: Some(1:1-1:2 (0-1)), alt: \"a\", reference_kind: Full, identifier: \"b\", label: Some(\"c\") }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn image() {
+        let mut node = Node::Image(Box::new(Image {
+            position: None,
+            alt: "a".into(),
+            url: "b".into(),
+            title: None,
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Image { position: None, alt: \"a\", url: \"b\", title: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Image { position: Some(1:1-1:2 (0-1)), alt: \"a\", url: \"b\", title: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn definition() {
+        let mut node = Node::Definition(Box::new(Definition {
+            position: None,
+            identifier: "a".into(),
+            label: None,
+            url: "b".into(),
+            title: None,
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Definition { position: None, url: \"b\", title: None, identifier: \"a\", label: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Definition { position: Some(1:1-1:2 (0-1)), url: \"b\", title: None, identifier: \"a\", label: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn abbreviation_definition() {
+        let mut node = Node::AbbreviationDefinition(Box::new(AbbreviationDefinition {
+            position: None,
+            label: "a".into(),
+            title: None,
+        }));
+
+        assert_eq!(
+            format!("{:?}", node),
+            "AbbreviationDefinition { position: None, label: \"a\", title: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "AbbreviationDefinition { position: Some(1:1-1:2 (0-1)), label: \"a\", title: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn wiki_link() {
+        let mut node = Node::WikiLink(Box::new(WikiLink {
             position: None,
-        });
+            target: "a".into(),
+            anchor: Some("b".into()),
+            label: None,
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "MdxjsEsm { value: \"a\", position: None, stops: [] }",
+            "WikiLink { position: None, target: \"a\", anchor: Some(\"b\"), label: None }",
             "should support `Debug`"
         );
-        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.to_string(), "a#b", "should support `ToString`");
         assert_eq!(node.children_mut(), None, "should support `children_mut`");
         assert_eq!(node.children(), None, "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
-        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        node.position_set(Some(Position::new(1, 1, 0, 1, 10, 9)));
         assert_eq!(
             format!("{:?}", node),
-            "MdxjsEsm { value: \"a\", position: Some(1:1-1:2 (0-1)), stops: [] }",
+            "WikiLink { position: Some(1:1-1:10 (0-9)), target: \"a\", anchor: Some(\"b\"), label: None }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn toml() {
-        let mut node = Node::Toml(Toml {
-            value: "a".into(),
+    fn cite() {
+        let mut node = Node::Cite(Cite {
             position: None,
+            citations: vec![Citation {
+                key: "a".into(),
+                mode: CitationMode::Normal,
+                prefix: None,
+                locator: None,
+                suffix: None,
+            }],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Toml { value: \"a\", position: None }",
+            "Cite { position: None, citations: [Citation { key: \"a\", mode: Normal, prefix: None, locator: None, suffix: None }] }",
             "should support `Debug`"
         );
-        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.to_string(), "", "should support `ToString`");
         assert_eq!(node.children_mut(), None, "should support `children_mut`");
         assert_eq!(node.children(), None, "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
-        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        node.position_set(Some(Position::new(1, 1, 0, 1, 5, 4)));
         assert_eq!(
             format!("{:?}", node),
-            "Toml { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "Cite { position: Some(1:1-1:5 (0-4)), citations: [Citation { key: \"a\", mode: Normal, prefix: None, locator: None, suffix: None }] }",
             "should support `position_set`"
         );
     }
 
+    // Parents.
+
     #[test]
-    fn yaml() {
-        let mut node = Node::Yaml(Yaml {
-            value: "a".into(),
+    fn root() {
+        let mut node = Node::Root(Root {
             position: None,
+            children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Yaml { value: \"a\", position: None }",
+            "Root { children: [], position: None }",
             "should support `Debug`"
         );
-        assert_eq!(node.to_string(), "a", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Yaml { value: \"a\", position: Some(1:1-1:2 (0-1)) }",
+            "Root { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
-    // Voids.
-
     #[test]
-    fn break_node() {
-        let mut node = Node::Break(Break { position: None });
+    fn block_quote() {
+        let mut node = Node::BlockQuote(BlockQuote {
+            position: None,
+            children: vec![],
+            alert: None,
+        });
 
         assert_eq!(
             format!("{:?}", node),
-            "Break { position: None }",
+            "BlockQuote { children: [], position: None, alert: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Break { position: Some(1:1-1:2 (0-1)) }",
+            "BlockQuote { children: [], position: Some(1:1-1:2 (0-1)), alert: None }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn thematic_break() {
-        let mut node = Node::ThematicBreak(ThematicBreak { position: None });
+    fn delete() {
+        let mut node = Node::Delete(Delete {
+            position: None,
+            children: vec![],
+        });
 
         assert_eq!(
             format!("{:?}", node),
-            "ThematicBreak { position: None }",
+            "Delete { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "ThematicBreak { position: Some(1:1-1:2 (0-1)) }",
+            "Delete { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn footnote_reference() {
-        let mut node = Node::FootnoteReference(FootnoteReference {
+    fn highlight() {
+        let mut node = Node::Highlight(Highlight {
             position: None,
-            identifier: "a".into(),
-            label: Some("b".into()),
+            children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "FootnoteReference { position: None, identifier: \"a\", label: Some(\"b\") }",
+            "Highlight { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "FootnoteReference { position: Some(1:1-1:2 (0-1)), identifier: \"a\", label: Some(\"b\") }",
+            "Highlight { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn image_reference() {
-        let mut node = Node::ImageReference(ImageReference {
+    fn insert() {
+        let mut node = Node::Insert(Insert {
             position: None,
-            alt: "a".into(),
-            identifier: "b".into(),
-            label: Some("c".into()),
-            reference_kind: ReferenceKind::Full,
+            children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "ImageReference { position: None, alt: \"a\", reference_kind: Full, identifier: \"b\", label: Some(\"c\") }",
+            "Insert { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "ImageReference { position: Some(1:1-1:2 (0-1)), alt: \"a\", reference_kind: Full, identifier: \"b\", label: Some(\"c\") }",
+            "Insert { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn image() {
-        let mut node = Node::Image(Image {
+    fn critic_addition() {
+        let mut node = Node::CriticAddition(CriticAddition {
             position: None,
-            alt: "a".into(),
-            url: "b".into(),
-            title: None,
+            children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Image { position: None, alt: \"a\", url: \"b\", title: None }",
+            "CriticAddition { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Image { position: Some(1:1-1:2 (0-1)), alt: \"a\", url: \"b\", title: None }",
+            "CriticAddition { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn definition() {
-        let mut node = Node::Definition(Definition {
+    fn critic_deletion() {
+        let mut node = Node::CriticDeletion(CriticDeletion {
             position: None,
-            identifier: "a".into(),
-            label: None,
-            url: "b".into(),
-            title: None,
+            children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Definition { position: None, url: \"b\", title: None, identifier: \"a\", label: None }",
+            "CriticDeletion { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
-        assert_eq!(node.children_mut(), None, "should support `children_mut`");
-        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
         assert_eq!(node.position(), None, "should support `position`");
         assert_eq!(node.position_mut(), None, "should support `position`");
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Definition { position: Some(1:1-1:2 (0-1)), url: \"b\", title: None, identifier: \"a\", label: None }",
+            "CriticDeletion { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
-    // Parents.
-
     #[test]
-    fn root() {
-        let mut node = Node::Root(Root {
+    fn critic_substitution() {
+        let mut node = Node::CriticSubstitution(CriticSubstitution {
             position: None,
             children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Root { children: [], position: None }",
+            "CriticSubstitution { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -1832,21 +5080,21 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Root { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "CriticSubstitution { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn block_quote() {
-        let mut node = Node::BlockQuote(BlockQuote {
+    fn critic_highlight() {
+        let mut node = Node::CriticHighlight(CriticHighlight {
             position: None,
             children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "BlockQuote { children: [], position: None }",
+            "CriticHighlight { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -1861,21 +5109,21 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "BlockQuote { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "CriticHighlight { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
-    fn delete() {
-        let mut node = Node::Delete(Delete {
+    fn critic_comment() {
+        let mut node = Node::CriticComment(CriticComment {
             position: None,
             children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Delete { children: [], position: None }",
+            "CriticComment { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -1890,7 +5138,7 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Delete { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "CriticComment { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
@@ -1900,11 +5148,12 @@ mod tests {
         let mut node = Node::Emphasis(Emphasis {
             position: None,
             children: vec![],
+            marker: None,
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Emphasis { children: [], position: None }",
+            "Emphasis { children: [], position: None, marker: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -1919,7 +5168,7 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Emphasis { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "Emphasis { children: [], position: Some(1:1-1:2 (0-1)), marker: None }",
             "should support `position_set`"
         );
     }
@@ -1929,11 +5178,12 @@ mod tests {
         let mut node = Node::Strong(Strong {
             position: None,
             children: vec![],
+            marker: None,
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "Strong { children: [], position: None }",
+            "Strong { children: [], position: None, marker: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -1948,7 +5198,7 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Strong { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "Strong { children: [], position: Some(1:1-1:2 (0-1)), marker: None }",
             "should support `position_set`"
         );
     }
@@ -2042,15 +5292,17 @@ mod tests {
 
     #[test]
     fn heading() {
-        let mut node = Node::Heading(Heading {
+        let mut node = Node::Heading(Box::new(Heading {
             position: None,
             depth: 1,
             children: vec![],
-        });
+            heading_kind: None,
+            attributes: None,
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "Heading { children: [], position: None, depth: 1 }",
+            "Heading { children: [], position: None, depth: 1, heading_kind: None, attributes: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -2065,22 +5317,23 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Heading { children: [], position: Some(1:1-1:2 (0-1)), depth: 1 }",
+            "Heading { children: [], position: Some(1:1-1:2 (0-1)), depth: 1, heading_kind: None, attributes: None }",
             "should support `position_set`"
         );
     }
 
     #[test]
     fn table() {
-        let mut node = Node::Table(Table {
+        let mut node = Node::Table(Box::new(Table {
             position: None,
             align: vec![],
             children: vec![],
-        });
+            caption: None,
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "Table { children: [], position: None, align: [] }",
+            "Table { children: [], position: None, align: [], caption: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -2095,7 +5348,7 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Table { children: [], position: Some(1:1-1:2 (0-1)), align: [] }",
+            "Table { children: [], position: Some(1:1-1:2 (0-1)), align: [], caption: None }",
             "should support `position_set`"
         );
     }
@@ -2139,11 +5392,71 @@ mod tests {
             ordered: false,
             start: None,
             children: vec![],
+            marker: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "List { children: [], position: None, ordered: false, start: None, spread: false, marker: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "List { children: [], position: Some(1:1-1:2 (0-1)), ordered: false, start: None, spread: false, marker: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn definition_list() {
+        let mut node = Node::DefinitionList(DefinitionList {
+            position: None,
+            spread: false,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "DefinitionList { children: [], position: None, spread: false }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "DefinitionList { children: [], position: Some(1:1-1:2 (0-1)), spread: false }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn definition_description() {
+        let mut node = Node::DefinitionDescription(DefinitionDescription {
+            position: None,
+            children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "List { children: [], position: None, ordered: false, start: None, spread: false }",
+            "DefinitionDescription { children: [], position: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -2158,20 +5471,20 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "List { children: [], position: Some(1:1-1:2 (0-1)), ordered: false, start: None, spread: false }",
+            "DefinitionDescription { children: [], position: Some(1:1-1:2 (0-1)) }",
             "should support `position_set`"
         );
     }
 
     #[test]
     fn link_reference() {
-        let mut node = Node::LinkReference(LinkReference {
+        let mut node = Node::LinkReference(Box::new(LinkReference {
             position: None,
             identifier: "a".into(),
             label: None,
             reference_kind: ReferenceKind::Full,
             children: vec![],
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
@@ -2197,16 +5510,17 @@ mod tests {
 
     #[test]
     fn link() {
-        let mut node = Node::Link(Link {
+        let mut node = Node::Link(Box::new(Link {
             position: None,
             url: "a".into(),
             title: None,
             children: vec![],
-        });
+            link_kind: None,
+        }));
 
         assert_eq!(
             format!("{:?}", node),
-            "Link { children: [], position: None, url: \"a\", title: None }",
+            "Link { children: [], position: None, url: \"a\", title: None, link_kind: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -2221,19 +5535,19 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "Link { children: [], position: Some(1:1-1:2 (0-1)), url: \"a\", title: None }",
+            "Link { children: [], position: Some(1:1-1:2 (0-1)), url: \"a\", title: None, link_kind: None }",
             "should support `position_set`"
         );
     }
 
     #[test]
     fn footnote_definition() {
-        let mut node = Node::FootnoteDefinition(FootnoteDefinition {
+        let mut node = Node::FootnoteDefinition(Box::new(FootnoteDefinition {
             position: None,
             identifier: "a".into(),
             label: None,
             children: vec![],
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
@@ -2259,12 +5573,12 @@ mod tests {
 
     #[test]
     fn mdx_jsx_flow_element() {
-        let mut node = Node::MdxJsxFlowElement(MdxJsxFlowElement {
+        let mut node = Node::MdxJsxFlowElement(Box::new(MdxJsxFlowElement {
             position: None,
             name: None,
             attributes: vec![],
             children: vec![],
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
@@ -2290,12 +5604,12 @@ mod tests {
 
     #[test]
     fn mdx_jsx_text_element() {
-        let mut node = Node::MdxJsxTextElement(MdxJsxTextElement {
+        let mut node = Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
             position: None,
             name: None,
             attributes: vec![],
             children: vec![],
-        });
+        }));
 
         assert_eq!(
             format!("{:?}", node),
@@ -2318,4 +5632,187 @@ mod tests {
             "should support `position_set`"
         );
     }
+
+    #[test]
+    fn node_path() {
+        let mut node = Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a".into(),
+                        position: None,
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: None,
+                        })],
+                        position: None,
+                        marker: None,
+                    }),
+                ],
+                position: None,
+            })],
+            position: None,
+        });
+
+        assert_eq!(
+            node.get(&NodePath::new()),
+            Some(&node),
+            "should get the root at the empty path"
+        );
+        assert_eq!(
+            node.get(&NodePath::from(vec![0, 1, 0]))
+                .map(ToString::to_string),
+            Some("b".into()),
+            "should get nodes at paths"
+        );
+        assert_eq!(
+            node.get(&NodePath::from(vec![0, 2])),
+            None,
+            "should not get missing nodes"
+        );
+        assert_eq!(
+            node.get(&NodePath::from(vec![0, 0, 0])),
+            None,
+            "should not get children of nodes that cannot have them"
+        );
+
+        if let Some(Node::Text(text)) = node.get_mut(&NodePath::from(vec![0, 0])) {
+            text.value = "c".into();
+        }
+        assert_eq!(node.to_string(), "cb", "should get nodes mutably");
+
+        let mut paths = vec![];
+        node.each_with_path(&mut |_, path| paths.push(path.clone()));
+        assert_eq!(
+            paths,
+            vec![
+                NodePath::new(),
+                NodePath::from(vec![0]),
+                NodePath::from(vec![0, 0]),
+                NodePath::from(vec![0, 1]),
+                NodePath::from(vec![0, 1, 0]),
+            ],
+            "should compute paths in document order"
+        );
+
+        let path = NodePath::from(vec![0, 1]);
+        assert_eq!(
+            path.parent(),
+            Some(NodePath::from(vec![0])),
+            "should get parent paths"
+        );
+        assert_eq!(
+            path.child(2),
+            NodePath::from(vec![0, 1, 2]),
+            "should get child paths"
+        );
+        assert_eq!(
+            NodePath::new().parent(),
+            None,
+            "should not go past the root"
+        );
+
+        let copy = node.clone();
+        assert_eq!(
+            path.resolve(&copy),
+            path.resolve(&node),
+            "should resolve paths across clones"
+        );
+        if let Some(Node::Emphasis(emphasis)) = path.resolve_mut(&mut node) {
+            emphasis.children.clear();
+        }
+        assert_eq!(node.to_string(), "c", "should resolve paths mutably");
+        assert_eq!(
+            path.resolve(&node).map(Node::kind),
+            Some(NodeKind::Emphasis),
+            "should resolve paths in changed trees"
+        );
+        assert_eq!(
+            path.child(0).resolve(&node),
+            None,
+            "should not resolve paths to removed nodes"
+        );
+    }
+
+    #[test]
+    fn stable_ids() {
+        let text = |value: &str, position: Option<Position>| {
+            Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: value.into(),
+                    position: position.clone(),
+                })],
+                position,
+            })
+        };
+
+        let before = Node::Root(Root {
+            children: vec![text("a", None), text("a", None), text("b", None)],
+            position: None,
+        })
+        .stable_ids();
+        let after = Node::Root(Root {
+            children: vec![
+                text("c", Some(Position::new(1, 1, 0, 1, 2, 1))),
+                text("a", Some(Position::new(3, 1, 3, 3, 2, 4))),
+                text("a", Some(Position::new(5, 1, 6, 5, 2, 7))),
+                text("b!", Some(Position::new(7, 1, 9, 7, 3, 11))),
+            ],
+            position: Some(Position::new(1, 1, 0, 7, 3, 11)),
+        })
+        .stable_ids();
+        let id = |ids: &BTreeMap<NodePath, u64>, path: Vec<usize>| ids[&NodePath::from(path)];
+
+        assert_eq!(before.len(), 7, "should get identifiers for all nodes");
+        assert_ne!(
+            id(&before, vec![0]),
+            id(&before, vec![1]),
+            "should get different identifiers for nodes with the same content"
+        );
+        assert_eq!(
+            id(&before, vec![0, 0]),
+            id(&after, vec![1, 0]),
+            "should get the same identifiers for unchanged nodes, ignoring positions"
+        );
+        assert_eq!(
+            id(&before, vec![1]),
+            id(&after, vec![2]),
+            "should get the same identifiers for unchanged nodes with the same content"
+        );
+        assert_ne!(
+            id(&before, vec![2]),
+            id(&after, vec![3]),
+            "should get different identifiers for changed nodes"
+        );
+        assert_ne!(
+            id(&before, vec![]),
+            id(&after, vec![]),
+            "should get different identifiers for ancestors of changed nodes"
+        );
+
+        let emphasis = |marker: Option<char>| {
+            Node::Emphasis(Emphasis {
+                children: vec![],
+                position: None,
+                marker,
+            })
+        };
+        assert_ne!(
+            emphasis(Some('*')).stable_ids()[&NodePath::new()],
+            emphasis(Some('_')).stable_ids()[&NodePath::new()],
+            "should get different identifiers for nodes whose fields differ"
+        );
+        assert_ne!(
+            emphasis(None).stable_ids()[&NodePath::new()],
+            Node::Strong(Strong {
+                children: vec![],
+                position: None,
+                marker: None,
+            })
+            .stable_ids()[&NodePath::new()],
+            "should get different identifiers for nodes of different kinds"
+        );
+    }
 }
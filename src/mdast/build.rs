@@ -0,0 +1,578 @@
+//! Construct [mdast][crate::mdast] trees without spelling out every field.
+//!
+//! Common nodes have constructors on [`Node`][], such as
+//! [`Node::text()`][] and [`Node::heading()`][], and on their structs, such
+//! as [`Paragraph::new()`][].
+//! Nodes with several optional fields have builders, such as
+//! [`Link::builder()`][].
+//! Every node struct converts into a [`Node`][] with `.into()`.
+//!
+//! Constructed nodes have no positional info.
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::{mdast::{Link, Node}, to_markdown};
+//!
+//! let tree = Node::root(vec![
+//!     Node::heading(2, vec![Node::text("Hello")]),
+//!     Node::paragraph(vec![
+//!         Node::text("See "),
+//!         Link::builder("https://example.com")
+//!             .child(Node::text("here"))
+//!             .into(),
+//!         Node::text("."),
+//!     ]),
+//! ]);
+//!
+//! assert_eq!(
+//!     to_markdown(tree),
+//!     "## Hello\n\nSee [here](https://example.com).\n"
+//! );
+//! ```
+
+use crate::mdast::{
+    Abbreviation, AbbreviationDefinition, BlockId, BlockQuote, Break, CharacterReference, Cite,
+    Code, Comment, CriticAddition, CriticComment, CriticDeletion, CriticHighlight,
+    CriticSubstitution, Definition, DefinitionDescription, DefinitionList, DefinitionTerm, Delete,
+    Emoji, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Highlight, Html, Image,
+    ImageReference, InlineCode, InlineMath, Insert, Link, LinkReference, List, ListItem, Math,
+    MdxFlowExpression, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression, MdxjsEsm, Node,
+    Paragraph, Root, Strong, Table, TableCell, TableRow, Text, TextValue, ThematicBreak, Toml,
+    WikiLink, Yaml,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// Implements `From` for [`Node`][] for each struct, boxing those that are
+/// boxed in it.
+macro_rules! into_node {
+    ($($variant:ident),* ; $($boxed:ident),*) => {
+        $(
+            impl From<$variant> for Node {
+                fn from(node: $variant) -> Self {
+                    Node::$variant(node)
+                }
+            }
+        )*
+        $(
+            impl From<$boxed> for Node {
+                fn from(node: $boxed) -> Self {
+                    Node::$boxed(Box::new(node))
+                }
+            }
+        )*
+    };
+}
+
+into_node! {
+    Root, BlockQuote, List, Toml, Yaml, Break, BlockId, Cite, InlineCode, InlineMath, Delete,
+    Highlight, Insert, CriticAddition, CriticDeletion, CriticSubstitution, CriticHighlight,
+    CriticComment, Emphasis, Html, Comment, Strong, Text, ThematicBreak, TableRow, TableCell, ListItem,
+    Paragraph, DefinitionList, DefinitionTerm, DefinitionDescription;
+    FootnoteDefinition, MdxJsxFlowElement, MdxjsEsm, CharacterReference, Emoji, Abbreviation,
+    MdxTextExpression,
+    FootnoteReference, Image, ImageReference, MdxJsxTextElement, Link, LinkReference, Code, Math,
+    MdxFlowExpression, Table, Definition, AbbreviationDefinition, WikiLink, Heading
+}
+
+impl Node {
+    /// Create a root.
+    #[must_use]
+    pub fn root(children: Vec<Node>) -> Node {
+        Root::new(children).into()
+    }
+
+    /// Create a block quote.
+    #[must_use]
+    pub fn block_quote(children: Vec<Node>) -> Node {
+        BlockQuote::new(children).into()
+    }
+
+    /// Create a list, which is ordered (starting at `1`) or not, and tight.
+    #[must_use]
+    pub fn list(ordered: bool, children: Vec<Node>) -> Node {
+        List::new(ordered, children).into()
+    }
+
+    /// Create a tight list item, without checkbox.
+    #[must_use]
+    pub fn list_item(children: Vec<Node>) -> Node {
+        ListItem::new(children).into()
+    }
+
+    /// Create a heading of rank `depth` (between `1` and `6`, both
+    /// including).
+    #[must_use]
+    pub fn heading(depth: u8, children: Vec<Node>) -> Node {
+        Heading::new(depth, children).into()
+    }
+
+    /// Create a paragraph.
+    #[must_use]
+    pub fn paragraph(children: Vec<Node>) -> Node {
+        Paragraph::new(children).into()
+    }
+
+    /// Create a thematic break.
+    #[must_use]
+    pub fn thematic_break() -> Node {
+        ThematicBreak { position: None }.into()
+    }
+
+    /// Create text.
+    #[must_use]
+    pub fn text(value: impl Into<TextValue>) -> Node {
+        Text::new(value).into()
+    }
+
+    /// Create emphasis.
+    #[must_use]
+    pub fn emphasis(children: Vec<Node>) -> Node {
+        Emphasis::new(children).into()
+    }
+
+    /// Create strong.
+    #[must_use]
+    pub fn strong(children: Vec<Node>) -> Node {
+        Strong::new(children).into()
+    }
+
+    /// Create delete (GFM strikethrough).
+    #[must_use]
+    pub fn delete(children: Vec<Node>) -> Node {
+        Delete::new(children).into()
+    }
+
+    /// Create highlight.
+    #[must_use]
+    pub fn highlight(children: Vec<Node>) -> Node {
+        Highlight::new(children).into()
+    }
+
+    /// Create insert.
+    #[must_use]
+    pub fn insert(children: Vec<Node>) -> Node {
+        Insert::new(children).into()
+    }
+
+    /// Create critic addition.
+    #[must_use]
+    pub fn critic_addition(children: Vec<Node>) -> Node {
+        CriticAddition::new(children).into()
+    }
+
+    /// Create critic deletion.
+    #[must_use]
+    pub fn critic_deletion(children: Vec<Node>) -> Node {
+        CriticDeletion::new(children).into()
+    }
+
+    /// Create critic substitution.
+    #[must_use]
+    pub fn critic_substitution(children: Vec<Node>) -> Node {
+        CriticSubstitution::new(children).into()
+    }
+
+    /// Create critic highlight.
+    #[must_use]
+    pub fn critic_highlight(children: Vec<Node>) -> Node {
+        CriticHighlight::new(children).into()
+    }
+
+    /// Create critic comment.
+    #[must_use]
+    pub fn critic_comment(children: Vec<Node>) -> Node {
+        CriticComment::new(children).into()
+    }
+
+    /// Create code (phrasing).
+    #[must_use]
+    pub fn inline_code(value: impl Into<TextValue>) -> Node {
+        InlineCode::new(value).into()
+    }
+
+    /// Create a link to `url`, without title.
+    #[must_use]
+    pub fn link(url: impl Into<String>, children: Vec<Node>) -> Node {
+        Link::builder(url).children(children).into()
+    }
+
+    /// Create an image of `url`, without title.
+    #[must_use]
+    pub fn image(url: impl Into<String>, alt: impl Into<String>) -> Node {
+        Image::builder(url).alt(alt).into()
+    }
+}
+
+impl Root {
+    /// Create a root.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Root {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl BlockQuote {
+    /// Create a block quote.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        BlockQuote {
+            children,
+            position: None,
+            alert: None,
+        }
+    }
+}
+
+impl List {
+    /// Create a list, which is ordered (starting at `1`) or not, and tight.
+    #[must_use]
+    pub fn new(ordered: bool, children: Vec<Node>) -> Self {
+        List {
+            children,
+            position: None,
+            ordered,
+            start: if ordered { Some(1) } else { None },
+            spread: false,
+            marker: None,
+        }
+    }
+}
+
+impl ListItem {
+    /// Create a tight list item, without checkbox.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        ListItem {
+            children,
+            position: None,
+            spread: false,
+            checked: None,
+        }
+    }
+}
+
+impl Heading {
+    /// Create a heading of rank `depth` (between `1` and `6`, both
+    /// including).
+    #[must_use]
+    pub fn new(depth: u8, children: Vec<Node>) -> Self {
+        Heading {
+            children,
+            position: None,
+            depth,
+            heading_kind: None,
+            attributes: None,
+        }
+    }
+}
+
+impl Paragraph {
+    /// Create a paragraph.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Paragraph {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl Text {
+    /// Create text.
+    #[must_use]
+    pub fn new(value: impl Into<TextValue>) -> Self {
+        Text {
+            value: value.into(),
+            position: None,
+        }
+    }
+}
+
+impl Emphasis {
+    /// Create emphasis.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Emphasis {
+            children,
+            position: None,
+            marker: None,
+        }
+    }
+}
+
+impl Strong {
+    /// Create strong.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Strong {
+            children,
+            position: None,
+            marker: None,
+        }
+    }
+}
+
+impl Delete {
+    /// Create delete (GFM strikethrough).
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Delete {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl Highlight {
+    /// Create highlight.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Highlight {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl Insert {
+    /// Create insert.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        Insert {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl CriticAddition {
+    /// Create critic addition.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        CriticAddition {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl CriticDeletion {
+    /// Create critic deletion.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        CriticDeletion {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl CriticSubstitution {
+    /// Create critic substitution.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        CriticSubstitution {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl CriticHighlight {
+    /// Create critic highlight.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        CriticHighlight {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl CriticComment {
+    /// Create critic comment.
+    #[must_use]
+    pub fn new(children: Vec<Node>) -> Self {
+        CriticComment {
+            children,
+            position: None,
+        }
+    }
+}
+
+impl InlineCode {
+    /// Create code (phrasing).
+    #[must_use]
+    pub fn new(value: impl Into<TextValue>) -> Self {
+        InlineCode {
+            value: value.into(),
+            position: None,
+            attributes: None,
+        }
+    }
+}
+
+impl Link {
+    /// Start building a link to `url`.
+    #[must_use]
+    pub fn builder(url: impl Into<String>) -> LinkBuilder {
+        LinkBuilder(Link {
+            children: Vec::new(),
+            position: None,
+            url: url.into(),
+            title: None,
+            link_kind: None,
+        })
+    }
+}
+
+/// Builder of [`Link`][]s.
+///
+/// Made by [`Link::builder()`][].
+#[derive(Clone, Debug)]
+pub struct LinkBuilder(Link);
+
+impl LinkBuilder {
+    /// Set the title.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.0.title = Some(title.into());
+        self
+    }
+
+    /// Add a child.
+    #[must_use]
+    pub fn child(mut self, child: impl Into<Node>) -> Self {
+        self.0.children.push(child.into());
+        self
+    }
+
+    /// Add children.
+    #[must_use]
+    pub fn children(mut self, children: impl IntoIterator<Item = Node>) -> Self {
+        self.0.children.extend(children);
+        self
+    }
+
+    /// Finish the link.
+    #[must_use]
+    pub fn build(self) -> Link {
+        self.0
+    }
+}
+
+impl From<LinkBuilder> for Node {
+    fn from(builder: LinkBuilder) -> Self {
+        builder.build().into()
+    }
+}
+
+impl Image {
+    /// Start building an image of `url`.
+    #[must_use]
+    pub fn builder(url: impl Into<String>) -> ImageBuilder {
+        ImageBuilder(Image {
+            position: None,
+            alt: String::new(),
+            url: url.into(),
+            title: None,
+        })
+    }
+}
+
+/// Builder of [`Image`][]s.
+///
+/// Made by [`Image::builder()`][].
+#[derive(Clone, Debug)]
+pub struct ImageBuilder(Image);
+
+impl ImageBuilder {
+    /// Set the alternative text.
+    #[must_use]
+    pub fn alt(mut self, alt: impl Into<String>) -> Self {
+        self.0.alt = alt.into();
+        self
+    }
+
+    /// Set the title.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.0.title = Some(title.into());
+        self
+    }
+
+    /// Finish the image.
+    #[must_use]
+    pub fn build(self) -> Image {
+        self.0
+    }
+}
+
+impl From<ImageBuilder> for Node {
+    fn from(builder: ImageBuilder) -> Self {
+        builder.build().into()
+    }
+}
+
+impl Code {
+    /// Start building code (flow) of `value`.
+    #[must_use]
+    pub fn builder(value: impl Into<String>) -> CodeBuilder {
+        CodeBuilder(Code {
+            value: value.into(),
+            position: None,
+            lang: None,
+            meta: None,
+            code_kind: None,
+            fence_marker: None,
+            fence_size: None,
+            fence_indent: None,
+            attributes: None,
+        })
+    }
+}
+
+/// Builder of [`Code`][]s.
+///
+/// Made by [`Code::builder()`][].
+#[derive(Clone, Debug)]
+pub struct CodeBuilder(Code);
+
+impl CodeBuilder {
+    /// Set the language.
+    #[must_use]
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.0.lang = Some(lang.into());
+        self
+    }
+
+    /// Set the custom info after the language.
+    #[must_use]
+    pub fn meta(mut self, meta: impl Into<String>) -> Self {
+        self.0.meta = Some(meta.into());
+        self
+    }
+
+    /// Set the marker of the fences: `` ` `` or `~`.
+    #[must_use]
+    pub fn fence_marker(mut self, marker: char) -> Self {
+        self.0.fence_marker = Some(marker);
+        self
+    }
+
+    /// Finish the code.
+    #[must_use]
+    pub fn build(self) -> Code {
+        self.0
+    }
+}
+
+impl From<CodeBuilder> for Node {
+    fn from(builder: CodeBuilder) -> Self {
+        builder.build().into()
+    }
+}
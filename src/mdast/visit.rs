@@ -0,0 +1,261 @@
+//! Walk [mdast][crate::mdast] trees with callbacks for each kind of node.
+//!
+//! Implement [`Visitor`][] (or [`VisitorMut`][], to change nodes) with the
+//! methods for the kinds of nodes you care about, such as
+//! [`enter_heading()`][Visitor::enter_heading], and pass it to [`walk()`][]
+//! (or [`walk_mut()`][]).
+//! Other methods do nothing by default.
+//!
+//! Trees are walked with a stack instead of by recursion, so that deeply
+//! nested trees do not overflow the call stack.
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::{mdast::{visit::{walk, Visitor}, Heading}, to_mdast, ParseOptions};
+//! # fn main() -> Result<(), markdown::message::Message> {
+//!
+//! #[derive(Default)]
+//! struct Depths(Vec<u8>);
+//!
+//! impl Visitor for Depths {
+//!     fn enter_heading(&mut self, node: &Heading) {
+//!         self.0.push(node.depth);
+//!     }
+//! }
+//!
+//! let tree = to_mdast("# a\n\n> ## b\n\n### c", &ParseOptions::default())?;
+//! let mut depths = Depths::default();
+//! walk(&tree, &mut depths);
+//!
+//! assert_eq!(depths.0, vec![1, 2, 3]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::mdast::{
+    Abbreviation, AbbreviationDefinition, BlockId, BlockQuote, Break, CharacterReference, Cite,
+    Code, Comment, CriticAddition, CriticComment, CriticDeletion, CriticHighlight,
+    CriticSubstitution, Definition, DefinitionDescription, DefinitionList, DefinitionTerm, Delete,
+    Emoji, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Highlight, Html, Image,
+    ImageReference, InlineCode, InlineMath, Insert, Link, LinkReference, List, ListItem, Math,
+    MdxFlowExpression, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression, MdxjsEsm, Node,
+    Paragraph, Root, Strong, Table, TableCell, TableRow, Text, ThematicBreak, Toml, WikiLink, Yaml,
+};
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+/// Defines [`Visitor`][] and [`VisitorMut`][], with an enter and exit method
+/// for each kind of node.
+macro_rules! visitors {
+    ($($variant:ident => $enter:ident, $exit:ident;)*) => {
+        /// Callbacks for when [`walk()`][] enters and exits nodes.
+        ///
+        /// Nodes are entered before their children and exited after them.
+        pub trait Visitor {
+            /// Called when entering any node.
+            ///
+            /// Calls the method for the kind of `node` by default, such as
+            /// [`enter_heading()`][Visitor::enter_heading].
+            fn enter(&mut self, node: &Node) {
+                match node {
+                    $(Node::$variant(x) => self.$enter(x),)*
+                }
+            }
+
+            /// Called when exiting any node.
+            ///
+            /// Calls the method for the kind of `node` by default, such as
+            /// [`exit_heading()`][Visitor::exit_heading].
+            fn exit(&mut self, node: &Node) {
+                match node {
+                    $(Node::$variant(x) => self.$exit(x),)*
+                }
+            }
+
+            $(
+                #[doc = concat!("Called when entering a [`", stringify!($variant), "`][] node.")]
+                fn $enter(&mut self, _node: &$variant) {}
+
+                #[doc = concat!("Called when exiting a [`", stringify!($variant), "`][] node.")]
+                fn $exit(&mut self, _node: &$variant) {}
+            )*
+        }
+
+        /// Callbacks for when [`walk_mut()`][] enters and exits nodes, which
+        /// can change them.
+        ///
+        /// Nodes are entered before their children and exited after them.
+        /// Children that are added when entering a node are walked too, and
+        /// ones that are removed are not.
+        pub trait VisitorMut {
+            /// Called when entering any node.
+            ///
+            /// Calls the method for the kind of `node` by default, such as
+            /// [`enter_heading()`][VisitorMut::enter_heading].
+            fn enter(&mut self, node: &mut Node) {
+                match node {
+                    $(Node::$variant(x) => self.$enter(x),)*
+                }
+            }
+
+            /// Called when exiting any node.
+            ///
+            /// Calls the method for the kind of `node` by default, such as
+            /// [`exit_heading()`][VisitorMut::exit_heading].
+            fn exit(&mut self, node: &mut Node) {
+                match node {
+                    $(Node::$variant(x) => self.$exit(x),)*
+                }
+            }
+
+            $(
+                #[doc = concat!("Called when entering a [`", stringify!($variant), "`][] node.")]
+                fn $enter(&mut self, _node: &mut $variant) {}
+
+                #[doc = concat!("Called when exiting a [`", stringify!($variant), "`][] node.")]
+                fn $exit(&mut self, _node: &mut $variant) {}
+            )*
+        }
+    };
+}
+
+visitors! {
+    // Document:
+    Root => enter_root, exit_root;
+
+    // Container:
+    BlockQuote => enter_block_quote, exit_block_quote;
+    FootnoteDefinition => enter_footnote_definition, exit_footnote_definition;
+    MdxJsxFlowElement => enter_mdx_jsx_flow_element, exit_mdx_jsx_flow_element;
+    List => enter_list, exit_list;
+    DefinitionList => enter_definition_list, exit_definition_list;
+
+    // Frontmatter:
+    MdxjsEsm => enter_mdxjs_esm, exit_mdxjs_esm;
+    Toml => enter_toml, exit_toml;
+    Yaml => enter_yaml, exit_yaml;
+
+    // Phrasing:
+    Break => enter_break, exit_break;
+    CharacterReference => enter_character_reference, exit_character_reference;
+    Emoji => enter_emoji, exit_emoji;
+    Abbreviation => enter_abbreviation, exit_abbreviation;
+    BlockId => enter_block_id, exit_block_id;
+    InlineCode => enter_inline_code, exit_inline_code;
+    InlineMath => enter_inline_math, exit_inline_math;
+    Delete => enter_delete, exit_delete;
+    Highlight => enter_highlight, exit_highlight;
+    Insert => enter_insert, exit_insert;
+    CriticAddition => enter_critic_addition, exit_critic_addition;
+    CriticDeletion => enter_critic_deletion, exit_critic_deletion;
+    CriticSubstitution => enter_critic_substitution, exit_critic_substitution;
+    CriticHighlight => enter_critic_highlight, exit_critic_highlight;
+    CriticComment => enter_critic_comment, exit_critic_comment;
+    Emphasis => enter_emphasis, exit_emphasis;
+    MdxTextExpression => enter_mdx_text_expression, exit_mdx_text_expression;
+    FootnoteReference => enter_footnote_reference, exit_footnote_reference;
+    Html => enter_html, exit_html;
+    Comment => enter_comment, exit_comment;
+    Image => enter_image, exit_image;
+    ImageReference => enter_image_reference, exit_image_reference;
+    MdxJsxTextElement => enter_mdx_jsx_text_element, exit_mdx_jsx_text_element;
+    Link => enter_link, exit_link;
+    LinkReference => enter_link_reference, exit_link_reference;
+    WikiLink => enter_wiki_link, exit_wiki_link;
+    Cite => enter_cite, exit_cite;
+    Strong => enter_strong, exit_strong;
+    Text => enter_text, exit_text;
+
+    // Flow:
+    Code => enter_code, exit_code;
+    Math => enter_math, exit_math;
+    MdxFlowExpression => enter_mdx_flow_expression, exit_mdx_flow_expression;
+    Heading => enter_heading, exit_heading;
+    Table => enter_table, exit_table;
+    ThematicBreak => enter_thematic_break, exit_thematic_break;
+
+    // Table content.
+    TableRow => enter_table_row, exit_table_row;
+
+    // Row content.
+    TableCell => enter_table_cell, exit_table_cell;
+
+    // List content.
+    ListItem => enter_list_item, exit_list_item;
+
+    // Definition list content.
+    DefinitionTerm => enter_definition_term, exit_definition_term;
+    DefinitionDescription => enter_definition_description, exit_definition_description;
+
+    // Content.
+    Definition => enter_definition, exit_definition;
+    AbbreviationDefinition => enter_abbreviation_definition, exit_abbreviation_definition;
+    Paragraph => enter_paragraph, exit_paragraph;
+}
+
+/// Walk `tree` in document order, calling `visitor` when entering and
+/// exiting each node.
+pub fn walk(tree: &Node, visitor: &mut impl Visitor) {
+    // Nodes, and whether their children were walked.
+    let mut stack = vec![(tree, false)];
+    while let Some((node, entered)) = stack.pop() {
+        if entered {
+            visitor.exit(node);
+        } else {
+            visitor.enter(node);
+            stack.push((node, true));
+            for child in node.children().into_iter().flatten().rev() {
+                stack.push((child, false));
+            }
+        }
+    }
+}
+
+/// Walk `tree` in document order, calling `visitor` when entering and
+/// exiting each node, which it can change.
+///
+/// While walking, nodes are moved out of their parents (and `tree` is
+/// replaced by an empty root), and put back when they are exited.
+pub fn walk_mut(tree: &mut Node, visitor: &mut impl VisitorMut) {
+    /// Node whose children are being walked.
+    struct Frame {
+        node: Node,
+        children: vec::IntoIter<Node>,
+        done: Vec<Node>,
+    }
+
+    /// Enter `node` and move its children out.
+    fn enter(mut node: Node, visitor: &mut impl VisitorMut) -> Frame {
+        visitor.enter(&mut node);
+        let children = node.children_mut().map(mem::take).unwrap_or_default();
+        let done = Vec::with_capacity(children.len());
+        Frame {
+            node,
+            children: children.into_iter(),
+            done,
+        }
+    }
+
+    let empty = Node::Root(Root {
+        children: vec![],
+        position: None,
+    });
+    let mut stack = vec![enter(mem::replace(tree, empty), visitor)];
+
+    while let Some(frame) = stack.last_mut() {
+        if let Some(child) = frame.children.next() {
+            let frame = enter(child, visitor);
+            stack.push(frame);
+        } else if let Some(Frame { mut node, done, .. }) = stack.pop() {
+            if let Some(children) = node.children_mut() {
+                *children = done;
+            }
+            visitor.exit(&mut node);
+            match stack.last_mut() {
+                Some(parent) => parent.done.push(node),
+                None => *tree = node,
+            }
+        }
+    }
+}
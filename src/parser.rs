@@ -1,13 +1,26 @@
 //! Turn bytes of markdown into events.
 
-use crate::event::{Event, Point};
+use crate::construct;
+use crate::event::{Event, Kind, Name, Point};
 use crate::message;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::subtokenize;
 use crate::tokenizer::Tokenizer;
-use crate::util::location::Location;
-use crate::ParseOptions;
-use alloc::{string::String, vec, vec::Vec};
+use crate::unist;
+use crate::util::{
+    location::Location,
+    normalize_identifier::normalize_identifier,
+    slice::{Position, Slice},
+};
+use crate::{DuplicateDefinitionKind, ParseOptions};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec,
+    vec::Vec,
+};
 
 /// Info needed, in all content types, when parsing markdown.
 ///
@@ -21,10 +34,17 @@ pub struct ParseState<'a> {
     pub options: &'a ParseOptions,
     /// List of chars.
     pub bytes: &'a [u8],
+    /// Characters that can start something in text.
+    pub text_markers: Vec<u8>,
     /// Set of defined definition identifiers.
-    pub definitions: Vec<String>,
+    pub definitions: BTreeSet<String>,
     /// Set of defined GFM footnote definition identifiers.
-    pub gfm_footnote_definitions: Vec<String>,
+    pub gfm_footnote_definitions: BTreeSet<String>,
+    /// Set of defined abbreviation labels.
+    pub abbreviations: BTreeSet<String>,
+    /// Messages about things that are not errors, such as duplicate
+    /// definitions.
+    pub messages: Vec<message::Message>,
 }
 
 /// Turn a string of markdown into events.
@@ -33,19 +53,52 @@ pub struct ParseState<'a> {
 pub fn parse<'a>(
     value: &'a str,
     options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
+    parse_in(value, options, None)
+}
+
+/// Turn a string of markdown, which is part of another document, into
+/// events.
+///
+/// Definitions, GFM footnote definitions, and abbreviations defined in the
+/// `outer` document are known in `value`.
+/// Used for the cells of grid tables.
+pub fn parse_in<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+    outer: Option<&ParseState>,
 ) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
     let bytes = value.as_bytes();
 
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("parse", bytes = bytes.len()).entered();
+
+    // Tabs stop at multiples of `tab_size`.
+    if options.tab_size == 0 {
+        return Err(message::Message {
+            place: None,
+            reason: "Unexpected `tab_size` of `0`, expected a number of columns between tab stops"
+                .into(),
+            rule_id: Box::new("tab-size".into()),
+            source: Box::new("markdown-rs".into()),
+        });
+    }
+
     let mut parse_state = ParseState {
         options,
         bytes,
+        text_markers: construct::text::markers(options),
         location: if options.mdx_esm_parse.is_some() || options.mdx_expression_parse.is_some() {
-            Some(Location::new(bytes))
+            Some(Location::new(bytes, options.tab_size))
         } else {
             None
         },
-        definitions: vec![],
-        gfm_footnote_definitions: vec![],
+        definitions: outer.map_or_else(BTreeSet::new, |outer| outer.definitions.clone()),
+        gfm_footnote_definitions: outer.map_or_else(BTreeSet::new, |outer| {
+            outer.gfm_footnote_definitions.clone()
+        }),
+        abbreviations: outer.map_or_else(BTreeSet::new, |outer| outer.abbreviations.clone()),
+        messages: vec![],
     };
 
     let start = Point {
@@ -56,6 +109,9 @@ pub fn parse<'a>(
     };
     let mut tokenizer = Tokenizer::new(start, &parse_state);
 
+    #[cfg(feature = "tracing")]
+    let flow_span = tracing::debug_span!("flow").entered();
+
     let state = tokenizer.push(
         (0, 0),
         (parse_state.bytes.len(), 0),
@@ -63,17 +119,274 @@ pub fn parse<'a>(
     );
     let mut result = tokenizer.flush(state, true)?;
     let mut events = tokenizer.events;
+    // Abbreviation definitions are only found in flow, so they are all known
+    // before text is parsed.
+    parse_state
+        .abbreviations
+        .extend(tokenizer.tokenize_state.abbreviations.drain(..));
+
+    #[cfg(feature = "tracing")]
+    flow_span.exit();
+
+    // Number of definition identifiers found, including duplicates.
+    let mut found = 0;
 
     loop {
         let fn_defs = &mut parse_state.gfm_footnote_definitions;
         let defs = &mut parse_state.definitions;
-        fn_defs.append(&mut result.gfm_footnote_definitions);
-        defs.append(&mut result.definitions);
+        found += result.gfm_footnote_definitions.len() + result.definitions.len();
+        fn_defs.extend(result.gfm_footnote_definitions.drain(..));
+        defs.extend(result.definitions.drain(..));
 
         if result.done {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(events = events.len(), "parsed");
+
+            if options.constructs.definition_list {
+                construct::definition_list::resolve(&mut events);
+            }
+
+            if options.constructs.gfm_alert {
+                construct::gfm_alert::resolve(&mut events, bytes);
+            }
+
+            // Cells are parsed as separate documents, which can contain
+            // definitions too.
+            if options.constructs.grid_table {
+                found += construct::grid_table::resolve(&mut events, &parse_state)?;
+            }
+
+            if let Some(max) = options.max_nesting {
+                if let Some(message) = nesting(&events, max) {
+                    return Err(message);
+                }
+            }
+
+            // Only look for duplicates when there might be some.
+            if found > parse_state.definitions.len() + parse_state.gfm_footnote_definitions.len() {
+                let mut messages = duplicates(
+                    &events,
+                    bytes,
+                    options.tab_size,
+                    options.duplicate_definitions,
+                );
+
+                if options.duplicate_definitions == DuplicateDefinitionKind::Error
+                    && !messages.is_empty()
+                {
+                    return Err(messages.remove(0));
+                }
+
+                parse_state.messages = messages;
+            }
+
             return Ok((events, parse_state));
         }
 
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("subtokenize", events = events.len()).entered();
+
         result = subtokenize(&mut events, &parse_state, &None)?;
     }
 }
+
+/// Find the first construct that is nested deeper than `max`.
+///
+/// Containers, attention, labels, and MDX JSX elements are counted.
+/// MDX JSX tags are not nested in events, so elements are tracked by whether
+/// their tags open or close.
+fn nesting(events: &[Event], max: usize) -> Option<message::Message> {
+    // Number of open nesting constructs.
+    let mut depth = 0;
+    // Number of open MDX JSX elements.
+    let mut jsx_depth = 0;
+    // Index of the enter of the current MDX JSX tag.
+    let mut jsx_enter = 0;
+    // Whether the current MDX JSX tag is a closing tag.
+    let mut jsx_closing = false;
+    // Whether the current MDX JSX tag is a self-closing tag.
+    let mut jsx_self_closing = false;
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        match event.name {
+            Name::BlockQuote
+            | Name::CriticAddition
+            | Name::CriticComment
+            | Name::CriticDeletion
+            | Name::CriticHighlight
+            | Name::DefinitionDescription
+            | Name::Emphasis
+            | Name::GfmFootnoteDefinition
+            | Name::GfmStrikethrough
+            | Name::GridTableCell
+            | Name::Highlight
+            | Name::Image
+            | Name::InlineFootnote
+            | Name::Insert
+            | Name::Link
+            | Name::ListItem
+            | Name::Strong => {
+                if event.kind == Kind::Enter {
+                    depth += 1;
+
+                    if depth + jsx_depth > max {
+                        return Some(too_deep(max, event.point.to_unist()));
+                    }
+                } else {
+                    depth -= 1;
+                }
+            }
+            Name::MdxJsxFlowTag | Name::MdxJsxTextTag => {
+                if event.kind == Kind::Enter {
+                    jsx_enter = index;
+                    jsx_closing = false;
+                    jsx_self_closing = false;
+                } else if jsx_closing {
+                    jsx_depth = jsx_depth.saturating_sub(1);
+                } else if !jsx_self_closing {
+                    jsx_depth += 1;
+
+                    if depth + jsx_depth > max {
+                        return Some(too_deep(max, events[jsx_enter].point.to_unist()));
+                    }
+                }
+            }
+            Name::MdxJsxTagClosingMarker => jsx_closing = true,
+            Name::MdxJsxTagSelfClosingMarker => jsx_self_closing = true,
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Create a message for a construct that is nested too deep.
+pub fn too_deep(max: usize, point: unist::Point) -> message::Message {
+    message::Message {
+        place: Some(Box::new(message::Place::Point(point))),
+        reason: format!(
+            "Unexpected construct nested deeper than `max_nesting` ({}), expected fewer nested constructs",
+            max
+        ),
+        rule_id: Box::new("max-nesting".into()),
+        source: Box::new("markdown-rs".into()),
+    }
+}
+
+/// Find definitions and GFM footnote definitions whose identifier was
+/// already defined.
+fn duplicates(
+    events: &[Event],
+    bytes: &[u8],
+    tab_size: usize,
+    kind: DuplicateDefinitionKind,
+) -> Vec<message::Message> {
+    let mut definitions: BTreeMap<String, unist::Point> = BTreeMap::new();
+    let mut gfm_footnote_definitions: BTreeMap<String, unist::Point> = BTreeMap::new();
+    // Start points of open (footnote) definitions, with their labels.
+    let mut stack: Vec<(unist::Point, Option<String>)> = vec![];
+    let mut messages = vec![];
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        match event.name {
+            Name::Definition | Name::GfmFootnoteDefinition => {
+                if event.kind == Kind::Enter {
+                    stack.push((event.point.to_unist(), None));
+                } else if let Some((start, Some(label))) = stack.pop() {
+                    let id = normalize_identifier(&label);
+                    let footnote = event.name == Name::GfmFootnoteDefinition;
+                    let map = if footnote {
+                        &mut gfm_footnote_definitions
+                    } else {
+                        &mut definitions
+                    };
+
+                    if let Some(first) = map.get(&id) {
+                        messages.push(duplicate(
+                            &label,
+                            footnote,
+                            first,
+                            unist::Position {
+                                start,
+                                end: event.point.to_unist(),
+                            },
+                            kind,
+                        ));
+                    } else {
+                        map.insert(id, start);
+                    }
+                }
+            }
+            Name::DefinitionLabelString | Name::GfmFootnoteDefinitionLabelString
+                if event.kind == Kind::Exit =>
+            {
+                if let Some(last) = stack.last_mut() {
+                    last.1 = Some(
+                        Slice::from_position(
+                            bytes,
+                            &Position::from_exit_event(events, index),
+                            tab_size,
+                        )
+                        .as_str()
+                        .into(),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    messages.sort_by_key(|message| match message.place.as_deref() {
+        Some(message::Place::Position(position)) => position.start.offset,
+        _ => 0,
+    });
+
+    messages
+}
+
+/// Create a message for a duplicate definition.
+fn duplicate(
+    id: &str,
+    footnote: bool,
+    first: &unist::Point,
+    position: unist::Position,
+    kind: DuplicateDefinitionKind,
+) -> message::Message {
+    let what = if footnote {
+        "footnote definition"
+    } else {
+        "definition"
+    };
+
+    message::Message {
+        place: Some(Box::new(message::Place::Position(position))),
+        reason: format!(
+            "Unexpected duplicate {} `{}`, expected unique identifiers (first defined at {}:{}){}",
+            what,
+            id,
+            first.line,
+            first.column,
+            match kind {
+                DuplicateDefinitionKind::First => ", the first definition is used",
+                DuplicateDefinitionKind::Last => ", the last definition is used",
+                DuplicateDefinitionKind::Error => "",
+            }
+        ),
+        rule_id: Box::new(if footnote {
+            "duplicate-footnote-definition".into()
+        } else {
+            "duplicate-definition".into()
+        }),
+        source: Box::new("markdown-rs".into()),
+    }
+}
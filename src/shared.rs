@@ -0,0 +1,116 @@
+//! Immutable syntax trees that share their nodes.
+//!
+//! A [`Node`][] owns its children, so cloning it clones the whole tree.
+//! A [`SharedNode`][] keeps each node behind an [`Arc`][], so that cloning
+//! is cheap, trees can be sent to and read from several threads, and a
+//! changed tree can reuse the unchanged parts of an earlier tree.
+
+use crate::mdast::Node;
+use alloc::{sync::Arc, vec::Vec};
+use core::mem;
+
+/// Immutable node whose children are shared.
+///
+/// Turn a [`Node`][] into a shared node with `SharedNode::from(node)`, and
+/// back with [`SharedNode::to_node()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{shared::SharedNode, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = SharedNode::from(to_mdast("# a\n\nb", &ParseOptions::default())?);
+/// let copy = tree.clone();
+///
+/// assert!(SharedNode::ptr_eq(&tree, &copy));
+/// assert_eq!(tree.children().len(), 2);
+/// assert_eq!(tree.to_node().to_string(), "ab");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharedNode(Arc<Inner>);
+
+/// Data of a shared node.
+#[derive(Debug, Eq, PartialEq)]
+struct Inner {
+    /// Node, without children.
+    node: Node,
+    /// Children.
+    children: Vec<SharedNode>,
+}
+
+impl SharedNode {
+    /// Get the node, without its children.
+    ///
+    /// The fields of the node (such as `depth` of a heading or `url` of a
+    /// link) are available, but its `children` are always empty: use
+    /// [`SharedNode::children()`][] instead.
+    pub fn node(&self) -> &Node {
+        &self.0.node
+    }
+
+    /// Get the children.
+    ///
+    /// Returns an empty slice for nodes that cannot have children.
+    pub fn children(&self) -> &[SharedNode] {
+        &self.0.children
+    }
+
+    /// Make a new node with the fields of this node and `children`.
+    ///
+    /// The children are shared, not cloned, which makes it cheap to change
+    /// a part of a tree.
+    /// Children are ignored for nodes that cannot have children.
+    #[must_use]
+    pub fn with_children(&self, children: Vec<SharedNode>) -> SharedNode {
+        let children = if self.0.node.children().is_some() {
+            children
+        } else {
+            Vec::new()
+        };
+
+        SharedNode(Arc::new(Inner {
+            node: self.0.node.clone(),
+            children,
+        }))
+    }
+
+    /// Turn this into a [`Node`][], cloning the whole tree.
+    pub fn to_node(&self) -> Node {
+        let mut node = self.0.node.clone();
+
+        if let Some(children) = node.children_mut() {
+            *children = self.0.children.iter().map(SharedNode::to_node).collect();
+        }
+
+        node
+    }
+
+    /// Check if two shared nodes are the same node, instead of two equal
+    /// nodes.
+    pub fn ptr_eq(a: &SharedNode, b: &SharedNode) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl From<Node> for SharedNode {
+    fn from(mut node: Node) -> Self {
+        let children = node
+            .children_mut()
+            .map(mem::take)
+            .unwrap_or_default()
+            .into_iter()
+            .map(SharedNode::from)
+            .collect();
+
+        SharedNode(Arc::new(Inner { node, children }))
+    }
+}
+
+impl From<&SharedNode> for Node {
+    fn from(node: &SharedNode) -> Self {
+        node.to_node()
+    }
+}
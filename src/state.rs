@@ -43,9 +43,28 @@ impl State {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[allow(clippy::enum_variant_names)]
 pub enum Name {
+    AbbreviationDefinitionStart,
+    AbbreviationDefinitionBefore,
+    AbbreviationDefinitionLabelOpen,
+    AbbreviationDefinitionLabelBefore,
+    AbbreviationDefinitionLabelInside,
+    AbbreviationDefinitionLabelAfter,
+    AbbreviationDefinitionMarkerAfter,
+    AbbreviationDefinitionTitleBefore,
+    AbbreviationDefinitionTitle,
+    AbbreviationDefinitionAfter,
+
     AttentionStart,
     AttentionInside,
 
+    AttributesTextStart,
+    AttributesTextAfter,
+    AttributesTextAfterSpaceOrTab,
+    AttributesStart,
+    AttributesValueBefore,
+    AttributesValue,
+    AttributesValueQuoted,
+
     AutolinkStart,
     AutolinkOpen,
     AutolinkSchemeOrEmailAtext,
@@ -75,6 +94,9 @@ pub enum Name {
     CharacterReferenceNumeric,
     CharacterReferenceValue,
 
+    CiteStart,
+    CiteInside,
+
     CodeIndentedStart,
     CodeIndentedAtBreak,
     CodeIndentedAfter,
@@ -88,6 +110,13 @@ pub enum Name {
     ContentDefinitionBefore,
     ContentDefinitionAfter,
 
+    CriticMarkupStart,
+    CriticMarkupOpen,
+    CriticMarkupBefore,
+    CriticMarkupInside,
+    CriticMarkupClose,
+    CriticMarkupCloseInside,
+
     DataStart,
     DataInside,
     DataAtBreak,
@@ -107,6 +136,14 @@ pub enum Name {
     DefinitionTitleAfter,
     DefinitionTitleAfterOptionalWhitespace,
 
+    DefinitionListStart,
+    DefinitionListBefore,
+    DefinitionListMarkerAfter,
+    DefinitionListWhitespaceAfter,
+    DefinitionListContStart,
+    DefinitionListContBlank,
+    DefinitionListContFilled,
+
     DestinationStart,
     DestinationEnclosedBefore,
     DestinationEnclosed,
@@ -122,13 +159,22 @@ pub enum Name {
     DocumentContainerNewBeforeNotBlockQuote,
     DocumentContainerNewBeforeNotList,
     DocumentContainerNewBeforeNotGfmFootnoteDefinition,
+    DocumentContainerNewBeforeNotDefinitionDescription,
     DocumentContainerNewAfter,
     DocumentContainersAfter,
     DocumentFlowInside,
     DocumentFlowEnd,
 
+    EmojiStart,
+    EmojiShortcodeBefore,
+    EmojiShortcode,
+
+    ExtensionStart,
+    ExtensionInside,
+
     FlowStart,
     FlowBeforeGfmTable,
+    FlowBeforeGridTable,
     FlowBeforeCodeIndented,
     FlowBeforeRaw,
     FlowBeforeHtml,
@@ -137,6 +183,7 @@ pub enum Name {
     FlowBeforeHeadingAtx,
     FlowBeforeHeadingSetext,
     FlowBeforeThematicBreak,
+    FlowBeforeAbbreviationDefinition,
     FlowAfter,
     FlowBlankLineBefore,
     FlowBlankLineAfter,
@@ -196,6 +243,13 @@ pub enum Name {
     GfmTaskListItemCheckAfter,
     GfmTaskListItemCheckAfterSpaceOrTab,
 
+    GridTableStart,
+    GridTableLineStart,
+    GridTableLineInside,
+    GridTableContinuationStart,
+    GridTableContinuationAfter,
+    GridTableAfter,
+
     GfmTableStart,
     GfmTableHeadRowBefore,
     GfmTableHeadRowStart,
@@ -216,6 +270,12 @@ pub enum Name {
     GfmTableBodyRowBreak,
     GfmTableBodyRowData,
     GfmTableBodyRowEscape,
+    GfmTableCaptionStart,
+    GfmTableCaptionOpen,
+    GfmTableCaptionMarker,
+    GfmTableCaptionTextBefore,
+    GfmTableCaptionText,
+    GfmTableCaptionAfter,
 
     HardBreakEscapeStart,
     HardBreakEscapeAfter,
@@ -324,6 +384,9 @@ pub enum Name {
     LabelStartImageOpen,
     LabelStartImageAfter,
 
+    LabelStartInlineFootnoteStart,
+    LabelStartInlineFootnoteOpen,
+
     LabelStartLinkStart,
 
     ListItemStart,
@@ -404,6 +467,12 @@ pub enum Name {
     NonLazyContinuationStart,
     NonLazyContinuationAfter,
 
+    ObsidianBlockIdStart,
+    ObsidianBlockIdValueBefore,
+    ObsidianBlockIdValue,
+    ObsidianBlockIdAfter,
+    ObsidianBlockIdAfterSpaceOrTab,
+
     ParagraphStart,
     ParagraphLineStart,
     ParagraphInside,
@@ -411,10 +480,18 @@ pub enum Name {
     RawFlowStart,
     RawFlowBeforeSequenceOpen,
     RawFlowSequenceOpen,
+    RawFlowLatexAfterSequenceOpen,
     RawFlowInfoBefore,
+    RawFlowInfoOpen,
     RawFlowInfo,
     RawFlowMetaBefore,
+    RawFlowMetaOpen,
     RawFlowMeta,
+    RawFlowMetaSpaceOrTab,
+    RawFlowMetaAttributes,
+    RawFlowAttributesBefore,
+    RawFlowAttributes,
+    RawFlowAttributesAfter,
     RawFlowAtNonLazyBreak,
     RawFlowCloseStart,
     RawFlowBeforeSequenceClose,
@@ -431,6 +508,11 @@ pub enum Name {
     RawTextBetween,
     RawTextData,
     RawTextSequenceClose,
+    RawTextAfter,
+
+    SmartPunctuationStart,
+    SmartPunctuationDashes,
+    SmartPunctuationEllipsis,
 
     SpaceOrTabStart,
     SpaceOrTabInside,
@@ -448,10 +530,18 @@ pub enum Name {
 
     TextStart,
     TextBefore,
+    TextBeforeConstruct,
     TextBeforeHtml,
     TextBeforeMdxJsx,
+    TextBeforeGfmAutolinkLiteralProtocol,
+    TextBeforeCharacterEscape,
     TextBeforeHardBreakEscape,
+    TextBeforeCite,
+    TextBeforeGfmLabelStartFootnote,
     TextBeforeLabelStartLink,
+    TextBeforeLabelStartInlineFootnote,
+    TextBeforeAttributes,
+    TextBeforeMdxExpression,
     TextBeforeData,
 
     ThematicBreakStart,
@@ -466,15 +556,47 @@ pub enum Name {
     TitleEscape,
     TitleInside,
     TitleNok,
+
+    WikiLinkStart,
+    WikiLinkOpen,
+    WikiLinkTargetBefore,
+    WikiLinkTarget,
+    WikiLinkTargetAfter,
+    WikiLinkAnchorBefore,
+    WikiLinkAnchor,
+    WikiLinkAnchorAfter,
+    WikiLinkLabelBefore,
+    WikiLinkLabel,
+    WikiLinkEnd,
+    WikiLinkEndClose,
 }
 
 #[allow(clippy::too_many_lines)]
 /// Call the corresponding state for a state name.
 pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
     let func = match name {
+        Name::AbbreviationDefinitionStart => construct::abbreviation_definition::start,
+        Name::AbbreviationDefinitionBefore => construct::abbreviation_definition::before,
+        Name::AbbreviationDefinitionLabelOpen => construct::abbreviation_definition::label_open,
+        Name::AbbreviationDefinitionLabelBefore => construct::abbreviation_definition::label_before,
+        Name::AbbreviationDefinitionLabelInside => construct::abbreviation_definition::label_inside,
+        Name::AbbreviationDefinitionLabelAfter => construct::abbreviation_definition::label_after,
+        Name::AbbreviationDefinitionMarkerAfter => construct::abbreviation_definition::marker_after,
+        Name::AbbreviationDefinitionTitleBefore => construct::abbreviation_definition::title_before,
+        Name::AbbreviationDefinitionTitle => construct::abbreviation_definition::title,
+        Name::AbbreviationDefinitionAfter => construct::abbreviation_definition::after,
+
         Name::AttentionStart => construct::attention::start,
         Name::AttentionInside => construct::attention::inside,
 
+        Name::AttributesTextStart => construct::attributes::text_start,
+        Name::AttributesTextAfter => construct::attributes::text_after,
+        Name::AttributesTextAfterSpaceOrTab => construct::attributes::text_after_space_or_tab,
+        Name::AttributesStart => construct::attributes::start,
+        Name::AttributesValueBefore => construct::attributes::value_before,
+        Name::AttributesValue => construct::attributes::value,
+        Name::AttributesValueQuoted => construct::attributes::value_quoted,
+
         Name::AutolinkStart => construct::autolink::start,
         Name::AutolinkOpen => construct::autolink::open,
         Name::AutolinkSchemeOrEmailAtext => construct::autolink::scheme_or_email_atext,
@@ -504,6 +626,9 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::CharacterReferenceNumeric => construct::character_reference::numeric,
         Name::CharacterReferenceValue => construct::character_reference::value,
 
+        Name::CiteStart => construct::cite::start,
+        Name::CiteInside => construct::cite::inside,
+
         Name::CodeIndentedStart => construct::code_indented::start,
         Name::CodeIndentedAtBreak => construct::code_indented::at_break,
         Name::CodeIndentedAfter => construct::code_indented::after,
@@ -517,6 +642,13 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::ContentDefinitionBefore => construct::content::definition_before,
         Name::ContentDefinitionAfter => construct::content::definition_after,
 
+        Name::CriticMarkupStart => construct::critic_markup::start,
+        Name::CriticMarkupOpen => construct::critic_markup::open,
+        Name::CriticMarkupBefore => construct::critic_markup::before,
+        Name::CriticMarkupInside => construct::critic_markup::inside,
+        Name::CriticMarkupClose => construct::critic_markup::close,
+        Name::CriticMarkupCloseInside => construct::critic_markup::close_inside,
+
         Name::DataStart => construct::partial_data::start,
         Name::DataInside => construct::partial_data::inside,
         Name::DataAtBreak => construct::partial_data::at_break,
@@ -538,6 +670,14 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
             construct::definition::title_after_optional_whitespace
         }
 
+        Name::DefinitionListStart => construct::definition_list::start,
+        Name::DefinitionListBefore => construct::definition_list::before,
+        Name::DefinitionListMarkerAfter => construct::definition_list::marker_after,
+        Name::DefinitionListWhitespaceAfter => construct::definition_list::whitespace_after,
+        Name::DefinitionListContStart => construct::definition_list::cont_start,
+        Name::DefinitionListContBlank => construct::definition_list::cont_blank,
+        Name::DefinitionListContFilled => construct::definition_list::cont_filled,
+
         Name::DestinationStart => construct::partial_destination::start,
         Name::DestinationEnclosedBefore => construct::partial_destination::enclosed_before,
         Name::DestinationEnclosed => construct::partial_destination::enclosed,
@@ -559,13 +699,24 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::DocumentContainerNewBeforeNotGfmFootnoteDefinition => {
             construct::document::container_new_before_not_footnote_definition
         }
+        Name::DocumentContainerNewBeforeNotDefinitionDescription => {
+            construct::document::container_new_before_not_definition_description
+        }
         Name::DocumentContainerNewAfter => construct::document::container_new_after,
         Name::DocumentContainersAfter => construct::document::containers_after,
         Name::DocumentFlowEnd => construct::document::flow_end,
         Name::DocumentFlowInside => construct::document::flow_inside,
 
+        Name::EmojiStart => construct::emoji::start,
+        Name::EmojiShortcodeBefore => construct::emoji::shortcode_before,
+        Name::EmojiShortcode => construct::emoji::shortcode,
+
+        Name::ExtensionStart => construct::extension::start,
+        Name::ExtensionInside => construct::extension::inside,
+
         Name::FlowStart => construct::flow::start,
         Name::FlowBeforeGfmTable => construct::flow::before_gfm_table,
+        Name::FlowBeforeGridTable => construct::flow::before_grid_table,
         Name::FlowBeforeCodeIndented => construct::flow::before_code_indented,
         Name::FlowBeforeRaw => construct::flow::before_raw,
         Name::FlowBeforeHtml => construct::flow::before_html,
@@ -574,6 +725,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::FlowBeforeHeadingAtx => construct::flow::before_heading_atx,
         Name::FlowBeforeHeadingSetext => construct::flow::before_heading_setext,
         Name::FlowBeforeThematicBreak => construct::flow::before_thematic_break,
+        Name::FlowBeforeAbbreviationDefinition => construct::flow::before_abbreviation_definition,
         Name::FlowAfter => construct::flow::after,
         Name::FlowBlankLineBefore => construct::flow::blank_line_before,
         Name::FlowBlankLineAfter => construct::flow::blank_line_after,
@@ -666,6 +818,12 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::GfmTableBodyRowBreak => construct::gfm_table::body_row_break,
         Name::GfmTableBodyRowData => construct::gfm_table::body_row_data,
         Name::GfmTableBodyRowEscape => construct::gfm_table::body_row_escape,
+        Name::GfmTableCaptionStart => construct::gfm_table::caption_start,
+        Name::GfmTableCaptionOpen => construct::gfm_table::caption_open,
+        Name::GfmTableCaptionMarker => construct::gfm_table::caption_marker,
+        Name::GfmTableCaptionTextBefore => construct::gfm_table::caption_text_before,
+        Name::GfmTableCaptionText => construct::gfm_table::caption_text,
+        Name::GfmTableCaptionAfter => construct::gfm_table::caption_after,
 
         Name::GfmTaskListItemCheckStart => construct::gfm_task_list_item_check::start,
         Name::GfmTaskListItemCheckInside => construct::gfm_task_list_item_check::inside,
@@ -675,6 +833,13 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
             construct::gfm_task_list_item_check::after_space_or_tab
         }
 
+        Name::GridTableStart => construct::grid_table::start,
+        Name::GridTableLineStart => construct::grid_table::line_start,
+        Name::GridTableLineInside => construct::grid_table::line_inside,
+        Name::GridTableContinuationStart => construct::grid_table::continuation_start,
+        Name::GridTableContinuationAfter => construct::grid_table::continuation_after,
+        Name::GridTableAfter => construct::grid_table::after,
+
         Name::HardBreakEscapeStart => construct::hard_break_escape::start,
         Name::HardBreakEscapeAfter => construct::hard_break_escape::after,
 
@@ -809,6 +974,8 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::LabelStartImageStart => construct::label_start_image::start,
         Name::LabelStartImageOpen => construct::label_start_image::open,
         Name::LabelStartImageAfter => construct::label_start_image::after,
+        Name::LabelStartInlineFootnoteStart => construct::label_start_inline_footnote::start,
+        Name::LabelStartInlineFootnoteOpen => construct::label_start_inline_footnote::open,
         Name::LabelStartLinkStart => construct::label_start_link::start,
 
         Name::ListItemStart => construct::list_item::start,
@@ -903,6 +1070,12 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::NonLazyContinuationStart => construct::partial_non_lazy_continuation::start,
         Name::NonLazyContinuationAfter => construct::partial_non_lazy_continuation::after,
 
+        Name::ObsidianBlockIdStart => construct::obsidian_block_id::start,
+        Name::ObsidianBlockIdValueBefore => construct::obsidian_block_id::value_before,
+        Name::ObsidianBlockIdValue => construct::obsidian_block_id::value,
+        Name::ObsidianBlockIdAfter => construct::obsidian_block_id::after,
+        Name::ObsidianBlockIdAfterSpaceOrTab => construct::obsidian_block_id::after_space_or_tab,
+
         Name::ParagraphStart => construct::paragraph::start,
         Name::ParagraphLineStart => construct::paragraph::line_start,
         Name::ParagraphInside => construct::paragraph::inside,
@@ -910,10 +1083,18 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::RawFlowStart => construct::raw_flow::start,
         Name::RawFlowBeforeSequenceOpen => construct::raw_flow::before_sequence_open,
         Name::RawFlowSequenceOpen => construct::raw_flow::sequence_open,
+        Name::RawFlowLatexAfterSequenceOpen => construct::raw_flow::latex_after_sequence_open,
         Name::RawFlowInfoBefore => construct::raw_flow::info_before,
+        Name::RawFlowInfoOpen => construct::raw_flow::info_open,
         Name::RawFlowInfo => construct::raw_flow::info,
         Name::RawFlowMetaBefore => construct::raw_flow::meta_before,
+        Name::RawFlowMetaOpen => construct::raw_flow::meta_open,
         Name::RawFlowMeta => construct::raw_flow::meta,
+        Name::RawFlowMetaSpaceOrTab => construct::raw_flow::meta_space_or_tab,
+        Name::RawFlowMetaAttributes => construct::raw_flow::meta_attributes,
+        Name::RawFlowAttributesBefore => construct::raw_flow::attributes_before,
+        Name::RawFlowAttributes => construct::raw_flow::attributes,
+        Name::RawFlowAttributesAfter => construct::raw_flow::attributes_after,
         Name::RawFlowAtNonLazyBreak => construct::raw_flow::at_non_lazy_break,
         Name::RawFlowCloseStart => construct::raw_flow::close_start,
         Name::RawFlowBeforeSequenceClose => construct::raw_flow::before_sequence_close,
@@ -930,6 +1111,11 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::RawTextBetween => construct::raw_text::between,
         Name::RawTextData => construct::raw_text::data,
         Name::RawTextSequenceClose => construct::raw_text::sequence_close,
+        Name::RawTextAfter => construct::raw_text::after,
+
+        Name::SmartPunctuationStart => construct::smart_punctuation::start,
+        Name::SmartPunctuationDashes => construct::smart_punctuation::dashes,
+        Name::SmartPunctuationEllipsis => construct::smart_punctuation::ellipsis,
 
         Name::SpaceOrTabStart => construct::partial_space_or_tab::start,
         Name::SpaceOrTabInside => construct::partial_space_or_tab::inside,
@@ -947,10 +1133,22 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
 
         Name::TextStart => construct::text::start,
         Name::TextBefore => construct::text::before,
+        Name::TextBeforeConstruct => construct::text::before_construct,
         Name::TextBeforeHtml => construct::text::before_html,
         Name::TextBeforeMdxJsx => construct::text::before_mdx_jsx,
+        Name::TextBeforeGfmAutolinkLiteralProtocol => {
+            construct::text::before_gfm_autolink_literal_protocol
+        }
+        Name::TextBeforeCharacterEscape => construct::text::before_character_escape,
         Name::TextBeforeHardBreakEscape => construct::text::before_hard_break_escape,
+        Name::TextBeforeCite => construct::text::before_cite,
+        Name::TextBeforeGfmLabelStartFootnote => construct::text::before_gfm_label_start_footnote,
         Name::TextBeforeLabelStartLink => construct::text::before_label_start_link,
+        Name::TextBeforeLabelStartInlineFootnote => {
+            construct::text::before_label_start_inline_footnote
+        }
+        Name::TextBeforeAttributes => construct::text::before_attributes,
+        Name::TextBeforeMdxExpression => construct::text::before_mdx_expression,
         Name::TextBeforeData => construct::text::before_data,
 
         Name::ThematicBreakStart => construct::thematic_break::start,
@@ -965,6 +1163,19 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::TitleEscape => construct::partial_title::escape,
         Name::TitleInside => construct::partial_title::inside,
         Name::TitleNok => construct::partial_title::nok,
+
+        Name::WikiLinkStart => construct::wiki_link::start,
+        Name::WikiLinkOpen => construct::wiki_link::open,
+        Name::WikiLinkTargetBefore => construct::wiki_link::target_before,
+        Name::WikiLinkTarget => construct::wiki_link::target,
+        Name::WikiLinkTargetAfter => construct::wiki_link::target_after,
+        Name::WikiLinkAnchorBefore => construct::wiki_link::anchor_before,
+        Name::WikiLinkAnchor => construct::wiki_link::anchor,
+        Name::WikiLinkAnchorAfter => construct::wiki_link::anchor_after,
+        Name::WikiLinkLabelBefore => construct::wiki_link::label_before,
+        Name::WikiLinkLabel => construct::wiki_link::label,
+        Name::WikiLinkEnd => construct::wiki_link::end,
+        Name::WikiLinkEndClose => construct::wiki_link::end_close,
     };
 
     func(tokenizer)
@@ -143,6 +143,19 @@ pub fn subtokenize(
                     }
                 }
 
+                // Check if this is the text of a heading.
+                // Used for attributes.
+                if tokenizer.parse_state.options.constructs.attributes
+                    && index > 0
+                    && events[index - 1].kind == Kind::Enter
+                    && matches!(
+                        events[index - 1].name,
+                        Name::HeadingAtxText | Name::HeadingSetextText
+                    )
+                {
+                    tokenizer.tokenize_state.text_in_heading = true;
+                }
+
                 // Loop through links to pass them in order to the subtokenizer.
                 while let Some(index) = link_index {
                     let enter = &events[index];
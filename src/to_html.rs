@@ -1,19 +1,33 @@
 //! Turn events into a string of HTML.
+use crate::construct::{
+    abbreviation_definition, emoji, gfm_alert, label_start_inline_footnote, smart_punctuation,
+};
 use crate::event::{Event, Kind, Name};
-use crate::mdast::AlignKind;
+use crate::mdast::{AlertKind, AlignKind, Attributes};
 use crate::util::{
+    attributes::parse as parse_attributes,
+    char,
     character_reference::decode as decode_character_reference,
+    cite::parse as parse_cite,
+    code_meta::CodeMeta,
     constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
-    encode::encode,
+    encode::encode_with_options,
+    extension::Extension,
     gfm_tagfilter::gfm_tagfilter,
-    infer::{gfm_table_align, list_loose},
+    infer::{
+        definition_list_loose, gfm_table_align, grid_table_align, grid_table_cell_tight, list_loose,
+    },
     normalize_identifier::normalize_identifier,
     sanitize_uri::{sanitize, sanitize_with_protocols},
     skip,
     slice::{Position, Slice},
 };
-use crate::{CompileOptions, LineEnding};
+use crate::{
+    CompileOptions, DuplicateDefinitionKind, EmojiLookup, GfmFootnotePlacementKind, LineEnding,
+    ParseOptions,
+};
 use alloc::{
+    collections::BTreeMap,
     format,
     string::{String, ToString},
     vec,
@@ -21,6 +35,17 @@ use alloc::{
 };
 use core::str;
 
+/// Grid table.
+#[derive(Debug)]
+struct GridTable {
+    /// Alignment of each column.
+    align: Vec<AlignKind>,
+    /// Current column.
+    column: usize,
+    /// Whether we are in the head.
+    in_head: bool,
+}
+
 /// Link, image, or footnote call.
 /// Resource or reference.
 /// Reused for temporary definitions as well, in the first pass.
@@ -57,10 +82,8 @@ struct Media {
 }
 
 /// Representation of a definition.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Definition {
-    /// Identifier.
-    id: String,
     /// The destination (url).
     ///
     /// Interpreted string content.
@@ -73,7 +96,6 @@ struct Definition {
 
 /// Context used to compile markdown.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug)]
 struct CompileContext<'a> {
     // Static info.
     /// List of events.
@@ -88,10 +110,23 @@ struct CompileContext<'a> {
     heading_atx_rank: Option<usize>,
     /// Buffer of heading (setext) text.
     heading_setext_buffer: Option<String>,
+    /// Block identifier (Obsidian) of heading.
+    heading_block_id: Option<String>,
+    /// Whether the current paragraph is a block identifier (Obsidian) of the
+    /// block before it, which is not output.
+    paragraph_block_id_after: bool,
+    /// Attributes of heading.
+    heading_attributes: Option<Attributes>,
     /// Whether raw (flow) (code (fenced), math (flow)) or code (indented) contains data.
     raw_flow_seen_data: Option<bool>,
     /// Number of raw (flow) fences.
     raw_flow_fences_count: Option<usize>,
+    /// Meta of code (fenced), when it highlights lines.
+    raw_flow_meta: Option<CodeMeta>,
+    /// Attributes of code (fenced).
+    raw_flow_attributes: Option<Attributes>,
+    /// Current line in raw (flow).
+    raw_flow_line: usize,
     /// Whether we are in code (text).
     raw_text_inside: bool,
     /// Whether we are in image text.
@@ -104,26 +139,57 @@ struct CompileContext<'a> {
     media_stack: Vec<Media>,
     /// Stack of containers.
     tight_stack: Vec<bool>,
-    /// List of definitions.
-    definitions: Vec<Definition>,
-    /// List of definitions.
-    gfm_footnote_definitions: Vec<(String, String)>,
+    /// Stack of block quotes, with whether they are GFM alerts.
+    block_quote_alert_stack: Vec<bool>,
+    /// Titles of abbreviation definitions, by label.
+    abbreviations: BTreeMap<String, Option<String>>,
+    /// Definitions, by identifier.
+    definitions: BTreeMap<String, Definition>,
+    /// GFM footnote definitions, by identifier.
+    gfm_footnote_definitions: BTreeMap<String, String>,
+    /// GFM footnote calls, in order, with how often they are called.
     gfm_footnote_definition_calls: Vec<(String, usize)>,
+    /// Indices into `gfm_footnote_definition_calls`, by identifier.
+    gfm_footnote_definition_call_indices: BTreeMap<String, usize>,
     gfm_footnote_definition_stack: Vec<(usize, usize)>,
+    /// Index in the HTML and index of the first call of each GFM footnote
+    /// section that ended before the current top-level section.
+    gfm_footnote_sections: Vec<(usize, usize)>,
+    /// Index of the first GFM footnote call in the current top-level
+    /// section.
+    gfm_footnote_section_start: usize,
+    /// Identifiers of inline footnotes, by the index of their start.
+    inline_footnotes: BTreeMap<usize, String>,
     /// Whether we are in a GFM table head.
     gfm_table_in_head: bool,
     /// Current GFM table alignment.
     gfm_table_align: Option<Vec<AlignKind>>,
     /// Current GFM table column.
     gfm_table_column: usize,
+    /// Stack of grid tables.
+    grid_table_stack: Vec<GridTable>,
     // Fields used to influance the current compilation.
     /// Ignore the next line ending.
     slurp_one_line_ending: bool,
+    /// Whether HTML that comes before this context, compiled by another
+    /// context, ends in something other than a line ending.
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    line_ending_before: bool,
     /// Whether to encode HTML.
     encode_html: bool,
     // Configuration
     /// Line ending to use.
     line_ending_default: LineEnding,
+    /// How to handle duplicate definitions.
+    duplicate_definitions: DuplicateDefinitionKind,
+    /// Function to turn the shortcodes of emoji into emoji.
+    emoji_lookup: Option<&'a EmojiLookup>,
+    /// Number of columns between tab stops.
+    tab_size: usize,
+    /// Whether block identifiers (Obsidian) are parsed.
+    obsidian_block_id: bool,
+    /// Constructs defined outside of `markdown-rs`.
+    extensions: &'a [Extension],
     // Intermediate results.
     /// Stack of buffers.
     buffers: Vec<String>,
@@ -138,33 +204,55 @@ impl<'a> CompileContext<'a> {
         bytes: &'a [u8],
         options: &'a CompileOptions,
         line_ending: LineEnding,
+        duplicate_definitions: DuplicateDefinitionKind,
+        emoji_lookup: Option<&'a EmojiLookup>,
+        tab_size: usize,
     ) -> CompileContext<'a> {
         CompileContext {
             events,
             bytes,
             heading_atx_rank: None,
             heading_setext_buffer: None,
+            heading_block_id: None,
+            paragraph_block_id_after: false,
+            heading_attributes: None,
             raw_flow_seen_data: None,
             raw_flow_fences_count: None,
+            raw_flow_meta: None,
+            raw_flow_attributes: None,
+            raw_flow_line: 1,
             raw_text_inside: false,
             character_reference_marker: None,
             list_expect_first_marker: None,
             media_stack: vec![],
-            definitions: vec![],
-            gfm_footnote_definitions: vec![],
+            abbreviations: BTreeMap::new(),
+            definitions: BTreeMap::new(),
+            gfm_footnote_definitions: BTreeMap::new(),
             gfm_footnote_definition_calls: vec![],
+            gfm_footnote_definition_call_indices: BTreeMap::new(),
             gfm_footnote_definition_stack: vec![],
+            gfm_footnote_sections: vec![],
+            gfm_footnote_section_start: 0,
+            inline_footnotes: BTreeMap::new(),
             gfm_table_in_head: false,
             gfm_table_align: None,
             gfm_table_column: 0,
+            grid_table_stack: vec![],
             tight_stack: vec![],
+            block_quote_alert_stack: vec![],
             slurp_one_line_ending: false,
+            line_ending_before: false,
             image_alt_inside: false,
             encode_html: true,
             line_ending_default: line_ending,
             buffers: vec![String::new()],
             index: 0,
             options,
+            duplicate_definitions,
+            emoji_lookup,
+            tab_size,
+            obsidian_block_id: false,
+            extensions: &[],
         }
     }
 
@@ -195,16 +283,29 @@ impl<'a> CompileContext<'a> {
     fn line_ending_if_needed(&mut self) {
         let last_buf_opt = self.buffers.last();
         let last_buf = last_buf_opt.expect("at least one buffer should exist");
-        let last_byte = last_buf.as_bytes().last();
+        let needed = match last_buf.as_bytes().last() {
+            None => self.buffers.len() == 1 && self.line_ending_before,
+            Some(b'\n' | b'\r') => false,
+            Some(_) => true,
+        };
 
-        if !matches!(last_byte, None | Some(b'\n' | b'\r')) {
+        if needed {
             self.line_ending();
         }
     }
 }
 
 /// Turn events and bytes into a string of HTML.
-pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> String {
+pub fn compile(
+    events: &[Event],
+    bytes: &[u8],
+    options: &CompileOptions,
+    parse_options: &ParseOptions,
+) -> String {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("to_html", events = events.len()).entered();
+
+    let tab_size = parse_options.tab_size;
     let mut index = 0;
     let mut line_ending_inferred = None;
 
@@ -216,7 +317,8 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         if event.kind == Kind::Exit
             && (event.name == Name::BlankLineEnding || event.name == Name::LineEnding)
         {
-            let slice = Slice::from_position(bytes, &Position::from_exit_event(events, index));
+            let slice =
+                Slice::from_position(bytes, &Position::from_exit_event(events, index), tab_size);
             line_ending_inferred = Some(slice.as_str().parse().unwrap());
             break;
         }
@@ -228,7 +330,19 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
     let line_ending_default =
         line_ending_inferred.unwrap_or_else(|| options.default_line_ending.clone());
 
-    let mut context = CompileContext::new(events, bytes, options, line_ending_default);
+    let mut context = CompileContext::new(
+        events,
+        bytes,
+        options,
+        line_ending_default,
+        parse_options.duplicate_definitions,
+        parse_options.emoji_lookup.as_deref(),
+        tab_size,
+    );
+    context.obsidian_block_id = parse_options.constructs.obsidian_block_id;
+    context.extensions = &parse_options.extensions;
+    context.abbreviations = abbreviation_definition::titles(events, bytes, tab_size);
+    context.inline_footnotes = label_start_inline_footnote::identifiers(events, bytes, tab_size);
     let mut definition_indices = vec![];
     let mut index = 0;
     let mut definition_inside = false;
@@ -265,14 +379,78 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         index += 1;
     }
 
-    let mut index = 0;
-    let jump_default = (events.len(), events.len());
-    let mut definition_index = 0;
+    // Footnote sections depend on where calls are, so they are compiled in
+    // one go.
+    #[cfg(feature = "rayon")]
+    let done = options.gfm_footnote_placement == GfmFootnotePlacementKind::Document
+        && compile_parallel(&mut context, &definition_indices);
+    #[cfg(not(feature = "rayon"))]
+    let done = false;
+
+    if !done {
+        handle_range(&mut context, &definition_indices, 0, events.len());
+    }
+
+    let start = context.gfm_footnote_section_start;
+    let end = context.gfm_footnote_definition_calls.len();
+
+    // No section to generate.
+    if end > start {
+        generate_footnote_section(&mut context, start, end);
+    }
+
+    // Place the earlier sections, from the last one, so that the indices of
+    // the ones before it stay valid.
+    let mut section_index = context.gfm_footnote_sections.len();
+    while section_index > 0 {
+        section_index -= 1;
+        let (offset, start) = context.gfm_footnote_sections[section_index];
+        let end = context
+            .gfm_footnote_sections
+            .get(section_index + 1)
+            .map_or(context.gfm_footnote_section_start, |d| d.1);
+        context.buffer();
+        generate_footnote_section(&mut context, start, end);
+        let mut value = context.resume();
+        let eol = context.line_ending_default.as_str();
+        let html = context
+            .buffers
+            .first_mut()
+            .expect("expected 1 final buffer");
+
+        // The HTML after the section starts with a line ending if the HTML
+        // before it does not end in one.
+        if !matches!(html.as_bytes()[..offset].last(), None | Some(b'\n' | b'\r')) {
+            value.truncate(value.len() - eol.len());
+            value.insert_str(0, eol);
+        }
+
+        html.insert_str(offset, &value);
+    }
+
+    debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
+    context
+        .buffers
+        .first()
+        .expect("expected 1 final buffer")
+        .into()
+}
+
+/// Handle the events from `start` to `end`, jumping over definitions.
+fn handle_range(
+    context: &mut CompileContext,
+    definition_indices: &[(usize, usize)],
+    start: usize,
+    end: usize,
+) {
+    let mut index = start;
+    let jump_default = (end, end);
+    let mut definition_index = definition_indices.partition_point(|jump| jump.0 < start);
     let mut jump = definition_indices
         .get(definition_index)
         .unwrap_or(&jump_default);
 
-    while index < events.len() {
+    while index < end {
         if index == jump.0 {
             index = jump.1 + 1;
             definition_index += 1;
@@ -280,22 +458,195 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
                 .get(definition_index)
                 .unwrap_or(&jump_default);
         } else {
-            handle(&mut context, index);
+            handle(context, index);
             index += 1;
         }
     }
+}
 
-    // No section to generate.
-    if !context.gfm_footnote_definition_calls.is_empty() {
-        generate_footnote_section(&mut context);
+/// Minimum number of events in a chunk that is compiled on its own.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 8192;
+
+/// State that one chunk passes to the next.
+#[cfg(feature = "rayon")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Carry {
+    /// Whether the HTML so far ends in something other than a line ending.
+    line_ending_before: bool,
+    /// Whether the next line ending is ignored.
+    slurp_one_line_ending: bool,
+}
+
+/// Result of compiling a chunk.
+#[cfg(feature = "rayon")]
+struct Chunk {
+    /// HTML.
+    html: String,
+    /// GFM footnote definitions found in the chunk.
+    gfm_footnote_definitions: BTreeMap<String, String>,
+    /// State for the next chunk.
+    slurp_one_line_ending: bool,
+}
+
+/// Compile the events in `context` in chunks of top-level constructs, on
+/// several threads.
+///
+/// Definitions must already be handled.
+/// Each chunk gets the footnote calls before it, found in a quick first
+/// pass, so that footnotes are numbered as if compiled in one go.
+/// Chunks are compiled assuming that the chunk before them ends in a line
+/// ending and does not slurp one: the few chunks where that is not the
+/// case are compiled again afterwards.
+///
+/// Returns whether the document was compiled, which is not the case when
+/// it is too small to split.
+#[cfg(feature = "rayon")]
+fn compile_parallel(context: &mut CompileContext, definition_indices: &[(usize, usize)]) -> bool {
+    use rayon::prelude::*;
+
+    let events = context.events;
+    let bytes = context.bytes;
+    let options = context.options;
+    let line_ending = &context.line_ending_default;
+    let abbreviations = &context.abbreviations;
+    let definitions = &context.definitions;
+    let inline_footnotes = &context.inline_footnotes;
+    let mut ranges = vec![];
+    let mut start = 0;
+    let mut depth = 0;
+
+    for (index, event) in events.iter().enumerate() {
+        if event.kind == Kind::Enter {
+            if depth == 0 && index - start >= PARALLEL_CHUNK_SIZE {
+                ranges.push((start, index));
+                start = index;
+            }
+
+            depth += 1;
+        } else {
+            depth -= 1;
+        }
     }
 
-    debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
-    context
-        .buffers
-        .first()
-        .expect("expected 1 final buffer")
-        .into()
+    if ranges.is_empty() {
+        return false;
+    }
+
+    ranges.push((start, events.len()));
+
+    // Find the footnote calls before each chunk.
+    let mut calls_before = vec![];
+    let mut calls: Vec<(String, usize)> = vec![];
+    let mut call_indices: BTreeMap<String, usize> = BTreeMap::new();
+    let mut label = None;
+
+    for &(start, end) in &ranges {
+        calls_before.push(calls.clone());
+
+        for index in start..end {
+            if events[index].kind == Kind::Enter && events[index].name == Name::InlineFootnote {
+                let id = inline_footnotes[&events[index].point.index].clone();
+                call_indices.insert(id.clone(), calls.len());
+                calls.push((id, 1));
+            } else if events[index].kind == Kind::Exit {
+                if events[index].name == Name::LabelText {
+                    label = Some(Position::from_exit_event(events, index).to_indices());
+                } else if events[index].name == Name::GfmFootnoteCall {
+                    let indices = label.expect("expected label in footnote call");
+                    let id = normalize_identifier(
+                        Slice::from_indices(bytes, indices.0, indices.1).as_str(),
+                    );
+
+                    if let Some(&call_index) = call_indices.get(&id) {
+                        calls[call_index].1 += 1;
+                    } else {
+                        call_indices.insert(id.clone(), calls.len());
+                        calls.push((id, 1));
+                    }
+                }
+            }
+        }
+    }
+
+    let compile_chunk = |index: usize, carry: Carry| {
+        let (start, end) = ranges[index];
+        let mut chunk = CompileContext::new(
+            events,
+            bytes,
+            options,
+            line_ending.clone(),
+            context.duplicate_definitions,
+            context.emoji_lookup,
+            context.tab_size,
+        );
+        chunk.extensions = context.extensions;
+        chunk.abbreviations.clone_from(abbreviations);
+        chunk.definitions.clone_from(definitions);
+        chunk.inline_footnotes.clone_from(inline_footnotes);
+        chunk
+            .gfm_footnote_definition_calls
+            .clone_from(&calls_before[index]);
+        chunk.gfm_footnote_definition_call_indices = calls_before[index]
+            .iter()
+            .enumerate()
+            .map(|(index, call)| (call.0.clone(), index))
+            .collect();
+        chunk.line_ending_before = carry.line_ending_before;
+        chunk.slurp_one_line_ending = carry.slurp_one_line_ending;
+        handle_range(&mut chunk, definition_indices, start, end);
+        debug_assert_eq!(chunk.buffers.len(), 1, "expected 1 final buffer");
+
+        Chunk {
+            html: chunk.resume(),
+            gfm_footnote_definitions: chunk.gfm_footnote_definitions,
+            slurp_one_line_ending: chunk.slurp_one_line_ending,
+        }
+    };
+
+    let assumed = Carry {
+        line_ending_before: false,
+        slurp_one_line_ending: false,
+    };
+    let chunks = (0..ranges.len())
+        .into_par_iter()
+        .map(|index| compile_chunk(index, assumed))
+        .collect::<Vec<_>>();
+    let mut carry = Carry {
+        line_ending_before: false,
+        slurp_one_line_ending: context.slurp_one_line_ending,
+    };
+    let mut html = String::new();
+    let mut gfm_footnote_definitions = BTreeMap::new();
+
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let chunk = if carry == assumed {
+            chunk
+        } else {
+            compile_chunk(index, carry)
+        };
+
+        html.push_str(&chunk.html);
+        for (id, value) in chunk.gfm_footnote_definitions {
+            define(
+                &mut gfm_footnote_definitions,
+                id,
+                value,
+                context.duplicate_definitions,
+            );
+        }
+        carry = Carry {
+            line_ending_before: !matches!(html.as_bytes().last(), None | Some(b'\n' | b'\r')),
+            slurp_one_line_ending: chunk.slurp_one_line_ending,
+        };
+    }
+
+    context.push(&html);
+    context.gfm_footnote_definitions = gfm_footnote_definitions;
+    context.gfm_footnote_definition_calls = calls;
+    context.gfm_footnote_definition_call_indices = call_indices;
+    context.slurp_one_line_ending = carry.slurp_one_line_ending;
+    true
 }
 
 /// Handle the event at `index`.
@@ -329,12 +680,20 @@ fn enter(context: &mut CompileContext) {
         | Name::ReferenceString
         | Name::ResourceTitleString => on_enter_buffer(context),
 
+        Name::AbbreviationDefinition => on_enter_abbreviation_definition(context),
         Name::BlockQuote => on_enter_block_quote(context),
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeFenced | Name::MathFlow => on_enter_raw_flow(context),
         Name::CodeText | Name::MathText => on_enter_raw_text(context),
+        Name::CriticAddition
+        | Name::CriticComment
+        | Name::CriticDeletion
+        | Name::CriticHighlight => on_enter_critic_markup(context),
         Name::Definition => on_enter_definition(context),
+        Name::DefinitionDescription => on_enter_definition_description(context),
         Name::DefinitionDestinationString => on_enter_definition_destination_string(context),
+        Name::DefinitionList => on_enter_definition_list(context),
+        Name::DefinitionTerm => on_enter_definition_term(context),
         Name::Emphasis => on_enter_emphasis(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmFootnoteDefinition => on_enter_gfm_footnote_definition(context),
@@ -342,13 +701,23 @@ fn enter(context: &mut CompileContext) {
         Name::GfmStrikethrough => on_enter_gfm_strikethrough(context),
         Name::GfmTable => on_enter_gfm_table(context),
         Name::GfmTableBody => on_enter_gfm_table_body(context),
+        Name::GfmTableCaption => on_enter_gfm_table_caption(context),
         Name::GfmTableCell => on_enter_gfm_table_cell(context),
         Name::GfmTableHead => on_enter_gfm_table_head(context),
         Name::GfmTableRow => on_enter_gfm_table_row(context),
         Name::GfmTaskListItemCheck => on_enter_gfm_task_list_item_check(context),
+        Name::GridTable => on_enter_grid_table(context),
+        Name::GridTableBody => on_enter_grid_table_body(context),
+        Name::GridTableCell => on_enter_grid_table_cell(context),
+        Name::GridTableHead => on_enter_grid_table_head(context),
+        Name::GridTableRow => on_enter_grid_table_row(context),
+        Name::HeadingAtx | Name::HeadingSetext => on_enter_heading(context),
+        Name::Highlight => on_enter_highlight(context),
         Name::HtmlFlow => on_enter_html_flow(context),
         Name::HtmlText => on_enter_html_text(context),
+        Name::Insert => on_enter_insert(context),
         Name::Image => on_enter_image(context),
+        Name::InlineFootnote => on_enter_inline_footnote(context),
         Name::Link => on_enter_link(context),
         Name::ListItemMarker => on_enter_list_item_marker(context),
         Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
@@ -356,6 +725,7 @@ fn enter(context: &mut CompileContext) {
         Name::Resource => on_enter_resource(context),
         Name::ResourceDestinationString => on_enter_resource_destination_string(context),
         Name::Strong => on_enter_strong(context),
+        Name::WikiLink => on_enter_wiki_link(context),
         _ => {}
     }
 }
@@ -363,8 +733,7 @@ fn enter(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit].
 fn exit(context: &mut CompileContext) {
     match context.events[context.index].name {
-        Name::CodeFencedFenceMeta
-        | Name::MathFlowFenceMeta
+        Name::MathFlowFenceMeta
         | Name::MdxJsxTextTag
         | Name::MdxTextExpression
         | Name::Resource => {
@@ -374,6 +743,8 @@ fn exit(context: &mut CompileContext) {
         Name::CharacterEscapeValue | Name::CodeTextData | Name::Data | Name::MathTextData => {
             on_exit_data(context);
         }
+        Name::Abbreviation => on_exit_abbreviation(context),
+        Name::AbbreviationDefinition => on_exit_abbreviation_definition(context),
         Name::AutolinkEmail => on_exit_autolink_email(context),
         Name::AutolinkProtocol => on_exit_autolink_protocol(context),
         Name::BlankLineEnding => on_exit_blank_line_ending(context),
@@ -386,17 +757,29 @@ fn exit(context: &mut CompileContext) {
             on_exit_character_reference_marker_hexadecimal(context);
         }
         Name::CharacterReferenceValue => on_exit_character_reference_value(context),
+        Name::Cite => on_exit_cite(context),
         Name::CodeFenced | Name::CodeIndented | Name::MathFlow => on_exit_raw_flow(context),
         Name::CodeFencedFence | Name::MathFlowFence => on_exit_raw_flow_fence(context),
         Name::CodeFencedFenceInfo => on_exit_raw_flow_fence_info(context),
+        Name::CodeFencedFenceMeta => on_exit_raw_flow_fence_meta(context),
         Name::CodeFlowChunk | Name::MathFlowChunk => on_exit_raw_flow_chunk(context),
         Name::CodeText | Name::MathText => on_exit_raw_text(context),
         Name::Definition => on_exit_definition(context),
+        Name::DefinitionDescription => on_exit_definition_description(context),
         Name::DefinitionDestinationString => on_exit_definition_destination_string(context),
         Name::DefinitionLabelString => on_exit_definition_label_string(context),
+        Name::DefinitionList => on_exit_definition_list(context),
+        Name::DefinitionTerm => on_exit_definition_term(context),
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
+        Name::EmojiShortcode => on_exit_emoji_shortcode(context),
+        Name::Extension(_) => on_exit_extension(context),
+        Name::CriticAddition
+        | Name::CriticComment
+        | Name::CriticDeletion
+        | Name::CriticHighlight => on_exit_critic_markup(context),
         Name::Emphasis => on_exit_emphasis(context),
         Name::Frontmatter => on_exit_frontmatter(context),
+        Name::GfmAlert => on_exit_gfm_alert(context),
         Name::GfmAutolinkLiteralEmail => on_exit_gfm_autolink_literal_email(context),
         Name::GfmAutolinkLiteralMailto => on_exit_gfm_autolink_literal_mailto(context),
         Name::GfmAutolinkLiteralProtocol => on_exit_gfm_autolink_literal_protocol(context),
@@ -411,20 +794,30 @@ fn exit(context: &mut CompileContext) {
         Name::GfmStrikethrough => on_exit_gfm_strikethrough(context),
         Name::GfmTable => on_exit_gfm_table(context),
         Name::GfmTableBody => on_exit_gfm_table_body(context),
+        Name::GfmTableCaption => on_exit_gfm_table_caption(context),
         Name::GfmTableCell => on_exit_gfm_table_cell(context),
         Name::GfmTableHead => on_exit_gfm_table_head(context),
         Name::GfmTableRow => on_exit_gfm_table_row(context),
         Name::GfmTaskListItemCheck => on_exit_gfm_task_list_item_check(context),
         Name::GfmTaskListItemValueChecked => on_exit_gfm_task_list_item_value_checked(context),
+        Name::GridTable => on_exit_grid_table(context),
+        Name::GridTableBody => on_exit_grid_table_body(context),
+        Name::GridTableCell => on_exit_grid_table_cell(context),
+        Name::GridTableHead => on_exit_grid_table_head(context),
+        Name::GridTableRow => on_exit_grid_table_row(context),
         Name::HardBreakEscape | Name::HardBreakTrailing => on_exit_break(context),
+        Name::HardBreakLineEnding => on_exit_hard_break_line_ending(context),
         Name::HeadingAtx => on_exit_heading_atx(context),
         Name::HeadingAtxSequence => on_exit_heading_atx_sequence(context),
         Name::HeadingAtxText => on_exit_heading_atx_text(context),
         Name::HeadingSetextText => on_exit_heading_setext_text(context),
         Name::HeadingSetextUnderlineSequence => on_exit_heading_setext_underline_sequence(context),
+        Name::Highlight => on_exit_highlight(context),
         Name::HtmlFlow | Name::HtmlText => on_exit_html(context),
         Name::HtmlFlowData | Name::HtmlTextData => on_exit_html_data(context),
+        Name::Insert => on_exit_insert(context),
         Name::Image | Name::Link => on_exit_media(context),
+        Name::InlineFootnote => on_exit_inline_footnote(context),
         Name::Label => on_exit_label(context),
         Name::LabelText => on_exit_label_text(context),
         Name::LineEnding => on_exit_line_ending(context),
@@ -435,6 +828,7 @@ fn exit(context: &mut CompileContext) {
         Name::ReferenceString => on_exit_reference_string(context),
         Name::ResourceDestinationString => on_exit_resource_destination_string(context),
         Name::ResourceTitleString => on_exit_resource_title_string(context),
+        Name::SmartPunctuation => on_exit_smart_punctuation(context),
         Name::Strong => on_exit_strong(context),
         Name::ThematicBreak => on_exit_thematic_break(context),
         _ => {}
@@ -448,11 +842,49 @@ fn on_enter_buffer(context: &mut CompileContext) {
     context.buffer();
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`AbbreviationDefinition`][Name::AbbreviationDefinition].
+fn on_enter_abbreviation_definition(context: &mut CompileContext) {
+    context.buffer();
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`BlockQuote`][Name::BlockQuote].
 fn on_enter_block_quote(context: &mut CompileContext) {
+    let alert = skip::opt(
+        context.events,
+        context.index + 1,
+        &[Name::BlockQuotePrefix, Name::SpaceOrTab],
+    );
+    let alert = if context
+        .events
+        .get(alert)
+        .map_or(false, |event| event.name == Name::GfmAlert)
+    {
+        let slice = Slice::from_position(
+            context.bytes,
+            &Position::from_exit_event(context.events, alert + 1),
+            context.tab_size,
+        );
+        Some(gfm_alert::kind(slice.as_str()))
+    } else {
+        None
+    };
+
+    let id = block_id_after(context, context.index);
     context.tight_stack.push(false);
+    context.block_quote_alert_stack.push(alert.is_some());
     context.line_ending_if_needed();
-    context.push("<blockquote>");
+
+    if let Some(alert) = alert {
+        let name = alert.name().to_lowercase();
+        context.push("<div class=\"markdown-alert markdown-alert-");
+        context.push(&name);
+        context.push("\"");
+    } else {
+        context.push("<blockquote");
+    }
+
+    push_block_id(context, id);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeIndented`][Name::CodeIndented].
@@ -472,6 +904,8 @@ fn on_enter_raw_flow(context: &mut CompileContext) {
 
     if context.events[context.index].name == Name::MathFlow {
         context.push(" class=\"language-math math-display\"");
+    } else {
+        context.raw_flow_attributes = attributes(context, context.index, &Name::CodeFencedFence);
     }
 }
 
@@ -482,12 +916,27 @@ fn on_enter_raw_text(context: &mut CompileContext) {
         context.push("<code");
         if context.events[context.index].name == Name::MathText {
             context.push(" class=\"language-math math-inline\"");
+        } else {
+            let attributes = attributes(context, context.index, &Name::CodeText);
+            push_attributes(context, attributes);
         }
         context.push(">");
     }
     context.buffer();
 }
 
+/// Handle [`Enter`][Kind::Enter]:{[`CriticAddition`][Name::CriticAddition],[`CriticComment`][Name::CriticComment],[`CriticDeletion`][Name::CriticDeletion],[`CriticHighlight`][Name::CriticHighlight]}.
+fn on_enter_critic_markup(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push(match context.events[context.index].name {
+            Name::CriticAddition => "<ins>",
+            Name::CriticDeletion => "<del>",
+            Name::CriticHighlight => "<mark>",
+            _ => "<span class=\"critic comment\">",
+        });
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Definition`][Name::Definition].
 fn on_enter_definition(context: &mut CompileContext) {
     context.buffer();
@@ -501,12 +950,32 @@ fn on_enter_definition(context: &mut CompileContext) {
     });
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionDescription`][Name::DefinitionDescription].
+fn on_enter_definition_description(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("<dd>");
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`DefinitionDestinationString`][Name::DefinitionDestinationString].
 fn on_enter_definition_destination_string(context: &mut CompileContext) {
     context.buffer();
     context.encode_html = false;
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionList`][Name::DefinitionList].
+fn on_enter_definition_list(context: &mut CompileContext) {
+    let loose = definition_list_loose(context.events, context.index);
+    context.tight_stack.push(!loose);
+    context.line_ending_if_needed();
+    context.push("<dl>");
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionTerm`][Name::DefinitionTerm].
+fn on_enter_definition_term(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("<dt>");
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Emphasis`][Name::Emphasis].
 fn on_enter_emphasis(context: &mut CompileContext) {
     if !context.image_alt_inside {
@@ -548,7 +1017,23 @@ fn on_enter_gfm_table(context: &mut CompileContext) {
     let align = gfm_table_align(context.events, context.index);
     context.gfm_table_align = Some(align);
     context.line_ending_if_needed();
-    context.push("<table>");
+    context.push("<table");
+    let id = block_id_after(context, context.index);
+    push_block_id(context, id);
+    context.push(">");
+
+    // The caption comes after the rows in markdown, but first in HTML, so
+    // capture the rows.
+    let mut index = context.index + 1;
+    while !(context.events[index].kind == Kind::Exit
+        && context.events[index].name == Name::GfmTable)
+    {
+        if context.events[index].name == Name::GfmTableCaption {
+            context.buffer();
+            break;
+        }
+        index += 1;
+    }
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmTableBody`][Name::GfmTableBody].
@@ -556,6 +1041,12 @@ fn on_enter_gfm_table_body(context: &mut CompileContext) {
     context.push("<tbody>");
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`GfmTableCaption`][Name::GfmTableCaption].
+fn on_enter_gfm_table_caption(context: &mut CompileContext) {
+    // Capture the caption, separate from the rows.
+    context.buffer();
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`GfmTableCell`][Name::GfmTableCell].
 fn on_enter_gfm_table_cell(context: &mut CompileContext) {
     let column = context.gfm_table_column;
@@ -608,6 +1099,104 @@ fn on_enter_gfm_task_list_item_check(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`GridTable`][Name::GridTable].
+fn on_enter_grid_table(context: &mut CompileContext) {
+    context.grid_table_stack.push(GridTable {
+        align: grid_table_align(context.events, context.bytes, context.index),
+        column: 0,
+        in_head: false,
+    });
+    context.line_ending_if_needed();
+    context.push("<table>");
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GridTableBody`][Name::GridTableBody].
+fn on_enter_grid_table_body(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("<tbody>");
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GridTableCell`][Name::GridTableCell].
+fn on_enter_grid_table_cell(context: &mut CompileContext) {
+    let table = context.grid_table_stack.last().unwrap();
+    let head = table.in_head;
+    let value = table.align.get(table.column).copied();
+    context
+        .tight_stack
+        .push(grid_table_cell_tight(context.events, context.index));
+    context.line_ending_if_needed();
+    context.push(if head { "<th" } else { "<td" });
+
+    match value {
+        Some(AlignKind::Left) => context.push(" align=\"left\""),
+        Some(AlignKind::Right) => context.push(" align=\"right\""),
+        Some(AlignKind::Center) => context.push(" align=\"center\""),
+        Some(AlignKind::None) | None => {}
+    }
+
+    context.push(">");
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GridTableHead`][Name::GridTableHead].
+fn on_enter_grid_table_head(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("<thead>");
+    context.grid_table_stack.last_mut().unwrap().in_head = true;
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GridTableRow`][Name::GridTableRow].
+fn on_enter_grid_table_row(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("<tr>");
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`HeadingAtx`][Name::HeadingAtx],[`HeadingSetext`][Name::HeadingSetext]}.
+fn on_enter_heading(context: &mut CompileContext) {
+    context.heading_block_id = block_id(context, context.index);
+    context.heading_attributes =
+        attributes(context, context.index, &context.events[context.index].name);
+
+    if context.options.gfm_footnote_placement != GfmFootnotePlacementKind::Section
+        || context.buffers.len() != 1
+        || !context.tight_stack.is_empty()
+        || context.gfm_footnote_definition_calls.len() == context.gfm_footnote_section_start
+    {
+        return;
+    }
+
+    // Find the rank.
+    let mut index = context.index + 1;
+    let rank_one = loop {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            let position = Position::from_exit_event(context.events, index);
+
+            if event.name == Name::HeadingAtxSequence {
+                break Slice::from_position(context.bytes, &position, context.tab_size).len() == 1;
+            } else if event.name == Name::HeadingSetextUnderlineSequence {
+                break context.bytes[position.start.index] == b'=';
+            }
+        }
+
+        index += 1;
+    };
+
+    if rank_one {
+        context
+            .gfm_footnote_sections
+            .push((context.buffers[0].len(), context.gfm_footnote_section_start));
+        context.gfm_footnote_section_start = context.gfm_footnote_definition_calls.len();
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`Highlight`][Name::Highlight].
+fn on_enter_highlight(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("<mark>");
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`HtmlFlow`][Name::HtmlFlow].
 fn on_enter_html_flow(context: &mut CompileContext) {
     context.line_ending_if_needed();
@@ -623,6 +1212,13 @@ fn on_enter_html_text(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Insert`][Name::Insert].
+fn on_enter_insert(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("<ins>");
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Image`][Name::Image].
 fn on_enter_image(context: &mut CompileContext) {
     context.media_stack.push(Media {
@@ -636,6 +1232,32 @@ fn on_enter_image(context: &mut CompileContext) {
     context.image_alt_inside = true; // Disallow tags.
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_enter_inline_footnote(context: &mut CompileContext) {
+    let id = context.inline_footnotes[&context.events[context.index].point.index].clone();
+    // Register the call now, instead of when exiting, so that an inline
+    // footnote is numbered before the inline footnotes it contains.
+    if !context
+        .gfm_footnote_definition_call_indices
+        .contains_key(&id)
+    {
+        let call_index = context.gfm_footnote_definition_calls.len();
+        context
+            .gfm_footnote_definition_call_indices
+            .insert(id.clone(), call_index);
+        context.gfm_footnote_definition_calls.push((id, 0));
+    }
+
+    context.media_stack.push(Media {
+        image: false,
+        label_id: None,
+        label: None,
+        reference_id: None,
+        destination: None,
+        title: None,
+    });
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
     context.media_stack.push(Media {
@@ -660,6 +1282,8 @@ fn on_enter_list(context: &mut CompileContext) {
     } else {
         "<ul"
     });
+    let id = block_id_after(context, context.index);
+    push_block_id(context, id);
     context.list_expect_first_marker = Some(true);
 }
 
@@ -671,7 +1295,12 @@ fn on_enter_list_item_marker(context: &mut CompileContext) {
 
     context.line_ending_if_needed();
 
-    context.push("<li>");
+    context.push("<li");
+    if *context.tight_stack.last().unwrap_or(&false) {
+        let id = list_item_block_id(context, context.index);
+        push_block_id(context, id);
+    }
+    context.push(">");
     context.list_expect_first_marker = Some(false);
 }
 
@@ -679,9 +1308,15 @@ fn on_enter_list_item_marker(context: &mut CompileContext) {
 fn on_enter_paragraph(context: &mut CompileContext) {
     let tight = context.tight_stack.last().unwrap_or(&false);
 
-    if !tight {
+    if block_id_before(context, context.index) {
+        context.paragraph_block_id_after = true;
+        context.buffer();
+    } else if !tight {
+        let id = block_id(context, context.index);
         context.line_ending_if_needed();
-        context.push("<p>");
+        context.push("<p");
+        push_block_id(context, id);
+        context.push(">");
     }
 }
 
@@ -706,6 +1341,104 @@ fn on_enter_strong(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`WikiLink`][Name::WikiLink].
+///
+/// Wiki links are void: their parts are collected here, and the rest of
+/// their events are ignored.
+fn on_enter_wiki_link(context: &mut CompileContext) {
+    let mut target = String::new();
+    let mut anchor = None;
+    let mut label = None;
+    let mut index = context.index + 1;
+
+    while context.events[index].name != Name::WikiLink {
+        if context.events[index].kind == Kind::Exit {
+            let value = Slice::from_position(
+                context.bytes,
+                &Position::from_exit_event(context.events, index),
+                context.tab_size,
+            )
+            .serialize();
+
+            match context.events[index].name {
+                Name::WikiLinkTarget => target = value,
+                Name::WikiLinkAnchor => anchor = Some(value),
+                Name::WikiLinkLabel => label = Some(value),
+                _ => {}
+            }
+        }
+
+        index += 1;
+    }
+
+    let mut url = target.clone();
+    if let Some(anchor) = &anchor {
+        url.push('#');
+        url.push_str(anchor);
+    }
+
+    let text = label.as_ref().unwrap_or(&url);
+    let text = encode_with_options(text, context.encode_html, context.options);
+
+    if context.image_alt_inside {
+        context.push(&text);
+    } else {
+        let url = if let Some(wiki_link_url) = &context.options.wiki_link_url {
+            wiki_link_url(&target, anchor.as_deref())
+        } else {
+            url
+        };
+        let url = if context.options.allow_dangerous_protocol {
+            sanitize(&url)
+        } else {
+            sanitize_with_protocols(&url, &SAFE_PROTOCOL_HREF)
+        };
+        context.push("<a href=\"");
+        context.push(&url);
+        context.push("\">");
+        context.push(&text);
+        context.push("</a>");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`Abbreviation`][Name::Abbreviation].
+fn on_exit_abbreviation(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str();
+
+    if !context.image_alt_inside {
+        context.push("<abbr");
+
+        if let Some(Some(title)) = context.abbreviations.get(value) {
+            let title = encode_with_options(title, context.encode_html, context.options);
+            context.push(" title=\"");
+            context.push(&title);
+            context.push("\"");
+        }
+
+        context.push(">");
+    }
+
+    context.push(&encode_with_options(
+        value,
+        context.encode_html,
+        context.options,
+    ));
+
+    if !context.image_alt_inside {
+        context.push("</abbr>");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`AbbreviationDefinition`][Name::AbbreviationDefinition].
+fn on_exit_abbreviation_definition(context: &mut CompileContext) {
+    context.resume();
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`AutolinkEmail`][Name::AutolinkEmail].
 fn on_exit_autolink_email(context: &mut CompileContext) {
     generate_autolink(
@@ -714,6 +1447,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         false,
@@ -728,6 +1462,7 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         false,
@@ -741,6 +1476,21 @@ fn on_exit_break(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`HardBreakLineEnding`][Name::HardBreakLineEnding].
+fn on_exit_hard_break_line_ending(context: &mut CompileContext) {
+    on_exit_break(context);
+    context.push(&encode_with_options(
+        Slice::from_position(
+            context.bytes,
+            &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
+        )
+        .as_str(),
+        context.encode_html,
+        context.options,
+    ));
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`BlankLineEnding`][Name::BlankLineEnding].
 fn on_exit_blank_line_ending(context: &mut CompileContext) {
     if context.index == context.events.len() - 1 {
@@ -753,7 +1503,12 @@ fn on_exit_block_quote(context: &mut CompileContext) {
     context.tight_stack.pop();
     context.line_ending_if_needed();
     context.slurp_one_line_ending = false;
-    context.push("</blockquote>");
+
+    if context.block_quote_alert_stack.pop().unwrap_or(false) {
+        context.push("</div>");
+    } else {
+        context.push("</blockquote>");
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceMarker`][Name::CharacterReferenceMarker].
@@ -780,25 +1535,76 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = decode_character_reference(slice.as_str(), marker, true)
         .expect("expected to parse only valid named references");
 
-    context.push(&encode(&value, context.encode_html));
+    context.push(&encode_with_options(
+        &value,
+        context.encode_html,
+        context.options,
+    ));
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`Cite`][Name::Cite].
+fn on_exit_cite(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str();
+    let text = encode_with_options(value, context.encode_html, context.options);
+
+    if context.image_alt_inside {
+        context.push(&text);
+    } else {
+        let (_, citations) = parse_cite(value).expect("expected to parse only valid citations");
+        let keys = citations
+            .iter()
+            .map(|citation| citation.key.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        context.push("<span class=\"citation\" data-cites=\"");
+        context.push(&encode_with_options(
+            &keys,
+            context.encode_html,
+            context.options,
+        ));
+        context.push("\">");
+        context.push(&text);
+        context.push("</span>");
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFlowChunk`][Name::CodeFlowChunk],[`MathFlowChunk`][Name::MathFlowChunk]}.
 fn on_exit_raw_flow_chunk(context: &mut CompileContext) {
+    let highlighted = context
+        .raw_flow_meta
+        .as_ref()
+        .map_or(false, |meta| meta.is_highlighted(context.raw_flow_line));
     context.raw_flow_seen_data = Some(true);
-    context.push(&encode(
+
+    if highlighted {
+        context.push("<span class=\"highlighted\">");
+    }
+
+    context.push(&encode_with_options(
         &Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         // Must serialize to get virtual spaces.
         .serialize(),
         context.encode_html,
+        context.options,
     ));
+
+    if highlighted {
+        context.push("</span>");
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFencedFence`][Name::CodeFencedFence],[`MathFlowFence`][Name::MathFlowFence]}.
@@ -808,6 +1614,8 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
         .expect("expected `raw_flow_fences_count`");
 
     if count == 0 {
+        let attributes = context.raw_flow_attributes.take();
+        push_attributes(context, attributes);
         context.push(">");
         context.slurp_one_line_ending = true;
     }
@@ -822,9 +1630,44 @@ fn on_exit_raw_flow_fence_info(context: &mut CompileContext) {
     let value = context.resume();
     context.push(" class=\"language-");
     context.push(&value);
+
+    // Classes from attributes are added to the class of the language.
+    if let Some(attributes) = context.raw_flow_attributes.as_mut() {
+        let classes = core::mem::take(&mut attributes.classes);
+
+        for class in classes {
+            context.push(" ");
+            context.push(&encode_with_options(
+                &class,
+                context.encode_html,
+                context.options,
+            ));
+        }
+    }
+
     context.push("\"");
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`CodeFencedFenceMeta`][Name::CodeFencedFenceMeta].
+fn on_exit_raw_flow_fence_meta(context: &mut CompileContext) {
+    context.resume();
+
+    if context.options.code_highlight_lines {
+        let meta = CodeMeta::parse(
+            Slice::from_position(
+                context.bytes,
+                &Position::from_exit_event(context.events, context.index),
+                context.tab_size,
+            )
+            .as_str(),
+        );
+
+        if !meta.highlight.is_empty() {
+            context.raw_flow_meta = Some(meta);
+        }
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
 fn on_exit_raw_flow(context: &mut CompileContext) {
     // One special case is if we are inside a container, and the raw (flow) was
@@ -855,6 +1698,8 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
     }
 
     context.push("</code></pre>");
+    context.raw_flow_meta = None;
+    context.raw_flow_line = 1;
 
     if let Some(count) = context.raw_flow_fences_count.take() {
         if count < 2 {
@@ -933,16 +1778,30 @@ fn on_exit_drop_slurp(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeTextData`][Name::CodeTextData],[`Data`][Name::Data],[`CharacterEscapeValue`][Name::CharacterEscapeValue]}.
 fn on_exit_data(context: &mut CompileContext) {
-    context.push(&encode(
+    context.push(&encode_with_options(
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         context.encode_html,
+        context.options,
     ));
 }
 
+/// Handle [`Exit`][Kind::Exit]:{[`CriticAddition`][Name::CriticAddition],[`CriticComment`][Name::CriticComment],[`CriticDeletion`][Name::CriticDeletion],[`CriticHighlight`][Name::CriticHighlight]}.
+fn on_exit_critic_markup(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push(match context.events[context.index].name {
+            Name::CriticAddition => "</ins>",
+            Name::CriticDeletion => "</del>",
+            Name::CriticHighlight => "</mark>",
+            _ => "</span>",
+        });
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Definition`][Name::Definition].
 fn on_exit_definition(context: &mut CompileContext) {
     context.resume();
@@ -951,11 +1810,43 @@ fn on_exit_definition(context: &mut CompileContext) {
     let id =
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str());
 
-    context.definitions.push(Definition {
+    define(
+        &mut context.definitions,
         id,
-        destination: media.destination,
-        title: media.title,
-    });
+        Definition {
+            destination: media.destination,
+            title: media.title,
+        },
+        context.duplicate_definitions,
+    );
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DefinitionDescription`][Name::DefinitionDescription].
+fn on_exit_definition_description(context: &mut CompileContext) {
+    let tight = context.tight_stack.last().unwrap_or(&false);
+    let before = skip::opt_back(
+        context.events,
+        context.index - 1,
+        &[
+            Name::BlankLineEnding,
+            Name::BlockQuotePrefix,
+            Name::LineEnding,
+            Name::SpaceOrTab,
+            // Also ignore things that don’t contribute to the document.
+            Name::AbbreviationDefinition,
+            Name::Definition,
+            Name::GfmFootnoteDefinition,
+        ],
+    );
+    let tight_paragraph = *tight && context.events[before].name == Name::Paragraph;
+
+    context.slurp_one_line_ending = false;
+
+    if !tight_paragraph {
+        context.line_ending_if_needed();
+    }
+
+    context.push("</dd>");
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionDestinationString`][Name::DefinitionDestinationString].
@@ -973,12 +1864,42 @@ fn on_exit_definition_label_string(context: &mut CompileContext) {
         Some(Position::from_exit_event(context.events, context.index).to_indices());
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`DefinitionList`][Name::DefinitionList].
+fn on_exit_definition_list(context: &mut CompileContext) {
+    context.tight_stack.pop();
+    context.line_ending_if_needed();
+    context.push("</dl>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DefinitionTerm`][Name::DefinitionTerm].
+fn on_exit_definition_term(context: &mut CompileContext) {
+    context.push("</dt>");
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionTitleString`][Name::DefinitionTitleString].
 fn on_exit_definition_title_string(context: &mut CompileContext) {
     let buf = context.resume();
     context.media_stack.last_mut().unwrap().title = Some(buf);
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`EmojiShortcode`][Name::EmojiShortcode].
+fn on_exit_emoji_shortcode(context: &mut CompileContext) {
+    let value = emoji::value(
+        Slice::from_position(
+            context.bytes,
+            &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
+        )
+        .as_str(),
+        context.emoji_lookup,
+    );
+    context.push(&encode_with_options(
+        &value,
+        context.encode_html,
+        context.options,
+    ));
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Emphasis`][Name::Emphasis].
 fn on_exit_emphasis(context: &mut CompileContext) {
     if !context.image_alt_inside {
@@ -986,12 +1907,49 @@ fn on_exit_emphasis(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`Extension`][Name::Extension].
+fn on_exit_extension(context: &mut CompileContext) {
+    let position = Position::from_exit_event(context.events, context.index);
+    let slice = Slice::from_position(context.bytes, &position, context.tab_size);
+    let value = slice.as_str();
+
+    if context.image_alt_inside {
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
+    } else if let Name::Extension(extension) = context.events[context.index].name {
+        context.push(&(context.extensions[extension].to_html)(value));
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) {
     context.resume();
     context.slurp_one_line_ending = true;
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`GfmAlert`][Name::GfmAlert].
+fn on_exit_gfm_alert(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let title = match gfm_alert::kind(slice.as_str()) {
+        AlertKind::Note => "Note",
+        AlertKind::Tip => "Tip",
+        AlertKind::Important => "Important",
+        AlertKind::Warning => "Warning",
+        AlertKind::Caution => "Caution",
+    };
+    context.line_ending_if_needed();
+    context.push("<p class=\"markdown-alert-title\">");
+    context.push(title);
+    context.push("</p>");
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`GfmAutolinkLiteralEmail`][Name::GfmAutolinkLiteralEmail].
 fn on_exit_gfm_autolink_literal_email(context: &mut CompileContext) {
     generate_autolink(
@@ -1000,6 +1958,7 @@ fn on_exit_gfm_autolink_literal_email(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1014,6 +1973,7 @@ fn on_exit_gfm_autolink_literal_mailto(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1028,6 +1988,7 @@ fn on_exit_gfm_autolink_literal_protocol(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1042,6 +2003,7 @@ fn on_exit_gfm_autolink_literal_www(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1056,6 +2018,7 @@ fn on_exit_gfm_autolink_literal_xmpp(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1067,59 +2030,7 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
     let indices = context.media_stack.pop().unwrap().label_id.unwrap();
     let id =
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str());
-    let safe_id = sanitize(&id.to_lowercase());
-    let mut call_index = 0;
-
-    // See if this has been called before.
-    while call_index < context.gfm_footnote_definition_calls.len() {
-        if context.gfm_footnote_definition_calls[call_index].0 == id {
-            break;
-        }
-        call_index += 1;
-    }
-
-    // New.
-    if call_index == context.gfm_footnote_definition_calls.len() {
-        context.gfm_footnote_definition_calls.push((id, 0));
-    }
-
-    // Increment.
-    context.gfm_footnote_definition_calls[call_index].1 += 1;
-
-    // No call is output in an image alt, though the definition and
-    // backreferences are generated as if it was the case.
-    if context.image_alt_inside {
-        return;
-    }
-
-    context.push("<sup><a href=\"#");
-    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-        context.push(&encode(value, context.encode_html));
-    } else {
-        context.push("user-content-");
-    }
-    context.push("fn-");
-    context.push(&safe_id);
-    context.push("\" id=\"");
-    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-        context.push(&encode(value, context.encode_html));
-    } else {
-        context.push("user-content-");
-    }
-    context.push("fnref-");
-    context.push(&safe_id);
-    if context.gfm_footnote_definition_calls[call_index].1 > 1 {
-        context.push("-");
-        context.push(
-            &context.gfm_footnote_definition_calls[call_index]
-                .1
-                .to_string(),
-        );
-    }
-    context.push("\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">");
-
-    context.push(&(call_index + 1).to_string());
-    context.push("</a></sup>");
+    generate_footnote_call(context, id);
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmFootnoteDefinitionLabelString`][Name::GfmFootnoteDefinitionLabelString].
@@ -1142,10 +2053,12 @@ fn on_exit_gfm_footnote_definition(context: &mut CompileContext) {
     let value = context.resume();
     let indices = context.gfm_footnote_definition_stack.pop().unwrap();
     context.tight_stack.pop();
-    context.gfm_footnote_definitions.push((
+    define(
+        &mut context.gfm_footnote_definitions,
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str()),
         value,
-    ));
+        context.duplicate_definitions,
+    );
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmStrikethrough`][Name::GfmStrikethrough].
@@ -1168,6 +2081,18 @@ fn on_exit_gfm_table_body(context: &mut CompileContext) {
     context.push("</tbody>");
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`GfmTableCaption`][Name::GfmTableCaption].
+fn on_exit_gfm_table_caption(context: &mut CompileContext) {
+    let caption = context.resume();
+    let rows = context.resume();
+    context.line_ending_if_needed();
+    context.push("<caption>");
+    context.push(&caption);
+    context.push("</caption>");
+    context.line_ending_if_needed();
+    context.push(&rows);
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`GfmTableCell`][Name::GfmTableCell].
 fn on_exit_gfm_table_cell(context: &mut CompileContext) {
     let align = context.gfm_table_align.as_ref().unwrap();
@@ -1225,6 +2150,49 @@ fn on_exit_gfm_task_list_item_value_checked(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`GridTable`][Name::GridTable].
+fn on_exit_grid_table(context: &mut CompileContext) {
+    context.grid_table_stack.pop();
+    context.line_ending_if_needed();
+    context.push("</table>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GridTableBody`][Name::GridTableBody].
+fn on_exit_grid_table_body(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("</tbody>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GridTableCell`][Name::GridTableCell].
+fn on_exit_grid_table_cell(context: &mut CompileContext) {
+    let tight = context.tight_stack.pop().unwrap();
+    let table = context.grid_table_stack.last_mut().unwrap();
+    let head = table.in_head;
+    table.column += 1;
+    // A line ending after a tight paragraph is not in the cell.
+    context.slurp_one_line_ending = false;
+
+    if !tight {
+        context.line_ending_if_needed();
+    }
+
+    context.push(if head { "</th>" } else { "</td>" });
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GridTableHead`][Name::GridTableHead].
+fn on_exit_grid_table_head(context: &mut CompileContext) {
+    context.grid_table_stack.last_mut().unwrap().in_head = false;
+    context.line_ending_if_needed();
+    context.push("</thead>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GridTableRow`][Name::GridTableRow].
+fn on_exit_grid_table_row(context: &mut CompileContext) {
+    context.grid_table_stack.last_mut().unwrap().column = 0;
+    context.line_ending_if_needed();
+    context.push("</tr>");
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`HeadingAtx`][Name::HeadingAtx].
 fn on_exit_heading_atx(context: &mut CompileContext) {
     let rank = context
@@ -1244,12 +2212,17 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
         let rank = Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .len();
         context.line_ending_if_needed();
         context.heading_atx_rank = Some(rank);
         context.push("<h");
         context.push(&rank.to_string());
+        let id = context.heading_block_id.take();
+        push_block_id(context, id);
+        let attributes = context.heading_attributes.take();
+        push_attributes(context, attributes);
         context.push(">");
     }
 }
@@ -1280,6 +2253,10 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
     context.line_ending_if_needed();
     context.push("<h");
     context.push(rank);
+    let id = context.heading_block_id.take();
+    push_block_id(context, id);
+    let attributes = context.heading_attributes.take();
+    push_attributes(context, attributes);
     context.push(">");
     context.push(&text);
     context.push("</h");
@@ -1287,6 +2264,13 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
     context.push(">");
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`Highlight`][Name::Highlight].
+fn on_exit_highlight(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("</mark>");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_exit_html(context: &mut CompileContext) {
     context.encode_html = true;
@@ -1297,18 +2281,58 @@ fn on_exit_html_data(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.as_str();
 
     let encoded = if context.options.gfm_tagfilter && context.options.allow_dangerous_html {
-        encode(&gfm_tagfilter(value), context.encode_html)
+        encode_with_options(&gfm_tagfilter(value), context.encode_html, context.options)
     } else {
-        encode(value, context.encode_html)
+        encode_with_options(value, context.encode_html, context.options)
     };
 
     context.push(&encoded);
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_exit_inline_footnote(context: &mut CompileContext) {
+    let media = context.media_stack.pop().unwrap();
+    let label = media.label.unwrap();
+    // Find the matching enter, as inline footnotes can be nested.
+    let mut index = context.index - 1;
+    let mut depth = 0;
+    loop {
+        let event = &context.events[index];
+        if event.name == Name::InlineFootnote {
+            if event.kind == Kind::Exit {
+                depth += 1;
+            } else if depth == 0 {
+                break;
+            } else {
+                depth -= 1;
+            }
+        }
+        index -= 1;
+    }
+    let id = context.inline_footnotes[&context.events[index].point.index].clone();
+    // The content of an inline footnote is phrasing, so it is wrapped in a
+    // paragraph, like the content of a GFM footnote definition would be.
+    define(
+        &mut context.gfm_footnote_definitions,
+        id.clone(),
+        format!("<p>{}</p>", label),
+        context.duplicate_definitions,
+    );
+    generate_footnote_call(context, id);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`Insert`][Name::Insert].
+fn on_exit_insert(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("</ins>");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Label`][Name::Label].
 fn on_exit_label(context: &mut CompileContext) {
     let buf = context.resume();
@@ -1328,22 +2352,63 @@ fn on_exit_line_ending(context: &mut CompileContext) {
     } else if context.slurp_one_line_ending
         // Ignore line endings after definitions.
         || (context.index > 1
-            && (context.events[context.index - 2].name == Name::Definition
+            && (context.events[context.index - 2].name == Name::AbbreviationDefinition
+                || context.events[context.index - 2].name == Name::Definition
                 || context.events[context.index - 2].name == Name::GfmFootnoteDefinition))
     {
         context.slurp_one_line_ending = false;
+    } else if context.options.east_asian_line_breaks && east_asian_line_break(context) {
+        // Drop soft line breaks between East Asian characters.
     } else {
-        context.push(&encode(
+        if context.raw_flow_meta.is_some() {
+            context.raw_flow_line += 1;
+        }
+
+        context.push(&encode_with_options(
             Slice::from_position(
                 context.bytes,
                 &Position::from_exit_event(context.events, context.index),
+                context.tab_size,
             )
             .as_str(),
             context.encode_html,
+            context.options,
         ));
     }
 }
 
+/// Check whether the line ending at the current exit is a soft line break
+/// between East Asian wide characters.
+fn east_asian_line_break(context: &CompileContext) -> bool {
+    // Soft line breaks come right after data.
+    if context.index < 2
+        || context.events[context.index - 2].kind != Kind::Exit
+        || context.events[context.index - 2].name != Name::Data
+        || !char::before_index(context.bytes, context.events[context.index - 1].point.index)
+            .map_or(false, char::east_asian_wide)
+    {
+        return false;
+    }
+
+    let mut index = context.index + 1;
+
+    // Skip container prefixes and indent on the next line.
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        match event.name {
+            Name::Data if event.kind == Kind::Enter => {
+                return char::after_index(context.bytes, event.point.index)
+                    .map_or(false, char::east_asian_wide);
+            }
+            Name::BlockQuotePrefix | Name::BlockQuoteMarker | Name::SpaceOrTab => index += 1,
+            _ => return false,
+        }
+    }
+
+    false
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
 fn on_exit_list(context: &mut CompileContext) {
     context.tight_stack.pop();
@@ -1367,6 +2432,7 @@ fn on_exit_list_item(context: &mut CompileContext) {
             Name::LineEnding,
             Name::SpaceOrTab,
             // Also ignore things that don’t contribute to the document.
+            Name::AbbreviationDefinition,
             Name::Definition,
             Name::GfmFootnoteDefinition,
         ],
@@ -1390,6 +2456,7 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
         let slice = Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         );
         let value = slice.as_str().parse::<u32>().ok().unwrap();
 
@@ -1424,24 +2491,19 @@ fn on_exit_media(context: &mut CompileContext) {
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str())
     });
 
-    let definition_index = if media.destination.is_none() {
-        id.map(|id| {
-            let mut index = 0;
-
-            while index < context.definitions.len() && context.definitions[index].id != id {
-                index += 1;
-            }
-
-            debug_assert!(
-                index < context.definitions.len(),
-                "expected defined definition"
-            );
-            index
-        })
+    let definition_id = if media.destination.is_none() {
+        id
     } else {
         None
     };
 
+    if let Some(ref id) = definition_id {
+        debug_assert!(
+            context.definitions.contains_key(id),
+            "expected defined definition"
+        );
+    }
+
     if !is_in_image {
         if media.image {
             context.push("<img src=\"");
@@ -1449,8 +2511,8 @@ fn on_exit_media(context: &mut CompileContext) {
             context.push("<a href=\"");
         };
 
-        let destination = if let Some(index) = definition_index {
-            context.definitions[index].destination.as_ref()
+        let destination = if let Some(ref id) = definition_id {
+            context.definitions[id].destination.as_ref()
         } else {
             media.destination.as_ref()
         };
@@ -1483,8 +2545,8 @@ fn on_exit_media(context: &mut CompileContext) {
     if !is_in_image {
         context.push("\"");
 
-        let title = if let Some(index) = definition_index {
-            context.definitions[index].title.clone()
+        let title = if let Some(ref id) = definition_id {
+            context.definitions[id].title.clone()
         } else {
             media.title
         };
@@ -1515,7 +2577,11 @@ fn on_exit_media(context: &mut CompileContext) {
 fn on_exit_paragraph(context: &mut CompileContext) {
     let tight = context.tight_stack.last().unwrap_or(&false);
 
-    if *tight {
+    if context.paragraph_block_id_after {
+        context.paragraph_block_id_after = false;
+        context.resume();
+        context.slurp_one_line_ending = true;
+    } else if *tight {
         context.slurp_one_line_ending = true;
     } else {
         context.push("</p>");
@@ -1544,6 +2610,16 @@ fn on_exit_resource_title_string(context: &mut CompileContext) {
     context.media_stack.last_mut().unwrap().title = Some(buf);
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`SmartPunctuation`][Name::SmartPunctuation].
+fn on_exit_smart_punctuation(context: &mut CompileContext) {
+    let position = Position::from_exit_event(context.events, context.index);
+    context.push(&smart_punctuation::value(
+        context.bytes,
+        position.start.index,
+        position.end.index,
+    ));
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Strong`][Name::Strong].
 fn on_exit_strong(context: &mut CompileContext) {
     if !context.image_alt_inside {
@@ -1557,16 +2633,98 @@ fn on_exit_thematic_break(context: &mut CompileContext) {
     context.push("<hr />");
 }
 
-/// Generate a footnote section.
-fn generate_footnote_section(context: &mut CompileContext) {
+/// Generate a footnote call to the definition of `id`.
+fn generate_footnote_call(context: &mut CompileContext, id: String) {
+    let safe_id = sanitize(&id.to_lowercase());
+    // See if this has been called before.
+    let call_index =
+        if let Some(&call_index) = context.gfm_footnote_definition_call_indices.get(&id) {
+            call_index
+        } else {
+            // New.
+            let call_index = context.gfm_footnote_definition_calls.len();
+            context
+                .gfm_footnote_definition_call_indices
+                .insert(id.clone(), call_index);
+            context.gfm_footnote_definition_calls.push((id, 0));
+            call_index
+        };
+
+    // Increment.
+    context.gfm_footnote_definition_calls[call_index].1 += 1;
+
+    // No call is output in an image alt, though the definition and
+    // backreferences are generated as if it was the case.
+    if context.image_alt_inside {
+        return;
+    }
+
+    context.push("<sup><a href=\"#");
+    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
+    } else {
+        context.push("user-content-");
+    }
+    context.push("fn-");
+    context.push(&safe_id);
+    context.push("\" id=\"");
+    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
+    } else {
+        context.push("user-content-");
+    }
+    context.push("fnref-");
+    context.push(&safe_id);
+    if context.gfm_footnote_definition_calls[call_index].1 > 1 {
+        context.push("-");
+        context.push(
+            &context.gfm_footnote_definition_calls[call_index]
+                .1
+                .to_string(),
+        );
+    }
+    context.push("\" data-footnote-ref=\"\" aria-describedby=\"");
+    // The label of the footnote section that contains the definition.
+    let section = context
+        .gfm_footnote_sections
+        .partition_point(|d| d.1 <= call_index)
+        + usize::from(call_index >= context.gfm_footnote_section_start);
+    context.push(&footnote_label_id(section));
+    context.push("\">");
+
+    context.push(&(call_index + 1).to_string());
+    context.push("</a></sup>");
+}
+
+/// Generate a footnote section, for the calls from `start` to `end`.
+fn generate_footnote_section(context: &mut CompileContext, start: usize, end: usize) {
     context.line_ending_if_needed();
     context.push("<section data-footnotes=\"\" class=\"footnotes\"><");
     if let Some(ref value) = context.options.gfm_footnote_label_tag_name {
-        context.push(&encode(value, context.encode_html));
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
     } else {
         context.push("h2");
     }
-    context.push(" id=\"footnote-label\" ");
+    context.push(" id=\"");
+    context.push(&footnote_label_id(
+        context
+            .gfm_footnote_sections
+            .partition_point(|d| d.1 < start)
+            + 1,
+    ));
+    context.push("\" ");
     if let Some(ref value) = context.options.gfm_footnote_label_attributes {
         context.push(value);
     } else {
@@ -1574,22 +2732,37 @@ fn generate_footnote_section(context: &mut CompileContext) {
     }
     context.push(">");
     if let Some(ref value) = context.options.gfm_footnote_label {
-        context.push(&encode(value, context.encode_html));
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
     } else {
         context.push("Footnotes");
     }
     context.push("</");
     if let Some(ref value) = context.options.gfm_footnote_label_tag_name {
-        context.push(&encode(value, context.encode_html));
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
     } else {
         context.push("h2");
     }
     context.push(">");
     context.line_ending();
-    context.push("<ol>");
+    // Calls are numbered across sections, so continue from earlier ones.
+    if start > 0 {
+        context.push("<ol start=\"");
+        context.push(&(start + 1).to_string());
+        context.push("\">");
+    } else {
+        context.push("<ol>");
+    }
 
-    let mut index = 0;
-    while index < context.gfm_footnote_definition_calls.len() {
+    let mut index = start;
+    while index < end {
         generate_footnote_item(context, index);
         index += 1;
     }
@@ -1601,30 +2774,36 @@ fn generate_footnote_section(context: &mut CompileContext) {
     context.line_ending();
 }
 
+/// Get the `id` of the label of the footnote section at `section`
+/// (1-indexed).
+fn footnote_label_id(section: usize) -> String {
+    if section > 1 {
+        format!("footnote-label-{}", section)
+    } else {
+        "footnote-label".into()
+    }
+}
+
 /// Generate a footnote item from a call.
 fn generate_footnote_item(context: &mut CompileContext, index: usize) {
     let id = &context.gfm_footnote_definition_calls[index].0;
     let safe_id = sanitize(&id.to_lowercase());
 
     // Find definition: we’ll always find it.
-    let mut definition_index = 0;
-    while definition_index < context.gfm_footnote_definitions.len() {
-        if &context.gfm_footnote_definitions[definition_index].0 == id {
-            break;
-        }
-        definition_index += 1;
-    }
-
-    debug_assert_ne!(
-        definition_index,
-        context.gfm_footnote_definitions.len(),
-        "expected definition"
-    );
+    let value = context
+        .gfm_footnote_definitions
+        .get(id)
+        .expect("expected definition")
+        .clone();
 
     context.line_ending();
     context.push("<li id=\"");
     if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-        context.push(&encode(value, context.encode_html));
+        context.push(&encode_with_options(
+            value,
+            context.encode_html,
+            context.options,
+        ));
     } else {
         context.push("user-content-");
     }
@@ -1642,7 +2821,11 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         }
         backreferences.push_str("<a href=\"#");
         if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-            backreferences.push_str(&encode(value, context.encode_html));
+            backreferences.push_str(&encode_with_options(
+                value,
+                context.encode_html,
+                context.options,
+            ));
         } else {
             backreferences.push_str("user-content-");
         }
@@ -1654,7 +2837,11 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         }
         backreferences.push_str("\" data-footnote-backref=\"\" aria-label=\"");
         if let Some(ref value) = context.options.gfm_footnote_back_label {
-            backreferences.push_str(&encode(value, context.encode_html));
+            backreferences.push_str(&encode_with_options(
+                value,
+                context.encode_html,
+                context.options,
+            ));
         } else {
             backreferences.push_str("Back to content");
         }
@@ -1669,7 +2856,6 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         reference_index += 1;
     }
 
-    let value = context.gfm_footnote_definitions[definition_index].1.clone();
     let bytes = value.as_bytes();
     let mut byte_index = bytes.len();
     // Move back past EOL.
@@ -1739,9 +2925,228 @@ fn generate_autolink(
         context.push("\">");
     }
 
-    context.push(&encode(value, context.encode_html));
+    context.push(&encode_with_options(
+        value,
+        context.encode_html,
+        context.options,
+    ));
 
     if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
         context.push("</a>");
     }
 }
+
+/// Add a (footnote) definition to `map`, unless an earlier one wins.
+fn define<T>(
+    map: &mut BTreeMap<String, T>,
+    id: String,
+    value: T,
+    duplicate_definitions: DuplicateDefinitionKind,
+) {
+    if duplicate_definitions == DuplicateDefinitionKind::Last {
+        map.insert(id, value);
+    } else {
+        map.entry(id).or_insert(value);
+    }
+}
+
+/// Get the value of the block identifier (Obsidian) in the paragraph or
+/// heading entered at `index`.
+fn block_id(context: &CompileContext, mut index: usize) -> Option<String> {
+    let name = &context.events[index].name;
+
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == Name::ObsidianBlockIdValue {
+                return Some(
+                    Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, index),
+                        context.tab_size,
+                    )
+                    .serialize(),
+                );
+            } else if event.name == *name {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Events between a block and a block identifier (Obsidian) on a line of its
+/// own after it.
+const BLOCK_ID_GAP: [Name; 4] = [
+    Name::BlankLineEnding,
+    Name::BlockQuotePrefix,
+    Name::LineEnding,
+    Name::SpaceOrTab,
+];
+
+/// Get the value of the block identifier (Obsidian) on a line of its own
+/// after the block quote, list, or table entered at `index`.
+fn block_id_after(context: &CompileContext, index: usize) -> Option<String> {
+    if !context.obsidian_block_id {
+        return None;
+    }
+
+    let name = context.events[index].name.clone();
+    let index = skip::opt(context.events, index, &[name]);
+    let index = skip::opt(context.events, index, &BLOCK_ID_GAP);
+
+    if context.events.get(index).map_or(false, |event| {
+        event.kind == Kind::Enter && event.name == Name::Paragraph
+    }) && block_id_only(context, index)
+    {
+        block_id(context, index)
+    } else {
+        None
+    }
+}
+
+/// Check whether the paragraph entered at `index` is a block identifier
+/// (Obsidian) on a line of its own after a block quote, list, or table, so
+/// that it identifies that block.
+fn block_id_before(context: &CompileContext, index: usize) -> bool {
+    if !context.obsidian_block_id || index == 0 || !block_id_only(context, index) {
+        return false;
+    }
+
+    let before = skip::opt_back(context.events, index - 1, &BLOCK_ID_GAP);
+    let event = &context.events[before];
+    event.kind == Kind::Exit
+        && matches!(
+            event.name,
+            Name::BlockQuote | Name::GfmTable | Name::ListOrdered | Name::ListUnordered
+        )
+}
+
+/// Check whether the paragraph entered at `index` contains nothing but a
+/// block identifier (Obsidian).
+fn block_id_only(context: &CompileContext, index: usize) -> bool {
+    if context.events[index + 1].name != Name::ObsidianBlockId {
+        return false;
+    }
+
+    let after = skip::opt(context.events, index + 1, &[Name::ObsidianBlockId]);
+    let after = skip::opt(context.events, after, &[Name::SpaceOrTab]);
+    context.events[after].name == Name::Paragraph
+}
+
+/// Get the value of the block identifier (Obsidian) in the first paragraph
+/// directly in the list item whose marker is at `index`.
+fn list_item_block_id(context: &CompileContext, mut index: usize) -> Option<String> {
+    let mut depth = 0;
+
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        match event.name {
+            Name::BlockQuote
+            | Name::GfmFootnoteDefinition
+            | Name::ListOrdered
+            | Name::ListUnordered => {
+                if event.kind == Kind::Enter {
+                    depth += 1;
+                } else {
+                    depth -= 1;
+                }
+            }
+            Name::ListItem if depth == 0 && event.kind == Kind::Exit => break,
+            Name::Paragraph if depth == 0 && event.kind == Kind::Enter => {
+                return if block_id_before(context, index) {
+                    None
+                } else {
+                    block_id(context, index)
+                };
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Get the attributes in the heading, code (fenced), or code (text) entered at
+/// `index`, before the exit of `name`.
+fn attributes(context: &CompileContext, mut index: usize, name: &Name) -> Option<Attributes> {
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == Name::AttributesValue {
+                return parse_attributes(
+                    Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, index),
+                        context.tab_size,
+                    )
+                    .as_str(),
+                );
+            } else if event.name == *name {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Push attributes, if there are some.
+///
+/// Event handlers (such as `onclick`) are dangerous, and only pushed when
+/// dangerous HTML is allowed.
+fn push_attributes(context: &mut CompileContext, attributes: Option<Attributes>) {
+    if let Some(attributes) = attributes {
+        if let Some(id) = &attributes.id {
+            push_attribute(context, "id", id);
+        }
+
+        if !attributes.classes.is_empty() {
+            push_attribute(context, "class", &attributes.classes.join(" "));
+        }
+
+        for (key, value) in &attributes.pairs {
+            if context.options.allow_dangerous_html
+                || !(key.len() > 1 && key.as_bytes()[..2].eq_ignore_ascii_case(b"on"))
+            {
+                push_attribute(context, key, value);
+            }
+        }
+    }
+}
+
+/// Push an attribute.
+fn push_attribute(context: &mut CompileContext, key: &str, value: &str) {
+    context.push(" ");
+    context.push(key);
+    context.push("=\"");
+    context.push(&encode_with_options(
+        value,
+        context.encode_html,
+        context.options,
+    ));
+    context.push("\"");
+}
+
+/// Push the `id` attribute of a block identifier (Obsidian), if there is one.
+fn push_block_id(context: &mut CompileContext, id: Option<String>) {
+    if let Some(id) = id {
+        context.push(" id=\"^");
+        context.push(&encode_with_options(
+            &id,
+            context.encode_html,
+            context.options,
+        ));
+        context.push("\"");
+    }
+}
@@ -1,27 +1,39 @@
 //! Turn events into a syntax tree.
 
+use crate::construct::{
+    abbreviation_definition, emoji, gfm_alert, label_start_inline_footnote, smart_punctuation,
+};
 use crate::event::{Event, Kind, Name};
 use crate::mdast::{
-    AttributeContent, AttributeValue, AttributeValueExpression, BlockQuote, Break, Code,
-    Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Html, Image,
-    ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math,
-    MdxFlowExpression, MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression,
-    MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell, TableRow, Text,
-    ThematicBreak, Toml, Yaml,
+    Abbreviation, AbbreviationDefinition, AttributeContent, AttributeValue,
+    AttributeValueExpression, Attributes, BlockId, BlockQuote, Break, CharacterReference, Cite,
+    Code, CodeKind, Comment, CriticAddition, CriticComment, CriticDeletion, CriticHighlight,
+    CriticSubstitution, Definition, DefinitionDescription, DefinitionList, DefinitionTerm, Delete,
+    Emoji, Emphasis, FootnoteDefinition, FootnoteReference, Heading, HeadingKind, Highlight, Html,
+    Image, ImageReference, InlineCode, InlineMath, Insert, Link, LinkKind, LinkReference, List,
+    ListItem, Math, MdxFlowExpression, MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement,
+    MdxTextExpression, MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell,
+    TableRow, Text, TextValue, ThematicBreak, Toml, WikiLink, Yaml,
 };
 use crate::message;
 use crate::unist::{Point, Position};
 use crate::util::{
+    attributes::parse as parse_attributes,
     character_reference::{
         decode as decode_character_reference, parse as parse_character_reference,
     },
-    infer::{gfm_table_align, list_item_loose, list_loose},
+    cite::parse as parse_cite,
+    infer::{
+        definition_list_loose, gfm_table_align, grid_table_align, list_item_loose, list_loose,
+    },
     mdx_collect::{collect, Result as CollectResult},
     normalize_identifier::normalize_identifier,
     slice::{Position as SlicePosition, Slice},
 };
+use crate::ParseOptions;
 use alloc::{
     boxed::Box,
+    collections::BTreeMap,
     format,
     string::{String, ToString},
     vec,
@@ -90,12 +102,16 @@ struct CompileContext<'a> {
     events: &'a [Event],
     /// List of bytes.
     bytes: &'a [u8],
+    /// Configuration.
+    options: &'a ParseOptions,
     // Fields used by handlers to track the things they need to track to
     // compile markdown.
+    abbreviations: BTreeMap<String, Option<String>>,
     character_reference_marker: u8,
     gfm_table_inside: bool,
     hard_break_after: bool,
     heading_setext_text_after: bool,
+    inline_footnotes: BTreeMap<usize, String>,
     jsx_tag_stack: Vec<JsxTag>,
     jsx_tag: Option<JsxTag>,
     media_reference_stack: Vec<Reference>,
@@ -103,13 +119,16 @@ struct CompileContext<'a> {
     // Intermediate results.
     /// Primary tree and buffers.
     trees: Vec<(Node, Vec<usize>, Vec<usize>)>,
+    /// Footnote definitions of inline footnotes, with the index of the
+    /// event where they start.
+    inline_footnote_definitions: Vec<(usize, Node)>,
     /// Current event index.
     index: usize,
 }
 
 impl<'a> CompileContext<'a> {
     /// Create a new compile context.
-    fn new(events: &'a [Event], bytes: &'a [u8]) -> CompileContext<'a> {
+    fn new(events: &'a [Event], bytes: &'a [u8], options: &'a ParseOptions) -> CompileContext<'a> {
         let tree = Node::Root(Root {
             children: vec![],
             position: Some(Position {
@@ -129,15 +148,23 @@ impl<'a> CompileContext<'a> {
         CompileContext {
             events,
             bytes,
+            options,
+            abbreviations: abbreviation_definition::titles(events, bytes, options.tab_size),
             character_reference_marker: 0,
             gfm_table_inside: false,
             hard_break_after: false,
             heading_setext_text_after: false,
+            inline_footnotes: label_start_inline_footnote::identifiers(
+                events,
+                bytes,
+                options.tab_size,
+            ),
             jsx_tag_stack: vec![],
             jsx_tag: None,
             media_reference_stack: vec![],
             raw_flow_fence_seen: false,
             trees: vec![(tree, vec![], vec![])],
+            inline_footnote_definitions: vec![],
             index: 0,
         }
     }
@@ -225,8 +252,15 @@ impl<'a> CompileContext<'a> {
 }
 
 /// Turn events and bytes into a syntax tree.
-pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, message::Message> {
-    let mut context = CompileContext::new(events, bytes);
+pub fn compile(
+    events: &[Event],
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<Node, message::Message> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("to_mdast", events = events.len()).entered();
+
+    let mut context = CompileContext::new(events, bytes, options);
 
     let mut index = 0;
     while index < events.len() {
@@ -235,13 +269,19 @@ pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, message::Message>
     }
 
     debug_assert_eq!(context.trees.len(), 1, "expected 1 final tree");
-    let (tree, _, event_stack) = context.trees.pop().unwrap();
+    let (mut tree, _, event_stack) = context.trees.pop().unwrap();
 
     if let Some(index) = event_stack.last() {
         let event = &events[*index];
         on_mismatch_error(&mut context, None, event)?;
     }
 
+    // Inline footnotes are defined at the end of the document, in order.
+    context.inline_footnote_definitions.sort_by_key(|d| d.0);
+    tree.children_mut()
+        .unwrap()
+        .extend(context.inline_footnote_definitions.into_iter().map(|d| d.1));
+
     Ok(tree)
 }
 
@@ -264,7 +304,6 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::AutolinkEmail
         | Name::AutolinkProtocol
         | Name::CharacterEscapeValue
-        | Name::CharacterReference
         | Name::CodeFlowChunk
         | Name::CodeTextData
         | Name::Data
@@ -273,26 +312,42 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::HtmlTextData
         | Name::MathFlowChunk
         | Name::MathTextData
-        | Name::MdxJsxTagAttributeValueLiteralValue => on_enter_data(context),
+        | Name::MdxJsxTagAttributeValueLiteralValue
+        | Name::SmartPunctuation => on_enter_data(context),
         Name::CodeFencedFenceInfo
         | Name::CodeFencedFenceMeta
         | Name::DefinitionDestinationString
         | Name::DefinitionLabelString
         | Name::DefinitionTitleString
         | Name::GfmFootnoteDefinitionLabelString
+        | Name::GfmTableCaptionText
         | Name::LabelText
         | Name::MathFlowFenceMeta
         | Name::MdxJsxTagAttributeValueLiteral
         | Name::ReferenceString
         | Name::ResourceDestinationString
         | Name::ResourceTitleString => on_enter_buffer(context),
+        Name::Abbreviation => on_enter_abbreviation(context),
+        Name::AbbreviationDefinition => on_enter_abbreviation_definition(context),
         Name::Autolink => on_enter_autolink(context),
         Name::BlockQuote => on_enter_block_quote(context),
+        Name::CharacterReference => on_enter_character_reference(context),
+        Name::Cite => on_enter_cite(context),
         Name::CodeFenced => on_enter_code_fenced(context),
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeText => on_enter_code_text(context),
+        Name::CriticAddition
+        | Name::CriticComment
+        | Name::CriticDeletion
+        | Name::CriticHighlight
+        | Name::CriticSubstitution => on_enter_critic_markup(context),
         Name::Definition => on_enter_definition(context),
+        Name::DefinitionDescription => on_enter_definition_description(context),
+        Name::DefinitionList => on_enter_definition_list(context),
+        Name::DefinitionTerm => on_enter_definition_term(context),
+        Name::Emoji => on_enter_emoji(context),
         Name::Emphasis => on_enter_emphasis(context),
+        Name::Extension(_) => on_enter_extension(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmAutolinkLiteralEmail
         | Name::GfmAutolinkLiteralMailto
@@ -303,12 +358,18 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::GfmFootnoteDefinition => on_enter_gfm_footnote_definition(context),
         Name::GfmStrikethrough => on_enter_gfm_strikethrough(context),
         Name::GfmTable => on_enter_gfm_table(context),
-        Name::GfmTableRow => on_enter_gfm_table_row(context),
-        Name::GfmTableCell => on_enter_gfm_table_cell(context),
-        Name::HardBreakEscape | Name::HardBreakTrailing => on_enter_hard_break(context),
+        Name::GfmTableRow | Name::GridTableRow => on_enter_table_row(context),
+        Name::GfmTableCell | Name::GridTableCell => on_enter_table_cell(context),
+        Name::GridTable => on_enter_grid_table(context),
+        Name::HardBreakEscape | Name::HardBreakLineEnding | Name::HardBreakTrailing => {
+            on_enter_hard_break(context);
+        }
         Name::HeadingAtx | Name::HeadingSetext => on_enter_heading(context),
+        Name::Highlight => on_enter_highlight(context),
         Name::HtmlFlow | Name::HtmlText => on_enter_html(context),
+        Name::Insert => on_enter_insert(context),
         Name::Image => on_enter_image(context),
+        Name::InlineFootnote => on_enter_inline_footnote(context),
         Name::Link => on_enter_link(context),
         Name::ListItem => on_enter_list_item(context),
         Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
@@ -317,6 +378,7 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::MdxEsm => on_enter_mdx_esm(context),
         Name::MdxFlowExpression => on_enter_mdx_flow_expression(context),
         Name::MdxTextExpression => on_enter_mdx_text_expression(context),
+        Name::ObsidianBlockId => on_enter_obsidian_block_id(context),
         Name::MdxJsxFlowTag | Name::MdxJsxTextTag => on_enter_mdx_jsx_tag(context),
         Name::MdxJsxTagClosingMarker => on_enter_mdx_jsx_tag_closing_marker(context)?,
         Name::MdxJsxTagAttribute => on_enter_mdx_jsx_tag_attribute(context)?,
@@ -330,6 +392,7 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::Resource => on_enter_resource(context),
         Name::Strong => on_enter_strong(context),
         Name::ThematicBreak => on_enter_thematic_break(context),
+        Name::WikiLink => on_enter_wiki_link(context),
         _ => {}
     }
 
@@ -339,21 +402,41 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
 /// Handle [`Exit`][Kind::Exit].
 fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
     match context.events[context.index].name {
-        Name::Autolink
+        Name::Abbreviation
+        | Name::AbbreviationDefinition
+        | Name::Autolink
         | Name::BlockQuote
-        | Name::CharacterReference
+        | Name::Cite
+        | Name::CriticAddition
+        | Name::CriticComment
+        | Name::CriticDeletion
+        | Name::CriticHighlight
+        | Name::CriticSubstitution
         | Name::Definition
+        | Name::DefinitionDescription
+        | Name::DefinitionList
+        | Name::DefinitionTerm
+        | Name::Emoji
         | Name::Emphasis
+        | Name::Extension(_)
         | Name::GfmFootnoteDefinition
         | Name::GfmStrikethrough
         | Name::GfmTableRow
         | Name::GfmTableCell
+        | Name::GridTable
+        | Name::GridTableRow
+        | Name::HardBreakLineEnding
         | Name::HeadingAtx
+        | Name::Highlight
+        | Name::InlineFootnote
+        | Name::Insert
         | Name::ListOrdered
         | Name::ListUnordered
+        | Name::ObsidianBlockId
         | Name::Paragraph
         | Name::Strong
-        | Name::ThematicBreak => {
+        | Name::ThematicBreak
+        | Name::WikiLink => {
             on_exit(context)?;
         }
         Name::CharacterEscapeValue
@@ -371,6 +454,11 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::MdxJsxTagAttributeExpression | Name::MdxJsxTagAttributeValueExpression => {
             on_exit_drop(context);
         }
+        Name::AbbreviationDefinitionLabelString => {
+            on_exit_abbreviation_definition_label_string(context);
+        }
+        Name::AbbreviationDefinitionTitle => on_exit_abbreviation_definition_title(context),
+        Name::AttributesValue => on_exit_attributes_value(context),
         Name::AutolinkProtocol => on_exit_autolink_protocol(context)?,
         Name::AutolinkEmail => on_exit_autolink_email(context)?,
         Name::CharacterReferenceMarker => on_exit_character_reference_marker(context),
@@ -380,6 +468,7 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::CharacterReferenceMarkerHexadecimal => {
             on_exit_character_reference_marker_hexadecimal(context);
         }
+        Name::CharacterReference => on_exit_character_reference(context)?,
         Name::CharacterReferenceValue => on_exit_character_reference_value(context),
         Name::CodeFencedFenceInfo => on_exit_code_fenced_fence_info(context),
         Name::CodeFencedFenceMeta | Name::MathFlowFenceMeta => on_exit_raw_flow_fence_meta(context),
@@ -392,7 +481,9 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
             on_exit_definition_id(context);
         }
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
+        Name::EmojiShortcode => on_exit_emoji_shortcode(context),
         Name::Frontmatter => on_exit_frontmatter(context)?,
+        Name::GfmAlert => on_exit_gfm_alert(context),
         Name::GfmAutolinkLiteralEmail
         | Name::GfmAutolinkLiteralMailto
         | Name::GfmAutolinkLiteralProtocol
@@ -400,6 +491,8 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::GfmAutolinkLiteralXmpp => on_exit_gfm_autolink_literal(context)?,
         Name::GfmFootnoteCall | Name::Image | Name::Link => on_exit_media(context)?,
         Name::GfmTable => on_exit_gfm_table(context)?,
+        Name::GfmTableCaptionText => on_exit_gfm_table_caption_text(context),
+        Name::GridTableCell => on_exit_grid_table_cell(context)?,
         Name::GfmTaskListItemValueUnchecked | Name::GfmTaskListItemValueChecked => {
             on_exit_gfm_task_list_item_value(context);
         }
@@ -427,10 +520,15 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
             on_exit_mdx_jsx_tag_attribute_value_literal(context);
         }
         Name::MdxJsxTagSelfClosingMarker => on_exit_mdx_jsx_tag_self_closing_marker(context),
+        Name::ObsidianBlockIdValue => on_exit_obsidian_block_id_value(context),
 
         Name::ReferenceString => on_exit_reference_string(context),
         Name::ResourceDestinationString => on_exit_resource_destination_string(context),
         Name::ResourceTitleString => on_exit_resource_title_string(context),
+        Name::SmartPunctuation => on_exit_smart_punctuation(context)?,
+        Name::WikiLinkAnchor | Name::WikiLinkLabel | Name::WikiLinkTarget => {
+            on_exit_wiki_link_part(context);
+        }
         _ => {}
     }
 
@@ -452,20 +550,55 @@ fn on_enter_data(context: &mut CompileContext) {
         context.tail_push_again();
     } else {
         context.tail_push(Node::Text(Text {
-            value: String::new(),
+            value: TextValue::default(),
             position: None,
         }));
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Abbreviation`][Name::Abbreviation].
+fn on_enter_abbreviation(context: &mut CompileContext) {
+    let value = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index + 1),
+        context.options.tab_size,
+    )
+    .serialize();
+    let title = context.abbreviations.get(&value).cloned().flatten();
+
+    context.tail_push(Node::Abbreviation(Box::new(Abbreviation {
+        value,
+        position: None,
+        title,
+    })));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`AbbreviationDefinition`][Name::AbbreviationDefinition].
+fn on_enter_abbreviation_definition(context: &mut CompileContext) {
+    context.tail_push(Node::AbbreviationDefinition(Box::new(
+        AbbreviationDefinition {
+            position: None,
+            label: String::new(),
+            title: None,
+        },
+    )));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Autolink`][Name::Autolink].
 fn on_enter_autolink(context: &mut CompileContext) {
-    context.tail_push(Node::Link(Link {
+    let link_kind = if context.events[context.index].name == Name::Autolink {
+        LinkKind::Autolink
+    } else {
+        LinkKind::GfmAutolinkLiteral
+    };
+
+    context.tail_push(Node::Link(Box::new(Link {
         url: String::new(),
         title: None,
         children: vec![],
         position: None,
-    }));
+        link_kind: Some(link_kind),
+    })));
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`BlockQuote`][Name::BlockQuote].
@@ -473,30 +606,89 @@ fn on_enter_block_quote(context: &mut CompileContext) {
     context.tail_push(Node::BlockQuote(BlockQuote {
         children: vec![],
         position: None,
+        alert: None,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`CharacterReference`][Name::CharacterReference].
+fn on_enter_character_reference(context: &mut CompileContext) {
+    if context.options.preserve_character_references {
+        context.tail_push(Node::CharacterReference(Box::new(CharacterReference {
+            value: String::new(),
+            position: None,
+            raw: String::new(),
+        })));
+    } else {
+        on_enter_data(context);
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`Cite`][Name::Cite].
+fn on_enter_cite(context: &mut CompileContext) {
+    let position = SlicePosition {
+        start: &context.events[context.index].point,
+        end: &context.events[context.index + 1].point,
+    };
+    let value = Slice::from_position(context.bytes, &position, context.options.tab_size);
+    let (_, citations) =
+        parse_cite(value.as_str()).expect("expected to parse only valid citations");
+    context.tail_push(Node::Cite(Cite {
+        position: None,
+        citations,
     }));
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeFenced`][Name::CodeFenced].
 fn on_enter_code_fenced(context: &mut CompileContext) {
-    context.tail_push(Node::Code(Code {
+    let fence = first_after(context, context.index, &Name::CodeFencedFenceSequence);
+    // The indent before the opening fence is right before the code.
+    let indent = if context.index > 0 && context.events[context.index - 1].name == Name::SpaceOrTab
+    {
+        Slice::from_position(
+            context.bytes,
+            &SlicePosition::from_exit_event(context.events, context.index - 1),
+            context.options.tab_size,
+        )
+        .len()
+    } else {
+        0
+    };
+
+    context.tail_push(Node::Code(Box::new(Code {
         lang: None,
         meta: None,
         value: String::new(),
         position: None,
-    }));
+        code_kind: Some(CodeKind::Fenced),
+        fence_marker: Some(char::from(context.bytes[fence.0])),
+        fence_size: Some(fence.1 - fence.0),
+        fence_indent: Some(indent),
+        attributes: None,
+    })));
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeIndented`][Name::CodeIndented].
 fn on_enter_code_indented(context: &mut CompileContext) {
-    on_enter_code_fenced(context);
+    context.tail_push(Node::Code(Box::new(Code {
+        lang: None,
+        meta: None,
+        value: String::new(),
+        position: None,
+        code_kind: Some(CodeKind::Indented),
+        fence_marker: None,
+        fence_size: None,
+        fence_indent: None,
+        attributes: None,
+    })));
     on_enter_buffer(context);
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeText`][Name::CodeText].
 fn on_enter_code_text(context: &mut CompileContext) {
     context.tail_push(Node::InlineCode(InlineCode {
-        value: String::new(),
+        value: TextValue::default(),
         position: None,
+        attributes: None,
     }));
     context.buffer();
 }
@@ -515,15 +707,16 @@ fn on_enter_mdx_esm(context: &mut CompileContext) {
     let result = collect(
         context.events,
         context.bytes,
+        context.options.tab_size,
         context.index,
         &[Name::MdxEsmData, Name::LineEnding],
         &[Name::MdxEsm],
     );
-    context.tail_push(Node::MdxjsEsm(MdxjsEsm {
+    context.tail_push(Node::MdxjsEsm(Box::new(MdxjsEsm {
         value: result.value,
         position: None,
         stops: result.stops,
-    }));
+    })));
     context.buffer();
 }
 
@@ -532,15 +725,16 @@ fn on_enter_mdx_flow_expression(context: &mut CompileContext) {
     let result = collect(
         context.events,
         context.bytes,
+        context.options.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxFlowExpression],
     );
-    context.tail_push(Node::MdxFlowExpression(MdxFlowExpression {
+    context.tail_push(Node::MdxFlowExpression(Box::new(MdxFlowExpression {
         value: result.value,
         position: None,
         stops: result.stops,
-    }));
+    })));
     context.buffer();
 }
 
@@ -549,37 +743,105 @@ fn on_enter_mdx_text_expression(context: &mut CompileContext) {
     let result = collect(
         context.events,
         context.bytes,
+        context.options.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxTextExpression],
     );
-    context.tail_push(Node::MdxTextExpression(MdxTextExpression {
+    context.tail_push(Node::MdxTextExpression(Box::new(MdxTextExpression {
         value: result.value,
         position: None,
         stops: result.stops,
-    }));
+    })));
     context.buffer();
 }
 
+/// Handle [`Enter`][Kind::Enter]:{[`CriticAddition`][Name::CriticAddition],[`CriticComment`][Name::CriticComment],[`CriticDeletion`][Name::CriticDeletion],[`CriticHighlight`][Name::CriticHighlight],[`CriticSubstitution`][Name::CriticSubstitution]}.
+fn on_enter_critic_markup(context: &mut CompileContext) {
+    let children = vec![];
+    let position = None;
+    context.tail_push(match context.events[context.index].name {
+        Name::CriticAddition => Node::CriticAddition(CriticAddition { children, position }),
+        Name::CriticComment => Node::CriticComment(CriticComment { children, position }),
+        Name::CriticDeletion => Node::CriticDeletion(CriticDeletion { children, position }),
+        Name::CriticHighlight => Node::CriticHighlight(CriticHighlight { children, position }),
+        _ => Node::CriticSubstitution(CriticSubstitution { children, position }),
+    });
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Definition`][Name::Definition].
 fn on_enter_definition(context: &mut CompileContext) {
-    context.tail_push(Node::Definition(Definition {
+    context.tail_push(Node::Definition(Box::new(Definition {
         url: String::new(),
         identifier: String::new(),
         label: None,
         title: None,
         position: None,
+    })));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionDescription`][Name::DefinitionDescription].
+fn on_enter_definition_description(context: &mut CompileContext) {
+    context.tail_push(Node::DefinitionDescription(DefinitionDescription {
+        children: vec![],
+        position: None,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionList`][Name::DefinitionList].
+fn on_enter_definition_list(context: &mut CompileContext) {
+    let spread = definition_list_loose(context.events, context.index);
+
+    context.tail_push(Node::DefinitionList(DefinitionList {
+        children: vec![],
+        position: None,
+        spread,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionTerm`][Name::DefinitionTerm].
+fn on_enter_definition_term(context: &mut CompileContext) {
+    context.tail_push(Node::DefinitionTerm(DefinitionTerm {
+        children: vec![],
+        position: None,
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Emoji`][Name::Emoji].
+fn on_enter_emoji(context: &mut CompileContext) {
+    context.tail_push(Node::Emoji(Box::new(Emoji {
+        value: String::new(),
+        position: None,
+        shortcode: String::new(),
+    })));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Emphasis`][Name::Emphasis].
 fn on_enter_emphasis(context: &mut CompileContext) {
     context.tail_push(Node::Emphasis(Emphasis {
         children: vec![],
         position: None,
+        marker: Some(marker(context)),
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Extension`][Name::Extension].
+fn on_enter_extension(context: &mut CompileContext) {
+    let position = SlicePosition {
+        start: &context.events[context.index].point,
+        end: &context.events[context.index + 1].point,
+    };
+    let value = Slice::from_position(context.bytes, &position, context.options.tab_size);
+    let value = value.as_str();
+    let mut node = if let Name::Extension(extension) = context.events[context.index].name {
+        (context.options.extensions[extension].to_mdast)(value)
+    } else {
+        unreachable!("expected extension")
+    };
+    node.position_set(None);
+    context.tail_push(node);
+}
+
 /// Handle [`Enter`][Kind::Enter]:{[`GfmAutolinkLiteralEmail`][Name::GfmAutolinkLiteralEmail],[`GfmAutolinkLiteralMailto`][Name::GfmAutolinkLiteralMailto],[`GfmAutolinkLiteralProtocol`][Name::GfmAutolinkLiteralProtocol],[`GfmAutolinkLiteralWww`][Name::GfmAutolinkLiteralWww],[`GfmAutolinkLiteralXmpp`][Name::GfmAutolinkLiteralXmpp]}.
 fn on_enter_gfm_autolink_literal(context: &mut CompileContext) {
     on_enter_autolink(context);
@@ -588,22 +850,22 @@ fn on_enter_gfm_autolink_literal(context: &mut CompileContext) {
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmFootnoteCall`][Name::GfmFootnoteCall].
 fn on_enter_gfm_footnote_call(context: &mut CompileContext) {
-    context.tail_push(Node::FootnoteReference(FootnoteReference {
+    context.tail_push(Node::FootnoteReference(Box::new(FootnoteReference {
         identifier: String::new(),
         label: None,
         position: None,
-    }));
+    })));
     context.media_reference_stack.push(Reference::new());
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmFootnoteDefinition`][Name::GfmFootnoteDefinition].
 fn on_enter_gfm_footnote_definition(context: &mut CompileContext) {
-    context.tail_push(Node::FootnoteDefinition(FootnoteDefinition {
+    context.tail_push(Node::FootnoteDefinition(Box::new(FootnoteDefinition {
         identifier: String::new(),
         label: None,
         children: vec![],
         position: None,
-    }));
+    })));
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmStrikethrough`][Name::GfmStrikethrough].
@@ -617,31 +879,43 @@ fn on_enter_gfm_strikethrough(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`GfmTable`][Name::GfmTable].
 fn on_enter_gfm_table(context: &mut CompileContext) {
     let align = gfm_table_align(context.events, context.index);
-    context.tail_push(Node::Table(Table {
+    context.tail_push(Node::Table(Box::new(Table {
         align,
         children: vec![],
         position: None,
-    }));
+        caption: None,
+    })));
     context.gfm_table_inside = true;
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`GfmTableRow`][Name::GfmTableRow].
-fn on_enter_gfm_table_row(context: &mut CompileContext) {
+/// Handle [`Enter`][Kind::Enter]:[`GridTable`][Name::GridTable].
+fn on_enter_grid_table(context: &mut CompileContext) {
+    let align = grid_table_align(context.events, context.bytes, context.index);
+    context.tail_push(Node::Table(Box::new(Table {
+        align,
+        children: vec![],
+        position: None,
+        caption: None,
+    })));
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`GfmTableRow`][Name::GfmTableRow],[`GridTableRow`][Name::GridTableRow]}.
+fn on_enter_table_row(context: &mut CompileContext) {
     context.tail_push(Node::TableRow(TableRow {
         children: vec![],
         position: None,
     }));
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`GfmTableCell`][Name::GfmTableCell].
-fn on_enter_gfm_table_cell(context: &mut CompileContext) {
+/// Handle [`Enter`][Kind::Enter]:{[`GfmTableCell`][Name::GfmTableCell],[`GridTableCell`][Name::GridTableCell]}.
+fn on_enter_table_cell(context: &mut CompileContext) {
     context.tail_push(Node::TableCell(TableCell {
         children: vec![],
         position: None,
     }));
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`HardBreakEscape`][Name::HardBreakEscape].
+/// Handle [`Enter`][Kind::Enter]:{[`HardBreakEscape`][Name::HardBreakEscape],[`HardBreakLineEnding`][Name::HardBreakLineEnding],[`HardBreakTrailing`][Name::HardBreakTrailing]}.
 fn on_enter_hard_break(context: &mut CompileContext) {
     context.tail_push(Node::Break(Break { position: None }));
 }
@@ -692,6 +966,7 @@ fn on_enter_strong(context: &mut CompileContext) {
     context.tail_push(Node::Strong(Strong {
         children: vec![],
         position: None,
+        marker: Some(marker(context)),
     }));
 }
 
@@ -700,12 +975,38 @@ fn on_enter_thematic_break(context: &mut CompileContext) {
     context.tail_push(Node::ThematicBreak(ThematicBreak { position: None }));
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`HeadingAtx`][Name::HeadingAtx].
+/// Handle [`Enter`][Kind::Enter]:[`WikiLink`][Name::WikiLink].
+fn on_enter_wiki_link(context: &mut CompileContext) {
+    context.tail_push(Node::WikiLink(Box::new(WikiLink {
+        position: None,
+        target: String::new(),
+        anchor: None,
+        label: None,
+    })));
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`HeadingAtx`][Name::HeadingAtx],[`HeadingSetext`][Name::HeadingSetext]}.
 fn on_enter_heading(context: &mut CompileContext) {
-    context.tail_push(Node::Heading(Heading {
+    let heading_kind = if context.events[context.index].name == Name::HeadingAtx {
+        HeadingKind::Atx
+    } else {
+        HeadingKind::Setext
+    };
+
+    context.tail_push(Node::Heading(Box::new(Heading {
         depth: 0, // Will be set later.
         children: vec![],
         position: None,
+        heading_kind: Some(heading_kind),
+        attributes: None,
+    })));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`Highlight`][Name::Highlight].
+fn on_enter_highlight(context: &mut CompileContext) {
+    context.tail_push(Node::Highlight(Highlight {
+        children: vec![],
+        position: None,
     }));
 }
 
@@ -718,25 +1019,44 @@ fn on_enter_html(context: &mut CompileContext) {
     context.buffer();
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Insert`][Name::Insert].
+fn on_enter_insert(context: &mut CompileContext) {
+    context.tail_push(Node::Insert(Insert {
+        children: vec![],
+        position: None,
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Image`][Name::Image].
 fn on_enter_image(context: &mut CompileContext) {
-    context.tail_push(Node::Image(Image {
+    context.tail_push(Node::Image(Box::new(Image {
         url: String::new(),
         title: None,
         alt: String::new(),
         position: None,
-    }));
+    })));
     context.media_reference_stack.push(Reference::new());
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_enter_inline_footnote(context: &mut CompileContext) {
+    let id = context.inline_footnotes[&context.events[context.index].point.index].clone();
+    context.tail_push(Node::FootnoteReference(Box::new(FootnoteReference {
+        identifier: id.clone(),
+        label: Some(id),
+        position: None,
+    })));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
-    context.tail_push(Node::Link(Link {
+    context.tail_push(Node::Link(Box::new(Link {
         url: String::new(),
         title: None,
         children: vec![],
         position: None,
-    }));
+        link_kind: Some(LinkKind::Resource),
+    })));
     context.media_reference_stack.push(Reference::new());
 }
 
@@ -744,6 +1064,7 @@ fn on_enter_link(context: &mut CompileContext) {
 fn on_enter_list(context: &mut CompileContext) {
     let ordered = context.events[context.index].name == Name::ListOrdered;
     let spread = list_loose(context.events, context.index, false);
+    let marker = first_after(context, context.index, &Name::ListItemMarker);
 
     context.tail_push(Node::List(List {
         ordered,
@@ -751,6 +1072,7 @@ fn on_enter_list(context: &mut CompileContext) {
         start: None,
         children: vec![],
         position: None,
+        marker: Some(char::from(context.bytes[marker.0])),
     }));
 }
 
@@ -768,11 +1090,11 @@ fn on_enter_list_item(context: &mut CompileContext) {
 
 /// Handle [`Enter`][Kind::Enter]:[`MathFlow`][Name::MathFlow].
 fn on_enter_math_flow(context: &mut CompileContext) {
-    context.tail_push(Node::Math(Math {
+    context.tail_push(Node::Math(Box::new(Math {
         meta: None,
         value: String::new(),
         position: None,
-    }));
+    })));
 }
 
 /// Handle [`Enter`][Kind::Enter]:{[`MdxJsxFlowTag`][Name::MdxJsxFlowTag],[`MdxJsxTextTag`][Name::MdxJsxTextTag]}.
@@ -849,6 +1171,7 @@ fn on_enter_mdx_jsx_tag_attribute_expression(
     let CollectResult { value, stops } = collect(
         context.events,
         context.bytes,
+        context.options.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxJsxTagAttributeExpression],
@@ -870,6 +1193,7 @@ fn on_enter_mdx_jsx_tag_attribute_value_expression(context: &mut CompileContext)
     let CollectResult { value, stops } = collect(
         context.events,
         context.bytes,
+        context.options.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxJsxTagAttributeValueExpression],
@@ -912,6 +1236,14 @@ fn on_enter_mdx_jsx_tag_self_closing_marker(
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`ObsidianBlockId`][Name::ObsidianBlockId].
+fn on_enter_obsidian_block_id(context: &mut CompileContext) {
+    context.tail_push(Node::BlockId(BlockId {
+        position: None,
+        value: String::new(),
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Paragraph`][Name::Paragraph].
 fn on_enter_paragraph(context: &mut CompileContext) {
     context.tail_push(Node::Paragraph(Paragraph {
@@ -926,12 +1258,57 @@ fn on_exit(context: &mut CompileContext) -> Result<(), message::Message> {
     Ok(())
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`AbbreviationDefinitionLabelString`][Name::AbbreviationDefinitionLabelString].
+fn on_exit_abbreviation_definition_label_string(context: &mut CompileContext) {
+    let label = abbreviation_definition::value(
+        context.events,
+        context.bytes,
+        context.index,
+        context.options.tab_size,
+    );
+
+    if let Node::AbbreviationDefinition(node) = context.tail_mut() {
+        node.label = label;
+    } else {
+        unreachable!("expected abbreviation definition on stack");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle].
+fn on_exit_abbreviation_definition_title(context: &mut CompileContext) {
+    let title = abbreviation_definition::value(
+        context.events,
+        context.bytes,
+        context.index,
+        context.options.tab_size,
+    );
+
+    if let Node::AbbreviationDefinition(node) = context.tail_mut() {
+        node.title = Some(title);
+    } else {
+        unreachable!("expected abbreviation definition on stack");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`AttributesValue`][Name::AttributesValue].
+fn on_exit_attributes_value(context: &mut CompileContext) {
+    let attributes = attributes(context, context.index);
+
+    match context.tail_mut() {
+        Node::Code(node) => node.attributes = attributes,
+        Node::Heading(node) => node.attributes = attributes,
+        // Code (text) is buffered: its attributes are added when it exits.
+        _ => {}
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`AutolinkProtocol`][Name::AutolinkProtocol].
 fn on_exit_autolink_protocol(context: &mut CompileContext) -> Result<(), message::Message> {
     on_exit_data(context)?;
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     if let Node::Link(link) = context.tail_mut() {
         link.url.push_str(value.as_str());
@@ -947,6 +1324,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) -> Result<(), message::M
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     if let Node::Link(link) = context.tail_mut() {
         link.url.push_str("mailto:");
@@ -977,20 +1355,36 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let value =
         decode_character_reference(slice.as_str(), context.character_reference_marker, true)
             .expect("expected to parse only valid named references");
 
-    if let Node::Text(node) = context.tail_mut() {
-        node.value.push_str(value.as_str());
-    } else {
-        unreachable!("expected text on stack");
+    match context.tail_mut() {
+        Node::Text(node) => node.value.push_str(value.as_str()),
+        Node::CharacterReference(node) => node.value = value,
+        _ => unreachable!("expected text or character reference on stack"),
     }
 
     context.character_reference_marker = 0;
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`CharacterReference`][Name::CharacterReference].
+fn on_exit_character_reference(context: &mut CompileContext) -> Result<(), message::Message> {
+    let raw = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
+    );
+
+    if let Node::CharacterReference(node) = context.tail_mut() {
+        node.raw = raw.as_str().into();
+    }
+
+    on_exit(context)
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`CodeFencedFenceInfo`][Name::CodeFencedFenceInfo].
 fn on_exit_code_fenced_fence_info(context: &mut CompileContext) {
     let value = context.resume().to_string();
@@ -1080,8 +1474,19 @@ fn on_exit_raw_text(context: &mut CompileContext) -> Result<(), message::Message
         }
     }
 
+    // Attributes, if any, are right before the exit: the value, the closing
+    // marker, and the attributes themselves are exited.
+    let attributes = if context.events[context.index - 1].name == Name::Attributes {
+        attributes(context, context.index - 4)
+    } else {
+        None
+    };
+
     match context.tail_mut() {
-        Node::InlineCode(node) => node.value = value,
+        Node::InlineCode(node) => {
+            node.value = value.into();
+            node.attributes = attributes;
+        }
         Node::InlineMath(node) => node.value = value,
         _ => unreachable!("expected inline code or math on stack for value"),
     }
@@ -1095,6 +1500,7 @@ fn on_exit_data(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     if let Node::Text(text) = context.tail_mut() {
         text.value.push_str(value.as_str());
@@ -1121,6 +1527,7 @@ fn on_exit_definition_id(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
 
@@ -1152,6 +1559,24 @@ fn on_exit_drop(context: &mut CompileContext) {
     context.resume();
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`EmojiShortcode`][Name::EmojiShortcode].
+fn on_exit_emoji_shortcode(context: &mut CompileContext) {
+    let shortcode = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
+    )
+    .serialize();
+    let value = emoji::value(&shortcode, context.options.emoji_lookup.as_deref());
+
+    if let Node::Emoji(node) = context.tail_mut() {
+        node.value = value;
+        node.shortcode = shortcode;
+    } else {
+        unreachable!("expected emoji on stack");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = trim_eol(context.resume().to_string(), true, true);
@@ -1166,6 +1591,21 @@ fn on_exit_frontmatter(context: &mut CompileContext) -> Result<(), message::Mess
     Ok(())
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`GfmAlert`][Name::GfmAlert].
+fn on_exit_gfm_alert(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
+    );
+
+    if let Node::BlockQuote(node) = context.tail_mut() {
+        node.alert = Some(gfm_alert::kind(slice.as_str()));
+    } else {
+        unreachable!("expected block quote on stack");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`GfmAutolinkLiteralEmail`][Name::GfmAutolinkLiteralEmail],[`GfmAutolinkLiteralMailto`][Name::GfmAutolinkLiteralMailto],[`GfmAutolinkLiteralProtocol`][Name::GfmAutolinkLiteralProtocol],[`GfmAutolinkLiteralWww`][Name::GfmAutolinkLiteralWww],[`GfmAutolinkLiteralXmpp`][Name::GfmAutolinkLiteralXmpp]}.
 fn on_exit_gfm_autolink_literal(context: &mut CompileContext) -> Result<(), message::Message> {
     on_exit_data(context)?;
@@ -1173,6 +1613,7 @@ fn on_exit_gfm_autolink_literal(context: &mut CompileContext) -> Result<(), mess
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
 
     let prefix = match &context.events[context.index].name {
@@ -1203,6 +1644,36 @@ fn on_exit_gfm_table(context: &mut CompileContext) -> Result<(), message::Messag
     Ok(())
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`GfmTableCaptionText`][Name::GfmTableCaptionText].
+fn on_exit_gfm_table_caption_text(context: &mut CompileContext) {
+    let mut fragment = context.resume();
+    let children = fragment.children_mut().unwrap().split_off(0);
+
+    if let Node::Table(node) = context.tail_mut() {
+        node.caption = Some(children);
+    } else {
+        unreachable!("expected table on stack");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GridTableCell`][Name::GridTableCell].
+fn on_exit_grid_table_cell(context: &mut CompileContext) -> Result<(), message::Message> {
+    if let Node::TableCell(node) = context.tail_mut() {
+        // A cell with just a paragraph contains its phrasing content.
+        if node.children.len() == 1 {
+            if let Node::Paragraph(_) = node.children[0] {
+                if let Some(Node::Paragraph(paragraph)) = node.children.pop() {
+                    node.children = paragraph.children;
+                }
+            }
+        }
+    } else {
+        unreachable!("expected table cell on stack");
+    }
+
+    on_exit(context)
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`GfmTaskListItemValueChecked`][Name::GfmTaskListItemValueChecked],[`GfmTaskListItemValueUnchecked`][Name::GfmTaskListItemValueUnchecked]}.
 fn on_exit_gfm_task_list_item_value(context: &mut CompileContext) {
     let checked = context.events[context.index].name == Name::GfmTaskListItemValueChecked;
@@ -1227,6 +1698,7 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
 
     if let Node::Heading(node) = context.tail_mut() {
@@ -1268,11 +1740,43 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`LabelText`][Name::LabelText].
 fn on_exit_label_text(context: &mut CompileContext) {
     let mut fragment = context.resume();
+    let (_, _, event_stack) = context.trees.last().unwrap();
+    let enter = *event_stack.last().unwrap();
+
+    // The label of an inline footnote is the content of its definition.
+    if context.events[enter].name == Name::InlineFootnote {
+        let mut index = enter;
+        while !(context.events[index].kind == Kind::Enter
+            && context.events[index].name == Name::LabelText)
+        {
+            index += 1;
+        }
+        let identifier = context.inline_footnotes[&context.events[enter].point.index].clone();
+        let children = fragment.children_mut().unwrap().split_off(0);
+        context.inline_footnote_definitions.push((
+            enter,
+            Node::FootnoteDefinition(Box::new(FootnoteDefinition {
+                identifier: identifier.clone(),
+                label: Some(identifier),
+                children: vec![Node::Paragraph(Paragraph {
+                    children,
+                    position: Some(Position {
+                        start: context.events[index].point.to_unist(),
+                        end: context.events[context.index].point.to_unist(),
+                    }),
+                })],
+                position: None,
+            })),
+        ));
+        return;
+    }
+
     let label = fragment.to_string();
     let children = fragment.children_mut().unwrap().split_off(0);
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
 
@@ -1316,6 +1820,12 @@ fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Mess
             | Node::Paragraph(_)
             | Node::Strong(_)
             | Node::Delete(_)
+            | Node::Highlight(_)
+            | Node::Insert(_)
+            | Node::CriticAddition(_)
+            | Node::CriticComment(_)
+            | Node::CriticDeletion(_)
+            | Node::CriticHighlight(_)
     ) {
         context.index -= 1;
         on_enter_data(context);
@@ -1329,16 +1839,47 @@ fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Mess
 /// Handle [`Exit`][Kind::Exit]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_exit_html(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = context.resume().to_string();
+    let comment = if context.options.html_comment_nodes {
+        html_comment(&value).map(String::from)
+    } else {
+        None
+    };
+    let tail = context.tail_mut();
 
-    match context.tail_mut() {
-        Node::Html(node) => node.value = value,
-        _ => unreachable!("expected html on stack for value"),
+    if let Some(value) = comment {
+        *tail = Node::Comment(Comment {
+            value,
+            position: tail.position().cloned(),
+        });
+    } else if let Node::Html(node) = tail {
+        node.value = value;
+    } else {
+        unreachable!("expected html on stack for value");
     }
 
     on_exit(context)?;
     Ok(())
 }
 
+/// Get the value of `value` if it is exactly one HTML comment, optionally
+/// with whitespace around it.
+fn html_comment(value: &str) -> Option<&str> {
+    let value = value.trim_matches(|char| char == ' ' || char == '\t');
+
+    // Empty comments, which are closed by their opening.
+    if value == "<!-->" || value == "<!--->" {
+        return Some("");
+    }
+
+    let inside = value.strip_prefix("<!--")?.strip_suffix("-->")?;
+
+    if inside.contains("-->") {
+        None
+    } else {
+        Some(inside)
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`GfmFootnoteCall`][Name::GfmFootnoteCall],[`Image`][Name::Image],[`Link`][Name::Link]}.
 fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
     let reference = context
@@ -1360,13 +1901,13 @@ fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
             Node::Image(_) => {
                 // Need to swap it with a reference version of the node.
                 if let Some(Node::Image(node)) = siblings.pop() {
-                    siblings.push(Node::ImageReference(ImageReference {
+                    siblings.push(Node::ImageReference(Box::new(ImageReference {
                         reference_kind: kind,
                         identifier: reference.identifier,
                         label: Some(reference.label),
                         alt: node.alt,
                         position: node.position,
-                    }));
+                    })));
                 } else {
                     unreachable!("impossible: it’s an image")
                 }
@@ -1374,13 +1915,13 @@ fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
             Node::Link(_) => {
                 // Need to swap it with a reference version of the node.
                 if let Some(Node::Link(node)) = siblings.pop() {
-                    siblings.push(Node::LinkReference(LinkReference {
+                    siblings.push(Node::LinkReference(Box::new(LinkReference {
                         reference_kind: kind,
                         identifier: reference.identifier,
                         label: Some(reference.label),
                         children: node.children,
                         position: node.position,
-                    }));
+                    })));
                 } else {
                     unreachable!("impossible: it’s a link")
                 }
@@ -1442,6 +1983,7 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
     let start = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     )
     .as_str()
     .parse()
@@ -1499,7 +2041,7 @@ fn on_exit_mdx_jsx_tag(context: &mut CompileContext) -> Result<(), message::Mess
         context.jsx_tag_stack.pop();
     } else {
         let node = if context.events[context.index].name == Name::MdxJsxFlowTag {
-            Node::MdxJsxFlowElement(MdxJsxFlowElement {
+            Node::MdxJsxFlowElement(Box::new(MdxJsxFlowElement {
                 name: tag.name.clone(),
                 attributes: tag.attributes.clone(),
                 children: vec![],
@@ -1507,9 +2049,9 @@ fn on_exit_mdx_jsx_tag(context: &mut CompileContext) -> Result<(), message::Mess
                     start: tag.start.clone(),
                     end: tag.end.clone(),
                 }),
-            })
+            }))
         } else {
-            Node::MdxJsxTextElement(MdxJsxTextElement {
+            Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                 name: tag.name.clone(),
                 attributes: tag.attributes.clone(),
                 children: vec![],
@@ -1517,7 +2059,7 @@ fn on_exit_mdx_jsx_tag(context: &mut CompileContext) -> Result<(), message::Mess
                     start: tag.start.clone(),
                     end: tag.end.clone(),
                 }),
-            })
+            }))
         };
 
         context.tail_push(node);
@@ -1542,6 +2084,7 @@ fn on_exit_mdx_jsx_tag_name_primary(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let value = slice.serialize();
     context.jsx_tag.as_mut().expect("expected tag").name = Some(value);
@@ -1552,6 +2095,7 @@ fn on_exit_mdx_jsx_tag_name_member(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let name = context
         .jsx_tag
@@ -1569,6 +2113,7 @@ fn on_exit_mdx_jsx_tag_name_local(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let name = context
         .jsx_tag
@@ -1593,6 +2138,7 @@ fn on_exit_mdx_jsx_tag_attribute_primary_name(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let value = slice.serialize();
 
@@ -1614,6 +2160,7 @@ fn on_exit_mdx_jsx_tag_attribute_name_local(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     if let Some(AttributeContent::Property(attribute)) = context
         .jsx_tag
@@ -1653,12 +2200,29 @@ fn on_exit_mdx_jsx_tag_self_closing_marker(context: &mut CompileContext) {
     context.jsx_tag.as_mut().expect("expected tag").self_closing = true;
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`ObsidianBlockIdValue`][Name::ObsidianBlockIdValue].
+fn on_exit_obsidian_block_id_value(context: &mut CompileContext) {
+    let value = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
+    )
+    .serialize();
+
+    if let Node::BlockId(node) = context.tail_mut() {
+        node.value = value;
+    } else {
+        unreachable!("expected block id on stack");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`ReferenceString`][Name::ReferenceString].
 fn on_exit_reference_string(context: &mut CompileContext) {
     let label = context.resume().to_string();
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
     let reference = context
@@ -1692,6 +2256,39 @@ fn on_exit_resource_title_string(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`SmartPunctuation`][Name::SmartPunctuation].
+fn on_exit_smart_punctuation(context: &mut CompileContext) -> Result<(), message::Message> {
+    let position = SlicePosition::from_exit_event(context.events, context.index);
+    let value = smart_punctuation::value(context.bytes, position.start.index, position.end.index);
+    if let Node::Text(text) = context.tail_mut() {
+        text.value.push_str(&value);
+    } else {
+        unreachable!("expected text on stack");
+    }
+    on_exit(context)
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`WikiLinkAnchor`][Name::WikiLinkAnchor],[`WikiLinkLabel`][Name::WikiLinkLabel],[`WikiLinkTarget`][Name::WikiLinkTarget]}.
+fn on_exit_wiki_link_part(context: &mut CompileContext) {
+    let value = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.options.tab_size,
+    )
+    .serialize();
+    let name = &context.events[context.index].name;
+
+    if let Node::WikiLink(node) = context.tail_mut() {
+        match name {
+            Name::WikiLinkAnchor => node.anchor = Some(value),
+            Name::WikiLinkLabel => node.label = Some(value),
+            _ => node.target = value,
+        }
+    } else {
+        unreachable!("expected wiki link on stack");
+    }
+}
+
 /// Create a position from an event.
 fn position_from_event(event: &Event) -> Position {
     let end = Point::new(event.point.line, event.point.column, event.point.index);
@@ -1712,6 +2309,37 @@ fn delve_mut<'tree>(mut node: &'tree mut Node, stack: &'tree [usize]) -> &'tree
     node
 }
 
+/// Get the byte indices of the first `name` after `index`.
+///
+/// Used to get markers, which always exist, such as the fence sequence of
+/// fenced code.
+fn first_after(context: &CompileContext, index: usize, name: &Name) -> (usize, usize) {
+    let mut index = index;
+
+    while !(context.events[index].kind == Kind::Exit && context.events[index].name == *name) {
+        index += 1;
+    }
+
+    SlicePosition::from_exit_event(context.events, index).to_indices()
+}
+
+/// Get the marker of the attention (emphasis, strong) entered at the current
+/// event.
+fn marker(context: &CompileContext) -> char {
+    char::from(context.bytes[context.events[context.index].point.index])
+}
+
+/// Get the attributes from the [`AttributesValue`][Name::AttributesValue]
+/// exited at `index`.
+fn attributes(context: &CompileContext, index: usize) -> Option<Box<Attributes>> {
+    let value = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, index),
+        context.options.tab_size,
+    );
+    parse_attributes(value.as_str()).map(Box::new)
+}
+
 /// Remove initial/final EOLs.
 fn trim_eol(value: String, at_start: bool, at_end: bool) -> String {
     let bytes = value.as_bytes();
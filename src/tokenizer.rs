@@ -18,7 +18,7 @@ use crate::subtokenize::Subresult;
 #[cfg(feature = "log")]
 use crate::util::char::format_byte_opt;
 
-use crate::util::{constant::TAB_SIZE, edit_map::EditMap};
+use crate::util::edit_map::EditMap;
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 /// Containers.
@@ -36,6 +36,8 @@ pub enum Container {
     ListItem,
     /// [GFM: Footnote definition][crate::construct::gfm_footnote_definition].
     GfmFootnoteDefinition,
+    /// [Definition list][crate::construct::definition_list] description.
+    DefinitionDescription,
 }
 
 /// Info used to tokenize a container.
@@ -104,6 +106,15 @@ pub enum LabelKind {
     ///
     /// Construct: [Label end][crate::construct::label_end].
     GfmUndefinedFootnote,
+    /// Label (inline footnote) start.
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    ///
+    /// Construct: [Label start (inline footnote)][crate::construct::label_start_inline_footnote].
+    InlineFootnote,
 }
 
 /// Label start, looking for an end.
@@ -201,6 +212,13 @@ pub struct TokenizeState<'a> {
     /// a list item.
     /// Used for GFM task list items.
     pub document_at_first_paragraph_of_list_item: bool,
+    /// How many containers the previous paragraph was in, if it was followed
+    /// by nothing but blank lines since.
+    /// Used for definition list terms.
+    pub document_definition_term: Option<usize>,
+    /// Whether this is the text of a heading.
+    /// Used for attributes.
+    pub text_in_heading: bool,
 
     // Couple of very frequent settings for parsing whitespace.
     pub space_or_tab_eol_content: Option<Content>,
@@ -231,6 +249,8 @@ pub struct TokenizeState<'a> {
     pub definitions: Vec<String>,
     /// List of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// List of defined abbreviation labels.
+    pub abbreviations: Vec<String>,
 
     // Last error message provided at an EOF of an expression.
     pub mdx_last_parse_error: Option<(String, String, String)>,
@@ -242,7 +262,7 @@ pub struct TokenizeState<'a> {
     /// Secondary marker.
     pub marker_b: u8,
     /// Several markers.
-    pub markers: &'static [u8],
+    pub markers: &'a [u8],
     /// Whether something was seen.
     pub seen: bool,
     /// Size.
@@ -349,8 +369,11 @@ impl<'a> Tokenizer<'a> {
                 document_child_state: None,
                 document_child: None,
                 document_at_first_paragraph_of_list_item: false,
+                document_definition_term: None,
+                text_in_heading: false,
                 definitions: vec![],
                 gfm_footnote_definitions: vec![],
+                abbreviations: vec![],
                 mdx_last_parse_error: None,
                 end: 0,
                 label_starts: vec![],
@@ -459,7 +482,11 @@ impl<'a> Tokenizer<'a> {
 
     /// Move to the next (virtual) byte.
     fn move_one(&mut self) {
-        match byte_action(self.parse_state.bytes, &self.point) {
+        match byte_action(
+            self.parse_state.bytes,
+            &self.point,
+            self.parse_state.options.tab_size,
+        ) {
             ByteAction::Ignore => {
                 self.point.index += 1;
             }
@@ -644,6 +671,9 @@ impl<'a> Tokenizer<'a> {
             let defs = &mut value.definitions;
             let fn_defs = &mut value.gfm_footnote_definitions;
             while index < resolvers.len() {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::trace_span!("resolve", name = ?resolvers[index]).entered();
+
                 if let Some(mut result) = call_resolve(self, resolvers[index])? {
                     fn_defs.append(&mut result.gfm_footnote_definitions);
                     defs.append(&mut result.definitions);
@@ -662,7 +692,11 @@ impl<'a> Tokenizer<'a> {
 fn move_point_back(tokenizer: &mut Tokenizer, point: &mut Point) {
     while point.index > 0 {
         point.index -= 1;
-        let action = byte_action(tokenizer.parse_state.bytes, point);
+        let action = byte_action(
+            tokenizer.parse_state.bytes,
+            point,
+            tokenizer.parse_state.options.tab_size,
+        );
         if !matches!(action, ByteAction::Ignore) {
             point.index += 1;
             break;
@@ -734,7 +768,11 @@ fn push_impl(
                 let action = if tokenizer.point.index < to.0
                     || (tokenizer.point.index == to.0 && tokenizer.point.vs < to.1)
                 {
-                    Some(byte_action(tokenizer.parse_state.bytes, &tokenizer.point))
+                    Some(byte_action(
+                        tokenizer.parse_state.bytes,
+                        &tokenizer.point,
+                        tokenizer.parse_state.options.tab_size,
+                    ))
                 } else if flush {
                     None
                 } else {
@@ -782,7 +820,7 @@ fn push_impl(
 }
 
 /// Figure out how to handle a byte.
-fn byte_action(bytes: &[u8], point: &Point) -> ByteAction {
+fn byte_action(bytes: &[u8], point: &Point, tab_size: usize) -> ByteAction {
     if point.index < bytes.len() {
         let byte = bytes[point.index];
 
@@ -796,11 +834,11 @@ fn byte_action(bytes: &[u8], point: &Point) -> ByteAction {
                 ByteAction::Normal(b'\n')
             }
         } else if byte == b'\t' {
-            let remainder = point.column % TAB_SIZE;
+            let remainder = point.column % tab_size;
             let vs = if remainder == 0 {
                 0
             } else {
-                TAB_SIZE - remainder
+                tab_size - remainder
             };
 
             // On the tab itself, first send it.
@@ -0,0 +1,57 @@
+//! Merge adjacent text nodes.
+
+use crate::mdast::Node;
+use crate::unist::Position;
+use alloc::vec::Vec;
+
+/// Merge adjacent [`Text`][crate::mdast::Text] siblings in `tree` into one.
+///
+/// Changes to trees, such as removing or unwrapping nodes, often leave text
+/// in several nodes next to each other, which breaks matching on the value
+/// of text.
+///
+/// The merged node starts where the first node starts and ends where the
+/// last node ends.
+/// When one of them has no positional info, the merged node has none
+/// either.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::NodeKind, to_mdast, transform::{compact, unwrap}, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("a *b* c", &ParseOptions::default())?;
+/// unwrap(&mut tree, &[NodeKind::Emphasis]);
+/// compact(&mut tree);
+///
+/// let paragraph = &tree.children().unwrap()[0];
+/// assert_eq!(paragraph.children().unwrap().len(), 1);
+/// assert_eq!(paragraph.to_string(), "a b c");
+/// # Ok(())
+/// # }
+/// ```
+pub fn compact(tree: &mut Node) {
+    if let Some(children) = tree.children_mut() {
+        let mut result: Vec<Node> = Vec::with_capacity(children.len());
+
+        for mut child in children.drain(..) {
+            compact(&mut child);
+
+            if let (Some(Node::Text(previous)), Node::Text(text)) = (result.last_mut(), &child) {
+                previous.value.push_str(&text.value);
+                previous.position = match (&previous.position, &text.position) {
+                    (Some(start), Some(end)) => Some(Position {
+                        start: start.start.clone(),
+                        end: end.end.clone(),
+                    }),
+                    _ => None,
+                };
+            } else {
+                result.push(child);
+            }
+        }
+
+        *children = result;
+    }
+}
@@ -0,0 +1,92 @@
+//! Split a document into an excerpt and a body.
+
+use crate::mdast::{Node, Root};
+use crate::unist::Position;
+
+/// Result of splitting a document with [`excerpt()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Excerpt {
+    /// Root with the nodes before the marker.
+    pub excerpt: Node,
+    /// Root with the nodes after the marker.
+    pub body: Node,
+}
+
+/// Split `tree` at an HTML comment containing `marker`, such as
+/// `<!-- more -->` for the marker `"more"`.
+///
+/// The comment must be a child of `tree`, as flow HTML or as a
+/// [`Comment`][crate::mdast::Comment], and its content
+/// (without `<!--`, `-->`, and surrounding whitespace) must be equal to
+/// `marker`.
+/// The comment itself is in neither result.
+/// Returns `None` if there is no such comment.
+///
+/// Both results are roots.
+/// The excerpt is positioned from the start of `tree` to the start of the
+/// comment, the body from the end of the comment to the end of `tree`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, transform::excerpt, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("a\n\n<!-- more -->\n\nb", &ParseOptions::default())?;
+/// let result = excerpt(&tree, "more").unwrap();
+///
+/// assert_eq!(result.excerpt.to_string(), "a");
+/// assert_eq!(result.body.to_string(), "b");
+/// # Ok(())
+/// # }
+/// ```
+pub fn excerpt(tree: &Node, marker: &str) -> Option<Excerpt> {
+    let children = tree.children()?;
+    let index = children.iter().position(|child| is_marker(child, marker))?;
+    let comment = children[index].position();
+    let position = tree.position();
+
+    let excerpt = Node::Root(Root {
+        children: children[0..index].to_vec(),
+        position: match (position, comment) {
+            (Some(position), Some(comment)) => Some(Position {
+                start: position.start.clone(),
+                end: comment.start.clone(),
+            }),
+            _ => None,
+        },
+    });
+
+    let body = Node::Root(Root {
+        children: children[(index + 1)..].to_vec(),
+        position: match (position, comment) {
+            (Some(position), Some(comment)) => Some(Position {
+                start: comment.end.clone(),
+                end: position.end.clone(),
+            }),
+            _ => None,
+        },
+    });
+
+    Some(Excerpt { excerpt, body })
+}
+
+/// Check if `node` is an HTML comment containing `marker`.
+fn is_marker(node: &Node, marker: &str) -> bool {
+    if let Node::Comment(comment) = node {
+        return comment.value.trim() == marker;
+    }
+
+    if let Node::Html(html) = node {
+        if let Some(inside) = html
+            .value
+            .trim()
+            .strip_prefix("<!--")
+            .and_then(|value| value.strip_suffix("-->"))
+        {
+            return inside.trim() == marker;
+        }
+    }
+
+    false
+}
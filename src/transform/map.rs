@@ -0,0 +1,84 @@
+//! Rebuild a tree by passing each node through a function.
+
+use crate::mdast::{Node, Root};
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+/// Replace every node in `tree`, including `tree` itself, with what `map`
+/// returns for it.
+///
+/// Nodes are passed after their children (so `map` gets nodes whose
+/// children are already mapped), and the nodes that `map` returns are not
+/// walked again.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_mdast, transform::map, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("a *b*", &ParseOptions::default())?;
+/// map(&mut tree, |node| match node {
+///     Node::Emphasis(x) => Node::Strong(markdown::mdast::Strong {
+///         children: x.children,
+///         position: x.position,
+///         marker: x.marker,
+///     }),
+///     node => node,
+/// });
+///
+/// assert_eq!(markdown::to_markdown(tree), "a **b**\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn map(tree: &mut Node, mut map: impl FnMut(Node) -> Node) {
+    flat_map(tree, |node| vec![map(node)]);
+    let node = mem::replace(tree, Root::new(vec![]).into());
+    *tree = map(node);
+}
+
+/// Replace every node in `tree` with the zero, one, or many nodes that `map`
+/// returns for it.
+///
+/// Nodes are passed after their children (so `map` gets nodes whose
+/// children are already mapped), and the nodes that `map` returns are not
+/// walked again.
+/// The node passed as `tree` itself is not passed to `map`.
+///
+/// This can be used to remove nodes (by returning nothing), to unwrap them
+/// (by returning their children), or to split them.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_mdast, transform::flat_map, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("a *b* <c>", &ParseOptions::default())?;
+/// flat_map(&mut tree, |node| match node {
+///     Node::Emphasis(x) => x.children,
+///     Node::Html(_) => vec![],
+///     node => vec![node],
+/// });
+///
+/// assert_eq!(markdown::to_markdown(tree), "a b \n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn flat_map(tree: &mut Node, mut map: impl FnMut(Node) -> Vec<Node>) {
+    visit(tree, &mut map);
+}
+
+/// Map the descendants of `node`.
+fn visit(node: &mut Node, map: &mut dyn FnMut(Node) -> Vec<Node>) {
+    if let Some(children) = node.children_mut() {
+        let mut result = Vec::with_capacity(children.len());
+
+        for mut child in children.drain(..) {
+            visit(&mut child, map);
+            result.extend(map(child));
+        }
+
+        *children = result;
+    }
+}
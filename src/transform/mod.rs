@@ -0,0 +1,36 @@
+//! Transforms that change [mdast][crate::mdast] trees in place.
+//!
+//! Each transform works on a tree made by [`to_mdast()`][crate::to_mdast]
+//! (or built by hand) and can be combined with the others, to prepare a
+//! document before it is turned back into markdown with
+//! [`to_markdown()`][crate::to_markdown].
+
+mod compact;
+mod excerpt;
+mod map;
+mod number_headings;
+mod redact;
+mod safe_links;
+mod sections;
+mod shift_headings;
+mod squeeze;
+mod strip;
+mod table;
+mod toc;
+mod truncate;
+mod unused_definitions;
+
+pub use compact::compact;
+pub use excerpt::{excerpt, Excerpt};
+pub use map::{flat_map, map};
+pub use number_headings::{number_headings, unnumber_headings, NumberHeadingsOptions};
+pub use redact::redact;
+pub use safe_links::{safe_links, SafeLinksAction, SafeLinksOptions};
+pub use sections::{sections, Section};
+pub use shift_headings::shift_headings;
+pub use squeeze::squeeze;
+pub use strip::{strip, unwrap};
+pub use table::TableSort;
+pub use toc::{toc, TocEntry, TocOptions};
+pub use truncate::{truncate, TruncateLimit};
+pub use unused_definitions::remove_unused_definitions;
@@ -0,0 +1,192 @@
+//! Number headings.
+
+use crate::mdast::{Node, Text};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Configuration for [`number_headings()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NumberHeadingsOptions {
+    /// Rank of the headings that get the first number.
+    ///
+    /// Headings with a lower rank are not numbered, and do not affect the
+    /// numbers of other headings.
+    ///
+    /// The default is `1`.
+    pub min_depth: u8,
+    /// Rank of the deepest headings that are numbered.
+    ///
+    /// The default is `6`.
+    pub max_depth: u8,
+    /// Value between numbers.
+    ///
+    /// The default is `"."`, which gives numbers such as `1.2.3`.
+    pub separator: String,
+}
+
+impl Default for NumberHeadingsOptions {
+    fn default() -> Self {
+        Self {
+            min_depth: 1,
+            max_depth: 6,
+            separator: ".".into(),
+        }
+    }
+}
+
+/// Prefix headings in `tree` with hierarchical numbers (`1`, `1.1`,
+/// `1.1.2`), followed by a space.
+///
+/// Only headings that are children of `tree`, with a rank between
+/// `min_depth` and `max_depth`, are numbered.
+/// When a heading is skipped (such as an `h3` directly after an `h1`), its
+/// number is `0`.
+///
+/// Numbers that are already at the start of headings (such as from an
+/// earlier call) are replaced, so running this multiple times gives the same
+/// result.
+/// Note that this means that headings that start with a number followed by
+/// whitespace (`# 2024 plans`) lose that number.
+/// Use [`unnumber_headings()`][] to remove numbers.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     to_mdast,
+///     transform::{number_headings, NumberHeadingsOptions},
+///     ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("# a\n## b\n## c\n# d", &ParseOptions::default())?;
+/// number_headings(&mut tree, &NumberHeadingsOptions::default());
+///
+/// assert_eq!(tree.to_string(), "1 a1.1 b1.2 c2 d");
+/// # Ok(())
+/// # }
+/// ```
+pub fn number_headings(tree: &mut Node, options: &NumberHeadingsOptions) {
+    let mut counts = [0_u32; 6];
+    let min = usize::from(options.min_depth.max(1));
+
+    each_heading(tree, options, &mut |depth, children| {
+        let index = usize::from(depth - 1);
+        counts[index] += 1;
+        for count in counts.iter_mut().skip(index + 1) {
+            *count = 0;
+        }
+
+        let number = counts[(min - 1)..=index]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(&options.separator);
+
+        strip_number(children, &options.separator);
+
+        if let Some(Node::Text(text)) = children.first_mut() {
+            text.value = format!("{} {}", number, text.value).into();
+        } else {
+            children.insert(
+                0,
+                Node::Text(Text {
+                    value: format!("{} ", number).into(),
+                    position: None,
+                }),
+            );
+        }
+    });
+}
+
+/// Remove numbers from the start of headings in `tree`, as added by
+/// [`number_headings()`][] with the same options.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     to_mdast,
+///     transform::{unnumber_headings, NumberHeadingsOptions},
+///     ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("# 1 a\n## 1.1 b", &ParseOptions::default())?;
+/// unnumber_headings(&mut tree, &NumberHeadingsOptions::default());
+///
+/// assert_eq!(tree.to_string(), "ab");
+/// # Ok(())
+/// # }
+/// ```
+pub fn unnumber_headings(tree: &mut Node, options: &NumberHeadingsOptions) {
+    each_heading(tree, options, &mut |_, children| {
+        strip_number(children, &options.separator);
+    });
+}
+
+/// Call `each` with the depth and children of headings that are numbered.
+fn each_heading(
+    tree: &mut Node,
+    options: &NumberHeadingsOptions,
+    each: &mut dyn FnMut(u8, &mut Vec<Node>),
+) {
+    let min = options.min_depth.max(1);
+    let max = options.max_depth.min(6);
+
+    if let Some(children) = tree.children_mut() {
+        for child in children {
+            if let Node::Heading(heading) = child {
+                if heading.depth >= min && heading.depth <= max {
+                    each(heading.depth, &mut heading.children);
+                }
+            }
+        }
+    }
+}
+
+/// Remove a number (such as `1.2`, optionally with a final separator)
+/// followed by whitespace from the start of `children`.
+fn strip_number(children: &mut Vec<Node>, separator: &str) {
+    if let Some(Node::Text(text)) = children.first_mut() {
+        let mut rest = text.value.as_str();
+        let mut numbers = 0;
+
+        loop {
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+            if digits == 0 {
+                break;
+            }
+
+            rest = &rest[digits..];
+            numbers += 1;
+
+            if separator.is_empty() {
+                break;
+            }
+
+            if let Some(after) = rest.strip_prefix(separator) {
+                rest = after;
+            } else {
+                break;
+            }
+        }
+
+        let after = rest.trim_start();
+
+        if numbers > 0 && after.len() < rest.len() {
+            let after = after.len();
+
+            if after == 0 {
+                children.remove(0);
+            } else {
+                let start = text.value.len() - after;
+                text.value.replace_range(0..start, "");
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Mask sensitive text in a tree.
+
+use crate::mdast::Node;
+use alloc::{string::String, vec::Vec};
+
+/// Mask the ranges that `find` reports in the values of `Text`,
+/// `InlineCode`, and `Code` nodes in `tree`.
+///
+/// `find` is called with each value and returns the byte ranges to mask, as
+/// `(start, end)` pairs.
+/// This can be a regular expression, a list of names, or anything else.
+/// Ranges that overlap are merged, ranges that are out of bounds or not on
+/// character boundaries are ignored.
+///
+/// Each character in a range is replaced by `mask`.
+/// Nodes are changed in place: the structure of the tree and the positions
+/// of nodes do not change (positions still describe the original text).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, transform::redact, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("Mail `alice`, or *alice*.", &ParseOptions::default())?;
+/// redact(&mut tree, '█', &|value| {
+///     value.match_indices("alice").map(|(index, name)| (index, index + name.len())).collect()
+/// });
+///
+/// assert_eq!(tree.to_string(), "Mail █████, or █████.");
+/// # Ok(())
+/// # }
+/// ```
+pub fn redact(tree: &mut Node, mask: char, find: &dyn Fn(&str) -> Vec<(usize, usize)>) {
+    match tree {
+        Node::Text(x) => x.value = redact_value(&x.value, mask, find).into(),
+        Node::InlineCode(x) => x.value = redact_value(&x.value, mask, find).into(),
+        Node::Code(x) => x.value = redact_value(&x.value, mask, find),
+        _ => {
+            if let Some(children) = tree.children_mut() {
+                for child in children {
+                    redact(child, mask, find);
+                }
+            }
+        }
+    }
+}
+
+/// Mask the ranges found in `value`.
+fn redact_value(value: &str, mask: char, find: &dyn Fn(&str) -> Vec<(usize, usize)>) -> String {
+    let mut ranges = find(value)
+        .into_iter()
+        .filter(|&(start, end)| {
+            start < end
+                && end <= value.len()
+                && value.is_char_boundary(start)
+                && value.is_char_boundary(end)
+        })
+        .collect::<Vec<_>>();
+
+    if ranges.is_empty() {
+        return value.into();
+    }
+
+    ranges.sort_unstable();
+
+    let mut result = String::with_capacity(value.len());
+    let mut last = 0;
+
+    for (start, end) in ranges {
+        let start = start.max(last);
+
+        if start >= end {
+            continue;
+        }
+
+        result.push_str(&value[last..start]);
+        for char in value[start..end].chars() {
+            // Keep line endings, so that lines stay lines.
+            result.push(if matches!(char, '\n' | '\r') {
+                char
+            } else {
+                mask
+            });
+        }
+        last = end;
+    }
+
+    result.push_str(&value[last..]);
+    result
+}
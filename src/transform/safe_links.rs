@@ -0,0 +1,213 @@
+//! Neutralize dangerous URLs in a tree.
+
+use crate::mdast::{Node, Text, WikiLink};
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// What to do with nodes that have a dangerous URL, in
+/// [`safe_links()`][].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SafeLinksAction {
+    /// Replace the URL with `#` (default).
+    Rewrite,
+    /// Remove the node.
+    ///
+    /// Links are replaced by their children, images by their alternative
+    /// text, wiki links by their text, and definitions are dropped.
+    Unwrap,
+}
+
+impl Default for SafeLinksAction {
+    fn default() -> Self {
+        Self::Rewrite
+    }
+}
+
+/// Configuration for [`safe_links()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SafeLinksOptions {
+    /// What to do with nodes that have a dangerous URL.
+    ///
+    /// The default is [`SafeLinksAction::Rewrite`][].
+    pub action: SafeLinksAction,
+    /// Protocols that are dangerous, compared case-insensitively.
+    ///
+    /// The default is `["javascript", "vbscript"]`.
+    pub protocols: Vec<String>,
+    /// Media types that are allowed in `data:` URLs of images, compared
+    /// case-insensitively.
+    ///
+    /// All other `data:` URLs, and all `data:` URLs of links and definitions,
+    /// are dangerous.
+    /// `image/svg+xml` is not in the default, as SVG can include scripts.
+    ///
+    /// The default is `["image/avif", "image/gif", "image/jpeg",
+    /// "image/png", "image/webp"]`.
+    pub data_media_types: Vec<String>,
+}
+
+impl Default for SafeLinksOptions {
+    fn default() -> Self {
+        Self {
+            action: SafeLinksAction::default(),
+            protocols: vec!["javascript".into(), "vbscript".into()],
+            data_media_types: vec![
+                "image/avif".into(),
+                "image/gif".into(),
+                "image/jpeg".into(),
+                "image/png".into(),
+                "image/webp".into(),
+            ],
+        }
+    }
+}
+
+/// Neutralize links, images, definitions, and wiki links in `tree` whose
+/// URLs use a dangerous protocol (such as `javascript:`), or are unexpected
+/// `data:` URLs.
+///
+/// Protocols are detected like browsers do: whitespace and control
+/// characters around URLs, and tabs and line endings in them, are ignored,
+/// so that `" java\tscript:"` is dangerous too.
+/// Character references in URLs are already decoded in trees made by
+/// [`to_mdast()`][crate::to_mdast].
+///
+/// Link references and image references have no URLs themselves: use their
+/// definitions.
+/// Definitions are checked like links, as links can use them too.
+/// The target of a wiki link is checked as its URL, and rewritten to an empty
+/// target (the current note).
+/// The node passed as `tree` itself is never changed, only its descendants.
+///
+/// When compiling to HTML directly, see
+/// [`allow_dangerous_protocol`][crate::CompileOptions::allow_dangerous_protocol]
+/// instead.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::Node,
+///     to_mdast,
+///     transform::{safe_links, SafeLinksAction, SafeLinksOptions},
+///     ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("[a](javascript:alert(1)) [b](https://b.c)", &ParseOptions::default())?;
+/// safe_links(&mut tree, &SafeLinksOptions::default());
+///
+/// if let Node::Link(link) = &tree.children().unwrap()[0].children().unwrap()[0] {
+///     assert_eq!(link.url, "#");
+/// }
+///
+/// let mut tree = to_mdast("[a](javascript:alert(1))", &ParseOptions::default())?;
+/// safe_links(
+///     &mut tree,
+///     &SafeLinksOptions {
+///         action: SafeLinksAction::Unwrap,
+///         ..SafeLinksOptions::default()
+///     },
+/// );
+///
+/// assert!(matches!(tree.children().unwrap()[0].children().unwrap()[0], Node::Text(_)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn safe_links(tree: &mut Node, options: &SafeLinksOptions) {
+    if let Some(children) = tree.children_mut() {
+        let mut result = Vec::with_capacity(children.len());
+
+        for mut child in children.drain(..) {
+            safe_links(&mut child, options);
+
+            let wiki = matches!(child, Node::WikiLink(_));
+            let url = match &mut child {
+                Node::Link(x) => Some((&mut x.url, false)),
+                Node::Image(x) => Some((&mut x.url, true)),
+                Node::Definition(x) => Some((&mut x.url, false)),
+                Node::WikiLink(x) => Some((&mut x.target, false)),
+                _ => None,
+            };
+
+            if let Some((url, image)) = url {
+                if is_dangerous(url, image, options) {
+                    if options.action == SafeLinksAction::Rewrite {
+                        *url = if wiki { String::new() } else { "#".into() };
+                    } else {
+                        match child {
+                            Node::Link(mut x) => result.append(&mut x.children),
+                            Node::WikiLink(x) => {
+                                let WikiLink {
+                                    position,
+                                    target,
+                                    anchor,
+                                    label,
+                                } = *x;
+                                let value = label.unwrap_or_else(|| match anchor {
+                                    Some(anchor) => format!("{}#{}", target, anchor),
+                                    None => target,
+                                });
+                                result.push(Node::Text(Text {
+                                    value: value.into(),
+                                    position,
+                                }));
+                            }
+                            Node::Image(x) if !x.alt.is_empty() => {
+                                result.push(Node::Text(Text {
+                                    value: x.alt.into(),
+                                    position: x.position,
+                                }));
+                            }
+                            _ => {}
+                        }
+
+                        continue;
+                    }
+                }
+            }
+
+            result.push(child);
+        }
+
+        *children = result;
+    }
+}
+
+/// Check whether `url` is dangerous.
+///
+/// `image` is whether `data:` URLs of `options.data_media_types` are okay.
+fn is_dangerous(url: &str, image: bool, options: &SafeLinksOptions) -> bool {
+    let url = url
+        .trim_matches(|char: char| char <= ' ')
+        .chars()
+        .filter(|char| !matches!(char, '\t' | '\n' | '\r'))
+        .collect::<String>();
+
+    let protocol = if let Some(index) = url.find(':') {
+        &url[..index]
+    } else {
+        return false;
+    };
+
+    // A slash, question mark, or number sign before the colon means it is a
+    // relative URL.
+    if protocol.contains(&['/', '?', '#'][..]) {
+        return false;
+    }
+
+    if protocol.eq_ignore_ascii_case("data") {
+        let rest = &url[protocol.len() + 1..];
+        let media_type = rest.split(&[';', ','][..]).next().unwrap_or("").trim();
+
+        !image
+            || !options
+                .data_media_types
+                .iter()
+                .any(|d| d.eq_ignore_ascii_case(media_type))
+    } else {
+        options
+            .protocols
+            .iter()
+            .any(|d| d.eq_ignore_ascii_case(protocol))
+    }
+}
@@ -0,0 +1,101 @@
+//! Split a document into sections at its headings.
+
+use crate::mdast::{Node, Root};
+use crate::unist::{Point, Position};
+use alloc::vec::Vec;
+
+/// Section of a document made by [`sections()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Section {
+    /// Heading that starts the section.
+    ///
+    /// This is `None` for the nodes before the first heading.
+    pub heading: Option<Node>,
+    /// Root with the nodes after the heading, up to the next section.
+    pub body: Node,
+}
+
+/// Split the children of `tree` into sections, each starting at a heading
+/// with a rank of at most `max_depth`.
+///
+/// Headings with a higher rank (such as an `h3` when `max_depth` is `2`)
+/// stay in the body of the section they are in.
+/// Nodes before the first heading form a section without heading, which is
+/// left out when there are no such nodes.
+///
+/// Bodies are roots.
+/// A body is positioned from the end of its heading (or the start of `tree`)
+/// to the start of the next heading (or the end of `tree`).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, transform::sections, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("a\n\n# b\n\nc\n\n## d\n\ne", &ParseOptions::default())?;
+/// let result = sections(&tree, 1);
+///
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].heading, None);
+/// assert_eq!(result[0].body.to_string(), "a");
+/// assert_eq!(result[1].heading.as_ref().unwrap().to_string(), "b");
+/// assert_eq!(result[1].body.to_string(), "cde");
+/// # Ok(())
+/// # }
+/// ```
+pub fn sections(tree: &Node, max_depth: u8) -> Vec<Section> {
+    let mut result = Vec::new();
+    let children = match tree.children() {
+        Some(children) => children,
+        None => return result,
+    };
+    let position = tree.position();
+    let mut heading: Option<&Node> = None;
+    let mut start = 0;
+
+    for index in 0..=children.len() {
+        let next = children
+            .get(index)
+            .filter(|child| matches!(child, Node::Heading(x) if x.depth <= max_depth));
+
+        if next.is_none() && index < children.len() {
+            continue;
+        }
+
+        if heading.is_some() || index > start {
+            let from = match heading {
+                Some(heading) => heading.position().map(|x| &x.end),
+                None => position.map(|x| &x.start),
+            };
+            let to = match next {
+                Some(next) => next.position().map(|x| &x.start),
+                None => position.map(|x| &x.end),
+            };
+
+            result.push(Section {
+                heading: heading.cloned(),
+                body: Node::Root(Root {
+                    children: children[start..index].to_vec(),
+                    position: span(from, to),
+                }),
+            });
+        }
+
+        heading = next;
+        start = index + 1;
+    }
+
+    result
+}
+
+/// Get the position from `start` to `end`, if both exist.
+fn span(start: Option<&Point>, end: Option<&Point>) -> Option<Position> {
+    match (start, end) {
+        (Some(start), Some(end)) => Some(Position {
+            start: start.clone(),
+            end: end.clone(),
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,58 @@
+//! Change the rank of headings.
+
+use crate::mdast::{Node, NodePath};
+use alloc::vec::Vec;
+
+/// Add `delta` to the rank of all headings in `tree`.
+///
+/// Ranks are clamped between `1` and `6`: shifting an `h5` by `3` gives an
+/// `h6`, shifting an `h2` by `-3` gives an `h1`.
+/// This can be used to embed a document in a section of another one, such
+/// as under an `h2` with a `delta` of `1`.
+///
+/// Returns the paths of headings whose rank was clamped, in document order,
+/// so that callers can report that the structure of the document changed.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::NodePath, to_markdown, to_mdast, transform::shift_headings, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("# a\n\n###### b", &ParseOptions::default())?;
+/// let clamped = shift_headings(&mut tree, 1);
+///
+/// assert_eq!(to_markdown(tree), "## a\n\n###### b\n\n");
+/// assert_eq!(clamped, vec![NodePath::from(vec![1])]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn shift_headings(tree: &mut Node, delta: i8) -> Vec<NodePath> {
+    let mut clamped = Vec::new();
+    shift(tree, delta, &mut NodePath::new(), &mut clamped);
+    clamped
+}
+
+/// Shift headings in `node`, at `path`, adding clamped ones to `clamped`.
+fn shift(node: &mut Node, delta: i8, path: &mut NodePath, clamped: &mut Vec<NodePath>) {
+    if let Node::Heading(heading) = node {
+        let depth = i16::from(heading.depth) + i16::from(delta);
+        let bounded = depth.clamp(1, 6);
+
+        if depth != bounded {
+            clamped.push(path.clone());
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let depth = bounded as u8;
+        heading.depth = depth;
+    }
+
+    if let Some(children) = node.children_mut() {
+        for (index, child) in children.iter_mut().enumerate() {
+            path.push(index);
+            shift(child, delta, path, clamped);
+            path.pop();
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Remove empty nodes left behind by other changes.
+
+use crate::mdast::Node;
+
+/// Remove empty paragraphs, emphasis, and strong from `tree`, and collapse
+/// runs of thematic breaks and of line breaks.
+///
+/// Nodes are empty when they only contain whitespace text, or nothing.
+/// Parents that become empty when their children are removed are removed
+/// too.
+/// The node passed as `tree` itself is never removed.
+///
+/// This cleans up the husks left by transforms that remove content, such as
+/// [`strip()`][crate::transform::strip] removing all images, similar to
+/// [`remark-squeeze-paragraphs`](https://github.com/remarkjs/remark-squeeze-paragraphs).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::NodeKind, to_mdast, transform::{squeeze, strip}, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("a\n\n*![b](c)*\n\n***\n\n![d](e)\n\n***", &ParseOptions::default())?;
+/// strip(&mut tree, &[NodeKind::Image]);
+/// squeeze(&mut tree);
+///
+/// let kinds = tree.children().unwrap().iter().map(|node| node.kind()).collect::<Vec<_>>();
+/// assert_eq!(kinds, vec![NodeKind::Paragraph, NodeKind::ThematicBreak]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn squeeze(tree: &mut Node) {
+    if let Some(children) = tree.children_mut() {
+        for child in children.iter_mut() {
+            squeeze(child);
+        }
+
+        children.retain(|child| !is_empty(child));
+        children.dedup_by(|b, a| {
+            matches!(
+                (a, b),
+                (Node::ThematicBreak(_), Node::ThematicBreak(_)) | (Node::Break(_), Node::Break(_))
+            )
+        });
+    }
+}
+
+/// Check whether `node` can be removed because it has no content.
+fn is_empty(node: &Node) -> bool {
+    match node {
+        Node::Paragraph(_) | Node::Emphasis(_) | Node::Strong(_) => {
+            node.children().map_or(true, |children| {
+                children.iter().all(|child| match child {
+                    Node::Text(text) => text.value.trim().is_empty(),
+                    _ => false,
+                })
+            })
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,79 @@
+//! Strip nodes of certain kinds from a tree.
+
+use crate::mdast::{Node, NodeKind};
+use alloc::vec::Vec;
+
+/// Remove all nodes of one of `kinds` from `tree`, including their children.
+///
+/// The node passed as `tree` itself is never removed.
+///
+/// This can be used to remove raw HTML when a document comes from an
+/// untrusted source (`NodeKind::Html`), to drop images for text-only output
+/// (`NodeKind::Image`, `NodeKind::ImageReference`), or to remove MDX nodes
+/// to export plain markdown.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::NodeKind, to_mdast, transform::strip, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("a <b>c</b>", &ParseOptions::default())?;
+/// strip(&mut tree, &[NodeKind::Html]);
+///
+/// assert_eq!(tree.to_string(), "a c");
+/// # Ok(())
+/// # }
+/// ```
+pub fn strip(tree: &mut Node, kinds: &[NodeKind]) {
+    if let Some(children) = tree.children_mut() {
+        children.retain(|child| !kinds.contains(&child.kind()));
+
+        for child in children {
+            strip(child, kinds);
+        }
+    }
+}
+
+/// Replace all nodes of one of `kinds` in `tree` with their children.
+///
+/// Nodes without children (such as `Image` or `Html`) are removed.
+/// The node passed as `tree` itself is never unwrapped.
+///
+/// Unlike [`strip()`][], this keeps the content of parents: unwrapping
+/// `NodeKind::Link` keeps the text of links but drops their URLs.
+/// Note that unwrapping can produce trees that cannot be represented in
+/// markdown, such as list items outside lists.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::NodeKind, to_mdast, transform::unwrap, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("a [b](c) d", &ParseOptions::default())?;
+/// unwrap(&mut tree, &[NodeKind::Link]);
+///
+/// assert_eq!(tree.children().unwrap()[0].children().unwrap().len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn unwrap(tree: &mut Node, kinds: &[NodeKind]) {
+    if let Some(children) = tree.children_mut() {
+        let mut result = Vec::with_capacity(children.len());
+
+        for mut child in children.drain(..) {
+            unwrap(&mut child, kinds);
+
+            if kinds.contains(&child.kind()) {
+                if let Some(grandchildren) = child.children_mut() {
+                    result.append(grandchildren);
+                }
+            } else {
+                result.push(child);
+            }
+        }
+
+        *children = result;
+    }
+}
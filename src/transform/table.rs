@@ -0,0 +1,191 @@
+//! Change GFM tables.
+
+use crate::mdast::{AlignKind, Node, Table, TableCell, TableRow};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+/// How to compare cells when sorting rows with
+/// [`Table::sort_rows()`][].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableSort {
+    /// Compare the text of cells.
+    Text,
+    /// Compare the text of cells as numbers.
+    ///
+    /// Cells that are not numbers are sorted after cells that are.
+    Numeric,
+}
+
+impl Table {
+    /// Sort the rows of the table, except for the header row, by the cells in
+    /// `column`.
+    ///
+    /// Sorting is stable.
+    /// Rows without a cell in `column` are sorted as if the cell is empty.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, transform::TableSort, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let mut tree = to_mdast("| a |\n| - |\n| c |\n| b |", &ParseOptions::gfm())?;
+    ///
+    /// if let Node::Table(table) = &mut tree.children_mut().unwrap()[0] {
+    ///     table.sort_rows(0, TableSort::Text, false);
+    /// }
+    ///
+    /// assert_eq!(tree.to_string(), "abc");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sort_rows(&mut self, column: usize, sort: TableSort, descending: bool) {
+        if self.children.len() < 2 {
+            return;
+        }
+
+        let mut rows = self.children.split_off(1);
+
+        rows.sort_by(|a, b| {
+            let a = cell_text(a, column);
+            let b = cell_text(b, column);
+            let order = match sort {
+                TableSort::Text => a.cmp(&b),
+                TableSort::Numeric => match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                    (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(&b),
+                },
+            };
+
+            if descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+
+        self.children.append(&mut rows);
+    }
+
+    /// Insert an empty column at `index`, aligned with `align`.
+    ///
+    /// Rows that are too short are padded with empty cells.
+    pub fn insert_column(&mut self, index: usize, align: AlignKind) {
+        while self.align.len() < index {
+            self.align.push(AlignKind::None);
+        }
+        self.align.insert(index, align);
+
+        for row in self.rows_mut() {
+            while row.len() < index {
+                row.push(empty_cell());
+            }
+            row.insert(index, empty_cell());
+        }
+    }
+
+    /// Remove the column at `index`.
+    pub fn remove_column(&mut self, index: usize) {
+        if index < self.align.len() {
+            self.align.remove(index);
+        }
+
+        for row in self.rows_mut() {
+            if index < row.len() {
+                row.remove(index);
+            }
+        }
+    }
+
+    /// Move the column at `from` to `to`, shifting the columns in between.
+    ///
+    /// Rows that are too short are padded with empty cells.
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        let size = from.max(to) + 1;
+
+        while self.align.len() < size {
+            self.align.push(AlignKind::None);
+        }
+        let align = self.align.remove(from);
+        self.align.insert(to, align);
+
+        for row in self.rows_mut() {
+            while row.len() < size {
+                row.push(empty_cell());
+            }
+            let cell = row.remove(from);
+            row.insert(to, cell);
+        }
+    }
+
+    /// Set the alignment of the column at `index`.
+    pub fn set_align(&mut self, index: usize, align: AlignKind) {
+        while self.align.len() <= index {
+            self.align.push(AlignKind::None);
+        }
+        self.align[index] = align;
+    }
+
+    /// Swap rows and columns.
+    ///
+    /// The first row (the header) becomes the first column.
+    /// Rows that are too short are padded with empty cells.
+    /// As alignment applies to columns, all columns are unaligned afterwards.
+    pub fn transpose(&mut self) {
+        let mut grid = self
+            .children
+            .drain(..)
+            .map(|row| match row {
+                Node::TableRow(row) => row.children,
+                _ => vec![],
+            })
+            .collect::<Vec<_>>();
+        let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let mut rows = Vec::with_capacity(columns);
+
+        for row in &mut grid {
+            row.resize(columns, empty_cell());
+            row.reverse();
+        }
+
+        for _ in 0..columns {
+            rows.push(Node::TableRow(TableRow {
+                children: grid.iter_mut().map(|row| row.pop().unwrap()).collect(),
+                position: None,
+            }));
+        }
+
+        self.align = vec![AlignKind::None; grid.len()];
+        self.children = rows;
+    }
+
+    /// Get the cells of each row.
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut Vec<Node>> {
+        self.children.iter_mut().filter_map(|row| match row {
+            Node::TableRow(row) => Some(&mut row.children),
+            _ => None,
+        })
+    }
+}
+
+/// Get the text of the cell at `column` in `row`.
+fn cell_text(row: &Node, column: usize) -> String {
+    row.children()
+        .and_then(|cells| cells.get(column))
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
+
+/// Make an empty cell.
+fn empty_cell() -> Node {
+    Node::TableCell(TableCell {
+        children: vec![],
+        position: None,
+    })
+}
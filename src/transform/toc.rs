@@ -0,0 +1,155 @@
+//! Get a table of contents from the headings in a tree.
+
+use crate::mdast::Node;
+use crate::unist::Position;
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Configuration for [`toc()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TocOptions {
+    /// Rank of the shallowest headings that are included.
+    ///
+    /// The default is `1`.
+    pub min_depth: u8,
+    /// Rank of the deepest headings that are included.
+    ///
+    /// The default is `6`.
+    pub max_depth: u8,
+}
+
+impl Default for TocOptions {
+    fn default() -> Self {
+        Self {
+            min_depth: 1,
+            max_depth: 6,
+        }
+    }
+}
+
+/// Heading in a table of contents made by [`toc()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TocEntry {
+    /// Rank of the heading (between `1` and `6`, both including).
+    pub depth: u8,
+    /// Text content of the heading.
+    pub text: String,
+    /// Identifier for the heading, like the ones GitHub makes, such as
+    /// `hello-world` for `Hello, world!`.
+    ///
+    /// Slugs are unique in a tree: repeated slugs get a suffix, such as
+    /// `hello-world-1`.
+    pub slug: String,
+    /// Positional info of the heading.
+    pub position: Option<Position>,
+    /// Headings in the section of this heading.
+    pub children: Vec<TocEntry>,
+}
+
+/// Get the headings in `tree`, as a hierarchy.
+///
+/// Only headings that are children of `tree`, with a rank between
+/// `min_depth` and `max_depth`, are included.
+/// Headings are nested in the closest heading before them that has a lower
+/// rank, even when ranks are skipped (such as an `h3` directly after an
+/// `h1`).
+/// Slugs are made for all headings that are children of `tree`, so that they
+/// stay the same when `min_depth` or `max_depth` change.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, transform::{toc, TocOptions}, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# Hello\n## *World*\n## Hello", &ParseOptions::default())?;
+/// let entries = toc(&tree, &TocOptions::default());
+///
+/// assert_eq!(entries.len(), 1);
+/// assert_eq!(entries[0].slug, "hello");
+/// assert_eq!(entries[0].children[0].text, "World");
+/// assert_eq!(entries[0].children[1].slug, "hello-1");
+/// # Ok(())
+/// # }
+/// ```
+pub fn toc(tree: &Node, options: &TocOptions) -> Vec<TocEntry> {
+    let mut result = vec![];
+    // Entries whose sections are not yet closed, shallowest first.
+    let mut open: Vec<TocEntry> = vec![];
+    let mut slugs = BTreeMap::new();
+
+    for child in tree.children().into_iter().flatten() {
+        if let Node::Heading(heading) = child {
+            let text = child.to_string();
+            let slug = unique(&mut slugs, &slug(&text));
+
+            if heading.depth < options.min_depth || heading.depth > options.max_depth {
+                continue;
+            }
+
+            close(&mut open, &mut result, heading.depth);
+            open.push(TocEntry {
+                depth: heading.depth,
+                text,
+                slug,
+                position: heading.position.clone(),
+                children: vec![],
+            });
+        }
+    }
+
+    close(&mut open, &mut result, 0);
+    result
+}
+
+/// Close the open entries with a rank of at least `depth`.
+fn close(open: &mut Vec<TocEntry>, result: &mut Vec<TocEntry>, depth: u8) {
+    while let Some(entry) = open.pop() {
+        if entry.depth < depth {
+            open.push(entry);
+            break;
+        }
+
+        match open.last_mut() {
+            Some(parent) => parent.children.push(entry),
+            None => result.push(entry),
+        }
+    }
+}
+
+/// Turn `text` into a slug: lowercase, with whitespace turned into dashes,
+/// and without punctuation and symbols other than `-` and `_`.
+fn slug(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|char| {
+            if char.is_whitespace() {
+                Some('-')
+            } else if char.is_alphanumeric() || char == '-' || char == '_' {
+                Some(char)
+            } else {
+                None
+            }
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Make `slug` unique among `slugs`, by adding a number if it is taken.
+fn unique(slugs: &mut BTreeMap<String, usize>, slug: &str) -> String {
+    let mut result = slug.to_string();
+
+    while slugs.contains_key(&result) {
+        let count = slugs.entry(slug.to_string()).or_insert(0);
+        *count += 1;
+        result = format!("{}-{}", slug, count);
+    }
+
+    slugs.insert(result.clone(), 0);
+    result
+}
@@ -0,0 +1,317 @@
+//! Truncate a tree to a certain amount of text.
+
+use crate::mdast::{Node, Paragraph, Text};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Unit and size of the text to keep when truncating.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TruncateLimit {
+    /// Keep this many characters (Unicode scalar values), whitespace
+    /// included.
+    Characters(usize),
+    /// Keep this many words (runs of non-whitespace).
+    Words(usize),
+}
+
+/// Info on where we are in the tree while truncating.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug)]
+struct State<'a> {
+    /// Whether to count words (`true`) or characters (`false`).
+    words: bool,
+    /// Number of units that can still be kept.
+    remaining: usize,
+    /// Whether we are in a word, which continues in the next text.
+    in_word: bool,
+    /// Whether something was cut.
+    done: bool,
+    /// Whether the ellipsis was placed.
+    ellipsis_placed: bool,
+    /// Value to add where text was cut.
+    ellipsis: Option<&'a str>,
+}
+
+/// Make a copy of `tree` that contains up to `limit` characters or words of
+/// text, optionally ending with `ellipsis` where text was cut.
+///
+/// The result is valid: parents are never left open and are dropped when
+/// they would end up empty.
+/// The values of code and math are never cut: when they do not fit, they are
+/// dropped completely.
+/// Nodes without text (such as breaks, images, or definitions) do not count
+/// toward the limit.
+///
+/// When something was cut and `ellipsis` is given, it is added as a `Text`
+/// node in the innermost phrasing parent (or as a paragraph in the innermost
+/// flow parent) that contains the cut.
+/// The positions of nodes that were cut still describe the whole original
+/// nodes.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     to_mdast,
+///     transform::{truncate, TruncateLimit},
+///     ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("a *b c* d", &ParseOptions::default())?;
+/// let preview = truncate(&tree, TruncateLimit::Words(2), Some("…"));
+///
+/// assert_eq!(preview.to_string(), "a b…");
+/// # Ok(())
+/// # }
+/// ```
+pub fn truncate(tree: &Node, limit: TruncateLimit, ellipsis: Option<&str>) -> Node {
+    let (words, remaining) = match limit {
+        TruncateLimit::Characters(size) => (false, size),
+        TruncateLimit::Words(size) => (true, size),
+    };
+    let mut state = State {
+        words,
+        remaining,
+        in_word: false,
+        done: false,
+        ellipsis_placed: false,
+        ellipsis,
+    };
+
+    if let Some(result) = visit(tree, &mut state) {
+        result
+    } else {
+        // The root is never dropped.
+        let mut result = tree.clone();
+        if let Some(children) = result.children_mut() {
+            children.clear();
+        }
+        result
+    }
+}
+
+/// Make a truncated copy of `node`, or `None` if nothing of it is kept.
+fn visit(node: &Node, state: &mut State) -> Option<Node> {
+    if state.done {
+        return None;
+    }
+
+    if !phrasing(node) {
+        state.in_word = false;
+    }
+
+    match node {
+        Node::Text(text) => {
+            let value = cut(&text.value, state);
+
+            if value.is_empty() {
+                None
+            } else {
+                Some(Node::Text(Text {
+                    value: value.into(),
+                    position: text.position.clone(),
+                }))
+            }
+        }
+        Node::CharacterReference(_)
+        | Node::Emoji(_)
+        | Node::Abbreviation(_)
+        | Node::InlineCode(_)
+        | Node::InlineMath(_)
+        | Node::Code(_)
+        | Node::Math(_) => {
+            let value = node.to_string();
+            let (size, in_word) = measure(&value, state);
+
+            if size > state.remaining {
+                state.done = true;
+                None
+            } else {
+                state.remaining -= size;
+                state.in_word = in_word;
+                Some(node.clone())
+            }
+        }
+        _ => {
+            if let Some(children) = node.children() {
+                let mut result = Vec::with_capacity(children.len());
+
+                for child in children {
+                    if let Some(child) = visit(child, state) {
+                        result.push(child);
+                    }
+
+                    if state.done {
+                        break;
+                    }
+                }
+
+                if state.done && !state.ellipsis_placed {
+                    place_ellipsis(node, &mut result, state);
+                }
+
+                if result.is_empty() && state.done && !children.is_empty() {
+                    return None;
+                }
+
+                let mut copy = node.clone();
+                *copy.children_mut().unwrap() = result;
+                Some(copy)
+            } else {
+                Some(node.clone())
+            }
+        }
+    }
+}
+
+/// Add the ellipsis to `children` of `parent`, if it can contain it.
+fn place_ellipsis(parent: &Node, children: &mut Vec<Node>, state: &mut State) {
+    let text = if let Some(ellipsis) = state.ellipsis {
+        ellipsis
+    } else {
+        state.ellipsis_placed = true;
+        return;
+    };
+
+    match parent {
+        Node::Paragraph(_)
+        | Node::Heading(_)
+        | Node::TableCell(_)
+        | Node::Emphasis(_)
+        | Node::Strong(_)
+        | Node::Delete(_)
+        | Node::Highlight(_)
+        | Node::Insert(_)
+        | Node::CriticAddition(_)
+        | Node::CriticDeletion(_)
+        | Node::CriticHighlight(_)
+        | Node::CriticComment(_)
+        | Node::Link(_)
+        | Node::LinkReference(_)
+        | Node::MdxJsxTextElement(_) => {
+            if matches!(children.last(), Some(Node::Break(_))) {
+                children.pop();
+            }
+
+            if let Some(Node::Text(last)) = children.last_mut() {
+                last.value.truncate(last.value.trim_end().len());
+                last.value.push_str(text);
+            } else {
+                children.push(Node::Text(Text {
+                    value: text.into(),
+                    position: None,
+                }));
+            }
+        }
+        Node::Root(_)
+        | Node::BlockQuote(_)
+        | Node::ListItem(_)
+        | Node::FootnoteDefinition(_)
+        | Node::MdxJsxFlowElement(_) => {
+            children.push(Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: text.into(),
+                    position: None,
+                })],
+                position: None,
+            }));
+        }
+        _ => return,
+    }
+
+    state.ellipsis_placed = true;
+}
+
+/// Keep as much of `value` as fits, marking the state as done if something
+/// was cut.
+fn cut(value: &str, state: &mut State) -> String {
+    let mut end = value.len();
+
+    for (index, char) in value.char_indices() {
+        let whitespace = char.is_whitespace();
+
+        if state.words {
+            if !whitespace && !state.in_word {
+                if state.remaining == 0 {
+                    end = index;
+                    break;
+                }
+                state.remaining -= 1;
+            }
+        } else {
+            if state.remaining == 0 {
+                end = index;
+                break;
+            }
+            state.remaining -= 1;
+        }
+
+        state.in_word = !whitespace;
+    }
+
+    if end == value.len() {
+        value.into()
+    } else {
+        state.done = true;
+        value[0..end].trim_end().into()
+    }
+}
+
+/// Get the size of `value` in the current unit, and whether it ends in a
+/// word.
+fn measure(value: &str, state: &State) -> (usize, bool) {
+    let mut in_word = state.in_word;
+    let mut size = 0;
+
+    for char in value.chars() {
+        let whitespace = char.is_whitespace();
+
+        if !state.words || (!whitespace && !in_word) {
+            size += 1;
+        }
+
+        in_word = !whitespace;
+    }
+
+    (size, in_word)
+}
+
+/// Check if `node` is phrasing content.
+fn phrasing(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Break(_)
+            | Node::BlockId(_)
+            | Node::CharacterReference(_)
+            | Node::Cite(_)
+            | Node::Emoji(_)
+            | Node::Abbreviation(_)
+            | Node::InlineCode(_)
+            | Node::InlineMath(_)
+            | Node::Delete(_)
+            | Node::Emphasis(_)
+            | Node::Highlight(_)
+            | Node::Insert(_)
+            | Node::CriticAddition(_)
+            | Node::CriticDeletion(_)
+            | Node::CriticSubstitution(_)
+            | Node::CriticHighlight(_)
+            | Node::CriticComment(_)
+            | Node::MdxTextExpression(_)
+            | Node::FootnoteReference(_)
+            | Node::Html(_)
+            | Node::Comment(_)
+            | Node::Image(_)
+            | Node::ImageReference(_)
+            | Node::MdxJsxTextElement(_)
+            | Node::Link(_)
+            | Node::LinkReference(_)
+            | Node::Strong(_)
+            | Node::Text(_)
+            | Node::WikiLink(_)
+    )
+}
@@ -0,0 +1,107 @@
+//! Remove definitions and footnote definitions that are never referenced.
+
+use crate::mdast::Node;
+use crate::util::normalize_identifier::normalize_identifier;
+use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
+
+/// Remove [`Definition`][crate::mdast::Definition]s and
+/// [`FootnoteDefinition`][crate::mdast::FootnoteDefinition]s that are not
+/// referenced anywhere in `tree`.
+///
+/// Definitions are matched with link and image references, footnote
+/// definitions with footnote references, on their normalized identifier.
+/// References that only occur inside unused footnote definitions do not
+/// count: removing such a footnote definition can make other definitions
+/// unused, which are then removed too.
+///
+/// Returns the removed nodes, in the order they were found, so that callers
+/// can report them.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, transform::remove_unused_definitions, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("[a]\n\n[a]: b\n[c]: d", &ParseOptions::default())?;
+/// let removed = remove_unused_definitions(&mut tree);
+///
+/// assert_eq!(removed.len(), 1);
+/// assert_eq!(tree.children().unwrap().len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn remove_unused_definitions(tree: &mut Node) -> Vec<Node> {
+    let mut removed = vec![];
+
+    loop {
+        let mut references = References::default();
+        collect(tree, &mut references);
+        let before = removed.len();
+        remove(tree, &references, &mut removed);
+
+        if removed.len() == before {
+            break;
+        }
+    }
+
+    removed
+}
+
+/// Identifiers that are referenced.
+#[derive(Debug, Default)]
+struct References {
+    /// Identifiers of link and image references.
+    definitions: BTreeSet<String>,
+    /// Identifiers of footnote references.
+    footnotes: BTreeSet<String>,
+}
+
+/// Turn an identifier into a form that can be compared.
+fn key(identifier: &str) -> String {
+    normalize_identifier(identifier).to_lowercase()
+}
+
+/// Collect all references in `node`.
+fn collect(node: &Node, references: &mut References) {
+    match node {
+        Node::LinkReference(x) => {
+            references.definitions.insert(key(&x.identifier));
+        }
+        Node::ImageReference(x) => {
+            references.definitions.insert(key(&x.identifier));
+        }
+        Node::FootnoteReference(x) => {
+            references.footnotes.insert(key(&x.identifier));
+        }
+        _ => {}
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            collect(child, references);
+        }
+    }
+}
+
+/// Remove unreferenced definitions from `node`, adding them to `removed`.
+fn remove(node: &mut Node, references: &References, removed: &mut Vec<Node>) {
+    if let Some(children) = node.children_mut() {
+        let mut index = 0;
+
+        while index < children.len() {
+            let used = match &children[index] {
+                Node::Definition(x) => references.definitions.contains(&key(&x.identifier)),
+                Node::FootnoteDefinition(x) => references.footnotes.contains(&key(&x.identifier)),
+                _ => true,
+            };
+
+            if used {
+                remove(&mut children[index], references, removed);
+                index += 1;
+            } else {
+                removed.push(children.remove(index));
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! [unist]: https://github.com/syntax-tree/unist
 
+use crate::util::constant::TAB_SIZE;
 use alloc::fmt;
 
 /// One place in a source file.
@@ -13,6 +14,10 @@ pub struct Point {
     /// 1-indexed integer representing a column in a source file.
     pub column: usize,
     /// 0-indexed integer representing a character in a source file.
+    ///
+    /// With the `serde` feature, points without offset get `0`, as offsets
+    /// are optional in unist.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub offset: usize,
 }
 
@@ -25,6 +30,98 @@ impl Point {
             offset,
         }
     }
+
+    /// Turn this point into a UTF-16 point in `value`, the source file it
+    /// points into.
+    ///
+    /// Uses `line` and `offset`.
+    /// Offsets that are in the middle of a character are moved to the start
+    /// of that character.
+    #[must_use]
+    pub fn to_utf16(&self, value: &str) -> Utf16Point {
+        let mut offset = self.offset.min(value.len());
+
+        while !value.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let start = value.as_bytes()[..offset]
+            .iter()
+            .rposition(|byte| matches!(byte, b'\n' | b'\r'))
+            .map_or(0, |index| index + 1);
+
+        Utf16Point {
+            line: self.line.saturating_sub(1),
+            character: value[start..offset].encode_utf16().count(),
+        }
+    }
+
+    /// Turn a UTF-16 point in `value`, the source file it points into, into a
+    /// point.
+    ///
+    /// Returns `None` if the line does not exist.
+    /// Characters past the end of the line are moved to the end of the line,
+    /// characters in the middle of a surrogate pair to the start of that
+    /// pair.
+    /// Like the parser, columns count bytes, and count tabs up to the next
+    /// tab stop (every 4 columns, or another
+    /// [`tab_size`][crate::ParseOptions::tab_size] with
+    /// [`from_utf16_with_tab_size()`][Point::from_utf16_with_tab_size]).
+    #[must_use]
+    pub fn from_utf16(value: &str, point: &Utf16Point) -> Option<Point> {
+        Point::from_utf16_with_tab_size(value, point, TAB_SIZE)
+    }
+
+    /// Turn a UTF-16 point in `value`, the source file it points into, into a
+    /// point, with tabs stopping at every `tab_size` columns.
+    ///
+    /// ## Panics
+    ///
+    /// This panics when `tab_size` is `0`.
+    #[must_use]
+    pub fn from_utf16_with_tab_size(
+        value: &str,
+        point: &Utf16Point,
+        tab_size: usize,
+    ) -> Option<Point> {
+        assert!(tab_size > 0, "expected `tab_size` to be more than `0`");
+        let bytes = value.as_bytes();
+        let mut index = 0;
+        let mut line = 0;
+
+        while line < point.line {
+            let byte = *bytes.get(index)?;
+            index += 1;
+
+            if byte == b'\n' || (byte == b'\r' && bytes.get(index) != Some(&b'\n')) {
+                line += 1;
+            }
+        }
+
+        let mut column = 1;
+        let mut character = 0;
+
+        for char in value[index..].chars() {
+            if matches!(char, '\n' | '\r') || character + char.len_utf16() > point.character {
+                break;
+            }
+
+            character += char.len_utf16();
+            index += char.len_utf8();
+            column += if char == '\t' {
+                let remainder = column % tab_size;
+                1 + if remainder == 0 {
+                    0
+                } else {
+                    tab_size - remainder
+                }
+            } else {
+                char.len_utf8()
+            };
+        }
+
+        Some(Point::new(point.line + 1, column, index))
+    }
 }
 
 impl fmt::Debug for Point {
@@ -61,6 +158,32 @@ impl Position {
     }
 }
 
+impl Position {
+    /// Turn this position into a UTF-16 position in `value`, the source file
+    /// it points into.
+    ///
+    /// See [`Point::to_utf16()`][] for more info.
+    #[must_use]
+    pub fn to_utf16(&self, value: &str) -> Utf16Position {
+        Utf16Position {
+            start: self.start.to_utf16(value),
+            end: self.end.to_utf16(value),
+        }
+    }
+
+    /// Turn a UTF-16 position in `value`, the source file it points into,
+    /// into a position.
+    ///
+    /// See [`Point::from_utf16()`][] for more info.
+    #[must_use]
+    pub fn from_utf16(value: &str, position: &Utf16Position) -> Option<Position> {
+        Some(Position {
+            start: Point::from_utf16(value, &position.start)?,
+            end: Point::from_utf16(value, &position.end)?,
+        })
+    }
+}
+
 impl fmt::Debug for Position {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -76,6 +199,33 @@ impl fmt::Debug for Position {
     }
 }
 
+/// One place in a source file, in UTF-16 code units.
+///
+/// This is how the
+/// [language server protocol](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#position)
+/// and JavaScript count; convert from and to [`Point`][] with
+/// [`Point::from_utf16()`][] and [`Point::to_utf16()`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Utf16Point {
+    /// 0-indexed integer representing a line in a source file.
+    pub line: usize,
+    /// 0-indexed integer representing a UTF-16 code unit in a line.
+    pub character: usize,
+}
+
+/// Location of a node in a source file, in UTF-16 code units.
+///
+/// See [`Utf16Point`][] for more info.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Utf16Position {
+    /// Represents the place of the first character of the source region.
+    pub start: Utf16Point,
+    /// Represents the place of the first character after the source region.
+    pub end: Utf16Point,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +250,103 @@ mod tests {
             "should support `Debug` on unist positions"
         );
     }
+
+    #[test]
+    fn utf16() {
+        let value = "a 😀 b\r\n\té\rc";
+
+        assert_eq!(
+            Point::new(1, 8, 7).to_utf16(value),
+            Utf16Point {
+                line: 0,
+                character: 5
+            },
+            "should count astral characters as two code units"
+        );
+
+        assert_eq!(
+            Point::new(2, 7, 13).to_utf16(value),
+            Utf16Point {
+                line: 1,
+                character: 2
+            },
+            "should count from the start of the line (CRLF)"
+        );
+
+        assert_eq!(
+            Point::new(3, 1, 14).to_utf16(value),
+            Utf16Point {
+                line: 2,
+                character: 0
+            },
+            "should count from the start of the line (CR)"
+        );
+
+        assert_eq!(
+            Point::from_utf16(
+                value,
+                &Utf16Point {
+                    line: 0,
+                    character: 5
+                }
+            ),
+            Some(Point::new(1, 8, 7)),
+            "should turn UTF-16 points into points"
+        );
+
+        assert_eq!(
+            Point::from_utf16(
+                value,
+                &Utf16Point {
+                    line: 0,
+                    character: 3
+                }
+            ),
+            Some(Point::new(1, 3, 2)),
+            "should move points in surrogate pairs back"
+        );
+
+        assert_eq!(
+            Point::from_utf16(
+                value,
+                &Utf16Point {
+                    line: 1,
+                    character: 2
+                }
+            ),
+            Some(Point::new(2, 7, 13)),
+            "should count tabs up to the next tab stop"
+        );
+
+        assert_eq!(
+            Point::from_utf16(
+                value,
+                &Utf16Point {
+                    line: 1,
+                    character: 10
+                }
+            ),
+            Some(Point::new(2, 7, 13)),
+            "should move points past the end of a line back"
+        );
+
+        assert_eq!(
+            Point::from_utf16(
+                value,
+                &Utf16Point {
+                    line: 3,
+                    character: 0
+                }
+            ),
+            None,
+            "should not find missing lines"
+        );
+
+        let position = Position::new(1, 3, 2, 3, 2, 15);
+        assert_eq!(
+            Position::from_utf16(value, &position.to_utf16(value)),
+            Some(position),
+            "should turn positions into UTF-16 positions and back"
+        );
+    }
 }
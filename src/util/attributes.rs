@@ -0,0 +1,164 @@
+//! Parse the value of attributes (such as `#id .class key="value"`).
+
+use crate::mdast::Attributes;
+use alloc::string::String;
+
+/// Parse `value`, what is between the braces of attributes.
+///
+/// Attributes are separated by whitespace.
+/// Each attribute is an identifier (`#id`), a class (`.class`), or a key and
+/// value (`key=value`, `key="value"`, or `key='value'`).
+/// The keys `id` and `class` set the identifier and add classes.
+///
+/// Returns `None` if `value` is empty or not valid.
+pub fn parse(value: &str) -> Option<Attributes> {
+    let bytes = value.as_bytes();
+    let mut attributes = Attributes::default();
+    let mut seen = false;
+    let mut index = 0;
+
+    loop {
+        let start = index;
+
+        while index < bytes.len() && matches!(bytes[index], b'\t' | b' ') {
+            index += 1;
+        }
+
+        if index == bytes.len() {
+            break;
+        }
+
+        // Attributes must be separated by whitespace.
+        if seen && index == start {
+            return None;
+        }
+
+        seen = true;
+
+        if matches!(bytes[index], b'#' | b'.') {
+            let marker = bytes[index];
+            index += 1;
+            let start = index;
+
+            while index < bytes.len() && is_value(bytes[index]) {
+                index += 1;
+            }
+
+            if index == start {
+                return None;
+            }
+
+            let name = String::from(&value[start..index]);
+
+            if marker == b'#' {
+                attributes.id = Some(name);
+            } else {
+                attributes.classes.push(name);
+            }
+        } else {
+            let start = index;
+
+            while index < bytes.len() && is_key(bytes[index], index == start) {
+                index += 1;
+            }
+
+            if index == start || index == bytes.len() || bytes[index] != b'=' {
+                return None;
+            }
+
+            let key = &value[start..index];
+            index += 1;
+
+            let result = if index < bytes.len() && matches!(bytes[index], b'"' | b'\'') {
+                let quote = bytes[index];
+                index += 1;
+                let start = index;
+
+                while index < bytes.len() && bytes[index] != quote {
+                    index += 1;
+                }
+
+                if index == bytes.len() {
+                    return None;
+                }
+
+                index += 1;
+                &value[start..index - 1]
+            } else {
+                let start = index;
+
+                while index < bytes.len() && is_value(bytes[index]) {
+                    index += 1;
+                }
+
+                if index == start {
+                    return None;
+                }
+
+                &value[start..index]
+            };
+
+            match key {
+                "id" => attributes.id = Some(result.into()),
+                "class" => attributes
+                    .classes
+                    .extend(result.split_ascii_whitespace().map(String::from)),
+                _ => attributes.pairs.push((key.into(), result.into())),
+            }
+        }
+    }
+
+    if seen {
+        Some(attributes)
+    } else {
+        None
+    }
+}
+
+/// Check whether `byte` can occur in a key.
+///
+/// Keys start with an ASCII letter, `_`, or `:`, and can then also contain
+/// digits, `-`, and `.`.
+fn is_key(byte: u8, first: bool) -> bool {
+    matches!(byte, b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z')
+        || (!first && matches!(byte, b'-' | b'.' | b'0'..=b'9'))
+}
+
+/// Check whether `byte` can occur in an unquoted value, identifier, or class.
+fn is_value(byte: u8) -> bool {
+    !matches!(
+        byte,
+        b'\t' | b'\n' | b'\r' | b' ' | b'"' | b'\'' | b'<' | b'=' | b'>' | b'`' | b'{' | b'}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn attributes() {
+        assert_eq!(parse(""), None, "should not support empty attributes");
+
+        assert_eq!(
+            parse(" #a .b.c  d=e f='g h' id=i class=\"j k\" "),
+            Some(Attributes {
+                id: Some("i".into()),
+                classes: vec!["b.c".into(), "j".into(), "k".into()],
+                pairs: vec![("d".into(), "e".into()), ("f".into(), "g h".into())]
+            }),
+            "should support identifiers, classes, and keys w/ values"
+        );
+
+        assert_eq!(parse("1,3"), None, "should not support ranges");
+        assert_eq!(parse("a"), None, "should not support keys w/o values");
+        assert_eq!(parse("#"), None, "should not support empty identifiers");
+        assert_eq!(parse("a=\"b"), None, "should not support unclosed quotes");
+        assert_eq!(
+            parse("a='b'.c"),
+            None,
+            "should not support attributes w/o whitespace between them"
+        );
+    }
+}
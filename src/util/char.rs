@@ -107,6 +107,58 @@ pub fn classify_opt(char_opt: Option<char>) -> Kind {
     char_opt.map_or(Kind::Whitespace, classify)
 }
 
+/// Get the number of columns that `char` takes up in a monospace font.
+///
+/// East Asian wide and fullwidth characters (such as CJK ideographs, and
+/// emoji) take up two columns, and combining marks, zero width characters,
+/// and variation selectors none.
+/// Control characters are treated as taking up one column.
+///
+/// ## References
+///
+/// *   [*UAX #11: East Asian Width*](https://www.unicode.org/reports/tr11/)
+pub fn width(char: char) -> usize {
+    match char {
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200B}'..='\u{200F}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{FEFF}' => 0,
+        '\u{1100}'..='\u{115F}'
+        | '\u{231A}'..='\u{231B}'
+        | '\u{2329}'..='\u{232A}'
+        | '\u{2E80}'..='\u{303E}'
+        | '\u{3041}'..='\u{33FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{A000}'..='\u{A4CF}'
+        | '\u{A960}'..='\u{A97F}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE10}'..='\u{FE19}'
+        | '\u{FE30}'..='\u{FE6F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1F300}'..='\u{1F64F}'
+        | '\u{1F900}'..='\u{1F9FF}'
+        | '\u{20000}'..='\u{2FFFD}'
+        | '\u{30000}'..='\u{3FFFD}' => 2,
+        _ => 1,
+    }
+}
+
+/// Check whether `char` is an East Asian wide or fullwidth character that
+/// is used in prose without spaces between words (such as CJK ideographs,
+/// kana, and fullwidth punctuation).
+///
+/// Unlike [`width`], this does not include emoji.
+pub fn east_asian_wide(char: char) -> bool {
+    !matches!(char, '\u{1F300}'..='\u{1F64F}' | '\u{1F900}'..='\u{1F9FF}') && width(char) == 2
+}
+
 /// Format an optional `char` (`none` means eof).
 pub fn format_opt(char: Option<char>) -> String {
     char.map_or("end of file".into(), |char| {
@@ -176,6 +228,40 @@ mod tests {
         assert_eq!(classify('a'), Kind::Other, "should classify other");
     }
 
+    #[test]
+    fn test_width() {
+        assert_eq!(width('a'), 1, "should count ASCII as one column");
+        assert_eq!(
+            width('é'),
+            1,
+            "should count other narrow characters as one column"
+        );
+        assert_eq!(
+            width('\u{0301}'),
+            0,
+            "should count combining marks as no columns"
+        );
+        assert_eq!(width('中'), 2, "should count CJK ideographs as two columns");
+        assert_eq!(
+            width('Ａ'),
+            2,
+            "should count fullwidth forms as two columns"
+        );
+        assert_eq!(width('😀'), 2, "should count emoji as two columns");
+    }
+
+    #[test]
+    fn test_east_asian_wide() {
+        assert!(east_asian_wide('中'), "should support CJK ideographs");
+        assert!(east_asian_wide('か'), "should support kana");
+        assert!(
+            east_asian_wide('。'),
+            "should support fullwidth punctuation"
+        );
+        assert!(!east_asian_wide('a'), "should not support ASCII");
+        assert!(!east_asian_wide('😀'), "should not support emoji");
+    }
+
     #[test]
     fn test_format_opt() {
         assert_eq!(
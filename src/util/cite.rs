@@ -0,0 +1,451 @@
+//! Parse citations (such as `[see @doe99, p. 33; @smith04]` or `@doe99`).
+
+use crate::mdast::{Citation, CitationMode};
+use alloc::{string::String, vec, vec::Vec};
+
+/// Terms that start a locator (such as `p.` in `p. 33`).
+const LOCATORS: [&str; 54] = [
+    "bk.",
+    "bks.",
+    "book",
+    "books",
+    "chap.",
+    "chaps.",
+    "chapter",
+    "chapters",
+    "col.",
+    "cols.",
+    "column",
+    "columns",
+    "fig.",
+    "figs.",
+    "figure",
+    "figures",
+    "l.",
+    "ll.",
+    "line",
+    "lines",
+    "n.",
+    "nn.",
+    "no.",
+    "nos.",
+    "note",
+    "notes",
+    "number",
+    "numbers",
+    "p.",
+    "pp.",
+    "page",
+    "pages",
+    "para.",
+    "paras.",
+    "paragraph",
+    "paragraphs",
+    "pt.",
+    "pts.",
+    "part",
+    "parts",
+    "sec.",
+    "secs.",
+    "section",
+    "sections",
+    "v.",
+    "vv.",
+    "verse",
+    "verses",
+    "vol.",
+    "vols.",
+    "volume",
+    "volumes",
+    "§",
+    "§§",
+];
+
+/// Parse the citations at the start of `value`.
+///
+/// `value` starts with `[`, for citations in brackets (such as
+/// `[see @doe99, p. 33; -@smith04]`), or with `@`, for a citation in text
+/// (such as `@doe99`, optionally followed by a space and a locator or suffix
+/// in brackets, as in `@doe99 [p. 33]`).
+///
+/// Returns the number of bytes that the citations take up, and the
+/// citations, or `None` if `value` does not start with citations.
+pub fn parse(value: &str) -> Option<(usize, Vec<Citation>)> {
+    if value.starts_with('[') {
+        let size = bracket(value)?;
+        let mut citations = vec![];
+
+        for item in value[1..size - 1].split(';') {
+            citations.push(citation(item)?);
+        }
+
+        Some((size, citations))
+    } else if let Some(rest) = value.strip_prefix('@') {
+        let (key, key_size) = key(rest)?;
+        let mut size = 1 + key_size;
+        let mut affix = "";
+
+        // A locator or suffix in brackets, which cannot hold citations.
+        if value[size..].starts_with(" [") {
+            if let Some(bracket_size) = bracket(&value[size + 1..]) {
+                let inside = &value[size + 2..size + bracket_size];
+
+                if !inside.contains('@') {
+                    affix = inside;
+                    size += 1 + bracket_size;
+                }
+            }
+        }
+
+        let (locator, suffix) = split_suffix(affix);
+
+        Some((
+            size,
+            vec![Citation {
+                key,
+                mode: CitationMode::AuthorInText,
+                prefix: None,
+                locator,
+                suffix,
+            }],
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check whether `value` must be wrapped in braces to be a key.
+pub fn key_needs_braces(value: &str) -> bool {
+    value.starts_with('{') || key(value).map_or(true, |(_, size)| size != value.len())
+}
+
+/// Get the size of the brackets at the start of `value`, including both
+/// brackets.
+///
+/// Brackets cannot contain other brackets, and cannot be followed by `(` or
+/// `[`, as they would form a link then.
+fn bracket(value: &str) -> Option<usize> {
+    let bytes = value.as_bytes();
+    let mut index = 1;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'[' => return None,
+            b']' => {
+                let size = index + 1;
+                return if matches!(bytes.get(size), Some(b'(' | b'[')) {
+                    None
+                } else {
+                    Some(size)
+                };
+            }
+            _ => index += 1,
+        }
+    }
+
+    None
+}
+
+/// Parse one citation in brackets (such as `see @doe99, p. 33`).
+fn citation(value: &str) -> Option<Citation> {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'@' {
+            let suppress = index > 0 && bytes[index - 1] == b'-';
+            let start = if suppress { index - 1 } else { index };
+
+            if start == 0 || matches!(bytes[start - 1], b'\t' | b' ') {
+                if let Some((key, key_size)) = key(&value[index + 1..]) {
+                    let prefix = value[..start].trim();
+                    let (locator, suffix) = split_suffix(&value[index + 1 + key_size..]);
+
+                    return Some(Citation {
+                        key,
+                        mode: if suppress {
+                            CitationMode::SuppressAuthor
+                        } else {
+                            CitationMode::Normal
+                        },
+                        prefix: if prefix.is_empty() {
+                            None
+                        } else {
+                            Some(prefix.into())
+                        },
+                        locator,
+                        suffix,
+                    });
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Parse a key at the start of `value` (after `@`).
+///
+/// Keys are either in braces (such as `{doe 99}`), or start with an
+/// alphanumeric or `_`, and can then also contain punctuation (`:.#$%&-+?<>~/`)
+/// that is followed by an alphanumeric or `_`.
+///
+/// Returns the key and the number of bytes it takes up.
+fn key(value: &str) -> Option<(String, usize)> {
+    if let Some(rest) = value.strip_prefix('{') {
+        let end = rest.find('}')?;
+        return if end == 0 {
+            None
+        } else {
+            Some((rest[..end].into(), end + 2))
+        };
+    }
+
+    let mut end = 0;
+
+    for (index, char) in value.char_indices() {
+        if char.is_alphanumeric() || char == '_' {
+            end = index + char.len_utf8();
+        } else if index == 0 || !":.#$%&-+?<>~/".contains(char) {
+            break;
+        }
+    }
+
+    if end == 0 {
+        None
+    } else {
+        Some((value[..end].into(), end))
+    }
+}
+
+/// Split what comes after a key into a locator (such as `p. 33`) and the
+/// rest of the suffix.
+fn split_suffix(value: &str) -> (Option<String>, Option<String>) {
+    let mut rest = value.trim();
+    rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    let mut locator = None;
+
+    if let Some(space) = rest.find(|char: char| char.is_whitespace()) {
+        if LOCATORS.contains(&&rest[..space]) {
+            let after = rest[space..].trim_start();
+            let size = locator_value(after);
+
+            if size > 0 {
+                let end = rest.len() - after.len() + size;
+                locator = Some(String::from(&rest[..end]));
+                rest = rest[end..].trim_start();
+                rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+            }
+        }
+    }
+
+    let suffix = if rest.is_empty() {
+        None
+    } else {
+        Some(rest.into())
+    };
+
+    (locator, suffix)
+}
+
+/// Get the size of the value of a locator at the start of `value` (such as
+/// `33-35, 38` in `33-35, 38, emphasis added`).
+fn locator_value(value: &str) -> usize {
+    let mut end = 0;
+    let mut chars = value.char_indices().peekable();
+
+    while let Some((index, char)) = chars.next() {
+        if char.is_alphanumeric() || matches!(char, '-' | '–') {
+            end = index + char.len_utf8();
+        } else if char == ',' {
+            // A list of pages continues if a digit follows.
+            let rest = value[index + 1..].trim_start();
+            if !rest.starts_with(|char: char| char.is_ascii_digit()) {
+                break;
+            }
+            while chars.peek().map_or(false, |(_, char)| char.is_whitespace()) {
+                chars.next();
+            }
+        } else {
+            break;
+        }
+    }
+
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cite(
+        key: &str,
+        mode: CitationMode,
+        prefix: Option<&str>,
+        locator: Option<&str>,
+        suffix: Option<&str>,
+    ) -> Citation {
+        Citation {
+            key: key.into(),
+            mode,
+            prefix: prefix.map(Into::into),
+            locator: locator.map(Into::into),
+            suffix: suffix.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn citations() {
+        assert_eq!(
+            parse("[@a] b"),
+            Some((4, vec![cite("a", CitationMode::Normal, None, None, None)])),
+            "should support a citation in brackets"
+        );
+
+        assert_eq!(
+            parse("[see @a, p. 33; -@b, chap. 1, emphasis added]"),
+            Some((
+                45,
+                vec![
+                    cite("a", CitationMode::Normal, Some("see"), Some("p. 33"), None),
+                    cite(
+                        "b",
+                        CitationMode::SuppressAuthor,
+                        None,
+                        Some("chap. 1"),
+                        Some("emphasis added")
+                    )
+                ]
+            )),
+            "should support prefixes, locators, suffixes, and several citations"
+        );
+
+        assert_eq!(
+            parse("[@a, pp. 33-35, 38, and elsewhere]"),
+            Some((
+                34,
+                vec![cite(
+                    "a",
+                    CitationMode::Normal,
+                    None,
+                    Some("pp. 33-35, 38"),
+                    Some("and elsewhere")
+                )]
+            )),
+            "should support lists of pages in locators"
+        );
+
+        assert_eq!(
+            parse("[@a, and elsewhere]"),
+            Some((
+                19,
+                vec![cite(
+                    "a",
+                    CitationMode::Normal,
+                    None,
+                    None,
+                    Some("and elsewhere")
+                )]
+            )),
+            "should support suffixes w/o locator"
+        );
+
+        assert_eq!(
+            parse("@a says"),
+            Some((
+                2,
+                vec![cite("a", CitationMode::AuthorInText, None, None, None)]
+            )),
+            "should support a citation in text"
+        );
+
+        assert_eq!(
+            parse("@a [p. 33] says"),
+            Some((
+                10,
+                vec![cite(
+                    "a",
+                    CitationMode::AuthorInText,
+                    None,
+                    Some("p. 33"),
+                    None
+                )]
+            )),
+            "should support a locator after a citation in text"
+        );
+
+        assert_eq!(
+            parse("@a [see @b]"),
+            Some((
+                2,
+                vec![cite("a", CitationMode::AuthorInText, None, None, None)]
+            )),
+            "should not support citations in brackets after a citation in text as a suffix"
+        );
+
+        assert_eq!(
+            parse("@doe:1999.b."),
+            Some((
+                11,
+                vec![cite(
+                    "doe:1999.b",
+                    CitationMode::AuthorInText,
+                    None,
+                    None,
+                    None
+                )]
+            )),
+            "should support internal punctuation in keys"
+        );
+
+        assert_eq!(
+            parse("@{a b}"),
+            Some((
+                6,
+                vec![cite("a b", CitationMode::AuthorInText, None, None, None)]
+            )),
+            "should support keys in braces"
+        );
+
+        assert_eq!(parse("[a]"), None, "should not support brackets w/o keys");
+        assert_eq!(
+            parse("[@a; b]"),
+            None,
+            "should not support citations w/o keys in brackets"
+        );
+        assert_eq!(parse("[a@b]"), None, "should not support emails");
+        assert_eq!(parse("[@a](b)"), None, "should not support links");
+        assert_eq!(
+            parse("[@a [b]]"),
+            None,
+            "should not support nested brackets"
+        );
+        assert_eq!(parse("[@a"), None, "should not support unclosed brackets");
+        assert_eq!(parse("@"), None, "should not support `@` w/o key");
+        assert_eq!(
+            parse("@{}"),
+            None,
+            "should not support empty keys in braces"
+        );
+        assert_eq!(
+            parse("@.a"),
+            None,
+            "should not support keys starting w/ punctuation"
+        );
+
+        assert!(
+            !key_needs_braces("doe:1999"),
+            "should not need braces for plain keys"
+        );
+        assert!(
+            key_needs_braces("a b"),
+            "should need braces for keys w/ whitespace"
+        );
+        assert!(
+            key_needs_braces("a."),
+            "should need braces for keys ending in punctuation"
+        );
+    }
+}
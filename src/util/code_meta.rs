@@ -0,0 +1,231 @@
+//! Parse the meta of code (fenced).
+
+use alloc::{string::String, vec::Vec};
+
+/// Info from the meta of code (fenced).
+///
+/// The meta is what comes after the language in the info string.
+/// For example, with `` ```rust {3,5-7} title=main.rs ``, the language is
+/// `rust` and the meta is `{3,5-7} title=main.rs`.
+///
+/// Ranges of lines to highlight are in braces, separated by commas, such as
+/// `{3,5-7}`.
+/// Other words are attributes, which can have values: `title=main.rs`,
+/// `title="main.rs"`, or `title='main.rs'`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::CodeMeta;
+/// # fn main() {
+///
+/// let meta = CodeMeta::parse("{3,5-7} title=\"main.rs\" showLineNumbers");
+///
+/// assert_eq!(meta.highlight, vec![(3, 3), (5, 7)]);
+/// assert_eq!(meta.get("title"), Some("main.rs"));
+/// assert_eq!(meta.get("showLineNumbers"), Some(""));
+/// assert!(meta.is_highlighted(6));
+/// assert!(!meta.is_highlighted(4));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CodeMeta {
+    /// Attributes, in order, with their values, if any.
+    pub attributes: Vec<(String, Option<String>)>,
+    /// Ranges of lines to highlight, as inclusive `(start, end)` pairs.
+    ///
+    /// Lines are 1-indexed.
+    pub highlight: Vec<(usize, usize)>,
+}
+
+impl CodeMeta {
+    /// Parse `value`, the meta of code (fenced).
+    ///
+    /// Invalid ranges (such as `{a}`, `{0}`, or `{3-1}`) are ignored.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let chars = value.chars().collect::<Vec<_>>();
+        let mut meta = CodeMeta::default();
+        let mut index = 0;
+
+        while index < chars.len() {
+            if chars[index].is_whitespace() {
+                index += 1;
+            } else if chars[index] == '{' {
+                let start = index + 1;
+                while index < chars.len() && chars[index] != '}' {
+                    index += 1;
+                }
+                meta.highlight
+                    .extend(ranges(&chars[start..index].iter().collect::<String>()));
+                index += 1;
+            } else {
+                let start = index;
+                while index < chars.len() && !chars[index].is_whitespace() && chars[index] != '=' {
+                    index += 1;
+                }
+                let key = chars[start..index].iter().collect::<String>();
+                let mut value = None;
+
+                if index < chars.len() && chars[index] == '=' {
+                    index += 1;
+
+                    if index < chars.len() && matches!(chars[index], '"' | '\'') {
+                        let quote = chars[index];
+                        index += 1;
+                        let start = index;
+                        while index < chars.len() && chars[index] != quote {
+                            index += 1;
+                        }
+                        value = Some(chars[start..index].iter().collect());
+                        index += 1;
+                    } else {
+                        let start = index;
+                        while index < chars.len() && !chars[index].is_whitespace() {
+                            index += 1;
+                        }
+                        value = Some(chars[start..index].iter().collect());
+                    }
+                }
+
+                meta.attributes.push((key, value));
+            }
+        }
+
+        meta
+    }
+
+    /// Parse the info string of code (fenced), from its `lang` and `meta`.
+    ///
+    /// Like `rustdoc`, words after commas in the language (such as `no_run`
+    /// in `rust,no_run`) are attributes without values, which come before
+    /// the attributes in `meta`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::CodeMeta;
+    /// # fn main() {
+    ///
+    /// let meta = CodeMeta::from_info(Some("rust,no_run"), Some("title=\"x\" lines=3-5"));
+    ///
+    /// assert_eq!(meta.get("no_run"), Some(""));
+    /// assert_eq!(meta.get("title"), Some("x"));
+    /// assert_eq!(meta.get("lines"), Some("3-5"));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_info(lang: Option<&str>, meta: Option<&str>) -> Self {
+        let mut result = CodeMeta::parse(meta.unwrap_or(""));
+
+        if let Some(lang) = lang {
+            let flags = lang
+                .split(',')
+                .skip(1)
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(|d| (d.into(), None))
+                .collect::<Vec<_>>();
+            result.attributes.splice(0..0, flags);
+        }
+
+        result
+    }
+
+    /// Get the value of the first attribute called `key`.
+    ///
+    /// Attributes without a value yield an empty string.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|d| d.0 == key)
+            .map(|d| d.1.as_deref().unwrap_or(""))
+    }
+
+    /// Check whether the 1-indexed `line` is highlighted.
+    #[must_use]
+    pub fn is_highlighted(&self, line: usize) -> bool {
+        self.highlight
+            .iter()
+            .any(|&(start, end)| line >= start && line <= end)
+    }
+}
+
+/// Parse comma-separated ranges, such as `3,5-7`.
+fn ranges(value: &str) -> Vec<(usize, usize)> {
+    value
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            let (start, end) = if let Some((start, end)) = part.split_once('-') {
+                (start.trim().parse().ok()?, end.trim().parse().ok()?)
+            } else {
+                let line = part.parse().ok()?;
+                (line, line)
+            };
+
+            if start == 0 || start > end {
+                None
+            } else {
+                Some((start, end))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn code_meta() {
+        assert_eq!(
+            CodeMeta::parse(""),
+            CodeMeta::default(),
+            "should support empty meta"
+        );
+
+        assert_eq!(
+            CodeMeta::parse("{1, 3-4,a,0,5-2,6-} a=b c='d e' f"),
+            CodeMeta {
+                attributes: vec![
+                    ("a".into(), Some("b".into())),
+                    ("c".into(), Some("d e".into())),
+                    ("f".into(), None)
+                ],
+                highlight: vec![(1, 1), (3, 4)]
+            },
+            "should support ranges and attributes, ignoring invalid ranges"
+        );
+
+        assert_eq!(
+            CodeMeta::parse("{2} {4-5} a=\"b"),
+            CodeMeta {
+                attributes: vec![("a".into(), Some("b".into()))],
+                highlight: vec![(2, 2), (4, 5)]
+            },
+            "should support several range groups, and unclosed quotes"
+        );
+
+        assert_eq!(
+            CodeMeta::from_info(Some("rust,no_run, ignore,"), Some("{2} a=b")),
+            CodeMeta {
+                attributes: vec![
+                    ("no_run".into(), None),
+                    ("ignore".into(), None),
+                    ("a".into(), Some("b".into()))
+                ],
+                highlight: vec![(2, 2)]
+            },
+            "should support words after commas in the language"
+        );
+
+        assert_eq!(
+            CodeMeta::from_info(None, None),
+            CodeMeta::default(),
+            "should support missing info"
+        );
+    }
+}
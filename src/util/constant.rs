@@ -282,6 +282,9 @@ pub const SAFE_PROTOCOL_SRC: [&str; 2] = ["http", "https"];
 /// constructs in markdown, most notable the whitespace required to form
 /// [code (indented)][code_indented].
 ///
+/// This is also the default of [`tab_size`][crate::ParseOptions::tab_size],
+/// which configures where tabs stop, but not how much whitespace is needed.
+///
 /// [code_indented]: crate::construct::code_indented
 pub const TAB_SIZE: usize = 4;
 
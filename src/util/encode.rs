@@ -1,6 +1,8 @@
 //! Encode HTML.
 
-use alloc::string::String;
+use crate::util::constant::CHARACTER_REFERENCES_HTML_4;
+use crate::{CharacterReferenceKind, CompileOptions};
+use alloc::{format, string::String};
 
 /// Encode dangerous html characters.
 ///
@@ -53,3 +55,73 @@ pub fn encode(value: &str, encode_html: bool) -> String {
 
     result
 }
+
+/// Encode dangerous html characters, and optionally more, as configured
+/// in `options`.
+///
+/// This is like [`encode`][], but uses `character_reference_kind`,
+/// `encode_apostrophe`, and `encode_non_ascii` from `options` when
+/// `encode_html` is `true`.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::{util::encode::encode_with_options, CompileOptions};
+///
+/// let options = CompileOptions {
+///     encode_non_ascii: true,
+///     ..CompileOptions::default()
+/// };
+///
+/// assert_eq!(encode_with_options("I <3 🦀", true, &options), "I &lt;3 &#x1F980;");
+/// ```
+pub fn encode_with_options(value: &str, encode_html: bool, options: &CompileOptions) -> String {
+    let numeric = options.character_reference_kind == CharacterReferenceKind::Numeric;
+
+    if !encode_html || !(numeric || options.encode_apostrophe || options.encode_non_ascii) {
+        return encode(value, encode_html);
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut start = 0;
+
+    for (index, original) in value.char_indices() {
+        let char = match original {
+            '\0' => '\u{FFFD}',
+            '&' | '"' | '<' | '>' => original,
+            '\'' if options.encode_apostrophe => original,
+            _ if options.encode_non_ascii && !original.is_ascii() => original,
+            _ => continue,
+        };
+
+        result.push_str(&value[start..index]);
+
+        if char.is_ascii() || options.encode_non_ascii {
+            result.push_str(&reference(char, numeric));
+        } else {
+            result.push(char);
+        }
+
+        start = index + original.len_utf8();
+    }
+
+    result.push_str(&value[start..]);
+
+    result
+}
+
+/// Turn `char` into a character reference.
+///
+/// Named references are only used for names from HTML 4.
+fn reference(char: char, numeric: bool) -> String {
+    let mut buffer = [0; 4];
+    let value = char.encode_utf8(&mut buffer);
+
+    if !numeric {
+        if let Some(d) = CHARACTER_REFERENCES_HTML_4.iter().find(|d| d.1 == value) {
+            return format!("&{};", d.0);
+        }
+    }
+
+    format!("&#x{:X};", char as u32)
+}
@@ -0,0 +1,141 @@
+//! Constructs defined outside of `markdown-rs`.
+
+use crate::mdast::Node;
+use alloc::{boxed::Box, fmt, string::String, vec::Vec};
+
+/// Signature of a function that tries to match an extension at the start of
+/// `value`.
+///
+/// `value` is the rest of the line, starting at one of the
+/// [`markers`][Extension::markers].
+/// Return the number of bytes that the extension takes up (at least `1`, at
+/// most `value.len()`), or `None` if it does not match.
+pub type ExtensionTokenize = dyn Fn(&str) -> Option<usize> + Send + Sync;
+
+/// Signature of a function that turns the source of an extension (such as
+/// `@alice`) into HTML.
+///
+/// The result is not encoded: it is passed through as it is.
+pub type ExtensionToHtml = dyn Fn(&str) -> String + Send + Sync;
+
+/// Signature of a function that turns the source of an extension (such as
+/// `@alice`) into a syntax tree node.
+///
+/// The position of the node is set by `markdown-rs`.
+pub type ExtensionToMdast = dyn Fn(&str) -> Node + Send + Sync;
+
+/// A construct in text (inline, like emphasis), defined outside of
+/// `markdown-rs`.
+///
+/// Extensions are like the constructs that `markdown-rs` has for, say,
+/// emoji or wiki links, but they are defined by you.
+/// They can be passed as [`extensions`][crate::ParseOptions::extensions].
+///
+/// At each of the `markers`, before the constructs of `markdown-rs`, the
+/// extensions are tried in order, and the first one whose `tokenize`
+/// matches is used.
+/// Which one matched is recorded, so `tokenize` is not called again when
+/// compiling.
+///
+/// Extensions are matchers of text, not state machines: they cannot span
+/// several lines, cannot be in flow (as blocks), and their source is not
+/// parsed as markdown.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::{Link, Node, Text},
+///     to_html_with_options, Extension, Options, ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// // A mention of a user, such as `@alice`.
+/// let mention = Extension {
+///     markers: vec![b'@'],
+///     tokenize: Box::new(|value| {
+///         let size = value[1..]
+///             .bytes()
+///             .take_while(u8::is_ascii_alphanumeric)
+///             .count();
+///         if size == 0 { None } else { Some(size + 1) }
+///     }),
+///     to_html: Box::new(|value| format!("<a href=\"/{}\">{}</a>", &value[1..], value)),
+///     to_mdast: Box::new(|value| {
+///         Node::Link(Box::new(Link {
+///             children: vec![Node::Text(Text {
+///                 value: value.into(),
+///                 position: None,
+///             })],
+///             position: None,
+///             url: format!("/{}", &value[1..]),
+///             title: None,
+///             link_kind: None,
+///         }))
+///     }),
+/// };
+///
+/// assert_eq!(
+///     to_html_with_options(
+///         "Hi, @alice!",
+///         &Options {
+///             parse: ParseOptions {
+///                 extensions: vec![mention],
+///                 ..ParseOptions::default()
+///             },
+///             ..Options::default()
+///         }
+///     )?,
+///     "<p>Hi, <a href=\"/alice\">@alice</a>!</p>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct Extension {
+    /// Bytes that the extension can start with (such as `b'@'`).
+    ///
+    /// These must be ASCII.
+    pub markers: Vec<u8>,
+    /// Function to match the extension.
+    pub tokenize: Box<ExtensionTokenize>,
+    /// Function to turn the extension into HTML.
+    pub to_html: Box<ExtensionToHtml>,
+    /// Function to turn the extension into a syntax tree node.
+    pub to_mdast: Box<ExtensionToMdast>,
+}
+
+impl fmt::Debug for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extension")
+            .field("markers", &self.markers)
+            .field("tokenize", &"[Function]")
+            .field("to_html", &"[Function]")
+            .field("to_mdast", &"[Function]")
+            .finish()
+    }
+}
+
+impl Extension {
+    /// Get the size of the extension at the start of `value`, if it matches
+    /// and the size is valid.
+    pub(crate) fn size(&self, value: &str) -> Option<usize> {
+        match (self.tokenize)(value) {
+            Some(size) if size > 0 && size <= value.len() && value.is_char_boundary(size) => {
+                Some(size)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Find the extension that matches at the start of `value`, as its index in
+/// `extensions`, and its size.
+pub fn find(extensions: &[Extension], value: &str) -> Option<(usize, usize)> {
+    let marker = *value.as_bytes().first()?;
+
+    extensions
+        .iter()
+        .enumerate()
+        .filter(|(_, extension)| extension.markers.contains(&marker))
+        .find_map(|(index, extension)| extension.size(value).map(|size| (index, size)))
+}
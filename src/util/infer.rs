@@ -2,8 +2,10 @@
 //!
 //! Used to share between `to_html` and `to_mdast`.
 
+use crate::construct::grid_table;
 use crate::event::{Event, Kind, Name};
 use crate::mdast::AlignKind;
+use crate::util::slice::Slice;
 use alloc::{vec, vec::Vec};
 
 /// Figure out if a list is spread or not.
@@ -142,6 +144,41 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
     false
 }
 
+/// Figure out if a definition list is spread or not.
+///
+/// A definition list is “loose” when there are blank lines between its terms
+/// and descriptions, or between the things in a description.
+pub fn definition_list_loose(events: &[Event], mut index: usize) -> bool {
+    debug_assert!(
+        matches!(events[index].name, Name::DefinitionList),
+        "expected definition list"
+    );
+    let mut balance = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            balance += 1;
+        } else {
+            balance -= 1;
+
+            if (balance == 1 || balance == 2) && event.name == Name::BlankLineEnding {
+                return true;
+            }
+
+            // Done.
+            if balance == 0 && event.name == Name::DefinitionList {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
 /// Figure out the alignment of a GFM table.
 pub fn gfm_table_align(events: &[Event], mut index: usize) -> Vec<AlignKind> {
     debug_assert!(
@@ -190,3 +227,81 @@ pub fn gfm_table_align(events: &[Event], mut index: usize) -> Vec<AlignKind> {
 
     align
 }
+
+/// Figure out the alignment of a grid table, from the border after its head.
+pub fn grid_table_align(events: &[Event], bytes: &[u8], mut index: usize) -> Vec<AlignKind> {
+    debug_assert!(
+        matches!(events[index].name, Name::GridTable),
+        "expected table"
+    );
+    // Cells can contain grid tables too.
+    let mut depth = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.name == Name::GridTable {
+            if event.kind == Kind::Enter {
+                depth += 1;
+            } else {
+                depth -= 1;
+            }
+        } else if depth == 1
+            && event.kind == Kind::Enter
+            && event.name == Name::GridTableBorder
+            && events[index + 2].kind == Kind::Exit
+            && events[index + 2].name == Name::GridTableHead
+        {
+            return grid_table::align(
+                Slice::from_indices(bytes, event.point.index, events[index + 1].point.index)
+                    .as_str(),
+            );
+        }
+
+        index += 1;
+    }
+
+    vec![]
+}
+
+/// Figure out if a grid table cell is tight: whether it contains just a
+/// paragraph, or nothing.
+pub fn grid_table_cell_tight(events: &[Event], mut index: usize) -> bool {
+    debug_assert!(
+        matches!(events[index].name, Name::GridTableCell),
+        "expected cell"
+    );
+    let mut balance = 0;
+    let mut paragraphs = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            balance += 1;
+
+            if balance == 2
+                && !matches!(
+                    event.name,
+                    Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+                )
+            {
+                if event.name != Name::Paragraph {
+                    return false;
+                }
+
+                paragraphs += 1;
+            }
+        } else {
+            balance -= 1;
+
+            if balance == 0 {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    paragraphs < 2
+}
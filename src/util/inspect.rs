@@ -0,0 +1,371 @@
+//! Show syntax trees in a readable way.
+
+use crate::mdast::{AlignKind, AttributeContent, Attributes, Node, NodeKind, ReferenceKind};
+use crate::unist::Position;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Maximum number of characters shown of values.
+const VALUE_SIZE_MAX: usize = 32;
+
+/// Show `node` and its descendants as an indented tree, with their kinds,
+/// fields, (shortened) values, and positions.
+///
+/// This is much easier to read than the `Debug` output of large trees, and
+/// is similar to [`unist-util-inspect`](https://github.com/syntax-tree/unist-util-inspect).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{inspect, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# a *b*", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     inspect(&tree),
+///     "root[1] (1:1-1:8, 0-7)
+/// └─0 heading[2] (1:1-1:8, 0-7)
+///     │ depth: 1
+///     ├─0 text \"a \" (1:3-1:5, 2-4)
+///     └─1 emphasis[1] (1:5-1:8, 4-7)
+///         └─0 text \"b\" (1:6-1:7, 5-6)"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn inspect(node: &Node) -> String {
+    let mut result = String::new();
+    inspect_node(&mut result, node, "");
+    result
+}
+
+/// Show `node`, prefixing lines after the first with `prefix`.
+fn inspect_node(result: &mut String, node: &Node, prefix: &str) {
+    result.push_str(name(node.kind()));
+
+    if let Some(children) = node.children() {
+        result.push('[');
+        result.push_str(&children.len().to_string());
+        result.push(']');
+    }
+
+    if let Some(value) = value(node) {
+        result.push(' ');
+        result.push_str(&shorten(value));
+    }
+
+    if let Some(position) = node.position() {
+        result.push(' ');
+        result.push_str(&inspect_position(position));
+    }
+
+    let children = node.children().map_or(&[][..], |children| &children[..]);
+    let field_prefix = if children.is_empty() { "  " } else { "│ " };
+
+    for (key, value) in fields(node) {
+        result.push('\n');
+        result.push_str(prefix);
+        result.push_str(field_prefix);
+        result.push_str(key);
+        result.push_str(": ");
+        result.push_str(&value);
+    }
+
+    for (index, child) in children.iter().enumerate() {
+        let last = index == children.len() - 1;
+        result.push('\n');
+        result.push_str(prefix);
+        result.push_str(if last { "└─" } else { "├─" });
+        result.push_str(&index.to_string());
+        result.push(' ');
+        inspect_node(
+            result,
+            child,
+            &format!("{}{}", prefix, if last { "    " } else { "│   " }),
+        );
+    }
+}
+
+/// Get the unist type of a node.
+fn name(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Root => "root",
+        NodeKind::BlockQuote => "blockquote",
+        NodeKind::FootnoteDefinition => "footnoteDefinition",
+        NodeKind::MdxJsxFlowElement => "mdxJsxFlowElement",
+        NodeKind::List => "list",
+        NodeKind::DefinitionList => "definitionList",
+        NodeKind::MdxjsEsm => "mdxjsEsm",
+        NodeKind::Toml => "toml",
+        NodeKind::Yaml => "yaml",
+        NodeKind::Break => "break",
+        NodeKind::CharacterReference => "characterReference",
+        NodeKind::Emoji => "emoji",
+        NodeKind::Abbreviation => "abbreviation",
+        NodeKind::BlockId => "blockId",
+        NodeKind::InlineCode => "inlineCode",
+        NodeKind::InlineMath => "inlineMath",
+        NodeKind::Delete => "delete",
+        NodeKind::Highlight => "highlight",
+        NodeKind::Insert => "insert",
+        NodeKind::CriticAddition => "criticAddition",
+        NodeKind::CriticDeletion => "criticDeletion",
+        NodeKind::CriticSubstitution => "criticSubstitution",
+        NodeKind::CriticHighlight => "criticHighlight",
+        NodeKind::CriticComment => "criticComment",
+        NodeKind::Emphasis => "emphasis",
+        NodeKind::MdxTextExpression => "mdxTextExpression",
+        NodeKind::FootnoteReference => "footnoteReference",
+        NodeKind::Html => "html",
+        NodeKind::Comment => "comment",
+        NodeKind::Image => "image",
+        NodeKind::ImageReference => "imageReference",
+        NodeKind::MdxJsxTextElement => "mdxJsxTextElement",
+        NodeKind::Link => "link",
+        NodeKind::LinkReference => "linkReference",
+        NodeKind::WikiLink => "wikiLink",
+        NodeKind::Cite => "cite",
+        NodeKind::Strong => "strong",
+        NodeKind::Text => "text",
+        NodeKind::Code => "code",
+        NodeKind::Math => "math",
+        NodeKind::MdxFlowExpression => "mdxFlowExpression",
+        NodeKind::Heading => "heading",
+        NodeKind::Table => "table",
+        NodeKind::ThematicBreak => "thematicBreak",
+        NodeKind::TableRow => "tableRow",
+        NodeKind::TableCell => "tableCell",
+        NodeKind::ListItem => "listItem",
+        NodeKind::DefinitionTerm => "definitionTerm",
+        NodeKind::DefinitionDescription => "definitionDescription",
+        NodeKind::Definition => "definition",
+        NodeKind::AbbreviationDefinition => "abbreviationDefinition",
+        NodeKind::Paragraph => "paragraph",
+    }
+}
+
+/// Get the value of a literal node.
+fn value(node: &Node) -> Option<&str> {
+    match node {
+        Node::MdxjsEsm(x) => Some(&x.value),
+        Node::Toml(x) => Some(&x.value),
+        Node::Yaml(x) => Some(&x.value),
+        Node::InlineCode(x) => Some(&x.value),
+        Node::InlineMath(x) => Some(&x.value),
+        Node::MdxTextExpression(x) => Some(&x.value),
+        Node::Html(x) => Some(&x.value),
+        Node::Comment(x) => Some(&x.value),
+        Node::Text(x) => Some(&x.value),
+        Node::CharacterReference(x) => Some(&x.value),
+        Node::Emoji(x) => Some(&x.value),
+        Node::Abbreviation(x) => Some(&x.value),
+        Node::BlockId(x) => Some(&x.value),
+        Node::Code(x) => Some(&x.value),
+        Node::Math(x) => Some(&x.value),
+        Node::MdxFlowExpression(x) => Some(&x.value),
+        _ => None,
+    }
+}
+
+/// Get the fields of a node, other than children, position, and value.
+///
+/// Fields that are not set are not included.
+fn fields(node: &Node) -> Vec<(&'static str, String)> {
+    let mut fields = vec![];
+
+    match node {
+        Node::BlockQuote(x) => {
+            if let Some(alert) = x.alert {
+                fields.push(("alert", alert.name().to_lowercase()));
+            }
+        }
+        Node::Heading(x) => {
+            fields.push(("depth", x.depth.to_string()));
+            push_id_and_classes(&mut fields, x.attributes.as_deref());
+        }
+        Node::InlineCode(x) => push_id_and_classes(&mut fields, x.attributes.as_deref()),
+        Node::CharacterReference(x) => fields.push(("raw", shorten(&x.raw))),
+        Node::Emoji(x) => fields.push(("shortcode", shorten(&x.shortcode))),
+        Node::Abbreviation(x) => push_optional(&mut fields, "title", x.title.as_deref()),
+        Node::List(x) => {
+            fields.push(("ordered", x.ordered.to_string()));
+            if let Some(start) = x.start {
+                fields.push(("start", start.to_string()));
+            }
+            fields.push(("spread", x.spread.to_string()));
+        }
+        Node::ListItem(x) => {
+            if let Some(checked) = x.checked {
+                fields.push(("checked", checked.to_string()));
+            }
+            fields.push(("spread", x.spread.to_string()));
+        }
+        Node::DefinitionList(x) => fields.push(("spread", x.spread.to_string())),
+        Node::Code(x) => {
+            push_optional(&mut fields, "lang", x.lang.as_deref());
+            push_optional(&mut fields, "meta", x.meta.as_deref());
+            push_id_and_classes(&mut fields, x.attributes.as_deref());
+        }
+        Node::Math(x) => push_optional(&mut fields, "meta", x.meta.as_deref()),
+        Node::Definition(x) => {
+            fields.push(("identifier", shorten(&x.identifier)));
+            push_optional(&mut fields, "label", x.label.as_deref());
+            fields.push(("url", shorten(&x.url)));
+            push_optional(&mut fields, "title", x.title.as_deref());
+        }
+        Node::AbbreviationDefinition(x) => {
+            fields.push(("label", shorten(&x.label)));
+            push_optional(&mut fields, "title", x.title.as_deref());
+        }
+        Node::Link(x) => {
+            fields.push(("url", shorten(&x.url)));
+            push_optional(&mut fields, "title", x.title.as_deref());
+        }
+        Node::Image(x) => {
+            fields.push(("url", shorten(&x.url)));
+            push_optional(&mut fields, "title", x.title.as_deref());
+            fields.push(("alt", shorten(&x.alt)));
+        }
+        Node::LinkReference(x) => {
+            fields.push(("identifier", shorten(&x.identifier)));
+            push_optional(&mut fields, "label", x.label.as_deref());
+            fields.push(("referenceType", reference_kind(x.reference_kind).into()));
+        }
+        Node::ImageReference(x) => {
+            fields.push(("identifier", shorten(&x.identifier)));
+            push_optional(&mut fields, "label", x.label.as_deref());
+            fields.push(("referenceType", reference_kind(x.reference_kind).into()));
+            fields.push(("alt", shorten(&x.alt)));
+        }
+        Node::WikiLink(x) => {
+            fields.push(("target", shorten(&x.target)));
+            push_optional(&mut fields, "anchor", x.anchor.as_deref());
+            push_optional(&mut fields, "label", x.label.as_deref());
+        }
+        Node::Cite(x) => fields.push((
+            "keys",
+            shorten(
+                &x.citations
+                    .iter()
+                    .map(|citation| citation.key.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+        )),
+        Node::FootnoteDefinition(x) => {
+            fields.push(("identifier", shorten(&x.identifier)));
+            push_optional(&mut fields, "label", x.label.as_deref());
+        }
+        Node::FootnoteReference(x) => {
+            fields.push(("identifier", shorten(&x.identifier)));
+            push_optional(&mut fields, "label", x.label.as_deref());
+        }
+        Node::Table(x) => fields.push((
+            "align",
+            format!(
+                "[{}]",
+                x.align
+                    .iter()
+                    .map(|align| match align {
+                        AlignKind::Left => "left",
+                        AlignKind::Right => "right",
+                        AlignKind::Center => "center",
+                        AlignKind::None => "none",
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )),
+        Node::MdxJsxFlowElement(x) => {
+            push_optional(&mut fields, "name", x.name.as_deref());
+            push_attributes(&mut fields, &x.attributes);
+        }
+        Node::MdxJsxTextElement(x) => {
+            push_optional(&mut fields, "name", x.name.as_deref());
+            push_attributes(&mut fields, &x.attributes);
+        }
+        _ => {}
+    }
+
+    fields
+}
+
+/// Add a string field, if it is set.
+fn push_optional(fields: &mut Vec<(&'static str, String)>, key: &'static str, value: Option<&str>) {
+    if let Some(value) = value {
+        fields.push((key, shorten(value)));
+    }
+}
+
+/// Add the attributes of a JSX element, if there are any.
+fn push_attributes(fields: &mut Vec<(&'static str, String)>, attributes: &[AttributeContent]) {
+    if !attributes.is_empty() {
+        let names = attributes
+            .iter()
+            .map(|attribute| match attribute {
+                AttributeContent::Expression { .. } => "{…}".into(),
+                AttributeContent::Property(property) => property.name.clone(),
+            })
+            .collect::<Vec<_>>();
+        fields.push(("attributes", format!("[{}]", names.join(", "))));
+    }
+}
+
+/// Add the identifier, classes, and the names of other attributes from
+/// attributes (such as `{#a .b}`), if there are any.
+fn push_id_and_classes(fields: &mut Vec<(&'static str, String)>, attributes: Option<&Attributes>) {
+    if let Some(attributes) = attributes {
+        push_optional(fields, "id", attributes.id.as_deref());
+        if !attributes.classes.is_empty() {
+            fields.push(("classes", format!("[{}]", attributes.classes.join(", "))));
+        }
+        if !attributes.pairs.is_empty() {
+            let names = attributes
+                .pairs
+                .iter()
+                .map(|pair| pair.0.as_str())
+                .collect::<Vec<_>>();
+            fields.push(("attributes", format!("[{}]", names.join(", "))));
+        }
+    }
+}
+
+/// Get the unist name of a reference kind.
+fn reference_kind(kind: ReferenceKind) -> &'static str {
+    match kind {
+        ReferenceKind::Shortcut => "shortcut",
+        ReferenceKind::Collapsed => "collapsed",
+        ReferenceKind::Full => "full",
+    }
+}
+
+/// Quote `value`, shortened if it is long.
+fn shorten(value: &str) -> String {
+    if value.chars().count() > VALUE_SIZE_MAX {
+        let mut short = value.chars().take(VALUE_SIZE_MAX).collect::<String>();
+        short = format!("{:?}", short);
+        short.insert(short.len() - 1, '…');
+        short
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// Show a position.
+fn inspect_position(position: &Position) -> String {
+    format!(
+        "({}:{}-{}:{}, {}-{})",
+        position.start.line,
+        position.start.column,
+        position.end.line,
+        position.end.column,
+        position.start.offset,
+        position.end.offset
+    )
+}
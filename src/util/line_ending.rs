@@ -1,4 +1,4 @@
-use alloc::{str::FromStr, string::String};
+use alloc::{str::FromStr, string::String, vec, vec::Vec};
 
 /// Type of line endings in markdown.
 ///
@@ -49,6 +49,62 @@ pub enum LineEnding {
 
 // xxxxxxxxxxxxxxx
 impl LineEnding {
+    /// Find the line ending that is used most in `value`.
+    ///
+    /// When line endings are used equally often, the one used first wins.
+    /// Returns `None` if there are no line endings in `value`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::LineEnding;
+    /// # fn main() {
+    ///
+    /// assert_eq!(
+    ///     LineEnding::detect("a\r\nb\nc\r\nd"),
+    ///     Some(LineEnding::CarriageReturnLineFeed)
+    /// );
+    /// assert_eq!(LineEnding::detect("a"), None);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn detect(value: &str) -> Option<LineEnding> {
+        let bytes = value.as_bytes();
+        // Line endings and how often they are used, in the order they are
+        // first used.
+        let mut counts: Vec<(LineEnding, usize)> = vec![];
+        let mut index = 0;
+
+        while index < bytes.len() {
+            let line_ending = match bytes[index] {
+                b'\r' if bytes.get(index + 1) == Some(&b'\n') => {
+                    index += 1;
+                    Some(LineEnding::CarriageReturnLineFeed)
+                }
+                b'\r' => Some(LineEnding::CarriageReturn),
+                b'\n' => Some(LineEnding::LineFeed),
+                _ => None,
+            };
+
+            if let Some(line_ending) = line_ending {
+                if let Some(count) = counts.iter_mut().find(|count| count.0 == line_ending) {
+                    count.1 += 1;
+                } else {
+                    counts.push((line_ending, 1));
+                }
+            }
+
+            index += 1;
+        }
+
+        // `max_by_key` gets the last of equal counts, so walk them in reverse.
+        counts
+            .into_iter()
+            .rev()
+            .max_by_key(|count| count.1)
+            .map(|count| count.0)
+    }
+
     /// Turn the line ending into a [str].
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -117,6 +173,22 @@ mod tests {
             "should support turning a carriage return + line feed into a string"
         );
 
+        assert_eq!(
+            LineEnding::detect("a\nb\r\nc\r\nd"),
+            Some(LineEnding::CarriageReturnLineFeed),
+            "should detect the line ending used most"
+        );
+        assert_eq!(
+            LineEnding::detect("a\rb\nc"),
+            Some(LineEnding::CarriageReturn),
+            "should detect the line ending used first, if used equally often"
+        );
+        assert_eq!(
+            LineEnding::detect("a"),
+            None,
+            "should not detect line endings in a value without them"
+        );
+
         assert_eq!(
             "aaa".parse::<LineEnding>(),
             Err("Expected CR, LF, or CRLF".into()),
@@ -0,0 +1,226 @@
+//! Map between byte offsets and lines and columns.
+
+use crate::unist::Point;
+use crate::util::constant::TAB_SIZE;
+use alloc::{vec, vec::Vec};
+
+/// Index of where lines start in a source file, to turn byte offsets into
+/// [`Point`][]s and back.
+///
+/// Lookups take `O(log n)` time in the number of lines.
+/// Like the parser, lines end at `\n`, `\r\n`, and `\r`, columns count
+/// bytes, and tabs count up to the next tab stop (every 4 columns, or
+/// another [`tab_size`][crate::ParseOptions::tab_size] with
+/// [`with_tab_size()`][LineIndex::with_tab_size]).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{unist::Point, LineIndex};
+/// # fn main() {
+///
+/// let index = LineIndex::new("a\n\tb");
+///
+/// assert_eq!(index.to_point(3), Some(Point::new(2, 5, 3)));
+/// assert_eq!(index.to_offset(2, 5), Some(3));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineIndex {
+    /// Byte index where each line starts.
+    starts: Vec<usize>,
+    /// Byte index of each tab.
+    tabs: Vec<usize>,
+    /// Number of bytes.
+    len: usize,
+    /// Number of columns between tab stops.
+    tab_size: usize,
+}
+
+impl LineIndex {
+    /// Create an index for `value`, with tabs stopping at every 4 columns.
+    #[must_use]
+    pub fn new(value: &str) -> Self {
+        Self::from_bytes(value.as_bytes(), TAB_SIZE)
+    }
+
+    /// Create an index for `value`, with tabs stopping at every `tab_size`
+    /// columns.
+    ///
+    /// ## Panics
+    ///
+    /// This panics when `tab_size` is `0`.
+    #[must_use]
+    pub fn with_tab_size(value: &str, tab_size: usize) -> Self {
+        assert!(tab_size > 0, "expected `tab_size` to be more than `0`");
+        Self::from_bytes(value.as_bytes(), tab_size)
+    }
+
+    /// Create an index for `bytes`.
+    pub(crate) fn from_bytes(bytes: &[u8], tab_size: usize) -> Self {
+        let mut starts = vec![0];
+        let mut tabs = vec![];
+        let mut index = 0;
+
+        while index < bytes.len() {
+            match bytes[index] {
+                b'\r' => {
+                    if index + 1 < bytes.len() && bytes[index + 1] == b'\n' {
+                        index += 1;
+                    }
+                    starts.push(index + 1);
+                }
+                b'\n' => starts.push(index + 1),
+                b'\t' => tabs.push(index),
+                _ => {}
+            }
+
+            index += 1;
+        }
+
+        LineIndex {
+            starts,
+            tabs,
+            len: bytes.len(),
+            tab_size,
+        }
+    }
+
+    /// Get the number of lines.
+    #[must_use]
+    pub fn lines(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Get the point for the byte `offset`.
+    ///
+    /// The end of the source (`offset` equal to its length) has a point too.
+    /// Returns `None` when `offset` is out of bounds.
+    #[must_use]
+    pub fn to_point(&self, offset: usize) -> Option<Point> {
+        if offset > self.len {
+            return None;
+        }
+
+        let line = self.starts.partition_point(|start| *start <= offset);
+        let start = self.starts[line - 1];
+        let mut column = 1;
+        let mut index = start;
+
+        for &tab in self.tabs_in(start, offset) {
+            column += tab - index;
+            column += self.tab_width(column);
+            index = tab + 1;
+        }
+
+        Some(Point::new(line, column + offset - index, offset))
+    }
+
+    /// Get the byte offset for the 1-indexed `line` and `column`.
+    ///
+    /// Columns in a tab move to that tab.
+    /// Returns `None` when `line` or `column` are out of bounds.
+    #[must_use]
+    pub fn to_offset(&self, line: usize, column: usize) -> Option<usize> {
+        if line == 0 || column == 0 {
+            return None;
+        }
+
+        let start = *self.starts.get(line - 1)?;
+        let end = self.starts.get(line).map_or(self.len, |next| next - 1);
+        let mut current = 1;
+        let mut index = start;
+
+        for &tab in self.tabs_in(start, end) {
+            if column < current + tab - index {
+                break;
+            }
+
+            current += tab - index;
+            index = tab;
+            let size = self.tab_width(current);
+
+            if column < current + size {
+                return Some(tab);
+            }
+
+            current += size;
+            index += 1;
+        }
+
+        let offset = index + column - current;
+
+        if offset > end {
+            None
+        } else {
+            Some(offset)
+        }
+    }
+
+    /// Get the tabs from `start` (inclusive) to `end` (exclusive).
+    fn tabs_in(&self, start: usize, end: usize) -> &[usize] {
+        let from = self.tabs.partition_point(|tab| *tab < start);
+        let to = self.tabs.partition_point(|tab| *tab < end);
+        &self.tabs[from..to]
+    }
+
+    /// Get the number of columns a tab at `column` takes.
+    fn tab_width(&self, column: usize) -> usize {
+        let remainder = column % self.tab_size;
+        1 + if remainder == 0 {
+            0
+        } else {
+            self.tab_size - remainder
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_index() {
+        let index = LineIndex::new("ab\r\n\tc\rd\te");
+
+        assert_eq!(index.lines(), 3, "should count lines");
+        assert_eq!(index.to_point(0), Some(Point::new(1, 1, 0)), "`a`");
+        assert_eq!(index.to_point(3), Some(Point::new(1, 4, 3)), "`\\n`");
+        assert_eq!(index.to_point(4), Some(Point::new(2, 1, 4)), "tab");
+        assert_eq!(index.to_point(5), Some(Point::new(2, 5, 5)), "`c`");
+        assert_eq!(index.to_point(8), Some(Point::new(3, 2, 8)), "tab");
+        assert_eq!(index.to_point(9), Some(Point::new(3, 5, 9)), "`e`");
+        assert_eq!(index.to_point(10), Some(Point::new(3, 6, 10)), "eof");
+        assert_eq!(index.to_point(11), None, "out of bounds");
+
+        assert_eq!(index.to_offset(1, 1), Some(0), "`a`");
+        assert_eq!(index.to_offset(1, 4), Some(3), "`\\n`");
+        assert_eq!(index.to_offset(1, 5), None, "past line");
+        assert_eq!(index.to_offset(2, 3), Some(4), "in tab");
+        assert_eq!(index.to_offset(2, 5), Some(5), "`c`");
+        assert_eq!(index.to_offset(3, 5), Some(9), "`e`");
+        assert_eq!(index.to_offset(3, 6), Some(10), "eof");
+        assert_eq!(index.to_offset(3, 7), None, "past eof");
+        assert_eq!(index.to_offset(4, 1), None, "missing line");
+        assert_eq!(index.to_offset(0, 1), None, "line 0");
+    }
+
+    #[test]
+    fn line_index_empty() {
+        let index = LineIndex::new("");
+
+        assert_eq!(index.lines(), 1, "should have a line");
+        assert_eq!(index.to_point(0), Some(Point::new(1, 1, 0)), "eof");
+        assert_eq!(index.to_offset(1, 1), Some(0), "eof");
+    }
+
+    #[test]
+    fn line_index_tab_size() {
+        let index = LineIndex::with_tab_size("\ta\n-\tb", 8);
+
+        assert_eq!(index.to_point(1), Some(Point::new(1, 9, 1)), "`a`");
+        assert_eq!(index.to_point(5), Some(Point::new(2, 9, 5)), "`b`");
+        assert_eq!(index.to_offset(2, 8), Some(4), "in tab");
+        assert_eq!(index.to_offset(2, 9), Some(5), "`b`");
+    }
+}
@@ -5,7 +5,7 @@
 //!   slices in a whole document, and byte indices into that whole document.
 
 use crate::unist::Point;
-use alloc::{vec, vec::Vec};
+use crate::util::line_index::LineIndex;
 
 /// Each stop represents a new slice, which contains the byte index into the
 /// corresponding string where the slice starts (`0`), and the byte index into
@@ -14,68 +14,26 @@ pub type Stop = (usize, usize);
 
 #[derive(Debug)]
 pub struct Location {
-    /// List, where each index is a line number (0-based), and each value is
-    /// the byte index *after* where the line ends.
-    indices: Vec<usize>,
+    /// Index of where lines start.
+    index: LineIndex,
 }
 
 impl Location {
-    /// Get an index for the given `bytes`.
-    ///
-    /// Port of <https://github.com/vfile/vfile-location/blob/main/index.js>
+    /// Get an index for the given `bytes`, with tabs stopping at every
+    /// `tab_size` columns.
     #[must_use]
-    pub fn new(bytes: &[u8]) -> Self {
-        let mut index = 0;
-        let mut location_index = Self { indices: vec![] };
-
-        while index < bytes.len() {
-            if bytes[index] == b'\r' {
-                if index + 1 < bytes.len() && bytes[index + 1] == b'\n' {
-                    location_index.indices.push(index + 2);
-                    index += 1;
-                } else {
-                    location_index.indices.push(index + 1);
-                }
-            } else if bytes[index] == b'\n' {
-                location_index.indices.push(index + 1);
-            }
-
-            index += 1;
+    pub fn new(bytes: &[u8], tab_size: usize) -> Self {
+        Self {
+            index: LineIndex::from_bytes(bytes, tab_size),
         }
-
-        location_index.indices.push(index + 1);
-        location_index
     }
 
     /// Get the line and column-based `point` for `offset` in the bound indices.
     ///
     /// Returns `None` when given out of bounds input.
-    ///
-    /// Port of <https://github.com/vfile/vfile-location/blob/main/index.js>
     #[must_use]
     pub fn to_point(&self, offset: usize) -> Option<Point> {
-        let mut index = 0;
-
-        if let Some(end) = self.indices.last() {
-            if offset < *end {
-                while index < self.indices.len() {
-                    if self.indices[index] > offset {
-                        break;
-                    }
-
-                    index += 1;
-                }
-
-                let previous = if index > 0 {
-                    self.indices[index - 1]
-                } else {
-                    0
-                };
-                return Some(Point::new(index + 1, offset + 1 - previous, offset));
-            }
-        }
-
-        None
+        self.index.to_point(offset)
     }
 
     /// Like `to_point`, but takes a relative offset from a certain string
@@ -113,7 +71,7 @@ mod tests {
 
     #[test]
     fn test_location_lf() {
-        let location = Location::new("ab\nc".as_bytes());
+        let location = Location::new("ab\nc".as_bytes(), 4);
         assert_eq!(
             location.to_point(0), // `a`
             Some(Point::new(1, 1, 0)),
@@ -150,7 +108,7 @@ mod tests {
 
     #[test]
     fn test_location_cr() {
-        let location = Location::new("a\rb".as_bytes());
+        let location = Location::new("a\rb".as_bytes(), 4);
         assert_eq!(
             location.to_point(0), // `a`
             Some(Point::new(1, 1, 0)),
@@ -170,7 +128,7 @@ mod tests {
 
     #[test]
     fn test_location_cr_lf() {
-        let location = Location::new("a\r\nb".as_bytes());
+        let location = Location::new("a\r\nb".as_bytes(), 4);
         assert_eq!(
             location.to_point(0), // `a`
             Some(Point::new(1, 1, 0)),
@@ -194,7 +152,7 @@ mod tests {
     }
     #[test]
     fn test_empty() {
-        let location = Location::new("".as_bytes());
+        let location = Location::new("".as_bytes(), 4);
         assert_eq!(location.to_point(0), Some(Point::new(1, 1, 0)), "to_point");
         assert_eq!(
             location.relative_to_point(&[], 0),
@@ -1,18 +1,25 @@
 //! Utilities used when processing markdown.
 
+pub mod attributes;
 pub mod char;
 pub mod character_reference;
+pub mod cite;
+pub mod code_meta;
 pub mod constant;
 pub mod edit_map;
 pub mod encode;
+pub mod extension;
 pub mod gfm_tagfilter;
 pub mod identifier;
 pub mod infer;
+pub mod inspect;
 pub mod line_ending;
+pub mod line_index;
 pub mod location;
 pub mod mdx;
 pub mod mdx_collect;
 pub mod normalize_identifier;
+pub mod roundtrip;
 pub mod sanitize_uri;
 pub mod skip;
 pub mod slice;
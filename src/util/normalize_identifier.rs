@@ -2,8 +2,8 @@
 
 use alloc::string::String;
 
-/// Normalize an identifier, as found in [references][label_end] and
-/// [definitions][definition], so it can be compared when matching.
+/// Normalize an identifier, as found in references and definitions, so it
+/// can be compared when matching.
 ///
 /// This collapsed whitespace found in markdown (`\t`, `\r`, `\n`, and ` `)
 /// into one space, trims it (as in, dropping the first and last space), and
@@ -23,24 +23,25 @@ use alloc::string::String;
 /// If we’d inverse the steps, for `ẞ`, we’d first uppercase without a
 /// change, and then lowercase to `ß`, which would not match `ss`.
 ///
+/// The `identifier` fields of references and definitions in
+/// [mdast][crate::mdast] are this value, lowercased.
+///
 /// ## Examples
 ///
-/// ```rust ignore
-/// markdown::util::normalize_identifier::normalize_identifier;
+/// ```
+/// use markdown::normalize_identifier;
 ///
-/// assert_eq!(normalize_identifier(" a "), "a");
-/// assert_eq!(normalize_identifier("a\t\r\nb"), "a b");
-/// assert_eq!(normalize_identifier("ПРИВЕТ"), "привет");
-/// assert_eq!(normalize_identifier("Привет"), "привет");
-/// assert_eq!(normalize_identifier("привет"), "привет");
+/// assert_eq!(normalize_identifier(" a "), "A");
+/// assert_eq!(normalize_identifier("a\t\r\nb"), "A B");
+/// assert_eq!(normalize_identifier("ПРИВЕТ"), "ПРИВЕТ");
+/// assert_eq!(normalize_identifier("Привет"), "ПРИВЕТ");
+/// assert_eq!(normalize_identifier("привет"), "ПРИВЕТ");
+/// assert_eq!(normalize_identifier("ẞ"), normalize_identifier("ss"));
 /// ```
 ///
 /// ## References
 ///
 /// *   [`micromark-util-normalize-identifier` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-normalize-identifier)
-///
-/// [definition]: crate::construct::definition
-/// [label_end]: crate::construct::label_end
 pub fn normalize_identifier(value: &str) -> String {
     // Note: it’ll grow a bit smaller for consecutive whitespace.
     let mut result = String::with_capacity(value.len());
@@ -59,7 +60,7 @@ pub fn normalize_identifier(value: &str) -> String {
         }
         // First non-whitespace we see after whitespace.
         else if in_whitespace {
-            if start != 0 {
+            if !result.is_empty() {
                 result.push(' ');
             }
 
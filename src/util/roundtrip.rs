@@ -0,0 +1,198 @@
+//! Check that syntax trees survive being turned into markdown and back.
+
+use crate::generate::{supported, to_markdown};
+use crate::mdast::{Node, NodePath};
+use crate::message;
+use crate::{to_mdast, ParseOptions};
+use alloc::{string::String, vec::Vec};
+
+/// Place where a tree and the tree parsed from its markdown first differ.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundtripDivergence {
+    /// Path to where the trees differ, which is the same in both trees.
+    pub path: NodePath,
+    /// Node at `path` in the original tree, if there is one.
+    pub expected: Option<Node>,
+    /// Node at `path` in the tree parsed from `markdown`, if there is one.
+    pub actual: Option<Node>,
+    /// Markdown made from the original tree, empty for unsupported nodes.
+    pub markdown: String,
+    /// Why the trees differ (should use markdown).
+    pub reason: String,
+}
+
+/// Parse `value`, turn the tree into markdown, parse that, and report where
+/// the two trees first differ.
+///
+/// See [`verify_roundtrip_tree()`][] for how trees are compared.
+///
+/// ## Errors
+///
+/// There are errors when `value` (or the markdown made from it) cannot be
+/// parsed, which happens with MDX.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::NodePath, verify_roundtrip, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// assert_eq!(verify_roundtrip("# a *b*", &ParseOptions::default())?, None);
+///
+/// // Loose lists are turned into tight lists.
+/// let divergence = verify_roundtrip("* a\n\n* b", &ParseOptions::default())?.unwrap();
+/// assert_eq!(divergence.path, NodePath::from(vec![0]));
+/// assert_eq!(divergence.markdown, "* a\n* b\n\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_roundtrip(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<Option<RoundtripDivergence>, message::Message> {
+    verify_roundtrip_tree(&to_mdast(value, options)?, options)
+}
+
+/// Turn `tree` into markdown, parse that, and report where the two trees
+/// first differ.
+///
+/// `tree` should be a root, as parsing always gives one.
+/// Nodes are compared without their children, in document order.
+/// Positions are ignored, and so are fields that only record how markdown
+/// was formatted (such as the marker of emphasis, or whether a heading is
+/// setext), so that trees built by hand can be checked too.
+///
+/// ## Errors
+///
+/// There are errors when the markdown made from `tree` cannot be parsed,
+/// which happens with MDX.
+///
+/// Nodes that cannot yet be turned into markdown are reported as a
+/// divergence at the first such node, without an `actual` node.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::{Node, Paragraph, Root, Text},
+///     verify_roundtrip_tree, ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = Node::Root(Root {
+///     children: vec![Node::Paragraph(Paragraph {
+///         children: vec![Node::Text(Text {
+///             value: "a".into(),
+///             position: None,
+///         })],
+///         position: None,
+///     })],
+///     position: None,
+/// });
+///
+/// assert_eq!(verify_roundtrip_tree(&tree, &ParseOptions::default())?, None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_roundtrip_tree(
+    tree: &Node,
+    options: &ParseOptions,
+) -> Result<Option<RoundtripDivergence>, message::Message> {
+    if let Some(path) = unsupported(tree, &mut NodePath::new()) {
+        return Ok(Some(RoundtripDivergence {
+            expected: tree.get(&path).cloned(),
+            actual: None,
+            path,
+            markdown: String::new(),
+            reason: "Unexpected unsupported node, expected a node that can be turned into markdown"
+                .into(),
+        }));
+    }
+
+    let markdown = to_markdown(tree);
+    let actual = to_mdast(&markdown, options)?;
+
+    Ok(
+        diverge(tree, &actual, &mut NodePath::new()).map(|path| RoundtripDivergence {
+            expected: tree.get(&path).cloned(),
+            actual: actual.get(&path).cloned(),
+            path,
+            markdown,
+            reason: "Unexpected different node, expected the same node after a round trip".into(),
+        }),
+    )
+}
+
+/// Find the path to the first node that cannot yet be turned into markdown.
+fn unsupported(node: &Node, path: &mut NodePath) -> Option<NodePath> {
+    if !supported(node) {
+        return Some(path.clone());
+    }
+
+    for (index, child) in node.children().into_iter().flatten().enumerate() {
+        path.push(index);
+
+        if let Some(path) = unsupported(child, path) {
+            return Some(path);
+        }
+
+        path.pop();
+    }
+
+    None
+}
+
+/// Find the path to where `expected` and `actual` first differ.
+fn diverge(expected: &Node, actual: &Node, path: &mut NodePath) -> Option<NodePath> {
+    if shallow(expected) != shallow(actual) {
+        return Some(path.clone());
+    }
+
+    let expected = expected.children().map_or(&[][..], Vec::as_slice);
+    let actual = actual.children().map_or(&[][..], Vec::as_slice);
+    let mut index = 0;
+
+    while index < expected.len().max(actual.len()) {
+        path.push(index);
+
+        if let (Some(expected), Some(actual)) = (expected.get(index), actual.get(index)) {
+            if let Some(path) = diverge(expected, actual, path) {
+                return Some(path);
+            }
+        } else {
+            return Some(path.clone());
+        }
+
+        path.pop();
+        index += 1;
+    }
+
+    None
+}
+
+/// Copy `node` without its children, position, and formatting info.
+fn shallow(node: &Node) -> Node {
+    let mut node = node.clone();
+    node.position_set(None);
+
+    if let Some(children) = node.children_mut() {
+        children.clear();
+    }
+
+    match &mut node {
+        Node::Code(x) => {
+            x.code_kind = None;
+            x.fence_marker = None;
+            x.fence_size = None;
+            x.fence_indent = None;
+        }
+        Node::Emphasis(x) => x.marker = None,
+        Node::Heading(x) => x.heading_kind = None,
+        Node::Link(x) => x.link_kind = None,
+        Node::List(x) => x.marker = None,
+        Node::Strong(x) => x.marker = None,
+        _ => {}
+    }
+
+    node
+}
@@ -1,7 +1,6 @@
 //! Deal with bytes.
 
 use crate::event::{Event, Kind, Point};
-use crate::util::constant::TAB_SIZE;
 use alloc::{format, string::String, vec};
 use core::str;
 
@@ -49,6 +48,15 @@ impl<'a> Position<'a> {
     }
 }
 
+/// Get the rest of the line in `bytes`, starting at `index`.
+pub fn rest_of_line(bytes: &[u8], index: usize) -> &str {
+    let end = bytes[index..]
+        .iter()
+        .position(|byte| matches!(byte, b'\n' | b'\r'))
+        .map_or(bytes.len(), |size| index + size);
+    str::from_utf8(&bytes[index..end]).unwrap_or_default()
+}
+
 /// Bytes belonging to a range.
 ///
 /// Includes info on virtual spaces before and after the bytes.
@@ -64,16 +72,19 @@ pub struct Slice<'a> {
 
 impl<'a> Slice<'a> {
     /// Get a slice for a position.
-    pub fn from_position(bytes: &'a [u8], position: &Position) -> Slice<'a> {
+    ///
+    /// Tabs stop at every `tab_size` columns.
+    pub fn from_position(bytes: &'a [u8], position: &Position, tab_size: usize) -> Slice<'a> {
         let mut before = position.start.vs;
         let mut after = position.end.vs;
         let mut start = position.start.index;
         let mut end = position.end.index;
 
         // If we have virtual spaces before, it means we are past the actual
-        // character at that index, and those virtual spaces.
+        // character at that index, and those virtual spaces, so the rest of
+        // the tab up to the next tab stop is included.
         if before > 0 {
-            before = TAB_SIZE - before;
+            before = tab_size - (position.start.column - 1) % tab_size;
             start += 1;
         };
 
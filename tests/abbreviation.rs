@@ -0,0 +1,212 @@
+use markdown::{
+    mdast::{
+        index_abbreviation_definitions, Abbreviation, AbbreviationDefinition, Node, Paragraph,
+        Root, Text,
+    },
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn abbreviation() -> Result<(), message::Message> {
+    let abbreviation = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                abbreviation: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("*[HTML]: Hypertext Markup Language\n\nHTML"),
+        "<p>*[HTML]: Hypertext Markup Language</p>\n<p>HTML</p>",
+        "should not support abbreviations by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*[HTML]: Hypertext Markup Language\n\nThe HTML spec.",
+            &abbreviation
+        )?,
+        "<p>The <abbr title=\"Hypertext Markup Language\">HTML</abbr> spec.</p>",
+        "should support abbreviations"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "The HTML spec.\n\n*[HTML]: Hypertext Markup Language",
+            &abbreviation
+        )?,
+        "<p>The <abbr title=\"Hypertext Markup Language\">HTML</abbr> spec.</p>\n",
+        "should support abbreviations before their definition"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[W3C]:\n\nW3C", &abbreviation)?,
+        "<p><abbr>W3C</abbr></p>",
+        "should support abbreviation definitions w/o title"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]: <b> & \"c\"\n\na", &abbreviation)?,
+        "<p><abbr title=\"&lt;b&gt; &amp; &quot;c&quot;\">a</abbr></p>",
+        "should encode titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[ HTML ]:   a  \n\nHTML", &abbreviation)?,
+        "<p><abbr title=\"a\">HTML</abbr></p>",
+        "should ignore whitespace around labels and titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("   *[a]: b\n\na", &abbreviation)?,
+        "<p><abbr title=\"b\">a</abbr></p>",
+        "should support up to three spaces of indent"
+    );
+
+    assert_eq!(
+        to_html_with_options("    *[a]: b\n\na", &abbreviation)?,
+        "<pre><code>*[a]: b\n</code></pre>\n<p>a</p>",
+        "should not support four spaces of indent"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[]: a\n*[ ]: b\n*[a", &abbreviation)?,
+        "<p>*[]: a\n*[ ]: b\n*[a</p>",
+        "should not support empty or unclosed labels"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a] b\n*[a]b", &abbreviation)?,
+        "<p>*[a] b\n*[a]b</p>",
+        "should not support a label w/o colon"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n*[a]: b", &abbreviation)?,
+        "<p>a\n*[a]: b</p>",
+        "should not support abbreviation definitions interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]: b\n*[c]: d\n# a c", &abbreviation)?,
+        "<h1><abbr title=\"b\">a</abbr> <abbr title=\"d\">c</abbr></h1>",
+        "should support abbreviation definitions next to each other"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\nXHTML HTML5 _HTML_ (HTML)", &abbreviation)?,
+        "<p>XHTML HTML5 <em><abbr title=\"a\">HTML</abbr></em> (<abbr title=\"a\">HTML</abbr>)</p>",
+        "should only support abbreviations as whole words"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[html]: a\n\nHTML html", &abbreviation)?,
+        "<p>HTML <abbr title=\"a\">html</abbr></p>",
+        "should match labels case-sensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n*[HTML 5]: b\n\nHTML 5 HTML", &abbreviation)?,
+        "<p><abbr title=\"b\">HTML 5</abbr> <abbr title=\"a\">HTML</abbr></p>",
+        "should prefer the longest label"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]: b\n*[a]: c\n\na", &abbreviation)?,
+        "<p><abbr title=\"b\">a</abbr></p>",
+        "should prefer the first of duplicate definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[é]: a\n\naé é", &abbreviation)?,
+        "<p>aé <abbr title=\"a\">é</abbr></p>",
+        "should support non-ASCII labels"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*[a]: b\n\n`a` <a> [a](a \"a\") <https://a> \\a",
+            &abbreviation
+        )?,
+        "<p><code>a</code> &lt;a&gt; <a href=\"a\" title=\"a\"><abbr title=\"b\">a</abbr></a> <a href=\"https://a\">https://a</a> \\<abbr title=\"b\">a</abbr></p>",
+        "should not support abbreviations in code, HTML, autolinks, destinations, or titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]: b\n\n![a](c)", &abbreviation)?,
+        "<p><img src=\"c\" alt=\"a\" /></p>",
+        "should support abbreviations in image alt text"
+    );
+
+    assert_eq!(
+        to_html_with_options("> *[a]: b\n\n- a", &abbreviation)?,
+        "<blockquote>\n</blockquote>\n<ul>\n<li><abbr title=\"b\">a</abbr></li>\n</ul>",
+        "should support abbreviation definitions in containers"
+    );
+
+    assert_eq!(
+        to_mdast("The HTML spec.\n\n*[HTML]: Hypertext", &abbreviation.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![
+                        Node::Text(Text {
+                            value: "The ".into(),
+                            position: Some(Position::new(1, 1, 0, 1, 5, 4))
+                        }),
+                        Node::Abbreviation(Box::new(Abbreviation {
+                            value: "HTML".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 9, 8)),
+                            title: Some("Hypertext".into())
+                        })),
+                        Node::Text(Text {
+                            value: " spec.".into(),
+                            position: Some(Position::new(1, 9, 8, 1, 15, 14))
+                        })
+                    ],
+                    position: Some(Position::new(1, 1, 0, 1, 15, 14))
+                }),
+                Node::AbbreviationDefinition(Box::new(AbbreviationDefinition {
+                    position: Some(Position::new(3, 1, 16, 3, 19, 34)),
+                    label: "HTML".into(),
+                    title: Some("Hypertext".into())
+                }))
+            ],
+            position: Some(Position::new(1, 1, 0, 3, 19, 34))
+        }),
+        "should support abbreviations as `Abbreviation`s and `AbbreviationDefinition`s in mdast"
+    );
+
+    let tree = to_mdast("*[a]: b\n*[a]: c\n*[d]:", &abbreviation.parse)?;
+    let index = index_abbreviation_definitions(&tree);
+
+    assert_eq!(
+        index.keys().collect::<Vec<_>>(),
+        vec!["a", "d"],
+        "should index abbreviation definitions by label"
+    );
+
+    assert_eq!(
+        index["a"].title.as_deref(),
+        Some("b"),
+        "should index the first of duplicate abbreviation definitions"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast(
+            "The HTML spec.\n\n*[HTML]: Hypertext\n*[W3C]:",
+            &abbreviation.parse
+        )?),
+        "The HTML spec.\n\n*[HTML]: Hypertext\n\n*[W3C]:\n",
+        "should serialize abbreviations and abbreviation definitions"
+    );
+
+    Ok(())
+}
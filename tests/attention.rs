@@ -1,6 +1,6 @@
 use markdown::{
     mdast::{Emphasis, Node, Paragraph, Root, Strong, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
@@ -851,7 +851,8 @@ fn attention() -> Result<(), message::Message> {
                             value: "alpha".into(),
                             position: Some(Position::new(1, 4, 3, 1, 9, 8))
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 10, 9))
+                        position: Some(Position::new(1, 3, 2, 1, 10, 9)),
+                        marker: Some('*'),
                     }),
                     Node::Text(Text {
                         value: " b ".into(),
@@ -862,7 +863,8 @@ fn attention() -> Result<(), message::Message> {
                             value: "bravo".into(),
                             position: Some(Position::new(1, 15, 14, 1, 20, 19))
                         }),],
-                        position: Some(Position::new(1, 13, 12, 1, 22, 21))
+                        position: Some(Position::new(1, 13, 12, 1, 22, 21)),
+                        marker: Some('*'),
                     }),
                     Node::Text(Text {
                         value: " c.".into(),
@@ -876,5 +878,44 @@ fn attention() -> Result<(), message::Message> {
         "should support attention as `Emphasis`, `Strong`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("_a_ __b__", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 2, 1, 1, 3, 2))
+                        }),],
+                        position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                        marker: Some('_'),
+                    }),
+                    Node::Text(Text {
+                        value: " ".into(),
+                        position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                    }),
+                    Node::Strong(Strong {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 7, 6, 1, 8, 7))
+                        }),],
+                        position: Some(Position::new(1, 5, 4, 1, 10, 9)),
+                        marker: Some('_'),
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 10, 9))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 10, 9))
+        }),
+        "should support `marker` fields on `Emphasis`, `Strong`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("*a* _b_ **c** __d__", &Default::default())?),
+        "*a* _b_ **c** __d__\n",
+        "should support `marker` fields to keep the markers of attention"
+    );
+
     Ok(())
 }
@@ -0,0 +1,264 @@
+use markdown::{
+    mdast::{
+        Attributes, Code, CodeKind, Heading, HeadingKind, InlineCode, Node, Paragraph, Root, Text,
+    },
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn attributes() -> Result<(), message::Message> {
+    let attributes = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attributes: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("# a {#b}"),
+        "<h1>a {#b}</h1>",
+        "should not support attributes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {#b .c .d e=f g=\"h i\"}", &attributes)?,
+        "<h1 id=\"b\" class=\"c d\" e=\"f\" g=\"h i\">a</h1>",
+        "should support attributes on headings (atx)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb {#c}\n=", &attributes)?,
+        "<h1 id=\"c\">a\nb</h1>",
+        "should support attributes on headings (setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {#b} #", &attributes)?,
+        "<h1 id=\"b\">a</h1>",
+        "should support attributes before a closing sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a{.b} \t", &attributes)?,
+        "<h1 class=\"b\">a</h1>",
+        "should support attributes w/o whitespace before, w/ whitespace after"
+    );
+
+    assert_eq!(
+        to_html_with_options("# {#a}", &attributes)?,
+        "<h1 id=\"a\"></h1>",
+        "should support attributes w/o text"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {#b} c", &attributes)?,
+        "<h1>a {#b} c</h1>",
+        "should not support attributes not at the end"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {#b}", &attributes)?,
+        "<p>a {#b}</p>",
+        "should not support attributes in paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {1,3} {} {b} {#} {.c\n# d {#e", &attributes)?,
+        "<h1>a {1,3} {} {b} {#} {.c</h1>\n<h1>d {#e</h1>",
+        "should not support invalid attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {b='c}' d=\"'\"}", &attributes)?,
+        "<h1 b=\"c}\" d=\"'\">a</h1>",
+        "should support braces and quotes in quoted values"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {id=b class=\"c d\" .e}", &attributes)?,
+        "<h1 id=\"b\" class=\"c d e\">a</h1>",
+        "should support `id` and `class` keys"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {b=\"<&>\"}", &attributes)?,
+        "<h1 b=\"&lt;&amp;&gt;\">a</h1>",
+        "should encode values"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a {onclick=\"alert(1)\" b=c}", &attributes)?,
+        "<h1 b=\"c\">a</h1>",
+        "should not support event handlers by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a {onclick=\"alert(1)\"}",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<h1 onclick=\"alert(1)\">a</h1>",
+        "should support event handlers w/ `allow_dangerous_html`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js {#a .b c=d}\ne\n```", &attributes)?,
+        "<pre><code class=\"language-js b\" id=\"a\" c=\"d\">e\n</code></pre>",
+        "should support attributes after the info of code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("``` {.a}\nb\n```\n~~~{#c}\nd\n~~~", &attributes)?,
+        "<pre><code class=\"a\">b\n</code></pre>\n<pre><code id=\"c\">d\n</code></pre>",
+        "should support attributes instead of the info of code (fenced)"
+    );
+
+    assert_eq!(
+        to_mdast("```js b=c {.d} \ne\n```", &attributes.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Code(Box::new(Code {
+                value: "e".into(),
+                position: Some(Position::new(1, 1, 0, 3, 4, 21)),
+                lang: Some("js".into()),
+                meta: Some("b=c".into()),
+                code_kind: Some(CodeKind::Fenced),
+                fence_marker: Some('`'),
+                fence_size: Some(3),
+                fence_indent: Some(0),
+                attributes: Some(Box::new(Attributes {
+                    id: None,
+                    classes: vec!["d".into()],
+                    pairs: vec![]
+                }))
+            }))],
+            position: Some(Position::new(1, 1, 0, 3, 4, 21))
+        }),
+        "should support attributes after the meta of code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js {1,3} a\nb\n```", &attributes)?,
+        "<pre><code class=\"language-js\">b\n</code></pre>",
+        "should not support invalid attributes in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$$ {#a}\nb\n$$",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        math_flow: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"language-math math-display\">b\n</code></pre>",
+        "should not support attributes on math (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `b`{#c .d} e", &attributes)?,
+        "<p>a <code id=\"c\" class=\"d\">b</code> e</p>",
+        "should support attributes after code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `b` {#c} `d`{e}", &attributes)?,
+        "<p>a <code>b</code> {#c} <code>d</code>{e}</p>",
+        "should not support attributes w/ whitespace before or invalid attributes after code (text)"
+    );
+
+    assert_eq!(
+        to_mdast("# a `b`{.c} {#d}", &attributes.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Box::new(Heading {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 5, 4))
+                    }),
+                    Node::InlineCode(InlineCode {
+                        value: "b".into(),
+                        position: Some(Position::new(1, 5, 4, 1, 12, 11)),
+                        attributes: Some(Box::new(Attributes {
+                            id: None,
+                            classes: vec!["c".into()],
+                            pairs: vec![]
+                        }))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 17, 16)),
+                depth: 1,
+                heading_kind: Some(HeadingKind::Atx),
+                attributes: Some(Box::new(Attributes {
+                    id: Some("d".into()),
+                    classes: vec![],
+                    pairs: vec![]
+                }))
+            }))],
+            position: Some(Position::new(1, 1, 0, 1, 17, 16))
+        }),
+        "should support attributes in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("a `b`{c=d}", &attributes.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::InlineCode(InlineCode {
+                        value: "b".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 11, 10)),
+                        attributes: Some(Box::new(Attributes {
+                            id: None,
+                            classes: vec![],
+                            pairs: vec![("c".into(), "d".into())]
+                        }))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support attributes on code (text) in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast(
+            "# a `b`{.c} {#d e='\"'}\n\nf {g=h}\n=\n\n```js i {j=k}\nl\n```",
+            &attributes.parse
+        )?),
+        "# a `b`{.c} {#d e='\"'}\n\nf {g=\"h\"}\n=========\n\n```js i {j=\"k\"}\nl\n```\n\n",
+        "should serialize attributes"
+    );
+
+    Ok(())
+}
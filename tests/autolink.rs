@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Link, Node, Paragraph, Root, Text},
+    mdast::{Link, LinkKind, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
@@ -287,28 +287,30 @@ fn autolink() -> Result<(), message::Message> {
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "https://alpha.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "https://alpha.com".into(),
                             position: Some(Position::new(1, 4, 3, 1, 21, 20))
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 22, 21))
-                    }),
+                        position: Some(Position::new(1, 3, 2, 1, 22, 21)),
+                        link_kind: Some(LinkKind::Autolink),
+                    })),
                     Node::Text(Text {
                         value: " b ".into(),
                         position: Some(Position::new(1, 22, 21, 1, 25, 24))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "mailto:bravo@charlie.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "bravo@charlie.com".into(),
                             position: Some(Position::new(1, 26, 25, 1, 43, 42))
                         }),],
-                        position: Some(Position::new(1, 25, 24, 1, 44, 43))
-                    }),
+                        position: Some(Position::new(1, 25, 24, 1, 44, 43)),
+                        link_kind: Some(LinkKind::Autolink),
+                    })),
                     Node::Text(Text {
                         value: " c.".into(),
                         position: Some(Position::new(1, 44, 43, 1, 47, 46))
@@ -0,0 +1,148 @@
+use markdown::{
+    mdast::{Break, Emphasis, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn breaks() -> Result<(), message::Message> {
+    let breaks = Options {
+        parse: ParseOptions {
+            breaks: true,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a\nb"),
+        "<p>a\nb</p>",
+        "should not turn line endings into hard breaks by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb", &breaks)?,
+        "<p>a<br />\nb</p>",
+        "should turn line endings into hard breaks w/ `breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\r\nb", &breaks)?,
+        "<p>a<br />\r\nb</p>",
+        "should keep the line ending after the hard break"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \nb\\\nc", &breaks)?,
+        "<p>a<br />\nb<br />\nc</p>",
+        "should not add another hard break after a hard break (trailing, escape)"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a\nb* **c\nd** [e\nf](g)", &breaks)?,
+        "<p><em>a<br />\nb</em> <strong>c<br />\nd</strong> <a href=\"g\">e<br />\nf</a></p>",
+        "should support hard breaks in emphasis, strong, and links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a\nb](c)", &breaks)?,
+        "<p><img src=\"c\" alt=\"a\nb\" /></p>",
+        "should not add hard breaks in image alt"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b\n'c')", &breaks)?,
+        "<p><a href=\"b\" title=\"c\">a</a></p>",
+        "should not add hard breaks in link resources"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "`a\nb` <i\nj>",
+            &Options {
+                parse: ParseOptions {
+                    breaks: true,
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<p><code>a b</code> <i\nj></p>",
+        "should not add hard breaks in code (text) or HTML (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n=", &breaks)?,
+        "<h1>a<br />\nb</h1>",
+        "should support hard breaks in setext headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\nb\n", &breaks)?,
+        "<p>a</p>\n<p>b</p>\n",
+        "should not add hard breaks between or after paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> [!NOTE]\n> a\n> b",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        gfm_alert: true,
+                        ..Constructs::gfm()
+                    },
+                    breaks: true,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<div class=\"markdown-alert markdown-alert-note\">\n<p class=\"markdown-alert-title\">Note</p>\n<p>a<br />\nb</p>\n</div>",
+        "should support GFM alerts w/ `breaks`"
+    );
+
+    assert_eq!(
+        to_mdast("a\n*b\nc*", &breaks.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::Break(Break {
+                        position: Some(Position::new(1, 2, 1, 2, 1, 2))
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![
+                            Node::Text(Text {
+                                value: "b".into(),
+                                position: Some(Position::new(2, 2, 3, 2, 3, 4))
+                            }),
+                            Node::Break(Break {
+                                position: Some(Position::new(2, 3, 4, 3, 1, 5))
+                            }),
+                            Node::Text(Text {
+                                value: "c".into(),
+                                position: Some(Position::new(3, 1, 5, 3, 2, 6))
+                            }),
+                        ],
+                        position: Some(Position::new(2, 1, 2, 3, 3, 7)),
+                        marker: Some('*')
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 3, 3, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 3, 7))
+        }),
+        "should support hard breaks from line endings as `Break`s in mdast"
+    );
+
+    Ok(())
+}
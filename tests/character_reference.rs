@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Node, Paragraph, Root, Text},
+    mdast::{CharacterReference, Link, LinkKind, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
@@ -229,5 +229,67 @@ fn character_reference() -> Result<(), message::Message> {
         "should support character references as `Text`s in mdast"
     );
 
+    let preserve = ParseOptions {
+        preserve_character_references: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_mdast("a &amp; &#35;b", &preserve)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::CharacterReference(Box::new(CharacterReference {
+                        value: "&".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 8, 7)),
+                        raw: "&amp;".into()
+                    })),
+                    Node::Text(Text {
+                        value: " ".into(),
+                        position: Some(Position::new(1, 8, 7, 1, 9, 8))
+                    }),
+                    Node::CharacterReference(Box::new(CharacterReference {
+                        value: "#".into(),
+                        position: Some(Position::new(1, 9, 8, 1, 14, 13)),
+                        raw: "&#35;".into()
+                    })),
+                    Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(1, 14, 13, 1, 15, 14))
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 15, 14))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 15, 14))
+        }),
+        "should support preserving character references as `CharacterReference`s in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("[&copy;](&amp;)", &preserve)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Link(Box::new(Link {
+                    children: vec![Node::CharacterReference(Box::new(CharacterReference {
+                        value: "©".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 8, 7)),
+                        raw: "&copy;".into()
+                    }))],
+                    position: Some(Position::new(1, 1, 0, 1, 16, 15)),
+                    url: "&".into(),
+                    title: None,
+                    link_kind: Some(LinkKind::Resource)
+                }))],
+                position: Some(Position::new(1, 1, 0, 1, 16, 15))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 16, 15))
+        }),
+        "should decode character references in strings when preserving them"
+    );
+
     Ok(())
 }
@@ -0,0 +1,145 @@
+use markdown::{
+    mdast::{Citation, CitationMode, Cite, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn cite() -> Result<(), message::Message> {
+    let cite = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                cite: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a [@b] c"),
+        "<p>a [@b] c</p>",
+        "should not support citations by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a [@b] c", &cite)?,
+        "<p>a <span class=\"citation\" data-cites=\"b\">[@b]</span> c</p>",
+        "should support citations in brackets"
+    );
+
+    assert_eq!(
+        to_html_with_options("a [see @b, p. 1; -@c] d", &cite)?,
+        "<p>a <span class=\"citation\" data-cites=\"b c\">[see @b, p. 1; -@c]</span> d</p>",
+        "should support several citations in brackets"
+    );
+
+    assert_eq!(
+        to_html_with_options("@a says b", &cite)?,
+        "<p><span class=\"citation\" data-cites=\"a\">@a</span> says b</p>",
+        "should support citations in text"
+    );
+
+    assert_eq!(
+        to_html_with_options("@a [p. 1] says b", &cite)?,
+        "<p><span class=\"citation\" data-cites=\"a\">@a [p. 1]</span> says b</p>",
+        "should support citations in text w/ a locator"
+    );
+
+    assert_eq!(
+        to_html_with_options("a@b.c", &cite)?,
+        "<p>a@b.c</p>",
+        "should not support citations in text after alphanumerics (emails)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a [b] c", &cite)?,
+        "<p>a [b] c</p>",
+        "should not support brackets w/o keys"
+    );
+
+    assert_eq!(
+        to_html_with_options("[@a](b)", &cite)?,
+        "<p><a href=\"b\"><span class=\"citation\" data-cites=\"a\">@a</span></a></p>",
+        "should not support citations in brackets followed by a resource (links), but support citations in text in them"
+    );
+
+    assert_eq!(
+        to_html_with_options("[@a]\n\n[@a]: b", &cite)?,
+        "<p><span class=\"citation\" data-cites=\"a\">[@a]</span></p>\n",
+        "should prefer citations over shortcut references"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[@a]*", &cite)?,
+        "<p><em><span class=\"citation\" data-cites=\"a\">[@a]</span></em></p>",
+        "should support citations in attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a [@b]](c)", &cite)?,
+        "<p><img src=\"c\" alt=\"a [@b]\" /></p>",
+        "should support citations in image descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("[@{<a>}]", &cite)?,
+        "<p><span class=\"citation\" data-cites=\"&lt;a&gt;\">[@{&lt;a&gt;}]</span></p>",
+        "should encode citations"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| @b [c] |", &{
+            let mut options = Options::gfm();
+            options.parse.constructs.cite = true;
+            options
+        })?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><span class=\"citation\" data-cites=\"b\">@b [c]</span></td>\n</tr>\n</tbody>\n</table>",
+        "should support citations in tables"
+    );
+
+    assert_eq!(
+        to_mdast("a [see @b, p. 1] c", &cite.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Cite(Cite {
+                        citations: vec![Citation {
+                            key: "b".into(),
+                            mode: CitationMode::Normal,
+                            prefix: Some("see".into()),
+                            locator: Some("p. 1".into()),
+                            suffix: None
+                        }],
+                        position: Some(Position::new(1, 3, 2, 1, 17, 16))
+                    }),
+                    Node::Text(Text {
+                        value: " c".into(),
+                        position: Some(Position::new(1, 17, 16, 1, 19, 18))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 19, 18))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 19, 18))
+        }),
+        "should support citations as `Cite`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast(
+            "[see @a, p. 1, b; -@{c d}] and @e [f]",
+            &cite.parse
+        )?),
+        "[see @a, p. 1, b; -@{c d}] and @e [f]\n",
+        "should serialize citations"
+    );
+
+    Ok(())
+}
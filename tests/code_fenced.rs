@@ -1,8 +1,8 @@
 use markdown::{
-    mdast::{Code, Node, Root},
+    mdast::{Code, CodeKind, Node, Root},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CodeMeta, CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -285,12 +285,17 @@ fn code_fenced() -> Result<(), message::Message> {
             &Default::default()
         )?,
         Node::Root(Root {
-            children: vec![Node::Code(Code {
+            children: vec![Node::Code(Box::new(Code {
                 lang: Some("js".into()),
                 meta: Some("extra".into()),
                 value: "console.log(1)\nconsole.log(2)".into(),
-                position: Some(Position::new(1, 1, 0, 4, 4, 45))
-            })],
+                position: Some(Position::new(1, 1, 0, 4, 4, 45)),
+                code_kind: Some(CodeKind::Fenced),
+                fence_marker: Some('`'),
+                fence_size: Some(3),
+                fence_indent: Some(0),
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 4, 4, 45))
         }),
         "should support code (fenced) as `Code`s in mdast"
@@ -299,12 +304,17 @@ fn code_fenced() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("```\nasd", &Default::default())?,
         Node::Root(Root {
-            children: vec![Node::Code(Code {
+            children: vec![Node::Code(Box::new(Code {
                 lang: None,
                 meta: None,
                 value: "asd".into(),
-                position: Some(Position::new(1, 1, 0, 2, 4, 7))
-            })],
+                position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+                code_kind: Some(CodeKind::Fenced),
+                fence_marker: Some('`'),
+                fence_size: Some(3),
+                fence_indent: Some(0),
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 2, 4, 7))
         }),
         "should support code (fenced) w/o closing fence in mdast"
@@ -313,12 +323,17 @@ fn code_fenced() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("```\rasd\r```", &Default::default())?,
         Node::Root(Root {
-            children: vec![Node::Code(Code {
+            children: vec![Node::Code(Box::new(Code {
                 lang: None,
                 meta: None,
                 value: "asd".into(),
-                position: Some(Position::new(1, 1, 0, 3, 4, 11))
-            })],
+                position: Some(Position::new(1, 1, 0, 3, 4, 11)),
+                code_kind: Some(CodeKind::Fenced),
+                fence_marker: Some('`'),
+                fence_size: Some(3),
+                fence_indent: Some(0),
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 3, 4, 11))
         }),
         "should support code (fenced) w/o CR line endings"
@@ -327,16 +342,93 @@ fn code_fenced() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("```\r\nasd\r\n```", &Default::default())?,
         Node::Root(Root {
-            children: vec![Node::Code(Code {
+            children: vec![Node::Code(Box::new(Code {
                 lang: None,
                 meta: None,
                 value: "asd".into(),
-                position: Some(Position::new(1, 1, 0, 3, 4, 13))
-            })],
+                position: Some(Position::new(1, 1, 0, 3, 4, 13)),
+                code_kind: Some(CodeKind::Fenced),
+                fence_marker: Some('`'),
+                fence_size: Some(3),
+                fence_indent: Some(0),
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 3, 4, 13))
         }),
         "should support code (fenced) w/o CR+LF line endings"
     );
 
+    assert_eq!(
+        to_mdast("  ~~~~\n  a\n  ~~~~", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Box::new(Code {
+                lang: None,
+                meta: None,
+                value: "a".into(),
+                position: Some(Position::new(1, 3, 2, 3, 7, 17)),
+                code_kind: Some(CodeKind::Fenced),
+                fence_marker: Some('~'),
+                fence_size: Some(4),
+                fence_indent: Some(2),
+                attributes: None,
+            }))],
+            position: Some(Position::new(1, 1, 0, 3, 7, 17))
+        }),
+        "should support `fence_marker`, `fence_size`, and `fence_indent` fields on code (fenced) in mdast"
+    );
+
+    let highlight = Options {
+        compile: CompileOptions {
+            code_highlight_lines: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("```rust {1,3-4} title=main.rs\na\nb\n\nc<\ne\n```", &highlight)?,
+        "<pre><code class=\"language-rust\"><span class=\"highlighted\">a</span>\nb\n\n<span class=\"highlighted\">c&lt;</span>\ne\n</code></pre>",
+        "should support highlighting lines w/ `code_highlight_lines`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```js {2}\n> a\n> b\n\n```\nc\n```", &highlight)?,
+        "<blockquote>\n<pre><code class=\"language-js\">a\n<span class=\"highlighted\">b</span>\n</code></pre>\n</blockquote>\n<pre><code>c\n</code></pre>",
+        "should support highlighting lines in containers, and reset afterwards"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js title=a\na\n```\n\n    b", &highlight)?,
+        "<pre><code class=\"language-js\">a\n</code></pre>\n<pre><code>b\n</code></pre>",
+        "should not highlight w/o ranges"
+    );
+
+    let tree = to_mdast(
+        "```rust,no_run title=\"x\" lines=3-5 {2}\na\n```",
+        &Default::default(),
+    )?;
+
+    if let Node::Code(code) = &tree.children().unwrap()[0] {
+        assert_eq!(
+            code.info(),
+            CodeMeta {
+                attributes: vec![
+                    ("no_run".into(), None),
+                    ("title".into(), Some("x".into())),
+                    ("lines".into(), Some("3-5".into()))
+                ],
+                highlight: vec![(2, 2)]
+            },
+            "should support structured info on `Code`"
+        );
+        assert_eq!(
+            code.meta.as_deref(),
+            Some("title=\"x\" lines=3-5 {2}"),
+            "should keep the raw meta next to structured info"
+        );
+    } else {
+        unreachable!("expected code");
+    }
+
     Ok(())
 }
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Code, Node, Root},
+    mdast::{Code, CodeKind, Node, Root},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
@@ -208,12 +208,17 @@ fn code_indented() -> Result<(), message::Message> {
             &Default::default()
         )?,
         Node::Root(Root {
-            children: vec![Node::Code(Code {
+            children: vec![Node::Code(Box::new(Code {
                 lang: None,
                 meta: None,
                 value: "console.log(1)\nconsole.log(2)".into(),
-                position: Some(Position::new(1, 1, 0, 2, 19, 34))
-            })],
+                position: Some(Position::new(1, 1, 0, 2, 19, 34)),
+                code_kind: Some(CodeKind::Indented),
+                fence_marker: None,
+                fence_size: None,
+                fence_indent: None,
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 3, 1, 35))
         }),
         "should support code (indented) as `Code`s in mdast"
@@ -191,7 +191,8 @@ fn code_text() -> Result<(), message::Message> {
                     }),
                     Node::InlineCode(InlineCode {
                         value: "alpha".into(),
-                        position: Some(Position::new(1, 3, 2, 1, 10, 9))
+                        position: Some(Position::new(1, 3, 2, 1, 10, 9)),
+                        attributes: None
                     }),
                     Node::Text(Text {
                         value: " b.".into(),
@@ -0,0 +1,186 @@
+use markdown::{
+    mdast::{CriticAddition, CriticDeletion, CriticSubstitution, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn critic_markup() -> Result<(), message::Message> {
+    let critic = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                critic_markup: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a {++b++} c"),
+        "<p>a {++b++} c</p>",
+        "should not support CriticMarkup by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++b++} c", &critic)?,
+        "<p>a <ins>b</ins> c</p>",
+        "should support additions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {--b--} c", &critic)?,
+        "<p>a <del>b</del> c</p>",
+        "should support deletions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {~~b~>c~~} d", &critic)?,
+        "<p>a <del>b</del><ins>c</ins> d</p>",
+        "should support substitutions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {==b==} c", &critic)?,
+        "<p>a <mark>b</mark> c</p>",
+        "should support highlights"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {>>b<<} c", &critic)?,
+        "<p>a <span class=\"critic comment\">b</span> c</p>",
+        "should support comments"
+    );
+
+    assert_eq!(
+        to_html_with_options("{==a==}{>>b<<}", &critic)?,
+        "<p><mark>a</mark><span class=\"critic comment\">b</span></p>",
+        "should support a comment right after a highlight"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++*b* [c](d)++} e", &critic)?,
+        "<p>a <ins><em>b</em> <a href=\"d\">c</a></ins> e</p>",
+        "should support text in CriticMarkup"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++{==b==}++} c", &critic)?,
+        "<p>a <ins><mark>b</mark></ins> c</p>",
+        "should support different kinds of CriticMarkup in each other"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a {++b*++} c", &critic)?,
+        "<p>*a <ins>b*</ins> c</p>",
+        "should not match attention inside and outside CriticMarkup"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++b\nc++} d", &critic)?,
+        "<p>a <ins>b\nc</ins> d</p>",
+        "should support line endings in CriticMarkup"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a {--b\n> c--} d", &critic)?,
+        "<blockquote>\n<p>a <del>b\nc</del> d</p>\n</blockquote>",
+        "should support line endings in CriticMarkup in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++b\n\nc++} d", &critic)?,
+        "<p>a {++b</p>\n<p>c++} d</p>",
+        "should not support CriticMarkup across paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++b", &critic)?,
+        "<p>a {++b</p>",
+        "should not support CriticMarkup w/o closing marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {++++} b", &critic)?,
+        "<p>a {++++} b</p>",
+        "should not support empty CriticMarkup"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {~~b~~} c", &critic)?,
+        "<p>a {~~b~~} c</p>",
+        "should not support substitutions w/o `~>`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {+-b-+} c", &critic)?,
+        "<p>a {+-b-+} c</p>",
+        "should not support mixed opening markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| {++b++} |", &{
+            let mut options = Options::gfm();
+            options.parse.constructs.critic_markup = true;
+            options
+        })?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><ins>b</ins></td>\n</tr>\n</tbody>\n</table>",
+        "should support CriticMarkup in tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a {++b++}](c)", &critic)?,
+        "<p><img src=\"c\" alt=\"a b\" /></p>",
+        "should support CriticMarkup in image descriptions"
+    );
+
+    assert_eq!(
+        to_mdast("a {~~b~>c~~}", &critic.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::CriticSubstitution(CriticSubstitution {
+                        children: vec![
+                            Node::CriticDeletion(CriticDeletion {
+                                children: vec![Node::Text(Text {
+                                    value: "b".into(),
+                                    position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                                })],
+                                position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                            }),
+                            Node::CriticAddition(CriticAddition {
+                                children: vec![Node::Text(Text {
+                                    value: "c".into(),
+                                    position: Some(Position::new(1, 9, 8, 1, 10, 9))
+                                })],
+                                position: Some(Position::new(1, 9, 8, 1, 10, 9))
+                            })
+                        ],
+                        position: Some(Position::new(1, 3, 2, 1, 13, 12))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+        }),
+        "should support substitutions as `CriticSubstitution`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast(
+            "a {++b++} {--c--} {~~d~>e~~} {==f==}{>>g<<}",
+            &critic.parse
+        )?),
+        "a {++b++} {--c--} {~~d~>e~~} {==f==}{>>g<<}\n",
+        "should serialize CriticMarkup"
+    );
+
+    Ok(())
+}
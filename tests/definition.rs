@@ -510,13 +510,13 @@ fn definition() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("[a]: <b> 'c'", &Default::default())?,
         Node::Root(Root {
-            children: vec![Node::Definition(Definition {
+            children: vec![Node::Definition(Box::new(Definition {
                 url: "b".into(),
                 identifier: "a".into(),
                 label: Some("a".into()),
                 title: Some("c".into()),
                 position: Some(Position::new(1, 1, 0, 1, 13, 12))
-            })],
+            }))],
             position: Some(Position::new(1, 1, 0, 1, 13, 12))
         }),
         "should support definitions as `Definition`s in mdast"
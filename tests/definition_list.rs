@@ -0,0 +1,212 @@
+use markdown::{
+    mdast::{DefinitionDescription, DefinitionList, DefinitionTerm, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn definition_list() -> Result<(), message::Message> {
+    let definition_list = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                definition_list: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a\n: b"),
+        "<p>a\n: b</p>",
+        "should not support definition lists by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>",
+        "should support definition lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n: c\n: d", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dt>b</dt>\n<dd>c</dd>\n<dd>d</dd>\n</dl>",
+        "should support several terms and descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\nc\n: d", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b\nc</dd>\n<dd>d</dd>\n</dl>",
+        "should support lazy lines in descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a\nb*\nc\n: d", &definition_list)?,
+        "<dl>\n<dt><em>a\nb</em></dt>\n<dt>c</dt>\n<dd>d</dd>\n</dl>",
+        "should support terms spanning several lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n: b", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n</dl>",
+        "should support a blank line between a term and a description (loose)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\n    c", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n<p>c</p>\n</dd>\n</dl>",
+        "should support indented flow in descriptions (loose)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\nc\n: d", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n<dt>c</dt>\n<dd>\n<p>d</p>\n</dd>\n</dl>",
+        "should support several groups in one list"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\nc", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>\n<p>c</p>",
+        "should exit a description at an unindented line after a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: - b\n    - c", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<ul>\n<li>b</li>\n<li>c</li>\n</ul>\n</dd>\n</dl>",
+        "should support containers in descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> : b", &definition_list)?,
+        "<blockquote>\n<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>\n</blockquote>",
+        "should support definition lists in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options(": a", &definition_list)?,
+        "<p>: a</p>",
+        "should not support a description without a term"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:b", &definition_list)?,
+        "<p>a\n:b</p>",
+        "should not support a marker without whitespace after it"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:\n", &definition_list)?,
+        "<p>a\n:</p>\n",
+        "should not support an empty description"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n# b\n: c", &definition_list)?,
+        "<p>a</p>\n<h1>b</h1>\n<p>: c</p>",
+        "should not support a description after something other than a paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n: b", &definition_list)?,
+        "<blockquote>\n<p>a\n: b</p>\n</blockquote>",
+        "should not support a description after a term in another container"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n: b", &definition_list)?,
+        "<ul>\n<li>a</li>\n</ul>\n<p>: b</p>",
+        "should not support a description after a term in a list item"
+    );
+
+    let gfm = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                definition_list: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("a\n: b", &gfm)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>",
+        "should support definition lists w/ GFM tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n: b", &gfm)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n</dl>",
+        "should support blank lines between a term and a description w/ GFM tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n: b", &gfm)?,
+        "<dl>\n<dt>| a |</dt>\n<dd>b</dd>\n</dl>",
+        "should support a term that looks like a table row w/ GFM tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n: b", &gfm)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>: b</td>\n</tr>\n</tbody>\n</table>",
+        "should not support a description after a table"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: | b |\n    | - |", &gfm)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<table>\n<thead>\n<tr>\n<th>b</th>\n</tr>\n</thead>\n</table>\n</dd>\n</dl>",
+        "should support tables in descriptions"
+    );
+
+    assert_eq!(
+        to_mdast("a\n: b", &definition_list.parse)?,
+        Node::Root(Root {
+            children: vec![Node::DefinitionList(DefinitionList {
+                children: vec![
+                    Node::DefinitionTerm(DefinitionTerm {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                        })],
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::DefinitionDescription(DefinitionDescription {
+                        children: vec![Node::Paragraph(Paragraph {
+                            children: vec![Node::Text(Text {
+                                value: "b".into(),
+                                position: Some(Position::new(2, 3, 4, 2, 4, 5))
+                            })],
+                            position: Some(Position::new(2, 3, 4, 2, 4, 5))
+                        })],
+                        position: Some(Position::new(2, 1, 2, 2, 4, 5))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 2, 4, 5)),
+                spread: false
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 4, 5))
+        }),
+        "should support definition lists as `DefinitionList`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("a\nb\n: c\n: d", &definition_list.parse)?),
+        "a\nb\n: c\n: d\n\n",
+        "should serialize definition lists"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast(
+            "a\n: b\n\n    c\n\nd\n: e",
+            &definition_list.parse
+        )?),
+        "a\n\n: b\n\n    c\n\nd\n\n: e\n\n",
+        "should serialize spread definition lists"
+    );
+
+    Ok(())
+}
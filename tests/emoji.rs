@@ -0,0 +1,169 @@
+use markdown::{
+    mdast::{Emoji, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+fn lookup(shortcode: &str) -> Option<String> {
+    match shortcode {
+        "tada" => Some("🎉".into()),
+        "+1" => Some("👍".into()),
+        "lt" => Some("<".into()),
+        _ => None,
+    }
+}
+
+#[test]
+fn emoji() -> Result<(), message::Message> {
+    let emoji = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                emoji: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+    let emoji_lookup = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                emoji: true,
+                ..Constructs::default()
+            },
+            emoji_lookup: Some(Box::new(lookup)),
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a :tada: b"),
+        "<p>a :tada: b</p>",
+        "should not support emoji by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :tada: b", &emoji)?,
+        "<p>a :tada: b</p>",
+        "should keep shortcodes w/o `emoji_lookup`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :tada: b :+1:", &emoji_lookup)?,
+        "<p>a 🎉 b 👍</p>",
+        "should support emoji w/ `emoji_lookup`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b: :tada:", &emoji_lookup)?,
+        "<p>a :b: 🎉</p>",
+        "should not support unknown shortcodes w/ `emoji_lookup`"
+    );
+
+    assert_eq!(
+        to_html_with_options(":lt:", &emoji_lookup)?,
+        "<p>&lt;</p>",
+        "should encode the value of emoji"
+    );
+
+    assert_eq!(
+        to_html_with_options(":tada::tada:", &emoji_lookup)?,
+        "<p>🎉🎉</p>",
+        "should support emoji next to each other"
+    );
+
+    assert_eq!(
+        to_html_with_options("a:tada: 10:tada:", &emoji_lookup)?,
+        "<p>a:tada: 10:tada:</p>",
+        "should not support emoji after an ASCII alphanumeric"
+    );
+
+    assert_eq!(
+        to_html_with_options("(:tada:)", &emoji_lookup)?,
+        "<p>(🎉)</p>",
+        "should support emoji after punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options(":: : tada: :tada : :ta da:", &emoji_lookup)?,
+        "<p>:: : tada: :tada : :ta da:</p>",
+        "should not support empty shortcodes or whitespace in shortcodes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":ta\nda:", &emoji_lookup)?,
+        "<p>:ta\nda:</p>",
+        "should not support line endings in shortcodes"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\:tada:", &emoji_lookup)?,
+        "<p>:tada:</p>",
+        "should not support an escaped marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("`:tada:`", &emoji_lookup)?,
+        "<p><code>:tada:</code></p>",
+        "should not support emoji in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a :tada:](b)", &emoji_lookup)?,
+        "<p><img src=\"b\" alt=\"a 🎉\" /></p>",
+        "should support emoji in image alt text"
+    );
+
+    assert_eq!(
+        to_mdast("a :tada: b", &emoji_lookup.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Emoji(Box::new(Emoji {
+                        value: "🎉".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 9, 8)),
+                        shortcode: "tada".into()
+                    })),
+                    Node::Text(Text {
+                        value: " b".into(),
+                        position: Some(Position::new(1, 9, 8, 1, 11, 10))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support emoji as `Emoji`s in mdast"
+    );
+
+    assert_eq!(
+        to_mdast(":a:", &emoji.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Emoji(Box::new(Emoji {
+                    value: ":a:".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                    shortcode: "a".into()
+                }))],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+        }),
+        "should use the shortcode as value w/o `emoji_lookup`"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("a :tada: b :c:", &emoji_lookup.parse)?),
+        "a :tada: b :c:\n",
+        "should serialize emoji"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use markdown::{
+    mdast::{InlineCode, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Extension, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// A mention of a user, such as `@alice`.
+fn mention() -> Extension {
+    Extension {
+        markers: vec![b'@'],
+        tokenize: Box::new(|value| {
+            let size = value[1..]
+                .bytes()
+                .take_while(u8::is_ascii_alphanumeric)
+                .count();
+            if size == 0 {
+                None
+            } else {
+                Some(size + 1)
+            }
+        }),
+        to_html: Box::new(|value| format!("<a href=\"/{}\">{}</a>", &value[1..], value)),
+        to_mdast: Box::new(|value| {
+            Node::InlineCode(InlineCode {
+                value: value.into(),
+                position: None,
+                attributes: None,
+            })
+        }),
+    }
+}
+
+/// A keyboard key, such as `++ctrl++`.
+fn key() -> Extension {
+    Extension {
+        markers: vec![b'+', b'*'],
+        tokenize: Box::new(|value| {
+            let marker = &value[..1];
+            let open = value.len() - value.trim_start_matches(marker).len();
+            let rest = &value[open..];
+            if open != 2 {
+                return None;
+            }
+            let close = format!("{}{}", marker, marker);
+            rest.find(&close)
+                .filter(|size| *size > 0)
+                .map(|size| open + size + 2)
+        }),
+        to_html: Box::new(|value| format!("<kbd>{}</kbd>", &value[2..value.len() - 2])),
+        to_mdast: Box::new(|value| {
+            Node::Text(Text {
+                value: value.into(),
+                position: None,
+            })
+        }),
+    }
+}
+
+fn with(extensions: Vec<Extension>) -> Options {
+    Options {
+        parse: ParseOptions {
+            extensions,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    }
+}
+
+#[test]
+fn extension() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("a @b c"),
+        "<p>a @b c</p>",
+        "should not support extensions by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a @b c", &with(vec![mention()]))?,
+        "<p>a <a href=\"/b\">@b</a> c</p>",
+        "should support an extension"
+    );
+
+    assert_eq!(
+        to_html_with_options("@b", &with(vec![mention()]))?,
+        "<p><a href=\"/b\">@b</a></p>",
+        "should support an extension at the start and end"
+    );
+
+    assert_eq!(
+        to_html_with_options("a @ b @. c", &with(vec![mention()]))?,
+        "<p>a @ b @. c</p>",
+        "should fall back to data if an extension does not match"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ++b++ **c** ++d", &with(vec![key()]))?,
+        "<p>a <kbd>b</kbd> <kbd>c</kbd> ++d</p>",
+        "should support several markers, before the constructs of `markdown-rs`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a *b* ***c***", &with(vec![key()]))?,
+        "<p>a <em>b</em> <em><strong>c</strong></em></p>",
+        "should support constructs of `markdown-rs` if an extension does not match"
+    );
+
+    let mut first = mention();
+    first.to_html = Box::new(|_| "1".into());
+    let mut second = mention();
+    second.to_html = Box::new(|_| "2".into());
+
+    assert_eq!(
+        to_html_with_options("a @b", &with(vec![first, second]))?,
+        "<p>a 1</p>",
+        "should use the first extension that matches"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ++b\nc++", &with(vec![key()]))?,
+        "<p>a ++b\nc++</p>",
+        "should not support line endings in extensions"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a @b* [@c](d)", &with(vec![mention()]))?,
+        "<p><em>a <a href=\"/b\">@b</a></em> <a href=\"d\"><a href=\"/c\">@c</a></a></p>",
+        "should support extensions in other constructs"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a @b](c)", &with(vec![mention()]))?,
+        "<p><img src=\"c\" alt=\"a @b\" /></p>",
+        "should support extensions in image alt text"
+    );
+
+    assert_eq!(
+        to_html_with_options("`@b` \\@c", &with(vec![mention()]))?,
+        "<p><code>@b</code> @c</p>",
+        "should not support extensions in code or after escapes"
+    );
+
+    let invalid = |size: usize| Extension {
+        markers: vec![b'@'],
+        tokenize: Box::new(move |_| Some(size)),
+        to_html: Box::new(|_| "x".into()),
+        to_mdast: Box::new(|_| {
+            Node::Text(Text {
+                value: "x".into(),
+                position: None,
+            })
+        }),
+    };
+
+    assert_eq!(
+        to_html_with_options("a @b", &with(vec![invalid(0)]))?,
+        "<p>a @b</p>",
+        "should ignore empty extensions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a @b", &with(vec![invalid(3)]))?,
+        "<p>a @b</p>",
+        "should ignore extensions past the end of the line"
+    );
+
+    assert_eq!(
+        to_html_with_options("a @é", &with(vec![invalid(2)]))?,
+        "<p>a @é</p>",
+        "should ignore extensions that end inside a character"
+    );
+
+    assert_eq!(
+        to_mdast("a @bc d", &with(vec![mention()]).parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::InlineCode(InlineCode {
+                        value: "@bc".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 6, 5)),
+                        attributes: None
+                    }),
+                    Node::Text(Text {
+                        value: " d".into(),
+                        position: Some(Position::new(1, 6, 5, 1, 8, 7))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 8, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should support extensions as nodes in mdast"
+    );
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut counted = mention();
+    let tokenize = counted.tokenize;
+    let count = Arc::clone(&calls);
+    counted.tokenize = Box::new(move |value| {
+        count.fetch_add(1, Ordering::Relaxed);
+        tokenize(value)
+    });
+    let options = with(vec![counted]);
+    to_html_with_options("a @b", &options)?;
+    to_mdast("a @b", &options.parse)?;
+    assert_eq!(
+        calls.load(Ordering::Relaxed),
+        2,
+        "should match extensions once, when parsing, and not again when compiling"
+    );
+
+    Ok(())
+}
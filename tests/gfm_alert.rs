@@ -0,0 +1,148 @@
+use markdown::{
+    mdast::{AlertKind, BlockQuote, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn gfm_alert() -> Result<(), message::Message> {
+    let alert = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                gfm_alert: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("> [!NOTE]\n> a"),
+        "<blockquote>\n<p>[!NOTE]\na</p>\n</blockquote>",
+        "should ignore alerts by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE]\n> a", &Options::gfm())?,
+        "<blockquote>\n<p>[!NOTE]\na</p>\n</blockquote>",
+        "should ignore alerts in GFM mode"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE]\n> a", &alert)?,
+        "<div class=\"markdown-alert markdown-alert-note\">\n<p class=\"markdown-alert-title\">Note</p>\n<p>a</p>\n</div>",
+        "should support alerts"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> [!TIP]\n\n> [!IMPORTANT]\n\n> [!WARNING]\n\n> [!CAUTION]",
+            &alert
+        )?,
+        "<div class=\"markdown-alert markdown-alert-tip\">\n<p class=\"markdown-alert-title\">Tip</p>\n</div>\n<div class=\"markdown-alert markdown-alert-important\">\n<p class=\"markdown-alert-title\">Important</p>\n</div>\n<div class=\"markdown-alert markdown-alert-warning\">\n<p class=\"markdown-alert-title\">Warning</p>\n</div>\n<div class=\"markdown-alert markdown-alert-caution\">\n<p class=\"markdown-alert-title\">Caution</p>\n</div>",
+        "should support all kinds of alerts"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!tip]\n> a", &alert)?,
+        "<div class=\"markdown-alert markdown-alert-tip\">\n<p class=\"markdown-alert-title\">Tip</p>\n<p>a</p>\n</div>",
+        "should support alerts case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE]  \n> a\n> b", &alert)?,
+        "<div class=\"markdown-alert markdown-alert-note\">\n<p class=\"markdown-alert-title\">Note</p>\n<p>a\nb</p>\n</div>",
+        "should support whitespace after the marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE]\n>\n> a", &alert)?,
+        "<div class=\"markdown-alert markdown-alert-note\">\n<p class=\"markdown-alert-title\">Note</p>\n<p>a</p>\n</div>",
+        "should support a blank line after the marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE]\na", &alert)?,
+        "<div class=\"markdown-alert markdown-alert-note\">\n<p class=\"markdown-alert-title\">Note</p>\n<p>a</p>\n</div>",
+        "should support a lazy line after the marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("- > [!CAUTION]\n  > *a*", &alert)?,
+        "<ul>\n<li>\n<div class=\"markdown-alert markdown-alert-caution\">\n<p class=\"markdown-alert-title\">Caution</p>\n<p><em>a</em></p>\n</div>\n</li>\n</ul>",
+        "should support alerts in other containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE] a", &alert)?,
+        "<blockquote>\n<p>[!NOTE] a</p>\n</blockquote>",
+        "should not support content after the marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> [!NOTE]", &alert)?,
+        "<blockquote>\n<p>a\n[!NOTE]</p>\n</blockquote>",
+        "should not support a marker that is not on the first line"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOPE]\n> a", &alert)?,
+        "<blockquote>\n<p>[!NOPE]\na</p>\n</blockquote>",
+        "should not support unknown kinds"
+    );
+
+    assert_eq!(
+        to_html_with_options("> # [!NOTE]", &alert)?,
+        "<blockquote>\n<h1>[!NOTE]</h1>\n</blockquote>",
+        "should not support a marker outside of a paragraph"
+    );
+
+    assert_eq!(
+        to_mdast("> [!NOTE]\n> a", &alert.parse)?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(2, 3, 12, 2, 4, 13))
+                    })],
+                    position: Some(Position::new(2, 3, 12, 2, 4, 13))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 4, 13)),
+                alert: Some(AlertKind::Note)
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 4, 13))
+        }),
+        "should support alerts as `BlockQuote`s w/ `alert` in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("> [!warning]\n> a", &alert.parse)?),
+        "> [!WARNING]\n> a\n",
+        "should serialize alerts"
+    );
+
+    assert!(
+        to_html_with_options(
+            "---\n\n>",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        frontmatter: true,
+                        gfm_alert: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )
+        .is_ok(),
+        "should not crash on an empty block quote at the end w/ frontmatter"
+    );
+
+    Ok(())
+}
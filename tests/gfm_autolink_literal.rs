@@ -4,10 +4,10 @@
 // Remove when solved.
 
 use markdown::{
-    mdast::{Link, Node, Paragraph, Root, Text},
+    mdast::{Link, LinkKind, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Options, ParseOptions,
+    CompileOptions, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -2751,67 +2751,72 @@ www.a/~
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "https://alpha.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "https://alpha.com".into(),
                             position: Some(Position::new(1, 3, 2, 1, 20, 19))
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 20, 19))
-                    }),
+                        position: Some(Position::new(1, 3, 2, 1, 20, 19)),
+                        link_kind: Some(LinkKind::GfmAutolinkLiteral),
+                    })),
                     Node::Text(Text {
                         value: " b ".into(),
                         position: Some(Position::new(1, 20, 19, 1, 23, 22))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "mailto:bravo@charlie.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "bravo@charlie.com".into(),
                             position: Some(Position::new(1, 23, 22, 1, 40, 39))
                         }),],
-                        position: Some(Position::new(1, 23, 22, 1, 40, 39))
-                    }),
+                        position: Some(Position::new(1, 23, 22, 1, 40, 39)),
+                        link_kind: Some(LinkKind::GfmAutolinkLiteral),
+                    })),
                     Node::Text(Text {
                         value: " c ".into(),
                         position: Some(Position::new(1, 40, 39, 1, 43, 42))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "http://www.delta.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "www.delta.com".into(),
                             position: Some(Position::new(1, 43, 42, 1, 56, 55))
                         }),],
-                        position: Some(Position::new(1, 43, 42, 1, 56, 55))
-                    }),
+                        position: Some(Position::new(1, 43, 42, 1, 56, 55)),
+                        link_kind: Some(LinkKind::GfmAutolinkLiteral),
+                    })),
                     Node::Text(Text {
                         value: " d ".into(),
                         position: Some(Position::new(1, 56, 55, 1, 59, 58))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "xmpp:echo@foxtrot.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "xmpp:echo@foxtrot.com".into(),
                             position: Some(Position::new(1, 59, 58, 1, 80, 79))
                         }),],
-                        position: Some(Position::new(1, 59, 58, 1, 80, 79))
-                    }),
+                        position: Some(Position::new(1, 59, 58, 1, 80, 79)),
+                        link_kind: Some(LinkKind::GfmAutolinkLiteral),
+                    })),
                     Node::Text(Text {
                         value: " e ".into(),
                         position: Some(Position::new(1, 80, 79, 1, 83, 82))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "mailto:golf@hotel.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "mailto:golf@hotel.com".into(),
                             position: Some(Position::new(1, 83, 82, 1, 104, 103))
                         }),],
-                        position: Some(Position::new(1, 83, 82, 1, 104, 103))
-                    }),
+                        position: Some(Position::new(1, 83, 82, 1, 104, 103)),
+                        link_kind: Some(LinkKind::GfmAutolinkLiteral),
+                    })),
                     Node::Text(Text {
                         value: " f.".into(),
                         position: Some(Position::new(1, 104, 103, 1, 107, 106))
@@ -2826,3 +2831,121 @@ www.a/~
 
     Ok(())
 }
+
+#[test]
+fn gfm_autolink_literal_options() -> Result<(), message::Message> {
+    let with = |protocols: &[&str], www: bool| Options {
+        parse: ParseOptions {
+            gfm_autolink_literal_protocols: protocols.iter().map(|d| (*d).into()).collect(),
+            gfm_autolink_literal_www: www,
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            allow_dangerous_protocol: true,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "a ftp://b.c d sftp://e.f g",
+            &with(&["ftp", "sftp", "https"], true)
+        )?,
+        "<p>a <a href=\"ftp://b.c\">ftp://b.c</a> d <a href=\"sftp://e.f\">sftp://e.f</a> g</p>",
+        "should support other protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("FTP://a.b Ftp://c.d", &with(&["ftp"], true))?,
+        "<p><a href=\"FTP://a.b\">FTP://a.b</a> <a href=\"Ftp://c.d\">Ftp://c.d</a></p>",
+        "should support protocols case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("s3://a.b git+ssh://c.d", &with(&["s3", "git+ssh"], true))?,
+        "<p><a href=\"s3://a.b\">s3://a.b</a> <a href=\"git+ssh://c.d\">git+ssh://c.d</a></p>",
+        "should support digits, `+`, `-`, and `.` in protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("wss://a.b www.c.d", &with(&["wss"], true))?,
+        "<p><a href=\"wss://a.b\">wss://a.b</a> <a href=\"http://www.c.d\">www.c.d</a></p>",
+        "should support protocols starting with `w`"
+    );
+
+    assert_eq!(
+        to_html_with_options("http://a.b https://c.d", &with(&["https"], true))?,
+        "<p>http://a.b <a href=\"https://c.d\">https://c.d</a></p>",
+        "should support leaving out default protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("aftp://b.c ftp:/d.e ftp:f.g", &with(&["ftp"], true))?,
+        "<p>aftp://b.c ftp:/d.e ftp:f.g</p>",
+        "should not support other protocols after letters, or without `//`"
+    );
+
+    assert_eq!(
+        to_html_with_options("https://a.b www.c.d e@f.gh", &with(&[], false))?,
+        "<p>https://a.b www.c.d <a href=\"mailto:e@f.gh\">e@f.gh</a></p>",
+        "should support turning off protocols and www"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "www.a.b",
+            &Options {
+                parse: ParseOptions {
+                    gfm_autolink_literal_www: false,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>www.a.b</p>",
+        "should do nothing if the construct is off"
+    );
+
+    let filter = Options {
+        parse: ParseOptions {
+            gfm_autolink_literal_filter: Some(Box::new(|value| !value.contains("evil"))),
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("a https://evil.com b https://good.com c", &filter)?,
+        "<p>a https://evil.com b <a href=\"https://good.com\">https://good.com</a> c</p>",
+        "should support vetoing protocol literals"
+    );
+
+    assert_eq!(
+        to_html_with_options("a www.evil.com b www.good.com c", &filter)?,
+        "<p>a www.evil.com b <a href=\"http://www.good.com\">www.good.com</a> c</p>",
+        "should support vetoing www literals"
+    );
+
+    assert_eq!(
+        to_html_with_options("a x@evil.com b mailto:y@evil.com c z@good.com d", &filter)?,
+        "<p>a x@evil.com b mailto:y@evil.com c <a href=\"mailto:z@good.com\">z@good.com</a> d</p>",
+        "should support vetoing email literals"
+    );
+
+    assert_eq!(
+        to_mdast("a www.evil.com b", &filter.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a www.evil.com b".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 17, 16))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 17, 16))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 17, 16))
+        }),
+        "should support vetoing literals (mdast)"
+    );
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{FootnoteDefinition, FootnoteReference, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Options, ParseOptions,
+    CompileOptions, GfmFootnotePlacementKind, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -1625,7 +1625,7 @@ multi-paragraph list items. <a href="#user-content-fnref-longnote" data-footnote
         to_mdast("[^a]: b\n\tc\n\nd [^a] e.", &ParseOptions::gfm())?,
         Node::Root(Root {
             children: vec![
-                Node::FootnoteDefinition(FootnoteDefinition {
+                Node::FootnoteDefinition(Box::new(FootnoteDefinition {
                     children: vec![Node::Paragraph(Paragraph {
                         children: vec![Node::Text(Text {
                             value: "b\nc".into(),
@@ -1636,18 +1636,18 @@ multi-paragraph list items. <a href="#user-content-fnref-longnote" data-footnote
                     identifier: "a".into(),
                     label: Some("a".into()),
                     position: Some(Position::new(1, 1, 0, 3, 1, 11))
-                }),
+                })),
                 Node::Paragraph(Paragraph {
                     children: vec![
                         Node::Text(Text {
                             value: "d ".into(),
                             position: Some(Position::new(4, 1, 12, 4, 3, 14))
                         }),
-                        Node::FootnoteReference(FootnoteReference {
+                        Node::FootnoteReference(Box::new(FootnoteReference {
                             identifier: "a".into(),
                             label: Some("a".into()),
                             position: Some(Position::new(4, 3, 14, 4, 7, 18))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " e.".into(),
                             position: Some(Position::new(4, 7, 18, 4, 10, 21))
@@ -1661,5 +1661,85 @@ multi-paragraph list items. <a href="#user-content-fnref-longnote" data-footnote
         "should support GFM footnotes as `FootnoteDefinition`, `FootnoteReference`s in mdast"
     );
 
+    let sections = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            gfm_footnote_placement: GfmFootnotePlacementKind::Section,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("# z\n\na[^x]\n\n## y\n\n> # w\n\nb\n===\n\nc[^y] d[^x]\n\n[^x]: e\n[^y]: f", &sections)?,
+        "<h1>z</h1>
+<p>a<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<h2>y</h2>
+<blockquote>
+<h1>w</h1>
+</blockquote>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-x\">
+<p>e <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a> <a href=\"#user-content-fnref-x-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩<sup>2</sup></a></p>
+</li>
+</ol>
+</section>
+<h1>b</h1>
+<p>c<sup><a href=\"#user-content-fn-y\" id=\"user-content-fnref-y\" data-footnote-ref=\"\" aria-describedby=\"footnote-label-2\">2</a></sup> d<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label-2\" class=\"sr-only\">Footnotes</h2>
+<ol start=\"2\">
+<li id=\"user-content-fn-y\">
+<p>f <a href=\"#user-content-fnref-y\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support placing footnotes at the end of top-level sections"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a[^x]\n# b\n\n[^x]: c\n\n# d", &sections)?,
+        "<ul>
+<li>a<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></li>
+</ul>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-x\">
+<p>c <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+<h1>b</h1>
+<h1>d</h1>",
+        "should not add sections w/o footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb[^x] c[^y]\n\n# d\n\ne[^z]\n\n[^x]: f\n[^y]: g\n[^z]: h", &sections)?,
+        "<h1>a</h1>
+<p>b<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c<sup><a href=\"#user-content-fn-y\" id=\"user-content-fnref-y\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-x\">
+<p>f <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-y\">
+<p>g <a href=\"#user-content-fnref-y\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+<h1>d</h1>
+<p>e<sup><a href=\"#user-content-fn-z\" id=\"user-content-fnref-z\" data-footnote-ref=\"\" aria-describedby=\"footnote-label-2\">3</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label-2\" class=\"sr-only\">Footnotes</h2>
+<ol start=\"3\">
+<li id=\"user-content-fn-z\">
+<p>h <a href=\"#user-content-fnref-z\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should continue numbering footnotes in later sections"
+    );
+
     Ok(())
 }
@@ -1,6 +1,6 @@
 use markdown::{
-    mdast::{AlignKind, InlineCode, Node, Root, Table, TableCell, TableRow, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    mdast::{AlignKind, Emphasis, InlineCode, Node, Root, Table, TableCell, TableRow, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
@@ -1843,7 +1843,7 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
             &ParseOptions::gfm()
         )?,
         Node::Root(Root {
-            children: vec![Node::Table(Table {
+            children: vec![Node::Table(Box::new(Table {
                 align: vec![
                     AlignKind::None,
                     AlignKind::Left,
@@ -1935,8 +1935,9 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
                         position: Some(Position::new(4, 1, 61, 4, 22, 82))
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 4, 22, 82))
-            })],
+                position: Some(Position::new(1, 1, 0, 4, 22, 82)),
+                caption: None
+            }))],
             position: Some(Position::new(1, 1, 0, 4, 22, 82))
         }),
         "should support GFM tables as `Table`, `TableRow`, `TableCell`s in mdast"
@@ -1945,20 +1946,22 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
     assert_eq!(
         to_mdast("| `a\\|b` |\n| - |", &ParseOptions::gfm())?,
         Node::Root(Root {
-            children: vec![Node::Table(Table {
+            children: vec![Node::Table(Box::new(Table {
                 align: vec![AlignKind::None,],
                 children: vec![Node::TableRow(TableRow {
                     children: vec![Node::TableCell(TableCell {
                         children: vec![Node::InlineCode(InlineCode {
                             value: "a|b".into(),
-                            position: Some(Position::new(1, 3, 2, 1, 9, 8))
+                            position: Some(Position::new(1, 3, 2, 1, 9, 8)),
+                            attributes: None
                         }),],
                         position: Some(Position::new(1, 1, 0, 1, 11, 10))
                     }),],
                     position: Some(Position::new(1, 1, 0, 1, 11, 10))
                 }),],
-                position: Some(Position::new(1, 1, 0, 2, 6, 16))
-            })],
+                position: Some(Position::new(1, 1, 0, 2, 6, 16)),
+                caption: None
+            }))],
             position: Some(Position::new(1, 1, 0, 2, 6, 16))
         }),
         "should support weird pipe escapes in code in tables"
@@ -1966,3 +1969,122 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
 
     Ok(())
 }
+
+#[test]
+fn gfm_table_caption() -> Result<(), message::Message> {
+    let caption = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                gfm_table: true,
+                gfm_table_caption: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |\nTable: c", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n<tr>\n<td>Table: c</td>\n</tr>\n</tbody>\n</table>",
+        "should not support captions by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |\nTable: c", &caption)?,
+        "<table>\n<caption>c</caption>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>",
+        "should support a `Table:` caption"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\nCaption: b", &caption)?,
+        "<table>\n<caption>b</caption>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>",
+        "should support a `Caption:` caption after a head"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n[ b ]  ", &caption)?,
+        "<table>\n<caption>b</caption>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>",
+        "should support a caption in brackets"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n[b][c]", &caption)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>[b][c]</td>\n</tr>\n</tbody>\n</table>",
+        "should not support `]` in a caption in brackets"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\nTable:  \nb", &caption)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>Table:</td>\n</tr>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>",
+        "should not support empty captions"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\nTable: *b* `c`", &caption)?,
+        "<table>\n<caption><em>b</em> <code>c</code></caption>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>",
+        "should support text in captions"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\nTable: b\n| c |", &caption)?,
+        "<table>\n<caption>b</caption>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>\n<p>| c |</p>",
+        "should end the table at a caption"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n\nTable: b", &caption)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>\n<p>Table: b</p>",
+        "should not support captions after blank lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("> | a |\n> | - |\nTable: b", &caption)?,
+        "<blockquote>\n<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>\n</blockquote>\n<p>Table: b</p>",
+        "should not support lazy captions"
+    );
+
+    assert_eq!(
+        to_html_with_options("> | a |\n> | - |\n> Table: b", &caption)?,
+        "<blockquote>\n<table>\n<caption>b</caption>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>\n</blockquote>",
+        "should support captions in containers"
+    );
+
+    assert_eq!(
+        to_mdast("| a |\n| - |\nTable: *b*", &caption.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Table(Box::new(Table {
+                align: vec![AlignKind::None],
+                children: vec![Node::TableRow(TableRow {
+                    children: vec![Node::TableCell(TableCell {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        })],
+                        position: Some(Position::new(1, 1, 0, 1, 6, 5))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 6, 5))
+                })],
+                position: Some(Position::new(1, 1, 0, 3, 11, 22)),
+                caption: Some(vec![Node::Emphasis(Emphasis {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(3, 9, 20, 3, 10, 21))
+                    })],
+                    position: Some(Position::new(3, 8, 19, 3, 11, 22)),
+                    marker: Some('*')
+                })])
+            }))],
+            position: Some(Position::new(1, 1, 0, 3, 11, 22))
+        }),
+        "should support captions as `caption` on `Table`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("| a |\n| - |\n| b |\n[*c*]", &caption.parse)?),
+        "| a |\n| --- |\n| b |\nTable: *c*\n\n",
+        "should serialize captions"
+    );
+
+    Ok(())
+}
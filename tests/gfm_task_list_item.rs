@@ -299,7 +299,8 @@ Text.</li>
                         position: Some(Position::new(3, 1, 16, 3, 4, 19))
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 3, 4, 19))
+                position: Some(Position::new(1, 1, 0, 3, 4, 19)),
+                marker: Some('*'),
             })],
             position: Some(Position::new(1, 1, 0, 3, 4, 19))
         }),
@@ -350,14 +351,16 @@ Text.</li>
                                     value: "c".into(),
                                     position: Some(Position::new(5, 2, 30, 5, 3, 31))
                                 }),],
-                                position: Some(Position::new(5, 1, 29, 5, 4, 32))
+                                position: Some(Position::new(5, 1, 29, 5, 4, 32)),
+                                marker: Some('*'),
                             })],
                             position: Some(Position::new(5, 1, 29, 5, 4, 32))
                         })],
                         position: Some(Position::new(4, 1, 21, 5, 4, 32))
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 5, 4, 32))
+                position: Some(Position::new(1, 1, 0, 5, 4, 32)),
+                marker: Some('*'),
             })],
             position: Some(Position::new(1, 1, 0, 5, 4, 32))
         }),
@@ -0,0 +1,215 @@
+use markdown::{
+    mdast::{AlignKind, List, ListItem, Node, Paragraph, Root, Table, TableCell, TableRow, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn grid_table() -> Result<(), message::Message> {
+    let grid = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                grid_table: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("+---+\n| a |\n+===+"),
+        "<p>+---+\n| a |\n+===+</p>",
+        "should not support grid tables by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("+---+---+\n| a | b |\n+===+===+\n| c | d |\n+---+---+", &grid)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th>b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c</td>\n<td>d</td>\n</tr>\n</tbody>\n</table>",
+        "should support grid tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("+---+\n| a |\n+===+", &grid)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>",
+        "should support grid tables w/o body"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "+-----+-----+\n| a   | b   |\n+=====+=====+\n| - c | d   |\n| - e |     |\n+-----+-----+\n| f   |     |\n|     |     |\n| g   |     |\n+-----+-----+",
+            &grid
+        )?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th>b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>\n<ul>\n<li>c</li>\n<li>e</li>\n</ul>\n</td>\n<td>d</td>\n</tr>\n<tr>\n<td>\n<p>f</p>\n<p>g</p>\n</td>\n<td></td>\n</tr>\n</tbody>\n</table>",
+        "should support flow content in cells spanning multiple lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("+-------+\n| a     |\n+=======+\n| ```js |\n| b     |\n| ```   |\n+-------+", &grid)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>\n<pre><code class=\"language-js\">b\n</code></pre>\n</td>\n</tr>\n</tbody>\n</table>",
+        "should support code in cells"
+    );
+
+    assert_eq!(
+        to_html_with_options("+----+----+-----+-----+\n| a  | b  | c   | d   |\n+====+:===+====:+:===:+\n| e  | f  | g   | h   |\n+----+----+-----+-----+", &grid)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n<th align=\"left\">b</th>\n<th align=\"right\">c</th>\n<th align=\"center\">d</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>e</td>\n<td align=\"left\">f</td>\n<td align=\"right\">g</td>\n<td align=\"center\">h</td>\n</tr>\n</tbody>\n</table>",
+        "should support alignment in the head border"
+    );
+
+    assert_eq!(
+        to_html_with_options("+------+\n| 中文 |\n+======+", &grid)?,
+        "<table>\n<thead>\n<tr>\n<th>中文</th>\n</tr>\n</thead>\n</table>",
+        "should measure wide characters by their width"
+    );
+
+    assert_eq!(
+        to_html_with_options("> +---+\n> | a |\n> +===+\n> | b |\n> +---+", &grid)?,
+        "<blockquote>\n<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>\n</blockquote>",
+        "should support grid tables in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "+-----------+\n| a         |\n+===========+\n| +---+---+ |\n| | b | c | |\n| +===+===+ |\n+-----------+",
+            &grid
+        )?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>\n<table>\n<thead>\n<tr>\n<th>b</th>\n<th>c</th>\n</tr>\n</thead>\n</table>\n</td>\n</tr>\n</tbody>\n</table>",
+        "should support grid tables in cells"
+    );
+
+    assert_eq!(
+        to_html_with_options("+-----+\n| [a] |\n+=====+\n\n[a]: b", &grid)?,
+        "<table>\n<thead>\n<tr>\n<th><a href=\"b\">a</a></th>\n</tr>\n</thead>\n</table>\n",
+        "should support references to definitions in the document"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "+--------+\n| [a]    |\n|        |\n| [a]: b |\n+========+\n\n[a]",
+            &grid
+        )?,
+        "<table>\n<thead>\n<tr>\n<th>\n<p><a href=\"b\">a</a></p>\n</th>\n</tr>\n</thead>\n</table>\n<p>[a]</p>",
+        "should support definitions in cells, only in that cell"
+    );
+
+    assert_eq!(
+        to_html_with_options("+---+\n| a |\n+---+", &grid)?,
+        "<p>+---+\n| a |\n+---+</p>",
+        "should not support grid tables w/o head"
+    );
+
+    assert_eq!(
+        to_html_with_options("+---+\n| a  |\n+===+", &grid)?,
+        "<p>+---+\n| a  |\n+===+</p>",
+        "should not support rows w/o dividers at the columns of the border"
+    );
+
+    assert_eq!(
+        to_html_with_options("+---+\n| a |\n+====+", &grid)?,
+        "<p>+---+\n| a |\n+====+</p>",
+        "should not support borders w/o `+` at the columns of the first border"
+    );
+
+    assert_eq!(
+        to_html_with_options("+---+\n| a |\n+===+\n| b |", &grid)?,
+        "<p>+---+\n| a |\n+===+\n| b |</p>",
+        "should not support grid tables ending in a row"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n+---+\n| b |\n+===+", &grid)?,
+        "<p>a\n+---+\n| b |\n+===+</p>",
+        "should not support grid tables interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options(" +---+\n | a |\n +===+", &grid)?,
+        "<p>+---+\n| a |\n+===+</p>",
+        "should not support indented grid tables"
+    );
+
+    assert_eq!(
+        to_mdast(
+            "+-------+\n| > *a* |\n+=======+",
+            &ParseOptions {
+                constructs: Constructs {
+                    grid_table: true,
+                    ..Default::default()
+                },
+                max_nesting: Some(1),
+                ..Default::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "2:5: Unexpected construct nested deeper than `max_nesting` (1), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should place messages from cells in the document"
+    );
+
+    assert_eq!(
+        to_mdast(
+            "+-----+\n| a   |\n+=====+\n| - b |\n|   c |\n+-----+",
+            &grid.parse
+        )?,
+        Node::Root(Root {
+            children: vec![Node::Table(Box::new(Table {
+                align: vec![AlignKind::None],
+                children: vec![
+                    Node::TableRow(TableRow {
+                        children: vec![Node::TableCell(TableCell {
+                            children: vec![Node::Text(Text {
+                                value: "a".into(),
+                                position: Some(Position::new(2, 3, 10, 2, 4, 11))
+                            })],
+                            position: Some(Position::new(2, 2, 9, 2, 7, 14))
+                        })],
+                        position: Some(Position::new(2, 1, 8, 2, 8, 15))
+                    }),
+                    Node::TableRow(TableRow {
+                        children: vec![Node::TableCell(TableCell {
+                            children: vec![Node::List(List {
+                                ordered: false,
+                                start: None,
+                                spread: false,
+                                marker: Some('-'),
+                                children: vec![Node::ListItem(ListItem {
+                                    spread: false,
+                                    checked: None,
+                                    children: vec![Node::Paragraph(Paragraph {
+                                        children: vec![Node::Text(Text {
+                                            value: "b\nc".into(),
+                                            position: Some(Position::new(4, 5, 28, 5, 6, 37))
+                                        })],
+                                        position: Some(Position::new(4, 5, 28, 5, 6, 37))
+                                    })],
+                                    position: Some(Position::new(4, 3, 26, 5, 6, 37))
+                                })],
+                                position: Some(Position::new(4, 3, 26, 5, 6, 37))
+                            })],
+                            position: Some(Position::new(4, 2, 25, 5, 7, 38))
+                        })],
+                        position: Some(Position::new(4, 1, 24, 5, 8, 39))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 6, 8, 47)),
+                caption: None
+            }))],
+            position: Some(Position::new(1, 1, 0, 6, 8, 47))
+        }),
+        "should support grid tables as `Table`s in mdast, w/ block content in cells"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast(
+            "+-----+-----+\n| a   | b   |\n+=====+:===:+\n| - c | d   |\n| - e |     |\n+-----+-----+\n| f   | 中  |\n|     |     |\n| g   |     |\n+-----+-----+",
+            &grid.parse
+        )?),
+        "+-----+-----+\n| a   | b   |\n+=====+:===:+\n| - c | d   |\n| - e |     |\n+-----+-----+\n| f   | 中  |\n|     |     |\n| g   |     |\n+-----+-----+\n\n",
+        "should serialize tables w/ block content as grid tables"
+    );
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Heading, Node, Root, Text},
+    mdast::{Heading, HeadingKind, Node, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -227,14 +227,16 @@ fn heading_atx() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("## alpha #", &Default::default())?,
         Node::Root(Root {
-            children: vec![Node::Heading(Heading {
+            children: vec![Node::Heading(Box::new(Heading {
                 depth: 2,
                 children: vec![Node::Text(Text {
                     value: "alpha".into(),
                     position: Some(Position::new(1, 4, 3, 1, 9, 8))
                 }),],
-                position: Some(Position::new(1, 1, 0, 1, 11, 10))
-            })],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                heading_kind: Some(HeadingKind::Atx),
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 1, 11, 10))
         }),
         "should support heading (atx) as `Heading`s in mdast"
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Heading, Node, Root, Text},
+    mdast::{Heading, HeadingKind, Node, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -300,14 +300,16 @@ fn heading_setext() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("alpha\nbravo\n==", &Default::default())?,
         Node::Root(Root {
-            children: vec![Node::Heading(Heading {
+            children: vec![Node::Heading(Box::new(Heading {
                 depth: 1,
                 children: vec![Node::Text(Text {
                     value: "alpha\nbravo".into(),
                     position: Some(Position::new(1, 1, 0, 2, 6, 11))
                 }),],
-                position: Some(Position::new(1, 1, 0, 3, 3, 14))
-            })],
+                position: Some(Position::new(1, 1, 0, 3, 3, 14)),
+                heading_kind: Some(HeadingKind::Setext),
+                attributes: None,
+            }))],
             position: Some(Position::new(1, 1, 0, 3, 3, 14))
         }),
         "should support heading (atx) as `Heading`s in mdast"
@@ -0,0 +1,129 @@
+use markdown::{
+    mdast::{Highlight, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn highlight() -> Result<(), message::Message> {
+    let highlight = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                highlight: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a ==b== c"),
+        "<p>a ==b== c</p>",
+        "should not support highlight by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b== c", &highlight)?,
+        "<p>a <mark>b</mark> c</p>",
+        "should support highlight"
+    );
+
+    assert_eq!(
+        to_html_with_options("a =b= c", &highlight)?,
+        "<p>a =b= c</p>",
+        "should not support highlight w/ one marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ===b=== c", &highlight)?,
+        "<p>a ===b=== c</p>",
+        "should not support highlight w/ three markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b= c", &highlight)?,
+        "<p>a ==b= c</p>",
+        "should not support highlight w/ sequences of different sizes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a == b == c", &highlight)?,
+        "<p>a == b == c</p>",
+        "should not support highlight w/ whitespace inside"
+    );
+
+    assert_eq!(
+        to_html_with_options("a==b==c", &highlight)?,
+        "<p>a<mark>b</mark>c</p>",
+        "should support highlight inside words"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b\nc== d", &highlight)?,
+        "<p>a <mark>b\nc</mark> d</p>",
+        "should support highlight over line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("==*a*== *==b==*", &highlight)?,
+        "<p><mark><em>a</em></mark> <em><mark>b</mark></em></p>",
+        "should support highlight and emphasis in each other"
+    );
+
+    assert_eq!(
+        to_html_with_options("![==a==](b)", &highlight)?,
+        "<p><img src=\"b\" alt=\"a\" /></p>",
+        "should not output `<mark>` in image alt"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n==", &highlight)?,
+        "<h1>a</h1>",
+        "should not change setext headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\==a==", &highlight)?,
+        "<p>==a==</p>",
+        "should not support highlight w/ an escaped marker"
+    );
+
+    assert_eq!(
+        to_mdast("a ==b== c", &highlight.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Highlight(Highlight {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                        }),],
+                        position: Some(Position::new(1, 3, 2, 1, 8, 7))
+                    }),
+                    Node::Text(Text {
+                        value: " c".into(),
+                        position: Some(Position::new(1, 8, 7, 1, 10, 9))
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 10, 9))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 10, 9))
+        }),
+        "should support highlight as `Highlight`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("a ==b *c*== d", &highlight.parse)?),
+        "a ==b *c*== d\n",
+        "should serialize highlight"
+    );
+
+    Ok(())
+}
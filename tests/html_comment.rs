@@ -0,0 +1,155 @@
+use markdown::{
+    mdast::{Comment, Html, Node, Paragraph, Root, Text},
+    message, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    CompileOptions, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn html_comment() -> Result<(), message::Message> {
+    let comments = ParseOptions {
+        html_comment_nodes: true,
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(
+        to_mdast("<!-- a -->", &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::Html(Html {
+                value: "<!-- a -->".into(),
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should not support comment nodes by default"
+    );
+
+    assert_eq!(
+        to_mdast("<!-- a -->", &comments)?,
+        Node::Root(Root {
+            children: vec![Node::Comment(Comment {
+                value: " a ".into(),
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support comment nodes (flow)"
+    );
+
+    assert_eq!(
+        to_mdast("a <!--b--> c", &comments)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Comment(Comment {
+                        value: "b".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 11, 10))
+                    }),
+                    Node::Text(Text {
+                        value: " c".into(),
+                        position: Some(Position::new(1, 11, 10, 1, 13, 12))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+        }),
+        "should support comment nodes (text)"
+    );
+
+    assert_eq!(
+        to_mdast("> <!-- a\n> b -->", &comments)?
+            .children()
+            .unwrap()[0]
+            .children()
+            .unwrap()[0],
+        Node::Comment(Comment {
+            value: " a\nb ".into(),
+            position: Some(Position::new(1, 3, 2, 2, 8, 16))
+        }),
+        "should support comments spanning lines"
+    );
+
+    assert_eq!(
+        to_mdast("  <!-- a -->  ", &comments)?.children().unwrap()[0],
+        Node::Comment(Comment {
+            value: " a ".into(),
+            position: Some(Position::new(1, 1, 0, 1, 15, 14))
+        }),
+        "should support whitespace around comments"
+    );
+
+    for (value, expected) in [("<!---->", ""), ("<!-->", ""), ("<!--->", "")] {
+        assert_eq!(
+            to_mdast(value, &comments)?.children().unwrap()[0],
+            Node::Comment(Comment {
+                value: expected.into(),
+                position: Some(Position::new(1, 1, 0, 1, value.len() + 1, value.len()))
+            }),
+            "should support empty comments ({})",
+            value
+        );
+    }
+
+    assert_eq!(
+        to_mdast("<!-- a --> b", &comments)?.children().unwrap()[0],
+        Node::Html(Html {
+            value: "<!-- a --> b".into(),
+            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+        }),
+        "should not support other things after a comment"
+    );
+
+    assert_eq!(
+        to_mdast("<!-- a --><!-- b -->", &comments)?
+            .children()
+            .unwrap()[0],
+        Node::Html(Html {
+            value: "<!-- a --><!-- b -->".into(),
+            position: Some(Position::new(1, 1, 0, 1, 21, 20))
+        }),
+        "should not support several comments in one node"
+    );
+
+    assert_eq!(
+        to_mdast("<div>\n<!-- a -->\n</div>", &comments)?
+            .children()
+            .unwrap()[0],
+        Node::Html(Html {
+            value: "<div>\n<!-- a -->\n</div>".into(),
+            position: Some(Position::new(1, 1, 0, 3, 7, 23))
+        }),
+        "should not support comments in other HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<!-- a -->",
+            &Options {
+                parse: ParseOptions {
+                    html_comment_nodes: true,
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<!-- a -->",
+        "should not affect HTML"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("- <!-- a -->\n- b <!--c-->", &comments)?),
+        "- <!-- a -->\n- b <!--c-->\n\n",
+        "should serialize comment nodes"
+    );
+
+    Ok(())
+}
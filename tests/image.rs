@@ -247,22 +247,22 @@ fn image() -> Result<(), message::Message> {
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::Image(Image {
+                    Node::Image(Box::new(Image {
                         alt: "alpha".into(),
                         url: String::new(),
                         title: None,
                         position: Some(Position::new(1, 3, 2, 1, 13, 12))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: " b ".into(),
                         position: Some(Position::new(1, 13, 12, 1, 16, 15))
                     }),
-                    Node::Image(Image {
+                    Node::Image(Box::new(Image {
                         alt: "bravo".into(),
                         url: "charlie".into(),
                         title: Some("delta".into()),
                         position: Some(Position::new(1, 16, 15, 1, 41, 40))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: " c.".into(),
                         position: Some(Position::new(1, 41, 40, 1, 44, 43))
@@ -282,48 +282,48 @@ fn image() -> Result<(), message::Message> {
         )?,
         Node::Root(Root {
             children: vec![
-                Node::Definition(Definition {
+                Node::Definition(Box::new(Definition {
                     identifier: "x".into(),
                     label: Some("x".into()),
                     url: "y".into(),
                     title: None,
                     position: Some(Position::new(1, 1, 0, 1, 7, 6))
-                }),
+                })),
                 Node::Paragraph(Paragraph {
                     children: vec![
                         Node::Text(Text {
                             value: "a ".into(),
                             position: Some(Position::new(3, 1, 8, 3, 3, 10))
                         }),
-                        Node::ImageReference(ImageReference {
+                        Node::ImageReference(Box::new(ImageReference {
                             reference_kind: ReferenceKind::Shortcut,
                             identifier: "x".into(),
                             label: Some("x".into()),
                             alt: "x".into(),
                             position: Some(Position::new(3, 3, 10, 3, 7, 14))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " b ".into(),
                             position: Some(Position::new(3, 7, 14, 3, 10, 17))
                         }),
-                        Node::ImageReference(ImageReference {
+                        Node::ImageReference(Box::new(ImageReference {
                             reference_kind: ReferenceKind::Collapsed,
                             identifier: "x".into(),
                             label: Some("x".into()),
                             alt: "x".into(),
                             position: Some(Position::new(3, 10, 17, 3, 16, 23))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " c ".into(),
                             position: Some(Position::new(3, 16, 23, 3, 19, 26))
                         }),
-                        Node::ImageReference(ImageReference {
+                        Node::ImageReference(Box::new(ImageReference {
                             reference_kind: ReferenceKind::Full,
                             identifier: "x".into(),
                             label: Some("x".into()),
                             alt: "d".into(),
                             position: Some(Position::new(3, 19, 26, 3, 26, 33))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " e.".into(),
                             position: Some(Position::new(3, 26, 33, 3, 29, 36))
@@ -0,0 +1,174 @@
+use markdown::{
+    mdast::{FootnoteDefinition, FootnoteReference, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn inline_footnote() -> Result<(), message::Message> {
+    let inline_footnote = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                label_start_inline_footnote: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a^[b] c"),
+        "<p>a^[b] c</p>",
+        "should not support inline footnotes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b *c*] d", &inline_footnote)?,
+        "<p>a<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> d</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>b <em>c</em> <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b] c^[d]", &inline_footnote)?,
+        "<p>a<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c<sup><a href=\"#user-content-fn-2\" id=\"user-content-fnref-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>b <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-2\">
+<p>d <a href=\"#user-content-fnref-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should number inline footnotes in order"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b] c[^1]\n\n[^1]: d", &inline_footnote)?,
+        "<p>a<sup><a href=\"#user-content-fn-2\" id=\"user-content-fnref-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-2\">
+<p>b <a href=\"#user-content-fnref-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-1\">
+<p>d <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should skip identifiers of GFM footnote definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[a ^[b]] c", &inline_footnote)?,
+        "<p><sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>a <sup><a href=\"#user-content-fn-2\" id=\"user-content-fnref-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-2\">
+<p>b <a href=\"#user-content-fnref-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support inline footnotes in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[a [b](c)]", &inline_footnote)?,
+        "<p><sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>a <a href=\"c\">b</a> <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support links in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[] b^ [c] d^[e", &inline_footnote)?,
+        "<p>a^[] b^ [c] d^[e</p>",
+        "should not support empty, split, or unclosed inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\^[a] ^\\[b]", &inline_footnote)?,
+        "<p>^[a] ^[b]</p>",
+        "should not support escaped inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("^[a](b)", &inline_footnote)?,
+        "<p><sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup>(b)</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>a <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not support a resource after inline footnotes"
+    );
+
+    assert_eq!(
+        to_mdast("a^[b] c", &inline_footnote.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![
+                        Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                        }),
+                        Node::FootnoteReference(Box::new(FootnoteReference {
+                            identifier: "1".into(),
+                            label: Some("1".into()),
+                            position: Some(Position::new(1, 2, 1, 1, 6, 5))
+                        })),
+                        Node::Text(Text {
+                            value: " c".into(),
+                            position: Some(Position::new(1, 6, 5, 1, 8, 7))
+                        })
+                    ],
+                    position: Some(Position::new(1, 1, 0, 1, 8, 7))
+                }),
+                Node::FootnoteDefinition(Box::new(FootnoteDefinition {
+                    identifier: "1".into(),
+                    label: Some("1".into()),
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                        })],
+                        position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                    })],
+                    position: None
+                }))
+            ],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should support inline footnotes as `FootnoteReference`s and `FootnoteDefinition`s in mdast"
+    );
+
+    Ok(())
+}
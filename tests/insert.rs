@@ -0,0 +1,129 @@
+use markdown::{
+    mdast::{Insert, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn insert() -> Result<(), message::Message> {
+    let insert = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                insert: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a ++b++ c"),
+        "<p>a ++b++ c</p>",
+        "should not support insert by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ++b++ c", &insert)?,
+        "<p>a <ins>b</ins> c</p>",
+        "should support insert"
+    );
+
+    assert_eq!(
+        to_html_with_options("a +b+ c", &insert)?,
+        "<p>a +b+ c</p>",
+        "should not support insert w/ one marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("a +++b+++ c", &insert)?,
+        "<p>a +++b+++ c</p>",
+        "should not support insert w/ three markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("C++ and C++", &insert)?,
+        "<p>C++ and C++</p>",
+        "should not support insert w/o opening sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ++b\nc++ d", &insert)?,
+        "<p>a <ins>b\nc</ins> d</p>",
+        "should support insert over line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "++a ~~b~~++",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        insert: true,
+                        ..Constructs::gfm()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><ins>a <del>b</del></ins></p>",
+        "should support insert and strikethrough in each other"
+    );
+
+    assert_eq!(
+        to_html_with_options("**++a++**", &insert)?,
+        "<p><strong><ins>a</ins></strong></p>",
+        "should support insert in strong"
+    );
+
+    assert_eq!(
+        to_html_with_options("![++a++](b)", &insert)?,
+        "<p><img src=\"b\" alt=\"a\" /></p>",
+        "should not output `<ins>` in image alt"
+    );
+
+    assert_eq!(
+        to_html_with_options("+ ++a++", &insert)?,
+        "<ul>\n<li><ins>a</ins></li>\n</ul>",
+        "should not change list items"
+    );
+
+    assert_eq!(
+        to_mdast("a ++b++ c", &insert.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Insert(Insert {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                        }),],
+                        position: Some(Position::new(1, 3, 2, 1, 8, 7))
+                    }),
+                    Node::Text(Text {
+                        value: " c".into(),
+                        position: Some(Position::new(1, 8, 7, 1, 10, 9))
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 10, 9))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 10, 9))
+        }),
+        "should support insert as `Insert`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("a ++b *c*++ d", &insert.parse)?),
+        "a ++b *c*++ d\n",
+        "should serialize insert"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use markdown::{inspect, mdast::Node, message, to_mdast, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn inspect_tree() -> Result<(), message::Message> {
+    assert_eq!(
+        inspect(&to_mdast("a *b*", &ParseOptions::default())?),
+        "root[1] (1:1-1:6, 0-5)
+└─0 paragraph[2] (1:1-1:6, 0-5)
+    ├─0 text \"a \" (1:1-1:3, 0-2)
+    └─1 emphasis[1] (1:3-1:6, 2-5)
+        └─0 text \"b\" (1:4-1:5, 3-4)",
+        "should show a tree"
+    );
+
+    assert_eq!(
+        inspect(&to_mdast("- [x] a\n\n1. b", &ParseOptions::gfm())?),
+        "root[2] (1:1-3:5, 0-13)
+├─0 list[1] (1:1-2:1, 0-8)
+│   │ ordered: false
+│   │ spread: false
+│   └─0 listItem[1] (1:1-2:1, 0-8)
+│       │ checked: true
+│       │ spread: false
+│       └─0 paragraph[1] (1:7-1:8, 6-7)
+│           └─0 text \"a\" (1:7-1:8, 6-7)
+└─1 list[1] (3:1-3:5, 9-13)
+    │ ordered: true
+    │ start: 1
+    │ spread: false
+    └─0 listItem[1] (3:1-3:5, 9-13)
+        │ spread: false
+        └─0 paragraph[1] (3:4-3:5, 12-13)
+            └─0 text \"b\" (3:4-3:5, 12-13)",
+        "should show fields of parents"
+    );
+
+    assert_eq!(
+        inspect(&to_mdast(
+            "```js x\nc\n```\n\n[e]: <f> \"g\"",
+            &ParseOptions::default()
+        )?),
+        "root[2] (1:1-5:13, 0-27)
+├─0 code \"c\" (1:1-3:4, 0-13)
+│     lang: \"js\"
+│     meta: \"x\"
+└─1 definition (5:1-5:13, 15-27)
+      identifier: \"e\"
+      label: \"e\"
+      url: \"f\"
+      title: \"g\"",
+        "should show fields of other nodes"
+    );
+
+    assert_eq!(
+        inspect(&to_mdast("<a b {...c} />", &ParseOptions::mdx())?),
+        "root[1] (1:1-1:15, 0-14)
+└─0 mdxJsxFlowElement[0] (1:1-1:15, 0-14)
+      name: \"a\"
+      attributes: [b, {…}]",
+        "should show attributes"
+    );
+
+    assert_eq!(
+        inspect(&to_mdast(
+            "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
+            &ParseOptions::default()
+        )?),
+        "root[1] (1:1-1:57, 0-56)
+└─0 paragraph[1] (1:1-1:57, 0-56)
+    └─0 text \"Lorem ipsum dolor sit amet, cons…\" (1:1-1:57, 0-56)",
+        "should shorten long values"
+    );
+
+    assert_eq!(
+        inspect(&Node::Text(markdown::mdast::Text {
+            value: "a\nb".into(),
+            position: None
+        })),
+        "text \"a\\nb\"",
+        "should escape values and support nodes without positions"
+    );
+
+    Ok(())
+}
@@ -107,6 +107,12 @@ fn link_reference() -> Result<(), message::Message> {
         "should match references to definitions w/ collapsing"
     );
 
+    assert_eq!(
+        to_html("[foobar]: /url\n\n[Baz][foo bar]"),
+        "<p>[Baz][foo bar]</p>",
+        "should not match references to definitions w/o whitespace"
+    );
+
     assert_eq!(
         to_html("[bar]: /url \"title\"\n\n[foo] [bar]"),
         "<p>[foo] <a href=\"/url\" title=\"title\">bar</a></p>",
@@ -429,20 +435,20 @@ fn link_reference() -> Result<(), message::Message> {
         to_mdast("[x]: y\n\na [x] b [x][] c [d][x] e.", &Default::default())?,
         Node::Root(Root {
             children: vec![
-                Node::Definition(Definition {
+                Node::Definition(Box::new(Definition {
                     identifier: "x".into(),
                     label: Some("x".into()),
                     url: "y".into(),
                     title: None,
                     position: Some(Position::new(1, 1, 0, 1, 7, 6))
-                }),
+                })),
                 Node::Paragraph(Paragraph {
                     children: vec![
                         Node::Text(Text {
                             value: "a ".into(),
                             position: Some(Position::new(3, 1, 8, 3, 3, 10))
                         }),
-                        Node::LinkReference(LinkReference {
+                        Node::LinkReference(Box::new(LinkReference {
                             reference_kind: ReferenceKind::Shortcut,
                             identifier: "x".into(),
                             label: Some("x".into()),
@@ -451,12 +457,12 @@ fn link_reference() -> Result<(), message::Message> {
                                 position: Some(Position::new(3, 4, 11, 3, 5, 12))
                             }),],
                             position: Some(Position::new(3, 3, 10, 3, 6, 13))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " b ".into(),
                             position: Some(Position::new(3, 6, 13, 3, 9, 16))
                         }),
-                        Node::LinkReference(LinkReference {
+                        Node::LinkReference(Box::new(LinkReference {
                             reference_kind: ReferenceKind::Collapsed,
                             identifier: "x".into(),
                             label: Some("x".into()),
@@ -465,12 +471,12 @@ fn link_reference() -> Result<(), message::Message> {
                                 position: Some(Position::new(3, 10, 17, 3, 11, 18))
                             }),],
                             position: Some(Position::new(3, 9, 16, 3, 14, 21))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " c ".into(),
                             position: Some(Position::new(3, 14, 21, 3, 17, 24))
                         }),
-                        Node::LinkReference(LinkReference {
+                        Node::LinkReference(Box::new(LinkReference {
                             reference_kind: ReferenceKind::Full,
                             identifier: "x".into(),
                             label: Some("x".into()),
@@ -479,7 +485,7 @@ fn link_reference() -> Result<(), message::Message> {
                                 position: Some(Position::new(3, 18, 25, 3, 19, 26))
                             }),],
                             position: Some(Position::new(3, 17, 24, 3, 23, 30))
-                        }),
+                        })),
                         Node::Text(Text {
                             value: " e.".into(),
                             position: Some(Position::new(3, 23, 30, 3, 26, 33))
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Image, Link, Node, Paragraph, Root, Text},
+    mdast::{Image, Link, LinkKind, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Options,
@@ -477,28 +477,30 @@ fn link_resource() -> Result<(), message::Message> {
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: String::new(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "alpha".into(),
                             position: Some(Position::new(1, 4, 3, 1, 9, 8))
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 12, 11))
-                    }),
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11)),
+                        link_kind: Some(LinkKind::Resource),
+                    })),
                     Node::Text(Text {
                         value: " b ".into(),
                         position: Some(Position::new(1, 12, 11, 1, 15, 14))
                     }),
-                    Node::Link(Link {
+                    Node::Link(Box::new(Link {
                         url: "charlie".into(),
                         title: Some("delta".into()),
                         children: vec![Node::Text(Text {
                             value: "bravo".into(),
                             position: Some(Position::new(1, 16, 15, 1, 21, 20))
                         }),],
-                        position: Some(Position::new(1, 15, 14, 1, 39, 38))
-                    }),
+                        position: Some(Position::new(1, 15, 14, 1, 39, 38)),
+                        link_kind: Some(LinkKind::Resource),
+                    })),
                     Node::Text(Text {
                         value: " c.".into(),
                         position: Some(Position::new(1, 39, 38, 1, 42, 41))
@@ -515,17 +517,18 @@ fn link_resource() -> Result<(), message::Message> {
         to_mdast("[![name](image)](url)", &Default::default())?,
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
-                children: vec![Node::Link(Link {
-                    children: vec![Node::Image(Image {
+                children: vec![Node::Link(Box::new(Link {
+                    children: vec![Node::Image(Box::new(Image {
                         alt: "name".into(),
                         url: "image".into(),
                         title: None,
                         position: Some(Position::new(1, 2, 1, 1, 16, 15)),
-                    }),],
+                    })),],
                     url: "url".into(),
                     title: None,
                     position: Some(Position::new(1, 1, 0, 1, 22, 21)),
-                }),],
+                    link_kind: Some(LinkKind::Resource),
+                })),],
                 position: Some(Position::new(1, 1, 0, 1, 22, 21)),
             }),],
             position: Some(Position::new(1, 1, 0, 1, 22, 21))
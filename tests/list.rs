@@ -1,6 +1,6 @@
 use markdown::{
     mdast::{List, ListItem, Node, Paragraph, Root, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
@@ -608,7 +608,8 @@ fn list() -> Result<(), message::Message> {
                     })],
                     position: Some(Position::new(1, 1, 0, 1, 4, 3))
                 })],
-                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                marker: Some('*'),
             })],
             position: Some(Position::new(1, 1, 0, 1, 4, 3))
         }),
@@ -648,13 +649,23 @@ fn list() -> Result<(), message::Message> {
                         position: Some(Position::new(2, 1, 5, 2, 5, 9))
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 2, 5, 9))
+                position: Some(Position::new(1, 1, 0, 2, 5, 9)),
+                marker: Some('.'),
             })],
             position: Some(Position::new(1, 1, 0, 2, 5, 9))
         }),
         "should support `start` fields on `List` w/ `ordered: true` in mdast"
     );
 
+    assert_eq!(
+        to_markdown(to_mdast(
+            "- a\n- b\n\n+ c\n\n1) d\n2) e",
+            &Default::default()
+        )?),
+        "- a\n- b\n\n+ c\n\n1) d\n2) e\n\n",
+        "should support `marker` fields on `List` to keep the markers of lists"
+    );
+
     assert_eq!(
         to_mdast("* a\n\n  b\n* c", &Default::default())?,
         Node::Root(Root {
@@ -697,7 +708,8 @@ fn list() -> Result<(), message::Message> {
                         position: Some(Position::new(4, 1, 9, 4, 4, 12))
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 4, 4, 12))
+                position: Some(Position::new(1, 1, 0, 4, 4, 12)),
+                marker: Some('*'),
             })],
             position: Some(Position::new(1, 1, 0, 4, 4, 12))
         }),
@@ -258,15 +258,91 @@ fn math_flow() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("$$extra\nabc\ndef\n$$", &math.parse)?,
         Node::Root(Root {
-            children: vec![Node::Math(Math {
+            children: vec![Node::Math(Box::new(Math {
                 meta: Some("extra".into()),
                 value: "abc\ndef".into(),
                 position: Some(Position::new(1, 1, 0, 4, 3, 18))
-            })],
+            }))],
             position: Some(Position::new(1, 1, 0, 4, 3, 18))
         }),
         "should support math (flow) as `Math`s in mdast"
     );
 
+    let latex = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_text: true,
+                math_flow: true,
+                ..Default::default()
+            },
+            math_latex_delimiters: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("\\[\na\n\\]", &math)?,
+        "<p>[\na\n]</p>",
+        "should not support LaTeX delimiters by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\\[ \nb\n  \\] \nc", &latex)?,
+        "<p>a</p>\n<pre><code class=\"language-math math-display\">b\n</code></pre>\n<p>c</p>",
+        "should support LaTeX delimiters w/ `math_latex_delimiters`, interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\[a\\]\n\\[ b\nc\n\\]", &latex)?,
+        "<p>[a]\n[ b\nc\n]</p>",
+        "should not support content on the lines of LaTeX delimiters"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\[\n$$\n\\]]\n\\]", &latex)?,
+        "<pre><code class=\"language-math math-display\">$$\n\\]]\n</code></pre>",
+        "should only close math (flow) w/ LaTeX delimiters w/ `\\]`"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\[\na", &latex)?,
+        "<pre><code class=\"language-math math-display\">a\n</code></pre>\n",
+        "should support unclosed math (flow) w/ LaTeX delimiters"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "\\| a |\n| - |",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        gfm_table: true,
+                        math_flow: true,
+                        ..Default::default()
+                    },
+                    math_latex_delimiters: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<table>\n<thead>\n<tr>\n<th>| a</th>\n</tr>\n</thead>\n</table>",
+        "should support escapes at the start of GFM tables"
+    );
+
+    assert_eq!(
+        to_mdast("\\[\na\n\\]", &latex.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Math(Box::new(Math {
+                meta: None,
+                value: "a".into(),
+                position: Some(Position::new(1, 1, 0, 3, 3, 7))
+            }))],
+            position: Some(Position::new(1, 1, 0, 3, 3, 7))
+        }),
+        "should support LaTeX delimiters as `Math`s in mdast"
+    );
+
     Ok(())
 }
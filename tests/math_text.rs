@@ -232,5 +232,63 @@ fn math_text() -> Result<(), message::Message> {
         "should support math (text) as `InlineMath`s in mdast"
     );
 
+    let latex = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_text: true,
+                math_flow: true,
+                ..Default::default()
+            },
+            math_latex_delimiters: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("\\(a\\)", &math)?,
+        "<p>(a)</p>",
+        "should not support LaTeX delimiters by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \\( b \\) c $d$", &latex)?,
+        "<p>a <code class=\"language-math math-inline\">b</code> c <code class=\"language-math math-inline\">d</code></p>",
+        "should support LaTeX delimiters w/ `math_latex_delimiters`"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\(a\\b \\(c\\)", &latex)?,
+        "<p><code class=\"language-math math-inline\">a\\b \\(c</code></p>",
+        "should support backslashes in math (text) w/ LaTeX delimiters"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\(a\nb\\)", &latex)?,
+        "<p><code class=\"language-math math-inline\">a b</code></p>",
+        "should support line endings in math (text) w/ LaTeX delimiters"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\(a\n\n\\\\(b\\)", &latex)?,
+        "<p>(a</p>\n<p>\\(b)</p>",
+        "should not support unclosed or escaped LaTeX delimiters"
+    );
+
+    assert_eq!(
+        to_mdast("\\(a\\)", &latex.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineMath(InlineMath {
+                    value: "a".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 6, 5))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 6, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 5))
+        }),
+        "should support LaTeX delimiters as `InlineMath`s in mdast"
+    );
+
     Ok(())
 }
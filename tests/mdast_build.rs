@@ -0,0 +1,89 @@
+use markdown::{
+    mdast::{Code, Heading, Image, Link, Node, Paragraph, Text},
+    to_markdown,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mdast_build() {
+    assert_eq!(
+        Node::text("a"),
+        Node::Text(Text {
+            value: "a".into(),
+            position: None
+        }),
+        "should create text"
+    );
+
+    assert_eq!(
+        Node::heading(2, vec![Node::text("a")]),
+        Node::Heading(Box::new(Heading {
+            children: vec![Node::text("a")],
+            position: None,
+            depth: 2,
+            heading_kind: None,
+            attributes: None
+        })),
+        "should create headings"
+    );
+
+    assert_eq!(
+        Node::from(Paragraph::new(vec![Node::text("a")])),
+        Node::paragraph(vec![Node::text("a")]),
+        "should convert structs into nodes"
+    );
+
+    let link = Link::builder("b").title("c").child(Node::text("a")).build();
+    assert_eq!(
+        (
+            link.url.as_str(),
+            link.title.as_deref(),
+            link.children.len()
+        ),
+        ("b", Some("c"), 1),
+        "should build links"
+    );
+
+    assert_eq!(
+        Node::from(Image::builder("b").alt("a").title("c")),
+        Node::Image(Box::new(Image {
+            position: None,
+            alt: "a".into(),
+            url: "b".into(),
+            title: Some("c".into())
+        })),
+        "should build images"
+    );
+
+    let tree = Node::root(vec![
+        Node::heading(
+            1,
+            vec![Node::text("a "), Node::emphasis(vec![Node::text("b")])],
+        ),
+        Node::paragraph(vec![
+            Node::strong(vec![Node::text("c")]),
+            Node::text(" "),
+            Node::inline_code("d"),
+            Node::text(" "),
+            Node::link("e", vec![Node::text("f")]),
+        ]),
+        Node::thematic_break(),
+        Code::builder("m").lang("n").meta("o").into(),
+        Node::list(
+            true,
+            vec![
+                Node::list_item(vec![Node::paragraph(vec![Node::text("k")])]),
+                Node::list_item(vec![Node::paragraph(vec![Node::text("l")])]),
+            ],
+        ),
+        Node::block_quote(vec![Node::paragraph(vec![Node::delete(vec![Node::text(
+            "j",
+        )])])]),
+    ]);
+
+    assert_eq!(
+        to_markdown(tree),
+        "# a *b*\n\n**c** `d` [f](e)\n\n***\n\n```n o\nm\n```\n\n1. k\n2. l\n\n> ~~j~~\n",
+        "should build trees that serialize"
+    );
+}
@@ -0,0 +1,74 @@
+use markdown::{
+    mdast::{index_definitions, index_footnote_definitions, Definition},
+    message, to_mdast,
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mdast_index_definitions() -> Result<(), message::Message> {
+    let tree = to_mdast("[a]\n\n[a]: b", &ParseOptions::default())?;
+    assert_eq!(
+        index_definitions(&tree).get("a").copied(),
+        Some(&Definition {
+            url: "b".into(),
+            title: None,
+            identifier: "a".into(),
+            label: Some("a".into()),
+            position: Some(Position::new(3, 1, 5, 3, 7, 11))
+        }),
+        "should index definitions"
+    );
+
+    let tree = to_mdast("[a]: b\n[A]: c", &ParseOptions::default())?;
+    let definitions = index_definitions(&tree);
+    assert_eq!(definitions.len(), 1, "should index duplicates once");
+    assert_eq!(
+        definitions["a"].url, "b",
+        "should prefer the first of duplicate definitions"
+    );
+
+    let tree = to_mdast("> * [A]: c", &ParseOptions::default())?;
+    assert_eq!(
+        index_definitions(&tree).keys().collect::<Vec<_>>(),
+        vec!["a"],
+        "should index nested definitions by normalized identifier"
+    );
+
+    let tree = to_mdast("[^a]: b", &ParseOptions::gfm())?;
+    assert_eq!(
+        index_definitions(&tree).len(),
+        0,
+        "should not index footnote definitions as definitions"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_index_footnote_definitions() -> Result<(), message::Message> {
+    let tree = to_mdast("[^a]: b\n\n[^A]: c\n\n> [^d]: e", &ParseOptions::gfm())?;
+    let definitions = index_footnote_definitions(&tree);
+
+    assert_eq!(
+        definitions.keys().collect::<Vec<_>>(),
+        vec!["a", "d"],
+        "should index footnote definitions"
+    );
+
+    assert_eq!(
+        definitions["a"].children[0].to_string(),
+        "b",
+        "should prefer the first of duplicate footnote definitions"
+    );
+
+    let tree = to_mdast("[a]: b", &ParseOptions::gfm())?;
+    assert_eq!(
+        index_footnote_definitions(&tree).len(),
+        0,
+        "should not index definitions as footnote definitions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use markdown::{mdast::NodeKind, message, to_mdast, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mdast_node_at_offset() -> Result<(), message::Message> {
+    let tree = to_mdast("# a\n\nb *c*", &ParseOptions::default())?;
+    let kinds = |offset: usize| {
+        tree.node_at_offset(offset)
+            .into_iter()
+            .map(|node| node.kind())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        kinds(2),
+        vec![NodeKind::Root, NodeKind::Heading, NodeKind::Text],
+        "should get the deepest node and its ancestors"
+    );
+
+    assert_eq!(
+        kinds(0),
+        vec![NodeKind::Root, NodeKind::Heading],
+        "should get a parent if no child contains the offset"
+    );
+
+    assert_eq!(
+        kinds(4),
+        vec![NodeKind::Root],
+        "should get the root between blocks"
+    );
+
+    assert_eq!(
+        kinds(7),
+        vec![NodeKind::Root, NodeKind::Paragraph, NodeKind::Emphasis],
+        "should treat the start of a node as inside it"
+    );
+
+    assert_eq!(
+        kinds(6),
+        vec![NodeKind::Root, NodeKind::Paragraph, NodeKind::Text],
+        "should treat the end of a node as outside it"
+    );
+
+    assert_eq!(
+        kinds(10),
+        vec![],
+        "should get nothing for offsets outside the tree"
+    );
+
+    let mut tree = to_mdast("a *b*", &ParseOptions::default())?;
+    tree.children_mut().unwrap()[0].position_set(None);
+    assert_eq!(
+        tree.node_at_offset(3).len(),
+        1,
+        "should skip nodes without positions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use markdown::{mdast::NodeKind, message, to_mdast, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mdast_source() -> Result<(), message::Message> {
+    let document = "# a\n\n> b *c &amp; \\d*\n> e";
+    let tree = to_mdast(document, &ParseOptions::default())?;
+    let source = |kind: NodeKind| {
+        tree.find(|node| node.kind() == kind)
+            .and_then(|node| node.source(document))
+    };
+
+    assert_eq!(
+        tree.source(document),
+        Some(document),
+        "should get the whole document for the root"
+    );
+
+    assert_eq!(
+        source(NodeKind::Heading),
+        Some("# a"),
+        "should get the source of a node, including markers"
+    );
+
+    assert_eq!(
+        source(NodeKind::Emphasis),
+        Some("*c &amp; \\d*"),
+        "should get character references and escapes as written"
+    );
+
+    assert_eq!(
+        source(NodeKind::Paragraph),
+        Some("b *c &amp; \\d*\n> e"),
+        "should include the prefixes of containers in nodes spanning lines"
+    );
+
+    let document = "\u{feff}a";
+    let tree = to_mdast(document, &ParseOptions::default())?;
+    assert_eq!(
+        tree.children().unwrap()[0].source(document),
+        Some("a"),
+        "should support a byte order mark"
+    );
+
+    let mut tree = to_mdast("a", &ParseOptions::default())?;
+    tree.position_set(None);
+    assert_eq!(
+        tree.source("a"),
+        None,
+        "should get nothing for nodes without positions"
+    );
+
+    let tree = to_mdast("abc", &ParseOptions::default())?;
+    assert_eq!(
+        tree.source("ab"),
+        None,
+        "should get nothing for offsets out of bounds"
+    );
+
+    assert_eq!(
+        tree.source("\u{e9}\u{e9}"),
+        None,
+        "should get nothing for offsets not on char boundaries"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use markdown::{
+    mdast::{
+        visit::{walk, walk_mut, Visitor, VisitorMut},
+        BlockQuote, Emphasis, Heading, Node, Paragraph, Root, Text,
+    },
+    message, to_mdast, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[derive(Default)]
+struct Events(Vec<String>);
+
+impl Visitor for Events {
+    fn enter(&mut self, node: &Node) {
+        self.0.push(format!("enter {:?}", node.kind()));
+    }
+
+    fn exit(&mut self, node: &Node) {
+        self.0.push(format!("exit {:?}", node.kind()));
+    }
+}
+
+#[derive(Default)]
+struct Texts(Vec<String>, usize);
+
+impl Visitor for Texts {
+    fn enter_text(&mut self, node: &Text) {
+        self.0.push(format!("{}:{}", self.1, node.value));
+    }
+
+    fn enter_emphasis(&mut self, _node: &Emphasis) {
+        self.1 += 1;
+    }
+
+    fn exit_emphasis(&mut self, _node: &Emphasis) {
+        self.1 -= 1;
+    }
+}
+
+struct Shout;
+
+impl VisitorMut for Shout {
+    fn enter_text(&mut self, node: &mut Text) {
+        node.value = node.value.to_uppercase().into();
+    }
+
+    fn enter_heading(&mut self, node: &mut Heading) {
+        node.children.clear();
+    }
+
+    fn exit_paragraph(&mut self, node: &mut Paragraph) {
+        node.children.push(Node::Text(Text {
+            value: "!".into(),
+            position: None,
+        }));
+    }
+}
+
+#[test]
+fn mdast_visit() -> Result<(), message::Message> {
+    let tree = to_mdast("# a\n\nb *c*", &ParseOptions::default())?;
+
+    let mut events = Events::default();
+    walk(&tree, &mut events);
+    assert_eq!(
+        events.0,
+        vec![
+            "enter Root",
+            "enter Heading",
+            "enter Text",
+            "exit Text",
+            "exit Heading",
+            "enter Paragraph",
+            "enter Text",
+            "exit Text",
+            "enter Emphasis",
+            "enter Text",
+            "exit Text",
+            "exit Emphasis",
+            "exit Paragraph",
+            "exit Root",
+        ],
+        "should enter and exit nodes in document order"
+    );
+
+    let mut texts = Texts::default();
+    walk(&tree, &mut texts);
+    assert_eq!(
+        texts.0,
+        vec!["0:a", "0:b ", "1:c"],
+        "should call the methods for kinds of nodes"
+    );
+
+    let mut changed = tree.clone();
+    walk_mut(&mut changed, &mut Shout);
+    assert_eq!(
+        changed.to_string(),
+        "B C!",
+        "should change nodes, and not walk removed children"
+    );
+    assert_eq!(
+        changed.children().map(Vec::len),
+        tree.children().map(Vec::len),
+        "should put nodes back"
+    );
+
+    let mut tree = Node::Text(Text {
+        value: "d".into(),
+        position: None,
+    });
+    for _ in 0..100_000 {
+        tree = Node::BlockQuote(BlockQuote {
+            children: vec![tree],
+            position: None,
+            alert: None,
+        });
+    }
+    let mut tree = Node::Root(Root {
+        children: vec![tree],
+        position: None,
+    });
+    let mut texts = Texts::default();
+    walk(&tree, &mut texts);
+    walk_mut(&mut tree, &mut Shout);
+    assert_eq!(texts.0, vec!["0:d"], "should walk deeply nested trees");
+    assert_eq!(tree.to_string(), "D", "should change deeply nested trees");
+
+    // Drop without recursion, which would overflow the stack.
+    let mut stack = vec![tree];
+    while let Some(mut node) = stack.pop() {
+        if let Some(children) = node.children_mut() {
+            stack.append(children);
+        }
+    }
+
+    Ok(())
+}
@@ -256,11 +256,11 @@ fn mdx_esm() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("import a from 'b'\nexport {a}", &swc.parse)?,
         Node::Root(Root {
-            children: vec![Node::MdxjsEsm(MdxjsEsm {
+            children: vec![Node::MdxjsEsm(Box::new(MdxjsEsm {
                 value: "import a from 'b'\nexport {a}".into(),
                 position: Some(Position::new(1, 1, 0, 2, 11, 28)),
                 stops: vec![(0, 0), (17, 17), (18, 18)]
-            })],
+            }))],
             position: Some(Position::new(1, 1, 0, 2, 11, 28))
         }),
         "should support mdx esm as `MdxjsEsm`s in mdast"
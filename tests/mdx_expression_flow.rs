@@ -134,11 +134,11 @@ fn mdx_expression_flow_agnostic() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("{alpha +\nbravo}", &mdx.parse)?,
         Node::Root(Root {
-            children: vec![Node::MdxFlowExpression(MdxFlowExpression {
+            children: vec![Node::MdxFlowExpression(Box::new(MdxFlowExpression {
                 value: "alpha +\nbravo".into(),
                 position: Some(Position::new(1, 1, 0, 2, 7, 15)),
                 stops: vec![(0, 1), (7, 8), (8, 9)]
-            })],
+            }))],
             position: Some(Position::new(1, 1, 0, 2, 7, 15))
         }),
         "should support mdx expressions (flow) as `MdxFlowExpression`s in mdast"
@@ -221,11 +221,11 @@ fn mdx_expression_text_agnostic() -> Result<(), message::Message> {
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::MdxTextExpression(MdxTextExpression {
+                    Node::MdxTextExpression(Box::new(MdxTextExpression {
                         value: "alpha".into(),
                         position: Some(Position::new(1, 3, 2, 1, 10, 9)),
                         stops: vec![(0, 3)]
-                    }),
+                    })),
                     Node::Text(Text {
                         value: " b.".into(),
                         position: Some(Position::new(1, 10, 9, 1, 13, 12))
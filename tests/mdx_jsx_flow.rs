@@ -198,7 +198,7 @@ fn mdx_jsx_flow_essence() -> Result<(), message::Message> {
     assert_eq!(
         to_mdast("<>\n  * a\n</>", &mdx.parse)?,
         Node::Root(Root {
-            children: vec![Node::MdxJsxFlowElement(MdxJsxFlowElement {
+            children: vec![Node::MdxJsxFlowElement(Box::new(MdxJsxFlowElement {
                 name: None,
                 attributes: vec![],
                 children: vec![Node::List(List {
@@ -217,10 +217,11 @@ fn mdx_jsx_flow_essence() -> Result<(), message::Message> {
                         })],
                         position: Some(Position::new(2, 1, 3, 2, 6, 8))
                     })],
-                    position: Some(Position::new(2, 1, 3, 2, 6, 8))
+                    position: Some(Position::new(2, 1, 3, 2, 6, 8)),
+                    marker: Some('*'),
                 })],
                 position: Some(Position::new(1, 1, 0, 3, 4, 12))
-            })],
+            }))],
             position: Some(Position::new(1, 1, 0, 3, 4, 12))
         }),
         "should support mdx jsx (flow) as `MdxJsxFlowElement`s in mdast"
@@ -57,12 +57,12 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("b".into()),
                         attributes: vec![],
                         children: vec![],
                         position: Some(Position::new(1, 3, 2, 1, 8, 7))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: " c.".into(),
                         position: Some(Position::new(1, 8, 7, 1, 11, 10))
@@ -84,7 +84,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         value: "a ".into(),
                         position: Some(Position::new(1, 1, 0, 1, 3, 2))
                     }),
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("b".into()),
                         attributes: vec![],
                         children: vec![
@@ -95,11 +95,12 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                                         position: Some(Position::new(1, 7, 6, 1, 8, 7))
                                     }),
                                 ],
-                                position: Some(Position::new(1, 6, 5, 1, 9, 8))
+                                position: Some(Position::new(1, 6, 5, 1, 9, 8)),
+                                marker: Some('*'),
                             }),
                         ],
                         position: Some(Position::new(1, 3, 2, 1, 13, 12))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: " d.".into(),
                         position: Some(Position::new(1, 13, 12, 1, 16, 15))
@@ -117,12 +118,12 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a:b".into()),
                         attributes: vec![],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 8, 7))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 8, 7, 1, 9, 8))
@@ -140,12 +141,12 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a.b.c".into()),
                         attributes: vec![],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 10, 9))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 10, 9, 1, 11, 10))
@@ -163,7 +164,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![AttributeContent::Expression {
                             value: "...b".into(),
@@ -171,7 +172,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         }],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 13, 12))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 13, 12, 1, 14, 13))
@@ -189,7 +190,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -203,7 +204,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 12, 11))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 12, 11, 1, 13, 12))
@@ -221,7 +222,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -242,7 +243,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 24, 23))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 24, 23, 1, 25, 24))
@@ -260,7 +261,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -270,7 +271,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 120, 119))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 120, 119, 1, 121, 120))
@@ -291,7 +292,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -305,7 +306,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 63, 62))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 63, 62, 1, 64, 63))
@@ -323,7 +324,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -333,7 +334,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 78, 77))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 78, 77, 1, 79, 78))
@@ -351,7 +352,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -369,7 +370,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 1, 22, 21))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(1, 22, 21, 1, 23, 22))
@@ -387,7 +388,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![
-                    Node::MdxJsxTextElement(MdxJsxTextElement {
+                    Node::MdxJsxTextElement(Box::new(MdxJsxTextElement {
                         name: Some("a".into()),
                         attributes: vec![
                             AttributeContent::Property(MdxJsxAttribute {
@@ -405,7 +406,7 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         ],
                         children: vec![],
                         position: Some(Position::new(1, 1, 0, 5, 3, 13))
-                    }),
+                    })),
                     Node::Text(Text {
                         value: ".".into(),
                         position: Some(Position::new(5, 3, 13, 5, 4, 14))
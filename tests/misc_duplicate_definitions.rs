@@ -0,0 +1,130 @@
+use markdown::{
+    message, to_html_with_messages, to_html_with_options, to_mdast_with_messages,
+    DuplicateDefinitionKind, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn duplicate_definitions() -> Result<(), message::Message> {
+    let last = Options {
+        parse: ParseOptions {
+            duplicate_definitions: DuplicateDefinitionKind::Last,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+    let error = Options {
+        parse: ParseOptions {
+            duplicate_definitions: DuplicateDefinitionKind::Error,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("[a]\n\n[a]: b\n[A]: c", &Options::default())?,
+        "<p><a href=\"b\">a</a></p>\n",
+        "should use the first definition by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]\n\n[a]: b\n[A]: c", &last)?,
+        "<p><a href=\"c\">a</a></p>\n",
+        "should support using the last definition"
+    );
+
+    assert_eq!(
+        to_html_with_options("[^a]\n\n[^a]: b\n\n[^a]: c", &Options::gfm())?,
+        "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-a\">
+<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should use the first footnote definition by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[^a]\n\n[^a]: b\n\n[^a]: c", &last)?,
+        "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-a\">
+<p>c <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support using the last footnote definition"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]: b\n[b]: c", &error)?,
+        "",
+        "should not crash on unique definitions when erroring on duplicates"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]: b\n\n> [a]: c", &error)
+            .err()
+            .unwrap()
+            .to_string(),
+        "3:3-3:9: Unexpected duplicate definition `a`, expected unique identifiers (first defined at 1:1) (markdown-rs:duplicate-definition)",
+        "should support erroring on duplicate definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options("[^a]: b\n[^a]: c", &error)
+            .err()
+            .unwrap()
+            .to_string(),
+        "2:1-2:8: Unexpected duplicate footnote definition `a`, expected unique identifiers (first defined at 1:1) (markdown-rs:duplicate-footnote-definition)",
+        "should support erroring on duplicate footnote definitions"
+    );
+
+    assert_eq!(
+        to_html_with_messages("[a]: b\n[a]: c\n[b]: d\n[a]: e", &Options::default())?
+            .1
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "2:1-2:7: Unexpected duplicate definition `a`, expected unique identifiers (first defined at 1:1), the first definition is used (markdown-rs:duplicate-definition)",
+            "4:1-4:7: Unexpected duplicate definition `a`, expected unique identifiers (first defined at 1:1), the first definition is used (markdown-rs:duplicate-definition)"
+        ],
+        "should report each duplicate"
+    );
+
+    assert_eq!(
+        to_html_with_messages("[a]: b\n[a]: c", &last)?
+            .1
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "2:1-2:7: Unexpected duplicate definition `a`, expected unique identifiers (first defined at 1:1), the last definition is used (markdown-rs:duplicate-definition)"
+        ],
+        "should report duplicates when the last definition is used"
+    );
+
+    assert_eq!(
+        to_mdast_with_messages("[a]: b\n[b]: c\n\n[^a]: d", &ParseOptions::gfm())?
+            .1
+            .len(),
+        0,
+        "should not report definitions and footnote definitions with the same identifier"
+    );
+
+    assert_eq!(
+        to_mdast_with_messages("[^a]: b\n[^a]: c", &ParseOptions::gfm())?
+            .1
+            .len(),
+        1,
+        "should report duplicates when turning markdown into mdast"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use markdown::{
+    message, to_html, to_html_with_options, CharacterReferenceKind, CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn encode() -> Result<(), message::Message> {
+    let numeric = &Options {
+        compile: CompileOptions {
+            character_reference_kind: CharacterReferenceKind::Numeric,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let non_ascii = &Options {
+        compile: CompileOptions {
+            encode_non_ascii: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let apostrophe = &Options {
+        compile: CompileOptions {
+            encode_apostrophe: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let xml = &Options {
+        compile: CompileOptions {
+            character_reference_kind: CharacterReferenceKind::Numeric,
+            encode_apostrophe: true,
+            encode_non_ascii: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a & \"b\" <c> 'd' é\0"),
+        "<p>a &amp; &quot;b&quot; &lt;c&gt; 'd' é\u{fffd}</p>",
+        "should use named references for dangerous characters by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a & \"b\" <c> 'd' é\0", numeric)?,
+        "<p>a &#x26; &#x22;b&#x22; &#x3C;c&#x3E; 'd' é\u{fffd}</p>",
+        "should support numeric references w/ `character_reference_kind: Numeric`"
+    );
+
+    assert_eq!(
+        to_html_with_options("'a'", apostrophe)?,
+        "<p>&#x27;a&#x27;</p>",
+        "should support encoding apostrophes w/ `encode_apostrophe`"
+    );
+
+    assert_eq!(
+        to_html_with_options("café © ☕ 🦀\0", non_ascii)?,
+        "<p>caf&eacute; &copy; &#x2615; &#x1F980;&#xFFFD;</p>",
+        "should support encoding non-ASCII w/ `encode_non_ascii`, using HTML 4 names"
+    );
+
+    assert_eq!(
+        to_html_with_options("café & 'b'", xml)?,
+        "<p>caf&#xE9; &#x26; &#x27;b&#x27;</p>",
+        "should support combining options"
+    );
+
+    assert_eq!(
+        to_html_with_options("[é](/é \"'é'\")\n\n![é](/a.png)", xml)?,
+        "<p><a href=\"/%C3%A9\" title=\"&#x27;&#xE9;&#x27;\">&#xE9;</a></p>\n<p><img src=\"/a.png\" alt=\"&#xE9;\" /></p>",
+        "should encode titles and alts, but not URLs (which are percent-encoded)"
+    );
+
+    assert_eq!(
+        to_html_with_options("`a é`\n\n    b 'c'", xml)?,
+        "<p><code>a &#xE9;</code></p>\n<pre><code>b &#x27;c&#x27;\n</code></pre>",
+        "should encode code"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<b title='é'>é</b>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    encode_non_ascii: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><b title='é'>&eacute;</b></p>",
+        "should not encode raw HTML"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use markdown::{
+    message, to_html, to_html_with_options, to_mdast, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_nesting() -> Result<(), message::Message> {
+    let max = |max_nesting| Options {
+        parse: ParseOptions {
+            max_nesting: Some(max_nesting),
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html(&">".repeat(1000)),
+        format!(
+            "{}{}",
+            "<blockquote>\n".repeat(1000),
+            "</blockquote>\n".repeat(1000)
+        )
+        .trim_end(),
+        "should support deeply nested constructs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("> > a", &max(2))?,
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>",
+        "should support constructs nested as deep as `max_nesting`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> > > a", &max(2))
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:5: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error on block quotes nested too deep"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n  * b\n    * c", &max(2))
+            .err()
+            .unwrap()
+            .to_string(),
+        "3:5: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error on list items nested too deep"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n>\n> * b\n>\n>   > c", &max(2))
+            .err()
+            .unwrap()
+            .to_string(),
+        "5:5: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error on containers nested too deep on later lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n> b", &max(1))?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<blockquote>\n<p>b</p>\n</blockquote>",
+        "should not count siblings"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a **b ~~c~~***", &max(2))
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:8: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error on attention nested too deep"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [*a*](b)", &max(2))
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:4: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should count containers and text together"
+    );
+
+    assert_eq!(
+        to_mdast("> [*a*](b)", &max(2).parse)
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:4: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error when turning markdown into mdast"
+    );
+
+    let mdx = |max_nesting| ParseOptions {
+        max_nesting: Some(max_nesting),
+        ..ParseOptions::mdx()
+    };
+
+    assert_eq!(
+        to_mdast("<a><b/><c>d</c></a>", &mdx(2)).map(|_| ()),
+        Ok(()),
+        "should support MDX JSX elements nested as deep as `max_nesting`"
+    );
+
+    assert_eq!(
+        to_mdast("<a>\n  <b>\n    *c*\n  </b>\n</a>", &mdx(2))
+            .err()
+            .unwrap()
+            .to_string(),
+        "3:5: Unexpected construct nested deeper than `max_nesting` (2), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error on MDX JSX elements and other constructs nested too deep"
+    );
+
+    assert_eq!(
+        to_mdast("<a><b><c /></b></a>", &mdx(1))
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:4: Unexpected construct nested deeper than `max_nesting` (1), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should error on MDX JSX elements nested too deep"
+    );
+
+    assert_eq!(
+        to_html_with_options(&">".repeat(100_000), &max(100))
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:101: Unexpected construct nested deeper than `max_nesting` (100), expected fewer nested constructs (markdown-rs:max-nesting)",
+        "should stop early on containers nested too deep"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> a",
+            &Options {
+                parse: ParseOptions {
+                    max_nesting: Some(0),
+                    constructs: Constructs {
+                        block_quote: false,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>&gt; a</p>",
+        "should support `max_nesting: Some(0)` for flat documents"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use markdown::{message, to_html_with_options, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn parallel() -> Result<(), message::Message> {
+    let size = 1000;
+    let mut input = String::new();
+    let mut html = String::new();
+    let mut backreferences = String::new();
+    let mut items = String::new();
+
+    for index in 0..size {
+        let reference = if index == 0 {
+            String::new()
+        } else {
+            format!("-{}", index + 1)
+        };
+
+        input.push_str(&format!(
+            "# {}\n\na[^a] *b*[^{}].\n\n- c\n- d\n\n",
+            index, index
+        ));
+        html.push_str(&format!(
+            "<h1>{}</h1>\n<p>a<sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a{}\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> <em>b</em><sup><a href=\"#user-content-fn-{}\" id=\"user-content-fnref-{}\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">{}</a></sup>.</p>\n<ul>\n<li>c</li>\n<li>d</li>\n</ul>\n",
+            index, reference, index, index, index + 2
+        ));
+
+        if index != 0 {
+            backreferences.push(' ');
+        }
+
+        backreferences.push_str(&format!(
+            "<a href=\"#user-content-fnref-a{}\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩{}</a>",
+            reference,
+            if index == 0 {
+                String::new()
+            } else {
+                format!("<sup>{}</sup>", index + 1)
+            }
+        ));
+        items.push_str(&format!(
+            "<li id=\"user-content-fn-{}\">\n<p>e <a href=\"#user-content-fnref-{}\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n",
+            index, index
+        ));
+    }
+
+    for index in 0..size {
+        input.push_str(&format!("[^{}]: e\n", index));
+    }
+
+    input.push_str("[^a]: f\n");
+    html.push_str(&format!(
+        "<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>f {}</p>\n</li>\n{}</ol>\n</section>\n",
+        backreferences, items
+    ));
+
+    assert_eq!(
+        to_html_with_options(&input, &Options::gfm())?,
+        html,
+        "should number footnotes across a large document"
+    );
+
+    Ok(())
+}
@@ -1,8 +1,16 @@
-use markdown::to_html;
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
 use pretty_assertions::assert_eq;
 
 #[test]
-fn soft_break() {
+fn soft_break() -> Result<(), message::Message> {
+    let east_asian = Options {
+        compile: CompileOptions {
+            east_asian_line_breaks: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
     assert_eq!(
         to_html("foo\nbaz"),
         "<p>foo\nbaz</p>",
@@ -14,4 +22,72 @@ fn soft_break() {
         "<p>foo\nbaz</p>",
         "should trim spaces around line endings"
     );
+
+    assert_eq!(
+        to_html("日本語の\n文章"),
+        "<p>日本語の\n文章</p>",
+        "should keep line endings between East Asian characters by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("日本語の\n文章", &east_asian)?,
+        "<p>日本語の文章</p>",
+        "should drop line endings between East Asian characters w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("中文。\n「中文」", &east_asian)?,
+        "<p>中文。「中文」</p>",
+        "should drop line endings between fullwidth punctuation w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("中文 \n  中文", &east_asian)?,
+        "<p>中文\n中文</p>",
+        "should keep line endings after trailing spaces w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("中文\nabc\n中文", &east_asian)?,
+        "<p>中文\nabc\n中文</p>",
+        "should keep line endings next to other characters w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("😀\n😀", &east_asian)?,
+        "<p>😀\n😀</p>",
+        "should keep line endings between emoji w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("*中文*\n中文\n*中文*", &east_asian)?,
+        "<p><em>中文</em>\n中文\n<em>中文</em></p>",
+        "should keep line endings next to other constructs w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("中文  \n中文\\\n中文", &east_asian)?,
+        "<p>中文<br />\n中文<br />\n中文</p>",
+        "should keep line endings after hard breaks w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("# 中文\n中文", &east_asian)?,
+        "<h1>中文</h1>\n<p>中文</p>",
+        "should keep line endings between flow constructs w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> 中文\n> 中文\n中文", &east_asian)?,
+        "<blockquote>\n<p>中文中文中文</p>\n</blockquote>",
+        "should drop line endings in block quotes w/ `east_asian_line_breaks`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- 中文\n  中文", &east_asian)?,
+        "<ul>\n<li>中文中文</li>\n</ul>",
+        "should drop line endings in list items w/ `east_asian_line_breaks`"
+    );
+
+    Ok(())
 }
@@ -1,4 +1,9 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::{Point, Position},
+    CompileOptions, Options, ParseOptions,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -290,3 +295,106 @@ fn tabs_virtual_spaces() {
         "should support a part of a tab as a container, and the rest of a tab as flow"
     );
 }
+
+#[test]
+fn tabs_size() -> Result<(), message::Message> {
+    let two = Options {
+        parse: ParseOptions {
+            tab_size: 2,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+    let eight = Options {
+        parse: ParseOptions {
+            tab_size: 8,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("\tx", &two)?,
+        "<p>x</p>",
+        "should not support a HT to start code w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options("\t\tx", &two)?,
+        "<pre><code>x\n</code></pre>",
+        "should support 2*HT to start code w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options("  \tx", &two)?,
+        "<pre><code>x\n</code></pre>",
+        "should support 2*SP + HT to start code w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_html_with_options("\tx", &eight)?,
+        "<pre><code>    x\n</code></pre>",
+        "should support the rest of a HT as code w/ `tab_size: 8`"
+    );
+
+    assert_eq!(
+        to_html_with_options("-\tx", &eight)?,
+        "<ul>\n<li>\n<pre><code>  x\n</code></pre>\n</li>\n</ul>",
+        "should support the rest of a HT after a list marker as code w/ `tab_size: 8`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n\tb", &eight)?,
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>  b\n</code></pre>\n</li>\n</ul>",
+        "should support a part of a tab as a container, and the rest of a tab as code w/ `tab_size: 8`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ```\n\t\tx\n  ```", &two)?,
+        "<ul>\n<li>\n<pre><code>\tx\n</code></pre>\n</li>\n</ul>",
+        "should strip a HT as the indent of list items in fenced code w/ `tab_size: 2`"
+    );
+
+    assert_eq!(
+        to_mdast("a\tb", &eight.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a\tb".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 10, 3))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 10, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 10, 3))
+        }),
+        "should expand tabs in positional info w/ `tab_size: 8`"
+    );
+
+    let point = Point::new(1, 10, 3);
+
+    assert_eq!(
+        Point::from_utf16_with_tab_size("a\tb", &point.to_utf16("a\tb"), 8),
+        Some(point),
+        "should expand tabs when turning UTF-16 points into points w/ `tab_size: 8`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "\tx",
+            &Options {
+                parse: ParseOptions {
+                    tab_size: 0,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Unexpected `tab_size` of `0`, expected a number of columns between tab stops (markdown-rs:tab-size)",
+        "should crash w/ `tab_size: 0`"
+    );
+
+    Ok(())
+}
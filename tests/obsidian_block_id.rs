@@ -0,0 +1,187 @@
+use markdown::{
+    mdast::{BlockId, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn obsidian_block_id() -> Result<(), message::Message> {
+    let obsidian = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                obsidian_block_id: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let gfm = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                gfm_alert: true,
+                gfm_table: true,
+                ..obsidian.parse.constructs.clone()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a ^b"),
+        "<p>a ^b</p>",
+        "should not support block identifiers by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b", &obsidian)?,
+        "<p id=\"^b\">a</p>",
+        "should support a block identifier at the end of a paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb ^c-1 \t", &obsidian)?,
+        "<p id=\"^c-1\">a\nb</p>",
+        "should support a block identifier followed by whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("a   ^b", &obsidian)?,
+        "<p id=\"^b\">a</p>",
+        "should not form a hard break before a block identifier"
+    );
+
+    assert_eq!(
+        to_html_with_options("^b", &obsidian)?,
+        "<p id=\"^b\"></p>",
+        "should support a block identifier on its own w/o a block before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n^b", &obsidian)?,
+        "<blockquote id=\"^b\">\n<p>a</p>\n</blockquote>\n",
+        "should place a block identifier on its own line on the block quote before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- c\n\n^b \n\nd", &obsidian)?,
+        "<ul id=\"^b\">\n<li>a</li>\n<li>c</li>\n</ul>\n<p>d</p>",
+        "should place a block identifier on its own line on the list before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n\n^b", &gfm)?,
+        "<table id=\"^b\">\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>\n",
+        "should place a block identifier on its own line on the table before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [!NOTE]\n> a\n\n^b", &gfm)?,
+        "<div class=\"markdown-alert markdown-alert-note\" id=\"^b\">\n<p class=\"markdown-alert-title\">Note</p>\n<p>a</p>\n</div>\n",
+        "should place a block identifier on its own line on the alert before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("> > a\n>\n> ^b", &obsidian)?,
+        "<blockquote>\n<blockquote id=\"^b\">\n<p>a</p>\n</blockquote>\n</blockquote>",
+        "should place a block identifier on its own line on a sibling in a container"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n^b c", &obsidian)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<p>^b c</p>",
+        "should not place a block identifier not on its own on the block before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b c", &obsidian)?,
+        "<p>a ^b c</p>",
+        "should not support a block identifier not at the end"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b\nc", &obsidian)?,
+        "<p>a ^b\nc</p>",
+        "should not support a block identifier not at the end of the last line"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^b", &obsidian)?,
+        "<p>a^b</p>",
+        "should not support a block identifier not preceded by whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b!", &obsidian)?,
+        "<p>a ^b!</p>",
+        "should not support other characters in a block identifier"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^", &obsidian)?,
+        "<p>a ^</p>",
+        "should not support an empty block identifier"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a ^b", &obsidian)?,
+        "<h1 id=\"^b\">a</h1>",
+        "should support a block identifier in a heading (atx)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b\n---", &obsidian)?,
+        "<h2 id=\"^b\">a</h2>",
+        "should support a block identifier in a heading (setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a ^b\n- c", &obsidian)?,
+        "<ul>\n<li id=\"^b\">a</li>\n<li>c</li>\n</ul>",
+        "should place a block identifier on the item in a tight list"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a ^b\n\n- c", &obsidian)?,
+        "<ul>\n<li>\n<p id=\"^b\">a</p>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should place a block identifier on the paragraph in a loose list"
+    );
+
+    assert_eq!(
+        to_html_with_options("- > a ^b", &obsidian)?,
+        "<ul>\n<li>\n<blockquote>\n<p id=\"^b\">a</p>\n</blockquote>\n</li>\n</ul>",
+        "should not place a block identifier in a nested container on the item"
+    );
+
+    assert_eq!(
+        to_html_with_options("- - a ^b", &obsidian)?,
+        "<ul>\n<li>\n<ul>\n<li id=\"^b\">a</li>\n</ul>\n</li>\n</ul>",
+        "should place a block identifier on the innermost item"
+    );
+
+    assert_eq!(
+        to_mdast("a ^b", &obsidian.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::BlockId(BlockId {
+                        value: "b".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 5, 4))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 5, 4))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 5, 4))
+        }),
+        "should support block identifiers as `BlockId`s in mdast"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use markdown::{
+    mdast::{Emphasis, Node, NodePath, Paragraph, Root, Text},
+    message, verify_roundtrip, verify_roundtrip_tree, Constructs, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn roundtrip() -> Result<(), message::Message> {
+    let gfm = ParseOptions::gfm();
+
+    assert_eq!(
+        verify_roundtrip(
+            "# a\n\nb *c* __d__ `e`\n\n> f\n\n1. g\n2. h\n\n```i\nj\n```\n\n[k]: l",
+            &ParseOptions::default()
+        )?,
+        None,
+        "should support documents that survive a round trip"
+    );
+
+    assert_eq!(
+        verify_roundtrip("| a |\n| :- |\n| b |\n\n~~c~~", &gfm)?,
+        None,
+        "should support GFM"
+    );
+
+    let divergence = verify_roundtrip("* a\n\n* b", &ParseOptions::default())?.unwrap();
+    assert_eq!(
+        divergence.path,
+        NodePath::from(vec![0]),
+        "should report the path of the first divergence"
+    );
+    assert_eq!(
+        divergence.markdown, "* a\n* b\n\n",
+        "should report the markdown"
+    );
+    assert!(
+        matches!(
+            (&divergence.expected, &divergence.actual),
+            (Some(Node::List(a)), Some(Node::List(b))) if a.spread && !b.spread
+        ),
+        "should report the nodes that differ"
+    );
+
+    let tree = Node::Root(Root {
+        children: vec![Node::Paragraph(Paragraph {
+            children: vec![
+                Node::Text(Text {
+                    value: "a ".into(),
+                    position: None,
+                }),
+                Node::Emphasis(Emphasis {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: None,
+                    })],
+                    position: None,
+                    marker: None,
+                }),
+            ],
+            position: None,
+        })],
+        position: None,
+    });
+    assert_eq!(
+        verify_roundtrip_tree(&tree, &ParseOptions::default())?,
+        None,
+        "should ignore positions and formatting info"
+    );
+
+    let tree = Node::Root(Root {
+        children: vec![Node::Paragraph(Paragraph {
+            children: vec![Node::Text(Text {
+                value: "*a*".into(),
+                position: None,
+            })],
+            position: None,
+        })],
+        position: None,
+    });
+    let divergence = verify_roundtrip_tree(&tree, &ParseOptions::default())?.unwrap();
+    assert_eq!(
+        divergence.path,
+        NodePath::from(vec![0, 0]),
+        "should report text that turns into something else"
+    );
+
+    let tree = Node::Root(Root {
+        children: vec![],
+        position: None,
+    });
+    let divergence = verify_roundtrip_tree(
+        &tree,
+        &ParseOptions {
+            constructs: Constructs {
+                frontmatter: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+    )?;
+    assert_eq!(divergence, None, "should support empty roots");
+
+    let divergence = verify_roundtrip("a ![b](c)", &ParseOptions::default())?.unwrap();
+    assert!(
+        matches!(
+            (&divergence.path, &divergence.expected, &divergence.actual),
+            (path, Some(Node::Image(_)), None) if *path == NodePath::from(vec![0, 1])
+        ),
+        "should report nodes that cannot be turned into markdown instead of crashing"
+    );
+    assert_eq!(
+        divergence.reason,
+        "Unexpected unsupported node, expected a node that can be turned into markdown",
+        "should report why nodes are unsupported"
+    );
+
+    Ok(())
+}
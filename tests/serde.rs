@@ -0,0 +1,182 @@
+#![cfg(feature = "serde")]
+
+use markdown::{mdast::Node, message, to_mdast, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn serde() -> Result<(), message::Message> {
+    let tree = to_mdast("> a *b*", &ParseOptions::default())?;
+
+    assert_eq!(
+        serde_json::to_value(&tree).unwrap(),
+        serde_json::json!({
+            "type": "root",
+            "children": [{
+                "type": "blockquote",
+                "children": [{
+                    "type": "paragraph",
+                    "children": [
+                        {
+                            "type": "text",
+                            "value": "a ",
+                            "position": {
+                                "start": {"line": 1, "column": 3, "offset": 2},
+                                "end": {"line": 1, "column": 5, "offset": 4}
+                            }
+                        },
+                        {
+                            "type": "emphasis",
+                            "children": [{
+                                "type": "text",
+                                "value": "b",
+                                "position": {
+                                    "start": {"line": 1, "column": 6, "offset": 5},
+                                    "end": {"line": 1, "column": 7, "offset": 6}
+                                }
+                            }],
+                            "position": {
+                                "start": {"line": 1, "column": 5, "offset": 4},
+                                "end": {"line": 1, "column": 8, "offset": 7}
+                            },
+                            "marker": "*"
+                        }
+                    ],
+                    "position": {
+                        "start": {"line": 1, "column": 3, "offset": 2},
+                        "end": {"line": 1, "column": 8, "offset": 7}
+                    }
+                }],
+                "position": {
+                    "start": {"line": 1, "column": 1, "offset": 0},
+                    "end": {"line": 1, "column": 8, "offset": 7}
+                }
+            }],
+            "position": {
+                "start": {"line": 1, "column": 1, "offset": 0},
+                "end": {"line": 1, "column": 8, "offset": 7}
+            }
+        }),
+        "should serialize with the names of fields in unist and mdast"
+    );
+
+    let tree = to_mdast(
+        "[a][b] ![c][]\n\n| d | e |\n| - | :-: |\n\n[b]: f",
+        &ParseOptions::gfm(),
+    )?;
+    let json = serde_json::to_value(&tree).unwrap();
+
+    assert_eq!(
+        json["children"][0]["children"][0]["referenceType"], "full",
+        "should use camel case for fields (`referenceType`)"
+    );
+    assert_eq!(
+        json["children"][1]["align"],
+        serde_json::json!([null, "center"]),
+        "should serialize no alignment as `null`"
+    );
+    assert_eq!(
+        serde_json::from_value::<Node>(json).unwrap(),
+        tree,
+        "should deserialize"
+    );
+
+    let tree = to_mdast("<a b c=\"d\" e={f} {...g} />", &ParseOptions::mdx())?;
+    let json = serde_json::to_value(&tree).unwrap();
+
+    assert_eq!(
+        json["children"][0]["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|attribute| (attribute["type"].clone(), attribute["value"].clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                serde_json::json!("mdxJsxAttribute"),
+                serde_json::json!(null)
+            ),
+            (serde_json::json!("mdxJsxAttribute"), serde_json::json!("d")),
+            (
+                serde_json::json!("mdxJsxAttribute"),
+                serde_json::json!({
+                    "type": "mdxJsxAttributeValueExpression",
+                    "value": "f",
+                    "stops": [[0, 14]]
+                })
+            ),
+            (
+                serde_json::json!("mdxJsxExpressionAttribute"),
+                serde_json::json!("...g")
+            ),
+        ],
+        "should serialize MDX JSX attributes like mdast"
+    );
+    assert_eq!(
+        serde_json::from_value::<Node>(json).unwrap(),
+        tree,
+        "should deserialize MDX JSX attributes"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn from_json() -> Result<(), message::Message> {
+    use markdown::{
+        mdast::{Image, List},
+        unist::Position,
+    };
+
+    let mut list = List::new(false, vec![Node::list_item(vec![Node::text("c")])]);
+    list.position = Some(Position::new(5, 1, 0, 5, 4, 0));
+
+    assert_eq!(
+        Node::from_json(
+            r#"{
+  "type": "root",
+  "children": [
+    {"type": "heading", "depth": 1, "children": [{"type": "text", "value": "a"}], "data": {"id": "a"}},
+    {"type": "paragraph", "children": [{"type": "image", "url": "b", "alt": null, "title": null}]},
+    {
+      "type": "list",
+      "ordered": false,
+      "start": null,
+      "children": [{"type": "listItem", "checked": null, "children": [{"type": "text", "value": "c"}]}],
+      "position": {"start": {"line": 5, "column": 1}, "end": {"line": 5, "column": 4}}
+    },
+    {"type": "table", "align": null, "children": []}
+  ]
+}"#
+        )?,
+        Node::root(vec![
+            Node::heading(1, vec![Node::text("a")]),
+            Node::paragraph(vec![Image::builder("b").into()]),
+            list.into(),
+            Node::Table(Box::new(markdown::mdast::Table {
+                children: vec![],
+                position: None,
+                align: vec![],
+                caption: None
+            })),
+        ]),
+        "should parse trees made in JavaScript, with missing, `null`, and unknown fields"
+    );
+
+    let message =
+        Node::from_json(r#"{"type": "root", "children": [{"type": "directive"}]}"#).unwrap_err();
+    assert!(
+        message.reason.starts_with(
+            "Cannot parse tree from JSON: unknown variant `directive`, expected one of `root`"
+        ),
+        "should error on unknown nodes"
+    );
+
+    assert_eq!(
+        Node::from_json("{").unwrap_err().to_string(),
+        "1:1: Cannot parse tree from JSON: EOF while parsing an object (markdown-rs:invalid-json)",
+        "should error on invalid JSON"
+    );
+
+    Ok(())
+}
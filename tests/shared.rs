@@ -0,0 +1,57 @@
+use markdown::{mdast::Node, message, shared::SharedNode, to_mdast, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn shared() -> Result<(), message::Message> {
+    let tree = to_mdast("# a *b*\n\n- c\n- d", &ParseOptions::default())?;
+    let shared = SharedNode::from(tree.clone());
+
+    assert_eq!(
+        shared.to_node(),
+        tree,
+        "should turn back into the same tree"
+    );
+
+    assert_eq!(
+        Node::from(&shared),
+        tree,
+        "should support `From<&SharedNode>` for `Node`"
+    );
+
+    assert_eq!(shared.children().len(), 2, "should expose children");
+
+    assert_eq!(
+        shared.children()[0].node().children().map(Vec::len),
+        Some(0),
+        "should not store children in nodes"
+    );
+
+    let copy = shared.clone();
+    assert!(
+        SharedNode::ptr_eq(&shared, &copy),
+        "should share nodes when cloned"
+    );
+
+    let changed = shared.with_children(vec![shared.children()[1].clone()]);
+    assert_eq!(changed.to_node().to_string(), "cd", "should swap children");
+    assert!(
+        SharedNode::ptr_eq(&changed.children()[0], &shared.children()[1]),
+        "should share unchanged children"
+    );
+
+    let text = shared.children()[0].children()[0].clone();
+    assert_eq!(
+        text.with_children(vec![shared.clone()]).children().len(),
+        0,
+        "should ignore children for nodes that cannot have them"
+    );
+
+    let handle = std::thread::spawn(move || copy.to_node().to_string());
+    assert_eq!(
+        handle.join().unwrap(),
+        "a bcd",
+        "should be usable from other threads"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use markdown::{
+    mdast::{Emphasis, InlineCode, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn smart_punctuation() -> Result<(), message::Message> {
+    let smart_punctuation = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                smart_punctuation: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("\"a\" -- b..."),
+        "<p>&quot;a&quot; -- b...</p>",
+        "should not support smart punctuation by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"a\" 'b'", &smart_punctuation)?,
+        "<p>“a” ‘b’</p>",
+        "should support quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("it's 'tis the '90s", &smart_punctuation)?,
+        "<p>it’s ‘tis the ‘90s</p>",
+        "should support apostrophes"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"'a' b\" (\"c\") [\"d\"]", &smart_punctuation)?,
+        "<p>“‘a’ b” (“c”) [“d”]</p>",
+        "should support quotes next to punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"*a*\" *\"b\"*", &smart_punctuation)?,
+        "<p>“<em>a</em>” <em>“b”</em></p>",
+        "should support quotes around and in attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \" b", &smart_punctuation)?,
+        "<p>a “ b</p>",
+        "should support a quote between whitespace as opening"
+    );
+
+    assert_eq!(
+        to_html_with_options("a - b -- c --- d", &smart_punctuation)?,
+        "<p>a - b – c — d</p>",
+        "should support en and em dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ---- b ----- c ------ d", &smart_punctuation)?,
+        "<p>a –– b —– c —— d</p>",
+        "should support sequences of more dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a.. b... c....", &smart_punctuation)?,
+        "<p>a.. b… c….</p>",
+        "should support ellipses"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\\"a\\\" b\\-- c\\...", &smart_punctuation)?,
+        "<p>&quot;a&quot; b-- c...</p>",
+        "should not support escaped smart punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("`\"a\" -- b...`", &smart_punctuation)?,
+        "<p><code>&quot;a&quot; -- b...</code></p>",
+        "should not support smart punctuation in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$\"a\" -- b...$",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        math_text: true,
+                        smart_punctuation: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><code class=\"language-math math-inline\">&quot;a&quot; -- b...</code></p>",
+        "should not support smart punctuation in math (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\"a\"\n--\n```", &smart_punctuation)?,
+        "<pre><code class=\"language-&quot;a&quot;\">--\n</code></pre>",
+        "should not support smart punctuation in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[\"a\"](b \"c -- d\")", &smart_punctuation)?,
+        "<p><a href=\"b\" title=\"c -- d\">“a”</a></p>",
+        "should support smart punctuation in labels, but not in titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("![\"a\" -- b](c)", &smart_punctuation)?,
+        "<p><img src=\"c\" alt=\"“a” – b\" /></p>",
+        "should support smart punctuation in image alt text"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n---\n\n- b -- c", &smart_punctuation)?,
+        "<p>a</p>\n<hr />\n<ul>\n<li>b – c</li>\n</ul>",
+        "should not interfere with thematic breaks or lists"
+    );
+
+    assert_eq!(
+        to_mdast("\"a\" *b* `c`...", &smart_punctuation.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "“a” ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 5, 4))
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                        })],
+                        position: Some(Position::new(1, 5, 4, 1, 8, 7)),
+                        marker: Some('*')
+                    }),
+                    Node::Text(Text {
+                        value: " ".into(),
+                        position: Some(Position::new(1, 8, 7, 1, 9, 8))
+                    }),
+                    Node::InlineCode(InlineCode {
+                        value: "c".into(),
+                        position: Some(Position::new(1, 9, 8, 1, 12, 11)),
+                        attributes: None
+                    }),
+                    Node::Text(Text {
+                        value: "…".into(),
+                        position: Some(Position::new(1, 12, 11, 1, 15, 14))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 15, 14))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 15, 14))
+        }),
+        "should support smart punctuation as text in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("\"a\" -- b...", &smart_punctuation.parse)?),
+        "“a” – b…\n",
+        "should serialize smart punctuation as typographic characters"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use markdown::{
+    mdast::{Node, Paragraph, Text, ThematicBreak},
+    message, to_markdown_preserving, to_mdast, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn to_markdown_preserving_formatting() -> Result<(), message::Message> {
+    let options = ParseOptions::gfm();
+    let source = "\n_a_  b\n\n\n| c |\n|-|\n\n   - d\n   - e\n\nSetext\n===\n";
+    let tree = to_mdast(source, &options)?;
+
+    assert_eq!(
+        to_markdown_preserving(&tree, source, &options)?,
+        source,
+        "should copy unchanged documents"
+    );
+
+    let mut changed = tree.clone();
+    if let Node::Paragraph(paragraph) = &mut changed.children_mut().unwrap()[0] {
+        paragraph.children.pop();
+    }
+    assert_eq!(
+        to_markdown_preserving(&changed, source, &options)?,
+        "_a_\n\n| c |\n|-|\n\n   - d\n   - e\n\nSetext\n===\n",
+        "should serialize changed blocks"
+    );
+
+    let mut changed = tree.clone();
+    changed.children_mut().unwrap().remove(1);
+    assert_eq!(
+        to_markdown_preserving(&changed, source, &options)?,
+        "\n_a_  b\n\n   - d\n   - e\n\nSetext\n===\n",
+        "should support removed blocks"
+    );
+
+    let mut changed = tree.clone();
+    changed.children_mut().unwrap().insert(
+        2,
+        Node::Paragraph(Paragraph {
+            children: vec![Node::Text(Text {
+                value: "f".into(),
+                position: None,
+            })],
+            position: None,
+        }),
+    );
+    changed
+        .children_mut()
+        .unwrap()
+        .push(Node::ThematicBreak(ThematicBreak { position: None }));
+    assert_eq!(
+        to_markdown_preserving(&changed, source, &options)?,
+        "\n_a_  b\n\n\n| c |\n|-|\n\nf\n\n   - d\n   - e\n\nSetext\n===\n\n***\n\n",
+        "should support new blocks"
+    );
+
+    let mut changed = tree;
+    changed.children_mut().unwrap().swap(0, 3);
+    assert_eq!(
+        to_markdown_preserving(&changed, source, &options)?,
+        "Setext\n===\n\n| c |\n|-|\n\n   - d\n   - e\n\n_a_  b\n",
+        "should support moved blocks"
+    );
+
+    let source = "> a\n> b\n\nc\n";
+    let mut changed = to_mdast(source, &options)?;
+    let paragraph = changed.children_mut().unwrap()[0]
+        .children_mut()
+        .unwrap()
+        .remove(0);
+    changed.children_mut().unwrap()[0] = paragraph;
+    assert_eq!(
+        to_markdown_preserving(&changed, source, &options)?,
+        "a\nb\n\nc\n",
+        "should not copy blocks moved out of their containers"
+    );
+
+    Ok(())
+}
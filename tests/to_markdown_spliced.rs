@@ -0,0 +1,105 @@
+use markdown::{
+    mdast::{Node, Paragraph, Text},
+    message, to_markdown_spliced, to_mdast, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn to_markdown_spliced_changes() -> Result<(), message::Message> {
+    let options = ParseOptions::gfm();
+    let source =
+        "\n# Title  #\n\n> _a_  b\n> c\n\n| d | e |\n|-|-|\n| f | g |\n\n   - h\n   - i\n";
+    let tree = to_mdast(source, &options)?;
+
+    assert_eq!(
+        to_markdown_spliced(&tree, &tree, source),
+        source,
+        "should copy unchanged documents"
+    );
+
+    let mut changed = tree.clone();
+    if let Node::Heading(heading) = &mut changed.children_mut().unwrap()[0] {
+        heading.children[0] = Node::Text(Text {
+            value: "New".into(),
+            position: None,
+        });
+    }
+    assert_eq!(
+        to_markdown_spliced(&changed, &tree, source),
+        "\n# New  #\n\n> _a_  b\n> c\n\n| d | e |\n|-|-|\n| f | g |\n\n   - h\n   - i\n",
+        "should keep the markup of parents of changed nodes"
+    );
+
+    let mut changed = tree.clone();
+    if let Node::BlockQuote(quote) = &mut changed.children_mut().unwrap()[1] {
+        if let Node::Paragraph(paragraph) = &mut quote.children[0] {
+            if let Node::Emphasis(emphasis) = &mut paragraph.children[0] {
+                emphasis.children.push(Node::Text(Text {
+                    value: "z".into(),
+                    position: None,
+                }));
+            }
+        }
+    }
+    assert_eq!(
+        to_markdown_spliced(&changed, &tree, source),
+        "\n# Title  #\n\n> _az_  b\n> c\n\n| d | e |\n|-|-|\n| f | g |\n\n   - h\n   - i\n",
+        "should regenerate nodes with more or fewer children"
+    );
+
+    let mut changed = tree.clone();
+    if let Node::Table(table) = &mut changed.children_mut().unwrap()[2] {
+        if let Node::TableRow(row) = &mut table.children[1] {
+            if let Node::TableCell(cell) = &mut row.children[1] {
+                cell.children[0] = Node::Text(Text {
+                    value: "x|y".into(),
+                    position: None,
+                });
+            }
+        }
+    }
+    assert_eq!(
+        to_markdown_spliced(&changed, &tree, source),
+        "\n# Title  #\n\n> _a_  b\n> c\n\n| d | e |\n|-|-|\n| f | x\\|y |\n\n   - h\n   - i\n",
+        "should splice into table cells"
+    );
+
+    let mut changed = tree.clone();
+    if let Node::List(list) = &mut changed.children_mut().unwrap()[3] {
+        if let Node::ListItem(item) = &mut list.children[1] {
+            item.children[0] = Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "j\nk".into(),
+                    position: None,
+                })],
+                position: None,
+            });
+        }
+    }
+    assert_eq!(
+        to_markdown_spliced(&changed, &tree, source),
+        "\n# Title  #\n\n> _a_  b\n> c\n\n| d | e |\n|-|-|\n| f | g |\n\n- h\n- j\n  k\n\n",
+        "should regenerate parents of changed nodes that span several lines"
+    );
+
+    let mut changed = tree.clone();
+    changed.children_mut().unwrap().remove(1);
+    assert_eq!(
+        to_markdown_spliced(&changed, &tree, source),
+        "\n# Title  #\n\n| d | e |\n|-|-|\n| f | g |\n\n   - h\n   - i\n",
+        "should support removed blocks"
+    );
+
+    let source = "> # a\n> b\n\n> c\n";
+    let tree = to_mdast(source, &options)?;
+    let mut changed = tree.clone();
+    let quote = changed.children_mut().unwrap().remove(0);
+    changed.children_mut().unwrap()[0].children_mut().unwrap()[0] = quote;
+    assert_eq!(
+        to_markdown_spliced(&changed, &tree, source),
+        "> > # a\n> >\n> > b\n",
+        "should not splice into nodes moved into other containers"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use markdown::{
+    mdast::{Node, NodeKind},
+    message, to_mdast,
+    transform::{compact, unwrap},
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_compact() -> Result<(), message::Message> {
+    let mut tree = Node::paragraph(vec![
+        Node::text("a"),
+        Node::text("b"),
+        Node::emphasis(vec![Node::text("c"), Node::text("d")]),
+        Node::text("e"),
+    ]);
+    compact(&mut tree);
+    assert_eq!(
+        tree,
+        Node::paragraph(vec![
+            Node::text("ab"),
+            Node::emphasis(vec![Node::text("cd")]),
+            Node::text("e"),
+        ]),
+        "should merge adjacent text, also in descendants"
+    );
+
+    let mut tree = to_mdast("a *b* c", &ParseOptions::default())?;
+    unwrap(&mut tree, &[NodeKind::Emphasis]);
+    compact(&mut tree);
+    let paragraph = &tree.children().unwrap()[0];
+    assert_eq!(
+        paragraph.children().unwrap()[0].position(),
+        Some(&Position::new(1, 1, 0, 1, 8, 7)),
+        "should merge positions"
+    );
+    assert_eq!(
+        paragraph.children().unwrap().len(),
+        1,
+        "should merge text left by unwrapping"
+    );
+
+    let mut tree = to_mdast("a", &ParseOptions::default())?;
+    tree.children_mut().unwrap()[0]
+        .children_mut()
+        .unwrap()
+        .push(Node::text("b"));
+    compact(&mut tree);
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap()[0].position(),
+        None,
+        "should drop positions if one node has none"
+    );
+
+    let mut tree = Node::paragraph(vec![
+        Node::text("a"),
+        Node::inline_code("b"),
+        Node::text("c"),
+    ]);
+    compact(&mut tree);
+    assert_eq!(
+        tree.children().unwrap().len(),
+        3,
+        "should not merge text with other nodes"
+    );
+
+    Ok(())
+}
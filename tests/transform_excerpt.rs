@@ -0,0 +1,98 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_mdast,
+    transform::{excerpt, Excerpt},
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_excerpt() -> Result<(), message::Message> {
+    assert_eq!(
+        excerpt(
+            &to_mdast("a\n\n<!-- more -->\n\nb", &ParseOptions::default())?,
+            "more"
+        ),
+        Some(Excerpt {
+            excerpt: Node::Root(Root {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                })],
+                position: Some(Position::new(1, 1, 0, 3, 1, 3))
+            }),
+            body: Node::Root(Root {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(5, 1, 18, 5, 2, 19))
+                    })],
+                    position: Some(Position::new(5, 1, 18, 5, 2, 19))
+                })],
+                position: Some(Position::new(3, 14, 16, 5, 2, 19))
+            })
+        }),
+        "should split at a marker"
+    );
+
+    assert_eq!(
+        excerpt(
+            &to_mdast(
+                "a\n\n<!-- more -->\n\nb",
+                &ParseOptions {
+                    html_comment_nodes: true,
+                    ..ParseOptions::default()
+                }
+            )?,
+            "more"
+        ),
+        excerpt(
+            &to_mdast("a\n\n<!-- more -->\n\nb", &ParseOptions::default())?,
+            "more"
+        ),
+        "should split at a marker as a comment node"
+    );
+
+    assert_eq!(
+        excerpt(
+            &to_mdast("a\n<!--more-->\nb", &ParseOptions::default())?,
+            "more"
+        )
+        .map(|result| (result.excerpt.to_string(), result.body.to_string())),
+        Some(("a".into(), "b".into())),
+        "should support a marker w/o whitespace, directly after a paragraph"
+    );
+
+    assert!(
+        excerpt(
+            &to_mdast("a\n\n<!-- fold -->\n\nb", &ParseOptions::default())?,
+            "fold"
+        )
+        .is_some(),
+        "should support a custom marker"
+    );
+
+    assert_eq!(
+        excerpt(
+            &to_mdast("a\n\n<!-- less -->\n\nb", &ParseOptions::default())?,
+            "more"
+        ),
+        None,
+        "should not split at other comments"
+    );
+
+    assert_eq!(
+        excerpt(
+            &to_mdast("a <!-- more --> b", &ParseOptions::default())?,
+            "more"
+        ),
+        None,
+        "should not split at phrasing comments"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use markdown::{
+    mdast::{Node, NodeKind},
+    message, to_mdast,
+    transform::{flat_map, map},
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_map() -> Result<(), message::Message> {
+    let mut tree = to_mdast("a *b*\n\nc", &ParseOptions::default())?;
+    let mut kinds = vec![];
+    map(&mut tree, |node| {
+        kinds.push(node.kind());
+        node
+    });
+    assert_eq!(
+        kinds,
+        vec![
+            NodeKind::Text,
+            NodeKind::Text,
+            NodeKind::Emphasis,
+            NodeKind::Paragraph,
+            NodeKind::Text,
+            NodeKind::Paragraph,
+            NodeKind::Root
+        ],
+        "should pass nodes after their children, and the tree itself"
+    );
+
+    let mut tree = to_mdast("a *b*", &ParseOptions::default())?;
+    map(&mut tree, |node| match node {
+        Node::Text(_) => Node::text("x"),
+        node => node,
+    });
+    assert_eq!(tree.to_string(), "xx", "should replace nodes");
+
+    let mut tree = to_mdast("a *b*", &ParseOptions::default())?;
+    map(&mut tree, |node| match node {
+        Node::Emphasis(_) => Node::emphasis(vec![Node::emphasis(vec![Node::text("c")])]),
+        node => node,
+    });
+    assert_eq!(
+        tree.find_all(|node| node.kind() == NodeKind::Emphasis)
+            .len(),
+        2,
+        "should not walk returned nodes"
+    );
+
+    let mut tree = to_mdast("a", &ParseOptions::default())?;
+    map(&mut tree, |node| match node {
+        Node::Root(_) => Node::root(vec![]),
+        node => node,
+    });
+    assert_eq!(tree, Node::root(vec![]), "should replace the tree itself");
+
+    Ok(())
+}
+
+#[test]
+fn transform_flat_map() -> Result<(), message::Message> {
+    let mut tree = to_mdast("a *b* `c`\n\nd", &ParseOptions::default())?;
+    flat_map(&mut tree, |node| match node {
+        Node::InlineCode(_) => vec![],
+        node => vec![node],
+    });
+    assert_eq!(tree.to_string(), "a b d", "should remove nodes");
+
+    let mut tree = to_mdast("a *b **c***", &ParseOptions::default())?;
+    flat_map(&mut tree, |node| match node {
+        Node::Emphasis(_) | Node::Strong(_) => node.children().cloned().unwrap_or_default(),
+        node => vec![node],
+    });
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap().len(),
+        3,
+        "should unwrap nodes, with their children mapped"
+    );
+
+    let mut tree = to_mdast("a", &ParseOptions::default())?;
+    flat_map(&mut tree, |node| match node {
+        Node::Text(_) => vec![Node::text("b"), Node::text("c")],
+        node => vec![node],
+    });
+    assert_eq!(
+        tree.to_string(),
+        "bc",
+        "should replace nodes with many nodes"
+    );
+
+    let mut tree = to_mdast("a", &ParseOptions::default())?;
+    flat_map(&mut tree, |_| vec![]);
+    assert_eq!(
+        (tree.kind(), tree.children().map(Vec::len)),
+        (NodeKind::Root, Some(0)),
+        "should not pass the tree itself"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use markdown::{
+    message, to_mdast,
+    transform::{number_headings, unnumber_headings, NumberHeadingsOptions},
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+fn headings(value: &str, options: &NumberHeadingsOptions) -> Result<Vec<String>, message::Message> {
+    let mut tree = to_mdast(value, &ParseOptions::default())?;
+    number_headings(&mut tree, options);
+    Ok(tree
+        .children()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect())
+}
+
+#[test]
+fn transform_number_headings() -> Result<(), message::Message> {
+    assert_eq!(
+        headings(
+            "# a\n## b\n### c\n### d\n## e\n# f\n## g",
+            &NumberHeadingsOptions::default()
+        )?,
+        vec!["1 a", "1.1 b", "1.1.1 c", "1.1.2 d", "1.2 e", "2 f", "2.1 g"],
+        "should number headings"
+    );
+
+    assert_eq!(
+        headings(
+            "# a\n## b\n### c\n## d",
+            &NumberHeadingsOptions {
+                min_depth: 2,
+                max_depth: 2,
+                separator: "-".into()
+            }
+        )?,
+        vec!["a", "1 b", "c", "2 d"],
+        "should support a depth range"
+    );
+
+    assert_eq!(
+        headings(
+            "## a\n### b\n## c\n### d",
+            &NumberHeadingsOptions {
+                min_depth: 2,
+                separator: "-".into(),
+                ..NumberHeadingsOptions::default()
+            }
+        )?,
+        vec!["1 a", "1-1 b", "2 c", "2-1 d"],
+        "should support a separator"
+    );
+
+    assert_eq!(
+        headings("# a\n### b", &NumberHeadingsOptions::default())?,
+        vec!["1 a", "1.0.1 b"],
+        "should use `0` for skipped ranks"
+    );
+
+    assert_eq!(
+        headings("# 3.1 a\n# 7 b\n# 2024", &NumberHeadingsOptions::default())?,
+        vec!["1 a", "2 b", "3 2024"],
+        "should replace existing numbers"
+    );
+
+    assert_eq!(
+        headings("# *a*\n# `b`", &NumberHeadingsOptions::default())?,
+        vec!["1 a", "2 b"],
+        "should support headings that do not start with text"
+    );
+
+    assert_eq!(
+        headings("> # a\n\n# b", &NumberHeadingsOptions::default())?,
+        vec!["a", "1 b"],
+        "should only number headings in the given node"
+    );
+
+    let mut tree = to_mdast("# a\n## *b*\n# c", &ParseOptions::default())?;
+    let before = tree.clone();
+    number_headings(&mut tree, &NumberHeadingsOptions::default());
+    let once = tree.clone();
+    number_headings(&mut tree, &NumberHeadingsOptions::default());
+    assert_eq!(tree, once, "should be idempotent");
+
+    unnumber_headings(&mut tree, &NumberHeadingsOptions::default());
+    assert_eq!(tree, before, "should remove numbers");
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use markdown::{mdast::Node, message, to_mdast, transform::redact, ParseOptions};
+use pretty_assertions::assert_eq;
+
+fn emails(value: &str) -> Vec<(usize, usize)> {
+    let mut result = vec![];
+
+    for (index, _) in value.match_indices('@') {
+        let start = value[..index]
+            .rfind(|char: char| char.is_whitespace())
+            .map_or(0, |start| start + 1);
+        let end = value[index..]
+            .find(|char: char| char.is_whitespace())
+            .map_or(value.len(), |end| index + end);
+        result.push((start, end));
+    }
+
+    result
+}
+
+#[test]
+fn transform_redact() -> Result<(), message::Message> {
+    let mut tree = to_mdast(
+        "Contact a@b.c or `d@e.f`.\n\n```\ng@h.i\n```",
+        &ParseOptions::default(),
+    )?;
+    let before = tree.clone();
+    redact(&mut tree, '*', &emails);
+
+    assert_eq!(
+        tree.to_string(),
+        "Contact ***** or *****.*****",
+        "should mask text, inline code, and code"
+    );
+
+    assert_eq!(
+        tree.children().unwrap().len(),
+        before.children().unwrap().len(),
+        "should keep the structure"
+    );
+
+    assert_eq!(tree.position(), before.position(), "should keep positions");
+
+    let mut tree = to_mdast("[a@b.c](d@e.f)", &ParseOptions::default())?;
+    redact(&mut tree, '*', &emails);
+    assert_eq!(tree.to_string(), "*****", "should mask link text");
+    assert!(
+        matches!(
+            &tree.children().unwrap()[0].children().unwrap()[0],
+            Node::Link(link) if link.url == "d@e.f"
+        ),
+        "should not mask urls"
+    );
+
+    let mut tree = to_mdast("abcdef", &ParseOptions::default())?;
+    redact(&mut tree, 'x', &|_| vec![(1, 3), (2, 4), (5, 100), (4, 4)]);
+    assert_eq!(
+        tree.to_string(),
+        "axxxef",
+        "should merge overlapping ranges and ignore invalid ones"
+    );
+
+    let mut tree = to_mdast("`a\nb`", &ParseOptions::default())?;
+    redact(&mut tree, 'x', &|value| vec![(0, value.len())]);
+    assert_eq!(
+        tree.to_string(),
+        "x\nx",
+        "should mask whole values, keeping line endings"
+    );
+
+    let mut tree = to_mdast("én", &ParseOptions::default())?;
+    redact(&mut tree, 'x', &|_| vec![(0, 1), (0, 2)]);
+    assert_eq!(tree.to_string(), "xn", "should mask characters, not bytes");
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use markdown::{
+    mdast::Node,
+    message, to_mdast,
+    transform::{safe_links, SafeLinksAction, SafeLinksOptions},
+    Constructs, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the URLs of links, images, and definitions, and the targets of wiki
+/// links, in `tree`.
+fn urls(tree: &Node) -> Vec<String> {
+    let mut urls = vec![];
+    tree.each_with_path(&mut |node, _| match node {
+        Node::Link(x) => urls.push(x.url.clone()),
+        Node::Image(x) => urls.push(x.url.clone()),
+        Node::Definition(x) => urls.push(x.url.clone()),
+        Node::WikiLink(x) => urls.push(x.target.clone()),
+        _ => {}
+    });
+    urls
+}
+
+#[test]
+fn transform_safe_links() -> Result<(), message::Message> {
+    let unwrap = SafeLinksOptions {
+        action: SafeLinksAction::Unwrap,
+        ..SafeLinksOptions::default()
+    };
+
+    let mut tree = to_mdast(
+        "[a](javascript:alert(1)) [b](VBScript:c) [d](https://e.f) [g](/h:i) [j](k)",
+        &ParseOptions::default(),
+    )?;
+    safe_links(&mut tree, &SafeLinksOptions::default());
+    assert_eq!(
+        urls(&tree),
+        vec!["#", "#", "https://e.f", "/h:i", "k"],
+        "should rewrite dangerous protocols to `#`, case-insensitively"
+    );
+
+    let mut tree = to_mdast(
+        "[a](<\tjava\tscript:b>) [c](&#x6A;avascript:d) [e](javascript%3Af)",
+        &ParseOptions::default(),
+    )?;
+    safe_links(&mut tree, &SafeLinksOptions::default());
+    assert_eq!(
+        urls(&tree),
+        vec!["#", "#", "javascript%3Af"],
+        "should ignore whitespace, and see decoded character references"
+    );
+
+    let mut tree = to_mdast(
+        "[a](data:image/png;base64,b) ![c](data:image/png;base64,d) ![e](data:image/svg+xml,f) ![g](DATA:Image/GIF,h) ![i](data:text/html,j)\n\n[k]: data:image/webp,l",
+        &ParseOptions::default(),
+    )?;
+    safe_links(&mut tree, &SafeLinksOptions::default());
+    assert_eq!(
+        urls(&tree),
+        vec![
+            "#",
+            "data:image/png;base64,d",
+            "#",
+            "DATA:Image/GIF,h",
+            "#",
+            "#"
+        ],
+        "should allow `data:` URLs of images w/ certain media types only"
+    );
+
+    let mut tree = to_mdast(
+        "[a][b] ![c][b]\n\n[b]: data:image/png;base64,d",
+        &ParseOptions::default(),
+    )?;
+    safe_links(&mut tree, &SafeLinksOptions::default());
+    assert_eq!(
+        urls(&tree),
+        vec!["#"],
+        "should treat definitions as links, as links can use them"
+    );
+
+    let wiki = ParseOptions {
+        constructs: Constructs {
+            wiki_link: true,
+            ..Constructs::default()
+        },
+        ..ParseOptions::default()
+    };
+
+    let mut tree = to_mdast(
+        "[[javascript:alert(1)#b]] [[data:text/html,c]] [[d]] [[#e]]",
+        &wiki,
+    )?;
+    safe_links(&mut tree, &SafeLinksOptions::default());
+    assert_eq!(
+        urls(&tree),
+        vec!["", "", "d", ""],
+        "should rewrite dangerous wiki link targets to the current note"
+    );
+
+    let mut tree = to_mdast("a [[javascript:b#c]] [[javascript:d|e]] [[f]]", &wiki)?;
+    safe_links(&mut tree, &unwrap);
+    assert_eq!(
+        tree.to_string(),
+        "a javascript:b#c e f",
+        "should replace dangerous wiki links by their text w/ `Unwrap`"
+    );
+    assert_eq!(
+        urls(&tree),
+        vec!["f"],
+        "should keep safe wiki links w/ `Unwrap`"
+    );
+
+    let mut tree = to_mdast(
+        "a [*b*](javascript:c) ![d](javascript:e) ![](javascript:f) [g](h)\n\n[i]: javascript:j",
+        &ParseOptions::default(),
+    )?;
+    safe_links(&mut tree, &unwrap);
+    assert_eq!(urls(&tree), vec!["h"], "should remove nodes w/ `Unwrap`");
+    assert_eq!(
+        tree.to_string(),
+        "a b d  g",
+        "should keep the text of links and the alt of images w/ `Unwrap`"
+    );
+    assert_eq!(
+        tree.children().unwrap().len(),
+        1,
+        "should drop definitions w/ `Unwrap`"
+    );
+
+    let mut tree = to_mdast("> - [a](javascript:b)", &ParseOptions::default())?;
+    safe_links(&mut tree, &SafeLinksOptions::default());
+    assert_eq!(urls(&tree), vec!["#"], "should work in nested nodes");
+
+    let mut tree = to_mdast("[a](ftp://b) [c](javascript:d)", &ParseOptions::default())?;
+    safe_links(
+        &mut tree,
+        &SafeLinksOptions {
+            protocols: vec!["ftp".into()],
+            ..SafeLinksOptions::default()
+        },
+    );
+    assert_eq!(
+        urls(&tree),
+        vec!["#", "javascript:d"],
+        "should support custom protocols"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use markdown::{
+    mdast::{Node, Root},
+    message, to_mdast,
+    transform::{sections, Section},
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_sections() -> Result<(), message::Message> {
+    let tree = to_mdast("# a\n\nb\n\n# c", &ParseOptions::default())?;
+    let result = sections(&tree, 6);
+    assert_eq!(result.len(), 2, "should split at headings");
+    assert_eq!(
+        result[0].heading.as_ref(),
+        Some(&tree.children().unwrap()[0]),
+        "should include headings"
+    );
+    assert!(
+        result[0]
+            .body
+            .eq_ignoring_positions(&Node::root(vec![Node::paragraph(vec![Node::text("b")])])),
+        "should include the nodes after headings in bodies"
+    );
+    assert_eq!(
+        result[1],
+        Section {
+            heading: Some(tree.children().unwrap()[2].clone()),
+            body: Node::Root(Root {
+                children: vec![],
+                position: Some(Position::new(5, 4, 11, 5, 4, 11)),
+            }),
+        },
+        "should make empty bodies for headings at the end"
+    );
+
+    let tree = to_mdast("a\n\n# b\n\nc", &ParseOptions::default())?;
+    let result = sections(&tree, 6);
+    assert_eq!(
+        result[0].heading, None,
+        "should make a section without heading for leading nodes"
+    );
+    assert_eq!(
+        result[0].body.position(),
+        Some(&Position::new(1, 1, 0, 3, 1, 3)),
+        "should position bodies from the start of the tree to the first heading"
+    );
+    assert_eq!(
+        result[1].body.position(),
+        Some(&Position::new(3, 4, 6, 5, 2, 9)),
+        "should position bodies from their heading to the end of the tree"
+    );
+
+    let tree = to_mdast("# a\n\n## b\n\n### c\n\n## d", &ParseOptions::default())?;
+    assert_eq!(
+        sections(&tree, 2)
+            .iter()
+            .map(|section| (
+                section.heading.as_ref().map(ToString::to_string),
+                section.body.to_string()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some("a".into()), "".into()),
+            (Some("b".into()), "c".into()),
+            (Some("d".into()), "".into()),
+        ],
+        "should keep deeper headings in bodies"
+    );
+
+    let tree = to_mdast("> # a\n\nb", &ParseOptions::default())?;
+    assert_eq!(
+        sections(&tree, 6).len(),
+        1,
+        "should only split at headings that are children of the tree"
+    );
+
+    let tree = to_mdast("", &ParseOptions::default())?;
+    assert_eq!(
+        sections(&tree, 6),
+        vec![],
+        "should not make sections for empty trees"
+    );
+
+    Ok(())
+}
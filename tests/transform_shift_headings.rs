@@ -0,0 +1,58 @@
+use markdown::{
+    mdast::{Node, NodePath},
+    message, to_mdast,
+    transform::shift_headings,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the ranks of all headings in `tree`.
+fn depths(tree: &Node) -> Vec<u8> {
+    tree.descendants()
+        .filter_map(|node| match node {
+            Node::Heading(heading) => Some(heading.depth),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn transform_shift_headings() -> Result<(), message::Message> {
+    let mut tree = to_mdast("# a\n## b\n### c", &ParseOptions::default())?;
+    assert_eq!(
+        shift_headings(&mut tree, 2),
+        vec![],
+        "should not report headings that fit"
+    );
+    assert_eq!(depths(&tree), vec![3, 4, 5], "should shift headings down");
+
+    let mut tree = to_mdast("# a\n## b\n### c", &ParseOptions::default())?;
+    assert_eq!(
+        shift_headings(&mut tree, -1),
+        vec![NodePath::from(vec![0])],
+        "should report headings clamped to `1`"
+    );
+    assert_eq!(depths(&tree), vec![1, 1, 2], "should shift headings up");
+
+    let mut tree = to_mdast("##### a\n\n> ###### b", &ParseOptions::default())?;
+    assert_eq!(
+        shift_headings(&mut tree, 1),
+        vec![NodePath::from(vec![1, 0])],
+        "should report headings clamped to `6`"
+    );
+    assert_eq!(depths(&tree), vec![6, 6], "should shift nested headings");
+
+    let mut tree = to_mdast("# a\n###### b", &ParseOptions::default())?;
+    assert_eq!(
+        shift_headings(&mut tree, i8::MAX).len(),
+        2,
+        "should support large deltas"
+    );
+    assert_eq!(depths(&tree), vec![6, 6], "should clamp large deltas");
+
+    let mut tree = to_mdast("## a", &ParseOptions::default())?;
+    shift_headings(&mut tree, 0);
+    assert_eq!(depths(&tree), vec![2], "should support a delta of `0`");
+
+    Ok(())
+}
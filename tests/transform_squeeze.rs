@@ -0,0 +1,89 @@
+use markdown::{
+    mdast::{Break, Node, NodeKind},
+    message, to_markdown, to_mdast,
+    transform::{squeeze, strip},
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_squeeze() -> Result<(), message::Message> {
+    let mut tree = Node::root(vec![
+        Node::paragraph(vec![]),
+        Node::paragraph(vec![Node::text(" \n")]),
+        Node::paragraph(vec![Node::text("a")]),
+    ]);
+    squeeze(&mut tree);
+    assert_eq!(
+        tree,
+        Node::root(vec![Node::paragraph(vec![Node::text("a")])]),
+        "should remove empty and whitespace-only paragraphs"
+    );
+
+    let mut tree = to_mdast("a *![b](c)* **![d](e)** f", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Image]);
+    squeeze(&mut tree);
+    assert_eq!(
+        to_markdown(tree),
+        "a   f\n",
+        "should remove empty emphasis and strong"
+    );
+
+    let mut tree = to_mdast("> ![a](b)\n\nc", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Image]);
+    squeeze(&mut tree);
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap().len(),
+        0,
+        "should remove empty paragraphs in containers"
+    );
+
+    let mut tree = to_mdast("*![a](b)*", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Image]);
+    squeeze(&mut tree);
+    assert_eq!(
+        tree.children().unwrap().len(),
+        0,
+        "should remove parents that become empty"
+    );
+
+    let mut tree = to_mdast("a\n\n***\n\n![b](c)\n\n---\n\nd", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Image]);
+    squeeze(&mut tree);
+    assert_eq!(
+        to_markdown(tree),
+        "a\n\n***\n\nd\n",
+        "should collapse runs of thematic breaks"
+    );
+
+    let mut tree = Node::root(vec![Node::paragraph(vec![
+        Node::text("a"),
+        Node::Break(Break { position: None }),
+        Node::Break(Break { position: None }),
+        Node::text("b"),
+    ])]);
+    squeeze(&mut tree);
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap().len(),
+        3,
+        "should collapse runs of breaks"
+    );
+
+    let mut tree = Node::paragraph(vec![]);
+    squeeze(&mut tree);
+    assert_eq!(
+        tree,
+        Node::paragraph(vec![]),
+        "should not remove the given node"
+    );
+
+    let mut tree = to_mdast("![a](b)", &ParseOptions::default())?;
+    squeeze(&mut tree);
+    assert_eq!(
+        tree.children().unwrap().len(),
+        1,
+        "should keep paragraphs with content other than text"
+    );
+
+    Ok(())
+}
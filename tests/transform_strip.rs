@@ -0,0 +1,85 @@
+use markdown::{
+    mdast::NodeKind,
+    message, to_mdast,
+    transform::{strip, unwrap},
+    Constructs, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_strip() -> Result<(), message::Message> {
+    let mut tree = to_mdast("a <b>c</b> d\n\n<div>\ne\n</div>", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Html]);
+    assert_eq!(
+        tree.to_string(),
+        "a c d",
+        "should strip flow and phrasing html"
+    );
+
+    let mut tree = to_mdast("a ![b](c) *d ![e]* f\n\n[e]: g", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Image, NodeKind::ImageReference]);
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap().len(),
+        4,
+        "should strip images"
+    );
+    assert_eq!(tree.to_string(), "a  d  f", "should strip nested images");
+
+    let mut tree = to_mdast(
+        "# a {b}\n\n<C>\nd\n</C>\n\n{e}",
+        &ParseOptions {
+            constructs: Constructs::mdx(),
+            ..ParseOptions::default()
+        },
+    )?;
+    strip(
+        &mut tree,
+        &[
+            NodeKind::MdxjsEsm,
+            NodeKind::MdxFlowExpression,
+            NodeKind::MdxTextExpression,
+            NodeKind::MdxJsxFlowElement,
+            NodeKind::MdxJsxTextElement,
+        ],
+    );
+    assert_eq!(
+        tree.children().unwrap().len(),
+        1,
+        "should strip mdx nodes (flow)"
+    );
+    assert_eq!(tree.to_string(), "a ", "should strip mdx nodes (text)");
+
+    let mut tree = to_mdast("a", &ParseOptions::default())?;
+    strip(&mut tree, &[NodeKind::Root]);
+    assert_eq!(tree.to_string(), "a", "should not strip the given node");
+
+    let mut tree = to_mdast("a [*b*](c) d", &ParseOptions::default())?;
+    unwrap(&mut tree, &[NodeKind::Link]);
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap()[1].kind(),
+        NodeKind::Emphasis,
+        "should unwrap nodes, keeping their children"
+    );
+
+    let mut tree = to_mdast("> a\n> > b", &ParseOptions::default())?;
+    unwrap(&mut tree, &[NodeKind::BlockQuote]);
+    assert_eq!(
+        tree.children()
+            .unwrap()
+            .iter()
+            .map(markdown::mdast::Node::kind)
+            .collect::<Vec<_>>(),
+        vec![NodeKind::Paragraph, NodeKind::Paragraph],
+        "should unwrap nested nodes"
+    );
+
+    let mut tree = to_mdast("a ![b](c) d", &ParseOptions::default())?;
+    unwrap(&mut tree, &[NodeKind::Image]);
+    assert_eq!(
+        tree.children().unwrap()[0].children().unwrap().len(),
+        2,
+        "should remove unwrapped nodes without children"
+    );
+
+    Ok(())
+}
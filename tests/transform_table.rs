@@ -0,0 +1,138 @@
+use markdown::{
+    mdast::{AlignKind, Node, Table},
+    message, to_mdast,
+    transform::TableSort,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+fn table(value: &str) -> Result<Table, message::Message> {
+    match to_mdast(value, &ParseOptions::gfm())?
+        .children_mut()
+        .unwrap()
+        .remove(0)
+    {
+        Node::Table(table) => Ok(*table),
+        _ => unreachable!("expected table"),
+    }
+}
+
+fn grid(table: &Table) -> Vec<Vec<String>> {
+    table
+        .children
+        .iter()
+        .map(|row| {
+            row.children()
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn transform_table() -> Result<(), message::Message> {
+    let source = "| a | b |\n| - | - |\n| x | 10 |\n| y | 9 |\n| z | - |";
+
+    let mut node = table(source)?;
+    node.sort_rows(1, TableSort::Text, false);
+    assert_eq!(
+        grid(&node),
+        vec![
+            vec!["a", "b"],
+            vec!["z", "-"],
+            vec!["x", "10"],
+            vec!["y", "9"]
+        ],
+        "should sort rows by text, keeping the header"
+    );
+
+    let mut node = table(source)?;
+    node.sort_rows(1, TableSort::Numeric, false);
+    assert_eq!(
+        grid(&node),
+        vec![
+            vec!["a", "b"],
+            vec!["y", "9"],
+            vec!["x", "10"],
+            vec!["z", "-"]
+        ],
+        "should sort rows numerically, with other values last"
+    );
+
+    let mut node = table(source)?;
+    node.sort_rows(0, TableSort::Text, true);
+    assert_eq!(
+        grid(&node),
+        vec![
+            vec!["a", "b"],
+            vec!["z", "-"],
+            vec!["y", "9"],
+            vec!["x", "10"]
+        ],
+        "should sort rows descending"
+    );
+
+    let mut node = table("| a | b |\n| :- | -: |\n| c |")?;
+    node.insert_column(1, AlignKind::Center);
+    assert_eq!(
+        node.align,
+        vec![AlignKind::Left, AlignKind::Center, AlignKind::Right],
+        "should insert an alignment"
+    );
+    assert_eq!(
+        grid(&node),
+        vec![vec!["a", "", "b"], vec!["c", ""]],
+        "should insert a column"
+    );
+
+    let mut node = table("| a | b |\n| :- | -: |\n| c | d |")?;
+    node.remove_column(0);
+    assert_eq!(
+        node.align,
+        vec![AlignKind::Right],
+        "should remove alignment"
+    );
+    assert_eq!(
+        grid(&node),
+        vec![vec!["b"], vec!["d"]],
+        "should remove a column"
+    );
+
+    let mut node = table("| a | b | c |\n| :- | - | -: |\n| d | e | f |")?;
+    node.move_column(0, 2);
+    assert_eq!(
+        node.align,
+        vec![AlignKind::None, AlignKind::Right, AlignKind::Left],
+        "should move alignment"
+    );
+    assert_eq!(
+        grid(&node),
+        vec![vec!["b", "c", "a"], vec!["e", "f", "d"]],
+        "should move a column"
+    );
+
+    let mut node = table("| a | b |\n| - | - |")?;
+    node.set_align(1, AlignKind::Center);
+    assert_eq!(
+        node.align,
+        vec![AlignKind::None, AlignKind::Center],
+        "should set alignment"
+    );
+
+    let mut node = table("| a | b |\n| :- | - |\n| c | d |\n| e |")?;
+    node.transpose();
+    assert_eq!(
+        grid(&node),
+        vec![vec!["a", "c", "e"], vec!["b", "d", ""]],
+        "should transpose"
+    );
+    assert_eq!(
+        node.align,
+        vec![AlignKind::None, AlignKind::None, AlignKind::None],
+        "should reset alignment when transposing"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use markdown::{
+    message, to_mdast,
+    transform::{toc, TocEntry, TocOptions},
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the slugs of `entries`, nested like them.
+fn slugs(entries: &[TocEntry]) -> Vec<(String, Vec<String>)> {
+    entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.slug.clone(),
+                slugs(&entry.children)
+                    .into_iter()
+                    .map(|(slug, _)| slug)
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn transform_toc() -> Result<(), message::Message> {
+    let tree = to_mdast("# a *b*\n\nc\n\n## d", &ParseOptions::default())?;
+    assert_eq!(
+        toc(&tree, &TocOptions::default()),
+        vec![TocEntry {
+            depth: 1,
+            text: "a b".into(),
+            slug: "a-b".into(),
+            position: Some(Position::new(1, 1, 0, 1, 8, 7)),
+            children: vec![TocEntry {
+                depth: 2,
+                text: "d".into(),
+                slug: "d".into(),
+                position: Some(Position::new(5, 1, 12, 5, 5, 16)),
+                children: vec![],
+            }],
+        }],
+        "should get entries"
+    );
+
+    let tree = to_mdast("## a\n# b\n### c\n## d\n# e", &ParseOptions::default())?;
+    assert_eq!(
+        slugs(&toc(&tree, &TocOptions::default())),
+        vec![
+            ("a".into(), vec![]),
+            ("b".into(), vec!["c".into(), "d".into()]),
+            ("e".into(), vec![])
+        ],
+        "should nest headings in the closest heading with a lower rank"
+    );
+
+    let tree = to_mdast(
+        "# Hello, World!\n# hello world\n# Hello-World\n# Été_2024 \u{1f600}",
+        &ParseOptions::default(),
+    )?;
+    assert_eq!(
+        toc(&tree, &TocOptions::default())
+            .into_iter()
+            .map(|entry| entry.slug)
+            .collect::<Vec<_>>(),
+        vec!["hello-world", "hello-world-1", "hello-world-2", "été_2024-"],
+        "should make unique slugs like GitHub"
+    );
+
+    let tree = to_mdast("# a\n## b\n### c\n#### a\n> # d", &ParseOptions::default())?;
+    assert_eq!(
+        slugs(&toc(
+            &tree,
+            &TocOptions {
+                min_depth: 2,
+                max_depth: 3
+            }
+        )),
+        vec![("b".into(), vec!["c".into()])],
+        "should support `min_depth` and `max_depth`, and ignore nested headings"
+    );
+    assert_eq!(
+        toc(
+            &tree,
+            &TocOptions {
+                min_depth: 4,
+                max_depth: 4
+            }
+        )[0]
+        .slug,
+        "a-1",
+        "should make slugs for excluded headings"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use markdown::{
+    mdast::{Node, NodeKind},
+    message, to_mdast,
+    transform::{truncate, TruncateLimit},
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+fn kinds(node: &Node) -> Vec<NodeKind> {
+    node.children()
+        .unwrap()
+        .iter()
+        .map(Node::kind)
+        .collect::<Vec<_>>()
+}
+
+#[test]
+fn transform_truncate() -> Result<(), message::Message> {
+    let tree = to_mdast("a b c", &ParseOptions::default())?;
+
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Words(5), Some("…")),
+        tree,
+        "should not change trees that fit"
+    );
+
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Words(2), None).to_string(),
+        "a b",
+        "should truncate words"
+    );
+
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Characters(4), None).to_string(),
+        "a b",
+        "should truncate characters, trimming trailing whitespace"
+    );
+
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Characters(3), Some("…")).to_string(),
+        "a b…",
+        "should add an ellipsis"
+    );
+
+    let tree = to_mdast("a *b c* d", &ParseOptions::default())?;
+    let result = truncate(&tree, TruncateLimit::Words(2), Some("…"));
+
+    assert_eq!(
+        result.to_string(),
+        "a b…",
+        "should truncate inside phrasing parents"
+    );
+
+    assert_eq!(
+        kinds(&result.children().unwrap()[0]),
+        vec![NodeKind::Text, NodeKind::Emphasis],
+        "should keep parents that contain the cut closed"
+    );
+
+    let tree = to_mdast("a*b* c", &ParseOptions::default())?;
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Words(1), None).to_string(),
+        "ab",
+        "should count words across nodes"
+    );
+
+    let tree = to_mdast("a `b c` d", &ParseOptions::default())?;
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Words(2), Some("…")).to_string(),
+        "a…",
+        "should not cut inside inline code"
+    );
+
+    let tree = to_mdast("a\n\n```\nb c\n```\n\nd", &ParseOptions::default())?;
+    let result = truncate(&tree, TruncateLimit::Words(2), Some("…"));
+    assert_eq!(
+        kinds(&result),
+        vec![NodeKind::Paragraph, NodeKind::Paragraph],
+        "should drop code that does not fit, adding the ellipsis in a paragraph"
+    );
+
+    let tree = to_mdast("# a\n\nb\n\n* c\n* d", &ParseOptions::default())?;
+    let result = truncate(&tree, TruncateLimit::Words(3), None);
+    assert_eq!(
+        kinds(&result),
+        vec![NodeKind::Heading, NodeKind::Paragraph, NodeKind::List],
+        "should keep blocks that fit"
+    );
+    assert_eq!(
+        result.children().unwrap()[2].children().unwrap().len(),
+        1,
+        "should drop parents that end up empty"
+    );
+
+    let tree = to_mdast("a", &ParseOptions::default())?;
+    assert_eq!(
+        truncate(&tree, TruncateLimit::Characters(0), None)
+            .children()
+            .unwrap()
+            .len(),
+        0,
+        "should keep an empty root"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use markdown::{
+    mdast::{Definition, Node},
+    message, to_mdast,
+    transform::remove_unused_definitions,
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn transform_unused_definitions() -> Result<(), message::Message> {
+    let mut tree = to_mdast("[a]\n\n[a]: b\n[c]: d", &ParseOptions::default())?;
+    let removed = remove_unused_definitions(&mut tree);
+
+    assert_eq!(
+        removed,
+        vec![Node::Definition(Box::new(Definition {
+            url: "d".into(),
+            title: None,
+            identifier: "c".into(),
+            label: Some("c".into()),
+            position: Some(Position::new(4, 1, 12, 4, 7, 18))
+        }))],
+        "should report unused definitions"
+    );
+
+    assert_eq!(
+        tree.children().unwrap().len(),
+        2,
+        "should remove unused definitions"
+    );
+
+    let mut tree = to_mdast("![A]\n\n[a]: b", &ParseOptions::default())?;
+    assert_eq!(
+        remove_unused_definitions(&mut tree).len(),
+        0,
+        "should keep definitions used by image references"
+    );
+
+    let mut tree = to_mdast("> [x]: y\n\n* [z]: w", &ParseOptions::default())?;
+    assert_eq!(
+        remove_unused_definitions(&mut tree).len(),
+        2,
+        "should remove unused definitions in containers"
+    );
+
+    let mut tree = to_mdast("a [^b]\n\n[^b]: c\n\n[^d]: e", &ParseOptions::gfm())?;
+    let removed = remove_unused_definitions(&mut tree);
+
+    assert_eq!(
+        removed.len(),
+        1,
+        "should remove unused footnote definitions"
+    );
+
+    assert!(
+        matches!(&removed[0], Node::FootnoteDefinition(x) if x.identifier == "d"),
+        "should report unused footnote definitions"
+    );
+
+    let mut tree = to_mdast(
+        "a\n\n[^b]: c [^d] [e]\n\n[^d]: f\n\n[e]: g",
+        &ParseOptions::gfm(),
+    )?;
+
+    assert_eq!(
+        remove_unused_definitions(&mut tree).len(),
+        3,
+        "should remove definitions only used in unused footnote definitions"
+    );
+
+    assert_eq!(
+        tree.children().unwrap().len(),
+        1,
+        "should leave only used nodes"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text, WikiLink},
+    message, to_html, to_html_with_options, to_markdown, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn wiki_link() -> Result<(), message::Message> {
+    let wiki = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                wiki_link: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("[[a]]"),
+        "<p>[[a]]</p>",
+        "should not support wiki links by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a [[b]] c", &wiki)?,
+        "<p>a <a href=\"b\">b</a> c</p>",
+        "should support wiki links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a#b]]", &wiki)?,
+        "<p><a href=\"a#b\">a#b</a></p>",
+        "should support wiki links w/ an anchor"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|b]]", &wiki)?,
+        "<p><a href=\"a\">b</a></p>",
+        "should support wiki links w/ a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a#b|c]]", &wiki)?,
+        "<p><a href=\"a#b\">c</a></p>",
+        "should support wiki links w/ an anchor and a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[#a]]", &wiki)?,
+        "<p><a href=\"#a\">#a</a></p>",
+        "should support wiki links w/o target, w/ an anchor"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a#^b#c]]", &wiki)?,
+        "<p><a href=\"a#%5Eb#c\">a#^b#c</a></p>",
+        "should support `#` in anchors"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a|b#c|d]]", &wiki)?,
+        "<p><a href=\"a\">b#c|d</a></p>",
+        "should support `#` and `|` in labels"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[Saturn rings]]", &wiki)?,
+        "<p><a href=\"Saturn%20rings\">Saturn rings</a></p>",
+        "should encode the URL"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a<b>|*c* & \\d]]", &wiki)?,
+        "<p><a href=\"a%3Cb%3E\">*c* &amp; \\d</a></p>",
+        "should not interpret anything in wiki links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[javascript:alert(1)]]", &wiki)?,
+        "<p><a href=\"\">javascript:alert(1)</a></p>",
+        "should not support dangerous protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[]]", &wiki)?,
+        "<p>[[]]</p>",
+        "should not support empty wiki links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a#]] [[a|]]", &wiki)?,
+        "<p>[[a#]] [[a|]]</p>",
+        "should not support empty anchors or labels"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a\nb]]", &wiki)?,
+        "<p>[[a\nb]]</p>",
+        "should not support line endings in wiki links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a]b]]", &wiki)?,
+        "<p>[[a]b]]</p>",
+        "should not support a single `]` in wiki links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[[a]]]", &wiki)?,
+        "<p>[<a href=\"a\">a</a>]</p>",
+        "should not support `[` in wiki links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[[a]](b)", &wiki)?,
+        "<p><a href=\"a\">a</a>(b)</p>",
+        "should prefer wiki links over links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[b [[a]]](c)", &wiki)?,
+        "<p>[b <a href=\"a\">a</a>](c)</p>",
+        "should not support wiki links in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![[[a]]](b)", &wiki)?,
+        "<p><img src=\"b\" alt=\"a\" /></p>",
+        "should support wiki links in image descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[[Saturn#Rings|rings]]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        wiki_link: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    wiki_link_url: Some(Box::new(|target, anchor| {
+                        format!("/{}/#{}", target, anchor.unwrap_or_default())
+                    })),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<p><a href=\"/Saturn/#Rings\">rings</a></p>",
+        "should support `wiki_link_url`"
+    );
+
+    assert_eq!(
+        to_mdast("a [[b#c|d]]", &wiki.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::WikiLink(Box::new(WikiLink {
+                        target: "b".into(),
+                        anchor: Some("c".into()),
+                        label: Some("d".into()),
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11))
+                    }))
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 12, 11))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 12, 11))
+        }),
+        "should support wiki links as `WikiLink`s in mdast"
+    );
+
+    assert_eq!(
+        to_markdown(to_mdast("a [[b#c|d]] [[e]]", &wiki.parse)?),
+        "a [[b#c|d]] [[e]]\n",
+        "should serialize wiki links"
+    );
+
+    Ok(())
+}